@@ -0,0 +1,122 @@
+use core::time::Duration;
+
+use embassy_executor::Spawner;
+use embassy_time::Timer;
+use fraktor_actor_rs::{
+  core::{
+    actor_prim::{Actor, ActorContext, actor_ref::ActorRef},
+    dispatcher::DispatcherConfig,
+    error::ActorError,
+    messaging::{AnyMessage, AnyMessageViewGeneric},
+    props::Props,
+    scheduler::SchedulerCommand,
+    system::{ActorSystem, ActorSystemConfig},
+  },
+  embassy::{dispatcher::EmbassyDispatchExecutor, scheduler::EmbassyTickDriver},
+};
+use fraktor_utils_rs::core::sync::ArcShared;
+
+struct Start;
+
+struct Tick;
+
+struct GuardianActor;
+
+impl Actor for GuardianActor {
+  fn receive(&mut self, ctx: &mut ActorContext<'_>, message: AnyMessageViewGeneric<'_>) -> Result<(), ActorError> {
+    if message.downcast_ref::<Start>().is_some() {
+      let pong =
+        ctx.spawn_child(&Props::from_fn(|| PongActor)).map_err(|_| ActorError::recoverable("failed to spawn pong"))?;
+      let ping =
+        ctx.spawn_child(&Props::from_fn(|| PingActor)).map_err(|_| ActorError::recoverable("failed to spawn ping"))?;
+      let start_ping = StartPing { target: pong.actor_ref().clone(), reply_to: ctx.self_ref(), count: 3 };
+      ping.tell(AnyMessage::new(start_ping)).map_err(|_| ActorError::recoverable("failed to start ping actor"))?;
+
+      // embassy-time の tick で発火するメッセージをスケジュールする
+      let scheduler_context = ctx.system().scheduler_context().expect("scheduler context");
+      let command = SchedulerCommand::SendMessage {
+        receiver:   ctx.self_ref(),
+        message:    AnyMessage::new(Tick),
+        dispatcher: None,
+        sender:     None,
+      };
+      scheduler_context
+        .scheduler()
+        .lock()
+        .schedule_once(Duration::from_millis(50), command)
+        .map_err(|_| ActorError::recoverable("failed to schedule"))?;
+    } else if let Some(reply) = message.downcast_ref::<PongReply>() {
+      println!("pong replied: {}", reply.index);
+    } else if message.downcast_ref::<Tick>().is_some() {
+      println!("scheduled tick received");
+    }
+    Ok(())
+  }
+}
+
+struct StartPing {
+  target:   ActorRef,
+  reply_to: ActorRef,
+  count:    u32,
+}
+
+struct PingMessage {
+  index:    u32,
+  reply_to: ActorRef,
+}
+
+struct PongReply {
+  index: u32,
+}
+
+struct PingActor;
+
+impl Actor for PingActor {
+  fn receive(&mut self, _ctx: &mut ActorContext<'_>, message: AnyMessageViewGeneric<'_>) -> Result<(), ActorError> {
+    if let Some(cmd) = message.downcast_ref::<StartPing>() {
+      for index in 1..=cmd.count {
+        let payload = PingMessage { index, reply_to: cmd.reply_to.clone() };
+        cmd.target.tell(AnyMessage::new(payload)).map_err(|_| ActorError::recoverable("failed to send ping"))?;
+      }
+    }
+    Ok(())
+  }
+}
+
+struct PongActor;
+
+impl Actor for PongActor {
+  fn receive(&mut self, _ctx: &mut ActorContext<'_>, message: AnyMessageViewGeneric<'_>) -> Result<(), ActorError> {
+    if let Some(ping) = message.downcast_ref::<PingMessage>() {
+      println!("received ping: {}", ping.index);
+      ping
+        .reply_to
+        .tell(AnyMessage::new(PongReply { index: ping.index }))
+        .map_err(|_| ActorError::recoverable("reply failed"))?;
+    }
+    Ok(())
+  }
+}
+
+#[embassy_executor::main]
+async fn main(spawner: Spawner) {
+  let spawner = spawner.make_send();
+  let executor = EmbassyDispatchExecutor::new(spawner).expect("dispatcher workers");
+  let adapter = ArcShared::new(executor.schedule_adapter());
+  let dispatcher = DispatcherConfig::from_executor(Box::new(executor)).with_schedule_adapter(adapter);
+  let config = ActorSystemConfig::default()
+    .with_default_dispatcher(dispatcher)
+    .with_tick_driver(EmbassyTickDriver::config(spawner));
+  let system = ActorSystem::new_with_config(&Props::from_fn(|| GuardianActor), &config).expect("system");
+
+  system.user_guardian_ref().tell(AnyMessage::new(Start)).expect("start");
+  // メールボックスとスケジューラは同じ executor 上のタスクで動くため、ブロックせずに待つ
+  Timer::after_millis(200).await;
+
+  let termination = system.when_terminated();
+  system.terminate().expect("terminate");
+  while !termination.is_ready() {
+    Timer::after_millis(1).await;
+  }
+  std::process::exit(0);
+}
@@ -16,4 +16,6 @@ pub mod serialization;
 pub mod spawn;
 pub mod supervision;
 pub mod system;
+#[cfg(any(test, feature = "test-support"))]
+pub mod testkit;
 pub mod typed;
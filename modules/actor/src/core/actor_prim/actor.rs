@@ -68,6 +68,11 @@ pub trait Actor<TB: RuntimeToolbox = NoStdToolbox>: Send {
     Ok(())
   }
 
+  /// Called when a supervised child reports a failure, before the supervisor strategy is applied.
+  ///
+  /// The default implementation does nothing.
+  fn on_child_failed(&mut self, _ctx: &mut ActorContextGeneric<'_, TB>, _child: Pid, _error: &ActorError) {}
+
   /// Provides the supervision strategy for child actors.
   ///
   /// This method allows actors to dynamically determine supervision behavior based on
@@ -174,6 +179,10 @@ where
     (**self).on_terminated(ctx, terminated)
   }
 
+  fn on_child_failed(&mut self, ctx: &mut ActorContextGeneric<'_, TB>, child: Pid, error: &ActorError) {
+    (**self).on_child_failed(ctx, child, error)
+  }
+
   fn supervisor_strategy(&mut self, ctx: &mut ActorContextGeneric<'_, TB>) -> SupervisorStrategy {
     (**self).supervisor_strategy(ctx)
   }
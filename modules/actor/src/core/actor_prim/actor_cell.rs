@@ -10,7 +10,7 @@ use fraktor_utils_rs::core::{
   runtime_toolbox::{NoStdToolbox, RuntimeToolbox, SyncMutexFamily, ToolboxMutex},
   sync::{ArcShared, sync_mutex_like::SyncMutexLike},
 };
-use portable_atomic::{AtomicBool, AtomicU64, Ordering};
+use portable_atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 
 use crate::core::{
   actor_prim::{
@@ -55,6 +55,13 @@ pub struct ActorCellGeneric<TB: RuntimeToolbox + 'static> {
   adapter_handle_counter: AtomicU64,
   pipe_task_counter:      AtomicU64,
   terminated:             AtomicBool,
+  actor_type:             Option<&'static str>,
+  props_name:             Option<String>,
+  dispatcher_id:          Option<String>,
+  mailbox_id:             Option<String>,
+  processed:              AtomicU64,
+  restarts:               AtomicU32,
+  last_failure:           ToolboxMutex<Option<String>, TB>,
 }
 
 unsafe impl<TB: RuntimeToolbox + 'static> Send for ActorCellGeneric<TB> {}
@@ -98,6 +105,10 @@ impl<TB: RuntimeToolbox + 'static> ActorCellGeneric<TB> {
     let watchers = <TB::MutexFamily as SyncMutexFamily>::create(Vec::new());
     let pipe_tasks = <TB::MutexFamily as SyncMutexFamily>::create(Vec::new());
     let adapter_handles = <TB::MutexFamily as SyncMutexFamily>::create(Vec::new());
+    let last_failure = <TB::MutexFamily as SyncMutexFamily>::create(None);
+    let mut middlewares = system.message_middlewares();
+    middlewares.extend(props.resolved_middleware().iter().cloned());
+    let pipeline = MessageInvokerPipelineGeneric::from_middlewares(middlewares);
 
     let cell = ArcShared::new(Self {
       pid,
@@ -106,7 +117,7 @@ impl<TB: RuntimeToolbox + 'static> ActorCellGeneric<TB> {
       system,
       factory,
       actor,
-      pipeline: MessageInvokerPipelineGeneric::new(),
+      pipeline,
       mailbox,
       dispatcher,
       sender,
@@ -118,6 +129,13 @@ impl<TB: RuntimeToolbox + 'static> ActorCellGeneric<TB> {
       adapter_handle_counter: AtomicU64::new(0),
       pipe_task_counter: AtomicU64::new(0),
       terminated: AtomicBool::new(false),
+      actor_type: props.actor_type(),
+      props_name: props.name().map(String::from),
+      dispatcher_id: props.dispatcher_id().map(String::from),
+      mailbox_id: props.mailbox_id().map(String::from),
+      processed: AtomicU64::new(0),
+      restarts: AtomicU32::new(0),
+      last_failure,
     });
 
     {
@@ -154,6 +172,41 @@ impl<TB: RuntimeToolbox + 'static> ActorCellGeneric<TB> {
     self.parent
   }
 
+  /// Returns the Rust type name of the actor, when the props recorded it.
+  pub(crate) const fn actor_type(&self) -> Option<&'static str> {
+    self.actor_type
+  }
+
+  /// Returns the name requested through the props, if any.
+  pub(crate) fn props_name(&self) -> Option<&str> {
+    self.props_name.as_deref()
+  }
+
+  /// Returns the identifier of the dispatcher the actor was spawned on, if it was registered.
+  pub(crate) fn dispatcher_id(&self) -> Option<&str> {
+    self.dispatcher_id.as_deref()
+  }
+
+  /// Returns the identifier of the mailbox configuration, if it was registered.
+  pub(crate) fn mailbox_id(&self) -> Option<&str> {
+    self.mailbox_id.as_deref()
+  }
+
+  /// Returns how many user messages the actor has been handed.
+  pub(crate) fn processed_count(&self) -> u64 {
+    self.processed.load(Ordering::Acquire)
+  }
+
+  /// Returns how many times the actor has been restarted.
+  pub(crate) fn restart_count(&self) -> u32 {
+    self.restarts.load(Ordering::Acquire)
+  }
+
+  /// Returns the reason of the most recent failure reported by the actor.
+  pub(crate) fn last_failure(&self) -> Option<String> {
+    self.last_failure.lock().clone()
+  }
+
   /// Returns a handle to the mailbox managed by this cell.
   #[must_use]
   pub fn mailbox(&self) -> ArcShared<MailboxGeneric<TB>> {
@@ -358,6 +411,7 @@ impl<TB: RuntimeToolbox + 'static> ActorCellGeneric<TB> {
     self.recreate_actor();
     let outcome = self.run_pre_start(LifecycleStage::Restarted);
     if outcome.is_ok() {
+      self.restarts.fetch_add(1, Ordering::AcqRel);
       self.mailbox.resume();
     }
     outcome
@@ -412,6 +466,7 @@ impl<TB: RuntimeToolbox + 'static> ActorCellGeneric<TB> {
 
   fn report_failure(&self, error: &ActorError, snapshot: Option<FailureMessageSnapshot>) {
     self.mailbox.suspend();
+    *self.last_failure.lock() = Some(String::from(error.reason().as_str()));
     let timestamp = self.system.monotonic_now();
     let payload = FailurePayload::from_error(self.pid, error, snapshot, timestamp);
     self.system.report_failure(payload);
@@ -419,6 +474,7 @@ impl<TB: RuntimeToolbox + 'static> ActorCellGeneric<TB> {
 
   fn handle_failure_message(&self, payload: &FailurePayload) {
     let actor_error = payload.to_actor_error();
+    self.notify_child_failed(payload.child(), &actor_error);
     let now = self.system.monotonic_now();
     let payload_ref = &payload;
     let (directive, affected) = self.handle_child_failure(payload.child(), &actor_error, now);
@@ -460,6 +516,15 @@ impl<TB: RuntimeToolbox + 'static> ActorCellGeneric<TB> {
     }
   }
 
+  fn notify_child_failed(&self, child: Pid, error: &ActorError) {
+    let system = ActorSystemGeneric::from_state(self.system.clone());
+    let mut ctx = ActorContextGeneric::new(&system, self.pid);
+    let mut actor = self.actor.lock();
+    actor.on_child_failed(&mut ctx, child, error);
+    drop(actor);
+    ctx.clear_reply_to();
+  }
+
   fn run_pre_start(&self, stage: LifecycleStage) -> Result<(), ActorError> {
     let system = ActorSystemGeneric::from_state(self.system.clone());
     let mut ctx = ActorContextGeneric::new(&system, self.pid);
@@ -488,6 +553,7 @@ impl<TB: RuntimeToolbox + 'static> MessageInvoker<TB> for ActorCellGeneric<TB> {
     let failure_candidate = message.clone();
     let result = self.pipeline.invoke_user(&mut *actor, &mut ctx, message);
     drop(actor);
+    self.processed.fetch_add(1, Ordering::AcqRel);
     if let Err(ref error) = result {
       let snapshot = FailureMessageSnapshot::from_message(&failure_candidate);
       self.report_failure(error, Some(snapshot));
@@ -1,4 +1,5 @@
 use alloc::{string::ToString, vec, vec::Vec};
+use core::time::Duration;
 
 use fraktor_utils_rs::core::{
   runtime_toolbox::{NoStdMutex, NoStdToolbox},
@@ -10,7 +11,7 @@ use crate::core::{
   actor_prim::{Actor, ActorContextGeneric, Pid},
   error::ActorError,
   mailbox::ScheduleHints,
-  messaging::{AnyMessage, AnyMessageViewGeneric, SystemMessage, message_invoker::MessageInvoker},
+  messaging::{AnyMessage, AnyMessageViewGeneric, FailurePayload, SystemMessage, message_invoker::MessageInvoker},
   props::Props,
   system::SystemState,
 };
@@ -83,6 +84,24 @@ impl Actor for RecordingActor {
   }
 }
 
+struct ChildFailureRecorderActor {
+  log: ArcShared<NoStdMutex<Vec<Pid>>>,
+}
+
+impl Actor for ChildFailureRecorderActor {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    _message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    Ok(())
+  }
+
+  fn on_child_failed(&mut self, _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>, child: Pid, _error: &ActorError) {
+    self.log.lock().push(child);
+  }
+}
+
 #[test]
 fn actor_cell_holds_components() {
   let system = ArcShared::new(SystemState::new());
@@ -237,3 +256,22 @@ fn system_queue_is_drained_before_user_queue() {
   let snapshot = log.lock().clone();
   assert_eq!(snapshot, vec!["pre_start", "receive"]);
 }
+
+#[test]
+fn failure_system_message_notifies_parent_hook() {
+  let state = ArcShared::new(SystemState::new());
+  let log = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let props = Props::from_fn({
+    let log = log.clone();
+    move || ChildFailureRecorderActor { log: log.clone() }
+  });
+  let cell =
+    ActorCell::create(state.clone(), Pid::new(43, 0), None, "parent".to_string(), &props).expect("create actor cell");
+  state.register_cell(cell.clone());
+
+  let child = Pid::new(44, 0);
+  let payload = FailurePayload::from_error(child, &ActorError::recoverable("boom"), None, Duration::ZERO);
+  MessageInvoker::invoke_system_message(&*cell, SystemMessage::Failure(payload)).expect("failure");
+
+  assert_eq!(log.lock().clone(), vec![child]);
+}
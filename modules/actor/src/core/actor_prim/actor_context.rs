@@ -12,7 +12,7 @@ use crate::core::{
   actor_prim::{ChildRefGeneric, Pid, actor_ref::ActorRefGeneric, pipe_spawn_error::PipeSpawnError},
   error::SendError,
   logging::LogLevel,
-  messaging::{AnyMessageGeneric, SystemMessage},
+  messaging::{AnyMessageGeneric, MessageHeaders, SystemMessage},
   props::PropsGeneric,
   spawn::SpawnError,
   system::ActorSystemGeneric,
@@ -23,6 +23,7 @@ pub struct ActorContextGeneric<'a, TB: RuntimeToolbox + 'static> {
   system:   ActorSystemGeneric<TB>,
   pid:      Pid,
   reply_to: Option<ActorRefGeneric<TB>>,
+  headers:  MessageHeaders,
   _marker:  PhantomData<&'a ()>,
 }
 
@@ -33,7 +34,7 @@ impl<TB: RuntimeToolbox + 'static> ActorContextGeneric<'_, TB> {
   /// Creates a new context placeholder.
   #[must_use]
   pub fn new(system: &ActorSystemGeneric<TB>, pid: Pid) -> Self {
-    Self { system: system.clone(), pid, reply_to: None, _marker: PhantomData }
+    Self { system: system.clone(), pid, reply_to: None, headers: MessageHeaders::new(), _marker: PhantomData }
   }
 
   /// Returns a reference to the actor system.
@@ -64,6 +65,25 @@ impl<TB: RuntimeToolbox + 'static> ActorContextGeneric<'_, TB> {
     self.reply_to = None;
   }
 
+  /// Returns the headers of the message being processed.
+  ///
+  /// Middleware may modify them before the actor runs; the actor then observes the modified
+  /// headers here rather than on the message view.
+  #[must_use]
+  pub const fn headers(&self) -> &MessageHeaders {
+    &self.headers
+  }
+
+  /// Returns the headers of the message being processed for modification.
+  pub const fn headers_mut(&mut self) -> &mut MessageHeaders {
+    &mut self.headers
+  }
+
+  /// Replaces the headers of the message being processed (used internally by the runtime).
+  pub const fn set_headers(&mut self, headers: MessageHeaders) -> MessageHeaders {
+    core::mem::replace(&mut self.headers, headers)
+  }
+
   /// Returns an [`ActorRef`] pointing to the running actor.
   ///
   /// # Panics
@@ -79,10 +99,14 @@ impl<TB: RuntimeToolbox + 'static> ActorContextGeneric<'_, TB> {
 
   /// Sends a reply to the caller if a reply target is present.
   ///
+  /// Propagated headers of the message being processed are copied onto the reply unless it
+  /// already carries a header of the same type.
+  ///
   /// # Errors
   ///
   /// Returns an error if no reply target is set or sending fails.
-  pub fn reply(&self, message: AnyMessageGeneric<TB>) -> Result<(), SendError<TB>> {
+  pub fn reply(&self, mut message: AnyMessageGeneric<TB>) -> Result<(), SendError<TB>> {
+    message.headers_mut().extend_propagated(&self.headers);
     match self.reply_to.as_ref() {
       | Some(target) => target.tell(message),
       | None => Err(SendError::no_recipient(message)),
@@ -201,6 +225,8 @@ impl<TB: RuntimeToolbox + 'static> ActorContextGeneric<'_, TB> {
 
   /// Pipes the completion of an asynchronous computation back to the running actor.
   ///
+  /// Propagated headers of the message being processed are copied onto the piped message.
+  ///
   /// # Errors
   ///
   /// Returns an error if the actor is unavailable or already stopped.
@@ -213,9 +239,12 @@ impl<TB: RuntimeToolbox + 'static> ActorContextGeneric<'_, TB> {
       return Err(PipeSpawnError::ActorUnavailable);
     };
 
+    let headers = self.headers.propagated();
     let mapped = async move {
       let value = future.await;
-      map(value)
+      let mut message = map(value);
+      message.headers_mut().extend_propagated(&headers);
+      message
     };
 
     cell.spawn_pipe_task(Box::pin(mapped))
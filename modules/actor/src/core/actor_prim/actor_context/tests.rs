@@ -8,13 +8,17 @@ use fraktor_utils_rs::core::{
 
 use super::{ActorContext, ActorContextGeneric};
 use crate::core::{
-  actor_prim::{Actor, ActorCell, Pid},
-  error::ActorError,
+  actor_prim::{
+    Actor, ActorCell, Pid,
+    actor_ref::{ActorRef, ActorRefSender},
+  },
+  error::{ActorError, SendError},
   futures::ActorFuture,
   logging::LogLevel,
   messaging::{AnyMessage, AnyMessageViewGeneric},
   props::Props,
   system::ActorSystem,
+  testkit::ActorTestKit,
 };
 
 struct TestActor;
@@ -77,6 +81,48 @@ impl Actor for ProbeActor {
   }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tenant(u32);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Internal;
+
+struct CapturingSender {
+  messages: ArcShared<NoStdMutex<Vec<AnyMessage>>>,
+}
+
+impl ActorRefSender<NoStdToolbox> for CapturingSender {
+  fn send(&self, message: AnyMessage) -> Result<(), SendError<NoStdToolbox>> {
+    self.messages.lock().push(message);
+    Ok(())
+  }
+}
+
+// "start" を受けると返信と pipe_to_self を行い、パイプされた i32 のヘッダを記録する
+// pipe_to_self で届いた値と、そのとき見えていたヘッダの組
+type PipedHeaders = ArcShared<NoStdMutex<Vec<(i32, Option<Tenant>, bool)>>>;
+
+struct HeaderEchoActor {
+  piped: PipedHeaders,
+}
+
+impl Actor for HeaderEchoActor {
+  fn receive(
+    &mut self,
+    context: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    if let Some(value) = message.downcast_ref::<i32>() {
+      let headers = context.headers();
+      self.piped.lock().push((*value, headers.get::<Tenant>().copied(), headers.contains::<Internal>()));
+    } else if message.downcast_ref::<&'static str>().is_some() {
+      context.pipe_to_self(async { 5_i32 }, AnyMessage::new).map_err(|_| ActorError::recoverable("pipe"))?;
+      context.reply(AnyMessage::new("pong")).map_err(|error| ActorError::from_send_error(&error))?;
+    }
+    Ok(())
+  }
+}
+
 #[test]
 fn actor_context_new() {
   let system = ActorSystem::new_empty();
@@ -291,3 +337,42 @@ fn spawn_child_watched_installs_watch() {
 
   assert!(child_cell.watchers_snapshot().contains(&parent_pid));
 }
+
+#[test]
+fn propagated_headers_flow_to_replies_and_piped_messages() {
+  let kit = ActorTestKit::new().expect("kit");
+  let piped = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let actor = kit
+    .spawn(&Props::from_fn({
+      let piped = piped.clone();
+      move || HeaderEchoActor { piped: piped.clone() }
+    }))
+    .expect("spawn");
+  let replies = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let reply_to = ActorRef::new(Pid::new(999, 0), ArcShared::new(CapturingSender { messages: replies.clone() }));
+
+  let mut request = AnyMessage::new("start").with_reply_to(reply_to);
+  request.headers_mut().insert_propagated(Tenant(42));
+  request.headers_mut().insert(Internal);
+  actor.tell(request).expect("tell");
+
+  wait_until(|| !piped.lock().is_empty());
+  assert_eq!(piped.lock().clone(), vec![(5, Some(Tenant(42)), false)]);
+  let replies = replies.lock();
+  assert_eq!(replies.len(), 1);
+  assert_eq!(replies[0].headers().get::<Tenant>(), Some(&Tenant(42)));
+  assert!(!replies[0].headers().contains::<Internal>());
+}
+
+#[test]
+fn headers_are_scoped_to_the_message_being_processed() {
+  let system = ActorSystem::new_empty();
+  let mut context = ActorContext::new(&system, Pid::new(1, 0));
+  assert!(context.headers().is_empty());
+
+  context.headers_mut().insert(Tenant(1));
+  let previous = context.set_headers(crate::core::messaging::MessageHeaders::new());
+
+  assert_eq!(previous.get::<Tenant>(), Some(&Tenant(1)));
+  assert!(context.headers().is_empty());
+}
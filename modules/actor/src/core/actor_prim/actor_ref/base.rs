@@ -63,9 +63,11 @@ impl<TB: RuntimeToolbox> ActorRefGeneric<TB> {
   }
 
   /// Returns the logical path of the actor if the system is still available.
+  ///
+  /// References to remote actors report the remote path known to the actor-ref provider.
   #[must_use]
   pub fn path(&self) -> Option<ActorPath> {
-    self.system.as_ref().and_then(|system| system.actor_path(&self.pid))
+    self.system.as_ref().and_then(|system| system.actor_path(&self.pid).or_else(|| system.remote_actor_path(&self.pid)))
   }
 
   /// Returns the canonical actor path including authority and UID when available.
@@ -79,7 +81,10 @@ impl<TB: RuntimeToolbox> ActorRefGeneric<TB> {
   /// # Errors
   ///
   /// Returns an error if the mailbox is full, closed, or the actor doesn't exist.
-  pub fn tell(&self, message: AnyMessageGeneric<TB>) -> Result<(), SendError<TB>> {
+  pub fn tell(&self, mut message: AnyMessageGeneric<TB>) -> Result<(), SendError<TB>> {
+    if let Some(system) = &self.system {
+      system.before_send(&mut message);
+    }
     match self.sender.send(message) {
       | Ok(()) => Ok(()),
       | Err(error) => {
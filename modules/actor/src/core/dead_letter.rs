@@ -2,13 +2,20 @@
 //!
 //! This module contains undeliverable message handling.
 
+mod dead_letter_config;
 mod dead_letter_entry;
 mod dead_letter_impl;
+mod dead_letter_query;
 mod dead_letter_reason;
+mod dead_letter_suppression;
 
+pub use dead_letter_config::DeadLetterConfig;
 pub use dead_letter_entry::{DeadLetterEntry, DeadLetterEntryGeneric};
 pub use dead_letter_impl::{DeadLetter, DeadLetterGeneric};
+pub use dead_letter_query::DeadLetterQuery;
 pub use dead_letter_reason::DeadLetterReason;
+pub(crate) use dead_letter_suppression::DeadLetterSuppressed;
+pub use dead_letter_suppression::DeadLetterSuppression;
 
 #[cfg(test)]
 mod tests;
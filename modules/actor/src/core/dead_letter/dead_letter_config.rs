@@ -0,0 +1,90 @@
+//! Retention and logging settings of the deadletter store.
+
+use core::time::Duration;
+
+/// Configures how many deadletters are retained and how many of them are logged.
+///
+/// Logging follows Pekko's `log-dead-letters`: the first [`log_limit`](Self::log_limit) deadletters
+/// are logged, then logging is suspended. Once
+/// [`log_suspend_duration`](Self::log_suspend_duration) has elapsed, the next deadletter logs a
+/// summary of the skipped ones and opens a new window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeadLetterConfig {
+  capacity:             usize,
+  log_limit:            Option<usize>,
+  log_suspend_duration: Duration,
+}
+
+impl DeadLetterConfig {
+  const DEFAULT_CAPACITY: usize = 512;
+  const DEFAULT_LOG_LIMIT: usize = 10;
+  const DEFAULT_LOG_SUSPEND_DURATION: Duration = Duration::from_secs(300);
+
+  /// Creates the default configuration.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self {
+      capacity:             Self::DEFAULT_CAPACITY,
+      log_limit:            Some(Self::DEFAULT_LOG_LIMIT),
+      log_suspend_duration: Self::DEFAULT_LOG_SUSPEND_DURATION,
+    }
+  }
+
+  /// Sets the number of deadletters retained; older entries are evicted first.
+  #[must_use]
+  pub const fn with_capacity(mut self, capacity: usize) -> Self {
+    self.capacity = capacity;
+    self
+  }
+
+  /// Sets the number of deadletters logged per window. `0` disables logging.
+  #[must_use]
+  pub const fn with_log_limit(mut self, limit: usize) -> Self {
+    self.log_limit = Some(limit);
+    self
+  }
+
+  /// Disables deadletter logging; entries are still retained and published.
+  #[must_use]
+  pub const fn with_logging_disabled(self) -> Self {
+    self.with_log_limit(0)
+  }
+
+  /// Logs every deadletter.
+  #[must_use]
+  pub const fn with_unlimited_logging(mut self) -> Self {
+    self.log_limit = None;
+    self
+  }
+
+  /// Sets how long logging stays suspended once the log limit has been reached.
+  #[must_use]
+  pub const fn with_log_suspend_duration(mut self, duration: Duration) -> Self {
+    self.log_suspend_duration = duration;
+    self
+  }
+
+  /// Returns the retention capacity.
+  #[must_use]
+  pub const fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  /// Returns the number of deadletters logged per window, or `None` when unlimited.
+  #[must_use]
+  pub const fn log_limit(&self) -> Option<usize> {
+    self.log_limit
+  }
+
+  /// Returns how long logging stays suspended once the log limit has been reached.
+  #[must_use]
+  pub const fn log_suspend_duration(&self) -> Duration {
+    self.log_suspend_duration
+  }
+}
+
+impl Default for DeadLetterConfig {
+  fn default() -> Self {
+    Self::new()
+  }
+}
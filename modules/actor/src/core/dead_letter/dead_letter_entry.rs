@@ -4,7 +4,11 @@ use core::time::Duration;
 
 use fraktor_utils_rs::core::runtime_toolbox::{NoStdToolbox, RuntimeToolbox};
 
-use crate::core::{actor_prim::Pid, dead_letter::dead_letter_reason::DeadLetterReason, messaging::AnyMessageGeneric};
+use crate::core::{
+  actor_prim::Pid,
+  dead_letter::{dead_letter_reason::DeadLetterReason, dead_letter_suppression::DeadLetterSuppressed},
+  messaging::AnyMessageGeneric,
+};
 
 /// Captures a single deadletter occurrence.
 #[derive(Debug)]
@@ -50,6 +54,13 @@ impl<TB: RuntimeToolbox> DeadLetterEntryGeneric<TB> {
   pub const fn timestamp(&self) -> Duration {
     self.timestamp
   }
+
+  /// Returns `true` when the message was marked with
+  /// [`DeadLetterSuppression`](crate::core::dead_letter::DeadLetterSuppression) and is not logged.
+  #[must_use]
+  pub fn is_suppressed(&self) -> bool {
+    self.message.headers().contains::<DeadLetterSuppressed>()
+  }
 }
 
 impl<TB: RuntimeToolbox> Clone for DeadLetterEntryGeneric<TB> {
@@ -1,7 +1,12 @@
 //! Deadletter repository publishing notifications to the event stream.
 
-use alloc::{format, vec::Vec};
-use core::time::Duration;
+use alloc::{
+  collections::{BTreeSet, VecDeque},
+  format,
+  string::String,
+  vec::Vec,
+};
+use core::{any::TypeId, time::Duration};
 
 use fraktor_utils_rs::core::{
   runtime_toolbox::{NoStdToolbox, RuntimeToolbox, SyncMutexFamily, ToolboxMutex},
@@ -10,7 +15,10 @@ use fraktor_utils_rs::core::{
 
 use crate::core::{
   actor_prim::Pid,
-  dead_letter::{DeadLetterEntryGeneric, dead_letter_reason::DeadLetterReason},
+  dead_letter::{
+    DeadLetterConfig, DeadLetterEntryGeneric, DeadLetterQuery, DeadLetterSuppressed, DeadLetterSuppression,
+    dead_letter_reason::DeadLetterReason,
+  },
   error::SendError,
   event_stream::{EventStreamEvent, EventStreamGeneric},
   logging::{LogEvent, LogLevel},
@@ -20,17 +28,69 @@ use crate::core::{
 const DEFAULT_CAPACITY: usize = 256;
 
 /// Collects undeliverable messages and notifies subscribers.
+///
+/// Entries are kept in a ring bounded by [`DeadLetterConfig::capacity`], counted per reason and
+/// logged subject to the configured rate limit. Payload types registered with
+/// [`register_suppression`](Self::register_suppression) are never logged.
 pub struct DeadLetterGeneric<TB: RuntimeToolbox + 'static> {
-  entries:      ToolboxMutex<Vec<DeadLetterEntryGeneric<TB>>, TB>,
-  capacity:     usize,
+  state:        ToolboxMutex<DeadLetterState<TB>, TB>,
   event_stream: ArcShared<EventStreamGeneric<TB>>,
 }
 
+struct DeadLetterState<TB: RuntimeToolbox + 'static> {
+  entries:    VecDeque<DeadLetterEntryGeneric<TB>>,
+  config:     DeadLetterConfig,
+  counts:     [u64; DeadLetterReason::ALL.len()],
+  window:     LogWindow,
+  suppressed: BTreeSet<TypeId>,
+}
+
+#[derive(Default)]
+struct LogWindow {
+  logged:       usize,
+  skipped:      u64,
+  suspended_at: Option<Duration>,
+}
+
+enum LogDecision {
+  Skip,
+  Log { skipped: u64, suspended_for: Option<Duration> },
+}
+
+impl LogWindow {
+  fn admit(&mut self, config: &DeadLetterConfig, now: Duration) -> LogDecision {
+    let Some(limit) = config.log_limit() else {
+      return LogDecision::Log { skipped: 0, suspended_for: None };
+    };
+    if limit == 0 {
+      return LogDecision::Skip;
+    }
+    let mut skipped = 0;
+    if let Some(since) = self.suspended_at {
+      if now.saturating_sub(since) < config.log_suspend_duration() {
+        self.skipped += 1;
+        return LogDecision::Skip;
+      }
+      // 停止期間が明けたので、読み飛ばした件数を報告して新しいウィンドウを開く
+      skipped = core::mem::take(&mut self.skipped);
+      self.suspended_at = None;
+      self.logged = 0;
+    }
+    self.logged += 1;
+    let mut suspended_for = None;
+    if self.logged >= limit {
+      self.suspended_at = Some(now);
+      suspended_for = Some(config.log_suspend_duration());
+    }
+    LogDecision::Log { skipped, suspended_for }
+  }
+}
+
 impl<TB: RuntimeToolbox + 'static> DeadLetterGeneric<TB> {
   /// Creates a new deadletter store with the provided buffer capacity.
   #[must_use]
   pub fn new(event_stream: ArcShared<EventStreamGeneric<TB>>, capacity: usize) -> Self {
-    Self { entries: <TB::MutexFamily as SyncMutexFamily>::create(Vec::new()), capacity, event_stream }
+    Self::with_config(event_stream, DeadLetterConfig::new().with_capacity(capacity))
   }
 
   /// Creates a new deadletter store with the default capacity.
@@ -39,6 +99,44 @@ impl<TB: RuntimeToolbox + 'static> DeadLetterGeneric<TB> {
     Self::new(event_stream, DEFAULT_CAPACITY)
   }
 
+  /// Creates a new deadletter store with the provided configuration.
+  #[must_use]
+  pub fn with_config(event_stream: ArcShared<EventStreamGeneric<TB>>, config: DeadLetterConfig) -> Self {
+    let state = DeadLetterState {
+      entries: VecDeque::new(),
+      config,
+      counts: [0; DeadLetterReason::ALL.len()],
+      window: LogWindow::default(),
+      suppressed: BTreeSet::new(),
+    };
+    Self { state: <TB::MutexFamily as SyncMutexFamily>::create(state), event_stream }
+  }
+
+  /// Replaces the configuration, evicting the oldest entries beyond the new capacity.
+  pub fn configure(&self, config: DeadLetterConfig) {
+    let mut state = self.state.lock();
+    state.config = config;
+    state.window = LogWindow::default();
+    let overflow = state.entries.len().saturating_sub(config.capacity());
+    state.entries.drain(..overflow);
+  }
+
+  /// Returns the active configuration.
+  #[must_use]
+  pub fn config(&self) -> DeadLetterConfig {
+    self.state.lock().config
+  }
+
+  /// Marks deadletters carrying a `T` payload as suppressed when they are recorded.
+  ///
+  /// Unlike [`AnyMessageGeneric::new_suppressed`], this also covers messages built with
+  /// [`AnyMessageGeneric::new`], e.g. by typed references or remoting.
+  pub fn register_suppression<T>(&self)
+  where
+    T: DeadLetterSuppression, {
+    self.state.lock().suppressed.insert(TypeId::of::<T>());
+  }
+
   /// Records a send error generated while targeting the specified pid.
   pub fn record_send_error(&self, target: Option<Pid>, error: &SendError<TB>, timestamp: Duration) {
     let reason = match error {
@@ -60,32 +158,80 @@ impl<TB: RuntimeToolbox + 'static> DeadLetterGeneric<TB> {
     target: Option<Pid>,
     timestamp: Duration,
   ) {
-    let entry = DeadLetterEntryGeneric::new(message, reason, target, timestamp);
-    {
-      let mut entries = self.entries.lock();
-      entries.push(entry.clone());
-      if entries.len() > self.capacity {
-        let overflow = entries.len() - self.capacity;
-        entries.drain(0..overflow);
+    let (entry, decision) = {
+      let mut state = self.state.lock();
+      let message = if state.suppressed.contains(&message.payload().type_id()) {
+        message.with_header(DeadLetterSuppressed)
+      } else {
+        message
+      };
+      let entry = DeadLetterEntryGeneric::new(message, reason, target, timestamp);
+      state.counts[reason.index()] += 1;
+      if state.config.capacity() > 0 {
+        if state.entries.len() >= state.config.capacity() {
+          state.entries.pop_front();
+        }
+        state.entries.push_back(entry.clone());
       }
-    }
+      let config = state.config;
+      let decision = if entry.is_suppressed() { LogDecision::Skip } else { state.window.admit(&config, timestamp) };
+      (entry, decision)
+    };
 
-    self.publish(&entry);
+    self.event_stream.publish(&EventStreamEvent::DeadLetter(entry.clone()));
+    if let LogDecision::Log { skipped, suspended_for } = decision {
+      self.log(&entry, skipped, suspended_for);
+    }
   }
 
-  /// Returns a snapshot of stored deadletters.
+  /// Returns a snapshot of stored deadletters, oldest first.
   #[must_use]
   pub fn entries(&self) -> Vec<DeadLetterEntryGeneric<TB>> {
-    self.entries.lock().clone()
+    self.state.lock().entries.iter().cloned().collect()
   }
 
-  fn publish(&self, entry: &DeadLetterEntryGeneric<TB>) {
-    self.event_stream.publish(&EventStreamEvent::DeadLetter(entry.clone()));
-    let (origin, message) = match entry.recipient() {
-      | Some(pid) => (Some(pid), format!("deadletter for pid {:?} (reason: {:?})", pid, entry.reason())),
-      | None => (None, format!("deadletter recorded (reason: {:?})", entry.reason())),
+  /// Returns the stored deadletters matching `query`, oldest first.
+  #[must_use]
+  pub fn query(&self, query: &DeadLetterQuery) -> Vec<DeadLetterEntryGeneric<TB>> {
+    let state = self.state.lock();
+    let mut matches: Vec<_> = state.entries.iter().rev().filter(|entry| query.matches(entry)).collect();
+    if let Some(limit) = query.limit() {
+      matches.truncate(limit);
+    }
+    matches.into_iter().rev().cloned().collect()
+  }
+
+  /// Returns the number of deadletters recorded for `reason`, including evicted ones.
+  #[must_use]
+  pub fn count(&self, reason: DeadLetterReason) -> u64 {
+    self.state.lock().counts[reason.index()]
+  }
+
+  /// Returns the number of deadletters recorded per reason, including evicted ones.
+  #[must_use]
+  pub fn counts(&self) -> Vec<(DeadLetterReason, u64)> {
+    let counts = self.state.lock().counts;
+    DeadLetterReason::ALL.into_iter().map(|reason| (reason, counts[reason.index()])).collect()
+  }
+
+  fn log(&self, entry: &DeadLetterEntryGeneric<TB>, skipped: u64, suspended_for: Option<Duration>) {
+    if skipped > 0 {
+      let message = format!("{skipped} deadletters were not logged while logging was suspended");
+      self.publish_log(message, entry.timestamp(), None);
+    }
+    let type_name = entry.message().type_name().unwrap_or("unknown");
+    let mut message: String = match entry.recipient() {
+      | Some(pid) => format!("deadletter for pid {:?} (reason: {:?}, type: {type_name})", pid, entry.reason()),
+      | None => format!("deadletter recorded (reason: {:?}, type: {type_name})", entry.reason()),
     };
-    let log = LogEvent::new(LogLevel::Warn, message, entry.timestamp(), origin);
+    if let Some(suspend) = suspended_for {
+      message.push_str(&format!("; log limit reached, further deadletters are not logged for {suspend:?}"));
+    }
+    self.publish_log(message, entry.timestamp(), entry.recipient());
+  }
+
+  fn publish_log(&self, message: String, timestamp: Duration, origin: Option<Pid>) {
+    let log = LogEvent::new(LogLevel::Warn, message, timestamp, origin);
     self.event_stream.publish(&EventStreamEvent::Log(log));
   }
 }
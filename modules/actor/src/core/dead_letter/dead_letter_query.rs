@@ -0,0 +1,71 @@
+//! Filter applied to retained deadletters.
+
+use core::any::{Any, TypeId};
+
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
+use crate::core::{
+  actor_prim::Pid,
+  dead_letter::{DeadLetterEntryGeneric, DeadLetterReason},
+};
+
+/// Selects retained deadletters by recipient, reason and message type.
+///
+/// Criteria left unset match every entry; the ones set must all match.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DeadLetterQuery {
+  recipient:    Option<Pid>,
+  reason:       Option<DeadLetterReason>,
+  message_type: Option<TypeId>,
+  limit:        Option<usize>,
+}
+
+impl DeadLetterQuery {
+  /// Creates a query matching every entry.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { recipient: None, reason: None, message_type: None, limit: None }
+  }
+
+  /// Only matches deadletters addressed to `recipient`.
+  #[must_use]
+  pub const fn with_recipient(mut self, recipient: Pid) -> Self {
+    self.recipient = Some(recipient);
+    self
+  }
+
+  /// Only matches deadletters recorded for `reason`.
+  #[must_use]
+  pub const fn with_reason(mut self, reason: DeadLetterReason) -> Self {
+    self.reason = Some(reason);
+    self
+  }
+
+  /// Only matches deadletters whose payload is a `T`.
+  #[must_use]
+  pub fn with_message_type<T: 'static>(mut self) -> Self {
+    self.message_type = Some(TypeId::of::<T>());
+    self
+  }
+
+  /// Returns at most the `limit` most recent matches.
+  #[must_use]
+  pub const fn with_limit(mut self, limit: usize) -> Self {
+    self.limit = Some(limit);
+    self
+  }
+
+  /// Returns the maximum number of matches, if limited.
+  #[must_use]
+  pub const fn limit(&self) -> Option<usize> {
+    self.limit
+  }
+
+  /// Returns `true` when `entry` satisfies every criterion.
+  #[must_use]
+  pub fn matches<TB: RuntimeToolbox>(&self, entry: &DeadLetterEntryGeneric<TB>) -> bool {
+    self.recipient.is_none_or(|recipient| entry.recipient() == Some(recipient))
+      && self.reason.is_none_or(|reason| entry.reason() == reason)
+      && self.message_type.is_none_or(|type_id| Any::type_id(entry.message().payload()) == type_id)
+  }
+}
@@ -1,7 +1,7 @@
 //! Reasons captured when messages are routed to deadletter storage.
 
 /// High level classification explaining why a message was not delivered.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum DeadLetterReason {
   /// Mailbox capacity or overflow strategy rejected the message.
   MailboxFull,
@@ -19,4 +19,41 @@ pub enum DeadLetterReason {
   ExplicitRouting,
   /// Serialization failure prevented message delivery.
   SerializationError,
+  /// An access control rule refused to deliver the message.
+  AccessDenied,
+}
+
+impl DeadLetterReason {
+  /// Every reason, in declaration order.
+  pub const ALL: [Self; 9] = [
+    Self::MailboxFull,
+    Self::MailboxSuspended,
+    Self::MailboxTimeout,
+    Self::RecipientUnavailable,
+    Self::MissingRecipient,
+    Self::FatalActorError,
+    Self::ExplicitRouting,
+    Self::SerializationError,
+    Self::AccessDenied,
+  ];
+
+  /// Returns the snake_case name of the reason, suitable for log fields and metric labels.
+  #[must_use]
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      | Self::MailboxFull => "mailbox_full",
+      | Self::MailboxSuspended => "mailbox_suspended",
+      | Self::MailboxTimeout => "mailbox_timeout",
+      | Self::RecipientUnavailable => "recipient_unavailable",
+      | Self::MissingRecipient => "missing_recipient",
+      | Self::FatalActorError => "fatal_actor_error",
+      | Self::ExplicitRouting => "explicit_routing",
+      | Self::SerializationError => "serialization_error",
+      | Self::AccessDenied => "access_denied",
+    }
+  }
+
+  pub(crate) const fn index(self) -> usize {
+    self as usize
+  }
 }
@@ -0,0 +1,17 @@
+//! Marker for messages whose deadletters are expected.
+
+use core::any::Any;
+
+/// Marks message types whose deadletters are expected and must not be logged.
+///
+/// Implementing the trait alone has no effect: register the type once with
+/// [`ActorSystemGeneric::register_dead_letter_suppression`](crate::core::system::ActorSystemGeneric::register_dead_letter_suppression)
+/// to cover every message carrying it, or build individual messages with
+/// [`AnyMessageGeneric::new_suppressed`](crate::core::messaging::AnyMessageGeneric::new_suppressed).
+/// Their deadletters are still retained, counted and published on the event stream, with
+/// [`DeadLetterEntryGeneric::is_suppressed`](super::DeadLetterEntryGeneric::is_suppressed) set.
+pub trait DeadLetterSuppression: Any + Send + Sync {}
+
+/// Header attached to messages built from a [`DeadLetterSuppression`] payload.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct DeadLetterSuppressed;
@@ -1,6 +1,9 @@
 extern crate alloc;
 
-use alloc::vec::Vec;
+use alloc::{
+  string::{String, ToString},
+  vec::Vec,
+};
 use core::time::Duration;
 
 use fraktor_utils_rs::core::{
@@ -10,7 +13,7 @@ use fraktor_utils_rs::core::{
 
 use crate::core::{
   actor_prim::Pid,
-  dead_letter::{DeadLetter, DeadLetterReason},
+  dead_letter::{DeadLetter, DeadLetterConfig, DeadLetterQuery, DeadLetterReason, DeadLetterSuppression},
   error::SendError,
   event_stream::{EventStream, EventStreamEvent, EventStreamSubscriber, subscriber_handle},
   logging::LogLevel,
@@ -95,3 +98,127 @@ fn record_send_error_maps_timeout_reason() {
     entries.iter().any(|entry| entry.recipient() == Some(pid) && entry.reason() == DeadLetterReason::MailboxTimeout)
   );
 }
+
+struct Heartbeat;
+
+impl DeadLetterSuppression for Heartbeat {}
+
+fn warn_logs(events: &ArcShared<NoStdMutex<Vec<EventStreamEvent<NoStdToolbox>>>>) -> Vec<String> {
+  events
+    .lock()
+    .iter()
+    .filter_map(|event| match event {
+      | EventStreamEvent::Log(log) if log.level() == LogLevel::Warn => Some(log.message().to_string()),
+      | _ => None,
+    })
+    .collect()
+}
+
+#[test]
+fn query_filters_by_recipient_reason_and_type() {
+  let deadletter = DeadLetter::with_config(
+    ArcShared::new(EventStream::default()),
+    DeadLetterConfig::new().with_capacity(3).with_logging_disabled(),
+  );
+  let (a, b) = (Pid::new(1, 0), Pid::new(2, 0));
+  deadletter.record_entry(AnyMessage::new(0_u8), DeadLetterReason::MailboxFull, Some(a), Duration::ZERO);
+  deadletter.record_entry(AnyMessage::new(1_u32), DeadLetterReason::MailboxFull, Some(a), Duration::ZERO);
+  deadletter.record_entry(AnyMessage::new(2_u32), DeadLetterReason::ExplicitRouting, Some(b), Duration::ZERO);
+  deadletter.record_entry(AnyMessage::new(3_u32), DeadLetterReason::MailboxFull, Some(b), Duration::ZERO);
+
+  let payloads = |query: DeadLetterQuery| -> Vec<u32> {
+    deadletter
+      .query(&query)
+      .iter()
+      .filter_map(|entry| entry.message().payload().downcast_ref::<u32>().copied())
+      .collect()
+  };
+  assert_eq!(deadletter.entries().len(), 3);
+  assert_eq!(payloads(DeadLetterQuery::new().with_recipient(a)), [1]);
+  assert_eq!(payloads(DeadLetterQuery::new().with_reason(DeadLetterReason::MailboxFull)), [1, 3]);
+  assert_eq!(payloads(DeadLetterQuery::new().with_recipient(b).with_message_type::<u32>()), [2, 3]);
+  assert_eq!(payloads(DeadLetterQuery::new().with_limit(1)), [3]);
+  assert!(deadletter.query(&DeadLetterQuery::new().with_message_type::<u8>()).is_empty());
+}
+
+#[test]
+fn counts_include_evicted_entries() {
+  let deadletter = DeadLetter::new(ArcShared::new(EventStream::default()), 1);
+  for _ in 0..3 {
+    deadletter.record_entry(AnyMessage::new(()), DeadLetterReason::MailboxTimeout, None, Duration::ZERO);
+  }
+  deadletter.record_entry(AnyMessage::new(()), DeadLetterReason::AccessDenied, None, Duration::ZERO);
+
+  assert_eq!(deadletter.entries().len(), 1);
+  assert_eq!(deadletter.count(DeadLetterReason::MailboxTimeout), 3);
+  assert_eq!(deadletter.count(DeadLetterReason::MailboxFull), 0);
+  let counts = deadletter.counts();
+  assert_eq!(counts.len(), DeadLetterReason::ALL.len());
+  assert!(counts.contains(&(DeadLetterReason::AccessDenied, 1)));
+}
+
+#[test]
+fn logging_is_suspended_after_the_limit_and_resumes_with_a_summary() {
+  let stream = ArcShared::new(EventStream::default());
+  let events = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let subscriber = subscriber_handle(RecordingSubscriber::new(events.clone()));
+  let _subscription = EventStream::subscribe_arc(&stream, &subscriber);
+  let config = DeadLetterConfig::new().with_log_limit(2).with_log_suspend_duration(Duration::from_millis(10));
+  let deadletter = DeadLetter::with_config(stream, config);
+
+  for millis in 0..5 {
+    deadletter.record_entry(AnyMessage::new(()), DeadLetterReason::MailboxFull, None, Duration::from_millis(millis));
+  }
+  let logs = warn_logs(&events);
+  assert_eq!(logs.len(), 2);
+  assert!(logs[1].contains("log limit reached"));
+
+  deadletter.record_entry(AnyMessage::new(()), DeadLetterReason::MailboxFull, None, Duration::from_millis(11));
+  let logs = warn_logs(&events);
+  assert_eq!(logs.len(), 4);
+  assert_eq!(logs[2], "3 deadletters were not logged while logging was suspended");
+  assert!(!logs[3].contains("log limit reached"));
+  assert_eq!(deadletter.count(DeadLetterReason::MailboxFull), 6);
+}
+
+#[test]
+fn suppressed_messages_are_recorded_but_not_logged() {
+  let stream = ArcShared::new(EventStream::default());
+  let events = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let subscriber = subscriber_handle(RecordingSubscriber::new(events.clone()));
+  let _subscription = EventStream::subscribe_arc(&stream, &subscriber);
+  let deadletter = DeadLetter::with_config(stream, DeadLetterConfig::new().with_unlimited_logging());
+
+  deadletter.record_entry(
+    AnyMessage::new_suppressed(Heartbeat),
+    DeadLetterReason::RecipientUnavailable,
+    None,
+    Duration::ZERO,
+  );
+
+  let entries = deadletter.entries();
+  assert_eq!(entries.len(), 1);
+  assert!(entries[0].is_suppressed());
+  assert!(warn_logs(&events).is_empty());
+  assert!(events.lock().iter().any(|event| matches!(event, EventStreamEvent::DeadLetter(_))));
+  assert_eq!(deadletter.count(DeadLetterReason::RecipientUnavailable), 1);
+}
+
+#[test]
+fn registered_suppression_covers_plainly_built_messages() {
+  let stream = ArcShared::new(EventStream::default());
+  let events = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let subscriber = subscriber_handle(RecordingSubscriber::new(events.clone()));
+  let _subscription = EventStream::subscribe_arc(&stream, &subscriber);
+  let deadletter = DeadLetter::with_config(stream, DeadLetterConfig::new().with_unlimited_logging());
+  deadletter.register_suppression::<Heartbeat>();
+
+  deadletter.record_entry(AnyMessage::new(Heartbeat), DeadLetterReason::RecipientUnavailable, None, Duration::ZERO);
+  deadletter.record_entry(AnyMessage::new(1_u32), DeadLetterReason::RecipientUnavailable, None, Duration::ZERO);
+
+  let entries = deadletter.entries();
+  assert_eq!(entries.len(), 2);
+  assert!(entries[0].is_suppressed());
+  assert!(!entries[1].is_suppressed());
+  assert_eq!(warn_logs(&events).len(), 1);
+}
@@ -28,6 +28,7 @@ mod dispatcher_state;
 mod dispatchers;
 mod inline_executor;
 mod inline_schedule_adapter;
+mod replay_executor;
 mod schedule_adapter;
 mod schedule_waker;
 mod tick_executor;
@@ -44,7 +45,10 @@ pub use dispatcher_sender::{DispatcherSender, DispatcherSenderGeneric};
 pub use dispatchers::{Dispatchers, DispatchersGeneric};
 pub use inline_executor::{InlineExecutor, InlineExecutorGeneric};
 pub use inline_schedule_adapter::InlineScheduleAdapter;
+pub use replay_executor::{ReplayExecutor, ReplayExecutorGeneric};
 pub use schedule_adapter::ScheduleAdapter;
+#[cfg(feature = "embassy")]
+pub(crate) use schedule_waker::ScheduleWaker;
 pub use tick_executor::{TickExecutor, TickExecutorGeneric};
 
 /// Dispatcher configuration module.
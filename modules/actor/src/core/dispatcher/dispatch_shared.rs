@@ -7,6 +7,7 @@ use fraktor_utils_rs::core::{
 mod tests;
 
 use super::dispatcher_core::DispatcherCore;
+use crate::core::actor_prim::Pid;
 
 /// Shared reference for driving dispatcher execution across threads.
 ///
@@ -34,4 +35,9 @@ impl<TB: RuntimeToolbox + 'static> DispatchSharedGeneric<TB> {
   pub fn drive(&self) {
     DispatcherCore::drive(&self.core);
   }
+
+  /// Returns the pid of the actor whose mailbox this dispatcher drains.
+  pub(crate) fn pid(&self) -> Option<Pid> {
+    self.core.mailbox().pid()
+  }
 }
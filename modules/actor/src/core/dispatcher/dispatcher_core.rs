@@ -102,6 +102,15 @@ impl<TB: RuntimeToolbox + 'static> DispatcherCore<TB> {
     }
   }
 
+  fn record_run(&self) {
+    let Some(trace) = self.system_state.as_ref().and_then(|state| state.execution_trace()) else {
+      return;
+    };
+    if let Some(pid) = self.mailbox.pid() {
+      trace.record_run(pid);
+    }
+  }
+
   fn elapsed_since_progress(&self) -> Option<Duration> {
     let last = self.last_progress.load(Ordering::Acquire);
     if last == 0 {
@@ -114,6 +123,7 @@ impl<TB: RuntimeToolbox + 'static> DispatcherCore<TB> {
   }
 
   pub(crate) fn drive(self_arc: &ArcShared<Self>) {
+    self_arc.record_run();
     self_arc.mailbox.set_running();
     loop {
       {
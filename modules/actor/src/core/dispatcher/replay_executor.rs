@@ -0,0 +1,46 @@
+use fraktor_utils_rs::core::runtime_toolbox::{NoStdToolbox, RuntimeToolbox};
+
+use super::{
+  dispatch_error::DispatchError, dispatch_executor::DispatchExecutor, dispatch_shared::DispatchSharedGeneric,
+};
+use crate::core::scheduler::ExecutionTraceGeneric;
+
+#[cfg(test)]
+mod tests;
+
+/// Executor that runs mailboxes inline in the order captured by an [`ExecutionTraceGeneric`].
+///
+/// Submitted dispatcher batches are held by the trace and released as soon as the replayed
+/// interleaving reaches them. Install it as the default dispatcher and on every custom
+/// dispatcher of a system configured with the same replaying trace: a mailbox run submitted to
+/// any other executor bypasses the trace and is reported as its
+/// [`divergence`](ExecutionTraceGeneric::divergence).
+pub struct ReplayExecutorGeneric<TB: RuntimeToolbox + 'static> {
+  trace: ExecutionTraceGeneric<TB>,
+}
+
+/// Type alias for `ReplayExecutorGeneric` with the default `NoStdToolbox`.
+pub type ReplayExecutor = ReplayExecutorGeneric<NoStdToolbox>;
+
+impl<TB: RuntimeToolbox + 'static> ReplayExecutorGeneric<TB> {
+  /// Creates an executor releasing mailbox runs according to `trace`.
+  #[must_use]
+  pub const fn new(trace: ExecutionTraceGeneric<TB>) -> Self {
+    Self { trace }
+  }
+}
+
+impl<TB> DispatchExecutor<TB> for ReplayExecutorGeneric<TB>
+where
+  TB: RuntimeToolbox + Send + Sync + 'static,
+{
+  fn execute(&mut self, dispatcher: DispatchSharedGeneric<TB>) -> Result<(), DispatchError> {
+    self.trace.hold(dispatcher);
+    self.trace.pump();
+    Ok(())
+  }
+
+  fn supports_blocking(&self) -> bool {
+    false
+  }
+}
@@ -0,0 +1,143 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::time::Duration;
+
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdMutex, NoStdToolbox},
+  sync::ArcShared,
+};
+
+use super::ReplayExecutorGeneric;
+use crate::core::{
+  actor_prim::{Actor, ActorContextGeneric, Pid},
+  dispatcher::DispatcherConfigGeneric,
+  error::ActorError,
+  messaging::{AnyMessage, AnyMessageViewGeneric},
+  props::Props,
+  scheduler::{DeterministicEvent, ExecutionTrace, SchedulerCommand},
+  system::ActorSystemConfig,
+  testkit::ActorTestKit,
+};
+
+type Log = ArcShared<NoStdMutex<Vec<&'static str>>>;
+
+struct Recorder {
+  name: &'static str,
+  log:  Log,
+}
+
+impl Actor for Recorder {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    _message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    self.log.lock().push(self.name);
+    Ok(())
+  }
+}
+
+fn kit(trace: &ExecutionTrace, replay_executor: bool) -> ActorTestKit {
+  let mut config = ActorSystemConfig::default().with_execution_trace(trace.clone());
+  if replay_executor {
+    let executor = ReplayExecutorGeneric::<NoStdToolbox>::new(trace.clone());
+    config = config.with_default_dispatcher(DispatcherConfigGeneric::from_executor(Box::new(executor)));
+  }
+  ActorTestKit::with_config(config).expect("kit")
+}
+
+fn run_scenario(trace: &ExecutionTrace) -> Vec<&'static str> {
+  run_scenario_with(trace, trace.is_replaying())
+}
+
+fn run_scenario_with(trace: &ExecutionTrace, replay_executor: bool) -> Vec<&'static str> {
+  let kit = kit(trace, replay_executor);
+  let log: Log = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let spawn = |name: &'static str| {
+    let log = log.clone();
+    kit.spawn(&Props::from_fn(move || Recorder { name, log: log.clone() })).expect("spawn")
+  };
+  let first = spawn("first");
+  let second = spawn("second");
+
+  let context = kit.system().scheduler_context().expect("scheduler");
+  context
+    .scheduler()
+    .lock()
+    .schedule_once(Duration::from_millis(20), SchedulerCommand::SendMessage {
+      receiver:   second.actor_ref().clone(),
+      message:    AnyMessage::new(0_u32),
+      dispatcher: None,
+      sender:     None,
+    })
+    .expect("schedule");
+  first.tell(AnyMessage::new(1_u32)).expect("tell");
+  kit.advance(Duration::from_millis(20));
+  first.tell(AnyMessage::new(2_u32)).expect("tell");
+  second.tell(AnyMessage::new(3_u32)).expect("tell");
+
+  let log = log.lock().clone();
+  log
+}
+
+#[test]
+fn replay_reproduces_the_recorded_interleaving() {
+  let recording = ExecutionTrace::recording();
+  let recorded_log = run_scenario(&recording);
+  let events = recording.events();
+  assert!(events.iter().any(|event| matches!(event, DeterministicEvent::Fired { .. })));
+
+  let replay = ExecutionTrace::replaying(events.clone());
+  assert_eq!(run_scenario(&replay), recorded_log);
+  assert_eq!(replay.divergence(), None);
+  assert_eq!(replay.remaining(), 0);
+  assert_eq!(replay.held(), 0);
+  assert_eq!(replay.events(), events);
+}
+
+#[test]
+fn replay_forces_a_captured_order_that_differs_from_submission_order() {
+  let recording = ExecutionTrace::recording();
+  assert_eq!(run_scenario(&recording), ["first", "second", "first", "second"]);
+  let mut events = recording.events();
+  // 最後の 2 回のメールボックス実行を入れ替え、別のインターリーブが捕捉されたことにする
+  let len = events.len();
+  events.swap(len - 2, len - 1);
+
+  let replay = ExecutionTrace::replaying(events);
+  assert_eq!(run_scenario(&replay), ["first", "second", "second", "first"]);
+  assert_eq!(replay.divergence(), None);
+  assert_eq!(replay.remaining(), 0);
+}
+
+#[test]
+fn dispatchers_without_the_replay_executor_are_reported_as_divergence() {
+  let recording = ExecutionTrace::recording();
+  run_scenario(&recording);
+
+  let replay = ExecutionTrace::replaying(recording.events());
+  run_scenario_with(&replay, false);
+  let first_run = recording.events().iter().position(|event| matches!(event, DeterministicEvent::MailboxRun { .. }));
+  assert_eq!(replay.divergence(), first_run);
+}
+
+#[test]
+fn replay_falls_back_to_fifo_when_the_expected_mailbox_never_runs() {
+  // 一度も実行されないメールボックスを期待させ、保留されたメールボックスが詰まる状況を作る
+  let replay = ExecutionTrace::replaying([DeterministicEvent::MailboxRun { pid: Pid::new(9_999, 0) }]);
+  let kit = kit(&replay, true);
+  let log: Log = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let actor = kit.spawn(&Props::from_fn({
+    let log = log.clone();
+    move || Recorder { name: "first", log: log.clone() }
+  }));
+  actor.expect("spawn").tell(AnyMessage::new(1_u32)).expect("tell");
+  assert!(log.lock().is_empty());
+  assert!(replay.held() > 0);
+
+  for _ in 0..10 {
+    kit.advance(Duration::from_millis(10));
+  }
+  assert_eq!(log.lock().clone(), ["first"]);
+  assert_eq!(replay.held(), 0);
+  assert_eq!(replay.divergence(), Some(0));
+}
@@ -50,15 +50,20 @@ impl<TB: RuntimeToolbox + 'static> EventStreamGeneric<TB> {
     subscriber: &EventStreamSubscriberShared<TB>,
   ) -> EventStreamSubscriptionGeneric<TB> {
     let id = stream.next_id.fetch_add(1, Ordering::Relaxed);
-    {
-      let mut list = stream.subscribers.lock();
-      list.push(EventStreamSubscriberEntryGeneric::new(id, subscriber.clone()));
-    }
+    Self::subscribe_entry(stream, EventStreamSubscriberEntryGeneric::new(id, subscriber.clone()))
+  }
+
+  fn subscribe_entry(
+    stream: &ArcShared<Self>,
+    entry: EventStreamSubscriberEntryGeneric<TB>,
+  ) -> EventStreamSubscriptionGeneric<TB> {
+    let id = entry.id();
+    let replay = entry.clone();
+    stream.subscribers.lock().push(entry);
 
     let snapshot = stream.buffer.lock().clone();
     for event in snapshot.iter() {
-      let mut guard = subscriber.lock();
-      guard.on_event(event);
+      replay.deliver(event);
     }
 
     EventStreamSubscriptionGeneric::new(stream.clone(), id)
@@ -71,13 +76,18 @@ impl<TB: RuntimeToolbox + 'static> EventStreamGeneric<TB> {
   /// - Non-blocking `publish()` (immediate return)
   /// - Better scalability with many subscribers
   /// - Natural actor processing model
+  ///
+  /// Mailbox metrics are not delivered to actor subscribers, since forwarding them would
+  /// enqueue messages that publish further metrics. Actor subscribers may publish while they
+  /// handle an event.
   #[must_use]
   pub fn subscribe_actor(
     stream: &ArcShared<Self>,
     actor_ref: ActorRefGeneric<TB>,
   ) -> EventStreamSubscriptionGeneric<TB> {
-    let subscriber = subscriber_handle(ActorRefEventStreamSubscriber::new(actor_ref));
-    Self::subscribe_arc(stream, &subscriber)
+    let id = stream.next_id.fetch_add(1, Ordering::Relaxed);
+    let subscriber = subscriber_handle(ActorRefEventStreamSubscriber::new(actor_ref.clone()));
+    Self::subscribe_entry(stream, EventStreamSubscriberEntryGeneric::for_actor(id, subscriber, actor_ref))
   }
 
   /// Removes the subscriber associated with the identifier.
@@ -101,9 +111,7 @@ impl<TB: RuntimeToolbox + 'static> EventStreamGeneric<TB> {
 
     let subscribers = self.subscribers.lock().clone();
     for entry in subscribers.iter() {
-      let handle = entry.subscriber();
-      let mut guard = handle.lock();
-      guard.on_event(event);
+      entry.deliver(event);
     }
   }
 }
@@ -3,21 +3,42 @@
 #[cfg(test)]
 mod tests;
 
-use fraktor_utils_rs::core::runtime_toolbox::{NoStdToolbox, RuntimeToolbox};
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdToolbox, RuntimeToolbox},
+  sync::sync_mutex_like::SyncMutexLike,
+};
 
-use crate::core::event_stream::EventStreamSubscriberShared;
+use crate::core::{
+  actor_prim::actor_ref::ActorRefGeneric,
+  event_stream::{EventStreamEvent, EventStreamSubscriberShared},
+  messaging::AnyMessageGeneric,
+};
 
 /// Maps subscription identifiers to subscriber instances.
 pub struct EventStreamSubscriberEntryGeneric<TB: RuntimeToolbox> {
   id:         u64,
   subscriber: EventStreamSubscriberShared<TB>,
+  actor:      Option<ActorRefGeneric<TB>>,
 }
 
 impl<TB: RuntimeToolbox> EventStreamSubscriberEntryGeneric<TB> {
   /// Creates a new subscriber entry.
   #[must_use]
   pub const fn new(id: u64, subscriber: EventStreamSubscriberShared<TB>) -> Self {
-    Self { id, subscriber }
+    Self { id, subscriber, actor: None }
+  }
+
+  /// Creates an entry delivering events straight to the mailbox of `actor`.
+  ///
+  /// Such entries never lock the subscriber, so the actor may publish while it handles an event
+  /// delivered inline. They skip mailbox metrics, since every enqueue publishes another one.
+  #[must_use]
+  pub(crate) const fn for_actor(
+    id: u64,
+    subscriber: EventStreamSubscriberShared<TB>,
+    actor: ActorRefGeneric<TB>,
+  ) -> Self {
+    Self { id, subscriber, actor: Some(actor) }
   }
 
   /// Returns the subscription identifier.
@@ -31,11 +52,23 @@ impl<TB: RuntimeToolbox> EventStreamSubscriberEntryGeneric<TB> {
   pub fn subscriber(&self) -> EventStreamSubscriberShared<TB> {
     self.subscriber.clone()
   }
+
+  /// Delivers the event to the subscriber.
+  pub(crate) fn deliver(&self, event: &EventStreamEvent<TB>) {
+    match &self.actor {
+      | Some(actor) => {
+        if !matches!(event, EventStreamEvent::Mailbox(_) | EventStreamEvent::MailboxPressure(_)) {
+          let _ = actor.tell(AnyMessageGeneric::new(event.clone()));
+        }
+      },
+      | None => self.subscriber.lock().on_event(event),
+    }
+  }
 }
 
 impl<TB: RuntimeToolbox> Clone for EventStreamSubscriberEntryGeneric<TB> {
   fn clone(&self) -> Self {
-    Self { id: self.id, subscriber: self.subscriber.clone() }
+    Self { id: self.id, subscriber: self.subscriber.clone(), actor: self.actor.clone() }
   }
 }
 
@@ -1,10 +1,36 @@
-use fraktor_utils_rs::core::runtime_toolbox::NoStdToolbox;
+use core::time::Duration;
+
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdMutex, NoStdToolbox},
+  sync::ArcShared,
+};
 
 use super::EventStreamSubscriberEntry;
-use crate::core::event_stream::{EventStreamEvent, EventStreamSubscriber, subscriber_handle};
+use crate::core::{
+  actor_prim::{
+    Pid,
+    actor_ref::{ActorRefGeneric, ActorRefSender},
+  },
+  error::SendError,
+  event_stream::{EventStreamEvent, EventStreamSubscriber, subscriber_handle},
+  logging::{LogEvent, LogLevel},
+  mailbox::MailboxMetricsEvent,
+  messaging::AnyMessageGeneric,
+};
 
 struct MockSubscriber;
 
+struct CountingSender {
+  count: NoStdMutex<usize>,
+}
+
+impl ActorRefSender<NoStdToolbox> for CountingSender {
+  fn send(&self, _message: AnyMessageGeneric<NoStdToolbox>) -> Result<(), SendError<NoStdToolbox>> {
+    *self.count.lock() += 1;
+    Ok(())
+  }
+}
+
 impl EventStreamSubscriber for MockSubscriber {
   fn on_event(&mut self, _event: &EventStreamEvent<NoStdToolbox>) {}
 }
@@ -39,3 +65,16 @@ fn event_stream_subscriber_entry_clone() {
   assert_eq!(entry1.id(), entry2.id());
   assert_eq!(entry1.id(), 5);
 }
+
+#[test]
+fn actor_entries_skip_mailbox_metrics() {
+  let sender = ArcShared::new(CountingSender { count: NoStdMutex::new(0) });
+  let actor = ActorRefGeneric::new(Pid::new(2, 0), sender.clone());
+  let entry = EventStreamSubscriberEntry::for_actor(1, subscriber_handle(MockSubscriber), actor);
+
+  entry.deliver(&EventStreamEvent::Mailbox(MailboxMetricsEvent::new(Pid::new(1, 0), 1, 0, None, None, Duration::ZERO)));
+  assert_eq!(*sender.count.lock(), 0);
+
+  entry.deliver(&EventStreamEvent::Log(LogEvent::new(LogLevel::Info, "message".into(), Duration::ZERO, None)));
+  assert_eq!(*sender.count.lock(), 1);
+}
@@ -45,6 +45,13 @@ pub enum RemotingLifecycleEvent {
     /// Correlation identifier assigned to the gating event.
     correlation_id: CorrelationId,
   },
+  /// Transport-level handshake (e.g. TLS) with a peer failed.
+  HandshakeFailed {
+    /// Authority (usually `host:port`) of the peer.
+    authority: String,
+    /// Describes why the handshake failed.
+    reason:    String,
+  },
   /// Remoting is shutting down or already stopped.
   Shutdown,
   /// Remoting encountered a fatal error.
@@ -5,10 +5,16 @@
 mod any_message;
 mod any_message_view;
 mod ask_response;
+mod message_headers;
 pub mod message_invoker;
 mod system_message;
+mod trace_context;
+mod trace_context_error;
 
 pub use any_message::{AnyMessage, AnyMessageGeneric};
 pub use any_message_view::{AnyMessageView, AnyMessageViewGeneric};
 pub use ask_response::{AskResponse, AskResponseGeneric};
+pub use message_headers::MessageHeaders;
 pub use system_message::{FailureClassification, FailureMessageSnapshot, FailurePayload, SystemMessage};
+pub use trace_context::TraceContext;
+pub use trace_context_error::TraceContextError;
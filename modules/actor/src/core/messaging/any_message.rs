@@ -11,12 +11,18 @@ use fraktor_utils_rs::core::{
   sync::ArcShared,
 };
 
-use crate::core::{actor_prim::actor_ref::ActorRefGeneric, messaging::AnyMessageViewGeneric};
+use crate::core::{
+  actor_prim::actor_ref::ActorRefGeneric,
+  dead_letter::{DeadLetterSuppressed, DeadLetterSuppression},
+  messaging::{AnyMessageViewGeneric, MessageHeaders},
+};
 
 /// Wraps an arbitrary payload for message passing.
 pub struct AnyMessageGeneric<TB: RuntimeToolbox> {
-  payload:  ArcShared<dyn Any + Send + Sync + 'static>,
-  reply_to: Option<ActorRefGeneric<TB>>,
+  payload:   ArcShared<dyn Any + Send + Sync + 'static>,
+  reply_to:  Option<ActorRefGeneric<TB>>,
+  headers:   MessageHeaders,
+  type_name: Option<&'static str>,
 }
 
 /// Type alias for [AnyMessageGeneric] with the default [NoStdToolbox].
@@ -28,7 +34,20 @@ impl<TB: RuntimeToolbox> AnyMessageGeneric<TB> {
   pub fn new<T>(payload: T) -> Self
   where
     T: Any + Send + Sync + 'static, {
-    Self { payload: ArcShared::new(payload), reply_to: None }
+    Self {
+      payload:   ArcShared::new(payload),
+      reply_to:  None,
+      headers:   MessageHeaders::new(),
+      type_name: Some(core::any::type_name::<T>()),
+    }
+  }
+
+  /// Creates a message whose deadletters are retained and published but never logged.
+  #[must_use]
+  pub fn new_suppressed<T>(payload: T) -> Self
+  where
+    T: DeadLetterSuppression, {
+    Self::new(payload).with_header(DeadLetterSuppressed)
   }
 
   /// Associates a reply target with this message and returns the updated instance.
@@ -44,10 +63,38 @@ impl<TB: RuntimeToolbox> AnyMessageGeneric<TB> {
     self.reply_to.as_ref()
   }
 
+  /// Attaches a typed header and returns the updated instance.
+  #[must_use]
+  pub fn with_header<T>(mut self, header: T) -> Self
+  where
+    T: Any + Send + Sync + 'static, {
+    self.headers.insert(header);
+    self
+  }
+
+  /// Returns the headers carried by this message.
+  #[must_use]
+  pub const fn headers(&self) -> &MessageHeaders {
+    &self.headers
+  }
+
+  /// Returns the headers carried by this message for modification.
+  pub const fn headers_mut(&mut self) -> &mut MessageHeaders {
+    &mut self.headers
+  }
+
+  /// Returns the payload type name, or `None` when the message was rebuilt from an erased payload.
+  #[must_use]
+  pub const fn type_name(&self) -> Option<&'static str> {
+    self.type_name
+  }
+
   /// Converts the owned message into a borrowed view.
   #[must_use]
   pub fn as_view(&self) -> AnyMessageViewGeneric<'_, TB> {
     AnyMessageViewGeneric::new(&*self.payload, self.reply_to.as_ref())
+      .with_headers(&self.headers)
+      .with_type_name(self.type_name)
   }
 
   /// Reconstructs a message from an erased payload pointer.
@@ -75,7 +122,7 @@ impl<TB: RuntimeToolbox> AnyMessageGeneric<TB> {
     payload: ArcShared<dyn Any + Send + Sync + 'static>,
     reply_to: Option<ActorRefGeneric<TB>>,
   ) -> Self {
-    Self { payload, reply_to }
+    Self { payload, reply_to, headers: MessageHeaders::new(), type_name: None }
   }
 
   /// Consumes the message and returns the payload alongside the reply target.
@@ -88,7 +135,12 @@ impl<TB: RuntimeToolbox> AnyMessageGeneric<TB> {
 
 impl<TB: RuntimeToolbox> Clone for AnyMessageGeneric<TB> {
   fn clone(&self) -> Self {
-    Self { payload: self.payload.clone(), reply_to: self.reply_to.clone() }
+    Self {
+      payload:   self.payload.clone(),
+      reply_to:  self.reply_to.clone(),
+      headers:   self.headers.clone(),
+      type_name: self.type_name,
+    }
   }
 }
 
@@ -97,6 +149,7 @@ impl<TB: RuntimeToolbox> fmt::Debug for AnyMessageGeneric<TB> {
     f.debug_struct("AnyMessage")
       .field("type_id", &self.payload.type_id())
       .field("has_reply_to", &self.reply_to.is_some())
+      .field("headers", &self.headers)
       .finish()
   }
 }
@@ -1,5 +1,8 @@
 use super::*;
-use crate::core::actor_prim::{Pid, actor_ref::ActorRef};
+use crate::core::{
+  actor_prim::{Pid, actor_ref::ActorRef},
+  messaging::TraceContext,
+};
 
 #[test]
 fn stores_payload_and_reply_to() {
@@ -15,3 +18,17 @@ fn stores_payload_and_reply_to() {
   assert!(view.reply_to().is_some());
   assert_eq!(view.reply_to().unwrap().pid(), Pid::new(0, 0));
 }
+
+#[test]
+fn headers_and_type_name_reach_the_view() {
+  let message: AnyMessage = AnyMessage::new(5_u32).with_header(TraceContext::new([1; 16], [2; 8], 1));
+  let cloned = message.clone();
+
+  let view = cloned.as_view();
+  assert_eq!(view.type_name(), Some("u32"));
+  assert_eq!(view.headers().get::<TraceContext>().map(TraceContext::span_id), Some([2; 8]));
+
+  let erased: AnyMessage = AnyMessage::from_erased(message.payload_arc(), None);
+  assert_eq!(erased.type_name(), None);
+  assert!(erased.as_view().headers().is_empty());
+}
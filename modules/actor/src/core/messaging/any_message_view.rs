@@ -7,14 +7,18 @@ use core::any::{Any, TypeId};
 
 use fraktor_utils_rs::core::runtime_toolbox::{NoStdToolbox, RuntimeToolbox};
 
-use crate::core::actor_prim::actor_ref::ActorRefGeneric;
+use crate::core::{actor_prim::actor_ref::ActorRefGeneric, messaging::MessageHeaders};
+
+static NO_HEADERS: MessageHeaders = MessageHeaders::new();
 
 /// Represents a borrowed view of an actor message.
 #[derive(Debug)]
 pub struct AnyMessageViewGeneric<'a, TB: RuntimeToolbox = NoStdToolbox> {
-  payload:  &'a (dyn Any + Send + Sync + 'static),
-  type_id:  TypeId,
-  reply_to: Option<&'a ActorRefGeneric<TB>>,
+  payload:   &'a (dyn Any + Send + Sync + 'static),
+  type_id:   TypeId,
+  reply_to:  Option<&'a ActorRefGeneric<TB>>,
+  headers:   &'a MessageHeaders,
+  type_name: Option<&'static str>,
 }
 
 /// Type alias for [AnyMessageViewGeneric] with the default [NoStdToolbox].
@@ -24,7 +28,21 @@ impl<'a, TB: RuntimeToolbox> AnyMessageViewGeneric<'a, TB> {
   /// Creates a new borrowed message view.
   #[must_use]
   pub fn new(payload: &'a (dyn Any + Send + Sync + 'static), reply_to: Option<&'a ActorRefGeneric<TB>>) -> Self {
-    Self { payload, type_id: (*payload).type_id(), reply_to }
+    Self { payload, type_id: (*payload).type_id(), reply_to, headers: &NO_HEADERS, type_name: None }
+  }
+
+  /// Associates the headers of the owning message with this view.
+  #[must_use]
+  pub const fn with_headers(mut self, headers: &'a MessageHeaders) -> Self {
+    self.headers = headers;
+    self
+  }
+
+  /// Records the payload type name reported by [`type_name`](Self::type_name).
+  #[must_use]
+  pub const fn with_type_name(mut self, type_name: Option<&'static str>) -> Self {
+    self.type_name = type_name;
+    self
   }
 
   /// Returns the [`TypeId`] of the payload.
@@ -44,4 +62,16 @@ impl<'a, TB: RuntimeToolbox> AnyMessageViewGeneric<'a, TB> {
   pub const fn reply_to(&self) -> Option<&'a ActorRefGeneric<TB>> {
     self.reply_to
   }
+
+  /// Returns the headers carried by the message.
+  #[must_use]
+  pub const fn headers(&self) -> &'a MessageHeaders {
+    self.headers
+  }
+
+  /// Returns the payload type name when it is known.
+  #[must_use]
+  pub const fn type_name(&self) -> Option<&'static str> {
+    self.type_name
+  }
 }
@@ -0,0 +1,140 @@
+//! Typed metadata attached to message envelopes.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+use core::{
+  any::{Any, TypeId},
+  fmt,
+};
+
+use fraktor_utils_rs::core::sync::ArcShared;
+
+#[derive(Clone)]
+struct HeaderEntry {
+  type_id:   TypeId,
+  value:     ArcShared<dyn Any + Send + Sync + 'static>,
+  propagate: bool,
+}
+
+/// Small map of typed headers carried alongside a message payload.
+///
+/// Each header is keyed by its Rust type, so a message holds at most one value per type.
+/// Cloning the map only clones the shared header pointers.
+///
+/// Headers inserted with [`insert_propagated`](Self::insert_propagated) are copied onto replies
+/// sent through [`ActorContextGeneric::reply`](crate::core::actor_prim::ActorContextGeneric::reply)
+/// and onto messages produced by
+/// [`ActorContextGeneric::pipe_to_self`](crate::core::actor_prim::ActorContextGeneric::pipe_to_self).
+#[derive(Clone, Default)]
+pub struct MessageHeaders {
+  entries: Vec<HeaderEntry>,
+}
+
+impl MessageHeaders {
+  /// Creates an empty header map.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { entries: Vec::new() }
+  }
+
+  /// Inserts `value`, replacing any header of the same type.
+  pub fn insert<T>(&mut self, value: T)
+  where
+    T: Any + Send + Sync + 'static, {
+    self.insert_erased(ArcShared::new(value), false);
+  }
+
+  /// Inserts `value` and marks it for propagation to replies and piped messages.
+  pub fn insert_propagated<T>(&mut self, value: T)
+  where
+    T: Any + Send + Sync + 'static, {
+    self.insert_erased(ArcShared::new(value), true);
+  }
+
+  /// Inserts an already type-erased header, replacing any header of the same type.
+  pub fn insert_erased(&mut self, value: ArcShared<dyn Any + Send + Sync + 'static>, propagate: bool) {
+    let type_id = Any::type_id(&*value);
+    let entry = HeaderEntry { type_id, value, propagate };
+    match self.entries.iter_mut().find(|existing| existing.type_id == type_id) {
+      | Some(existing) => *existing = entry,
+      | None => self.entries.push(entry),
+    }
+  }
+
+  /// Returns the header of type `T`, if present.
+  #[must_use]
+  pub fn get<T>(&self) -> Option<&T>
+  where
+    T: Any + Send + Sync + 'static, {
+    self.get_erased(TypeId::of::<T>()).and_then(|value| value.downcast_ref::<T>())
+  }
+
+  /// Returns the header whose type is `type_id`, if present.
+  #[must_use]
+  pub fn get_erased(&self, type_id: TypeId) -> Option<&(dyn Any + Send + Sync + 'static)> {
+    self.entry(type_id).map(|entry| &*entry.value)
+  }
+
+  /// Returns `true` when a header of type `T` is present.
+  #[must_use]
+  pub fn contains<T>(&self) -> bool
+  where
+    T: Any + Send + Sync + 'static, {
+    self.entry(TypeId::of::<T>()).is_some()
+  }
+
+  /// Returns `true` when the header whose type is `type_id` is marked for propagation.
+  #[must_use]
+  pub fn is_propagated(&self, type_id: TypeId) -> bool {
+    self.entry(type_id).is_some_and(|entry| entry.propagate)
+  }
+
+  /// Removes the header of type `T` and reports whether it was present.
+  pub fn remove<T>(&mut self) -> bool
+  where
+    T: Any + Send + Sync + 'static, {
+    let type_id = TypeId::of::<T>();
+    let before = self.entries.len();
+    self.entries.retain(|entry| entry.type_id != type_id);
+    self.entries.len() != before
+  }
+
+  /// Copies the propagated headers of `source` that are not already present.
+  pub fn extend_propagated(&mut self, source: &MessageHeaders) {
+    for entry in source.entries.iter().filter(|entry| entry.propagate) {
+      if self.entry(entry.type_id).is_none() {
+        self.entries.push(entry.clone());
+      }
+    }
+  }
+
+  /// Returns a map holding only the propagated headers.
+  #[must_use]
+  pub fn propagated(&self) -> MessageHeaders {
+    Self { entries: self.entries.iter().filter(|entry| entry.propagate).cloned().collect() }
+  }
+
+  /// Returns the number of headers.
+  #[must_use]
+  pub const fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  /// Returns `true` when no header is present.
+  #[must_use]
+  pub const fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  fn entry(&self, type_id: TypeId) -> Option<&HeaderEntry> {
+    self.entries.iter().find(|entry| entry.type_id == type_id)
+  }
+}
+
+impl fmt::Debug for MessageHeaders {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("MessageHeaders").field("len", &self.entries.len()).finish()
+  }
+}
@@ -0,0 +1,59 @@
+use super::MessageHeaders;
+
+#[derive(Debug, PartialEq)]
+struct Tenant(&'static str);
+
+#[test]
+fn insert_replaces_headers_of_the_same_type() {
+  let mut headers = MessageHeaders::new();
+  assert!(headers.is_empty());
+  headers.insert(Tenant("a"));
+  headers.insert(7_u32);
+  headers.insert(Tenant("b"));
+
+  assert_eq!(headers.len(), 2);
+  assert_eq!(headers.get::<Tenant>(), Some(&Tenant("b")));
+  assert_eq!(headers.get::<u32>(), Some(&7));
+  assert!(headers.get::<u64>().is_none());
+}
+
+#[test]
+fn remove_drops_only_the_requested_type() {
+  let mut headers = MessageHeaders::new();
+  headers.insert(Tenant("a"));
+  headers.insert(7_u32);
+
+  assert!(headers.remove::<Tenant>());
+  assert!(!headers.remove::<Tenant>());
+  assert!(!headers.contains::<Tenant>());
+  assert!(headers.contains::<u32>());
+}
+
+#[test]
+fn only_propagated_headers_are_extended() {
+  let mut source = MessageHeaders::new();
+  source.insert_propagated(Tenant("a"));
+  source.insert(7_u32);
+  let mut target = MessageHeaders::new();
+  target.insert_propagated(9_u64);
+
+  target.extend_propagated(&source);
+
+  assert_eq!(target.get::<Tenant>(), Some(&Tenant("a")));
+  assert!(target.is_propagated(core::any::TypeId::of::<Tenant>()));
+  assert!(!target.contains::<u32>());
+  assert_eq!(source.propagated().len(), 1);
+}
+
+#[test]
+fn existing_headers_win_over_propagated_ones() {
+  let mut source = MessageHeaders::new();
+  source.insert_propagated(Tenant("parent"));
+  let mut target = MessageHeaders::new();
+  target.insert(Tenant("own"));
+
+  target.extend_propagated(&source);
+
+  assert_eq!(target.get::<Tenant>(), Some(&Tenant("own")));
+  assert!(!target.is_propagated(core::any::TypeId::of::<Tenant>()));
+}
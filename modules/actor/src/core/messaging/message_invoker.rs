@@ -1,11 +1,19 @@
 //! Message invocation primitives and middleware pipeline.
 
 mod invoker_trait;
+mod logging_middleware;
+mod message_type_allowlist;
 mod middleware;
+mod middleware_registry_error;
+mod middlewares;
 mod pipeline;
 
 pub use invoker_trait::MessageInvoker;
+pub use logging_middleware::LoggingMiddleware;
+pub use message_type_allowlist::MessageTypeAllowlist;
 pub use middleware::MessageInvokerMiddleware;
+pub use middleware_registry_error::MiddlewareRegistryError;
+pub use middlewares::{Middlewares, MiddlewaresGeneric};
 pub use pipeline::{MessageInvokerPipeline, MessageInvokerPipelineGeneric};
 
 #[cfg(test)]
@@ -0,0 +1,67 @@
+//! Middleware logging every received message.
+
+use alloc::format;
+
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
+use super::MessageInvokerMiddleware;
+use crate::core::{
+  actor_prim::ActorContextGeneric, error::ActorError, logging::LogLevel, messaging::AnyMessageViewGeneric,
+};
+
+/// Logs the type of every message an actor receives, and the failures of its handler.
+///
+/// Receipts are logged at the configured level (`Debug` by default) and failures at `Warn`, both
+/// through the event stream so any logger subscriber picks them up.
+#[derive(Clone, Copy, Debug)]
+pub struct LoggingMiddleware {
+  level: LogLevel,
+}
+
+impl LoggingMiddleware {
+  /// Name under which the middleware is registered by default.
+  pub const NAME: &'static str = "logging";
+
+  /// Creates the middleware logging receipts at `Debug`.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { level: LogLevel::Debug }
+  }
+
+  /// Sets the level used for receipts.
+  #[must_use]
+  pub const fn with_level(mut self, level: LogLevel) -> Self {
+    self.level = level;
+    self
+  }
+}
+
+impl Default for LoggingMiddleware {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> MessageInvokerMiddleware<TB> for LoggingMiddleware {
+  fn before_user(
+    &self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    message: &AnyMessageViewGeneric<'_, TB>,
+  ) -> Result<(), ActorError> {
+    ctx.log(self.level, format!("received {}", message.type_name().unwrap_or("unknown")));
+    Ok(())
+  }
+
+  fn after_user(
+    &self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    message: &AnyMessageViewGeneric<'_, TB>,
+    result: Result<(), ActorError>,
+  ) -> Result<(), ActorError> {
+    if let Err(error) = &result {
+      let type_name = message.type_name().unwrap_or("unknown");
+      ctx.log(LogLevel::Warn, format!("failed to handle {type_name}: {:?}", error.reason()));
+    }
+    result
+  }
+}
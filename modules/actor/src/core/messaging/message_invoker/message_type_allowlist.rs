@@ -0,0 +1,62 @@
+//! Middleware restricting the message types an actor accepts.
+
+use alloc::vec::Vec;
+use core::any::{Any, TypeId};
+
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
+use super::MessageInvokerMiddleware;
+use crate::core::{
+  actor_prim::ActorContextGeneric, dead_letter::DeadLetterReason, error::ActorError, messaging::AnyMessageGeneric,
+};
+
+/// Delivers only messages whose payload type has been allowed.
+///
+/// Other messages never reach the actor: they are recorded as deadletters with
+/// [`DeadLetterReason::AccessDenied`] and the actor keeps running. Register an instance under a
+/// name with
+/// [`ActorSystemConfigGeneric::with_named_middleware`](crate::core::system::ActorSystemConfigGeneric::with_named_middleware)
+/// to refer to it from props.
+#[derive(Clone, Debug, Default)]
+pub struct MessageTypeAllowlist {
+  allowed: Vec<TypeId>,
+}
+
+impl MessageTypeAllowlist {
+  /// Creates an allowlist that rejects every message.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { allowed: Vec::new() }
+  }
+
+  /// Allows messages whose payload is a `T`.
+  #[must_use]
+  pub fn allow<T: 'static>(mut self) -> Self {
+    let type_id = TypeId::of::<T>();
+    if !self.allowed.contains(&type_id) {
+      self.allowed.push(type_id);
+    }
+    self
+  }
+
+  /// Returns `true` when payloads of type `type_id` are delivered.
+  #[must_use]
+  pub fn is_allowed(&self, type_id: TypeId) -> bool {
+    self.allowed.contains(&type_id)
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> MessageInvokerMiddleware<TB> for MessageTypeAllowlist {
+  fn around_user<'c>(
+    &self,
+    ctx: &mut ActorContextGeneric<'c, TB>,
+    message: &AnyMessageGeneric<TB>,
+    next: &mut dyn FnMut(&mut ActorContextGeneric<'c, TB>) -> Result<(), ActorError>,
+  ) -> Result<(), ActorError> {
+    if self.is_allowed(Any::type_id(message.payload())) {
+      return next(ctx);
+    }
+    ctx.system().record_dead_letter(message.clone(), DeadLetterReason::AccessDenied, Some(ctx.pid()));
+    Ok(())
+  }
+}
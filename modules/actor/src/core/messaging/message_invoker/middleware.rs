@@ -2,7 +2,11 @@
 
 use fraktor_utils_rs::core::runtime_toolbox::{NoStdToolbox, RuntimeToolbox};
 
-use crate::core::{actor_prim::ActorContextGeneric, error::ActorError, messaging::AnyMessageViewGeneric};
+use crate::core::{
+  actor_prim::ActorContextGeneric,
+  error::ActorError,
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+};
 
 /// Middleware hook executed before and after user message handling.
 pub trait MessageInvokerMiddleware<TB: RuntimeToolbox + 'static = NoStdToolbox>: Send + Sync {
@@ -19,6 +23,23 @@ pub trait MessageInvokerMiddleware<TB: RuntimeToolbox + 'static = NoStdToolbox>:
     Ok(())
   }
 
+  /// Wraps the actor's handler; `next` runs the remaining middleware and then the actor.
+  ///
+  /// Runs after every [`before_user`](Self::before_user) hook succeeded. The first registered
+  /// middleware is the outermost wrapper. Returning without calling `next` skips the actor.
+  ///
+  /// # Errors
+  ///
+  /// Returns the handler's error, or an error raised by the middleware itself.
+  fn around_user<'c>(
+    &self,
+    ctx: &mut ActorContextGeneric<'c, TB>,
+    _message: &AnyMessageGeneric<TB>,
+    next: &mut dyn FnMut(&mut ActorContextGeneric<'c, TB>) -> Result<(), ActorError>,
+  ) -> Result<(), ActorError> {
+    next(ctx)
+  }
+
   /// Called after the actor has processed the message.
   ///
   /// # Errors
@@ -32,4 +53,12 @@ pub trait MessageInvokerMiddleware<TB: RuntimeToolbox + 'static = NoStdToolbox>:
   ) -> Result<(), ActorError> {
     result
   }
+
+  /// Called when a message is sent through an actor reference bound to a system that installed
+  /// this middleware, letting it attach headers before the message is enqueued.
+  ///
+  /// Only system-wide middleware registered through
+  /// [`ActorSystemConfigGeneric::with_message_middleware`](crate::core::system::ActorSystemConfigGeneric::with_message_middleware)
+  /// observes outbound messages.
+  fn before_send(&self, _message: &mut AnyMessageGeneric<TB>) {}
 }
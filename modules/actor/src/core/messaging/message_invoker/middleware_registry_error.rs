@@ -0,0 +1,34 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Error raised when registering or resolving middleware names fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MiddlewareRegistryError {
+  /// Middleware name already exists.
+  Duplicate(String),
+  /// Middleware name was not found.
+  Unknown(String),
+}
+
+impl MiddlewareRegistryError {
+  /// Creates a middleware duplicate error.
+  #[must_use]
+  pub fn duplicate(name: impl Into<String>) -> Self {
+    Self::Duplicate(name.into())
+  }
+
+  /// Creates a middleware unknown error.
+  #[must_use]
+  pub fn unknown(name: impl Into<String>) -> Self {
+    Self::Unknown(name.into())
+  }
+}
+
+impl fmt::Display for MiddlewareRegistryError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Self::Duplicate(name) => write!(f, "middleware '{}' already exists", name),
+      | Self::Unknown(name) => write!(f, "middleware '{}' not found", name),
+    }
+  }
+}
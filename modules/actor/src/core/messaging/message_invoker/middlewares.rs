@@ -0,0 +1,92 @@
+use alloc::{string::String, vec::Vec};
+
+use ahash::RandomState;
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdToolbox, RuntimeToolbox, SyncMutexFamily, ToolboxMutex},
+  sync::{ArcShared, sync_mutex_like::SyncMutexLike},
+};
+use hashbrown::HashMap;
+
+use super::{LoggingMiddleware, MessageInvokerMiddleware, MiddlewareRegistryError};
+
+#[cfg(test)]
+mod tests;
+
+// 名前からミドルウェアへの対応表
+type MiddlewareMap<TB> = HashMap<String, ArcShared<dyn MessageInvokerMiddleware<TB>>, RandomState>;
+
+/// Registry that resolves the middleware names passed to
+/// [`PropsGeneric::with_middleware`](crate::core::props::PropsGeneric::with_middleware).
+///
+/// [`LoggingMiddleware`] is registered as `"logging"` by default.
+pub struct MiddlewaresGeneric<TB: RuntimeToolbox + 'static> {
+  entries: ToolboxMutex<MiddlewareMap<TB>, TB>,
+}
+
+/// Type alias using the default toolbox.
+pub type Middlewares = MiddlewaresGeneric<NoStdToolbox>;
+
+impl<TB: RuntimeToolbox + 'static> MiddlewaresGeneric<TB> {
+  /// Creates an empty middleware registry.
+  #[must_use]
+  pub fn new() -> Self {
+    Self { entries: <TB::MutexFamily as SyncMutexFamily>::create(HashMap::with_hasher(RandomState::new())) }
+  }
+
+  /// Registers middleware under the provided name.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`MiddlewareRegistryError::Duplicate`] when the name already exists.
+  pub fn register(
+    &self,
+    name: impl Into<String>,
+    middleware: ArcShared<dyn MessageInvokerMiddleware<TB>>,
+  ) -> Result<(), MiddlewareRegistryError> {
+    let mut entries = self.entries.lock();
+    let name = name.into();
+    if entries.contains_key(&name) {
+      return Err(MiddlewareRegistryError::duplicate(name));
+    }
+    entries.insert(name, middleware);
+    Ok(())
+  }
+
+  /// Registers or replaces the middleware under the provided name.
+  pub fn register_or_update(&self, name: impl Into<String>, middleware: ArcShared<dyn MessageInvokerMiddleware<TB>>) {
+    self.entries.lock().insert(name.into(), middleware);
+  }
+
+  /// Resolves the middleware registered under the name.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`MiddlewareRegistryError::Unknown`] when the name has not been registered.
+  pub fn resolve(&self, name: &str) -> Result<ArcShared<dyn MessageInvokerMiddleware<TB>>, MiddlewareRegistryError> {
+    self.entries.lock().get(name).cloned().ok_or_else(|| MiddlewareRegistryError::unknown(name))
+  }
+
+  /// Resolves every name in order.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`MiddlewareRegistryError::Unknown`] for the first name that has not been registered.
+  pub fn resolve_all(
+    &self,
+    names: &[String],
+  ) -> Result<Vec<ArcShared<dyn MessageInvokerMiddleware<TB>>>, MiddlewareRegistryError> {
+    names.iter().map(|name| self.resolve(name)).collect()
+  }
+
+  /// Ensures the built-in middleware entries exist.
+  pub fn ensure_defaults(&self) {
+    let mut entries = self.entries.lock();
+    entries.entry(String::from(LoggingMiddleware::NAME)).or_insert_with(|| ArcShared::new(LoggingMiddleware::new()));
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> Default for MiddlewaresGeneric<TB> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,35 @@
+use alloc::string::String;
+
+use super::*;
+
+#[test]
+fn defaults_register_the_logging_middleware() {
+  let registry = MiddlewaresGeneric::<NoStdToolbox>::new();
+  assert!(matches!(registry.resolve(LoggingMiddleware::NAME), Err(MiddlewareRegistryError::Unknown(_))));
+
+  registry.ensure_defaults();
+
+  assert!(registry.resolve(LoggingMiddleware::NAME).is_ok());
+}
+
+#[test]
+fn register_duplicate_middleware_fails() {
+  let registry = MiddlewaresGeneric::<NoStdToolbox>::new();
+  registry.register("dup", ArcShared::new(LoggingMiddleware::new())).expect("first register");
+
+  assert!(matches!(
+    registry.register("dup", ArcShared::new(LoggingMiddleware::new())),
+    Err(MiddlewareRegistryError::Duplicate(_))
+  ));
+  registry.register_or_update("dup", ArcShared::new(LoggingMiddleware::new()));
+}
+
+#[test]
+fn resolve_all_reports_the_first_unknown_name() {
+  let registry = MiddlewaresGeneric::<NoStdToolbox>::new();
+  registry.ensure_defaults();
+  let names = [String::from(LoggingMiddleware::NAME), String::from("missing"), String::from("other")];
+
+  assert_eq!(registry.resolve_all(&names[..1]).map(|resolved| resolved.len()), Ok(1));
+  assert_eq!(registry.resolve_all(&names).err(), Some(MiddlewareRegistryError::unknown("missing")));
+}
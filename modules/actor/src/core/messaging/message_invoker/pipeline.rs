@@ -57,19 +57,24 @@ impl<TB: RuntimeToolbox + 'static> MessageInvokerPipelineGeneric<TB> {
       | None => ctx.clear_reply_to(),
     }
 
+    let previous_headers = ctx.set_headers(message.headers().clone());
     let view = message.as_view();
 
-    if let Err(error) = self.invoke_before(ctx, &view) {
+    if let Err((entered, error)) = self.invoke_before(ctx, &view) {
+      // before_user を通過したミドルウェアには after_user で後始末の機会を与える
+      let result = Self::invoke_after(&self.user_middlewares[..entered], ctx, &view, Err(error));
       restore_reply(ctx, previous);
-      return Err(error);
+      ctx.set_headers(previous_headers);
+      return result;
     }
 
-    let mut result = actor.receive(ctx, view);
+    let mut result = Self::invoke_around(&self.user_middlewares, actor, ctx, &message);
 
     let view_after = message.as_view();
-    result = self.invoke_after(ctx, &view_after, result);
+    result = Self::invoke_after(&self.user_middlewares, ctx, &view_after, result);
 
     restore_reply(ctx, previous);
+    ctx.set_headers(previous_headers);
     result
   }
 
@@ -77,20 +82,36 @@ impl<TB: RuntimeToolbox + 'static> MessageInvokerPipelineGeneric<TB> {
     &self,
     ctx: &mut ActorContextGeneric<'_, TB>,
     message: &AnyMessageViewGeneric<'_, TB>,
-  ) -> Result<(), ActorError> {
-    for middleware in &self.user_middlewares {
-      middleware.before_user(ctx, message)?;
+  ) -> Result<(), (usize, ActorError)> {
+    for (index, middleware) in self.user_middlewares.iter().enumerate() {
+      middleware.before_user(ctx, message).map_err(|error| (index, error))?;
     }
     Ok(())
   }
 
+  fn invoke_around<A>(
+    middlewares: &[ArcShared<dyn MessageInvokerMiddleware<TB>>],
+    actor: &mut A,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    message: &AnyMessageGeneric<TB>,
+  ) -> Result<(), ActorError>
+  where
+    A: Actor<TB>, {
+    match middlewares.split_first() {
+      | None => actor.receive(ctx, message.as_view()),
+      | Some((middleware, rest)) => {
+        middleware.around_user(ctx, message, &mut |ctx| Self::invoke_around(rest, actor, ctx, message))
+      },
+    }
+  }
+
   fn invoke_after(
-    &self,
+    middlewares: &[ArcShared<dyn MessageInvokerMiddleware<TB>>],
     ctx: &mut ActorContextGeneric<'_, TB>,
     message: &AnyMessageViewGeneric<'_, TB>,
     mut result: Result<(), ActorError>,
   ) -> Result<(), ActorError> {
-    for middleware in self.user_middlewares.iter().rev() {
+    for middleware in middlewares.iter().rev() {
       result = middleware.after_user(ctx, message, result);
     }
     result
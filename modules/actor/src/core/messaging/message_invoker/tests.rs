@@ -13,9 +13,16 @@ use crate::core::{
     Actor, ActorContext, ActorContextGeneric, Pid,
     actor_ref::{ActorRef, ActorRefSender},
   },
+  dead_letter::DeadLetterReason,
   error::{ActorError, SendError},
-  messaging::{AnyMessage, AnyMessageViewGeneric},
-  system::ActorSystem,
+  messaging::{
+    AnyMessage, AnyMessageGeneric, AnyMessageViewGeneric,
+    message_invoker::{LoggingMiddleware, MessageTypeAllowlist},
+  },
+  props::Props,
+  spawn::SpawnError,
+  system::{ActorSystem, ActorSystemConfig},
+  testkit::ActorTestKit,
 };
 
 struct RecordingSender;
@@ -120,6 +127,93 @@ impl MessageInvokerMiddleware<NoStdToolbox> for RecordingMiddleware {
   }
 }
 
+struct RejectingMiddleware;
+
+impl MessageInvokerMiddleware<NoStdToolbox> for RejectingMiddleware {
+  fn before_user(
+    &self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    _message: &AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    Err(ActorError::recoverable("rejected"))
+  }
+}
+
+// next の呼び出し前後を記録する
+struct WrappingMiddleware {
+  inner: RecordingMiddleware,
+}
+
+impl MessageInvokerMiddleware<NoStdToolbox> for WrappingMiddleware {
+  fn around_user<'c>(
+    &self,
+    ctx: &mut ActorContextGeneric<'c, NoStdToolbox>,
+    _message: &AnyMessage,
+    next: &mut dyn FnMut(&mut ActorContextGeneric<'c, NoStdToolbox>) -> Result<(), ActorError>,
+  ) -> Result<(), ActorError> {
+    self.inner.record("enter");
+    let result = next(ctx);
+    self.inner.record("exit");
+    result
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tag(u8);
+
+// 送信されるメッセージにタグを付け、受信時にそれを記録する
+struct TaggingMiddleware {
+  seen: ArcShared<NoStdMutex<Vec<Option<Tag>>>>,
+}
+
+impl MessageInvokerMiddleware<NoStdToolbox> for TaggingMiddleware {
+  fn before_user(
+    &self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    message: &AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    if message.downcast_ref::<u32>().is_some() {
+      self.seen.lock().push(message.headers().get::<Tag>().copied());
+    }
+    Ok(())
+  }
+
+  fn before_send(&self, message: &mut AnyMessageGeneric<NoStdToolbox>) {
+    if !message.headers().contains::<Tag>() {
+      message.headers_mut().insert(Tag(7));
+    }
+  }
+}
+
+// ミドルウェアがコンテキストのヘッダへ書いた値をアクターが読めるか確認する
+struct HeaderWritingMiddleware;
+
+impl MessageInvokerMiddleware<NoStdToolbox> for HeaderWritingMiddleware {
+  fn before_user(
+    &self,
+    ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    _message: &AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    ctx.headers_mut().insert(Tag(9));
+    Ok(())
+  }
+}
+
+struct HeaderReadingActor {
+  seen: Vec<(Option<Tag>, Option<Tag>)>,
+}
+
+impl Actor for HeaderReadingActor {
+  fn receive(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    self.seen.push((ctx.headers().get::<Tag>().copied(), message.headers().get::<Tag>().copied()));
+    Ok(())
+  }
+}
+
 #[test]
 fn pipeline_sets_and_clears_reply_to() {
   let system = ActorSystem::new_empty();
@@ -182,3 +276,128 @@ fn middleware_executes_in_expected_order() {
     String::from("a:after"),
   ]);
 }
+
+#[test]
+fn rejected_message_unwinds_entered_middleware() {
+  let system = ActorSystem::new_empty();
+  let mut ctx = ActorContext::new(&system, Pid::new(43, 0));
+  let log = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let mut actor = LoggingActor::new(log.clone());
+
+  let middleware_a: ArcShared<dyn MessageInvokerMiddleware<NoStdToolbox>> =
+    ArcShared::new(RecordingMiddleware::new("a", log.clone()));
+  let rejecting: ArcShared<dyn MessageInvokerMiddleware<NoStdToolbox>> = ArcShared::new(RejectingMiddleware);
+  let middleware_c: ArcShared<dyn MessageInvokerMiddleware<NoStdToolbox>> =
+    ArcShared::new(RecordingMiddleware::new("c", log.clone()));
+  let pipeline = MessageInvokerPipeline::from_middlewares(vec![middleware_a, rejecting, middleware_c]);
+
+  let result = pipeline.invoke_user(&mut actor, &mut ctx, AnyMessage::new(1_u8));
+
+  assert!(result.is_err());
+  assert_eq!(log.lock().clone(), vec![String::from("a:before"), String::from("a:after")]);
+}
+
+#[test]
+fn system_middleware_wraps_actors_and_decorates_outbound_messages() {
+  let seen = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let middleware: ArcShared<dyn MessageInvokerMiddleware<NoStdToolbox>> =
+    ArcShared::new(TaggingMiddleware { seen: seen.clone() });
+  let kit = ActorTestKit::with_config(ActorSystemConfig::default().with_message_middleware(middleware)).expect("kit");
+  let actor = kit.spawn(&Props::from_fn(CaptureActor::new)).expect("spawn");
+
+  actor.tell(AnyMessage::new(1_u32)).expect("tell");
+  actor.tell(AnyMessage::new(2_u32).with_header(Tag(3))).expect("tell");
+
+  assert_eq!(seen.lock().clone(), vec![Some(Tag(7)), Some(Tag(3))]);
+}
+
+#[test]
+fn middleware_can_rewrite_headers_seen_through_the_context() {
+  let system = ActorSystem::new_empty();
+  let mut ctx = ActorContext::new(&system, Pid::new(44, 0));
+  let mut actor = HeaderReadingActor { seen: Vec::new() };
+  let middleware: ArcShared<dyn MessageInvokerMiddleware<NoStdToolbox>> = ArcShared::new(HeaderWritingMiddleware);
+  let pipeline = MessageInvokerPipeline::from_middlewares(vec![middleware]);
+
+  pipeline.invoke_user(&mut actor, &mut ctx, AnyMessage::new(1_u8).with_header(Tag(1))).expect("invoke");
+
+  assert_eq!(actor.seen, vec![(Some(Tag(9)), Some(Tag(1)))]);
+  assert!(ctx.headers().is_empty());
+}
+
+#[test]
+fn around_middleware_wraps_the_actor_in_registration_order() {
+  let system = ActorSystem::new_empty();
+  let mut ctx = ActorContext::new(&system, Pid::new(45, 0));
+  let log = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let mut actor = LoggingActor::new(log.clone());
+
+  let recording: ArcShared<dyn MessageInvokerMiddleware<NoStdToolbox>> =
+    ArcShared::new(RecordingMiddleware::new("a", log.clone()));
+  let outer: ArcShared<dyn MessageInvokerMiddleware<NoStdToolbox>> =
+    ArcShared::new(WrappingMiddleware { inner: RecordingMiddleware::new("w1", log.clone()) });
+  let inner: ArcShared<dyn MessageInvokerMiddleware<NoStdToolbox>> =
+    ArcShared::new(WrappingMiddleware { inner: RecordingMiddleware::new("w2", log.clone()) });
+  let pipeline = MessageInvokerPipeline::from_middlewares(vec![recording, outer, inner]);
+
+  pipeline.invoke_user(&mut actor, &mut ctx, AnyMessage::new(1_u8)).expect("invoke");
+
+  assert_eq!(log.lock().clone(), vec![
+    String::from("a:before"),
+    String::from("w1:enter"),
+    String::from("w2:enter"),
+    String::from("actor"),
+    String::from("w2:exit"),
+    String::from("w1:exit"),
+    String::from("a:after"),
+  ]);
+}
+
+#[test]
+fn allowlist_routes_other_message_types_to_dead_letters() {
+  let system = ActorSystem::new_empty();
+  let pid = Pid::new(46, 0);
+  let mut ctx = ActorContext::new(&system, pid);
+  let mut actor = CaptureActor::new();
+  let allowlist: ArcShared<dyn MessageInvokerMiddleware<NoStdToolbox>> =
+    ArcShared::new(MessageTypeAllowlist::new().allow::<u32>());
+  let pipeline = MessageInvokerPipeline::from_middlewares(vec![allowlist]);
+
+  pipeline.invoke_user(&mut actor, &mut ctx, AnyMessage::new(5_u32)).expect("allowed");
+  pipeline.invoke_user(&mut actor, &mut ctx, AnyMessage::new("denied")).expect("denied");
+
+  assert_eq!(actor.payloads(), vec![5_u32]);
+  let dead_letters = system.dead_letters();
+  assert_eq!(dead_letters.len(), 1);
+  assert_eq!(dead_letters[0].reason(), DeadLetterReason::AccessDenied);
+  assert_eq!(dead_letters[0].recipient(), Some(pid));
+}
+
+#[test]
+fn props_resolve_middleware_names_through_the_system_registry() {
+  let log = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let recording: ArcShared<dyn MessageInvokerMiddleware<NoStdToolbox>> =
+    ArcShared::new(RecordingMiddleware::new("named", log.clone()));
+  let kit =
+    ActorTestKit::with_config(ActorSystemConfig::default().with_named_middleware("recording", recording)).expect("kit");
+  let actor_log = log.clone();
+  let props = Props::from_fn(move || LoggingActor::new(actor_log.clone()))
+    .with_middleware([LoggingMiddleware::NAME, "recording"]);
+  let actor = kit.spawn(&props).expect("spawn");
+
+  actor.tell(AnyMessage::new(1_u8)).expect("tell");
+
+  assert_eq!(log.lock().clone(), vec![
+    String::from("named:before"),
+    String::from("actor"),
+    String::from("named:after")
+  ]);
+}
+
+#[test]
+fn unknown_middleware_names_fail_the_spawn() {
+  let kit = ActorTestKit::new().expect("kit");
+  let props = Props::from_fn(CaptureActor::new).with_middleware(["missing"]);
+
+  assert!(matches!(kit.spawn(&props), Err(SpawnError::InvalidProps(reason)) if reason.contains("missing")));
+}
@@ -0,0 +1,164 @@
+//! W3C trace context carried in message headers.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use super::TraceContextError;
+
+const VERSION: &str = "00";
+const TRACEPARENT_LEN: usize = 55;
+
+/// Trace and span identifiers following the W3C Trace Context recommendation.
+///
+/// The context is attached to messages as a header so that the span created for the receiving
+/// actor can be parented to the sender's span, locally and across remoting.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TraceContext {
+  trace_id:    [u8; 16],
+  span_id:     [u8; 8],
+  flags:       u8,
+  trace_state: Option<String>,
+}
+
+impl TraceContext {
+  /// Flag bit marking the trace as sampled.
+  pub const FLAG_SAMPLED: u8 = 0b1;
+
+  /// Creates a context from explicit identifiers.
+  #[must_use]
+  pub const fn new(trace_id: [u8; 16], span_id: [u8; 8], flags: u8) -> Self {
+    Self { trace_id, span_id, flags, trace_state: None }
+  }
+
+  /// Attaches the vendor-specific `tracestate` value.
+  #[must_use]
+  pub fn with_trace_state(mut self, trace_state: impl Into<String>) -> Self {
+    self.trace_state = Some(trace_state.into());
+    self
+  }
+
+  /// Returns the trace identifier shared by every span of the trace.
+  #[must_use]
+  pub const fn trace_id(&self) -> [u8; 16] {
+    self.trace_id
+  }
+
+  /// Returns the identifier of the span this context points at.
+  #[must_use]
+  pub const fn span_id(&self) -> [u8; 8] {
+    self.span_id
+  }
+
+  /// Returns the trace flags.
+  #[must_use]
+  pub const fn flags(&self) -> u8 {
+    self.flags
+  }
+
+  /// Returns `true` when the sampled flag is set.
+  #[must_use]
+  pub const fn is_sampled(&self) -> bool {
+    self.flags & Self::FLAG_SAMPLED != 0
+  }
+
+  /// Returns the vendor-specific `tracestate` value, if any.
+  #[must_use]
+  pub fn trace_state(&self) -> Option<&str> {
+    self.trace_state.as_deref()
+  }
+
+  /// Derives the context of a child span within the same trace.
+  #[must_use]
+  pub fn child(&self, span_id: [u8; 8]) -> Self {
+    Self { trace_id: self.trace_id, span_id, flags: self.flags, trace_state: self.trace_state.clone() }
+  }
+
+  /// Returns the trace identifier as lowercase hex.
+  #[must_use]
+  pub fn trace_id_hex(&self) -> String {
+    to_hex(&self.trace_id)
+  }
+
+  /// Returns the span identifier as lowercase hex.
+  #[must_use]
+  pub fn span_id_hex(&self) -> String {
+    to_hex(&self.span_id)
+  }
+
+  /// Renders the context as a `traceparent` header value.
+  #[must_use]
+  pub fn to_traceparent(&self) -> String {
+    let mut value = String::with_capacity(TRACEPARENT_LEN);
+    let _ = write!(value, "{VERSION}-{}-{}-{:02x}", self.trace_id_hex(), self.span_id_hex(), self.flags);
+    value
+  }
+
+  /// Parses a `traceparent` header value.
+  ///
+  /// Versions newer than `00` are accepted as long as they start with the version `00` fields.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TraceContextError`] when the value is malformed, uses the reserved version or
+  /// carries all-zero identifiers.
+  pub fn parse_traceparent(value: &str) -> Result<Self, TraceContextError> {
+    let value = value.trim();
+    let bytes = value.as_bytes();
+    if bytes.len() < TRACEPARENT_LEN || bytes[2] != b'-' || bytes[35] != b'-' || bytes[52] != b'-' {
+      return Err(TraceContextError::InvalidFormat);
+    }
+    let version = parse_hex::<1>(&value[0..2])?[0];
+    if version == 0xff {
+      return Err(TraceContextError::UnsupportedVersion);
+    }
+    let exact = bytes.len() == TRACEPARENT_LEN;
+    if !exact && (version == 0 || bytes[TRACEPARENT_LEN] != b'-') {
+      return Err(TraceContextError::InvalidFormat);
+    }
+    let trace_id = parse_hex::<16>(&value[3..35])?;
+    let span_id = parse_hex::<8>(&value[36..52])?;
+    let flags = parse_hex::<1>(&value[53..55])?[0];
+    if trace_id == [0; 16] || span_id == [0; 8] {
+      return Err(TraceContextError::ZeroId);
+    }
+    Ok(Self::new(trace_id, span_id, flags))
+  }
+}
+
+impl fmt::Display for TraceContext {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.to_traceparent())
+  }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+  let mut value = String::with_capacity(bytes.len() * 2);
+  for byte in bytes {
+    let _ = write!(value, "{byte:02x}");
+  }
+  value
+}
+
+fn parse_hex<const N: usize>(text: &str) -> Result<[u8; N], TraceContextError> {
+  let digits = text.as_bytes();
+  if digits.len() != N * 2 {
+    return Err(TraceContextError::InvalidFormat);
+  }
+  let mut out = [0_u8; N];
+  for (slot, pair) in out.iter_mut().zip(digits.chunks_exact(2)) {
+    *slot = (hex_digit(pair[0])? << 4) | hex_digit(pair[1])?;
+  }
+  Ok(out)
+}
+
+const fn hex_digit(digit: u8) -> Result<u8, TraceContextError> {
+  // W3C の仕様に従い、大文字の16進数は受け付けない
+  match digit {
+    | b'0'..=b'9' => Ok(digit - b'0'),
+    | b'a'..=b'f' => Ok(digit - b'a' + 10),
+    | _ => Err(TraceContextError::InvalidFormat),
+  }
+}
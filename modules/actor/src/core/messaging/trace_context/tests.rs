@@ -0,0 +1,48 @@
+use super::TraceContext;
+use crate::core::messaging::TraceContextError;
+
+const SAMPLE: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+#[test]
+fn parses_and_renders_traceparent() {
+  let context = TraceContext::parse_traceparent(SAMPLE).expect("parse");
+  assert_eq!(context.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+  assert_eq!(context.span_id_hex(), "00f067aa0ba902b7");
+  assert!(context.is_sampled());
+  assert_eq!(context.to_traceparent(), SAMPLE);
+}
+
+#[test]
+fn child_keeps_trace_id_and_state() {
+  let parent = TraceContext::new([1; 16], [2; 8], 0).with_trace_state("vendor=1");
+  let child = parent.child([3; 8]);
+  assert_eq!(child.trace_id(), parent.trace_id());
+  assert_eq!(child.span_id(), [3; 8]);
+  assert!(!child.is_sampled());
+  assert_eq!(child.trace_state(), Some("vendor=1"));
+}
+
+#[test]
+fn rejects_invalid_headers() {
+  assert_eq!(TraceContext::parse_traceparent("00-abc"), Err(TraceContextError::InvalidFormat));
+  assert_eq!(
+    TraceContext::parse_traceparent("00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01"),
+    Err(TraceContextError::InvalidFormat)
+  );
+  assert_eq!(
+    TraceContext::parse_traceparent("ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+    Err(TraceContextError::UnsupportedVersion)
+  );
+  assert_eq!(
+    TraceContext::parse_traceparent("00-00000000000000000000000000000000-00f067aa0ba902b7-01"),
+    Err(TraceContextError::ZeroId)
+  );
+  assert_eq!(TraceContext::parse_traceparent(&alloc::format!("{SAMPLE}-extra")), Err(TraceContextError::InvalidFormat));
+}
+
+#[test]
+fn accepts_future_versions_with_extra_fields() {
+  let header = "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra";
+  let context = TraceContext::parse_traceparent(header).expect("parse");
+  assert_eq!(context.to_traceparent(), SAMPLE);
+}
@@ -0,0 +1,22 @@
+//! Errors raised while parsing W3C trace context headers.
+
+/// Reasons a `traceparent` header could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceContextError {
+  /// The header does not follow the `version-trace_id-parent_id-flags` layout.
+  InvalidFormat,
+  /// The header uses the reserved `ff` version.
+  UnsupportedVersion,
+  /// The trace id or parent id consists of zeros only.
+  ZeroId,
+}
+
+impl core::fmt::Display for TraceContextError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      | Self::InvalidFormat => write!(f, "traceparent header is malformed"),
+      | Self::UnsupportedVersion => write!(f, "traceparent version is not supported"),
+      | Self::ZeroId => write!(f, "traceparent ids must not be all zeros"),
+    }
+  }
+}
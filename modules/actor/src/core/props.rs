@@ -4,6 +4,8 @@
 
 /// Props structure module.
 mod base;
+/// Deployment descriptor module.
+mod deploy;
 /// Actor factory module.
 mod factory;
 /// Mailbox configuration module.
@@ -13,6 +15,7 @@ mod mailbox_requirement;
 mod supervisor_options;
 
 pub use base::{Props, PropsGeneric};
+pub use deploy::Deploy;
 pub use factory::ActorFactory;
 pub use mailbox_config::MailboxConfig;
 pub use mailbox_requirement::MailboxRequirement;
@@ -6,19 +6,27 @@ use fraktor_utils_rs::core::{
   sync::ArcShared,
 };
 
-use super::{factory::ActorFactory, mailbox_config::MailboxConfig, mailbox_requirement::MailboxRequirement};
-use crate::core::{actor_prim::Actor, dispatcher::DispatcherConfigGeneric, mailbox::MailboxPolicy};
+use super::{
+  deploy::Deploy, factory::ActorFactory, mailbox_config::MailboxConfig, mailbox_requirement::MailboxRequirement,
+};
+use crate::core::{
+  actor_prim::Actor, dispatcher::DispatcherConfigGeneric, mailbox::MailboxPolicy,
+  messaging::message_invoker::MessageInvokerMiddleware,
+};
 
 /// Immutable configuration describing how to construct an actor.
 pub struct PropsGeneric<TB: RuntimeToolbox + 'static> {
-  factory:           ArcShared<ToolboxMutex<Box<dyn ActorFactory<TB>>, TB>>,
-  name:              Option<String>,
-  mailbox:           MailboxConfig,
-  mailbox_id:        Option<String>,
-  middleware:        Vec<String>,
-  dispatcher:        DispatcherConfigGeneric<TB>,
-  dispatcher_id:     Option<String>,
-  dispatcher_custom: bool,
+  factory:             ArcShared<ToolboxMutex<Box<dyn ActorFactory<TB>>, TB>>,
+  actor_type:          Option<&'static str>,
+  name:                Option<String>,
+  mailbox:             MailboxConfig,
+  mailbox_id:          Option<String>,
+  middleware:          Vec<String>,
+  resolved_middleware: Vec<ArcShared<dyn MessageInvokerMiddleware<TB>>>,
+  dispatcher:          DispatcherConfigGeneric<TB>,
+  dispatcher_id:       Option<String>,
+  dispatcher_custom:   bool,
+  deploy:              Option<Deploy>,
 }
 
 /// Type alias for [PropsGeneric] with the default [NoStdToolbox].
@@ -31,14 +39,17 @@ impl<TB: RuntimeToolbox + 'static> PropsGeneric<TB> {
     let factory_mutex: ToolboxMutex<Box<dyn ActorFactory<TB>>, TB> =
       <TB::MutexFamily as SyncMutexFamily>::create(factory);
     Self {
-      factory:           ArcShared::new(factory_mutex),
-      name:              None,
-      mailbox:           MailboxConfig::default(),
-      mailbox_id:        None,
-      middleware:        Vec::new(),
-      dispatcher:        DispatcherConfigGeneric::default(),
-      dispatcher_id:     None,
-      dispatcher_custom: false,
+      factory:             ArcShared::new(factory_mutex),
+      actor_type:          None,
+      name:                None,
+      mailbox:             MailboxConfig::default(),
+      mailbox_id:          None,
+      middleware:          Vec::new(),
+      resolved_middleware: Vec::new(),
+      dispatcher:          DispatcherConfigGeneric::default(),
+      dispatcher_id:       None,
+      dispatcher_custom:   false,
+      deploy:              None,
     }
   }
 
@@ -48,7 +59,9 @@ impl<TB: RuntimeToolbox + 'static> PropsGeneric<TB> {
   where
     F: FnMut() -> A + Send + Sync + 'static,
     A: Actor<TB> + Sync + 'static, {
-    Self::new(Box::new(factory))
+    let mut props = Self::new(Box::new(factory));
+    props.actor_type = Some(core::any::type_name::<A>());
+    props
   }
 
   /// Returns the actor factory.
@@ -57,6 +70,14 @@ impl<TB: RuntimeToolbox + 'static> PropsGeneric<TB> {
     &self.factory
   }
 
+  /// Returns the Rust type name of the actor built by the factory, when it is known.
+  ///
+  /// Only props created through [`from_fn`](Self::from_fn) know the actor type.
+  #[must_use]
+  pub const fn actor_type(&self) -> Option<&'static str> {
+    self.actor_type
+  }
+
   /// Returns the configured actor name, if any.
   #[must_use]
   pub fn name(&self) -> Option<&str> {
@@ -110,6 +131,12 @@ impl<TB: RuntimeToolbox + 'static> PropsGeneric<TB> {
     self.dispatcher_id.as_deref()
   }
 
+  /// Returns the deployment descriptor, if the actor should run on another node.
+  #[must_use]
+  pub const fn deploy(&self) -> Option<&Deploy> {
+    self.deploy.as_ref()
+  }
+
   /// Updates the mailbox configuration.
   #[must_use]
   pub fn with_mailbox(mut self, config: MailboxConfig) -> Self {
@@ -119,6 +146,12 @@ impl<TB: RuntimeToolbox + 'static> PropsGeneric<TB> {
   }
 
   /// Registers middleware identifiers used when constructing the message pipeline.
+  ///
+  /// Names are resolved through the system's
+  /// [`MiddlewaresGeneric`](crate::core::messaging::message_invoker::MiddlewaresGeneric) registry
+  /// at spawn time, and run in order after the system-wide middleware. Spawning fails with
+  /// [`SpawnError::InvalidProps`](crate::core::spawn::SpawnError::InvalidProps) when a name is
+  /// unknown.
   #[must_use]
   pub fn with_middleware<I, S>(mut self, middleware: I) -> Self
   where
@@ -135,6 +168,13 @@ impl<TB: RuntimeToolbox + 'static> PropsGeneric<TB> {
     self
   }
 
+  /// Requests that the actor be deployed according to the provided descriptor.
+  #[must_use]
+  pub fn with_deploy(mut self, deploy: Deploy) -> Self {
+    self.deploy = Some(deploy);
+    self
+  }
+
   /// Overrides the dispatcher configuration used when constructing actors.
   #[must_use]
   pub fn with_dispatcher(mut self, dispatcher: DispatcherConfigGeneric<TB>) -> Self {
@@ -174,31 +214,45 @@ impl<TB: RuntimeToolbox + 'static> PropsGeneric<TB> {
     self
   }
 
-  pub(crate) fn with_resolved_dispatcher(mut self, dispatcher: DispatcherConfigGeneric<TB>) -> Self {
+  pub(crate) fn with_resolved_dispatcher(mut self, id: &str, dispatcher: DispatcherConfigGeneric<TB>) -> Self {
     self.dispatcher = dispatcher;
-    self.dispatcher_id = None;
+    self.dispatcher_id = Some(String::from(id));
     self.dispatcher_custom = true;
     self
   }
 
-  pub(crate) fn with_resolved_mailbox(mut self, mailbox: MailboxConfig) -> Self {
+  pub(crate) const fn with_resolved_mailbox(mut self, mailbox: MailboxConfig) -> Self {
     self.mailbox = mailbox;
-    self.mailbox_id = None;
     self
   }
+
+  pub(crate) fn with_resolved_middleware(
+    mut self,
+    middleware: Vec<ArcShared<dyn MessageInvokerMiddleware<TB>>>,
+  ) -> Self {
+    self.resolved_middleware = middleware;
+    self
+  }
+
+  pub(crate) fn resolved_middleware(&self) -> &[ArcShared<dyn MessageInvokerMiddleware<TB>>] {
+    &self.resolved_middleware
+  }
 }
 
 impl<TB: RuntimeToolbox + 'static> Clone for PropsGeneric<TB> {
   fn clone(&self) -> Self {
     Self {
-      factory:           self.factory.clone(),
-      name:              self.name.clone(),
-      mailbox:           self.mailbox,
-      mailbox_id:        self.mailbox_id.clone(),
-      middleware:        self.middleware.clone(),
-      dispatcher:        self.dispatcher.clone(),
-      dispatcher_id:     self.dispatcher_id.clone(),
-      dispatcher_custom: self.dispatcher_custom,
+      factory:             self.factory.clone(),
+      actor_type:          self.actor_type,
+      name:                self.name.clone(),
+      mailbox:             self.mailbox,
+      mailbox_id:          self.mailbox_id.clone(),
+      middleware:          self.middleware.clone(),
+      resolved_middleware: self.resolved_middleware.clone(),
+      dispatcher:          self.dispatcher.clone(),
+      dispatcher_id:       self.dispatcher_id.clone(),
+      dispatcher_custom:   self.dispatcher_custom,
+      deploy:              self.deploy.clone(),
     }
   }
 }
@@ -0,0 +1,48 @@
+//! Deployment descriptor attached to props.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{string::String, vec::Vec};
+
+use crate::core::actor_prim::actor_path::ActorPathParts;
+
+/// Describes where and how an actor should be deployed.
+///
+/// A remote deployment does not ship the actor factory itself. Instead, the target node looks up
+/// `factory_id` in its whitelist of deployable factories and rebuilds the props from `args`. The
+/// child is spawned under the target node's `/system/remote` deployment daemon.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deploy {
+  target:     ActorPathParts,
+  factory_id: String,
+  args:       Vec<u8>,
+}
+
+impl Deploy {
+  /// Creates a deployment onto the node identified by `target`.
+  #[must_use]
+  pub fn remote(target: ActorPathParts, factory_id: impl Into<String>, args: Vec<u8>) -> Self {
+    Self { target, factory_id: factory_id.into(), args }
+  }
+
+  /// Returns the address of the node hosting the actor.
+  #[must_use]
+  pub const fn target(&self) -> &ActorPathParts {
+    &self.target
+  }
+
+  /// Returns the identifier of the props factory registered on the target node.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // String の Deref が const でないため const fn にできない
+  pub fn factory_id(&self) -> &str {
+    &self.factory_id
+  }
+
+  /// Returns the serialized factory arguments.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // Vec の Deref が const でないため const fn にできない
+  pub fn args(&self) -> &[u8] {
+    &self.args
+  }
+}
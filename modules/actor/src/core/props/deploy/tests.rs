@@ -0,0 +1,13 @@
+use alloc::vec;
+
+use super::Deploy;
+use crate::core::actor_prim::actor_path::ActorPathParts;
+
+#[test]
+fn deploy_remote_exposes_components() {
+  let target = ActorPathParts::with_authority("worker-system", Some(("10.0.0.5", 2552)));
+  let deploy = Deploy::remote(target.clone(), "batch-worker", vec![1, 2, 3]);
+  assert_eq!(deploy.target(), &target);
+  assert_eq!(deploy.factory_id(), "batch-worker");
+  assert_eq!(deploy.args(), &[1, 2, 3]);
+}
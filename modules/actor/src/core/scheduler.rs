@@ -16,6 +16,7 @@ mod dump;
 mod dump_job;
 mod error;
 mod execution_batch;
+mod execution_trace;
 mod fixed_delay_context;
 mod fixed_delay_policy;
 mod fixed_rate_context;
@@ -60,6 +61,7 @@ pub use dump::SchedulerDump;
 pub use dump_job::SchedulerDumpJob;
 pub use error::SchedulerError;
 pub use execution_batch::ExecutionBatch;
+pub use execution_trace::{ExecutionTrace, ExecutionTraceGeneric};
 pub use fixed_delay_policy::FixedDelayPolicy;
 pub use fixed_rate_policy::FixedRatePolicy;
 pub use handle::SchedulerHandle;
@@ -81,8 +83,6 @@ pub use task_run_handle::TaskRunHandle;
 pub use task_run_on_close::TaskRunOnClose;
 pub use task_run_priority::TaskRunPriority;
 pub use task_run_summary::TaskRunSummary;
-#[cfg(any(test, feature = "test-support"))]
-pub use tick_driver::ManualTestDriver;
 pub use tick_driver::{
   AutoDriverMetadata, AutoProfileKind, HardwareKind, HardwareTickDriver, SchedulerTickExecutor,
   SchedulerTickHandleOwned, SchedulerTickMetrics, SchedulerTickMetricsProbe, TICK_DRIVER_MATRIX, TickDriver,
@@ -91,4 +91,6 @@ pub use tick_driver::{
   TickDriverRuntime, TickExecutorSignal, TickFeed, TickFeedHandle, TickMetricsMode, TickPulseHandler, TickPulseSource,
   next_tick_driver_id,
 };
+#[cfg(any(test, feature = "test-support"))]
+pub use tick_driver::{ManualTestDriver, ManualTickController};
 pub use warning::SchedulerWarning;
@@ -1,6 +1,24 @@
-use crate::core::scheduler::ExecutionBatch;
+use alloc::vec::Vec;
+use core::{fmt, num::NonZeroU32, str::FromStr};
 
-/// Kinds of deterministic log entries emitted by the scheduler.
+use crate::core::{
+  actor_prim::Pid,
+  scheduler::{BatchMode, ExecutionBatch},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Kinds of deterministic log entries emitted by the scheduler and, within an execution trace,
+/// by the dispatchers.
+///
+/// Events render as single text lines so that logs captured in CI can be stored and parsed back
+/// with [`parse`](Self::parse):
+///
+/// - `scheduled <handle> <scheduled tick> <deadline tick>`
+/// - `fired <handle> <tick> <runs> <missed runs> <oneshot|fixed-rate|fixed-delay>`
+/// - `cancelled <handle> <tick>`
+/// - `run <pid> <generation>`
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DeterministicEvent {
   /// Timer registration event.
@@ -28,4 +46,70 @@ pub enum DeterministicEvent {
     /// Tick when the cancellation occurred.
     cancelled_tick: u64,
   },
+  /// A dispatcher started draining the mailbox of an actor.
+  MailboxRun {
+    /// Actor owning the mailbox.
+    pid: Pid,
+  },
+}
+
+impl DeterministicEvent {
+  /// Parses an event from its text form, returning `None` when the line is malformed.
+  #[must_use]
+  pub fn parse(line: &str) -> Option<Self> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    match fields.as_slice() {
+      | ["scheduled", handle_id, scheduled_tick, deadline_tick] => Some(Self::Scheduled {
+        handle_id:      number(handle_id)?,
+        scheduled_tick: number(scheduled_tick)?,
+        deadline_tick:  number(deadline_tick)?,
+      }),
+      | ["fired", handle_id, fired_tick, runs, missed_runs, mode] => Some(Self::Fired {
+        handle_id:  number(handle_id)?,
+        fired_tick: number(fired_tick)?,
+        batch:      ExecutionBatch::new(number::<NonZeroU32>(runs)?, number(missed_runs)?, parse_mode(mode)?),
+      }),
+      | ["cancelled", handle_id, cancelled_tick] => {
+        Some(Self::Cancelled { handle_id: number(handle_id)?, cancelled_tick: number(cancelled_tick)? })
+      },
+      | ["run", value, generation] => Some(Self::MailboxRun { pid: Pid::new(number(value)?, number(generation)?) }),
+      | _ => None,
+    }
+  }
+}
+
+impl fmt::Display for DeterministicEvent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Self::Scheduled { handle_id, scheduled_tick, deadline_tick } => {
+        write!(f, "scheduled {handle_id} {scheduled_tick} {deadline_tick}")
+      },
+      | Self::Fired { handle_id, fired_tick, batch } => {
+        write!(f, "fired {handle_id} {fired_tick} {} {} {}", batch.runs(), batch.missed_runs(), mode_name(batch.mode()))
+      },
+      | Self::Cancelled { handle_id, cancelled_tick } => write!(f, "cancelled {handle_id} {cancelled_tick}"),
+      | Self::MailboxRun { pid } => write!(f, "run {} {}", pid.value(), pid.generation()),
+    }
+  }
+}
+
+fn number<T: FromStr>(field: &str) -> Option<T> {
+  field.parse().ok()
+}
+
+const fn mode_name(mode: BatchMode) -> &'static str {
+  match mode {
+    | BatchMode::OneShot => "oneshot",
+    | BatchMode::FixedRate => "fixed-rate",
+    | BatchMode::FixedDelay => "fixed-delay",
+  }
+}
+
+fn parse_mode(name: &str) -> Option<BatchMode> {
+  match name {
+    | "oneshot" => Some(BatchMode::OneShot),
+    | "fixed-rate" => Some(BatchMode::FixedRate),
+    | "fixed-delay" => Some(BatchMode::FixedDelay),
+    | _ => None,
+  }
 }
@@ -0,0 +1,39 @@
+use alloc::string::ToString;
+use core::num::NonZeroU32;
+
+use super::DeterministicEvent;
+use crate::core::{
+  actor_prim::Pid,
+  scheduler::{BatchMode, ExecutionBatch},
+};
+
+#[test]
+fn events_round_trip_through_their_text_form() {
+  let runs = NonZeroU32::new(2).expect("non-zero");
+  let events = [
+    DeterministicEvent::Scheduled { handle_id: 7, scheduled_tick: 40, deadline_tick: 42 },
+    DeterministicEvent::Fired { handle_id: 7, fired_tick: 42, batch: ExecutionBatch::oneshot() },
+    DeterministicEvent::Fired {
+      handle_id:  8,
+      fired_tick: 50,
+      batch:      ExecutionBatch::periodic(runs, 1, BatchMode::FixedRate),
+    },
+    DeterministicEvent::Cancelled { handle_id: 8, cancelled_tick: 51 },
+    DeterministicEvent::MailboxRun { pid: Pid::new(3, 1) },
+  ];
+
+  for event in events {
+    assert_eq!(DeterministicEvent::parse(&event.to_string()), Some(event));
+  }
+}
+
+#[test]
+fn malformed_lines_are_rejected() {
+  assert_eq!(DeterministicEvent::parse(""), None);
+  assert_eq!(DeterministicEvent::parse("run 3"), None);
+  assert_eq!(DeterministicEvent::parse("run 3 1 9"), None);
+  assert_eq!(DeterministicEvent::parse("cancelled x 1"), None);
+  assert_eq!(DeterministicEvent::parse("fired 1 2 0 0 oneshot"), None);
+  assert_eq!(DeterministicEvent::parse("fired 1 2 1 0 hourly"), None);
+  assert_eq!(DeterministicEvent::parse("spawn 1 2"), None);
+}
@@ -12,6 +12,10 @@ impl DeterministicLog {
     Self { entries: Vec::with_capacity(capacity), capacity }
   }
 
+  pub(crate) const fn unbounded() -> Self {
+    Self { entries: Vec::new(), capacity: usize::MAX }
+  }
+
   pub(crate) fn record(&mut self, event: DeterministicEvent) {
     if self.entries.len() < self.capacity {
       self.entries.push(event);
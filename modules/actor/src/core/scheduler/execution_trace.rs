@@ -0,0 +1,232 @@
+use alloc::{collections::VecDeque, vec::Vec};
+
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdToolbox, RuntimeToolbox, SyncMutexFamily, ToolboxMutex},
+  sync::{ArcShared, sync_mutex_like::SyncMutexLike},
+};
+
+use super::{DeterministicEvent, deterministic_log::DeterministicLog};
+use crate::core::{actor_prim::Pid, dispatcher::DispatchSharedGeneric};
+
+#[cfg(test)]
+mod tests;
+
+/// Consecutive pumps without progress after which a replay waiting for a mailbox run gives up.
+const MAX_STALLED_PUMPS: usize = 3;
+
+/// Shared [`DeterministicEvent`] log of the scheduler and all dispatchers.
+///
+/// A trace attached through
+/// [`ActorSystemConfigGeneric::with_execution_trace`](crate::core::system::ActorSystemConfigGeneric::with_execution_trace)
+/// receives every deterministic event of the scheduler and, as
+/// [`DeterministicEvent::MailboxRun`], every time a dispatcher starts draining a mailbox. The
+/// events use the format of the scheduler's deterministic log, so a captured trace and
+/// [`SchedulerDiagnostics::replay`](crate::core::scheduler::SchedulerDiagnostics::replay) feed
+/// [`replaying`](Self::replaying) alike.
+///
+/// A [`recording`](Self::recording) trace only collects the events. A
+/// [`replaying`](Self::replaying) trace additionally compares them with a previously captured
+/// run and, combined with
+/// [`ReplayExecutorGeneric`](crate::core::dispatcher::ReplayExecutorGeneric), holds back mailbox
+/// runs until the captured interleaving allows them. Every dispatcher of the system must use
+/// that executor: a mailbox run the trace did not release is reported as a
+/// [`divergence`](Self::divergence).
+///
+/// Replay is best effort: once an observed event differs from the expected one the trace
+/// reports the divergence and releases held mailboxes in FIFO order. The same happens when the
+/// expected mailbox run cannot happen, e.g. because the message that would schedule it sits in
+/// a held mailbox: after a few consecutive [`pump`](Self::pump)s that neither release nor hold
+/// a mailbox, the trace reports the divergence instead of waiting forever.
+pub struct ExecutionTraceGeneric<TB: RuntimeToolbox + 'static> {
+  state: ArcShared<ToolboxMutex<TraceState<TB>, TB>>,
+}
+
+/// Type alias for [`ExecutionTraceGeneric`] with the default [`NoStdToolbox`].
+pub type ExecutionTrace = ExecutionTraceGeneric<NoStdToolbox>;
+
+struct TraceState<TB: RuntimeToolbox + 'static> {
+  expected:   Option<DeterministicLog>,
+  cursor:     usize,
+  recorded:   DeterministicLog,
+  divergence: Option<usize>,
+  held:       VecDeque<DispatchSharedGeneric<TB>>,
+  pumping:    bool,
+  releasing:  Option<Pid>,
+  // 直前の pump 終了時の保留数と、進展のないまま終わった連続 pump 回数
+  last_held:  usize,
+  stalled:    usize,
+}
+
+impl<TB: RuntimeToolbox + 'static> TraceState<TB> {
+  fn record(&mut self, event: DeterministicEvent) {
+    if let Some(expected) = &self.expected
+      && self.divergence.is_none()
+    {
+      if expected.entries().get(self.cursor) == Some(&event) {
+        self.cursor += 1;
+      } else {
+        self.divergence = Some(self.recorded.entries().len());
+      }
+    }
+    self.recorded.record(event);
+  }
+
+  /// Picks the next held task to run; `released` tells whether this pump already ran one.
+  fn next_release(&mut self, released: bool) -> Option<DispatchSharedGeneric<TB>> {
+    let mut index = match &self.expected {
+      | Some(expected) if self.divergence.is_none() && self.cursor < expected.entries().len() => {
+        // pid を持たないメールボックスはトレースに現れないため順序を強制しない
+        self.held.iter().position(|task| task.pid().is_none()).or_else(|| match expected.entries()[self.cursor] {
+          | DeterministicEvent::MailboxRun { pid } => self.held.iter().position(|task| task.pid() == Some(pid)),
+          // タイマー関連のイベントはスケジューラが進めるまで待つ
+          | DeterministicEvent::Scheduled { .. }
+          | DeterministicEvent::Fired { .. }
+          | DeterministicEvent::Cancelled { .. } => None,
+        })
+      },
+      | _ => (!self.held.is_empty()).then_some(0),
+    };
+    if index.is_none() && self.waits_for_mailbox_run() {
+      // 解放も新たな保留もない pump が続く場合、期待した実行は起こり得ないとみなして FIFO に戻す
+      self.stalled = if released || self.held.len() != self.last_held { 0 } else { self.stalled + 1 };
+      if self.stalled >= MAX_STALLED_PUMPS {
+        self.divergence = Some(self.recorded.entries().len());
+        index = Some(0);
+      }
+    }
+    let task = index.and_then(|index| self.held.remove(index));
+    self.releasing = task.as_ref().and_then(DispatchSharedGeneric::pid);
+    if task.is_none() {
+      self.pumping = false;
+      self.last_held = self.held.len();
+    }
+    task
+  }
+
+  fn waits_for_mailbox_run(&self) -> bool {
+    !self.held.is_empty()
+      && self.divergence.is_none()
+      && self.expected.as_ref().is_some_and(|expected| {
+        matches!(expected.entries().get(self.cursor), Some(DeterministicEvent::MailboxRun { .. }))
+      })
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> Clone for ExecutionTraceGeneric<TB> {
+  fn clone(&self) -> Self {
+    Self { state: self.state.clone() }
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> ExecutionTraceGeneric<TB> {
+  /// Creates a trace that records the events of the current run.
+  #[must_use]
+  pub fn recording() -> Self {
+    Self::with_expected(None)
+  }
+
+  /// Creates a trace that forces the interleaving captured in `expected`, e.g. the
+  /// [`events`](Self::events) of a recording trace or a
+  /// [`DeterministicReplay`](crate::core::scheduler::DeterministicReplay).
+  #[must_use]
+  pub fn replaying(expected: impl IntoIterator<Item = DeterministicEvent>) -> Self {
+    let mut log = DeterministicLog::unbounded();
+    for event in expected {
+      log.record(event);
+    }
+    Self::with_expected(Some(log))
+  }
+
+  fn with_expected(expected: Option<DeterministicLog>) -> Self {
+    let state = TraceState {
+      expected,
+      cursor: 0,
+      recorded: DeterministicLog::unbounded(),
+      divergence: None,
+      held: VecDeque::new(),
+      pumping: false,
+      releasing: None,
+      last_held: 0,
+      stalled: 0,
+    };
+    Self { state: ArcShared::new(<TB::MutexFamily as SyncMutexFamily>::create(state)) }
+  }
+
+  /// Returns `true` when the trace replays a captured run.
+  #[must_use]
+  pub fn is_replaying(&self) -> bool {
+    self.state.lock().expected.is_some()
+  }
+
+  /// Returns the events observed so far.
+  #[must_use]
+  pub fn events(&self) -> Vec<DeterministicEvent> {
+    self.state.lock().recorded.entries().to_vec()
+  }
+
+  /// Returns the number of expected events that have not been observed yet.
+  #[must_use]
+  pub fn remaining(&self) -> usize {
+    let state = self.state.lock();
+    state.expected.as_ref().map_or(0, |expected| expected.entries().len().saturating_sub(state.cursor))
+  }
+
+  /// Returns the index of the first observed event that differed from the captured run.
+  #[must_use]
+  pub fn divergence(&self) -> Option<usize> {
+    self.state.lock().divergence
+  }
+
+  /// Returns the number of mailbox runs held back by the replay.
+  #[must_use]
+  pub fn held(&self) -> usize {
+    self.state.lock().held.len()
+  }
+
+  /// Runs the held mailboxes the captured interleaving allows at this point.
+  ///
+  /// Tick drivers call this after each scheduler pass, so held mailboxes waiting for a timer
+  /// firing resume without another dispatch request.
+  pub fn pump(&self) {
+    {
+      let mut state = self.state.lock();
+      if state.pumping {
+        return;
+      }
+      state.pumping = true;
+    }
+    // ドライブ中に保留されたタスクも同じループで拾うため、ロックは 1 件ごとに取り直す
+    let mut released = false;
+    loop {
+      let task = self.state.lock().next_release(released);
+      match task {
+        | Some(task) => {
+          released = true;
+          task.drive();
+        },
+        | None => break,
+      }
+    }
+  }
+
+  pub(crate) fn record(&self, event: DeterministicEvent) {
+    self.state.lock().record(event);
+  }
+
+  /// Records that the mailbox of `pid` started running.
+  ///
+  /// While replaying, a run the trace did not release was submitted to an executor other than
+  /// [`ReplayExecutorGeneric`](crate::core::dispatcher::ReplayExecutorGeneric) and therefore
+  /// escapes the captured interleaving, so it is reported as a divergence.
+  pub(crate) fn record_run(&self, pid: Pid) {
+    let mut state = self.state.lock();
+    if state.expected.is_some() && state.divergence.is_none() && state.releasing != Some(pid) {
+      state.divergence = Some(state.recorded.entries().len());
+    }
+    state.record(DeterministicEvent::MailboxRun { pid });
+  }
+
+  pub(crate) fn hold(&self, task: DispatchSharedGeneric<TB>) {
+    self.state.lock().held.push_back(task);
+  }
+}
@@ -0,0 +1,45 @@
+use alloc::vec;
+
+use super::ExecutionTrace;
+use crate::core::{
+  actor_prim::Pid,
+  scheduler::{DeterministicEvent, ExecutionBatch},
+};
+
+const TIMER: DeterministicEvent =
+  DeterministicEvent::Fired { handle_id: 1, fired_tick: 3, batch: ExecutionBatch::oneshot() };
+const RUN: DeterministicEvent = DeterministicEvent::MailboxRun { pid: Pid::new(4, 0) };
+
+#[test]
+fn recording_trace_collects_events_in_order() {
+  let trace = ExecutionTrace::recording();
+  trace.record(RUN);
+  trace.record(TIMER);
+
+  assert!(!trace.is_replaying());
+  assert_eq!(trace.events(), vec![RUN, TIMER]);
+  assert_eq!(trace.remaining(), 0);
+  assert_eq!(trace.divergence(), None);
+}
+
+#[test]
+fn replaying_trace_tracks_progress_and_the_first_divergence() {
+  let trace = ExecutionTrace::replaying(vec![TIMER, RUN, RUN]);
+  trace.record(TIMER);
+  assert_eq!(trace.remaining(), 2);
+  assert_eq!(trace.divergence(), None);
+
+  trace.record(TIMER);
+  trace.record(RUN);
+  assert_eq!(trace.divergence(), Some(1));
+  assert_eq!(trace.remaining(), 2);
+}
+
+#[test]
+fn mailbox_runs_not_released_by_the_replay_diverge() {
+  let trace = ExecutionTrace::replaying(vec![RUN]);
+  trace.record_run(Pid::new(4, 0));
+
+  assert_eq!(trace.divergence(), Some(0));
+  assert_eq!(trace.events(), vec![RUN]);
+}
@@ -5,6 +5,6 @@ pub enum RunnerMode {
   Manual,
   /// Placeholder for async host drivers (tokio, std timers).
   AsyncHost,
-  /// Placeholder for hardware-backed drivers (embassy/SysTick).
+  /// Hardware-backed drivers (embassy-time, SysTick).
   Hardware,
 }
@@ -15,13 +15,19 @@ use fraktor_utils_rs::core::{
 use hashbrown::HashMap;
 
 use super::{
-  DeterministicEvent, ExecutionBatch, SchedulerDiagnostics, SchedulerDiagnosticsEvent,
+  DeterministicEvent, ExecutionBatch, ExecutionTraceGeneric, SchedulerDiagnostics, SchedulerDiagnosticsEvent,
   SchedulerDiagnosticsSubscription, SchedulerHandle, SchedulerMode, SchedulerWarning, TaskRunEntry, TaskRunHandle,
   TaskRunOnClose, TaskRunPriority, TaskRunQueue, TaskRunSummary, cancellable_registry::CancellableRegistry,
   command::SchedulerCommand, config::SchedulerConfig, dump::SchedulerDump, dump_job::SchedulerDumpJob,
   error::SchedulerError, fixed_delay_context::FixedDelayContext, fixed_rate_context::FixedRateContext,
   metrics::SchedulerMetrics, periodic_batch_decision::PeriodicBatchDecision,
 };
+#[cfg(any(test, feature = "test-support"))]
+use crate::core::{actor_prim::actor_ref::ActorRefGeneric, messaging::AnyMessageGeneric};
+
+#[cfg(any(test, feature = "test-support"))]
+pub(crate) type PendingMessage<TB> =
+  (SchedulerDumpJob, ActorRefGeneric<TB>, AnyMessageGeneric<TB>, Option<crate::core::actor_prim::Pid>);
 
 const DEFAULT_DRIFT_BUDGET_PCT: u8 = 5;
 
@@ -41,6 +47,7 @@ pub struct Scheduler<TB: RuntimeToolbox> {
   task_run_seq:  u64,
   shutting_down: bool,
   diagnostics:   SchedulerDiagnostics,
+  trace:         Option<ExecutionTraceGeneric<TB>>,
 }
 
 #[allow(dead_code)]
@@ -107,6 +114,7 @@ impl<TB: RuntimeToolbox> Scheduler<TB> {
       task_run_seq: 0,
       shutting_down: false,
       diagnostics: SchedulerDiagnostics::with_capacity(config.diagnostics_capacity()),
+      trace: None,
     }
   }
 
@@ -146,6 +154,17 @@ impl<TB: RuntimeToolbox> Scheduler<TB> {
     self.diagnostics.enable_deterministic_log(capacity);
   }
 
+  /// Attaches an execution trace receiving every deterministic event of the scheduler.
+  pub fn set_execution_trace(&mut self, trace: ExecutionTraceGeneric<TB>) {
+    self.trace = Some(trace);
+  }
+
+  /// Returns the attached execution trace, if any.
+  #[must_use]
+  pub fn execution_trace(&self) -> Option<ExecutionTraceGeneric<TB>> {
+    self.trace.clone()
+  }
+
   /// Returns the diagnostics snapshot.
   #[must_use]
   pub const fn diagnostics(&self) -> &SchedulerDiagnostics {
@@ -168,6 +187,24 @@ impl<TB: RuntimeToolbox> Scheduler<TB> {
     SchedulerDump::new(self.config.resolution(), self.current_tick, self.metrics, jobs, self.warnings.clone())
   }
 
+  /// Returns the pending jobs that deliver a message, alongside their receiver, payload and the
+  /// pid of the logical sender.
+  #[cfg(any(test, feature = "test-support"))]
+  pub(crate) fn pending_messages(&self) -> Vec<PendingMessage<TB>> {
+    self
+      .jobs
+      .values()
+      .filter_map(|job| match &job.command {
+        | SchedulerCommand::SendMessage { receiver, message, sender, .. } => {
+          let next_tick = job.periodic.as_ref().map(PeriodicContext::next_deadline_ticks);
+          let dump = SchedulerDumpJob::new(job.handle.raw(), job.mode, job.deadline_tick, next_tick);
+          Some((dump, receiver.clone(), message.clone(), sender.as_ref().map(ActorRefGeneric::pid)))
+        },
+        | _ => None,
+      })
+      .collect()
+  }
+
   /// Registers a one-shot job backed by the provided [`SchedulerCommand`].
   ///
   /// The command executes exactly once when `delay` expires unless cancelled or shutdown interrupts
@@ -321,8 +358,9 @@ impl<TB: RuntimeToolbox> Scheduler<TB> {
             continue;
           }
 
-          Self::execute_command(&job.command, &batch);
+          // コマンドが起こすメールボックス実行より先に発火を記録する
           self.record_fire_event(handle_id, batch);
+          Self::execute_command(&job.command, &batch);
           executed += 1;
 
           if job.periodic.is_some() {
@@ -518,8 +556,15 @@ impl<TB: RuntimeToolbox> Scheduler<TB> {
     }
   }
 
+  fn record_deterministic_event(&mut self, event: DeterministicEvent) {
+    if let Some(trace) = &self.trace {
+      trace.record(event);
+    }
+    self.diagnostics.record(event);
+  }
+
   fn record_scheduled_event(&mut self, handle_id: u64, deadline: TimerInstant, mode: SchedulerMode) {
-    self.diagnostics.record(DeterministicEvent::Scheduled {
+    self.record_deterministic_event(DeterministicEvent::Scheduled {
       handle_id,
       scheduled_tick: self.current_tick,
       deadline_tick: deadline.ticks(),
@@ -532,12 +577,12 @@ impl<TB: RuntimeToolbox> Scheduler<TB> {
   }
 
   fn record_fire_event(&mut self, handle_id: u64, batch: ExecutionBatch) {
-    self.diagnostics.record(DeterministicEvent::Fired { handle_id, fired_tick: self.current_tick, batch });
+    self.record_deterministic_event(DeterministicEvent::Fired { handle_id, fired_tick: self.current_tick, batch });
     self.publish_stream_with_drop(SchedulerDiagnosticsEvent::Fired { handle_id, fired_tick: self.current_tick, batch });
   }
 
   fn record_cancel_event(&mut self, handle_id: u64) {
-    self.diagnostics.record(DeterministicEvent::Cancelled { handle_id, cancelled_tick: self.current_tick });
+    self.record_deterministic_event(DeterministicEvent::Cancelled { handle_id, cancelled_tick: self.current_tick });
     self
       .publish_stream_with_drop(SchedulerDiagnosticsEvent::Cancelled { handle_id, cancelled_tick: self.current_tick });
   }
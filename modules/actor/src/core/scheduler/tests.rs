@@ -897,7 +897,7 @@ fn run_stress_profile(job_count: usize, drift_ticks: u64) -> StressReport {
           max_drift_pct = cmp::max(max_drift_pct, pct);
         }
       },
-      | DeterministicEvent::Cancelled { .. } => {},
+      | DeterministicEvent::Cancelled { .. } | DeterministicEvent::MailboxRun { .. } => {},
     }
   }
 
@@ -953,6 +953,7 @@ fn assert_deterministic_invariants(events: &[DeterministicEvent]) {
         }
         record.cancelled_tick = Some(cancelled_tick);
       },
+      | DeterministicEvent::MailboxRun { .. } => {},
     }
   }
 }
@@ -38,7 +38,7 @@ pub use bootstrap::TickDriverBootstrap;
 pub use hardware_driver::HardwareTickDriver;
 pub use hardware_kind::HardwareKind;
 #[cfg(any(test, feature = "test-support"))]
-pub use manual_test_driver::ManualTestDriver;
+pub use manual_test_driver::{ManualTestDriver, ManualTickController};
 pub use scheduler_tick_executor::SchedulerTickExecutor;
 pub use scheduler_tick_handle_owned::SchedulerTickHandleOwned;
 pub use tick_driver_config::TickDriverConfig;
@@ -105,10 +105,15 @@ impl<TB: RuntimeToolbox> ManualTickController<TB> {
 
   /// Drives the scheduler for pending ticks.
   pub fn drive(&self) {
-    self.state.with_runner(|runner, scheduler| {
+    let trace = self.state.with_runner(|runner, scheduler| {
       let mut guard = scheduler.lock();
       runner.drive(&mut guard);
+      guard.execution_trace()
     });
+    // スケジューラのロックを解放してから、タイマー待ちで保留されたメールボックスを再開する
+    if let Some(trace) = trace.flatten() {
+      trace.pump();
+    }
   }
 
   /// Convenience helper that injects ticks and drives immediately.
@@ -48,8 +48,15 @@ impl<TB: RuntimeToolbox + 'static> SchedulerTickExecutor<TB> {
       return;
     }
 
-    let mut guard = self.scheduler.lock();
-    self.runner.drive(&mut guard);
+    let trace = {
+      let mut guard = self.scheduler.lock();
+      self.runner.drive(&mut guard);
+      guard.execution_trace()
+    };
+    // スケジューラのロックを解放してから、タイマー待ちで保留されたメールボックスを再開する
+    if let Some(trace) = trace {
+      trace.pump();
+    }
   }
 
   /// Returns the associated signal for async waiting.
@@ -28,8 +28,8 @@ pub use builder::SerializationSetupBuilder;
 pub use builder_error::SerializationBuilderError;
 // Re-exports from builtin
 pub use builtin::{
-  BOOL_ID, BYTES_ID, BoolSerializer, BytesSerializer, I32_ID, I32Serializer, NULL_ID, NullSerializer, STRING_ID,
-  StringSerializer, register_defaults,
+  BOOL_ID, BYTES_ID, BoolSerializer, BytesSerializer, CLUSTER_SINGLETON_ID, I32_ID, I32Serializer, NULL_ID,
+  NullSerializer, REMOTE_DEPLOYMENT_ID, STRING_ID, StringSerializer, register_defaults,
 };
 // Re-exports from call_scope
 pub use call_scope::SerializationCallScope;
@@ -35,6 +35,17 @@ pub const STRING_ID: SerializerId = SerializerId::from_raw(4);
 /// Serializer ID for byte array type.
 pub const BYTES_ID: SerializerId = SerializerId::from_raw(5);
 
+/// Serializer ID reserved for the remoting deployment protocol.
+///
+/// The serializer itself is registered by the remoting extension, not by [`register_defaults`].
+pub const REMOTE_DEPLOYMENT_ID: SerializerId = SerializerId::from_raw(16);
+
+/// Serializer ID reserved for the cluster singleton protocol.
+///
+/// The serializer itself is registered by the cluster singleton actors, not by
+/// [`register_defaults`].
+pub const CLUSTER_SINGLETON_ID: SerializerId = SerializerId::from_raw(17);
+
 /// Registers built-in serializers required by the runtime.
 ///
 /// # Errors
@@ -37,6 +37,7 @@ use crate::core::{
     serialization_registry::{SerializationRegistryGeneric, SerializerResolutionOrigin},
     serialization_setup::SerializationSetup,
     serialized_message::SerializedMessage,
+    serializer::Serializer,
     serializer_id::SerializerId,
     transport_information::TransportInformation,
   },
@@ -203,6 +204,13 @@ impl<TB: RuntimeToolbox + 'static> SerializationExtensionGeneric<TB> {
     Ok(format!("local://{path}"))
   }
 
+  /// Registers an additional serializer at runtime.
+  ///
+  /// Returns `false` when a serializer is already registered under the identifier.
+  pub fn register_serializer(&self, id: SerializerId, serializer: ArcShared<dyn Serializer>) -> bool {
+    self.registry.register_serializer(id, serializer)
+  }
+
   /// Registers an additional binding at runtime.
   ///
   /// # Errors
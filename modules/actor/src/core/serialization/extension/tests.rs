@@ -494,6 +494,21 @@ fn runtime_binding_without_manifest_in_remote_scope_fails() {
   assert!(matches!(error, SerializationError::ManifestMissing { scope: SerializationCallScope::Remote }));
 }
 
+#[test]
+fn runtime_serializer_registration_enables_binding() {
+  let (extension, _) = build_extension::<NoStdToolbox>(None);
+  let secondary_id = SerializerId::try_from(432).expect("secondary");
+  let secondary: ArcShared<dyn Serializer> = ArcShared::new(SecondarySerializer::new(secondary_id));
+
+  assert!(extension.register_serializer(secondary_id, secondary.clone()));
+  assert!(!extension.register_serializer(secondary_id, secondary));
+  extension
+    .register_binding(TypeId::of::<SecondaryPayload>(), core::any::type_name::<SecondaryPayload>(), secondary_id)
+    .expect("dynamic binding");
+  let serialized = extension.serialize(&SecondaryPayload(9), SerializationCallScope::Local).expect("serialize");
+  assert_eq!(serialized.serializer_id(), secondary_id);
+}
+
 #[test]
 fn shutdown_blocks_deserialize_and_actor_path_calls() {
   let (extension, _) = build_extension::<NoStdToolbox>(None);
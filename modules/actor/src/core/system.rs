@@ -2,6 +2,7 @@
 //!
 //! This module contains the actor system management.
 
+mod actor_node_snapshot;
 mod actor_path_handle;
 mod actor_path_registry;
 mod actor_ref_provider;
@@ -9,6 +10,7 @@ mod actor_ref_provider_installer;
 mod actor_ref_resolve_error;
 mod actor_system_build_error;
 mod actor_system_config;
+mod actor_tree_snapshot;
 mod authority_state;
 mod base;
 mod extended_actor_system;
@@ -18,6 +20,7 @@ mod local_actor_ref_provider_installer;
 mod register_extra_top_level_error;
 mod remote_authority;
 mod remote_authority_error;
+mod remote_deploy_hook;
 mod remote_watch_hook;
 mod remote_watch_hook_shared;
 mod remoting_config;
@@ -27,6 +30,7 @@ mod system_guardian_actor;
 mod system_guardian_protocol;
 mod system_state;
 
+pub use actor_node_snapshot::ActorNodeSnapshot;
 pub use actor_path_handle::ActorPathHandle;
 pub use actor_path_registry::ActorPathRegistry;
 pub use actor_ref_provider::ActorRefProvider;
@@ -34,6 +38,7 @@ pub use actor_ref_provider_installer::ActorRefProviderInstaller;
 pub use actor_ref_resolve_error::ActorRefResolveError;
 pub use actor_system_build_error::ActorSystemBuildError;
 pub use actor_system_config::{ActorSystemConfig, ActorSystemConfigGeneric};
+pub use actor_tree_snapshot::ActorTreeSnapshot;
 pub use authority_state::AuthorityState;
 pub use base::{ActorSystem, ActorSystemGeneric};
 pub use extended_actor_system::{ExtendedActorSystem, ExtendedActorSystemGeneric};
@@ -43,6 +48,7 @@ pub use local_actor_ref_provider_installer::LocalActorRefProviderInstaller;
 pub use register_extra_top_level_error::RegisterExtraTopLevelError;
 pub use remote_authority::{RemoteAuthorityManager, RemoteAuthorityManagerGeneric};
 pub use remote_authority_error::RemoteAuthorityError;
+pub use remote_deploy_hook::RemoteDeployHook;
 pub use remote_watch_hook::RemoteWatchHook;
 pub use remote_watch_hook_shared::RemoteWatchHookShared;
 pub use remoting_config::RemotingConfig;
@@ -0,0 +1,103 @@
+//! Point-in-time view of a single actor inside an [`ActorTreeSnapshot`](super::ActorTreeSnapshot).
+
+use alloc::{string::String, vec::Vec};
+
+use serde::{Serialize, Serializer};
+
+use crate::core::actor_prim::Pid;
+
+/// Snapshot describing one live actor and its children.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ActorNodeSnapshot {
+  #[serde(serialize_with = "serialize_pid")]
+  pub(crate) pid:              Pid,
+  pub(crate) path:             String,
+  pub(crate) actor_type:       Option<&'static str>,
+  pub(crate) props_name:       Option<String>,
+  pub(crate) dispatcher_id:    Option<String>,
+  pub(crate) mailbox_id:       Option<String>,
+  pub(crate) user_queue_len:   usize,
+  pub(crate) system_queue_len: usize,
+  pub(crate) processed:        u64,
+  pub(crate) restarts:         u32,
+  pub(crate) last_failure:     Option<String>,
+  pub(crate) children:         Vec<ActorNodeSnapshot>,
+}
+
+impl ActorNodeSnapshot {
+  /// Returns the actor pid.
+  #[must_use]
+  pub const fn pid(&self) -> Pid {
+    self.pid
+  }
+
+  /// Returns the actor path rendered as a string.
+  #[must_use]
+  pub const fn path(&self) -> &str {
+    self.path.as_str()
+  }
+
+  /// Returns the Rust type name of the actor, when the props recorded it.
+  #[must_use]
+  pub const fn actor_type(&self) -> Option<&'static str> {
+    self.actor_type
+  }
+
+  /// Returns the name requested through the props, if any.
+  #[must_use]
+  pub fn props_name(&self) -> Option<&str> {
+    self.props_name.as_deref()
+  }
+
+  /// Returns the registered dispatcher identifier, if the actor was spawned on one.
+  #[must_use]
+  pub fn dispatcher_id(&self) -> Option<&str> {
+    self.dispatcher_id.as_deref()
+  }
+
+  /// Returns the registered mailbox identifier, if the actor was spawned with one.
+  #[must_use]
+  pub fn mailbox_id(&self) -> Option<&str> {
+    self.mailbox_id.as_deref()
+  }
+
+  /// Returns the queued user messages.
+  #[must_use]
+  pub const fn user_queue_len(&self) -> usize {
+    self.user_queue_len
+  }
+
+  /// Returns the queued system messages.
+  #[must_use]
+  pub const fn system_queue_len(&self) -> usize {
+    self.system_queue_len
+  }
+
+  /// Returns how many user messages the actor has processed.
+  #[must_use]
+  pub const fn processed(&self) -> u64 {
+    self.processed
+  }
+
+  /// Returns how many times the actor has been restarted.
+  #[must_use]
+  pub const fn restarts(&self) -> u32 {
+    self.restarts
+  }
+
+  /// Returns the reason of the most recent failure, if any.
+  #[must_use]
+  pub fn last_failure(&self) -> Option<&str> {
+    self.last_failure.as_deref()
+  }
+
+  /// Returns the snapshots of the actor's children.
+  #[must_use]
+  pub const fn children(&self) -> &[ActorNodeSnapshot] {
+    self.children.as_slice()
+  }
+}
+
+fn serialize_pid<S: Serializer>(pid: &Pid, serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.collect_str(pid)
+}
@@ -1,6 +1,9 @@
 //! Actor system configuration API.
 
-use alloc::string::{String, ToString};
+use alloc::{
+  string::{String, ToString},
+  vec::Vec,
+};
 
 use fraktor_utils_rs::core::{
   runtime_toolbox::{NoStdToolbox, RuntimeToolbox},
@@ -9,9 +12,11 @@ use fraktor_utils_rs::core::{
 
 use crate::core::{
   actor_prim::actor_path::GuardianKind as PathGuardianKind,
+  dead_letter::DeadLetterConfig,
   dispatcher::DispatcherConfigGeneric,
   extension::ExtensionInstallers,
-  scheduler::{SchedulerConfig, TickDriverConfig},
+  messaging::message_invoker::MessageInvokerMiddleware,
+  scheduler::{ExecutionTraceGeneric, SchedulerConfig, TickDriverConfig},
   system::{ActorRefProviderInstaller, RemotingConfig},
 };
 
@@ -30,6 +35,10 @@ where
   extension_installers:      Option<ExtensionInstallers<TB>>,
   provider_installer:        Option<ArcShared<dyn ActorRefProviderInstaller<TB>>>,
   default_dispatcher_config: Option<DispatcherConfigGeneric<TB>>,
+  execution_trace:           Option<ExecutionTraceGeneric<TB>>,
+  message_middlewares:       Vec<ArcShared<dyn MessageInvokerMiddleware<TB>>>,
+  dead_letter_config:        DeadLetterConfig,
+  named_middlewares:         Vec<(String, ArcShared<dyn MessageInvokerMiddleware<TB>>)>,
 }
 
 /// Type alias for [ActorSystemConfigGeneric] with the default [NoStdToolbox].
@@ -97,6 +106,47 @@ where
     self
   }
 
+  /// Attaches an execution trace recording (or replaying) scheduler events and mailbox runs.
+  ///
+  /// A replaying trace requires every dispatcher, including the default one, to use a
+  /// [`ReplayExecutorGeneric`](crate::core::dispatcher::ReplayExecutorGeneric) sharing the trace.
+  #[must_use]
+  pub fn with_execution_trace(mut self, trace: ExecutionTraceGeneric<TB>) -> Self {
+    self.execution_trace = Some(trace);
+    self
+  }
+
+  /// Appends middleware applied to every actor of the system.
+  ///
+  /// System-wide middleware runs before the middleware of individual props and also observes
+  /// messages sent through system-bound actor references.
+  #[must_use]
+  pub fn with_message_middleware(mut self, middleware: ArcShared<dyn MessageInvokerMiddleware<TB>>) -> Self {
+    self.message_middlewares.push(middleware);
+    self
+  }
+
+  /// Registers middleware under `name` so props can refer to it through
+  /// [`PropsGeneric::with_middleware`](crate::core::props::PropsGeneric::with_middleware).
+  ///
+  /// A name registered twice keeps the last middleware.
+  #[must_use]
+  pub fn with_named_middleware(
+    mut self,
+    name: impl Into<String>,
+    middleware: ArcShared<dyn MessageInvokerMiddleware<TB>>,
+  ) -> Self {
+    self.named_middlewares.push((name.into(), middleware));
+    self
+  }
+
+  /// Sets the deadletter retention and logging configuration.
+  #[must_use]
+  pub const fn with_dead_letter_config(mut self, config: DeadLetterConfig) -> Self {
+    self.dead_letter_config = config;
+    self
+  }
+
   /// Returns the system name.
   #[must_use]
   #[allow(clippy::missing_const_for_fn)] // String の Deref が const でないため const fn にできない
@@ -163,6 +213,30 @@ where
   pub const fn default_dispatcher_config(&self) -> Option<&DispatcherConfigGeneric<TB>> {
     self.default_dispatcher_config.as_ref()
   }
+
+  /// Returns the execution trace if set.
+  #[must_use]
+  pub const fn execution_trace(&self) -> Option<&ExecutionTraceGeneric<TB>> {
+    self.execution_trace.as_ref()
+  }
+
+  /// Returns the system-wide message middleware.
+  #[must_use]
+  pub const fn message_middlewares(&self) -> &[ArcShared<dyn MessageInvokerMiddleware<TB>>] {
+    self.message_middlewares.as_slice()
+  }
+
+  /// Returns the middleware registered by name.
+  #[must_use]
+  pub const fn named_middlewares(&self) -> &[(String, ArcShared<dyn MessageInvokerMiddleware<TB>>)] {
+    self.named_middlewares.as_slice()
+  }
+
+  /// Returns the deadletter configuration.
+  #[must_use]
+  pub const fn dead_letter_config(&self) -> DeadLetterConfig {
+    self.dead_letter_config
+  }
 }
 
 impl<TB> Default for ActorSystemConfigGeneric<TB>
@@ -179,6 +253,10 @@ where
       extension_installers:      None,
       provider_installer:        None,
       default_dispatcher_config: None,
+      execution_trace:           None,
+      message_middlewares:       Vec::new(),
+      dead_letter_config:        DeadLetterConfig::new(),
+      named_middlewares:         Vec::new(),
     }
   }
 }
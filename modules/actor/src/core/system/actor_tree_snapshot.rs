@@ -0,0 +1,56 @@
+//! Point-in-time view of the live actor hierarchy.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+
+use serde::Serialize;
+
+use super::ActorNodeSnapshot;
+use crate::core::actor_prim::Pid;
+
+/// Snapshot of every live actor, rooted at the root guardian.
+///
+/// Produced by [`ActorSystemGeneric::snapshot_tree`](super::ActorSystemGeneric::snapshot_tree);
+/// the tree is serializable so it can be shipped to dashboards as-is.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ActorTreeSnapshot {
+  root: Option<ActorNodeSnapshot>,
+}
+
+impl ActorTreeSnapshot {
+  pub(crate) const fn new(root: Option<ActorNodeSnapshot>) -> Self {
+    Self { root }
+  }
+
+  /// Returns the root guardian node, or `None` once the system has terminated.
+  #[must_use]
+  pub const fn root(&self) -> Option<&ActorNodeSnapshot> {
+    self.root.as_ref()
+  }
+
+  /// Returns the number of actors in the tree.
+  #[must_use]
+  pub fn actor_count(&self) -> usize {
+    self.nodes().len()
+  }
+
+  /// Finds the node describing `pid`.
+  #[must_use]
+  pub fn find(&self, pid: Pid) -> Option<&ActorNodeSnapshot> {
+    self.nodes().into_iter().find(|node| node.pid() == pid)
+  }
+
+  /// Returns every node in depth-first order, parents before children.
+  #[must_use]
+  pub fn nodes(&self) -> Vec<&ActorNodeSnapshot> {
+    let mut nodes = Vec::new();
+    let mut stack: Vec<&ActorNodeSnapshot> = self.root.iter().collect();
+    while let Some(node) = stack.pop() {
+      nodes.push(node);
+      stack.extend(node.children().iter().rev());
+    }
+    nodes
+  }
+}
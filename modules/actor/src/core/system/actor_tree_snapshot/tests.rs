@@ -0,0 +1,120 @@
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdMutex, NoStdToolbox},
+  sync::ArcShared,
+};
+
+use crate::core::{
+  actor_prim::{Actor, ActorContextGeneric},
+  error::ActorError,
+  messaging::{AnyMessage, AnyMessageViewGeneric},
+  props::Props,
+  testkit::ActorTestKit,
+};
+
+type Observed = ArcShared<NoStdMutex<Option<(usize, usize)>>>;
+
+struct Fill;
+
+struct Worker;
+
+// 自分宛てにメッセージを積み、処理前のキュー長をスナップショットから記録する
+struct Filler {
+  observed: Observed,
+}
+
+impl Actor for Filler {
+  fn receive(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    if message.downcast_ref::<Fill>().is_some() {
+      let self_ref = ctx.self_ref();
+      self_ref.tell(AnyMessage::new(1_u32)).map_err(|error| ActorError::from_send_error(&error))?;
+      self_ref.tell(AnyMessage::new(2_u32)).map_err(|error| ActorError::from_send_error(&error))?;
+      let tree = ctx.system().snapshot_tree();
+      let node = tree.find(ctx.pid()).ok_or_else(|| ActorError::recoverable("missing node"))?;
+      *self.observed.lock() = Some((node.user_queue_len(), node.system_queue_len()));
+    }
+    Ok(())
+  }
+}
+
+impl Actor for Worker {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    if message.downcast_ref::<&'static str>() == Some(&"boom") {
+      return Err(ActorError::recoverable("boom"));
+    }
+    Ok(())
+  }
+}
+
+#[test]
+fn snapshot_lists_spawned_actors_under_their_parents() {
+  let kit = ActorTestKit::new().expect("kit");
+  let worker = kit.spawn(&Props::from_fn(|| Worker).with_name("worker")).expect("spawn");
+
+  let tree = kit.system().snapshot_tree();
+  let root = tree.root().expect("root");
+  assert!(root.path().starts_with('/'));
+  assert_eq!(tree.actor_count(), tree.nodes().len());
+
+  let node = tree.find(worker.pid()).expect("worker node");
+  assert!(node.path().ends_with("/worker"));
+  assert_eq!(node.props_name(), Some("worker"));
+  assert!(node.actor_type().is_some_and(|name| name.ends_with("::Worker")));
+  assert_eq!(node.dispatcher_id(), Some("default"));
+  assert_eq!(node.mailbox_id(), None);
+  assert!(node.children().is_empty());
+
+  let parent =
+    tree.nodes().into_iter().find(|candidate| candidate.children().iter().any(|child| child.pid() == worker.pid()));
+  assert!(parent.is_some());
+}
+
+#[test]
+fn snapshot_reports_processed_restarts_and_last_failure() {
+  let kit = ActorTestKit::new().expect("kit");
+  let worker = kit.spawn(&Props::from_fn(|| Worker)).expect("spawn");
+  worker.tell(AnyMessage::new("hello")).expect("tell");
+  worker.tell(AnyMessage::new("boom")).expect("tell");
+  worker.tell(AnyMessage::new("hello")).expect("tell");
+
+  let tree = kit.system().snapshot_tree();
+  let node = tree.find(worker.pid()).expect("worker node");
+  assert_eq!(node.processed(), 3);
+  assert_eq!(node.restarts(), 1);
+  assert_eq!(node.last_failure(), Some("boom"));
+}
+
+#[test]
+fn snapshot_reports_queued_messages() {
+  let kit = ActorTestKit::new().expect("kit");
+  let observed: Observed = ArcShared::new(NoStdMutex::new(None));
+  let sink = observed.clone();
+  let filler = kit.spawn(&Props::from_fn(move || Filler { observed: sink.clone() })).expect("spawn");
+  filler.tell(AnyMessage::new(Fill)).expect("tell");
+
+  assert_eq!(*observed.lock(), Some((2, 0)));
+  let tree = kit.system().snapshot_tree();
+  let node = tree.find(filler.pid()).expect("filler node");
+  assert_eq!(node.user_queue_len(), 0);
+  assert_eq!(node.processed(), 3);
+}
+
+#[test]
+fn snapshot_serializes_to_json() {
+  let kit = ActorTestKit::new().expect("kit");
+  let worker = kit.spawn(&Props::from_fn(|| Worker).with_name("worker")).expect("spawn");
+
+  let value = serde_json::to_value(kit.system().snapshot_tree()).expect("json");
+  assert!(value["root"]["children"].is_array());
+  let rendered = serde_json::to_string(&value).expect("json");
+  assert!(rendered.contains(&alloc::format!("\"pid\":\"{}\"", worker.pid())));
+  assert!(rendered.contains("\"props_name\":\"worker\""));
+  assert!(rendered.contains("\"user_queue_len\":0"));
+}
@@ -24,7 +24,7 @@ use crate::core::{
     actor_path::{ActorPath, ActorPathParts, ActorPathScheme, ActorUid, PathSegment},
     actor_ref::ActorRefGeneric,
   },
-  dead_letter::{DeadLetterEntryGeneric, DeadLetterReason},
+  dead_letter::{DeadLetterEntryGeneric, DeadLetterQuery, DeadLetterReason, DeadLetterSuppression},
   error::SendError,
   event_stream::{
     EventStreamEvent, EventStreamGeneric, EventStreamSubscriberShared, EventStreamSubscriptionGeneric,
@@ -37,7 +37,10 @@ use crate::core::{
   scheduler::{SchedulerBackedDelayProvider, SchedulerContext, TickDriverConfig},
   serialization::default_serialization_extension_id,
   spawn::SpawnError,
-  system::{ActorRefResolveError, actor_system_config::ActorSystemConfigGeneric, system_state::SystemStateGeneric},
+  system::{
+    ActorRefResolveError, ActorTreeSnapshot, actor_system_config::ActorSystemConfigGeneric,
+    system_state::SystemStateGeneric,
+  },
 };
 
 const PARENT_MISSING: &str = "parent actor not found";
@@ -277,6 +280,11 @@ impl<TB: RuntimeToolbox + 'static> ActorSystemGeneric<TB> {
       self.state.install_scheduler_context(ArcShared::new(context));
     }
 
+    if let Some(trace) = config.execution_trace() {
+      let ctx = self.scheduler_context().ok_or(SpawnError::SystemUnavailable)?;
+      ctx.scheduler().lock().set_execution_trace(trace.clone());
+    }
+
     // Install tick driver runtime if tick_driver_config is provided
     if let Some(tick_driver_config) = config.tick_driver_config() {
       let ctx = self.scheduler_context().ok_or(SpawnError::SystemUnavailable)?;
@@ -307,6 +315,12 @@ impl<TB: RuntimeToolbox + 'static> ActorSystemGeneric<TB> {
     self.state.event_stream()
   }
 
+  /// Captures the live actor tree with mailbox depths and per-actor processing stats.
+  #[must_use]
+  pub fn snapshot_tree(&self) -> ActorTreeSnapshot {
+    self.state.snapshot_tree()
+  }
+
   /// Returns the scheduler service when initialized.
   #[must_use]
   pub fn scheduler_context(&self) -> Option<ArcShared<SchedulerContext<TB>>> {
@@ -346,6 +360,25 @@ impl<TB: RuntimeToolbox + 'static> ActorSystemGeneric<TB> {
     self.state.dead_letters()
   }
 
+  /// Returns the retained dead letters matching `query`.
+  #[must_use]
+  pub fn query_dead_letters(&self, query: &DeadLetterQuery) -> Vec<DeadLetterEntryGeneric<TB>> {
+    self.state.query_dead_letters(query)
+  }
+
+  /// Returns the number of dead letters recorded per reason since the system started.
+  #[must_use]
+  pub fn dead_letter_counts(&self) -> Vec<(DeadLetterReason, u64)> {
+    self.state.dead_letter_counts()
+  }
+
+  /// Suppresses the logging of dead letters carrying a `T` payload, however the message was built.
+  pub fn register_dead_letter_suppression<T>(&self)
+  where
+    T: DeadLetterSuppression, {
+    self.state.register_dead_letter_suppression::<T>();
+  }
+
   /// Records a deadletter entry that will also be published to the event stream.
   pub fn record_dead_letter(&self, message: AnyMessageGeneric<TB>, reason: DeadLetterReason, recipient: Option<Pid>) {
     self.state.record_dead_letter(message, reason, recipient);
@@ -416,11 +449,16 @@ impl<TB: RuntimeToolbox + 'static> ActorSystemGeneric<TB> {
   ///
   /// # Errors
   ///
-  /// Returns [`SpawnError::InvalidProps`] when the parent pid is unknown.
+  /// Returns [`SpawnError::InvalidProps`] when the parent pid is unknown or when the props request
+  /// a remote deployment that cannot be issued.
   pub(crate) fn spawn_child(&self, parent: Pid, props: &PropsGeneric<TB>) -> Result<ChildRefGeneric<TB>, SpawnError> {
     if self.state.cell(&parent).is_none() {
       return Err(SpawnError::invalid_props(PARENT_MISSING));
     }
+    if props.deploy().is_some() {
+      let actor = self.state.deploy_remote_child(parent, props)?;
+      return Ok(ChildRefGeneric::new(actor, self.state.clone()));
+    }
     self.spawn_with_parent(Some(parent), props)
   }
 
@@ -596,24 +634,32 @@ impl<TB: RuntimeToolbox + 'static> ActorSystemGeneric<TB> {
 
   fn resolve_props(&self, props: &PropsGeneric<TB>) -> Result<PropsGeneric<TB>, SpawnError> {
     let mut resolved = props.clone();
-    if let Some(dispatcher_id) = resolved.dispatcher_id() {
+    if let Some(dispatcher_id) = props.dispatcher_id() {
       let config = self
         .state
         .dispatchers()
         .resolve(dispatcher_id)
         .map_err(|error| SpawnError::invalid_props(error.to_string()))?;
-      resolved = resolved.with_resolved_dispatcher(config);
+      resolved = resolved.with_resolved_dispatcher(dispatcher_id, config);
     } else if !resolved.has_custom_dispatcher() {
       // If no dispatcher_id is specified, use the system's default dispatcher
       if let Ok(default_config) = self.state.dispatchers().resolve("default") {
-        resolved = resolved.with_resolved_dispatcher(default_config);
+        resolved = resolved.with_resolved_dispatcher("default", default_config);
       }
     }
-    if let Some(mailbox_id) = resolved.mailbox_id() {
+    if let Some(mailbox_id) = props.mailbox_id() {
       let config =
         self.state.mailboxes().resolve(mailbox_id).map_err(|error| SpawnError::invalid_props(error.to_string()))?;
       resolved = resolved.with_resolved_mailbox(config);
     }
+    if !props.middleware().is_empty() {
+      let middleware = self
+        .state
+        .middlewares()
+        .resolve_all(props.middleware())
+        .map_err(|error| SpawnError::invalid_props(error.to_string()))?;
+      resolved = resolved.with_resolved_middleware(middleware);
+    }
     Ok(resolved)
   }
 
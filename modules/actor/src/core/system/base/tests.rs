@@ -16,7 +16,7 @@ use fraktor_utils_rs::core::{
 use super::ActorSystem;
 use crate::core::{
   actor_prim::{
-    Actor, ActorCell,
+    Actor, ActorCell, Pid,
     actor_path::{ActorPath, ActorPathParts, ActorPathScheme},
     actor_ref::ActorRefGeneric,
   },
@@ -25,12 +25,12 @@ use crate::core::{
   event_stream::{EventStreamEvent, EventStreamSubscriber, subscriber_handle},
   lifecycle::LifecycleStage,
   messaging::SystemMessage,
-  props::{MailboxConfig, MailboxRequirement, Props},
+  props::{Deploy, MailboxConfig, MailboxRequirement, Props},
   scheduler::{
     AutoDriverMetadata, AutoProfileKind, SchedulerConfig, SchedulerContext, TickDriverId, TickDriverKind,
     TickDriverMetadata,
   },
-  system::{ActorRefProvider, ActorRefResolveError, ActorSystemConfig, RemotingConfig},
+  system::{ActorRefProvider, ActorRefResolveError, ActorSystemConfig, RemoteDeployHook, RemotingConfig},
 };
 
 struct TestActor;
@@ -306,6 +306,86 @@ fn spawn_child_resolves_mailbox_id_with_requirements() {
   assert!(matches!(result, Err(crate::core::spawn::SpawnError::InvalidProps(_))));
 }
 
+struct RecordingDeployHook {
+  parents: ArcShared<NoStdMutex<Vec<Pid>>>,
+  stopped: ArcShared<NoStdMutex<Vec<Pid>>>,
+}
+
+impl RemoteDeployHook<NoStdToolbox> for RecordingDeployHook {
+  fn deploy_child(
+    &mut self,
+    parent: Pid,
+    _props: &Props,
+  ) -> Result<ActorRefGeneric<NoStdToolbox>, crate::core::spawn::SpawnError> {
+    self.parents.lock().push(parent);
+    Ok(ActorRefGeneric::null())
+  }
+
+  fn stop_child(&mut self, child: Pid) {
+    self.stopped.lock().push(child);
+  }
+}
+
+fn deploy_props() -> Props {
+  let target = ActorPathParts::with_authority("worker-system", Some(("127.0.0.1", 2552)));
+  Props::from_fn(|| TestActor).with_deploy(Deploy::remote(target, "worker", Vec::new()))
+}
+
+#[test]
+fn spawn_child_with_deploy_fails_without_hook() {
+  let system = ActorSystem::new_empty();
+  let parent_pid = system.allocate_pid();
+  let parent_name = system.state().assign_name(None, Some("parent"), parent_pid).expect("parent name");
+  let parent_cell = ActorCell::create(system.state(), parent_pid, None, parent_name, &Props::from_fn(|| TestActor))
+    .expect("create actor cell");
+  system.state().register_cell(parent_cell);
+
+  let result = system.spawn_child(parent_pid, &deploy_props());
+  assert!(matches!(result, Err(crate::core::spawn::SpawnError::InvalidProps(_))));
+}
+
+#[test]
+fn spawn_child_with_deploy_routes_to_hook() {
+  let system = ActorSystem::new_empty();
+  let parent_pid = system.allocate_pid();
+  let parent_name = system.state().assign_name(None, Some("parent"), parent_pid).expect("parent name");
+  let parent_cell = ActorCell::create(system.state(), parent_pid, None, parent_name, &Props::from_fn(|| TestActor))
+    .expect("create actor cell");
+  system.state().register_cell(parent_cell);
+  let parents = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let stopped = ArcShared::new(NoStdMutex::new(Vec::new()));
+  system.extended().register_remote_deploy_hook(RecordingDeployHook { parents: parents.clone(), stopped });
+
+  assert!(system.spawn_child(parent_pid, &deploy_props()).is_ok());
+  assert_eq!(parents.lock().clone(), vec![parent_pid]);
+  assert!(system.children(parent_pid).is_empty());
+}
+
+#[test]
+fn remote_children_are_stopped_through_the_deploy_hook() {
+  let system = ActorSystem::new_empty();
+  let parent_pid = system.allocate_pid();
+  let parent_name = system.state().assign_name(None, Some("parent"), parent_pid).expect("parent name");
+  let parent_cell = ActorCell::create(system.state(), parent_pid, None, parent_name, &Props::from_fn(|| TestActor))
+    .expect("create actor cell");
+  system.state().register_cell(parent_cell);
+  let parents = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let stopped = ArcShared::new(NoStdMutex::new(Vec::new()));
+  system.extended().register_remote_deploy_hook(RecordingDeployHook { parents, stopped: stopped.clone() });
+
+  let child = system.spawn_child(parent_pid, &deploy_props()).expect("deploy").pid();
+  assert_eq!(system.state().child_pids(parent_pid), vec![child]);
+
+  assert!(system.state().send_system_message(child, SystemMessage::Recreate).is_ok());
+  assert!(system.state().send_system_message(child, SystemMessage::Stop).is_ok());
+  assert_eq!(stopped.lock().clone(), vec![child]);
+
+  system.extended().remote_child_terminated(child);
+  assert!(system.state().child_pids(parent_pid).is_empty());
+  assert!(system.state().send_system_message(child, SystemMessage::Stop).is_err());
+  assert_eq!(stopped.lock().clone(), vec![child]);
+}
+
 #[test]
 fn actor_system_spawn_without_guardian() {
   let system = ActorSystem::new_empty();
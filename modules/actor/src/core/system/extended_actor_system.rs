@@ -8,12 +8,14 @@ use fraktor_utils_rs::core::{
   sync::ArcShared,
 };
 
-use super::{ActorRefProvider, ActorSystemGeneric, RegisterExtraTopLevelError, RemoteWatchHook};
+use super::{ActorRefProvider, ActorSystemGeneric, RegisterExtraTopLevelError, RemoteDeployHook, RemoteWatchHook};
 use crate::core::{
-  actor_prim::{ChildRefGeneric, actor_ref::ActorRefGeneric},
+  actor_prim::{ChildRefGeneric, Pid, actor_ref::ActorRefGeneric},
   dispatcher::DispatchersGeneric,
+  error::{ActorError, SendError},
   extension::{Extension, ExtensionId},
   mailbox::MailboxesGeneric,
+  messaging::{FailurePayload, SystemMessage, message_invoker::MiddlewaresGeneric},
   props::PropsGeneric,
   spawn::SpawnError,
 };
@@ -55,6 +57,12 @@ impl<TB: RuntimeToolbox + 'static> ExtendedActorSystemGeneric<TB> {
     self.inner.state().mailboxes()
   }
 
+  /// Returns the middleware registry.
+  #[must_use]
+  pub fn middlewares(&self) -> ArcShared<MiddlewaresGeneric<TB>> {
+    self.inner.state().middlewares()
+  }
+
   /// Registers the provided extension and returns the shared instance.
   pub fn register_extension<E>(&self, ext_id: &E) -> ArcShared<E::Ext>
   where
@@ -112,6 +120,49 @@ impl<TB: RuntimeToolbox + 'static> ExtendedActorSystemGeneric<TB> {
     self.inner.state().register_remote_watch_hook(dyn_hook);
   }
 
+  /// Registers a remote deploy hook that spawns children whose props target another node.
+  ///
+  /// The hook will be wrapped in a `ToolboxMutex` internally for thread-safe access.
+  pub fn register_remote_deploy_hook<H>(&self, hook: H)
+  where
+    H: RemoteDeployHook<TB>, {
+    let dyn_hook: Box<dyn RemoteDeployHook<TB>> = Box::new(hook);
+    self.inner.state().register_remote_deploy_hook(dyn_hook);
+  }
+
+  /// Delivers a `Terminated` notification for `terminated` to the `watcher` actor.
+  ///
+  /// Remoting daemons use this to relay the termination of actors that live on another node.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`SendError`] when the watcher mailbox rejects the system message.
+  pub fn notify_terminated(&self, watcher: Pid, terminated: Pid) -> Result<(), SendError<TB>> {
+    self.inner.state().send_system_message(watcher, SystemMessage::Terminated(terminated))
+  }
+
+  /// Reports the failure of a remotely deployed child to the supervisor of its parent.
+  ///
+  /// The parent receives the failure as a regular supervision signal, so its
+  /// [`SupervisorStrategy`](crate::core::supervision::SupervisorStrategy) decides whether the child
+  /// is stopped or escalated.
+  pub fn report_remote_child_failure(&self, child: Pid, error: &ActorError) {
+    let state = self.inner.state();
+    let payload = FailurePayload::from_error(child, error, None, state.monotonic_now());
+    state.report_failure(payload);
+  }
+
+  /// Releases a remotely deployed child whose hosting node reported its termination.
+  ///
+  /// The parent is detached from the child and receives a `Terminated` notification. Unknown
+  /// children are ignored.
+  pub fn remote_child_terminated(&self, child: Pid) {
+    let state = self.inner.state();
+    if let Some(parent) = state.remove_remote_child(child) {
+      let _ = state.send_system_message(parent, SystemMessage::Terminated(child));
+    }
+  }
+
   /// Registers an extra top-level actor name before the system finishes startup.
   ///
   /// # Errors
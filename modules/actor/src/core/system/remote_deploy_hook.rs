@@ -0,0 +1,33 @@
+//! Hook used by remoting to deploy child actors onto other nodes.
+
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
+use crate::core::{
+  actor_prim::{Pid, actor_ref::ActorRefGeneric},
+  props::PropsGeneric,
+  spawn::SpawnError,
+};
+
+/// Allows remoting providers to spawn children whose props carry a remote [`Deploy`].
+///
+/// Implementations should be wrapped in a `ToolboxMutex` by callers to ensure thread-safe access.
+///
+/// [`Deploy`]: crate::core::props::Deploy
+pub trait RemoteDeployHook<TB>: Send + 'static
+where
+  TB: RuntimeToolbox + 'static, {
+  /// Deploys the child described by `props` on behalf of `parent` and returns a reference to it.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`SpawnError`] when the deployment request cannot be issued.
+  fn deploy_child(&mut self, parent: Pid, props: &PropsGeneric<TB>) -> Result<ActorRefGeneric<TB>, SpawnError>;
+
+  /// Asks the hosting node to stop a child previously returned by [`deploy_child`].
+  ///
+  /// Invoked when the parent stops the child or terminates itself. Restart directives are not
+  /// forwarded because the hosting node already supervises the child locally.
+  ///
+  /// [`deploy_child`]: RemoteDeployHook::deploy_child
+  fn stop_child(&mut self, _child: Pid) {}
+}
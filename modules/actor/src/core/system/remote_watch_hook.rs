@@ -2,7 +2,7 @@
 
 use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
 
-use crate::core::actor_prim::Pid;
+use crate::core::actor_prim::{Pid, actor_path::ActorPath};
 
 /// Allows custom providers to reroute `SystemMessage::Watch/Unwatch` for remote actors.
 ///
@@ -16,4 +16,10 @@ where
 
   /// Handles an unwatch request. Returns `true` when the provider consumed the message.
   fn handle_unwatch(&mut self, target: Pid, watcher: Pid) -> bool;
+
+  /// Returns the remote path of an actor reference created by the provider, if it knows `pid`.
+  fn remote_path(&self, pid: Pid) -> Option<ActorPath> {
+    let _ = pid;
+    None
+  }
 }
@@ -5,10 +5,12 @@ use fraktor_utils_rs::core::{
   sync::{ArcShared, sync_mutex_like::SyncMutexLike},
 };
 
-use super::{ActorRefProvider, RemoteWatchHook};
+use super::{ActorRefProvider, RemoteDeployHook, RemoteWatchHook};
 use crate::core::{
   actor_prim::{Pid, actor_path::ActorPathScheme, actor_ref::ActorRefGeneric},
   error::ActorError,
+  props::PropsGeneric,
+  spawn::SpawnError,
 };
 
 /// Shared wrapper that provides thread-safe access to a provider implementing
@@ -61,6 +63,18 @@ impl<TB: RuntimeToolbox + 'static, P: RemoteWatchHook<TB> + Send + 'static> Remo
   fn handle_unwatch(&mut self, target: Pid, watcher: Pid) -> bool {
     self.inner.lock().handle_unwatch(target, watcher)
   }
+
+  fn remote_path(&self, pid: Pid) -> Option<crate::core::actor_prim::actor_path::ActorPath> {
+    self.inner.lock().remote_path(pid)
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static, P: RemoteDeployHook<TB> + Send + 'static> RemoteDeployHook<TB>
+  for RemoteWatchHookShared<TB, P>
+{
+  fn deploy_child(&mut self, parent: Pid, props: &PropsGeneric<TB>) -> Result<ActorRefGeneric<TB>, SpawnError> {
+    self.inner.lock().deploy_child(parent, props)
+  }
 }
 
 impl<TB: RuntimeToolbox + 'static, P: ActorRefProvider<TB> + RemoteWatchHook<TB> + Send + 'static> ActorRefProvider<TB>
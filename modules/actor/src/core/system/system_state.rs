@@ -25,8 +25,8 @@ use hashbrown::HashMap;
 use portable_atomic::{AtomicBool, AtomicU64, Ordering};
 
 use super::{
-  ActorPathRegistry, ActorRefProvider, AuthorityState, GuardianKind, RemoteAuthorityError,
-  RemoteAuthorityManagerGeneric, RemoteWatchHook, RemotingConfig,
+  ActorNodeSnapshot, ActorPathRegistry, ActorRefProvider, ActorTreeSnapshot, AuthorityState, GuardianKind,
+  RemoteAuthorityError, RemoteAuthorityManagerGeneric, RemoteDeployHook, RemoteWatchHook, RemotingConfig,
 };
 use crate::core::{
   actor_prim::{
@@ -34,15 +34,22 @@ use crate::core::{
     actor_path::{ActorPath, ActorPathParser, ActorPathParts, ActorPathScheme, GuardianKind as PathGuardianKind},
     actor_ref::ActorRefGeneric,
   },
-  dead_letter::{DeadLetterEntryGeneric, DeadLetterGeneric, DeadLetterReason},
+  dead_letter::{
+    DeadLetterConfig, DeadLetterEntryGeneric, DeadLetterGeneric, DeadLetterQuery, DeadLetterReason,
+    DeadLetterSuppression,
+  },
   dispatcher::DispatchersGeneric,
   error::{ActorError, SendError},
   event_stream::{EventStreamEvent, EventStreamGeneric, RemoteAuthorityEvent, TickDriverSnapshot},
   futures::ActorFuture,
   logging::{LogEvent, LogLevel},
   mailbox::MailboxesGeneric,
-  messaging::{AnyMessageGeneric, FailurePayload, SystemMessage},
-  scheduler::{SchedulerContext, TaskRunSummary, TickDriverBootstrap, TickDriverRuntime},
+  messaging::{
+    AnyMessageGeneric, FailurePayload, SystemMessage,
+    message_invoker::{MessageInvokerMiddleware, MiddlewaresGeneric},
+  },
+  props::PropsGeneric,
+  scheduler::{ExecutionTraceGeneric, SchedulerContext, TaskRunSummary, TickDriverBootstrap, TickDriverRuntime},
   spawn::{NameRegistry, NameRegistryError, SpawnError},
   supervision::SupervisorDirective,
   system::{RegisterExtraTopLevelError, ReservationPolicy},
@@ -113,14 +120,19 @@ pub struct SystemStateGeneric<TB: RuntimeToolbox + 'static> {
   actor_ref_provider_callers_by_scheme:
     ToolboxMutex<HashMap<ActorPathScheme, ActorRefProviderCaller<TB>, RandomState>, TB>,
   remote_watch_hook: ToolboxMutex<Option<Box<dyn RemoteWatchHook<TB>>>, TB>,
+  remote_deploy_hook: ToolboxMutex<Option<Box<dyn RemoteDeployHook<TB>>>, TB>,
+  remote_children: ToolboxMutex<HashMap<Pid, Pid, RandomState>, TB>,
   dispatchers: ArcShared<DispatchersGeneric<TB>>,
   mailboxes: ArcShared<MailboxesGeneric<TB>>,
+  middlewares: ArcShared<MiddlewaresGeneric<TB>>,
   path_identity: ToolboxMutex<PathIdentity, TB>,
   actor_path_registry: ToolboxMutex<ActorPathRegistry, TB>,
   remote_authority_mgr: ArcShared<RemoteAuthorityManagerGeneric<TB>>,
   scheduler_context: ToolboxMutex<Option<ArcShared<SchedulerContext<TB>>>, TB>,
   tick_driver_runtime: ToolboxMutex<Option<TickDriverRuntime<TB>>, TB>,
   remoting_config: ToolboxMutex<Option<RemotingConfig>, TB>,
+  execution_trace: ToolboxMutex<Option<ExecutionTraceGeneric<TB>>, TB>,
+  message_middlewares: ToolboxMutex<Vec<ArcShared<dyn MessageInvokerMiddleware<TB>>>, TB>,
 }
 
 /// Type alias for [SystemStateGeneric] with the default [NoStdToolbox].
@@ -130,13 +142,14 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
   /// Creates a fresh state container without any registered actors.
   #[must_use]
   pub fn new() -> Self {
-    const DEAD_LETTER_CAPACITY: usize = 512;
     let event_stream = ArcShared::new(EventStreamGeneric::default());
-    let dead_letter = ArcShared::new(DeadLetterGeneric::new(event_stream.clone(), DEAD_LETTER_CAPACITY));
+    let dead_letter = ArcShared::new(DeadLetterGeneric::with_config(event_stream.clone(), DeadLetterConfig::new()));
     let dispatchers = ArcShared::new(DispatchersGeneric::new());
     dispatchers.ensure_default();
     let mailboxes = ArcShared::new(MailboxesGeneric::new());
     mailboxes.ensure_default();
+    let middlewares = ArcShared::new(MiddlewaresGeneric::new());
+    middlewares.ensure_defaults();
     Self {
       next_pid: AtomicU64::new(0),
       clock: AtomicU64::new(0),
@@ -163,8 +176,11 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
       extensions: <TB::MutexFamily as SyncMutexFamily>::create(HashMap::with_hasher(RandomState::new())),
       actor_ref_providers: <TB::MutexFamily as SyncMutexFamily>::create(HashMap::with_hasher(RandomState::new())),
       remote_watch_hook: <TB::MutexFamily as SyncMutexFamily>::create(None),
+      remote_deploy_hook: <TB::MutexFamily as SyncMutexFamily>::create(None),
+      remote_children: <TB::MutexFamily as SyncMutexFamily>::create(HashMap::with_hasher(RandomState::new())),
       dispatchers,
       mailboxes,
+      middlewares,
       path_identity: <TB::MutexFamily as SyncMutexFamily>::create(PathIdentity::default()),
       actor_path_registry: <TB::MutexFamily as SyncMutexFamily>::create(ActorPathRegistry::new()),
       remote_authority_mgr: ArcShared::new(RemoteAuthorityManagerGeneric::new()),
@@ -174,6 +190,8 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
       scheduler_context: <TB::MutexFamily as SyncMutexFamily>::create(None),
       tick_driver_runtime: <TB::MutexFamily as SyncMutexFamily>::create(None),
       remoting_config: <TB::MutexFamily as SyncMutexFamily>::create(None),
+      execution_trace: <TB::MutexFamily as SyncMutexFamily>::create(None),
+      message_middlewares: <TB::MutexFamily as SyncMutexFamily>::create(Vec::new()),
     }
   }
 
@@ -205,6 +223,13 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
       }
     }
 
+    *self.execution_trace.lock() = config.execution_trace().cloned();
+    self.dead_letter.configure(config.dead_letter_config());
+    *self.message_middlewares.lock() = config.message_middlewares().to_vec();
+    for (name, middleware) in config.named_middlewares() {
+      self.middlewares.register_or_update(name.clone(), middleware.clone());
+    }
+
     // Register default dispatcher if configured
     if let Some(dispatcher_config) = config.default_dispatcher_config() {
       // Overwrite the "default" entry using register_or_update
@@ -222,6 +247,18 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
     self.register_actor_path(pid);
   }
 
+  /// Returns the pids of the actors currently watched by `watcher`.
+  #[cfg(any(test, feature = "test-support"))]
+  pub(crate) fn watched_by(&self, watcher: Pid) -> Vec<Pid> {
+    self
+      .cells
+      .lock()
+      .values()
+      .filter(|cell| cell.watchers_snapshot().contains(&watcher))
+      .map(|cell| cell.pid())
+      .collect()
+  }
+
   /// Removes the actor cell associated with the pid.
   pub(crate) fn remove_cell(&self, pid: &Pid) -> Option<ArcShared<ActorCellGeneric<TB>>> {
     let reservation_source = {
@@ -519,6 +556,34 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
     Some(path)
   }
 
+  /// Captures the live actor hierarchy together with per-actor mailbox and processing stats.
+  #[must_use]
+  pub fn snapshot_tree(&self) -> ActorTreeSnapshot {
+    ActorTreeSnapshot::new(self.root_guardian().map(|root| self.snapshot_node(&root)))
+  }
+
+  fn snapshot_node(&self, cell: &ActorCellGeneric<TB>) -> ActorNodeSnapshot {
+    let pid = cell.pid();
+    let mailbox = cell.mailbox();
+    // 停止中の子はスナップショット取得の途中でセルが外れることがあるため、見つかったものだけを辿る
+    let children =
+      cell.children().iter().filter_map(|child| self.cell(child)).map(|child| self.snapshot_node(&child)).collect();
+    ActorNodeSnapshot {
+      pid,
+      path: self.actor_path(&pid).map(|path| path.to_relative_string()).unwrap_or_default(),
+      actor_type: cell.actor_type(),
+      props_name: cell.props_name().map(String::from),
+      dispatcher_id: cell.dispatcher_id().map(String::from),
+      mailbox_id: cell.mailbox_id().map(String::from),
+      user_queue_len: mailbox.user_len(),
+      system_queue_len: mailbox.system_len(),
+      processed: cell.processed_count(),
+      restarts: cell.restart_count(),
+      last_failure: cell.last_failure(),
+      children,
+    }
+  }
+
   /// Returns the shared event stream handle.
   #[must_use]
   pub fn event_stream(&self) -> ArcShared<EventStreamGeneric<TB>> {
@@ -531,6 +596,25 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
     self.dead_letter.entries()
   }
 
+  /// Returns the retained deadletter entries matching `query`.
+  #[must_use]
+  pub fn query_dead_letters(&self, query: &DeadLetterQuery) -> Vec<DeadLetterEntryGeneric<TB>> {
+    self.dead_letter.query(query)
+  }
+
+  /// Returns the number of deadletters recorded per reason since the system started.
+  #[must_use]
+  pub fn dead_letter_counts(&self) -> Vec<(DeadLetterReason, u64)> {
+    self.dead_letter.counts()
+  }
+
+  /// Suppresses the logging of deadletters carrying a `T` payload.
+  pub fn register_dead_letter_suppression<T>(&self)
+  where
+    T: DeadLetterSuppression, {
+    self.dead_letter.register_suppression::<T>();
+  }
+
   /// Registers an ask future so the actor system can track its completion.
   pub(crate) fn register_ask_future(&self, future: ArcShared<ActorFuture<AnyMessageGeneric<TB>, TB>>) {
     self.ask_futures.lock().push(future);
@@ -608,6 +692,52 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
     *guard = Some(hook);
   }
 
+  pub(crate) fn register_remote_deploy_hook(&self, hook: Box<dyn RemoteDeployHook<TB>>) {
+    let mut guard = self.remote_deploy_hook.lock();
+    *guard = Some(hook);
+  }
+
+  /// Forwards a remote deployment to the registered hook.
+  ///
+  /// The deployed child is registered under `parent` so that it is stopped together with the
+  /// parent and its failures are supervised by the parent.
+  pub(crate) fn deploy_remote_child(
+    &self,
+    parent: Pid,
+    props: &PropsGeneric<TB>,
+  ) -> Result<ActorRefGeneric<TB>, SpawnError> {
+    let child = {
+      let mut guard = self.remote_deploy_hook.lock();
+      match guard.as_mut() {
+        | Some(hook) => hook.deploy_child(parent, props)?,
+        | None => return Err(SpawnError::invalid_props("remote deployment requires a remoting provider")),
+      }
+    };
+    self.remote_children.lock().insert(child.pid(), parent);
+    self.register_child(parent, child.pid());
+    Ok(child)
+  }
+
+  /// Releases a remotely deployed child after the hosting node reported its termination.
+  ///
+  /// Returns the supervising parent when the child was still registered.
+  pub(crate) fn remove_remote_child(&self, child: Pid) -> Option<Pid> {
+    let parent = self.remote_children.lock().remove(&child)?;
+    self.unregister_child(Some(parent), child);
+    Some(parent)
+  }
+
+  fn remote_parent_of(&self, child: &Pid) -> Option<Pid> {
+    self.remote_children.lock().get(child).copied()
+  }
+
+  fn stop_remote_child(&self, child: Pid) {
+    let mut guard = self.remote_deploy_hook.lock();
+    if let Some(hook) = guard.as_mut() {
+      hook.stop_child(child);
+    }
+  }
+
   pub(crate) fn actor_ref_provider<P>(&self) -> Option<ArcShared<P>>
   where
     P: Any + Send + Sync + 'static, {
@@ -624,6 +754,11 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
     guard.get(&scheme).map(|caller| caller(path))
   }
 
+  /// Returns the remote path of a PID allocated by a remote actor-ref provider.
+  pub(crate) fn remote_actor_path(&self, pid: &Pid) -> Option<ActorPath> {
+    self.remote_watch_hook.lock().as_ref().and_then(|hook| hook.remote_path(*pid))
+  }
+
   fn forward_remote_watch(&self, target: Pid, watcher: Pid) -> bool {
     let mut guard = self.remote_watch_hook.lock();
     guard.as_mut().is_some_and(|hook| hook.handle_watch(target, watcher))
@@ -681,6 +816,13 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
         },
         | SystemMessage::Terminated(_) => Ok(()),
         | SystemMessage::PipeTask(_) => Ok(()),
+        // リモート配備された子は配備先ノードに停止を依頼する
+        | SystemMessage::Stop if self.remote_parent_of(&pid).is_some() => {
+          self.stop_remote_child(pid);
+          Ok(())
+        },
+        // 再起動は配備先ノードの監督下で既に行われているため、ここでは何もしない
+        | SystemMessage::Recreate if self.remote_parent_of(&pid).is_some() => Ok(()),
         | other => Err(SendError::<TB>::closed(AnyMessageGeneric::new(other))),
       }
     }
@@ -760,12 +902,36 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
     self.mailboxes.clone()
   }
 
+  /// Returns the middleware registry.
+  #[must_use]
+  pub fn middlewares(&self) -> ArcShared<MiddlewaresGeneric<TB>> {
+    self.middlewares.clone()
+  }
+
   /// Returns the remoting configuration when it has been configured.
   #[must_use]
   pub fn remoting_config(&self) -> Option<RemotingConfig> {
     self.remoting_config.lock().clone()
   }
 
+  /// Returns the execution trace configured for the system, if any.
+  #[must_use]
+  pub fn execution_trace(&self) -> Option<ExecutionTraceGeneric<TB>> {
+    self.execution_trace.lock().clone()
+  }
+
+  /// Returns the middleware applied to every actor of the system.
+  pub(crate) fn message_middlewares(&self) -> Vec<ArcShared<dyn MessageInvokerMiddleware<TB>>> {
+    self.message_middlewares.lock().clone()
+  }
+
+  /// Lets the system-wide middleware decorate a message about to be sent.
+  pub(crate) fn before_send(&self, message: &mut AnyMessageGeneric<TB>) {
+    for middleware in self.message_middlewares.lock().iter() {
+      middleware.before_send(message);
+    }
+  }
+
   /// Installs the scheduler service handle.
   pub fn install_scheduler_context(&self, context: ArcShared<SchedulerContext<TB>>) {
     let mut guard = self.scheduler_context.lock();
@@ -808,7 +974,7 @@ impl<TB: RuntimeToolbox + 'static> SystemStateGeneric<TB> {
     let message = format!("actor {:?} failed: {}", payload.child(), payload.reason().as_str());
     self.emit_log(LogLevel::Error, message, Some(payload.child()));
 
-    if let Some(parent_pid) = self.parent_of(&payload.child())
+    if let Some(parent_pid) = self.parent_of(&payload.child()).or_else(|| self.remote_parent_of(&payload.child()))
       && let Some(parent_cell) = self.cell(&parent_pid)
     {
       if let Some(stats) = parent_cell.snapshot_child_restart_stats(payload.child()) {
@@ -13,6 +13,7 @@ use crate::core::{
     actor_path::{ActorPath, ActorPathScheme, ActorUid, GuardianKind as PathGuardianKind, PathResolutionError},
     actor_ref::ActorRefGeneric,
   },
+  dead_letter::{DeadLetterConfig, DeadLetterQuery, DeadLetterReason},
   error::ActorError,
   event_stream::{EventStream, EventStreamEvent, EventStreamSubscriber, subscriber_handle},
   messaging::{AnyMessage, AnyMessageViewGeneric},
@@ -255,6 +256,21 @@ fn system_state_deadletters() {
   assert_eq!(dead_letters.len(), 0);
 }
 
+#[test]
+fn system_state_applies_dead_letter_config() {
+  let state = SystemState::new();
+  let config = ActorSystemConfig::default().with_dead_letter_config(DeadLetterConfig::new().with_capacity(1));
+  state.apply_actor_system_config(&config);
+  let pid = state.allocate_pid();
+
+  state.record_dead_letter(AnyMessage::new(1_u32), DeadLetterReason::ExplicitRouting, Some(pid));
+  state.record_dead_letter(AnyMessage::new(2_u32), DeadLetterReason::ExplicitRouting, None);
+
+  assert_eq!(state.dead_letters().len(), 1);
+  assert!(state.query_dead_letters(&DeadLetterQuery::new().with_recipient(pid)).is_empty());
+  assert!(state.dead_letter_counts().contains(&(DeadLetterReason::ExplicitRouting, 2)));
+}
+
 #[test]
 fn system_state_register_ask_future() {
   use fraktor_utils_rs::core::sync::ArcShared;
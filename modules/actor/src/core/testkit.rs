@@ -0,0 +1,28 @@
+//! Deterministic test toolkit driven by virtual time.
+//!
+//! [`ActorTestKit`] hosts an actor system backed by a manual tick driver, so every timeout in
+//! the kit is measured in scheduler ticks instead of wall-clock time. Messages are dispatched
+//! inline, which lets probes and [`BehaviorTestKit`] observe the effects of a message as soon as
+//! it has been sent.
+
+mod actor_test_kit;
+mod behavior_effect;
+mod behavior_test_kit;
+mod event_stream_probe;
+mod fishing_outcome;
+mod probe_actor;
+mod probe_command;
+mod probe_sender;
+mod test_kit_error;
+mod test_probe;
+mod typed_test_probe;
+mod virtual_clock;
+
+pub use actor_test_kit::{ActorTestKit, ActorTestKitGeneric};
+pub use behavior_effect::{BehaviorEffect, BehaviorEffectGeneric};
+pub use behavior_test_kit::{BehaviorTestKit, BehaviorTestKitGeneric};
+pub use event_stream_probe::{EventStreamProbe, EventStreamProbeGeneric};
+pub use fishing_outcome::FishingOutcome;
+pub use test_kit_error::TestKitError;
+pub use test_probe::{TestProbe, TestProbeGeneric};
+pub use typed_test_probe::{TypedTestProbe, TypedTestProbeGeneric};
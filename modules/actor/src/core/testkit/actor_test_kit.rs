@@ -0,0 +1,168 @@
+//! Actor system harness running on virtual time.
+
+#[cfg(test)]
+mod tests;
+
+use core::time::Duration;
+
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdToolbox, RuntimeToolbox},
+  sync::sync_mutex_like::SyncMutexLike,
+};
+
+use super::{
+  event_stream_probe::EventStreamProbeGeneric, test_probe::TestProbeGeneric, typed_test_probe::TypedTestProbeGeneric,
+  virtual_clock::VirtualClock,
+};
+use crate::core::{
+  actor_prim::{Actor, ActorContextGeneric, ChildRefGeneric},
+  error::{ActorError, SendError},
+  messaging::AnyMessageViewGeneric,
+  props::PropsGeneric,
+  scheduler::{ManualTestDriver, ManualTickController, SchedulerConfig, TickDriverConfig},
+  spawn::SpawnError,
+  system::{ActorSystemConfigGeneric, ActorSystemGeneric},
+  typed::{TypedChildRefGeneric, TypedPropsGeneric},
+};
+
+/// Hosts an actor system driven by a [`ManualTestDriver`] for deterministic tests.
+///
+/// Actors spawned through the kit run under the kit guardian (`/user/testkit/<name>`) and process
+/// messages inline.
+/// Time only moves when the kit or one of its probes advances it, so timers and timeouts behave
+/// identically on every run.
+pub struct ActorTestKitGeneric<TB: RuntimeToolbox + 'static> {
+  system: ActorSystemGeneric<TB>,
+  clock:  VirtualClock<TB>,
+}
+
+/// Type alias for [ActorTestKitGeneric] with the default [NoStdToolbox].
+pub type ActorTestKit = ActorTestKitGeneric<NoStdToolbox>;
+
+struct TestKitGuardian;
+
+impl<TB: RuntimeToolbox + 'static> Actor<TB> for TestKitGuardian {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, TB>,
+    _message: AnyMessageViewGeneric<'_, TB>,
+  ) -> Result<(), ActorError> {
+    Ok(())
+  }
+}
+
+impl<TB: RuntimeToolbox + Default + 'static> ActorTestKitGeneric<TB> {
+  /// Creates a kit with the default actor system configuration.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`SpawnError`] when the actor system cannot be started.
+  pub fn new() -> Result<Self, SpawnError> {
+    Self::with_config(ActorSystemConfigGeneric::default())
+  }
+
+  /// Creates a kit from `config`, replacing its tick driver with a manual one.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`SpawnError`] when the actor system cannot be started.
+  pub fn with_config(config: ActorSystemConfigGeneric<TB>) -> Result<Self, SpawnError> {
+    let driver = ManualTestDriver::new();
+    let controller = driver.controller();
+    let config = config.with_tick_driver(TickDriverConfig::manual(driver));
+    let guardian = PropsGeneric::from_fn(|| TestKitGuardian).with_name(Self::GUARDIAN_NAME);
+    let system = ActorSystemGeneric::new_with_config(&guardian, &config)?;
+    let resolution = system
+      .scheduler_context()
+      .map_or_else(|| SchedulerConfig::default().resolution(), |context| context.scheduler().lock().resolution());
+    Ok(Self { system, clock: VirtualClock::new(controller, resolution) })
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> ActorTestKitGeneric<TB> {
+  /// Name of the guardian under which the kit spawns actors.
+  pub const GUARDIAN_NAME: &'static str = "testkit";
+
+  /// Returns the actor system hosted by the kit.
+  #[must_use]
+  pub const fn system(&self) -> &ActorSystemGeneric<TB> {
+    &self.system
+  }
+
+  /// Returns the controller of the manual tick driver.
+  #[must_use]
+  pub const fn controller(&self) -> &ManualTickController<TB> {
+    self.clock.controller()
+  }
+
+  /// Returns the virtual duration of one scheduler tick.
+  #[must_use]
+  pub const fn resolution(&self) -> Duration {
+    self.clock.resolution()
+  }
+
+  /// Spawns an actor under the user guardian.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`SpawnError`] when the actor cannot be spawned.
+  pub fn spawn(&self, props: &PropsGeneric<TB>) -> Result<ChildRefGeneric<TB>, SpawnError> {
+    self.system.spawn(props)
+  }
+
+  /// Spawns a typed actor under the user guardian.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`SpawnError`] when the actor cannot be spawned.
+  pub fn spawn_typed<M>(&self, props: &TypedPropsGeneric<M, TB>) -> Result<TypedChildRefGeneric<M, TB>, SpawnError>
+  where
+    M: Send + Sync + 'static, {
+    self.spawn(props.to_untyped()).map(TypedChildRefGeneric::from_untyped)
+  }
+
+  /// Creates an untyped probe.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`SpawnError`] when the probe actor cannot be spawned.
+  pub fn create_probe(&self) -> Result<TestProbeGeneric<TB>, SpawnError> {
+    TestProbeGeneric::spawn(&self.system, self.clock.clone())
+  }
+
+  /// Creates a probe accepting messages of type `M`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`SpawnError`] when the probe actor cannot be spawned.
+  pub fn create_typed_probe<M>(&self) -> Result<TypedTestProbeGeneric<M, TB>, SpawnError>
+  where
+    M: Clone + Send + Sync + 'static, {
+    self.create_probe().map(TypedTestProbeGeneric::from_untyped)
+  }
+
+  /// Creates a probe recording the events published from now on.
+  #[must_use]
+  pub fn create_event_stream_probe(&self) -> EventStreamProbeGeneric<TB> {
+    EventStreamProbeGeneric::subscribe(&self.system, self.clock.clone())
+  }
+
+  /// Advances virtual time by `duration`, rounded up to whole ticks.
+  pub fn advance(&self, duration: Duration) {
+    self.clock.advance(duration);
+  }
+
+  /// Advances virtual time by `ticks` scheduler ticks.
+  pub fn advance_ticks(&self, ticks: u32) {
+    self.clock.advance_ticks(ticks);
+  }
+
+  /// Terminates the hosted actor system.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error when the termination request cannot be delivered.
+  pub fn shutdown(&self) -> Result<(), SendError<TB>> {
+    self.system.terminate()
+  }
+}
@@ -0,0 +1,90 @@
+use core::time::Duration;
+
+use crate::core::{
+  event_stream::EventStreamEvent,
+  lifecycle::LifecycleStage,
+  messaging::AnyMessageGeneric,
+  scheduler::SchedulerCommand,
+  testkit::{ActorTestKit, TestKitError},
+  typed::{Behaviors, TypedPropsGeneric},
+};
+
+#[test]
+fn scheduled_message_arrives_only_after_virtual_time_advances() {
+  let kit = ActorTestKit::new().expect("kit");
+  let probe = kit.create_probe().expect("probe");
+  let context = kit.system().scheduler_context().expect("scheduler");
+  context
+    .scheduler()
+    .lock()
+    .schedule_once(Duration::from_millis(100), SchedulerCommand::SendMessage {
+      receiver:   probe.actor_ref().clone(),
+      message:    AnyMessageGeneric::new(9_u32),
+      dispatcher: None,
+      sender:     None,
+    })
+    .expect("schedule");
+
+  probe.expect_no_message(Duration::from_millis(50)).expect("too early");
+  probe.expect_message(&9_u32, Duration::from_millis(100)).expect("fired");
+}
+
+#[test]
+fn advance_fires_timers_without_a_probe_waiting() {
+  let kit = ActorTestKit::new().expect("kit");
+  let probe = kit.create_probe().expect("probe");
+  let context = kit.system().scheduler_context().expect("scheduler");
+  context
+    .scheduler()
+    .lock()
+    .schedule_once(Duration::from_millis(30), SchedulerCommand::SendMessage {
+      receiver:   probe.actor_ref().clone(),
+      message:    AnyMessageGeneric::new("tick"),
+      dispatcher: None,
+      sender:     None,
+    })
+    .expect("schedule");
+
+  kit.advance(Duration::from_millis(30));
+  assert_eq!(probe.pending(), 1);
+}
+
+#[test]
+fn typed_probe_decodes_replies_of_a_typed_actor() {
+  let kit = ActorTestKit::new().expect("kit");
+  let probe = kit.create_typed_probe::<u32>().expect("probe");
+  let reply_to = probe.actor_ref();
+  let doubler = kit
+    .spawn_typed(&TypedPropsGeneric::from_behavior_factory(move || {
+      let reply_to = reply_to.clone();
+      Behaviors::receive_message(move |_ctx, value: &u32| {
+        reply_to.tell(value * 2).expect("reply");
+        Ok(Behaviors::same())
+      })
+    }))
+    .expect("doubler");
+
+  doubler.tell(21).expect("tell");
+  assert_eq!(probe.receive_message(Duration::from_millis(10)).expect("reply"), 42);
+
+  probe.as_untyped().actor_ref().tell(AnyMessageGeneric::new("text")).expect("tell");
+  assert!(matches!(probe.receive_message(Duration::from_millis(10)), Err(TestKitError::Unexpected(_))));
+}
+
+#[test]
+fn event_stream_probe_observes_lifecycle_events() {
+  let kit = ActorTestKit::new().expect("kit");
+  let events = kit.create_event_stream_probe();
+  let probe = kit.create_probe().expect("probe");
+
+  let started = events
+    .expect_event(Duration::from_millis(10), |event| {
+      matches!(event, EventStreamEvent::Lifecycle(lifecycle)
+        if lifecycle.pid() == probe.pid() && lifecycle.stage() == LifecycleStage::Started)
+    })
+    .expect("started");
+  assert!(matches!(started, EventStreamEvent::Lifecycle(_)));
+  events
+    .expect_no_event(Duration::from_millis(10), |event| matches!(event, EventStreamEvent::Lifecycle(_)))
+    .expect("no further lifecycle events");
+}
@@ -0,0 +1,55 @@
+//! Side effects recorded by the behavior test kit.
+
+use alloc::string::String;
+use core::time::Duration;
+
+use fraktor_utils_rs::core::runtime_toolbox::{NoStdToolbox, RuntimeToolbox};
+
+use crate::core::{
+  actor_prim::{Pid, actor_ref::ActorRefGeneric},
+  messaging::AnyMessageGeneric,
+  scheduler::SchedulerMode,
+};
+
+/// Side effect performed by the behavior under test while handling a step.
+#[derive(Clone, Debug)]
+pub enum BehaviorEffectGeneric<TB: RuntimeToolbox + 'static> {
+  /// A child actor was spawned.
+  Spawned {
+    /// Pid of the new child.
+    child: Pid,
+    /// Name assigned to the child.
+    name:  String,
+  },
+  /// A child actor stopped.
+  Stopped {
+    /// Pid of the stopped child.
+    child: Pid,
+  },
+  /// The actor started watching `target`.
+  Watched {
+    /// Pid of the watched actor.
+    target: Pid,
+  },
+  /// The actor stopped watching `target`.
+  Unwatched {
+    /// Pid of the actor no longer watched.
+    target: Pid,
+  },
+  /// A message was scheduled on the actor system scheduler.
+  Scheduled {
+    /// Raw identifier of the scheduler handle.
+    handle_id: u64,
+    /// Receiver of the scheduled message.
+    receiver:  ActorRefGeneric<TB>,
+    /// Scheduled message.
+    message:   AnyMessageGeneric<TB>,
+    /// Virtual delay until the first delivery.
+    delay:     Duration,
+    /// Scheduling mode of the job.
+    mode:      SchedulerMode,
+  },
+}
+
+/// Type alias for [BehaviorEffectGeneric] with the default [NoStdToolbox].
+pub type BehaviorEffect = BehaviorEffectGeneric<NoStdToolbox>;
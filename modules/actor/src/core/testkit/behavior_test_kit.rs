@@ -0,0 +1,250 @@
+//! Synchronous harness for typed behaviors.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{collections::VecDeque, format, vec::Vec};
+use core::time::Duration;
+
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdToolbox, RuntimeToolbox, SyncMutexFamily, ToolboxMutex},
+  sync::{ArcShared, sync_mutex_like::SyncMutexLike},
+};
+
+use super::{
+  actor_test_kit::ActorTestKitGeneric, behavior_effect::BehaviorEffectGeneric, test_kit_error::TestKitError,
+};
+use crate::core::{
+  actor_prim::Pid,
+  event_stream::{EventStreamEvent, EventStreamSubscriber, EventStreamSubscriptionGeneric, subscriber_handle},
+  lifecycle::{LifecycleEvent, LifecycleStage},
+  spawn::SpawnError,
+  typed::{Behavior, TypedActorRefGeneric, TypedChildRefGeneric, TypedPropsGeneric},
+};
+
+type LifecycleQueue<TB> = ArcShared<ToolboxMutex<VecDeque<LifecycleEvent>, TB>>;
+
+/// Runs a typed [`Behavior`] step by step and records the effects of every step.
+///
+/// Messages are processed inline, so each call to [`run`](Self::run) returns once the behavior
+/// has handled the message. Spawned and stopped children are taken from the lifecycle events of
+/// the step, so a child that lives within a single message is still reported. Watches and
+/// scheduled messages are detected by comparing the actor system before and after the step; a
+/// scheduled message counts as an effect when the behavior is its sender, or when it has no sender
+/// and is not addressed to one of the behavior's descendants, whose own timers are not effects of
+/// the behavior.
+pub struct BehaviorTestKitGeneric<M, TB>
+where
+  M: Send + Sync + 'static,
+  TB: RuntimeToolbox + 'static, {
+  kit:           ActorTestKitGeneric<TB>,
+  actor:         TypedChildRefGeneric<M, TB>,
+  lifecycle:     LifecycleQueue<TB>,
+  _subscription: EventStreamSubscriptionGeneric<TB>,
+  children:      Vec<Pid>,
+  descendants:   Vec<Pid>,
+  watched:       Vec<Pid>,
+  scheduled:     Vec<u64>,
+  effects:       VecDeque<BehaviorEffectGeneric<TB>>,
+}
+
+/// Type alias for [BehaviorTestKitGeneric] with the default [NoStdToolbox].
+pub type BehaviorTestKit<M> = BehaviorTestKitGeneric<M, NoStdToolbox>;
+
+struct LifecycleRecorder<TB: RuntimeToolbox + 'static> {
+  events: LifecycleQueue<TB>,
+}
+
+impl<TB: RuntimeToolbox + 'static> EventStreamSubscriber<TB> for LifecycleRecorder<TB> {
+  fn on_event(&mut self, event: &EventStreamEvent<TB>) {
+    if let EventStreamEvent::Lifecycle(event) = event {
+      self.events.lock().push_back(event.clone());
+    }
+  }
+}
+
+impl<M, TB> BehaviorTestKitGeneric<M, TB>
+where
+  M: Send + Sync + 'static,
+  TB: RuntimeToolbox + Default + 'static,
+{
+  /// Spawns the behavior produced by `factory` and records the effects of its setup.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`SpawnError`] when the actor system or the behavior cannot be started.
+  pub fn new<F>(factory: F) -> Result<Self, SpawnError>
+  where
+    F: Fn() -> Behavior<M, TB> + Send + Sync + 'static, {
+    let kit = ActorTestKitGeneric::new()?;
+    // セットアップ中に生成された子も記録するため、spawn より前に購読する
+    let lifecycle: LifecycleQueue<TB> = ArcShared::new(<TB::MutexFamily as SyncMutexFamily>::create(VecDeque::new()));
+    let subscriber = subscriber_handle(LifecycleRecorder::<TB> { events: lifecycle.clone() });
+    let subscription = kit.system().subscribe_event_stream(&subscriber);
+    let actor = kit.spawn_typed(&TypedPropsGeneric::from_behavior_factory(factory))?;
+    let mut behavior_kit = Self {
+      kit,
+      actor,
+      lifecycle,
+      _subscription: subscription,
+      children: Vec::new(),
+      descendants: Vec::new(),
+      watched: Vec::new(),
+      scheduled: Vec::new(),
+      effects: VecDeque::new(),
+    };
+    behavior_kit.record_effects();
+    Ok(behavior_kit)
+  }
+}
+
+impl<M, TB> BehaviorTestKitGeneric<M, TB>
+where
+  M: Send + Sync + 'static,
+  TB: RuntimeToolbox + 'static,
+{
+  /// Delivers `message` to the behavior and records the effects of handling it.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::SendFailed`] when the behavior is no longer running.
+  pub fn run(&mut self, message: M) -> Result<(), TestKitError> {
+    let result = self.actor.tell(message).map_err(|error| TestKitError::SendFailed(format!("{error:?}")));
+    self.record_effects();
+    result
+  }
+
+  /// Advances virtual time by `duration` and records the effects of the fired timers.
+  pub fn advance(&mut self, duration: Duration) {
+    self.kit.advance(duration);
+    self.record_effects();
+  }
+
+  /// Removes and returns every recorded effect in occurrence order.
+  #[must_use]
+  pub fn retrieve_effects(&mut self) -> Vec<BehaviorEffectGeneric<TB>> {
+    self.effects.drain(..).collect()
+  }
+
+  /// Removes and returns the first recorded effect satisfying `predicate`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Unexpected`] when no recorded effect matches.
+  pub fn expect_effect<F>(&mut self, predicate: F) -> Result<BehaviorEffectGeneric<TB>, TestKitError>
+  where
+    F: FnMut(&BehaviorEffectGeneric<TB>) -> bool, {
+    let index = self.effects.iter().position(predicate).ok_or_else(|| {
+      TestKitError::Unexpected(format!("no matching effect among {} recorded effects", self.effects.len()))
+    })?;
+    self.effects.remove(index).ok_or_else(|| TestKitError::Unexpected("recorded effect vanished".into()))
+  }
+
+  /// Returns `true` when effects are recorded but not retrieved yet.
+  #[must_use]
+  pub fn has_effects(&self) -> bool {
+    !self.effects.is_empty()
+  }
+
+  /// Returns the reference of the behavior under test.
+  #[must_use]
+  pub fn self_ref(&self) -> TypedActorRefGeneric<M, TB> {
+    self.actor.actor_ref()
+  }
+
+  /// Returns the pid of the behavior under test.
+  #[must_use]
+  pub const fn pid(&self) -> Pid {
+    self.actor.pid()
+  }
+
+  /// Returns `true` while the behavior has not stopped.
+  #[must_use]
+  pub fn is_alive(&self) -> bool {
+    self.kit.system().state().cell(&self.pid()).is_some()
+  }
+
+  /// Returns the kit hosting the behavior, e.g. to create probes.
+  #[must_use]
+  pub const fn kit(&self) -> &ActorTestKitGeneric<TB> {
+    &self.kit
+  }
+
+  fn record_effects(&mut self) {
+    let state = self.kit.system().state();
+    let pid = self.pid();
+
+    let events: Vec<LifecycleEvent> = self.lifecycle.lock().drain(..).collect();
+    for event in events {
+      let child = event.pid();
+      let is_child = event.parent() == Some(pid);
+      match event.stage() {
+        | LifecycleStage::Started => {
+          if event.parent().is_some_and(|parent| parent == pid || self.descendants.contains(&parent)) {
+            self.descendants.push(child);
+          }
+          if is_child {
+            self.children.push(child);
+            self.effects.push_back(BehaviorEffectGeneric::Spawned { child, name: event.name().into() });
+          }
+        },
+        // 再起動時にも Stopped が発行されるため、セルが残っている子は停止とみなさない
+        | LifecycleStage::Stopped if is_child && state.cell(&child).is_none() => {
+          if let Some(index) = self.children.iter().position(|known| *known == child) {
+            self.children.remove(index);
+            self.effects.push_back(BehaviorEffectGeneric::Stopped { child });
+          }
+        },
+        | LifecycleStage::Stopped | LifecycleStage::Restarted => {},
+      }
+    }
+    // post_stop が失敗した子は Stopped を発行しないため、消えた子も停止として扱う
+    let live = state.child_pids(pid);
+    let (kept, gone): (Vec<Pid>, Vec<Pid>) = self.children.iter().partition(|child| live.contains(child));
+    for child in gone {
+      self.effects.push_back(BehaviorEffectGeneric::Stopped { child });
+    }
+    self.children = kept;
+
+    let watched = state.watched_by(pid);
+    for target in watched.iter().filter(|target| !self.watched.contains(target)) {
+      self.effects.push_back(BehaviorEffectGeneric::Watched { target: *target });
+    }
+    // 監視対象が停止した場合は監視解除ではないため、生存している対象のみ Unwatched とする
+    for target in self.watched.iter().filter(|target| !watched.contains(target)) {
+      if state.cell(target).is_some() {
+        self.effects.push_back(BehaviorEffectGeneric::Unwatched { target: *target });
+      }
+    }
+    self.watched = watched;
+
+    let Some(context) = self.kit.system().scheduler_context() else {
+      return;
+    };
+    let scheduler = context.scheduler();
+    let scheduler = scheduler.lock();
+    let current_tick = scheduler.dump().current_tick();
+    let pending = scheduler.pending_messages();
+    for (job, receiver, message, sender) in &pending {
+      if self.scheduled.contains(&job.handle_id()) {
+        continue;
+      }
+      let issued_by_behavior = match sender {
+        | Some(sender) => *sender == pid,
+        | None => !self.descendants.contains(&receiver.pid()),
+      };
+      if !issued_by_behavior {
+        continue;
+      }
+      let ticks = u32::try_from(job.deadline_tick().saturating_sub(current_tick)).unwrap_or(u32::MAX);
+      self.effects.push_back(BehaviorEffectGeneric::Scheduled {
+        handle_id: job.handle_id(),
+        receiver:  receiver.clone(),
+        message:   message.clone(),
+        delay:     self.kit.resolution().saturating_mul(ticks),
+        mode:      job.mode(),
+      });
+    }
+    self.scheduled = pending.iter().map(|(job, ..)| job.handle_id()).collect();
+  }
+}
@@ -0,0 +1,121 @@
+use core::time::Duration;
+
+use fraktor_utils_rs::core::runtime_toolbox::NoStdToolbox;
+
+use crate::core::{
+  error::ActorError,
+  scheduler::SchedulerMode,
+  testkit::{BehaviorEffect, BehaviorTestKit},
+  typed::{Behavior, Behaviors, TypedPropsGeneric},
+};
+
+enum Command {
+  SpawnWorker,
+  SpawnTransient,
+  SpawnTicker,
+  Remind,
+  Stop,
+}
+
+fn ticker() -> Behavior<u32, NoStdToolbox> {
+  Behaviors::setup(|ctx| {
+    let scheduler = ctx.system().scheduler_context().expect("scheduler");
+    let self_ref = ctx.self_ref();
+    scheduler
+      .with_scheduler(|scheduler| scheduler.schedule_once(Duration::from_millis(10), self_ref.clone(), 1, None, None))
+      .expect("schedule");
+    Behaviors::empty()
+  })
+}
+
+fn parent() -> Behavior<Command, NoStdToolbox> {
+  Behaviors::receive_message(|ctx, command: &Command| {
+    match command {
+      | Command::SpawnWorker => {
+        ctx
+          .spawn_child_watched(&TypedPropsGeneric::<u32, _>::from_behavior_factory(Behaviors::empty))
+          .map_err(|_| ActorError::recoverable("spawn failed"))?;
+      },
+      | Command::SpawnTransient => {
+        let child = ctx
+          .spawn_child(&TypedPropsGeneric::<u32, _>::from_behavior_factory(Behaviors::empty))
+          .map_err(|_| ActorError::recoverable("spawn failed"))?;
+        child.stop().map_err(|_| ActorError::recoverable("stop failed"))?;
+      },
+      | Command::SpawnTicker => {
+        ctx
+          .spawn_child(&TypedPropsGeneric::<u32, _>::from_behavior_factory(ticker))
+          .map_err(|_| ActorError::recoverable("spawn failed"))?;
+      },
+      | Command::Remind => {
+        let scheduler = ctx.system().scheduler_context().expect("scheduler");
+        let self_ref = ctx.self_ref();
+        scheduler
+          .with_scheduler(|scheduler| {
+            scheduler.schedule_once(Duration::from_millis(40), self_ref.clone(), Command::Stop, None, None)
+          })
+          .map_err(|_| ActorError::recoverable("schedule failed"))?;
+      },
+      | Command::Stop => return Ok(Behaviors::stopped()),
+    }
+    Ok(Behaviors::same())
+  })
+}
+
+#[test]
+fn run_records_spawned_and_watched_children() {
+  let mut kit = BehaviorTestKit::new(parent).expect("kit");
+  assert!(!kit.has_effects());
+
+  kit.run(Command::SpawnWorker).expect("run");
+  let child = match kit.expect_effect(|effect| matches!(effect, BehaviorEffect::Spawned { .. })).expect("spawned") {
+    | BehaviorEffect::Spawned { child, .. } => child,
+    | other => panic!("unexpected effect: {other:?}"),
+  };
+  kit
+    .expect_effect(|effect| matches!(effect, BehaviorEffect::Watched { target } if *target == child))
+    .expect("watched");
+  assert!(!kit.has_effects());
+}
+
+#[test]
+fn scheduled_message_is_recorded_and_fires_on_advance() {
+  let mut kit = BehaviorTestKit::new(parent).expect("kit");
+
+  kit.run(Command::Remind).expect("run");
+  let effects = kit.retrieve_effects();
+  assert_eq!(effects.len(), 1);
+  assert!(matches!(
+    &effects[0],
+    BehaviorEffect::Scheduled { receiver, delay, mode: SchedulerMode::OneShot, .. }
+      if receiver.pid() == kit.pid() && *delay >= Duration::from_millis(40)
+  ));
+
+  assert!(kit.is_alive());
+  kit.advance(Duration::from_millis(50));
+  assert!(!kit.is_alive());
+}
+
+#[test]
+fn child_spawned_and_stopped_within_one_message_is_recorded() {
+  let mut kit = BehaviorTestKit::new(parent).expect("kit");
+
+  kit.run(Command::SpawnTransient).expect("run");
+  let effects = kit.retrieve_effects();
+  assert_eq!(effects.len(), 2, "unexpected effects: {effects:?}");
+  let child = match &effects[0] {
+    | BehaviorEffect::Spawned { child, .. } => *child,
+    | other => panic!("unexpected effect: {other:?}"),
+  };
+  assert!(matches!(&effects[1], BehaviorEffect::Stopped { child: stopped } if *stopped == child));
+}
+
+#[test]
+fn timers_of_children_are_not_attributed_to_the_behavior() {
+  let mut kit = BehaviorTestKit::new(parent).expect("kit");
+
+  kit.run(Command::SpawnTicker).expect("run");
+  let effects = kit.retrieve_effects();
+  assert_eq!(effects.len(), 1, "unexpected effects: {effects:?}");
+  assert!(matches!(&effects[0], BehaviorEffect::Spawned { .. }));
+}
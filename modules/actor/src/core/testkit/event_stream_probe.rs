@@ -0,0 +1,113 @@
+//! Probe recording the events published on the event stream.
+
+use alloc::{collections::VecDeque, format, vec::Vec};
+use core::time::Duration;
+
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdToolbox, RuntimeToolbox, SyncMutexFamily, ToolboxMutex},
+  sync::{ArcShared, sync_mutex_like::SyncMutexLike},
+};
+
+use super::{test_kit_error::TestKitError, virtual_clock::VirtualClock};
+use crate::core::{
+  event_stream::{EventStreamEvent, EventStreamSubscriber, EventStreamSubscriptionGeneric, subscriber_handle},
+  system::ActorSystemGeneric,
+};
+
+type EventQueue<TB> = ArcShared<ToolboxMutex<VecDeque<EventStreamEvent<TB>>, TB>>;
+
+/// Subscribes to the event stream and records events until dropped.
+///
+/// Events are consumed in publication order; the waiting methods advance virtual time like
+/// [`TestProbeGeneric`](super::TestProbeGeneric).
+pub struct EventStreamProbeGeneric<TB: RuntimeToolbox + 'static> {
+  events:        EventQueue<TB>,
+  clock:         VirtualClock<TB>,
+  _subscription: EventStreamSubscriptionGeneric<TB>,
+}
+
+/// Type alias for [EventStreamProbeGeneric] with the default [NoStdToolbox].
+pub type EventStreamProbe = EventStreamProbeGeneric<NoStdToolbox>;
+
+struct EventRecorder<TB: RuntimeToolbox + 'static> {
+  events: EventQueue<TB>,
+}
+
+impl<TB: RuntimeToolbox + 'static> EventStreamSubscriber<TB> for EventRecorder<TB> {
+  fn on_event(&mut self, event: &EventStreamEvent<TB>) {
+    self.events.lock().push_back(event.clone());
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> EventStreamProbeGeneric<TB> {
+  pub(crate) fn subscribe(system: &ActorSystemGeneric<TB>, clock: VirtualClock<TB>) -> Self {
+    let events: EventQueue<TB> = ArcShared::new(<TB::MutexFamily as SyncMutexFamily>::create(VecDeque::new()));
+    let subscriber = subscriber_handle(EventRecorder::<TB> { events: events.clone() });
+    let subscription = system.subscribe_event_stream(&subscriber);
+    Self { events, clock, _subscription: subscription }
+  }
+
+  /// Receives the next event, waiting up to `timeout` of virtual time.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Timeout`] when no event is published in time.
+  pub fn receive_event(&self, timeout: Duration) -> Result<EventStreamEvent<TB>, TestKitError> {
+    self
+      .clock
+      .await_some(timeout, || self.events.lock().pop_front())
+      .ok_or_else(|| TestKitError::Timeout(format!("an event within {timeout:?}")))
+  }
+
+  /// Discards events until one satisfies `predicate`, waiting up to `timeout` of virtual time.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Timeout`] when no matching event is published in time.
+  pub fn expect_event<F>(&self, timeout: Duration, mut predicate: F) -> Result<EventStreamEvent<TB>, TestKitError>
+  where
+    F: FnMut(&EventStreamEvent<TB>) -> bool, {
+    self
+      .clock
+      .await_some(timeout, || {
+        let mut events = self.events.lock();
+        while let Some(event) = events.pop_front() {
+          if predicate(&event) {
+            return Some(event);
+          }
+        }
+        None
+      })
+      .ok_or_else(|| TestKitError::Timeout(format!("a matching event within {timeout:?}")))
+  }
+
+  /// Advances virtual time by `duration` and checks that no event satisfying `predicate` is
+  /// published meanwhile; other events are discarded.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Unexpected`] when a matching event is published.
+  pub fn expect_no_event<F>(&self, duration: Duration, mut predicate: F) -> Result<(), TestKitError>
+  where
+    F: FnMut(&EventStreamEvent<TB>) -> bool, {
+    let matched = self.clock.await_some(duration, || {
+      let mut events = self.events.lock();
+      while let Some(event) = events.pop_front() {
+        if predicate(&event) {
+          return Some(event);
+        }
+      }
+      None
+    });
+    match matched {
+      | Some(_) => Err(TestKitError::Unexpected(format!("published a matching event within {duration:?}"))),
+      | None => Ok(()),
+    }
+  }
+
+  /// Removes and returns every recorded event.
+  #[must_use]
+  pub fn drain_events(&self) -> Vec<EventStreamEvent<TB>> {
+    self.events.lock().drain(..).collect()
+  }
+}
@@ -0,0 +1,14 @@
+//! Decision returned by the closure passed to `fish_for_message`.
+
+use alloc::string::String;
+
+/// Tells a probe how to treat a message while fishing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FishingOutcome {
+  /// The message is the one being fished for; fishing stops and returns it.
+  Complete,
+  /// The message is discarded and fishing continues.
+  Continue,
+  /// The message must not arrive; fishing fails with the given reason.
+  Fail(String),
+}
@@ -0,0 +1,52 @@
+//! Actor backing a probe so that it can take part in death watch.
+
+use alloc::vec::Vec;
+
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{RuntimeToolbox, ToolboxMutex},
+  sync::{ArcShared, sync_mutex_like::SyncMutexLike},
+};
+
+use super::probe_command::ProbeCommand;
+use crate::core::{
+  actor_prim::{Actor, ActorContextGeneric, Pid},
+  error::ActorError,
+  messaging::AnyMessageViewGeneric,
+};
+
+/// Pids whose termination has been observed by a probe.
+pub(crate) type TerminatedLog<TB> = ArcShared<ToolboxMutex<Vec<Pid>, TB>>;
+
+/// Watches actors on behalf of a probe and records their termination.
+pub(crate) struct ProbeActor<TB: RuntimeToolbox + 'static> {
+  terminated: TerminatedLog<TB>,
+}
+
+impl<TB: RuntimeToolbox + 'static> ProbeActor<TB> {
+  pub(crate) const fn new(terminated: TerminatedLog<TB>) -> Self {
+    Self { terminated }
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> Actor<TB> for ProbeActor<TB> {
+  fn receive(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    message: AnyMessageViewGeneric<'_, TB>,
+  ) -> Result<(), ActorError> {
+    match message.downcast_ref::<ProbeCommand<TB>>() {
+      | Some(ProbeCommand::Watch(target)) => {
+        ctx.watch(target).map_err(|_| ActorError::recoverable("probe watch failed"))
+      },
+      | Some(ProbeCommand::Unwatch(target)) => {
+        ctx.unwatch(target).map_err(|_| ActorError::recoverable("probe unwatch failed"))
+      },
+      | None => Ok(()),
+    }
+  }
+
+  fn on_terminated(&mut self, _ctx: &mut ActorContextGeneric<'_, TB>, terminated: Pid) -> Result<(), ActorError> {
+    self.terminated.lock().push(terminated);
+    Ok(())
+  }
+}
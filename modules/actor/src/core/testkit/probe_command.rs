@@ -0,0 +1,11 @@
+//! Control messages understood by the probe actor.
+
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
+use crate::core::actor_prim::actor_ref::ActorRefGeneric;
+
+/// Requests the probe actor to change its death watch registrations.
+pub(crate) enum ProbeCommand<TB: RuntimeToolbox + 'static> {
+  Watch(ActorRefGeneric<TB>),
+  Unwatch(ActorRefGeneric<TB>),
+}
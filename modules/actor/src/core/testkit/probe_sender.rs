@@ -0,0 +1,31 @@
+//! Actor reference sender recording the messages delivered to a probe.
+
+use alloc::collections::VecDeque;
+
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{RuntimeToolbox, ToolboxMutex},
+  sync::{ArcShared, sync_mutex_like::SyncMutexLike},
+};
+
+use crate::core::{actor_prim::actor_ref::ActorRefSender, error::SendError, messaging::AnyMessageGeneric};
+
+/// Queue of the messages received by a probe.
+pub(crate) type ProbeQueue<TB> = ArcShared<ToolboxMutex<VecDeque<AnyMessageGeneric<TB>>, TB>>;
+
+/// Appends every message sent to the probe reference to the probe queue.
+pub(crate) struct ProbeSender<TB: RuntimeToolbox + 'static> {
+  queue: ProbeQueue<TB>,
+}
+
+impl<TB: RuntimeToolbox + 'static> ProbeSender<TB> {
+  pub(crate) const fn new(queue: ProbeQueue<TB>) -> Self {
+    Self { queue }
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> ActorRefSender<TB> for ProbeSender<TB> {
+  fn send(&self, message: AnyMessageGeneric<TB>) -> Result<(), SendError<TB>> {
+    self.queue.lock().push_back(message);
+    Ok(())
+  }
+}
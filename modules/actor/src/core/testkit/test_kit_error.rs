@@ -0,0 +1,36 @@
+//! Failures reported by test kit expectations.
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::core::spawn::SpawnError;
+
+/// Describes why a test kit expectation did not hold.
+#[derive(Debug)]
+pub enum TestKitError {
+  /// Nothing matching the expectation arrived before the virtual timeout elapsed.
+  Timeout(String),
+  /// A message, event or effect arrived that does not match the expectation.
+  Unexpected(String),
+  /// An actor required by the kit could not be spawned.
+  Spawn(SpawnError),
+  /// A message could not be delivered to the actor under test.
+  SendFailed(String),
+}
+
+impl fmt::Display for TestKitError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Self::Timeout(expected) => write!(f, "timeout while waiting for {expected}"),
+      | Self::Unexpected(reason) => write!(f, "unexpected: {reason}"),
+      | Self::Spawn(error) => write!(f, "spawn failed: {error:?}"),
+      | Self::SendFailed(reason) => write!(f, "send failed: {reason}"),
+    }
+  }
+}
+
+impl From<SpawnError> for TestKitError {
+  fn from(error: SpawnError) -> Self {
+    Self::Spawn(error)
+  }
+}
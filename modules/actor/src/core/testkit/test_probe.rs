@@ -0,0 +1,199 @@
+//! Untyped probe actor recording the messages it receives.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{collections::VecDeque, format, vec::Vec};
+use core::{any::Any, fmt::Debug, time::Duration};
+
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdToolbox, RuntimeToolbox, SyncMutexFamily},
+  sync::{ArcShared, sync_mutex_like::SyncMutexLike},
+};
+
+use super::{
+  fishing_outcome::FishingOutcome,
+  probe_actor::{ProbeActor, TerminatedLog},
+  probe_command::ProbeCommand,
+  probe_sender::{ProbeQueue, ProbeSender},
+  test_kit_error::TestKitError,
+  virtual_clock::VirtualClock,
+};
+use crate::core::{
+  actor_prim::{ChildRefGeneric, Pid, actor_ref::ActorRefGeneric},
+  messaging::AnyMessageGeneric,
+  props::PropsGeneric,
+  spawn::SpawnError,
+  system::ActorSystemGeneric,
+};
+
+/// Probe whose reference can be handed to the actors under test.
+///
+/// Messages sent to [`actor_ref`](Self::actor_ref) are queued in arrival order and consumed by
+/// the `expect_*` and `receive_*` methods. Timeouts are virtual: while waiting, the probe advances
+/// the manual tick driver one tick at a time, firing scheduled messages deterministically.
+pub struct TestProbeGeneric<TB: RuntimeToolbox + 'static> {
+  actor_ref:  ActorRefGeneric<TB>,
+  watcher:    ChildRefGeneric<TB>,
+  queue:      ProbeQueue<TB>,
+  terminated: TerminatedLog<TB>,
+  clock:      VirtualClock<TB>,
+}
+
+/// Type alias for [TestProbeGeneric] with the default [NoStdToolbox].
+pub type TestProbe = TestProbeGeneric<NoStdToolbox>;
+
+impl<TB: RuntimeToolbox + 'static> TestProbeGeneric<TB> {
+  pub(crate) fn spawn(system: &ActorSystemGeneric<TB>, clock: VirtualClock<TB>) -> Result<Self, SpawnError> {
+    let queue: ProbeQueue<TB> = ArcShared::new(<TB::MutexFamily as SyncMutexFamily>::create(VecDeque::new()));
+    let terminated: TerminatedLog<TB> = ArcShared::new(<TB::MutexFamily as SyncMutexFamily>::create(Vec::new()));
+    let props = PropsGeneric::from_fn({
+      let terminated = terminated.clone();
+      move || ProbeActor::new(terminated.clone())
+    });
+    let watcher = system.spawn(&props)?;
+    // 受信したメッセージを所有権ごと記録するため、独自の sender を持つ参照を公開する
+    let sender = ArcShared::new(ProbeSender::new(queue.clone()));
+    let actor_ref = ActorRefGeneric::with_system(watcher.pid(), sender, system.state());
+    Ok(Self { actor_ref, watcher, queue, terminated, clock })
+  }
+
+  /// Returns the reference recording every message sent to the probe.
+  #[must_use]
+  pub const fn actor_ref(&self) -> &ActorRefGeneric<TB> {
+    &self.actor_ref
+  }
+
+  /// Returns the pid of the probe.
+  #[must_use]
+  pub const fn pid(&self) -> Pid {
+    self.watcher.pid()
+  }
+
+  /// Returns the number of received messages not consumed yet.
+  #[must_use]
+  pub fn pending(&self) -> usize {
+    self.queue.lock().len()
+  }
+
+  /// Receives the next message, waiting up to `timeout` of virtual time.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Timeout`] when no message arrives in time.
+  pub fn receive_message(&self, timeout: Duration) -> Result<AnyMessageGeneric<TB>, TestKitError> {
+    self.clock.await_some(timeout, || self.queue.lock().pop_front()).ok_or_else(|| Self::timeout("a message", timeout))
+  }
+
+  /// Receives the next message and checks that it equals `expected`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Timeout`] when no message arrives in time and
+  /// [`TestKitError::Unexpected`] when the next message differs from `expected`.
+  pub fn expect_message<M>(&self, expected: &M, timeout: Duration) -> Result<AnyMessageGeneric<TB>, TestKitError>
+  where
+    M: Any + PartialEq + Debug, {
+    let message = self.receive_message(timeout)?;
+    match message.payload().downcast_ref::<M>() {
+      | Some(actual) if actual == expected => Ok(message),
+      | Some(actual) => Err(TestKitError::Unexpected(format!("expected {expected:?}, received {actual:?}"))),
+      | None => Err(TestKitError::Unexpected(format!("expected {expected:?}, received a message of another type"))),
+    }
+  }
+
+  /// Advances virtual time by `duration` and checks that no message arrives meanwhile.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Unexpected`] when a message is received.
+  pub fn expect_no_message(&self, duration: Duration) -> Result<(), TestKitError> {
+    match self.clock.await_some(duration, || self.queue.lock().pop_front()) {
+      | Some(message) => Err(TestKitError::Unexpected(format!("received {message:?} while expecting no message"))),
+      | None => Ok(()),
+    }
+  }
+
+  /// Receives exactly `count` messages, waiting up to `timeout` of virtual time for all of them.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Timeout`] when fewer messages arrive in time; the messages received
+  /// so far stay queued.
+  pub fn receive_n(&self, count: usize, timeout: Duration) -> Result<Vec<AnyMessageGeneric<TB>>, TestKitError> {
+    self
+      .clock
+      .await_some(timeout, || {
+        let mut queue = self.queue.lock();
+        (queue.len() >= count).then(|| queue.drain(..count).collect())
+      })
+      .ok_or_else(|| Self::timeout(&format!("{count} messages"), timeout))
+  }
+
+  /// Discards messages until `fisher` completes, waiting up to `timeout` of virtual time.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Unexpected`] when `fisher` fails a message and
+  /// [`TestKitError::Timeout`] when no message completes in time.
+  pub fn fish_for_message<F>(&self, timeout: Duration, mut fisher: F) -> Result<AnyMessageGeneric<TB>, TestKitError>
+  where
+    F: FnMut(&AnyMessageGeneric<TB>) -> FishingOutcome, {
+    self
+      .clock
+      .await_some(timeout, || {
+        while let Some(message) = self.queue.lock().pop_front() {
+          match fisher(&message) {
+            | FishingOutcome::Complete => return Some(Ok(message)),
+            | FishingOutcome::Continue => {},
+            | FishingOutcome::Fail(reason) => return Some(Err(TestKitError::Unexpected(reason))),
+          }
+        }
+        None
+      })
+      .unwrap_or_else(|| Err(Self::timeout("a message completing the fishing", timeout)))
+  }
+
+  /// Watches `target` so that its termination can be expected.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::SendFailed`] when the probe is no longer running.
+  pub fn watch(&self, target: &ActorRefGeneric<TB>) -> Result<(), TestKitError> {
+    self.command(ProbeCommand::Watch(target.clone()))
+  }
+
+  /// Stops watching `target`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::SendFailed`] when the probe is no longer running.
+  pub fn unwatch(&self, target: &ActorRefGeneric<TB>) -> Result<(), TestKitError> {
+    self.command(ProbeCommand::Unwatch(target.clone()))
+  }
+
+  /// Waits up to `timeout` of virtual time for the watched actor `target` to terminate.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Timeout`] when the termination is not observed in time.
+  pub fn expect_terminated(&self, target: Pid, timeout: Duration) -> Result<(), TestKitError> {
+    self
+      .clock
+      .await_some(timeout, || {
+        let mut terminated = self.terminated.lock();
+        let index = terminated.iter().position(|pid| *pid == target)?;
+        terminated.remove(index);
+        Some(())
+      })
+      .ok_or_else(|| Self::timeout(&format!("termination of {target:?}"), timeout))
+  }
+
+  fn command(&self, command: ProbeCommand<TB>) -> Result<(), TestKitError> {
+    self.watcher.tell(AnyMessageGeneric::new(command)).map_err(|error| TestKitError::SendFailed(format!("{error:?}")))
+  }
+
+  fn timeout(expected: &str, timeout: Duration) -> TestKitError {
+    TestKitError::Timeout(format!("{expected} within {timeout:?}"))
+  }
+}
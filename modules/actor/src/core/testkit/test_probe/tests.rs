@@ -0,0 +1,105 @@
+use core::time::Duration;
+
+use fraktor_utils_rs::core::runtime_toolbox::NoStdToolbox;
+
+use crate::core::{
+  actor_prim::{Actor, ActorContextGeneric},
+  error::ActorError,
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+  props::PropsGeneric,
+  testkit::{ActorTestKit, FishingOutcome, TestKitError},
+};
+
+struct IdleActor;
+
+impl Actor for IdleActor {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    _message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    Ok(())
+  }
+}
+
+const TIMEOUT: Duration = Duration::from_millis(50);
+
+#[test]
+fn expect_message_consumes_messages_in_arrival_order() {
+  let kit = ActorTestKit::new().expect("kit");
+  let probe = kit.create_probe().expect("probe");
+
+  probe.actor_ref().tell(AnyMessageGeneric::new(1_u32)).expect("tell");
+  probe.actor_ref().tell(AnyMessageGeneric::new(2_u32)).expect("tell");
+
+  assert_eq!(probe.pending(), 2);
+  probe.expect_message(&1_u32, TIMEOUT).expect("first");
+  probe.expect_message(&2_u32, TIMEOUT).expect("second");
+  assert_eq!(probe.pending(), 0);
+}
+
+#[test]
+fn expect_message_reports_mismatch_and_timeout() {
+  let kit = ActorTestKit::new().expect("kit");
+  let probe = kit.create_probe().expect("probe");
+
+  probe.actor_ref().tell(AnyMessageGeneric::new(3_u32)).expect("tell");
+  assert!(matches!(probe.expect_message(&4_u32, TIMEOUT), Err(TestKitError::Unexpected(_))));
+  assert!(matches!(probe.expect_message(&4_u32, TIMEOUT), Err(TestKitError::Timeout(_))));
+}
+
+#[test]
+fn expect_no_message_fails_when_a_message_is_pending() {
+  let kit = ActorTestKit::new().expect("kit");
+  let probe = kit.create_probe().expect("probe");
+
+  probe.expect_no_message(TIMEOUT).expect("silence");
+  probe.actor_ref().tell(AnyMessageGeneric::new("late")).expect("tell");
+  assert!(matches!(probe.expect_no_message(TIMEOUT), Err(TestKitError::Unexpected(_))));
+}
+
+#[test]
+fn receive_n_keeps_messages_queued_on_timeout() {
+  let kit = ActorTestKit::new().expect("kit");
+  let probe = kit.create_probe().expect("probe");
+
+  probe.actor_ref().tell(AnyMessageGeneric::new(1_u32)).expect("tell");
+  assert!(matches!(probe.receive_n(2, TIMEOUT), Err(TestKitError::Timeout(_))));
+  assert_eq!(probe.pending(), 1);
+
+  probe.actor_ref().tell(AnyMessageGeneric::new(2_u32)).expect("tell");
+  let messages = probe.receive_n(2, TIMEOUT).expect("messages");
+  assert_eq!(messages.len(), 2);
+}
+
+#[test]
+fn fish_for_message_skips_until_complete_or_fail() {
+  let kit = ActorTestKit::new().expect("kit");
+  let probe = kit.create_probe().expect("probe");
+
+  for value in [1_u32, 2, 3] {
+    probe.actor_ref().tell(AnyMessageGeneric::new(value)).expect("tell");
+  }
+  let fished = probe
+    .fish_for_message(TIMEOUT, |message| match message.payload().downcast_ref::<u32>() {
+      | Some(2) => FishingOutcome::Complete,
+      | _ => FishingOutcome::Continue,
+    })
+    .expect("fished");
+  assert_eq!(fished.payload().downcast_ref::<u32>(), Some(&2));
+
+  let failed = probe.fish_for_message(TIMEOUT, |_| FishingOutcome::Fail("boom".into()));
+  assert!(matches!(failed, Err(TestKitError::Unexpected(reason)) if reason == "boom"));
+}
+
+#[test]
+fn expect_terminated_observes_watched_actor_stop() {
+  let kit = ActorTestKit::new().expect("kit");
+  let probe = kit.create_probe().expect("probe");
+  let target = kit.spawn(&PropsGeneric::from_fn(|| IdleActor)).expect("target");
+
+  assert!(matches!(probe.expect_terminated(target.pid(), TIMEOUT), Err(TestKitError::Timeout(_))));
+  probe.watch(target.actor_ref()).expect("watch");
+  target.stop().expect("stop");
+  probe.expect_terminated(target.pid(), TIMEOUT).expect("terminated");
+}
@@ -0,0 +1,137 @@
+//! Typed probe accepting messages of a single protocol.
+
+use alloc::{format, vec::Vec};
+use core::{fmt::Debug, marker::PhantomData, time::Duration};
+
+use fraktor_utils_rs::core::runtime_toolbox::{NoStdToolbox, RuntimeToolbox};
+
+use super::{fishing_outcome::FishingOutcome, test_kit_error::TestKitError, test_probe::TestProbeGeneric};
+use crate::core::{actor_prim::Pid, messaging::AnyMessageGeneric, typed::actor_prim::TypedActorRefGeneric};
+
+/// Typed view over a [`TestProbeGeneric`] that decodes messages as `M`.
+///
+/// Receiving a message of another type is reported as [`TestKitError::Unexpected`].
+pub struct TypedTestProbeGeneric<M, TB>
+where
+  M: Send + Sync + 'static,
+  TB: RuntimeToolbox + 'static, {
+  inner:   TestProbeGeneric<TB>,
+  _marker: PhantomData<M>,
+}
+
+/// Type alias for [TypedTestProbeGeneric] with the default [NoStdToolbox].
+pub type TypedTestProbe<M> = TypedTestProbeGeneric<M, NoStdToolbox>;
+
+impl<M, TB> TypedTestProbeGeneric<M, TB>
+where
+  M: Clone + Send + Sync + 'static,
+  TB: RuntimeToolbox + 'static,
+{
+  pub(crate) const fn from_untyped(inner: TestProbeGeneric<TB>) -> Self {
+    Self { inner, _marker: PhantomData }
+  }
+
+  /// Returns the typed reference recording every message sent to the probe.
+  #[must_use]
+  pub fn actor_ref(&self) -> TypedActorRefGeneric<M, TB> {
+    TypedActorRefGeneric::from_untyped(self.inner.actor_ref().clone())
+  }
+
+  /// Returns the pid of the probe.
+  #[must_use]
+  pub const fn pid(&self) -> Pid {
+    self.inner.pid()
+  }
+
+  /// Returns the underlying untyped probe.
+  #[must_use]
+  pub const fn as_untyped(&self) -> &TestProbeGeneric<TB> {
+    &self.inner
+  }
+
+  /// Receives the next message, waiting up to `timeout` of virtual time.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Timeout`] when no message arrives in time and
+  /// [`TestKitError::Unexpected`] when the message is not an `M`.
+  pub fn receive_message(&self, timeout: Duration) -> Result<M, TestKitError> {
+    Self::decode(&self.inner.receive_message(timeout)?)
+  }
+
+  /// Receives the next message and checks that it equals `expected`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Timeout`] when no message arrives in time and
+  /// [`TestKitError::Unexpected`] when the next message differs from `expected`.
+  pub fn expect_message(&self, expected: &M, timeout: Duration) -> Result<M, TestKitError>
+  where
+    M: PartialEq + Debug, {
+    let message = self.inner.expect_message(expected, timeout)?;
+    Self::decode(&message)
+  }
+
+  /// Advances virtual time by `duration` and checks that no message arrives meanwhile.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Unexpected`] when a message is received.
+  pub fn expect_no_message(&self, duration: Duration) -> Result<(), TestKitError> {
+    self.inner.expect_no_message(duration)
+  }
+
+  /// Receives exactly `count` messages, waiting up to `timeout` of virtual time for all of them.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Timeout`] when fewer messages arrive in time and
+  /// [`TestKitError::Unexpected`] when one of them is not an `M`.
+  pub fn receive_n(&self, count: usize, timeout: Duration) -> Result<Vec<M>, TestKitError> {
+    self.inner.receive_n(count, timeout)?.iter().map(Self::decode).collect()
+  }
+
+  /// Discards messages until `fisher` completes, waiting up to `timeout` of virtual time.
+  ///
+  /// Messages that are not an `M` are discarded.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Unexpected`] when `fisher` fails a message and
+  /// [`TestKitError::Timeout`] when no message completes in time.
+  pub fn fish_for_message<F>(&self, timeout: Duration, mut fisher: F) -> Result<M, TestKitError>
+  where
+    F: FnMut(&M) -> FishingOutcome, {
+    let message = self.inner.fish_for_message(timeout, |message| match message.payload().downcast_ref::<M>() {
+      | Some(typed) => fisher(typed),
+      | None => FishingOutcome::Continue,
+    })?;
+    Self::decode(&message)
+  }
+
+  /// Watches `target` so that its termination can be expected.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::SendFailed`] when the probe is no longer running.
+  pub fn watch<C>(&self, target: &TypedActorRefGeneric<C, TB>) -> Result<(), TestKitError>
+  where
+    C: Send + Sync + 'static, {
+    self.inner.watch(target.as_untyped())
+  }
+
+  /// Waits up to `timeout` of virtual time for the watched actor `target` to terminate.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TestKitError::Timeout`] when the termination is not observed in time.
+  pub fn expect_terminated(&self, target: Pid, timeout: Duration) -> Result<(), TestKitError> {
+    self.inner.expect_terminated(target, timeout)
+  }
+
+  fn decode(message: &AnyMessageGeneric<TB>) -> Result<M, TestKitError> {
+    message.payload().downcast_ref::<M>().cloned().ok_or_else(|| {
+      TestKitError::Unexpected(format!("received a message that is not a {}", core::any::type_name::<M>()))
+    })
+  }
+}
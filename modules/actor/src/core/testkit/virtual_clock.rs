@@ -0,0 +1,61 @@
+//! Virtual time source shared by the test kit and its probes.
+
+use core::time::Duration;
+
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
+use crate::core::scheduler::ManualTickController;
+
+/// Converts timeouts into scheduler ticks and advances them through the manual tick driver.
+pub(crate) struct VirtualClock<TB: RuntimeToolbox + 'static> {
+  controller: ManualTickController<TB>,
+  resolution: Duration,
+}
+
+impl<TB: RuntimeToolbox + 'static> Clone for VirtualClock<TB> {
+  fn clone(&self) -> Self {
+    Self { controller: self.controller.clone(), resolution: self.resolution }
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> VirtualClock<TB> {
+  pub(crate) const fn new(controller: ManualTickController<TB>, resolution: Duration) -> Self {
+    Self { controller, resolution }
+  }
+
+  pub(crate) const fn controller(&self) -> &ManualTickController<TB> {
+    &self.controller
+  }
+
+  pub(crate) const fn resolution(&self) -> Duration {
+    self.resolution
+  }
+
+  /// Returns the number of ticks covering `duration`, rounded up.
+  pub(crate) fn ticks_for(&self, duration: Duration) -> u32 {
+    let resolution = self.resolution.as_nanos().max(1);
+    let ticks = duration.as_nanos().div_ceil(resolution);
+    u32::try_from(ticks).unwrap_or(u32::MAX)
+  }
+
+  pub(crate) fn advance(&self, duration: Duration) {
+    self.advance_ticks(self.ticks_for(duration));
+  }
+
+  pub(crate) fn advance_ticks(&self, ticks: u32) {
+    if ticks > 0 {
+      self.controller.inject_and_drive(ticks);
+    }
+  }
+
+  /// Polls `probe` after every tick until it yields a value or `timeout` has elapsed.
+  pub(crate) fn await_some<R>(&self, timeout: Duration, mut probe: impl FnMut() -> Option<R>) -> Option<R> {
+    for _ in 0..self.ticks_for(timeout) {
+      if let Some(value) = probe() {
+        return Some(value);
+      }
+      self.controller.inject_and_drive(1);
+    }
+    probe()
+  }
+}
@@ -9,7 +9,7 @@ use fraktor_utils_rs::core::{
 };
 
 use crate::core::{
-  dead_letter::DeadLetterEntryGeneric,
+  dead_letter::{DeadLetterEntryGeneric, DeadLetterQuery, DeadLetterReason, DeadLetterSuppression},
   error::SendError,
   event_stream::{EventStreamEvent, EventStreamGeneric, EventStreamSubscriberShared, EventStreamSubscriptionGeneric},
   futures::ActorFuture,
@@ -133,6 +133,25 @@ where
     self.inner.dead_letters()
   }
 
+  /// Returns the retained dead letters matching `query`.
+  #[must_use]
+  pub fn query_dead_letters(&self, query: &DeadLetterQuery) -> Vec<DeadLetterEntryGeneric<TB>> {
+    self.inner.query_dead_letters(query)
+  }
+
+  /// Returns the number of dead letters recorded per reason since the system started.
+  #[must_use]
+  pub fn dead_letter_counts(&self) -> Vec<(DeadLetterReason, u64)> {
+    self.inner.dead_letter_counts()
+  }
+
+  /// Suppresses the logging of dead letters carrying a `T` payload, however the message was built.
+  pub fn register_dead_letter_suppression<T>(&self)
+  where
+    T: DeadLetterSuppression, {
+    self.inner.register_dead_letter_suppression::<T>();
+  }
+
   /// Emits a log event with the specified severity.
   pub fn emit_log(&self, level: LogLevel, message: impl Into<String>, origin: Option<crate::core::actor_prim::Pid>) {
     self.inner.emit_log(level, message, origin)
@@ -0,0 +1,11 @@
+//! Embassy runtime bindings.
+//!
+//! Actor systems running on embassy use the
+//! [`NoStdToolbox`](fraktor_utils_rs::core::runtime_toolbox::NoStdToolbox): dispatcher batches run
+//! on a fixed set of worker tasks and the scheduler runs as a task, all spawned through a
+//! [`SendSpawner`](embassy_executor::SendSpawner), and ticks are produced by `embassy-time`.
+
+/// Dispatcher bindings running mailboxes on embassy worker tasks.
+pub mod dispatcher;
+/// Tick driver bindings backed by `embassy-time`.
+pub mod scheduler;
@@ -0,0 +1,8 @@
+//! Dispatcher bindings for the embassy runtime.
+
+mod embassy_dispatch_executor;
+mod embassy_dispatch_queue;
+mod embassy_schedule_adapter;
+
+pub use embassy_dispatch_executor::EmbassyDispatchExecutor;
+pub use embassy_schedule_adapter::EmbassyScheduleAdapter;
@@ -0,0 +1,84 @@
+use embassy_executor::{SendSpawner, SpawnError};
+use embassy_futures::yield_now;
+use fraktor_utils_rs::core::{runtime_toolbox::NoStdToolbox, sync::ArcShared};
+
+use super::{
+  embassy_dispatch_queue::{EmbassyDispatchQueue, QUEUE_CAPACITY},
+  embassy_schedule_adapter::EmbassyScheduleAdapter,
+};
+use crate::core::dispatcher::{DispatchError, DispatchExecutor, DispatchShared};
+
+#[cfg(test)]
+mod tests;
+
+/// Number of worker tasks spawned by each executor.
+const WORKER_COUNT: usize = 2;
+
+/// Executor draining dispatcher batches on a fixed set of embassy worker tasks.
+///
+/// Submissions are queued on a bounded channel of capacity 64 shared by the workers; when it
+/// is full the dispatcher is parked in an overflow list drained by the same workers, so
+/// submissions are never rejected and mailboxes are never left without a scheduled batch. A
+/// worker yields to the embassy executor after every batch. Dropping the executor stops its
+/// workers.
+pub struct EmbassyDispatchExecutor {
+  queue: ArcShared<EmbassyDispatchQueue>,
+}
+
+impl EmbassyDispatchExecutor {
+  /// Creates an executor and spawns its worker tasks on the executor behind `spawner`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`SpawnError`] when the embassy task pool has no room left for the workers (each
+  /// process supports up to four live executors).
+  pub fn new(spawner: SendSpawner) -> Result<Self, SpawnError> {
+    let queue = ArcShared::new(EmbassyDispatchQueue::new());
+    for spawned in 0..WORKER_COUNT {
+      if let Err(error) = spawner.spawn(run_worker(queue.clone())) {
+        queue.stop(spawned);
+        return Err(error);
+      }
+    }
+    Ok(Self { queue })
+  }
+
+  /// Creates a schedule adapter that helps drain this executor's queue while a mailbox offer
+  /// is pending.
+  #[must_use]
+  pub fn schedule_adapter(&self) -> EmbassyScheduleAdapter {
+    EmbassyScheduleAdapter::new(self.queue.clone())
+  }
+
+  /// Returns the capacity of the channel feeding the workers.
+  #[must_use]
+  pub const fn queue_capacity(&self) -> usize {
+    QUEUE_CAPACITY
+  }
+}
+
+impl DispatchExecutor<NoStdToolbox> for EmbassyDispatchExecutor {
+  fn execute(&mut self, dispatcher: DispatchShared) -> Result<(), DispatchError> {
+    self.queue.push(dispatcher);
+    Ok(())
+  }
+
+  fn supports_blocking(&self) -> bool {
+    false
+  }
+}
+
+impl Drop for EmbassyDispatchExecutor {
+  fn drop(&mut self) {
+    self.queue.stop(WORKER_COUNT);
+  }
+}
+
+#[embassy_executor::task(pool_size = 8)]
+async fn run_worker(queue: ArcShared<EmbassyDispatchQueue>) {
+  while let Some(dispatcher) = queue.pop().await {
+    dispatcher.drive();
+    // 1 バッチごとに実行権を返し、他のワーカーやタスクを飢えさせない
+    yield_now().await;
+  }
+}
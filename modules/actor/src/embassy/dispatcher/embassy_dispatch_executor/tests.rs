@@ -0,0 +1,171 @@
+extern crate std;
+
+use alloc::{boxed::Box, vec::Vec};
+use core::time::Duration;
+use std::{sync::mpsc, thread, time::Instant};
+
+use embassy_executor::{Executor, SendSpawner};
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdMutex, NoStdToolbox},
+  sync::ArcShared,
+};
+
+use super::EmbassyDispatchExecutor;
+use crate::{
+  core::{
+    actor_prim::{Actor, ActorContextGeneric},
+    dispatcher::DispatcherConfigGeneric,
+    error::ActorError,
+    messaging::{AnyMessage, AnyMessageViewGeneric},
+    props::Props,
+    scheduler::{HardwareKind, SchedulerCommand, TickDriverKind},
+    system::{ActorSystem, ActorSystemConfig},
+  },
+  embassy::scheduler::EmbassyTickDriver,
+};
+
+type Log = ArcShared<NoStdMutex<Vec<u32>>>;
+
+struct Recorder {
+  log: Log,
+}
+
+impl Actor for Recorder {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    if let Some(value) = message.downcast_ref::<u32>() {
+      self.log.lock().push(*value);
+    }
+    Ok(())
+  }
+}
+
+struct Guardian;
+
+impl Actor for Guardian {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    _message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    Ok(())
+  }
+}
+
+fn spawn_executor() -> SendSpawner {
+  let (sender, receiver) = mpsc::channel();
+  thread::spawn(move || {
+    let executor: &'static mut Executor = Box::leak(Box::new(Executor::new()));
+    executor.run(|spawner| sender.send(spawner.make_send()).expect("spawner"));
+  });
+  receiver.recv().expect("spawner")
+}
+
+fn embassy_system(spawner: SendSpawner) -> ActorSystem {
+  let executor = EmbassyDispatchExecutor::new(spawner).expect("workers");
+  let adapter = ArcShared::new(executor.schedule_adapter());
+  let dispatcher = DispatcherConfigGeneric::from_executor(Box::new(executor)).with_schedule_adapter(adapter);
+  let config = ActorSystemConfig::default()
+    .with_default_dispatcher(dispatcher)
+    .with_tick_driver(EmbassyTickDriver::config(spawner));
+  ActorSystem::new_with_config(&Props::from_fn(|| Guardian), &config).expect("system")
+}
+
+fn wait_for(log: &Log, expected: &[u32]) -> bool {
+  let deadline = Instant::now() + std::time::Duration::from_secs(2);
+  while Instant::now() < deadline {
+    if log.lock().as_slice() == expected {
+      return true;
+    }
+    thread::sleep(std::time::Duration::from_millis(1));
+  }
+  false
+}
+
+fn shutdown(system: &ActorSystem) {
+  let termination = system.when_terminated();
+  system.terminate().expect("terminate");
+  let deadline = Instant::now() + std::time::Duration::from_secs(2);
+  while !termination.is_ready() && Instant::now() < deadline {
+    thread::sleep(std::time::Duration::from_millis(1));
+  }
+}
+
+#[test]
+fn mailboxes_are_drained_by_embassy_tasks() {
+  let system = embassy_system(spawn_executor());
+  let log: Log = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let recorder_log = log.clone();
+  let actor = system.spawn(&Props::from_fn(move || Recorder { log: recorder_log.clone() })).expect("spawn");
+
+  for value in 1..=3_u32 {
+    actor.tell(AnyMessage::new(value)).expect("tell");
+  }
+
+  assert!(wait_for(&log, &[1, 2, 3]));
+  shutdown(&system);
+}
+
+#[test]
+fn more_mailboxes_than_workers_are_all_drained() {
+  let system = embassy_system(spawn_executor());
+  let log: Log = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let actors: Vec<_> = (0..40)
+    .map(|_| {
+      let recorder_log = log.clone();
+      system.spawn(&Props::from_fn(move || Recorder { log: recorder_log.clone() })).expect("spawn")
+    })
+    .collect();
+
+  for (value, actor) in (0_u32..).zip(&actors) {
+    actor.tell(AnyMessage::new(value)).expect("tell");
+  }
+
+  let deadline = Instant::now() + std::time::Duration::from_secs(2);
+  while log.lock().len() < actors.len() && Instant::now() < deadline {
+    thread::sleep(std::time::Duration::from_millis(1));
+  }
+  let mut received = log.lock().clone();
+  received.sort_unstable();
+  assert_eq!(received, (0..40).collect::<Vec<u32>>());
+  shutdown(&system);
+}
+
+#[test]
+fn dropping_the_executor_releases_its_workers() {
+  let spawner = spawn_executor();
+  // ワーカーが停止しなければタスクプールを使い切り、生成に失敗する
+  for _ in 0..8 {
+    let executor = EmbassyDispatchExecutor::new(spawner).expect("workers");
+    drop(executor);
+    thread::sleep(std::time::Duration::from_millis(20));
+  }
+}
+
+#[test]
+fn scheduled_messages_fire_on_embassy_time() {
+  let system = embassy_system(spawn_executor());
+  let snapshot = system.tick_driver_snapshot().expect("snapshot");
+  assert_eq!(snapshot.kind, TickDriverKind::Hardware { source: HardwareKind::Embassy });
+
+  let log: Log = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let recorder_log = log.clone();
+  let actor = system.spawn(&Props::from_fn(move || Recorder { log: recorder_log.clone() })).expect("spawn");
+  let context = system.scheduler_context().expect("scheduler");
+  context
+    .scheduler()
+    .lock()
+    .schedule_once(Duration::from_millis(20), SchedulerCommand::SendMessage {
+      receiver:   actor.actor_ref().clone(),
+      message:    AnyMessage::new(7_u32),
+      dispatcher: None,
+      sender:     None,
+    })
+    .expect("schedule");
+
+  assert!(wait_for(&log, &[7]));
+  shutdown(&system);
+}
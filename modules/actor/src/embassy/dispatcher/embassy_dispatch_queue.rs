@@ -0,0 +1,83 @@
+use alloc::collections::VecDeque;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use embassy_sync::{
+  blocking_mutex::raw::CriticalSectionRawMutex,
+  channel::{Channel, TrySendError},
+};
+use fraktor_utils_rs::core::runtime_toolbox::NoStdMutex;
+
+use crate::core::dispatcher::DispatchShared;
+
+/// Capacity of the channel feeding the worker tasks.
+pub(super) const QUEUE_CAPACITY: usize = 64;
+
+pub(super) enum WorkerCommand {
+  Drive(DispatchShared),
+  // オーバーフロー列や停止要求を確認させるためだけにワーカーを起こす
+  Wake,
+}
+
+/// Dispatchers waiting for a worker task.
+///
+/// Ready dispatchers go through a bounded channel; when it is full they are parked in an overflow
+/// list that workers drain before waiting on the channel again, so submissions never fail.
+pub(super) struct EmbassyDispatchQueue {
+  ready:    Channel<CriticalSectionRawMutex, WorkerCommand, QUEUE_CAPACITY>,
+  overflow: NoStdMutex<VecDeque<DispatchShared>>,
+  stopped:  AtomicBool,
+}
+
+impl EmbassyDispatchQueue {
+  pub(super) const fn new() -> Self {
+    Self { ready: Channel::new(), overflow: NoStdMutex::new(VecDeque::new()), stopped: AtomicBool::new(false) }
+  }
+
+  pub(super) fn push(&self, dispatcher: DispatchShared) {
+    if let Err(TrySendError::Full(WorkerCommand::Drive(dispatcher))) =
+      self.ready.try_send(WorkerCommand::Drive(dispatcher))
+    {
+      self.overflow.lock().push_back(dispatcher);
+      // チャネルが空になってからワーカーが待機に入った場合でもオーバーフローを拾えるよう起こす
+      let _ = self.ready.try_send(WorkerCommand::Wake);
+    }
+  }
+
+  /// Takes a queued dispatcher without waiting.
+  pub(super) fn try_pop(&self) -> Option<DispatchShared> {
+    if let Some(dispatcher) = self.overflow.lock().pop_front() {
+      return Some(dispatcher);
+    }
+    loop {
+      match self.ready.try_receive() {
+        | Ok(WorkerCommand::Drive(dispatcher)) => return Some(dispatcher),
+        | Ok(WorkerCommand::Wake) => continue,
+        | Err(_) => return None,
+      }
+    }
+  }
+
+  /// Waits for the next dispatcher; returns `None` once the queue is stopped.
+  pub(super) async fn pop(&self) -> Option<DispatchShared> {
+    loop {
+      if self.stopped.load(Ordering::Acquire) {
+        return None;
+      }
+      if let Some(dispatcher) = self.overflow.lock().pop_front() {
+        return Some(dispatcher);
+      }
+      match self.ready.receive().await {
+        | WorkerCommand::Drive(dispatcher) => return Some(dispatcher),
+        | WorkerCommand::Wake => continue,
+      }
+    }
+  }
+
+  pub(super) fn stop(&self, workers: usize) {
+    self.stopped.store(true, Ordering::Release);
+    // 待機中のワーカーを起こす。チャネルが満杯なら全ワーカーが処理中で、次のループで停止に気付く
+    for _ in 0..workers {
+      let _ = self.ready.try_send(WorkerCommand::Wake);
+    }
+  }
+}
@@ -0,0 +1,59 @@
+use core::{
+  sync::atomic::{AtomicUsize, Ordering},
+  task::Waker,
+};
+
+use fraktor_utils_rs::core::{runtime_toolbox::NoStdToolbox, sync::ArcShared};
+
+use super::embassy_dispatch_queue::EmbassyDispatchQueue;
+use crate::core::dispatcher::{Dispatcher, ScheduleAdapter, ScheduleWaker};
+
+/// Schedule adapter for dispatchers driven by
+/// [`EmbassyDispatchExecutor`](super::EmbassyDispatchExecutor).
+///
+/// Mailbox wakers re-register the dispatcher with the executor's queue. While a mailbox offer is
+/// pending the adapter runs one queued batch inline instead of spinning, so the receiver that
+/// has to make room can progress even on a single-threaded executor. Pending offers and rejected
+/// executions are counted for diagnostics.
+pub struct EmbassyScheduleAdapter {
+  queue:          ArcShared<EmbassyDispatchQueue>,
+  pending_calls:  AtomicUsize,
+  rejected_calls: AtomicUsize,
+}
+
+impl EmbassyScheduleAdapter {
+  pub(super) const fn new(queue: ArcShared<EmbassyDispatchQueue>) -> Self {
+    Self { queue, pending_calls: AtomicUsize::new(0), rejected_calls: AtomicUsize::new(0) }
+  }
+
+  /// Returns how many mailbox offers yielded `Poll::Pending`.
+  #[must_use]
+  pub fn pending_calls(&self) -> usize {
+    self.pending_calls.load(Ordering::Relaxed)
+  }
+
+  /// Returns how many dispatcher executions were rejected after exhausting retries.
+  #[must_use]
+  pub fn rejected_calls(&self) -> usize {
+    self.rejected_calls.load(Ordering::Relaxed)
+  }
+}
+
+impl ScheduleAdapter<NoStdToolbox> for EmbassyScheduleAdapter {
+  fn create_waker(&self, dispatcher: Dispatcher) -> Waker {
+    ScheduleWaker::<NoStdToolbox>::into_waker(dispatcher)
+  }
+
+  fn on_pending(&self) {
+    self.pending_calls.fetch_add(1, Ordering::Relaxed);
+    // 同期呼び出しからは await できないため、待っている受信側を含む待機中のバッチを代わりに進める
+    match self.queue.try_pop() {
+      | Some(dispatcher) => dispatcher.drive(),
+      | None => core::hint::spin_loop(),
+    }
+  }
+
+  fn notify_rejected(&self, _attempts: usize) {
+    self.rejected_calls.fetch_add(1, Ordering::Relaxed);
+  }
+}
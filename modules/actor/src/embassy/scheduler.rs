@@ -0,0 +1,5 @@
+//! Scheduler bindings for the embassy runtime.
+
+mod embassy_tick_driver;
+
+pub use embassy_tick_driver::EmbassyTickDriver;
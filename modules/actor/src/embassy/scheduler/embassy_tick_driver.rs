@@ -0,0 +1,124 @@
+use alloc::boxed::Box;
+use core::time::Duration;
+
+use embassy_executor::SendSpawner;
+use embassy_futures::select::{Either, select};
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal};
+use embassy_time::Ticker;
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdMutex, NoStdToolbox},
+  sync::ArcShared,
+};
+
+use crate::core::scheduler::{
+  HardwareKind, Scheduler, SchedulerTickExecutor, TickDriver, TickDriverConfig, TickDriverControl, TickDriverError,
+  TickDriverHandleGeneric, TickDriverId, TickDriverKind, TickDriverRuntime, TickExecutorSignal, TickFeed,
+  TickFeedHandle, next_tick_driver_id,
+};
+
+#[cfg(test)]
+mod tests;
+
+type StopSignal = ArcShared<Signal<CriticalSectionRawMutex, ()>>;
+
+/// Tick driver producing scheduler ticks from an `embassy-time` [`Ticker`].
+///
+/// The ticker runs in an embassy task; shutting the driver down stops the task and marks the
+/// feed inactive.
+pub struct EmbassyTickDriver {
+  id:         TickDriverId,
+  spawner:    SendSpawner,
+  resolution: Duration,
+}
+
+impl EmbassyTickDriver {
+  /// Creates a driver that ticks every `resolution` on the executor behind `spawner`.
+  #[must_use]
+  pub fn new(spawner: SendSpawner, resolution: Duration) -> Self {
+    Self { id: next_tick_driver_id(), spawner, resolution }
+  }
+
+  /// Creates a complete tick driver configuration running on embassy.
+  ///
+  /// The builder starts an [`EmbassyTickDriver`] at the scheduler resolution and spawns a
+  /// scheduler task that drains the feed whenever ticks arrive. Both tasks stop when the actor
+  /// system shuts down.
+  #[must_use]
+  pub fn config(spawner: SendSpawner) -> TickDriverConfig<NoStdToolbox> {
+    TickDriverConfig::new(move |ctx| {
+      let scheduler: ArcShared<NoStdMutex<Scheduler<NoStdToolbox>>> = ctx.scheduler();
+      let (resolution, capacity) = {
+        let guard = scheduler.lock();
+        (guard.config().resolution(), guard.config().profile().tick_buffer_quota())
+      };
+
+      let signal = TickExecutorSignal::new();
+      let feed = TickFeed::new(resolution, capacity, signal);
+      let handle = Self::new(spawner, resolution).start(feed.clone())?;
+
+      let stop: StopSignal = ArcShared::new(Signal::new());
+      let executor = SchedulerTickExecutor::new(scheduler, feed.clone(), feed.signal());
+      if spawner.spawn(drive_scheduler(executor, stop.clone())).is_err() {
+        handle.shutdown();
+        return Err(TickDriverError::SpawnFailed);
+      }
+
+      Ok(TickDriverRuntime::new(handle, feed).with_executor_shutdown(move || stop.signal(())))
+    })
+  }
+}
+
+impl TickDriver<NoStdToolbox> for EmbassyTickDriver {
+  fn id(&self) -> TickDriverId {
+    self.id
+  }
+
+  fn kind(&self) -> TickDriverKind {
+    TickDriverKind::Hardware { source: HardwareKind::Embassy }
+  }
+
+  fn resolution(&self) -> Duration {
+    self.resolution
+  }
+
+  fn start(
+    &mut self,
+    feed: TickFeedHandle<NoStdToolbox>,
+  ) -> Result<TickDriverHandleGeneric<NoStdToolbox>, TickDriverError> {
+    let stop: StopSignal = ArcShared::new(Signal::new());
+    let period =
+      embassy_time::Duration::from_micros(u64::try_from(self.resolution.as_micros()).unwrap_or(u64::MAX).max(1));
+    self.spawner.spawn(produce_ticks(period, feed, stop.clone())).map_err(|_| TickDriverError::SpawnFailed)?;
+
+    let control: Box<dyn TickDriverControl> = Box::new(EmbassyTickDriverControl { stop });
+    let control = ArcShared::new(NoStdMutex::new(control));
+    Ok(TickDriverHandleGeneric::new(self.id, self.kind(), self.resolution, control))
+  }
+}
+
+struct EmbassyTickDriverControl {
+  stop: StopSignal,
+}
+
+impl TickDriverControl for EmbassyTickDriverControl {
+  fn shutdown(&mut self) {
+    self.stop.signal(());
+  }
+}
+
+#[embassy_executor::task(pool_size = 4)]
+async fn produce_ticks(period: embassy_time::Duration, feed: TickFeedHandle<NoStdToolbox>, stop: StopSignal) {
+  let mut ticker = Ticker::every(period);
+  while let Either::First(()) = select(ticker.next(), stop.wait()).await {
+    feed.enqueue(1);
+  }
+  feed.mark_driver_inactive();
+}
+
+#[embassy_executor::task(pool_size = 4)]
+async fn drive_scheduler(mut executor: SchedulerTickExecutor<NoStdToolbox>, stop: StopSignal) {
+  let signal = executor.signal();
+  while let Either::First(()) = select(signal.wait_async(), stop.wait()).await {
+    executor.drive_pending();
+  }
+}
@@ -0,0 +1,53 @@
+extern crate std;
+
+use alloc::boxed::Box;
+use core::time::Duration;
+use std::{sync::mpsc, thread, time::Instant};
+
+use embassy_executor::{Executor, SendSpawner};
+use fraktor_utils_rs::core::runtime_toolbox::NoStdToolbox;
+
+use super::EmbassyTickDriver;
+use crate::core::scheduler::{HardwareKind, TickDriver, TickDriverKind, TickExecutorSignal, TickFeed};
+
+fn spawn_executor() -> SendSpawner {
+  let (sender, receiver) = mpsc::channel();
+  thread::spawn(move || {
+    let executor: &'static mut Executor = Box::leak(Box::new(Executor::new()));
+    executor.run(|spawner| sender.send(spawner.make_send()).expect("spawner"));
+  });
+  receiver.recv().expect("spawner")
+}
+
+fn wait_until(condition: impl Fn() -> bool) -> bool {
+  let deadline = Instant::now() + std::time::Duration::from_secs(2);
+  while Instant::now() < deadline {
+    if condition() {
+      return true;
+    }
+    thread::sleep(std::time::Duration::from_millis(1));
+  }
+  condition()
+}
+
+#[test]
+fn driver_enqueues_ticks_until_shutdown() {
+  let resolution = Duration::from_millis(1);
+  let mut driver = EmbassyTickDriver::new(spawn_executor(), resolution);
+  assert_eq!(driver.kind(), TickDriverKind::Hardware { source: HardwareKind::Embassy });
+  assert_eq!(driver.resolution(), resolution);
+
+  let signal = TickExecutorSignal::new();
+  let feed = TickFeed::<NoStdToolbox>::new(resolution, 64, signal.clone());
+  let handle = driver.start(feed.clone()).expect("start");
+  assert_eq!(handle.id(), driver.id());
+
+  assert!(wait_until(|| feed.driver_active()));
+  assert!(signal.arm());
+  let mut ticks = 0;
+  feed.drain_pending(|count| ticks += count);
+  assert!(ticks > 0);
+
+  handle.shutdown();
+  assert!(wait_until(|| !feed.driver_active()));
+}
@@ -58,6 +58,9 @@ extern crate alloc;
 
 /// Core actor runtime module containing all actor system components.
 pub mod core;
+/// Embassy runtime bindings for embedded targets.
+#[cfg(feature = "embassy")]
+pub mod embassy;
 /// Standard library runtime bindings and utilities.
 #[allow(cfg_std_forbid)]
 #[cfg(feature = "std")]
@@ -10,12 +10,16 @@ pub mod error;
 pub mod event_stream;
 /// Future utilities specialised for the standard toolbox.
 pub mod futures;
+// 運用向け HTTP エンドポイントが共有する最小限の HTTP サーバ
+mod http;
 /// Logging adapters specialised for the standard toolbox.
 pub mod logging;
 /// Mailbox bindings for the standard toolbox.
 pub mod mailbox;
 /// Messaging primitives specialised for the standard toolbox.
 pub mod messaging;
+/// Prometheus metrics exporter for the standard toolbox.
+pub mod metrics;
 /// Props and dispatcher configuration bindings for the standard toolbox.
 pub mod props;
 /// Scheduler utilities specialised for the standard toolbox runtime.
@@ -4,3 +4,5 @@ use fraktor_utils_rs::std::runtime_toolbox::StdToolbox;
 pub type DispatchShared = crate::core::dispatcher::DispatchSharedGeneric<StdToolbox>;
 /// Dispatcher specialised for `StdToolbox`.
 pub type Dispatcher = crate::core::dispatcher::DispatcherGeneric<StdToolbox>;
+/// Replay executor specialised for `StdToolbox`.
+pub type ReplayExecutor = crate::core::dispatcher::ReplayExecutorGeneric<StdToolbox>;
@@ -0,0 +1,5 @@
+mod http_endpoint;
+mod http_response;
+
+pub(crate) use http_endpoint::HttpEndpoint;
+pub(crate) use http_response::HttpResponse;
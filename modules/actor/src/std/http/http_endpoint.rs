@@ -0,0 +1,100 @@
+extern crate std;
+
+use std::{
+  boxed::Box,
+  io::{self, BufRead, BufReader},
+  net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+  string::String,
+  sync::atomic::{AtomicBool, Ordering},
+  thread::{self, JoinHandle},
+  time::Duration,
+};
+
+use fraktor_utils_rs::core::sync::ArcShared;
+
+use super::http_response::HttpResponse;
+
+#[cfg(test)]
+mod tests;
+
+/// Longest time a client may take to send its request or receive the response.
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+
+type Handler = Box<dyn Fn(&str, &str) -> HttpResponse + Send>;
+
+/// Minimal HTTP/1.1 server answering each request with the response of a handler.
+///
+/// The handler receives the request method and path. Requests are served one at a time on a
+/// dedicated thread; reads and writes time out after 2 seconds so a stalled client cannot block
+/// other requests or the shutdown. The thread stops when the endpoint is dropped.
+pub(crate) struct HttpEndpoint {
+  local_addr: SocketAddr,
+  stopping:   ArcShared<AtomicBool>,
+  worker:     Option<JoinHandle<()>>,
+}
+
+impl HttpEndpoint {
+  /// Binds to `addr` and serves requests on a thread named `name`.
+  pub(crate) fn bind<H>(addr: impl ToSocketAddrs, name: &str, handler: H) -> io::Result<Self>
+  where
+    H: Fn(&str, &str) -> HttpResponse + Send + 'static, {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    let stopping = ArcShared::new(AtomicBool::new(false));
+    let flag = stopping.clone();
+    let handler: Handler = Box::new(handler);
+    let worker = thread::Builder::new().name(String::from(name)).spawn(move || serve(&listener, &handler, &flag))?;
+    Ok(Self { local_addr, stopping, worker: Some(worker) })
+  }
+
+  /// Returns the address the endpoint is listening on.
+  pub(crate) const fn local_addr(&self) -> SocketAddr {
+    self.local_addr
+  }
+
+  fn stop(&mut self) {
+    let Some(worker) = self.worker.take() else {
+      return;
+    };
+    self.stopping.store(true, Ordering::Release);
+    // accept でブロックしているスレッドを起こすため、自分自身へ接続する
+    let _ = TcpStream::connect(self.local_addr);
+    let _ = worker.join();
+  }
+}
+
+impl Drop for HttpEndpoint {
+  fn drop(&mut self) {
+    self.stop();
+  }
+}
+
+fn serve(listener: &TcpListener, handler: &Handler, stopping: &AtomicBool) {
+  for stream in listener.incoming() {
+    if stopping.load(Ordering::Acquire) {
+      break;
+    }
+    if let Ok(stream) = stream {
+      let _ = respond(stream, handler);
+    }
+  }
+}
+
+fn respond(mut stream: TcpStream, handler: &Handler) -> io::Result<()> {
+  // 応答しないクライアントがスレッドを占有し、停止処理まで止めないよう入出力に期限を設ける
+  stream.set_read_timeout(Some(IO_TIMEOUT))?;
+  stream.set_write_timeout(Some(IO_TIMEOUT))?;
+  let mut reader = BufReader::new(stream.try_clone()?);
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line)?;
+  // ヘッダは使わないが、応答前に読み切ってクライアント側のリセットを避ける
+  let mut header = String::new();
+  while reader.read_line(&mut header)? > 2 {
+    header.clear();
+  }
+
+  let mut parts = request_line.split_whitespace();
+  let method = parts.next().unwrap_or_default();
+  let path = parts.next().unwrap_or_default();
+  handler(method, path).write_to(&mut stream)
+}
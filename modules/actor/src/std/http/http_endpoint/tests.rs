@@ -0,0 +1,49 @@
+extern crate std;
+
+use std::{
+  io::{Read, Write},
+  net::TcpStream,
+  string::String,
+  time::{Duration, Instant},
+};
+
+use super::HttpEndpoint;
+use crate::std::http::HttpResponse;
+
+fn endpoint() -> HttpEndpoint {
+  HttpEndpoint::bind("127.0.0.1:0", "http-endpoint-test", |method, path| {
+    HttpResponse::new("200 OK", "text/plain", std::format!("{method} {path}"))
+  })
+  .expect("bind")
+}
+
+fn request(endpoint: &HttpEndpoint, line: &str) -> String {
+  let mut stream = TcpStream::connect(endpoint.local_addr()).expect("connect");
+  stream.write_all(std::format!("{line}\r\nHost: localhost\r\n\r\n").as_bytes()).expect("write");
+  let mut response = String::new();
+  stream.read_to_string(&mut response).expect("read");
+  response
+}
+
+#[test]
+fn passes_method_and_path_to_the_handler() {
+  let endpoint = endpoint();
+  let response = request(&endpoint, "DELETE /items HTTP/1.1");
+  assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+  assert!(response.contains("Content-Type: text/plain\r\n"));
+  assert!(response.ends_with("\r\n\r\nDELETE /items"));
+}
+
+#[test]
+fn stalled_clients_do_not_block_requests_or_shutdown() {
+  let endpoint = endpoint();
+  let stalled = TcpStream::connect(endpoint.local_addr()).expect("connect");
+
+  assert!(request(&endpoint, "GET / HTTP/1.1").starts_with("HTTP/1.1 200 OK"));
+
+  let _idle = TcpStream::connect(endpoint.local_addr()).expect("connect");
+  let started = Instant::now();
+  drop(endpoint);
+  assert!(started.elapsed() < Duration::from_secs(4));
+  drop(stalled);
+}
@@ -0,0 +1,32 @@
+extern crate std;
+
+use std::{
+  io::{self, Write},
+  vec::Vec,
+};
+
+/// Response produced by an [`HttpEndpoint`](super::HttpEndpoint) handler.
+pub(crate) struct HttpResponse {
+  status:       &'static str,
+  content_type: &'static str,
+  body:         Vec<u8>,
+}
+
+impl HttpResponse {
+  /// Creates a response with the status line text `status` (e.g. `200 OK`).
+  pub(crate) fn new(status: &'static str, content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+    Self { status, content_type, body: body.into() }
+  }
+
+  pub(super) fn write_to(&self, stream: &mut impl Write) -> io::Result<()> {
+    let head = std::format!(
+      "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+      self.status,
+      self.content_type,
+      self.body.len()
+    );
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(&self.body)?;
+    stream.flush()
+  }
+}
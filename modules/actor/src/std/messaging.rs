@@ -1,2 +1,6 @@
+mod catch_panic_middleware;
+mod tracing_middleware;
 mod types;
+pub use catch_panic_middleware::CatchPanicMiddleware;
+pub use tracing_middleware::TracingMiddleware;
 pub use types::*;
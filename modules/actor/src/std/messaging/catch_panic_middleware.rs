@@ -0,0 +1,58 @@
+//! Middleware converting handler panics into actor errors.
+
+extern crate std;
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{format, string::String};
+use core::any::Any;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use fraktor_utils_rs::std::runtime_toolbox::StdToolbox;
+
+use crate::core::{
+  actor_prim::ActorContextGeneric,
+  error::ActorError,
+  messaging::{AnyMessageGeneric, message_invoker::MessageInvokerMiddleware},
+};
+
+/// Catches panics raised while an actor handles a message and reports them as a recoverable
+/// [`ActorError`], so the supervisor restarts the actor instead of the dispatcher thread unwinding.
+///
+/// The standard [`ActorSystemConfig`](crate::std::system::ActorSystemConfig) registers it as
+/// [`NAME`](Self::NAME). The panic hook still runs, so the panic message is printed as usual.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CatchPanicMiddleware;
+
+impl CatchPanicMiddleware {
+  /// Name under which the middleware is registered by default.
+  pub const NAME: &'static str = "catch_panic";
+
+  /// Creates the middleware.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+impl MessageInvokerMiddleware<StdToolbox> for CatchPanicMiddleware {
+  fn around_user<'c>(
+    &self,
+    ctx: &mut ActorContextGeneric<'c, StdToolbox>,
+    _message: &AnyMessageGeneric<StdToolbox>,
+    next: &mut dyn FnMut(&mut ActorContextGeneric<'c, StdToolbox>) -> Result<(), ActorError>,
+  ) -> Result<(), ActorError> {
+    match catch_unwind(AssertUnwindSafe(|| next(ctx))) {
+      | Ok(result) => result,
+      | Err(payload) => Err(ActorError::recoverable(format!("actor panicked: {}", panic_message(&*payload)))),
+    }
+  }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+  if let Some(message) = payload.downcast_ref::<&'static str>() {
+    return String::from(*message);
+  }
+  payload.downcast_ref::<String>().cloned().unwrap_or_else(|| String::from("non-string panic payload"))
+}
@@ -0,0 +1,52 @@
+extern crate std;
+
+use std::sync::{Arc, Mutex};
+
+use fraktor_utils_rs::std::runtime_toolbox::StdToolbox;
+
+use crate::{
+  core::{
+    actor_prim::{Actor, ActorContextGeneric},
+    error::ActorError,
+    messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+    props::PropsGeneric,
+    testkit::ActorTestKitGeneric,
+  },
+  std::{messaging::CatchPanicMiddleware, system::ActorSystemConfig},
+};
+
+type Seen = Arc<Mutex<Vec<u32>>>;
+
+struct Fragile {
+  seen: Seen,
+}
+
+impl Actor<StdToolbox> for Fragile {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    message: AnyMessageViewGeneric<'_, StdToolbox>,
+  ) -> Result<(), ActorError> {
+    match message.downcast_ref::<u32>() {
+      | Some(0) => panic!("zero is not accepted"),
+      | Some(value) => self.seen.lock().expect("lock").push(*value),
+      | None => {},
+    }
+    Ok(())
+  }
+}
+
+#[test]
+fn panics_restart_the_actor_instead_of_unwinding() {
+  let kit = ActorTestKitGeneric::with_config(ActorSystemConfig::default().into_inner()).expect("kit");
+  let seen: Seen = Arc::default();
+  let actor_seen = seen.clone();
+  let props =
+    PropsGeneric::from_fn(move || Fragile { seen: actor_seen.clone() }).with_middleware([CatchPanicMiddleware::NAME]);
+  let actor = kit.spawn(&props).expect("spawn");
+
+  actor.tell(AnyMessageGeneric::new(0_u32)).expect("tell");
+  actor.tell(AnyMessageGeneric::new(7_u32)).expect("tell");
+
+  assert_eq!(*seen.lock().expect("lock"), [7]);
+}
@@ -0,0 +1,145 @@
+//! `tracing` middleware propagating W3C trace context between actors.
+
+extern crate std;
+
+#[cfg(test)]
+mod tests;
+
+use core::{
+  cell::RefCell,
+  hash::BuildHasher,
+  sync::atomic::{AtomicU64, Ordering},
+};
+use std::{collections::hash_map::RandomState, thread_local, vec::Vec};
+
+use fraktor_utils_rs::std::runtime_toolbox::StdToolbox;
+use tracing::{field, info_span, span::EnteredSpan};
+
+use crate::core::{
+  actor_prim::ActorContextGeneric,
+  error::ActorError,
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric, TraceContext, message_invoker::MessageInvokerMiddleware},
+};
+
+thread_local! {
+  static ACTIVE: RefCell<Vec<ActiveSpan>> = const { RefCell::new(Vec::new()) };
+}
+
+struct ActiveSpan {
+  context: TraceContext,
+  span:    EnteredSpan,
+}
+
+/// Middleware that opens a `tracing` span for every message an actor receives.
+///
+/// The span is named `actor.receive` and records the actor path, the message type and the W3C
+/// trace and span ids. When the incoming message carries a [`TraceContext`] header the span joins
+/// that trace as a child, otherwise a new trace is started.
+///
+/// Install it with
+/// [`ActorSystemConfig::with_message_middleware`](crate::std::system::ActorSystemConfig::with_message_middleware):
+/// messages sent while a span is active then inherit its context, so the trace follows `tell`,
+/// `ask` and remoting hops.
+pub struct TracingMiddleware {
+  ids:      RandomState,
+  sequence: AtomicU64,
+}
+
+impl TracingMiddleware {
+  /// Target used for the emitted spans.
+  pub const TARGET: &'static str = "fraktor::actor::receive";
+
+  /// Creates the middleware.
+  #[must_use]
+  pub fn new() -> Self {
+    Self { ids: RandomState::new(), sequence: AtomicU64::new(0) }
+  }
+
+  /// Returns the trace context of the message currently processed on this thread, if any.
+  #[must_use]
+  pub fn current() -> Option<TraceContext> {
+    ACTIVE.with(|active| active.borrow().last().map(|entry| entry.context.clone()))
+  }
+
+  fn next_id(&self) -> u64 {
+    loop {
+      let id = self.ids.hash_one(self.sequence.fetch_add(1, Ordering::Relaxed));
+      if id != 0 {
+        return id;
+      }
+    }
+  }
+
+  fn next_span_id(&self) -> [u8; 8] {
+    self.next_id().to_be_bytes()
+  }
+
+  fn next_trace_id(&self) -> [u8; 16] {
+    let mut trace_id = [0_u8; 16];
+    trace_id[..8].copy_from_slice(&self.next_id().to_be_bytes());
+    trace_id[8..].copy_from_slice(&self.next_id().to_be_bytes());
+    trace_id
+  }
+}
+
+impl Default for TracingMiddleware {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl MessageInvokerMiddleware<StdToolbox> for TracingMiddleware {
+  fn before_user(
+    &self,
+    ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    message: &AnyMessageViewGeneric<'_, StdToolbox>,
+  ) -> Result<(), ActorError> {
+    let parent = message.headers().get::<TraceContext>();
+    let context = match parent {
+      | Some(parent) => parent.child(self.next_span_id()),
+      | None => TraceContext::new(self.next_trace_id(), self.next_span_id(), TraceContext::FLAG_SAMPLED),
+    };
+    let path = ctx.system().state().actor_path(&ctx.pid()).map(|path| path.to_relative_string());
+    let span = info_span!(
+      target: TracingMiddleware::TARGET,
+      "actor.receive",
+      actor.path = path.as_deref().unwrap_or("unknown"),
+      message.r#type = message.type_name().unwrap_or("unknown"),
+      trace_id = %context.trace_id_hex(),
+      span_id = %context.span_id_hex(),
+      parent_span_id = field::Empty,
+      error = field::Empty,
+    );
+    if let Some(parent) = parent {
+      span.record("parent_span_id", field::display(parent.span_id_hex()));
+    }
+    let entry = ActiveSpan { context, span: span.entered() };
+    ACTIVE.with(|active| active.borrow_mut().push(entry));
+    Ok(())
+  }
+
+  fn after_user(
+    &self,
+    _ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    _message: &AnyMessageViewGeneric<'_, StdToolbox>,
+    result: Result<(), ActorError>,
+  ) -> Result<(), ActorError> {
+    if let Some(entry) = ACTIVE.with(|active| active.borrow_mut().pop()) {
+      if let Err(error) = &result {
+        entry.span.record("error", field::debug(error.reason()));
+      }
+      // ここで EnteredSpan を破棄してスパンを閉じる
+      drop(entry);
+    }
+    result
+  }
+
+  fn before_send(&self, message: &mut AnyMessageGeneric<StdToolbox>) {
+    if message.headers().contains::<TraceContext>() {
+      return;
+    }
+    if let Some(context) = Self::current() {
+      message.headers_mut().insert(context);
+    }
+  }
+}
@@ -0,0 +1,178 @@
+extern crate std;
+
+use alloc::{borrow::ToOwned, collections::BTreeMap, format, string::String, vec::Vec};
+use std::{
+  fmt,
+  sync::{Arc, Mutex},
+};
+
+use fraktor_utils_rs::{core::sync::ArcShared, std::runtime_toolbox::StdToolbox};
+use tracing::{
+  Event, Metadata, Subscriber,
+  field::{Field, Visit},
+  span::{Attributes, Id, Record},
+  subscriber::with_default,
+};
+
+use super::TracingMiddleware;
+use crate::core::{
+  actor_prim::{Actor, ActorContextGeneric, actor_ref::ActorRefGeneric},
+  error::ActorError,
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric, TraceContext, message_invoker::MessageInvokerMiddleware},
+  props::PropsGeneric,
+  system::ActorSystemConfigGeneric,
+  testkit::ActorTestKitGeneric,
+};
+
+type Seen = Arc<Mutex<Vec<Option<TraceContext>>>>;
+
+struct Forwarder {
+  next: ActorRefGeneric<StdToolbox>,
+}
+
+impl Actor<StdToolbox> for Forwarder {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    _message: AnyMessageViewGeneric<'_, StdToolbox>,
+  ) -> Result<(), ActorError> {
+    self.next.tell(AnyMessageGeneric::new(String::from("hop"))).map_err(|error| ActorError::from_send_error(&error))
+  }
+}
+
+struct Sink {
+  seen: Seen,
+}
+
+impl Actor<StdToolbox> for Sink {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    message: AnyMessageViewGeneric<'_, StdToolbox>,
+  ) -> Result<(), ActorError> {
+    self.seen.lock().expect("lock").push(message.headers().get::<TraceContext>().cloned());
+    Ok(())
+  }
+}
+
+fn kit() -> ActorTestKitGeneric<StdToolbox> {
+  let middleware: ArcShared<dyn MessageInvokerMiddleware<StdToolbox>> = ArcShared::new(TracingMiddleware::new());
+  ActorTestKitGeneric::with_config(ActorSystemConfigGeneric::default().with_message_middleware(middleware))
+    .expect("kit")
+}
+
+#[test]
+fn spans_follow_messages_across_actors() {
+  let collector = RecordingSubscriber::default();
+  let seen: Seen = Arc::default();
+  let kit = kit();
+
+  with_default(collector.clone(), || {
+    let sink_seen = seen.clone();
+    let sink = kit
+      .spawn(&PropsGeneric::from_fn(move || Sink { seen: sink_seen.clone() }).with_name("sink"))
+      .expect("spawn sink");
+    let next = sink.actor_ref().clone();
+    let forwarder = kit
+      .spawn(&PropsGeneric::from_fn(move || Forwarder { next: next.clone() }).with_name("forwarder"))
+      .expect("spawn forwarder");
+    forwarder.tell(AnyMessageGeneric::new(1_u32)).expect("tell");
+  });
+
+  let spans = collector.spans();
+  assert_eq!(spans.len(), 2);
+  let (first, second) = (&spans[0], &spans[1]);
+  assert!(first["actor.path"].ends_with("/forwarder"));
+  assert_eq!(first["message.type"], "u32");
+  assert!(!first.contains_key("parent_span_id"));
+  assert!(second["actor.path"].ends_with("/sink"));
+  assert_eq!(second["message.type"], core::any::type_name::<String>());
+  assert_eq!(second["trace_id"], first["trace_id"]);
+  assert_eq!(second["parent_span_id"], first["span_id"]);
+  assert_ne!(second["span_id"], first["span_id"]);
+
+  let seen = seen.lock().expect("lock").clone();
+  let header = seen[0].as_ref().expect("trace header");
+  assert_eq!(header.trace_id_hex(), first["trace_id"]);
+  assert_eq!(header.span_id_hex(), first["span_id"]);
+  assert!(TracingMiddleware::current().is_none());
+}
+
+#[test]
+fn incoming_trace_context_is_continued() {
+  let collector = RecordingSubscriber::default();
+  let seen: Seen = Arc::default();
+  let kit = kit();
+  let parent =
+    TraceContext::parse_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").expect("parse");
+
+  with_default(collector.clone(), || {
+    let sink_seen = seen.clone();
+    let sink = kit.spawn(&PropsGeneric::from_fn(move || Sink { seen: sink_seen.clone() })).expect("spawn");
+    sink.tell(AnyMessageGeneric::new(1_u32).with_header(parent.clone())).expect("tell");
+  });
+
+  let spans = collector.spans();
+  assert_eq!(spans.len(), 1);
+  assert_eq!(spans[0]["trace_id"], "4bf92f3577b34da6a3ce929d0e0e4736");
+  assert_eq!(spans[0]["parent_span_id"], "00f067aa0ba902b7");
+  assert_eq!(seen.lock().expect("lock").clone(), [Some(parent)]);
+}
+
+// スパンの生成時と後からの record で渡されたフィールドを文字列として記録する
+#[derive(Clone, Default)]
+struct RecordingSubscriber {
+  spans: Arc<Mutex<Vec<BTreeMap<String, String>>>>,
+}
+
+impl RecordingSubscriber {
+  fn spans(&self) -> Vec<BTreeMap<String, String>> {
+    self.spans.lock().expect("lock").clone()
+  }
+}
+
+impl Subscriber for RecordingSubscriber {
+  fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+    true
+  }
+
+  fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+    let mut visitor = FieldVisitor::default();
+    attributes.record(&mut visitor);
+    let mut spans = self.spans.lock().expect("lock");
+    spans.push(visitor.fields);
+    Id::from_u64(spans.len() as u64)
+  }
+
+  fn record(&self, span: &Id, values: &Record<'_>) {
+    let mut visitor = FieldVisitor::default();
+    values.record(&mut visitor);
+    let mut spans = self.spans.lock().expect("lock");
+    if let Some(fields) = spans.get_mut(span.into_u64() as usize - 1) {
+      fields.extend(visitor.fields);
+    }
+  }
+
+  fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+  fn event(&self, _: &Event<'_>) {}
+
+  fn enter(&self, _: &Id) {}
+
+  fn exit(&self, _: &Id) {}
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+  fields: BTreeMap<String, String>,
+}
+
+impl Visit for FieldVisitor {
+  fn record_str(&mut self, field: &Field, value: &str) {
+    self.fields.insert(field.name().to_owned(), value.to_owned());
+  }
+
+  fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+    self.fields.insert(field.name().to_owned(), format!("{value:?}"));
+  }
+}
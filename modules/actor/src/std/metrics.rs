@@ -0,0 +1,13 @@
+mod metrics_collector;
+mod metrics_core;
+mod metrics_registry;
+mod metrics_subscriber;
+mod processing_time_middleware;
+mod prometheus_endpoint;
+mod prometheus_metrics;
+mod prometheus_metrics_config;
+
+pub use metrics_collector::MetricsCollector;
+pub use metrics_registry::MetricsRegistry;
+pub use prometheus_metrics::PrometheusMetrics;
+pub use prometheus_metrics_config::PrometheusMetricsConfig;
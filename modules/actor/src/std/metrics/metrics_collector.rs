@@ -0,0 +1,11 @@
+use super::MetricsRegistry;
+
+/// Source of metrics that are read on demand rather than pushed through the event stream.
+///
+/// Collectors registered with
+/// [`PrometheusMetrics::register_collector`](super::PrometheusMetrics::register_collector)
+/// run right before every scrape and typically copy a snapshot of another subsystem into gauges.
+pub trait MetricsCollector: Send + Sync + 'static {
+  /// Writes the current values into `registry`.
+  fn collect(&self, registry: &mut MetricsRegistry);
+}
@@ -0,0 +1,170 @@
+extern crate std;
+
+use core::time::Duration;
+use std::{boxed::Box, collections::HashMap, string::String, vec::Vec};
+
+use fraktor_utils_rs::{
+  core::sync::ArcShared,
+  std::{StdSyncMutex, runtime_toolbox::StdToolbox},
+};
+
+use super::{MetricsCollector, MetricsRegistry};
+use crate::core::{
+  actor_prim::Pid, event_stream::EventStreamEvent, lifecycle::LifecycleStage, scheduler::TickDriverKind,
+  system::SystemStateGeneric,
+};
+
+const MAILBOX_DEPTH: &str = "fraktor_mailbox_depth";
+const ACTORS: &str = "fraktor_actors";
+const PROCESSING_SECONDS: &str = "fraktor_actor_processing_seconds";
+const RESTARTS: &str = "fraktor_actor_restarts_total";
+const DEAD_LETTERS: &str = "fraktor_dead_letters_total";
+const SCHEDULER_LAG: &str = "fraktor_scheduler_lag_seconds";
+const SCHEDULER_TICKS: &str = "fraktor_scheduler_ticks_per_second";
+const SCHEDULER_DROPPED: &str = "fraktor_scheduler_dropped_ticks";
+const TICK_RESOLUTION: &str = "fraktor_tick_driver_resolution_seconds";
+const UNKNOWN_KIND: &str = "unknown";
+
+/// Aggregation state shared by the event stream subscriber, the processing time middleware and
+/// the scrape endpoint.
+#[derive(Clone)]
+pub(crate) struct MetricsCore {
+  state:      ArcShared<StdSyncMutex<MetricsState>>,
+  // 収集時に他のサブシステムのロックを取るため、集計状態とは別のロックで保持する
+  collectors: ArcShared<StdSyncMutex<Vec<Box<dyn MetricsCollector>>>>,
+}
+
+struct MetricsState {
+  registry:        MetricsRegistry,
+  latency_buckets: Vec<f64>,
+  actors:          HashMap<Pid, TrackedActor>,
+  depth_by_kind:   HashMap<&'static str, usize>,
+}
+
+struct TrackedActor {
+  kind:  &'static str,
+  depth: usize,
+}
+
+impl MetricsCore {
+  pub(crate) fn new(latency_buckets: &[f64]) -> Self {
+    let state = MetricsState {
+      registry:        MetricsRegistry::new(),
+      latency_buckets: latency_buckets.to_vec(),
+      actors:          HashMap::new(),
+      depth_by_kind:   HashMap::new(),
+    };
+    Self {
+      state:      ArcShared::new(StdSyncMutex::new(state)),
+      collectors: ArcShared::new(StdSyncMutex::new(Vec::new())),
+    }
+  }
+
+  pub(crate) fn register_collector(&self, collector: Box<dyn MetricsCollector>) {
+    self.collectors.lock().push(collector);
+  }
+
+  pub(crate) fn with_registry<R>(&self, f: impl FnOnce(&MetricsRegistry) -> R) -> R {
+    f(&self.state.lock().registry)
+  }
+
+  pub(crate) fn render(&self) -> String {
+    let mut collected = MetricsRegistry::new();
+    for collector in self.collectors.lock().iter() {
+      collector.collect(&mut collected);
+    }
+    let mut rendered = self.state.lock().registry.render();
+    rendered.push_str(&collected.render());
+    rendered
+  }
+
+  pub(crate) fn record_processing_time(&self, system: &SystemStateGeneric<StdToolbox>, pid: Pid, elapsed: Duration) {
+    let mut state = self.state.lock();
+    let kind = state.track(system, pid).kind;
+    let buckets = core::mem::take(&mut state.latency_buckets);
+    state.registry.observe(
+      PROCESSING_SECONDS,
+      "Time spent in Actor::receive, by actor kind.",
+      &buckets,
+      &[("actor_kind", kind)],
+      elapsed.as_secs_f64(),
+    );
+    state.latency_buckets = buckets;
+  }
+
+  pub(crate) fn on_event(&self, system: &SystemStateGeneric<StdToolbox>, event: &EventStreamEvent<StdToolbox>) {
+    let mut state = self.state.lock();
+    match event {
+      | EventStreamEvent::Lifecycle(event) => match event.stage() {
+        | LifecycleStage::Started => {
+          let kind = state.track(system, event.pid()).kind;
+          state.adjust_actors(kind, 1);
+        },
+        | LifecycleStage::Restarted => {
+          let kind = state.track(system, event.pid()).kind;
+          state.registry.increment_counter(RESTARTS, "Actor restarts, by actor kind.", &[("actor_kind", kind)], 1);
+        },
+        | LifecycleStage::Stopped => {
+          if let Some(actor) = state.actors.remove(&event.pid()) {
+            state.set_depth(actor.kind, actor.depth, 0);
+            state.adjust_actors(actor.kind, -1);
+          }
+        },
+      },
+      | EventStreamEvent::Mailbox(event) => {
+        let actor = state.track(system, event.pid());
+        let (kind, previous) = (actor.kind, core::mem::replace(&mut actor.depth, event.user_len()));
+        state.set_depth(kind, previous, event.user_len());
+      },
+      | EventStreamEvent::DeadLetter(entry) => {
+        let reason = entry.reason().as_str();
+        state.registry.increment_counter(DEAD_LETTERS, "Dead letters, by reason.", &[("reason", reason)], 1);
+      },
+      | EventStreamEvent::SchedulerTick(metrics) => {
+        let lag = metrics.drift().map_or(0.0, |drift| drift.as_secs_f64());
+        state.registry.set_gauge(SCHEDULER_LAG, "Drift of the scheduler tick against its resolution.", &[], lag);
+        let ticks = f64::from(metrics.ticks_per_sec());
+        state.registry.set_gauge(SCHEDULER_TICKS, "Scheduler ticks per second.", &[], ticks);
+        let dropped = metrics.dropped_total() as f64;
+        state.registry.set_gauge(SCHEDULER_DROPPED, "Scheduler ticks dropped since the driver started.", &[], dropped);
+      },
+      | EventStreamEvent::TickDriver(snapshot) => {
+        let driver = driver_label(snapshot.kind);
+        let resolution = snapshot.resolution.as_secs_f64();
+        state.registry.set_gauge(TICK_RESOLUTION, "Resolution of the tick driver.", &[("driver", driver)], resolution);
+      },
+      | _ => {},
+    }
+  }
+}
+
+impl MetricsState {
+  fn track(&mut self, system: &SystemStateGeneric<StdToolbox>, pid: Pid) -> &mut TrackedActor {
+    self.actors.entry(pid).or_insert_with(|| {
+      let kind = system.cell(&pid).and_then(|cell| cell.actor_type()).unwrap_or(UNKNOWN_KIND);
+      TrackedActor { kind, depth: 0 }
+    })
+  }
+
+  fn set_depth(&mut self, kind: &'static str, previous: usize, current: usize) {
+    let total = self.depth_by_kind.entry(kind).or_insert(0);
+    *total = total.saturating_sub(previous).saturating_add(current);
+    let total = *total as f64;
+    self.registry.set_gauge(MAILBOX_DEPTH, "Queued user messages, by actor kind.", &[("actor_kind", kind)], total);
+  }
+
+  fn adjust_actors(&mut self, kind: &'static str, delta: i64) {
+    let labels = [("actor_kind", kind)];
+    let current = self.registry.gauge(ACTORS, &labels).unwrap_or(0.0);
+    self.registry.set_gauge(ACTORS, "Running actors, by actor kind.", &labels, (current + delta as f64).max(0.0));
+  }
+}
+
+const fn driver_label(kind: TickDriverKind) -> &'static str {
+  match kind {
+    | TickDriverKind::Auto => "auto",
+    | TickDriverKind::Hardware { .. } => "hardware",
+    #[cfg(any(test, feature = "test-support"))]
+    | TickDriverKind::ManualTest => "manual_test",
+  }
+}
@@ -0,0 +1,234 @@
+extern crate std;
+
+#[cfg(test)]
+mod tests;
+
+use core::fmt::Write;
+use std::{
+  collections::BTreeMap,
+  string::{String, ToString},
+  vec::Vec,
+};
+
+type Labels = Vec<(String, String)>;
+
+/// Counters, gauges and histograms rendered in the Prometheus text exposition format.
+///
+/// Metric families are created on first use; the help text and buckets given at that point are
+/// kept. Series are identified by their label set and rendered in a stable order.
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+  families: BTreeMap<String, MetricFamily>,
+}
+
+#[derive(Debug)]
+struct MetricFamily {
+  help:   String,
+  kind:   MetricKind,
+  series: BTreeMap<Labels, MetricValue>,
+}
+
+#[derive(Debug)]
+enum MetricKind {
+  Counter,
+  Gauge,
+  Histogram(Vec<f64>),
+}
+
+#[derive(Debug)]
+enum MetricValue {
+  Counter(u64),
+  Gauge(f64),
+  Histogram { counts: Vec<u64>, sum: f64, count: u64 },
+}
+
+impl MetricsRegistry {
+  /// Creates an empty registry.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { families: BTreeMap::new() }
+  }
+
+  /// Adds `value` to the counter `name` for the given labels.
+  pub fn increment_counter(&mut self, name: &str, help: &str, labels: &[(&str, &str)], value: u64) {
+    let family = self.family(name, help, MetricKind::Counter);
+    if let MetricValue::Counter(total) = family.series.entry(to_labels(labels)).or_insert(MetricValue::Counter(0)) {
+      *total = total.saturating_add(value);
+    }
+  }
+
+  /// Sets the gauge `name` for the given labels.
+  pub fn set_gauge(&mut self, name: &str, help: &str, labels: &[(&str, &str)], value: f64) {
+    let family = self.family(name, help, MetricKind::Gauge);
+    family.series.insert(to_labels(labels), MetricValue::Gauge(value));
+  }
+
+  /// Records `value` into the histogram `name` for the given labels.
+  ///
+  /// `buckets` are the upper bounds used when the histogram is first created; they are sorted
+  /// and the implicit `+Inf` bucket is always added.
+  pub fn observe(&mut self, name: &str, help: &str, buckets: &[f64], labels: &[(&str, &str)], value: f64) {
+    let mut bounds = buckets.to_vec();
+    bounds.sort_by(f64::total_cmp);
+    let family = self.family(name, help, MetricKind::Histogram(bounds));
+    let MetricKind::Histogram(bounds) = &family.kind else {
+      return;
+    };
+    let slots = bounds.len();
+    let entry = family.series.entry(to_labels(labels)).or_insert_with(|| MetricValue::Histogram {
+      counts: std::vec![0; slots],
+      sum:    0.0,
+      count:  0,
+    });
+    if let MetricValue::Histogram { counts, sum, count } = entry {
+      // バケットは上限値以下の観測数を累積で持つため、該当する全バケットを加算する
+      for (slot, bound) in counts.iter_mut().zip(bounds) {
+        if value <= *bound {
+          *slot += 1;
+        }
+      }
+      *sum += value;
+      *count += 1;
+    }
+  }
+
+  /// Replaces the histogram `name` for the given labels with pre-aggregated values.
+  ///
+  /// `buckets` pairs each upper bound with the cumulative number of observations at or below it,
+  /// as kept by histograms maintained outside the registry. The bounds are only used when the
+  /// histogram is first created; later calls are mapped onto the existing bounds.
+  pub fn set_histogram(
+    &mut self,
+    name: &str,
+    help: &str,
+    labels: &[(&str, &str)],
+    buckets: &[(f64, u64)],
+    sum: f64,
+    count: u64,
+  ) {
+    let mut bounds: Vec<f64> = buckets.iter().map(|(bound, _)| *bound).collect();
+    bounds.sort_by(f64::total_cmp);
+    let family = self.family(name, help, MetricKind::Histogram(bounds));
+    let MetricKind::Histogram(bounds) = &family.kind else {
+      return;
+    };
+    // 累積値なので、各境界以下で最も大きい件数がその境界のバケット値になる
+    let counts = bounds
+      .iter()
+      .map(|bound| {
+        buckets.iter().filter(|(upper, _)| upper <= bound).map(|(_, cumulative)| *cumulative).max().unwrap_or(0)
+      })
+      .collect();
+    family.series.insert(to_labels(labels), MetricValue::Histogram { counts, sum, count });
+  }
+
+  /// Returns the value of a counter series, if present.
+  #[must_use]
+  pub fn counter(&self, name: &str, labels: &[(&str, &str)]) -> Option<u64> {
+    match self.families.get(name)?.series.get(&to_labels(labels))? {
+      | MetricValue::Counter(value) => Some(*value),
+      | _ => None,
+    }
+  }
+
+  /// Returns the value of a gauge series, if present.
+  #[must_use]
+  pub fn gauge(&self, name: &str, labels: &[(&str, &str)]) -> Option<f64> {
+    match self.families.get(name)?.series.get(&to_labels(labels))? {
+      | MetricValue::Gauge(value) => Some(*value),
+      | _ => None,
+    }
+  }
+
+  /// Returns the number of observations of a histogram series, if present.
+  #[must_use]
+  pub fn histogram_count(&self, name: &str, labels: &[(&str, &str)]) -> Option<u64> {
+    match self.families.get(name)?.series.get(&to_labels(labels))? {
+      | MetricValue::Histogram { count, .. } => Some(*count),
+      | _ => None,
+    }
+  }
+
+  /// Renders every metric family in the Prometheus text exposition format (version 0.0.4).
+  #[must_use]
+  pub fn render(&self) -> String {
+    let mut out = String::new();
+    for (name, family) in &self.families {
+      let kind = match family.kind {
+        | MetricKind::Counter => "counter",
+        | MetricKind::Gauge => "gauge",
+        | MetricKind::Histogram(_) => "histogram",
+      };
+      let _ = writeln!(out, "# HELP {name} {}", escape_help(&family.help));
+      let _ = writeln!(out, "# TYPE {name} {kind}");
+      for (labels, value) in &family.series {
+        match (value, &family.kind) {
+          | (MetricValue::Counter(total), _) => write_sample(&mut out, name, labels, None, &total.to_string()),
+          | (MetricValue::Gauge(value), _) => write_sample(&mut out, name, labels, None, &format_value(*value)),
+          | (MetricValue::Histogram { counts, sum, count }, MetricKind::Histogram(bounds)) => {
+            let bucket = std::format!("{name}_bucket");
+            for (bound, cumulative) in bounds.iter().zip(counts) {
+              let le = format_value(*bound);
+              write_sample(&mut out, &bucket, labels, Some(&le), &cumulative.to_string());
+            }
+            write_sample(&mut out, &bucket, labels, Some("+Inf"), &count.to_string());
+            write_sample(&mut out, &std::format!("{name}_sum"), labels, None, &format_value(*sum));
+            write_sample(&mut out, &std::format!("{name}_count"), labels, None, &count.to_string());
+          },
+          | (MetricValue::Histogram { .. }, _) => {},
+        }
+      }
+    }
+    out
+  }
+
+  fn family(&mut self, name: &str, help: &str, kind: MetricKind) -> &mut MetricFamily {
+    self.families.entry(name.to_string()).or_insert_with(|| MetricFamily {
+      help: help.to_string(),
+      kind,
+      series: BTreeMap::new(),
+    })
+  }
+}
+
+fn to_labels(labels: &[(&str, &str)]) -> Labels {
+  let mut labels: Labels = labels.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+  labels.sort();
+  labels
+}
+
+fn write_sample(out: &mut String, name: &str, labels: &Labels, le: Option<&str>, value: &str) {
+  out.push_str(name);
+  if !labels.is_empty() || le.is_some() {
+    out.push('{');
+    let mut first = true;
+    let extra = le.map(|le| ("le", le));
+    for (key, value) in labels.iter().map(|(key, value)| (key.as_str(), value.as_str())).chain(extra) {
+      if !first {
+        out.push(',');
+      }
+      first = false;
+      let _ = write!(out, "{key}=\"{}\"", escape_label(value));
+    }
+    out.push('}');
+  }
+  let _ = writeln!(out, " {value}");
+}
+
+fn format_value(value: f64) -> String {
+  if value.is_nan() {
+    String::from("NaN")
+  } else if value.is_infinite() {
+    String::from(if value > 0.0 { "+Inf" } else { "-Inf" })
+  } else {
+    value.to_string()
+  }
+}
+
+fn escape_help(help: &str) -> String {
+  help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn escape_label(value: &str) -> String {
+  value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
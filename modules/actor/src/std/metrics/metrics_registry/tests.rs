@@ -0,0 +1,57 @@
+use super::MetricsRegistry;
+
+#[test]
+fn counters_and_gauges_render_with_sorted_labels() {
+  let mut registry = MetricsRegistry::new();
+  registry.increment_counter("jobs_total", "Processed jobs.", &[("queue", "b"), ("app", "x")], 2);
+  registry.increment_counter("jobs_total", "Processed jobs.", &[("app", "x"), ("queue", "b")], 3);
+  registry.set_gauge("depth", "Queue depth.", &[], 4.5);
+
+  assert_eq!(registry.counter("jobs_total", &[("queue", "b"), ("app", "x")]), Some(5));
+  assert_eq!(
+    registry.render(),
+    "# HELP depth Queue depth.\n# TYPE depth gauge\ndepth 4.5\n# HELP jobs_total Processed jobs.\n# TYPE jobs_total \
+     counter\njobs_total{app=\"x\",queue=\"b\"} 5\n"
+  );
+}
+
+#[test]
+fn histograms_render_cumulative_buckets() {
+  let mut registry = MetricsRegistry::new();
+  for value in [0.05, 0.2, 3.0] {
+    registry.observe("latency_seconds", "Latency.", &[1.0, 0.1], &[("kind", "a")], value);
+  }
+
+  assert_eq!(registry.histogram_count("latency_seconds", &[("kind", "a")]), Some(3));
+  let rendered = registry.render();
+  assert!(rendered.contains("# TYPE latency_seconds histogram\n"));
+  assert!(rendered.contains("latency_seconds_bucket{kind=\"a\",le=\"0.1\"} 1\n"));
+  assert!(rendered.contains("latency_seconds_bucket{kind=\"a\",le=\"1\"} 2\n"));
+  assert!(rendered.contains("latency_seconds_bucket{kind=\"a\",le=\"+Inf\"} 3\n"));
+  assert!(rendered.contains("latency_seconds_sum{kind=\"a\"} 3.25\n"));
+  assert!(rendered.contains("latency_seconds_count{kind=\"a\"} 3\n"));
+}
+
+#[test]
+fn label_values_are_escaped() {
+  let mut registry = MetricsRegistry::new();
+  registry.set_gauge("info", "Info.", &[("name", "a\"b\\c")], 1.0);
+
+  assert!(registry.render().contains("info{name=\"a\\\"b\\\\c\"} 1\n"));
+}
+
+#[test]
+fn pre_aggregated_histograms_render_their_buckets() {
+  let mut registry = MetricsRegistry::new();
+  registry.set_histogram("rtt_seconds", "Round trips.", &[("peer", "a")], &[(0.1, 1), (0.5, 3)], 0.9, 4);
+  registry.set_histogram("rtt_seconds", "Round trips.", &[("peer", "a")], &[(0.1, 2), (0.5, 3)], 1.0, 5);
+
+  assert_eq!(registry.histogram_count("rtt_seconds", &[("peer", "a")]), Some(5));
+  let rendered = registry.render();
+  assert!(rendered.contains("# TYPE rtt_seconds histogram\n"));
+  assert!(rendered.contains("rtt_seconds_bucket{peer=\"a\",le=\"0.1\"} 2\n"));
+  assert!(rendered.contains("rtt_seconds_bucket{peer=\"a\",le=\"0.5\"} 3\n"));
+  assert!(rendered.contains("rtt_seconds_bucket{peer=\"a\",le=\"+Inf\"} 5\n"));
+  assert!(rendered.contains("rtt_seconds_sum{peer=\"a\"} 1\n"));
+  assert!(rendered.contains("rtt_seconds_count{peer=\"a\"} 5\n"));
+}
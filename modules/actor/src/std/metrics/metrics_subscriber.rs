@@ -0,0 +1,25 @@
+use fraktor_utils_rs::{core::sync::ArcShared, std::runtime_toolbox::StdToolbox};
+
+use super::metrics_core::MetricsCore;
+use crate::core::{
+  event_stream::{EventStreamEvent, EventStreamSubscriber},
+  system::SystemStateGeneric,
+};
+
+/// Feeds event stream events into the shared metrics state.
+pub(crate) struct MetricsSubscriber {
+  core:   MetricsCore,
+  system: ArcShared<SystemStateGeneric<StdToolbox>>,
+}
+
+impl MetricsSubscriber {
+  pub(crate) const fn new(core: MetricsCore, system: ArcShared<SystemStateGeneric<StdToolbox>>) -> Self {
+    Self { core, system }
+  }
+}
+
+impl EventStreamSubscriber<StdToolbox> for MetricsSubscriber {
+  fn on_event(&mut self, event: &EventStreamEvent<StdToolbox>) {
+    self.core.on_event(&self.system, event);
+  }
+}
@@ -0,0 +1,52 @@
+extern crate std;
+
+use core::cell::RefCell;
+use std::{thread_local, time::Instant, vec::Vec};
+
+use fraktor_utils_rs::std::runtime_toolbox::StdToolbox;
+
+use super::metrics_core::MetricsCore;
+use crate::core::{
+  actor_prim::ActorContextGeneric,
+  error::ActorError,
+  messaging::{AnyMessageViewGeneric, message_invoker::MessageInvokerMiddleware},
+};
+
+thread_local! {
+  static STARTED: RefCell<Vec<Instant>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Middleware measuring how long `Actor::receive` takes for every user message.
+pub(crate) struct ProcessingTimeMiddleware {
+  core: MetricsCore,
+}
+
+impl ProcessingTimeMiddleware {
+  pub(crate) const fn new(core: MetricsCore) -> Self {
+    Self { core }
+  }
+}
+
+impl MessageInvokerMiddleware<StdToolbox> for ProcessingTimeMiddleware {
+  fn before_user(
+    &self,
+    _ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    _message: &AnyMessageViewGeneric<'_, StdToolbox>,
+  ) -> Result<(), ActorError> {
+    STARTED.with(|started| started.borrow_mut().push(Instant::now()));
+    Ok(())
+  }
+
+  fn after_user(
+    &self,
+    ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    _message: &AnyMessageViewGeneric<'_, StdToolbox>,
+    result: Result<(), ActorError>,
+  ) -> Result<(), ActorError> {
+    // 入れ子の呼び出しに備えて、開始時刻はスレッドごとのスタックで管理する
+    if let Some(started) = STARTED.with(|started| started.borrow_mut().pop()) {
+      self.core.record_processing_time(&ctx.system().state(), ctx.pid(), started.elapsed());
+    }
+    result
+  }
+}
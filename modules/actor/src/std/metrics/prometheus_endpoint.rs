@@ -0,0 +1,33 @@
+extern crate std;
+
+use std::{
+  io,
+  net::{SocketAddr, ToSocketAddrs},
+};
+
+use super::metrics_core::MetricsCore;
+use crate::std::http::{HttpEndpoint, HttpResponse};
+
+/// Path serving the metrics.
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Minimal HTTP endpoint answering `GET /metrics` with the Prometheus text format.
+pub(crate) struct PrometheusEndpoint {
+  server: HttpEndpoint,
+}
+
+impl PrometheusEndpoint {
+  pub(crate) fn bind(addr: impl ToSocketAddrs, core: MetricsCore) -> io::Result<Self> {
+    let server = HttpEndpoint::bind(addr, "prometheus-endpoint", move |method, path| match (method, path) {
+      | ("GET", METRICS_PATH) => HttpResponse::new("200 OK", CONTENT_TYPE, core.render()),
+      | (_, METRICS_PATH) => HttpResponse::new("405 Method Not Allowed", CONTENT_TYPE, "method not allowed\n"),
+      | _ => HttpResponse::new("404 Not Found", CONTENT_TYPE, "not found\n"),
+    })?;
+    Ok(Self { server })
+  }
+
+  pub(crate) const fn local_addr(&self) -> SocketAddr {
+    self.server.local_addr()
+  }
+}
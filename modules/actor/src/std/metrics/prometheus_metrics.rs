@@ -0,0 +1,141 @@
+extern crate std;
+
+#[cfg(test)]
+mod tests;
+
+use std::{boxed::Box, format, net::SocketAddr, string::String};
+
+use fraktor_utils_rs::{
+  core::sync::ArcShared,
+  std::{StdSyncMutex, runtime_toolbox::StdToolbox},
+};
+
+use super::{
+  MetricsCollector, MetricsRegistry, PrometheusMetricsConfig, metrics_core::MetricsCore,
+  metrics_subscriber::MetricsSubscriber, processing_time_middleware::ProcessingTimeMiddleware,
+  prometheus_endpoint::PrometheusEndpoint,
+};
+use crate::{
+  core::{
+    event_stream::subscriber_handle,
+    extension::{Extension, ExtensionId, ExtensionInstaller},
+    messaging::message_invoker::MessageInvokerMiddleware,
+    system::{ActorSystemBuildError, ActorSystemGeneric},
+  },
+  std::event_stream::EventStreamSubscription,
+};
+
+/// Prometheus exporter aggregating actor, mailbox, scheduler and custom metrics.
+///
+/// Once installed as an extension (it implements [`ExtensionInstaller`]), the exporter subscribes
+/// to the event stream and maintains:
+///
+/// - `fraktor_mailbox_depth` and `fraktor_actors`, gauges per actor kind;
+/// - `fraktor_actor_restarts_total` per actor kind and `fraktor_dead_letters_total` per reason;
+/// - `fraktor_scheduler_lag_seconds`, `fraktor_scheduler_ticks_per_second`,
+///   `fraktor_scheduler_dropped_ticks` and `fraktor_tick_driver_resolution_seconds`;
+/// - `fraktor_actor_processing_seconds`, a histogram per actor kind fed by
+///   [`middleware`](Self::middleware).
+///
+/// The actor kind is the Rust type name of the actor recorded by
+/// [`Props::from_fn`](crate::core::props::PropsGeneric::from_fn), or `unknown`. Metrics owned by
+/// other subsystems, such as cluster membership, are added with
+/// [`register_collector`](Self::register_collector).
+///
+/// The metrics can be rendered with [`render`](Self::render) or served on `GET /metrics` when a
+/// listen address is configured.
+#[derive(Clone)]
+pub struct PrometheusMetrics {
+  inner: ArcShared<PrometheusMetricsInner>,
+}
+
+struct PrometheusMetricsInner {
+  config:       PrometheusMetricsConfig,
+  core:         MetricsCore,
+  subscription: StdSyncMutex<Option<EventStreamSubscription>>,
+  endpoint:     StdSyncMutex<Option<PrometheusEndpoint>>,
+}
+
+impl PrometheusMetrics {
+  /// Name under which [`middleware`](Self::middleware) is registered when the exporter is
+  /// installed.
+  pub const TIMING_MIDDLEWARE: &'static str = "timing";
+
+  /// Creates an exporter that starts collecting once installed into an actor system.
+  #[must_use]
+  pub fn new(config: PrometheusMetricsConfig) -> Self {
+    let core = MetricsCore::new(config.latency_buckets());
+    let inner =
+      PrometheusMetricsInner { config, core, subscription: StdSyncMutex::new(None), endpoint: StdSyncMutex::new(None) };
+    Self { inner: ArcShared::new(inner) }
+  }
+
+  /// Returns the middleware recording the processing time of user messages.
+  ///
+  /// Register it with
+  /// [`ActorSystemConfig::with_message_middleware`](crate::std::system::ActorSystemConfig::with_message_middleware)
+  /// to populate `fraktor_actor_processing_seconds` for every actor, or name
+  /// [`TIMING_MIDDLEWARE`](Self::TIMING_MIDDLEWARE) in the props of the actors to measure.
+  #[must_use]
+  pub fn middleware(&self) -> ArcShared<dyn MessageInvokerMiddleware<StdToolbox>> {
+    ArcShared::new(ProcessingTimeMiddleware::new(self.inner.core.clone()))
+  }
+
+  /// Adds a collector that runs before every rendering.
+  pub fn register_collector(&self, collector: impl MetricsCollector) {
+    self.inner.core.register_collector(Box::new(collector));
+  }
+
+  /// Renders the current metrics in the Prometheus text exposition format.
+  #[must_use]
+  pub fn render(&self) -> String {
+    self.inner.core.render()
+  }
+
+  /// Runs `f` against the metrics aggregated from the event stream.
+  pub fn with_registry<R>(&self, f: impl FnOnce(&MetricsRegistry) -> R) -> R {
+    self.inner.core.with_registry(f)
+  }
+
+  /// Returns the address the HTTP endpoint is listening on, if it is running.
+  #[must_use]
+  pub fn local_addr(&self) -> Option<SocketAddr> {
+    self.inner.endpoint.lock().as_ref().map(PrometheusEndpoint::local_addr)
+  }
+
+  /// Stops collecting events and shuts the HTTP endpoint down.
+  pub fn shutdown(&self) {
+    self.inner.subscription.lock().take();
+    self.inner.endpoint.lock().take();
+  }
+}
+
+impl Extension<StdToolbox> for PrometheusMetrics {}
+
+impl ExtensionInstaller<StdToolbox> for PrometheusMetrics {
+  fn install(&self, system: &ActorSystemGeneric<StdToolbox>) -> Result<(), ActorSystemBuildError> {
+    if let Some(addr) = self.inner.config.listen_addr() {
+      let endpoint = PrometheusEndpoint::bind(addr, self.inner.core.clone()).map_err(|error| {
+        ActorSystemBuildError::Configuration(format!("failed to bind the metrics endpoint to {addr}: {error}"))
+      })?;
+      *self.inner.endpoint.lock() = Some(endpoint);
+    }
+    let subscriber = subscriber_handle(MetricsSubscriber::new(self.inner.core.clone(), system.state()));
+    *self.inner.subscription.lock() = Some(system.subscribe_event_stream(&subscriber));
+    system.extended().middlewares().register_or_update(Self::TIMING_MIDDLEWARE, self.middleware());
+    let _ = system.extended().register_extension(&PrometheusMetricsId { metrics: self.clone() });
+    Ok(())
+  }
+}
+
+struct PrometheusMetricsId {
+  metrics: PrometheusMetrics,
+}
+
+impl ExtensionId<StdToolbox> for PrometheusMetricsId {
+  type Ext = PrometheusMetrics;
+
+  fn create_extension(&self, _system: &ActorSystemGeneric<StdToolbox>) -> Self::Ext {
+    self.metrics.clone()
+  }
+}
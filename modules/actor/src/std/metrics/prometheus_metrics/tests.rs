@@ -0,0 +1,127 @@
+extern crate std;
+
+use alloc::string::String;
+use std::{
+  io::{Read, Write},
+  net::TcpStream,
+};
+
+use fraktor_utils_rs::std::runtime_toolbox::StdToolbox;
+
+use super::PrometheusMetrics;
+use crate::{
+  core::{
+    actor_prim::{Actor, ActorContextGeneric},
+    dead_letter::DeadLetterReason,
+    error::ActorError,
+    extension::ExtensionInstallers,
+    messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+    props::PropsGeneric,
+    system::ActorSystemConfigGeneric,
+    testkit::ActorTestKitGeneric,
+  },
+  std::metrics::{MetricsCollector, MetricsRegistry, PrometheusMetricsConfig},
+};
+
+struct Worker;
+
+impl Actor<StdToolbox> for Worker {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    message: AnyMessageViewGeneric<'_, StdToolbox>,
+  ) -> Result<(), ActorError> {
+    match message.downcast_ref::<&str>() {
+      | Some(&"fail") => Err(ActorError::recoverable("boom")),
+      | _ => Ok(()),
+    }
+  }
+}
+
+struct StaticCollector;
+
+impl MetricsCollector for StaticCollector {
+  fn collect(&self, registry: &mut MetricsRegistry) {
+    registry.set_gauge("custom_members", "Members.", &[], 3.0);
+  }
+}
+
+fn kit(metrics: &PrometheusMetrics) -> ActorTestKitGeneric<StdToolbox> {
+  let installers = ExtensionInstallers::default().with_extension_installer(metrics.clone());
+  let config = ActorSystemConfigGeneric::default()
+    .with_message_middleware(metrics.middleware())
+    .with_extension_installers(installers);
+  ActorTestKitGeneric::with_config(config).expect("kit")
+}
+
+fn worker_kind() -> (&'static str, &'static str) {
+  ("actor_kind", core::any::type_name::<Worker>())
+}
+
+#[test]
+fn actor_activity_is_aggregated_per_actor_kind() {
+  let metrics = PrometheusMetrics::new(PrometheusMetricsConfig::new());
+  let kit = kit(&metrics);
+
+  let worker = kit.spawn(&PropsGeneric::from_fn(|| Worker)).expect("spawn");
+  worker.tell(AnyMessageGeneric::new("ok")).expect("tell");
+  worker.tell(AnyMessageGeneric::new("ok")).expect("tell");
+  worker.tell(AnyMessageGeneric::new("fail")).expect("tell");
+  worker.stop().expect("stop");
+  kit.system().record_dead_letter(AnyMessageGeneric::new("late"), DeadLetterReason::MailboxFull, Some(worker.pid()));
+
+  metrics.with_registry(|registry| {
+    assert_eq!(registry.histogram_count("fraktor_actor_processing_seconds", &[worker_kind()]), Some(3));
+    assert_eq!(registry.counter("fraktor_actor_restarts_total", &[worker_kind()]), Some(1));
+    assert_eq!(registry.gauge("fraktor_actors", &[worker_kind()]), Some(0.0));
+    assert_eq!(registry.gauge("fraktor_mailbox_depth", &[worker_kind()]), Some(0.0));
+    assert_eq!(registry.counter("fraktor_dead_letters_total", &[("reason", "mailbox_full")]), Some(1));
+  });
+  let rendered = metrics.render();
+  assert!(rendered.contains("# TYPE fraktor_actor_processing_seconds histogram\n"));
+  assert!(rendered.contains("# TYPE fraktor_dead_letters_total counter\n"));
+}
+
+#[test]
+fn timing_middleware_is_resolved_by_name() {
+  let metrics = PrometheusMetrics::new(PrometheusMetricsConfig::new());
+  let installers = ExtensionInstallers::default().with_extension_installer(metrics.clone());
+  let kit = ActorTestKitGeneric::with_config(ActorSystemConfigGeneric::default().with_extension_installers(installers))
+    .expect("kit");
+
+  let timed = PropsGeneric::from_fn(|| Worker).with_middleware([PrometheusMetrics::TIMING_MIDDLEWARE]);
+  kit.spawn(&timed).expect("spawn").tell(AnyMessageGeneric::new("ok")).expect("tell");
+  kit.spawn(&PropsGeneric::from_fn(|| Worker)).expect("spawn").tell(AnyMessageGeneric::new("ok")).expect("tell");
+
+  metrics.with_registry(|registry| {
+    assert_eq!(registry.histogram_count("fraktor_actor_processing_seconds", &[worker_kind()]), Some(1));
+  });
+}
+
+#[test]
+fn collectors_are_rendered_after_event_metrics() {
+  let metrics = PrometheusMetrics::new(PrometheusMetricsConfig::new());
+  metrics.register_collector(StaticCollector);
+
+  assert!(metrics.render().contains("# TYPE custom_members gauge\ncustom_members 3\n"));
+}
+
+#[test]
+fn endpoint_serves_the_text_format() {
+  let metrics = PrometheusMetrics::new(PrometheusMetricsConfig::new().with_listen_port(0));
+  metrics.register_collector(StaticCollector);
+  let _kit = kit(&metrics);
+  let addr = metrics.local_addr().expect("endpoint running");
+
+  let mut stream = TcpStream::connect(addr).expect("connect");
+  stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").expect("write");
+  let mut response = String::new();
+  stream.read_to_string(&mut response).expect("read");
+
+  assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+  assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+  assert!(response.contains("custom_members 3\n"));
+
+  metrics.shutdown();
+  assert!(metrics.local_addr().is_none());
+}
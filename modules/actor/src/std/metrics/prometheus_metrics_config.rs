@@ -0,0 +1,65 @@
+extern crate std;
+
+use std::{
+  net::{Ipv4Addr, SocketAddr},
+  vec::Vec,
+};
+
+/// Default upper bounds, in seconds, of the processing time histogram.
+const DEFAULT_LATENCY_BUCKETS: [f64; 10] = [0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0];
+
+/// Settings of [`PrometheusMetrics`](super::PrometheusMetrics).
+#[derive(Clone, Debug)]
+pub struct PrometheusMetricsConfig {
+  listen_addr:     Option<SocketAddr>,
+  latency_buckets: Vec<f64>,
+}
+
+impl PrometheusMetricsConfig {
+  /// Creates a config that only renders metrics on demand.
+  #[must_use]
+  pub fn new() -> Self {
+    Self { listen_addr: None, latency_buckets: DEFAULT_LATENCY_BUCKETS.to_vec() }
+  }
+
+  /// Serves `GET /metrics` on `127.0.0.1:port` once the metrics are installed.
+  ///
+  /// Port `0` lets the operating system pick a free port; see
+  /// [`PrometheusMetrics::local_addr`](super::PrometheusMetrics::local_addr).
+  #[must_use]
+  pub const fn with_listen_port(self, port: u16) -> Self {
+    self.with_listen_addr(SocketAddr::new(std::net::IpAddr::V4(Ipv4Addr::LOCALHOST), port))
+  }
+
+  /// Serves `GET /metrics` on `addr` once the metrics are installed.
+  #[must_use]
+  pub const fn with_listen_addr(mut self, addr: SocketAddr) -> Self {
+    self.listen_addr = Some(addr);
+    self
+  }
+
+  /// Overrides the upper bounds, in seconds, of the processing time histogram.
+  #[must_use]
+  pub fn with_latency_buckets(mut self, buckets: impl Into<Vec<f64>>) -> Self {
+    self.latency_buckets = buckets.into();
+    self
+  }
+
+  /// Returns the address of the HTTP endpoint, if enabled.
+  #[must_use]
+  pub const fn listen_addr(&self) -> Option<SocketAddr> {
+    self.listen_addr
+  }
+
+  /// Returns the upper bounds of the processing time histogram.
+  #[must_use]
+  pub const fn latency_buckets(&self) -> &[f64] {
+    self.latency_buckets.as_slice()
+  }
+}
+
+impl Default for PrometheusMetricsConfig {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -1,5 +1,9 @@
 //! Scheduler utilities specialised for the standard toolbox runtime.
 
+mod execution_trace_file;
+
 /// Tick driver integrations for standard runtimes.
 #[cfg(feature = "tokio-executor")]
 pub mod tick;
+
+pub use execution_trace_file::ExecutionTraceFile;
@@ -0,0 +1,58 @@
+//! Persistence of execution traces for CI capture and local replay.
+
+extern crate std;
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::Write as _;
+use std::{fs, io, path::Path};
+
+use crate::core::scheduler::DeterministicEvent;
+
+#[cfg(test)]
+mod tests;
+
+/// Stores execution traces and deterministic logs as text files with one event per line.
+///
+/// Save the [`events`](crate::core::scheduler::ExecutionTraceGeneric::events) of a recording trace
+/// when a test fails, then pass the loaded events to
+/// [`ExecutionTraceGeneric::replaying`](crate::core::scheduler::ExecutionTraceGeneric::replaying)
+/// to reproduce the same interleaving. Lines use the text form of [`DeterministicEvent`]; blank
+/// lines and lines starting with `#` are ignored.
+pub struct ExecutionTraceFile;
+
+impl ExecutionTraceFile {
+  /// Writes `events` to `path`, replacing any existing file.
+  ///
+  /// # Errors
+  ///
+  /// Returns an [`io::Error`] when the file cannot be written.
+  pub fn save(path: impl AsRef<Path>, events: &[DeterministicEvent]) -> io::Result<()> {
+    let mut contents = String::new();
+    for event in events {
+      let _ = writeln!(contents, "{event}");
+    }
+    fs::write(path, contents)
+  }
+
+  /// Reads the events stored at `path`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an [`io::Error`] when the file cannot be read, or one of kind
+  /// [`io::ErrorKind::InvalidData`] naming the first malformed line.
+  pub fn load(path: impl AsRef<Path>) -> io::Result<Vec<DeterministicEvent>> {
+    let contents = fs::read_to_string(path)?;
+    let mut events = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+      let line = line.trim();
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      let event = DeterministicEvent::parse(line).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("malformed execution trace line {}: {line}", index + 1))
+      })?;
+      events.push(event);
+    }
+    Ok(events)
+  }
+}
@@ -0,0 +1,40 @@
+extern crate std;
+
+use std::{env, fs, io, path::PathBuf, process};
+
+use super::ExecutionTraceFile;
+use crate::core::{
+  actor_prim::Pid,
+  scheduler::{DeterministicEvent, ExecutionBatch},
+};
+
+fn temp_path(name: &str) -> PathBuf {
+  env::temp_dir().join(format!("fraktor-{}-{name}.trace", process::id()))
+}
+
+#[test]
+fn saved_traces_load_back() {
+  let path = temp_path("round-trip");
+  let events = [DeterministicEvent::MailboxRun { pid: Pid::new(1, 0) }, DeterministicEvent::Fired {
+    handle_id:  2,
+    fired_tick: 5,
+    batch:      ExecutionBatch::oneshot(),
+  }];
+
+  ExecutionTraceFile::save(&path, &events).expect("save");
+  let loaded = ExecutionTraceFile::load(&path).expect("load");
+  let _ = fs::remove_file(&path);
+
+  assert_eq!(loaded, events);
+}
+
+#[test]
+fn malformed_lines_are_reported() {
+  let path = temp_path("malformed");
+  fs::write(&path, "# captured in CI\n\nrun 1 0\nrun one 0\n").expect("write");
+  let error = ExecutionTraceFile::load(&path).expect_err("malformed");
+  let _ = fs::remove_file(&path);
+
+  assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+  assert!(error.to_string().contains("line 4"));
+}
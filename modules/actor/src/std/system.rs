@@ -1,5 +1,9 @@
 mod actor_system_config;
+#[cfg(feature = "introspection-http")]
+mod actor_tree_endpoint;
 mod base;
 
 pub use actor_system_config::*;
+#[cfg(feature = "introspection-http")]
+pub use actor_tree_endpoint::ActorTreeEndpoint;
 pub use base::*;
@@ -5,20 +5,33 @@ use fraktor_utils_rs::{core::sync::ArcShared, std::runtime_toolbox::StdToolbox};
 use crate::{
   core::{
     actor_prim::actor_path::GuardianKind,
+    dead_letter::DeadLetterConfig,
     dispatcher::DispatcherConfigGeneric,
     extension::ExtensionInstallers,
+    messaging::message_invoker::MessageInvokerMiddleware,
     scheduler::{SchedulerConfig, TickDriverConfig},
     system::{ActorRefProviderInstaller, ActorSystemConfigGeneric as CoreActorSystemConfigGeneric, RemotingConfig},
   },
-  std::dispatcher::DispatcherConfig,
+  std::{dispatcher::DispatcherConfig, messaging::CatchPanicMiddleware},
 };
 
 /// Configuration for the actor system.
-#[derive(Default)]
+///
+/// Besides the core built-ins, the middleware registry contains
+/// [`CatchPanicMiddleware`] under [`CatchPanicMiddleware::NAME`].
 pub struct ActorSystemConfig {
   inner: CoreActorSystemConfigGeneric<StdToolbox>,
 }
 
+impl Default for ActorSystemConfig {
+  fn default() -> Self {
+    let catch_panic: ArcShared<dyn MessageInvokerMiddleware<StdToolbox>> = ArcShared::new(CatchPanicMiddleware::new());
+    Self {
+      inner: CoreActorSystemConfigGeneric::default().with_named_middleware(CatchPanicMiddleware::NAME, catch_panic),
+    }
+  }
+}
+
 impl ActorSystemConfig {
   /// Sets the actor system name.
   #[must_use]
@@ -78,6 +91,31 @@ impl ActorSystemConfig {
     self
   }
 
+  /// Appends middleware applied to every actor of the system.
+  #[must_use]
+  pub fn with_message_middleware(mut self, middleware: ArcShared<dyn MessageInvokerMiddleware<StdToolbox>>) -> Self {
+    self.inner = self.inner.with_message_middleware(middleware);
+    self
+  }
+
+  /// Registers middleware under `name` so props can refer to it by name.
+  #[must_use]
+  pub fn with_named_middleware(
+    mut self,
+    name: impl Into<String>,
+    middleware: ArcShared<dyn MessageInvokerMiddleware<StdToolbox>>,
+  ) -> Self {
+    self.inner = self.inner.with_named_middleware(name, middleware);
+    self
+  }
+
+  /// Sets the deadletter retention and logging configuration.
+  #[must_use]
+  pub fn with_dead_letter_config(mut self, config: DeadLetterConfig) -> Self {
+    self.inner = self.inner.with_dead_letter_config(config);
+    self
+  }
+
   /// Returns the system name.
   #[must_use]
   pub fn system_name(&self) -> &str {
@@ -0,0 +1,76 @@
+extern crate std;
+
+use std::{
+  io,
+  net::{SocketAddr, ToSocketAddrs},
+  string::ToString,
+  vec::Vec,
+};
+
+use fraktor_utils_rs::std::runtime_toolbox::StdToolbox;
+
+use crate::{
+  core::system::ActorSystemGeneric,
+  std::{
+    http::{HttpEndpoint, HttpResponse},
+    system::ActorSystem,
+  },
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Path serving the actor tree snapshot.
+const SNAPSHOT_PATH: &str = "/actors";
+const CONTENT_TYPE: &str = "application/json";
+
+/// Minimal HTTP endpoint exposing [`ActorSystem::snapshot_tree`] as JSON for ops dashboards.
+///
+/// `GET /actors` answers with the current
+/// [`ActorTreeSnapshot`](crate::core::system::ActorTreeSnapshot); any other request gets `404` or
+/// `405`. Requests are served one at a time on a dedicated thread, which stops when the endpoint is
+/// shut down or dropped; reads and writes time out after 2 seconds so a stalled client cannot
+/// block other requests or the shutdown.
+pub struct ActorTreeEndpoint {
+  server: HttpEndpoint,
+}
+
+impl ActorTreeEndpoint {
+  /// Binds the endpoint to `addr` and starts serving snapshots of `system`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an I/O error when the listener cannot be bound or the serving thread cannot be
+  /// spawned.
+  pub fn bind(system: &ActorSystem, addr: impl ToSocketAddrs) -> io::Result<Self> {
+    let system = system.as_core().clone();
+    let server = HttpEndpoint::bind(addr, "actor-tree-endpoint", move |method, path| respond(&system, method, path))?;
+    Ok(Self { server })
+  }
+
+  /// Returns the address the endpoint is listening on.
+  #[must_use]
+  pub const fn local_addr(&self) -> SocketAddr {
+    self.server.local_addr()
+  }
+
+  /// Stops serving and waits for the serving thread to exit.
+  pub fn shutdown(self) {
+    drop(self);
+  }
+}
+
+fn respond(system: &ActorSystemGeneric<StdToolbox>, method: &str, path: &str) -> HttpResponse {
+  match (method, path) {
+    | ("GET", SNAPSHOT_PATH) => match serde_json::to_vec(&system.snapshot_tree()) {
+      | Ok(body) => HttpResponse::new("200 OK", CONTENT_TYPE, body),
+      | Err(error) => HttpResponse::new("500 Internal Server Error", CONTENT_TYPE, error_body(&error.to_string())),
+    },
+    | (_, SNAPSHOT_PATH) => HttpResponse::new("405 Method Not Allowed", CONTENT_TYPE, error_body("method not allowed")),
+    | _ => HttpResponse::new("404 Not Found", CONTENT_TYPE, error_body("not found")),
+  }
+}
+
+fn error_body(message: &str) -> Vec<u8> {
+  serde_json::json!({ "error": message }).to_string().into_bytes()
+}
@@ -0,0 +1,72 @@
+extern crate std;
+
+use std::{
+  io::{Read, Write},
+  net::TcpStream,
+  string::String,
+};
+
+use super::ActorTreeEndpoint;
+use crate::{
+  core::{
+    error::ActorError,
+    scheduler::{ManualTestDriver, TickDriverConfig},
+  },
+  std::{
+    actor_prim::{Actor, ActorContext},
+    messaging::AnyMessageView,
+    props::Props,
+    system::ActorSystem,
+  },
+};
+
+struct Guardian;
+
+impl Actor for Guardian {
+  fn receive(&mut self, _ctx: &mut ActorContext<'_, '_>, _message: AnyMessageView<'_>) -> Result<(), ActorError> {
+    Ok(())
+  }
+}
+
+fn system() -> ActorSystem {
+  let props = Props::from_fn(|| Guardian);
+  ActorSystem::new(&props, TickDriverConfig::manual(ManualTestDriver::new())).expect("system")
+}
+
+fn request(endpoint: &ActorTreeEndpoint, line: &str) -> (String, String) {
+  let mut stream = TcpStream::connect(endpoint.local_addr()).expect("connect");
+  stream.write_all(std::format!("{line}\r\nHost: localhost\r\n\r\n").as_bytes()).expect("write");
+  let mut response = String::new();
+  stream.read_to_string(&mut response).expect("read");
+  let (head, body) = response.split_once("\r\n\r\n").expect("response");
+  let status = head.lines().next().expect("status line").to_owned();
+  (status, body.to_owned())
+}
+
+#[test]
+fn serves_the_actor_tree_as_json() {
+  let system = system();
+  let endpoint = ActorTreeEndpoint::bind(&system, "127.0.0.1:0").expect("bind");
+
+  let (status, body) = request(&endpoint, "GET /actors HTTP/1.1");
+  assert_eq!(status, "HTTP/1.1 200 OK");
+  let value: serde_json::Value = serde_json::from_str(&body).expect("json");
+  let expected = system.snapshot_tree();
+  let root = expected.root().expect("root");
+  assert_eq!(value["root"]["pid"], std::format!("{}", root.pid()));
+  assert_eq!(value["root"]["children"].as_array().map(|children| children.len()), Some(root.children().len()));
+
+  endpoint.shutdown();
+}
+
+#[test]
+fn rejects_unknown_paths_and_methods() {
+  let system = system();
+  let endpoint = ActorTreeEndpoint::bind(&system, "127.0.0.1:0").expect("bind");
+
+  let (status, body) = request(&endpoint, "GET /missing HTTP/1.1");
+  assert_eq!(status, "HTTP/1.1 404 Not Found");
+  assert!(body.contains("not found"));
+  let (status, _) = request(&endpoint, "POST /actors HTTP/1.1");
+  assert_eq!(status, "HTTP/1.1 405 Method Not Allowed");
+}
@@ -6,13 +6,14 @@ pub use crate::std::dispatcher::{DispatchExecutor, DispatchShared, Dispatcher, D
 use crate::{
   core::{
     actor_prim::{Pid, actor_path::ActorPath},
+    dead_letter::{DeadLetterQuery, DeadLetterReason, DeadLetterSuppression},
     event_stream::{TickDriverSnapshot, subscriber_handle as core_subscriber_handle},
     logging::LogLevel,
     scheduler::{SchedulerContext, TickDriverConfig},
     spawn::SpawnError,
     system::{
-      ActorRefResolveError, ActorSystemGeneric as CoreActorSystemGeneric, ExtendedActorSystemGeneric,
-      SystemStateGeneric as CoreSystemStateGeneric,
+      ActorRefResolveError, ActorSystemGeneric as CoreActorSystemGeneric, ActorTreeSnapshot,
+      ExtendedActorSystemGeneric, SystemStateGeneric as CoreSystemStateGeneric,
     },
   },
   std::{
@@ -49,7 +50,7 @@ impl ActorSystem {
   /// Returns [`SpawnError`] when the user guardian props cannot be initialised or tick driver setup
   /// fails.
   pub fn new(props: &Props, tick_driver_config: TickDriverConfig<StdToolbox>) -> Result<Self, SpawnError> {
-    CoreActorSystemGeneric::new(props.as_core(), tick_driver_config).map(Self::from_core)
+    Self::new_with_config(props, &ActorSystemConfig::default().with_tick_driver(tick_driver_config))
   }
 
   /// Creates a new actor system with an explicit configuration.
@@ -123,6 +124,12 @@ impl ActorSystem {
     self.inner.tick_driver_snapshot()
   }
 
+  /// Captures the live actor tree with mailbox depths and per-actor processing stats.
+  #[must_use]
+  pub fn snapshot_tree(&self) -> ActorTreeSnapshot {
+    self.inner.snapshot_tree()
+  }
+
   /// Returns the shared scheduler context if installed.
   #[must_use]
   pub fn scheduler_context(&self) -> Option<ArcShared<SchedulerContext<StdToolbox>>> {
@@ -142,6 +149,25 @@ impl ActorSystem {
     self.inner.dead_letters()
   }
 
+  /// Returns the retained dead letters matching `query`.
+  #[must_use]
+  pub fn query_dead_letters(&self, query: &DeadLetterQuery) -> Vec<DeadLetterEntry> {
+    self.inner.query_dead_letters(query)
+  }
+
+  /// Returns the number of dead letters recorded per reason since the system started.
+  #[must_use]
+  pub fn dead_letter_counts(&self) -> Vec<(DeadLetterReason, u64)> {
+    self.inner.dead_letter_counts()
+  }
+
+  /// Suppresses the logging of dead letters carrying a `T` payload, however the message was built.
+  pub fn register_dead_letter_suppression<T>(&self)
+  where
+    T: DeadLetterSuppression, {
+    self.inner.register_dead_letter_suppression::<T>();
+  }
+
   /// Emits a log event with the specified severity.
   pub fn emit_log(&self, level: LogLevel, message: impl Into<String>, origin: Option<Pid>) {
     self.inner.emit_log(level, message, origin)
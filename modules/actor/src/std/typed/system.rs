@@ -4,7 +4,11 @@ use fraktor_utils_rs::{core::sync::ArcShared, std::runtime_toolbox::StdToolbox};
 
 use crate::{
   core::{
-    actor_prim::Pid, event_stream::subscriber_handle as core_subscriber_handle, logging::LogLevel, spawn::SpawnError,
+    actor_prim::Pid,
+    dead_letter::{DeadLetterQuery, DeadLetterReason, DeadLetterSuppression},
+    event_stream::subscriber_handle as core_subscriber_handle,
+    logging::LogLevel,
+    spawn::SpawnError,
     typed::TypedActorSystemGeneric as CoreTypedActorSystemGeneric,
   },
   std::{
@@ -91,6 +95,25 @@ where
     self.inner.dead_letters()
   }
 
+  /// Returns the retained dead letters matching `query`.
+  #[must_use]
+  pub fn query_dead_letters(&self, query: &DeadLetterQuery) -> Vec<DeadLetterEntry> {
+    self.inner.query_dead_letters(query)
+  }
+
+  /// Returns the number of dead letters recorded per reason since the system started.
+  #[must_use]
+  pub fn dead_letter_counts(&self) -> Vec<(DeadLetterReason, u64)> {
+    self.inner.dead_letter_counts()
+  }
+
+  /// Suppresses the logging of dead letters carrying a `T` payload, however the message was built.
+  pub fn register_dead_letter_suppression<T>(&self)
+  where
+    T: DeadLetterSuppression, {
+    self.inner.register_dead_letter_suppression::<T>();
+  }
+
   /// Emits a log event with the specified severity.
   pub fn emit_log(&self, level: LogLevel, message: impl Into<String>, origin: Option<Pid>) {
     self.inner.emit_log(level, message, origin);
@@ -16,7 +16,20 @@ mod cluster_provider;
 mod cluster_provider_error;
 mod cluster_pub_sub;
 mod cluster_pub_sub_impl;
+mod cluster_router;
+mod cluster_router_settings;
+mod cluster_singleton_action;
+mod cluster_singleton_delivery;
+mod cluster_singleton_event;
+mod cluster_singleton_manager;
+mod cluster_singleton_manager_actor;
+mod cluster_singleton_pdu;
+mod cluster_singleton_proxy;
+mod cluster_singleton_proxy_actor;
+mod cluster_singleton_serializer;
+mod cluster_singleton_settings;
 mod cluster_topology;
+mod deactivation_reason;
 mod delivery_policy;
 mod dispatch_drop_policy;
 mod gossip_engine;
@@ -24,14 +37,19 @@ mod gossip_event;
 mod gossip_outbound;
 mod gossip_state;
 mod gossiper;
+mod grain_handoff;
 mod grain_key;
+mod grain_lifecycle_signal;
 mod grain_rpc_router;
+mod grain_state_provider;
+mod handoff_completion;
 mod identity_event;
 mod identity_lookup;
 mod identity_setup_error;
 mod identity_table;
 mod kind_registry;
 mod lookup_error;
+mod member_path;
 mod membership_delta;
 mod membership_error;
 mod membership_event;
@@ -52,6 +70,7 @@ mod outbound_state;
 mod partition_behavior;
 mod partition_identity_lookup;
 mod partition_identity_lookup_config;
+mod passivation_policy;
 mod pid_cache;
 mod pid_cache_event;
 mod pub_sub_broker;
@@ -59,6 +78,7 @@ mod pub_sub_error;
 mod pub_sub_event;
 mod pub_sub_metrics;
 mod pub_sub_topic_metrics;
+mod rebalance_coordinator;
 mod rendezvous_hasher;
 mod resolve_error;
 mod resolve_result;
@@ -87,7 +107,18 @@ pub use cluster_provider::{ClusterProvider, LocalClusterProvider, NoopClusterPro
 pub use cluster_provider_error::ClusterProviderError;
 pub use cluster_pub_sub::ClusterPubSub;
 pub use cluster_pub_sub_impl::ClusterPubSubImpl;
+pub use cluster_router::ClusterRouter;
+pub use cluster_router_settings::ClusterRouterSettings;
+pub use cluster_singleton_action::ClusterSingletonAction;
+pub use cluster_singleton_delivery::ClusterSingletonDelivery;
+pub use cluster_singleton_event::ClusterSingletonEvent;
+pub use cluster_singleton_manager::ClusterSingletonManager;
+pub use cluster_singleton_manager_actor::ClusterSingletonManagerActor;
+pub use cluster_singleton_proxy::ClusterSingletonProxy;
+pub use cluster_singleton_proxy_actor::ClusterSingletonProxyActor;
+pub use cluster_singleton_settings::ClusterSingletonSettings;
 pub use cluster_topology::ClusterTopology;
+pub use deactivation_reason::DeactivationReason;
 pub use delivery_policy::DeliveryPolicy;
 pub use dispatch_drop_policy::DispatchDropPolicy;
 pub use gossip_engine::GossipEngine;
@@ -95,14 +126,19 @@ pub use gossip_event::GossipEvent;
 pub use gossip_outbound::GossipOutbound;
 pub use gossip_state::GossipState;
 pub use gossiper::Gossiper;
+pub use grain_handoff::GrainHandoff;
 pub use grain_key::GrainKey;
+pub use grain_lifecycle_signal::GrainLifecycleSignal;
 pub use grain_rpc_router::GrainRpcRouter;
+pub use grain_state_provider::GrainStateProvider;
+pub use handoff_completion::HandoffCompletion;
 pub use identity_event::IdentityEvent;
 pub use identity_lookup::IdentityLookup;
 pub use identity_setup_error::IdentitySetupError;
 pub use identity_table::IdentityTable;
 pub use kind_registry::{KindRegistry, TOPIC_ACTOR_KIND};
 pub use lookup_error::LookupError;
+pub use member_path::MemberPath;
 pub use membership_delta::MembershipDelta;
 pub use membership_error::MembershipError;
 pub use membership_event::MembershipEvent;
@@ -123,6 +159,7 @@ pub use outbound_state::OutboundState;
 pub use partition_behavior::PartitionBehavior;
 pub use partition_identity_lookup::PartitionIdentityLookup;
 pub use partition_identity_lookup_config::PartitionIdentityLookupConfig;
+pub use passivation_policy::PassivationPolicy;
 pub use pid_cache::PidCache;
 pub use pid_cache_event::PidCacheEvent;
 pub use pub_sub_broker::PubSubBroker;
@@ -130,6 +167,7 @@ pub use pub_sub_error::PubSubError;
 pub use pub_sub_event::PubSubEvent;
 pub use pub_sub_metrics::PubSubMetrics;
 pub use pub_sub_topic_metrics::PubSubTopicMetrics;
+pub use rebalance_coordinator::RebalanceCoordinator;
 pub use rendezvous_hasher::RendezvousHasher;
 pub use resolve_error::ResolveError;
 pub use resolve_result::ResolveResult;
@@ -1,18 +1,29 @@
 //! Defines an activated cluster kind.
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
+
+use crate::core::passivation_policy::PassivationPolicy;
+
+#[cfg(test)]
+mod tests;
 
 /// Describes a cluster kind that can be activated.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ActivatedKind {
-  name: String,
+  name:               String,
+  required_roles:     Vec<String>,
+  passivation_policy: PassivationPolicy,
 }
 
 impl ActivatedKind {
   /// Creates a new activated kind with the provided name.
   #[must_use]
   pub fn new(name: impl Into<String>) -> Self {
-    Self { name: name.into() }
+    Self {
+      name:               name.into(),
+      required_roles:     Vec::new(),
+      passivation_policy: PassivationPolicy::new(),
+    }
   }
 
   /// Returns the kind name.
@@ -21,4 +32,37 @@ impl ActivatedKind {
   pub fn name(&self) -> &str {
     &self.name
   }
+
+  /// Restricts placement to members advertising all of the given roles.
+  #[must_use]
+  pub fn with_required_roles(mut self, roles: Vec<String>) -> Self {
+    self.required_roles = roles;
+    self
+  }
+
+  /// Returns the roles a member must advertise to host this kind.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn required_roles(&self) -> &[String] {
+    &self.required_roles
+  }
+
+  /// Returns true when a member with `roles` may host this kind.
+  #[must_use]
+  pub fn accepts_roles(&self, roles: &[String]) -> bool {
+    self.required_roles.iter().all(|required| roles.contains(required))
+  }
+
+  /// Sets the passivation policy applied to activations of this kind.
+  #[must_use]
+  pub const fn with_passivation_policy(mut self, policy: PassivationPolicy) -> Self {
+    self.passivation_policy = policy;
+    self
+  }
+
+  /// Returns the passivation policy.
+  #[must_use]
+  pub const fn passivation_policy(&self) -> &PassivationPolicy {
+    &self.passivation_policy
+  }
 }
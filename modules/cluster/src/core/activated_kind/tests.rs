@@ -0,0 +1,16 @@
+use alloc::{string::ToString, vec};
+
+use super::ActivatedKind;
+
+#[test]
+fn kind_without_required_roles_accepts_any_member() {
+  let kind = ActivatedKind::new("user");
+  assert!(kind.accepts_roles(&[]));
+}
+
+#[test]
+fn kind_with_required_roles_needs_all_of_them() {
+  let kind = ActivatedKind::new("payments").with_required_roles(vec!["payments".to_string(), "eu".to_string()]);
+  assert!(!kind.accepts_roles(&["payments".to_string()]));
+  assert!(kind.accepts_roles(&["eu".to_string(), "payments".to_string(), "extra".to_string()]));
+}
@@ -15,4 +15,9 @@ pub enum ActivationError {
   },
   /// No authority candidates were provided.
   NoAuthority,
+  /// The grain is being handed off to a new owner.
+  HandoffInProgress {
+    /// Grain key string representation.
+    key: String,
+  },
 }
@@ -22,8 +22,8 @@ use fraktor_utils_rs::core::{
 
 use crate::core::{
   ActivatedKind, ClusterError, ClusterEvent, ClusterExtensionConfig, ClusterMetrics, ClusterMetricsSnapshot,
-  ClusterProvider, ClusterPubSub, ClusterTopology, Gossiper, IdentityLookup, IdentitySetupError, KindRegistry,
-  MetricsError, PidCache, StartupMode,
+  ClusterProvider, ClusterPubSub, ClusterTopology, Gossiper, GrainKey, GrainLifecycleSignal, HandoffCompletion,
+  IdentityLookup, IdentitySetupError, KindRegistry, MetricsError, PidCache, SerializedMessage, StartupMode,
 };
 
 /// Aggregates configuration and shared dependencies for cluster runtime flows.
@@ -42,6 +42,7 @@ pub struct ClusterCore<TB: RuntimeToolbox + 'static> {
   metrics:             Option<ClusterMetrics>,
   blocked_members:     Vec<String>,
   member_count:        usize,
+  members:             Vec<String>,
   pid_cache:           Option<PidCache>,
   last_topology_hash:  Option<u64>,
 }
@@ -80,6 +81,7 @@ impl<TB: RuntimeToolbox + 'static> ClusterCore<TB> {
       metrics,
       blocked_members: Vec::new(),
       member_count: 0,
+      members: Vec::new(),
       pid_cache: None,
       last_topology_hash: None,
     }
@@ -296,6 +298,56 @@ impl<TB: RuntimeToolbox + 'static> ClusterCore<TB> {
     self.blocked_members = self.block_list_provider.blocked_members();
   }
 
+  /// Resolves the PID hosting `key`, activating the grain if needed.
+  ///
+  /// Returns `None` while the grain is being handed off to a new owner; requests sent in the
+  /// meantime must go through [`route_grain_request`](Self::route_grain_request).
+  #[must_use]
+  pub fn resolve_grain(&self, key: &GrainKey, now: u64) -> Option<String> {
+    self.identity_lookup.lock().get(key, now)
+  }
+
+  /// Routes a request addressed to `key`.
+  ///
+  /// Returns the message back for immediate delivery, or `None` when it was buffered because
+  /// the grain is being handed off. Buffered requests are returned by
+  /// [`complete_grain_handoff`](Self::complete_grain_handoff) and
+  /// [`expire_grain_handoffs`](Self::expire_grain_handoffs).
+  #[must_use]
+  pub fn route_grain_request(&self, key: &GrainKey, message: SerializedMessage) -> Option<SerializedMessage> {
+    self.identity_lookup.lock().buffer_during_handoff(key, message)
+  }
+
+  /// Completes the handoff of `key` once the previous owner drained and stopped the grain.
+  ///
+  /// Returns the PID on the new owner and the requests buffered during the move, or `None`
+  /// if the key was not being handed off.
+  pub fn complete_grain_handoff(&self, key: &GrainKey, state: Option<Vec<u8>>, now: u64) -> Option<HandoffCompletion> {
+    self.identity_lookup.lock().complete_handoff(key, state, now)
+  }
+
+  /// Reports that a grain stopped after a [`GrainLifecycleSignal::Deactivating`] signal.
+  ///
+  /// Saves `state` (or the last snapshot when `None`) through the installed state provider.
+  pub fn complete_grain_deactivation(&self, key: &GrainKey, state: Option<Vec<u8>>) {
+    self.identity_lookup.lock().complete_deactivation(key, state);
+  }
+
+  /// Drains lifecycle signals to deliver to the grains hosted by this node.
+  #[must_use]
+  pub fn drain_grain_lifecycle_signals(&self) -> Vec<GrainLifecycleSignal> {
+    self.identity_lookup.lock().drain_lifecycle_signals()
+  }
+
+  /// Force-completes handoffs whose previous owner left or did not report within the
+  /// configured timeout.
+  ///
+  /// Must be called periodically by the runtime hosting the grains; the returned completions
+  /// carry the buffered requests to deliver to the new activations.
+  pub fn expire_grain_handoffs(&self, now: u64) -> Vec<HandoffCompletion> {
+    self.identity_lookup.lock().expire_handoffs(now)
+  }
+
   /// Applies a topology update and returns the event to be published.
   ///
   /// This method applies the topology internally but does NOT publish the event.
@@ -367,14 +419,24 @@ impl<TB: RuntimeToolbox + 'static> ClusterCore<TB> {
       }
     }
 
-    // IdentityLookup に離脱メンバーを伝播
-    // 注: update_topology は完全なメンバーリストを必要とするため、
-    // ClusterTopology のデルタ情報からは on_member_left のみを呼び出す
+    // デルタから完全なメンバーリストを組み立てる
+    for authority in topology.joined() {
+      if !self.members.contains(authority) {
+        self.members.push(authority.clone());
+      }
+    }
+    self.members.retain(|member| !topology.left().contains(member));
+
+    // IdentityLookup に離脱メンバーを伝播した後、完全なメンバーリストで再配置を促す
     {
       let mut identity_guard = self.identity_lookup.lock();
+      for (authority, roles) in topology.member_roles() {
+        identity_guard.update_member_roles(authority, roles.clone());
+      }
       for authority in topology.left() {
         identity_guard.on_member_left(authority);
       }
+      identity_guard.update_topology(self.members.clone());
     }
 
     true
@@ -12,7 +12,8 @@ use fraktor_utils_rs::core::{
 use super::*;
 use crate::core::{
   ActivatedKind, ClusterEvent, ClusterProviderError, ClusterPubSub, ClusterTopology, Gossiper, IdentityLookup,
-  IdentitySetupError, KindRegistry, MetricsError, PidCacheEvent, StartupMode, TOPIC_ACTOR_KIND, grain_key::GrainKey,
+  IdentitySetupError, KindRegistry, MetricsError, PartitionIdentityLookup, PartitionIdentityLookupConfig,
+  PidCacheEvent, RendezvousHasher, SerializedMessage, StartupMode, TOPIC_ACTOR_KIND, grain_key::GrainKey,
   pid_cache::PidCache, pub_sub_error::PubSubError,
 };
 
@@ -1149,3 +1150,168 @@ fn metrics_disabled_full_lifecycle_events_continue() {
   // metrics は終始 Disabled のまま
   assert!(matches!(core.metrics(), Err(MetricsError::Disabled)));
 }
+
+type RecordedRoles = Vec<(String, Vec<String>)>;
+
+#[derive(Clone)]
+struct RoleRecordingIdentityLookup {
+  roles: ArcShared<NoStdMutex<RecordedRoles>>,
+}
+
+impl IdentityLookup for RoleRecordingIdentityLookup {
+  fn setup_member(&mut self, _kinds: &[ActivatedKind]) -> Result<(), IdentitySetupError> {
+    Ok(())
+  }
+
+  fn setup_client(&mut self, _kinds: &[ActivatedKind]) -> Result<(), IdentitySetupError> {
+    Ok(())
+  }
+
+  fn update_member_roles(&mut self, authority: &str, roles: Vec<String>) {
+    self.roles.lock().push((authority.to_string(), roles));
+  }
+}
+
+#[test]
+fn apply_topology_forwards_member_roles_to_identity_lookup() {
+  let lookup = RoleRecordingIdentityLookup { roles: ArcShared::new(NoStdMutex::new(Vec::new())) };
+  let mut core = ClusterCore::new(
+    &ClusterExtensionConfig::new(),
+    wrap_provider(StubProvider),
+    ArcShared::new(StubBlockListProvider::new(vec![])),
+    ArcShared::new(EventStreamGeneric::<NoStdToolbox>::default()),
+    wrap_gossiper(StubGossiper::new()),
+    wrap_pubsub(StubPubSub::new()),
+    KindRegistry::new(),
+    wrap_identity_lookup(lookup.clone()),
+  );
+
+  let mut roles = alloc::collections::BTreeMap::new();
+  roles.insert("node-b".to_string(), vec!["payments".to_string()]);
+  let topology = ClusterTopology::new(7, vec!["node-b".to_string()], vec![]).with_member_roles(roles);
+  core.apply_topology(&topology);
+
+  assert_eq!(lookup.roles.lock().clone(), vec![("node-b".to_string(), vec!["payments".to_string()])]);
+}
+
+type RecordedTopologies = Vec<Vec<String>>;
+
+#[derive(Clone)]
+struct TopologyRecordingIdentityLookup {
+  topologies: ArcShared<NoStdMutex<RecordedTopologies>>,
+}
+
+impl IdentityLookup for TopologyRecordingIdentityLookup {
+  fn setup_member(&mut self, _kinds: &[ActivatedKind]) -> Result<(), IdentitySetupError> {
+    Ok(())
+  }
+
+  fn setup_client(&mut self, _kinds: &[ActivatedKind]) -> Result<(), IdentitySetupError> {
+    Ok(())
+  }
+
+  fn update_topology(&mut self, authorities: Vec<String>) {
+    self.topologies.lock().push(authorities);
+  }
+}
+
+#[test]
+fn apply_topology_passes_full_member_list_to_identity_lookup() {
+  let lookup = TopologyRecordingIdentityLookup { topologies: ArcShared::new(NoStdMutex::new(Vec::new())) };
+  let mut core = ClusterCore::new(
+    &ClusterExtensionConfig::new(),
+    wrap_provider(StubProvider),
+    ArcShared::new(StubBlockListProvider::new(vec![])),
+    ArcShared::new(EventStreamGeneric::<NoStdToolbox>::default()),
+    wrap_gossiper(StubGossiper::new()),
+    wrap_pubsub(StubPubSub::new()),
+    KindRegistry::new(),
+    wrap_identity_lookup(lookup.clone()),
+  );
+
+  core.apply_topology(&ClusterTopology::new(1, vec!["node-a".to_string(), "node-b".to_string()], vec![]));
+  core.apply_topology(&ClusterTopology::new(2, vec!["node-c".to_string()], vec!["node-a".to_string()]));
+
+  assert_eq!(lookup.topologies.lock().clone(), vec![vec!["node-a".to_string(), "node-b".to_string()], vec![
+    "node-b".to_string(),
+    "node-c".to_string()
+  ],]);
+}
+
+fn build_core_with_partition_lookup(config: PartitionIdentityLookupConfig) -> ClusterCore<NoStdToolbox> {
+  ClusterCore::new(
+    &ClusterExtensionConfig::new(),
+    wrap_provider(StubProvider),
+    ArcShared::new(StubBlockListProvider::new(vec![])),
+    ArcShared::new(EventStreamGeneric::<NoStdToolbox>::default()),
+    wrap_gossiper(StubGossiper::new()),
+    wrap_pubsub(StubPubSub::new()),
+    KindRegistry::new(),
+    wrap_identity_lookup(PartitionIdentityLookup::new(config)),
+  )
+}
+
+// node-b の参加で node-a から node-b へ移動するキーを探す
+fn key_moving_to_node_b() -> GrainKey {
+  let both = vec!["node-a".to_string(), "node-b".to_string()];
+  (0..)
+    .map(|index| GrainKey::new(alloc::format!("user:{index}")))
+    .find(|key| RendezvousHasher::select(&both, key).is_some_and(|owner| owner == "node-b"))
+    .expect("moving key")
+}
+
+#[test]
+fn moved_grain_resolves_on_new_owner_after_drain_completes() {
+  let mut core = build_core_with_partition_lookup(PartitionIdentityLookupConfig::default());
+  let key = key_moving_to_node_b();
+
+  core.apply_topology(&ClusterTopology::new(1, vec!["node-a".to_string()], vec![]));
+  let old_pid = core.resolve_grain(&key, 1).expect("initial activation");
+  assert!(old_pid.starts_with("node-a"));
+
+  core.apply_topology(&ClusterTopology::new(2, vec!["node-b".to_string()], vec![]));
+  assert_eq!(core.resolve_grain(&key, 2), None);
+  let request = SerializedMessage::new(vec![7], 1);
+  assert_eq!(core.route_grain_request(&key, request.clone()), None);
+
+  let completion = core.complete_grain_handoff(&key, None, 3).expect("handoff completion");
+  assert!(completion.pid.starts_with("node-b"));
+  assert_eq!(completion.buffered, vec![request.clone()]);
+
+  assert_eq!(core.resolve_grain(&key, 4), Some(completion.pid));
+  assert_eq!(core.route_grain_request(&key, request.clone()), Some(request));
+}
+
+#[test]
+fn stalled_handoff_is_force_completed_after_timeout() {
+  let mut core =
+    build_core_with_partition_lookup(PartitionIdentityLookupConfig::default().with_handoff_timeout_secs(10));
+  let key = key_moving_to_node_b();
+
+  core.apply_topology(&ClusterTopology::new(1, vec!["node-a".to_string()], vec![]));
+  core.resolve_grain(&key, 1).expect("initial activation");
+  core.apply_topology(&ClusterTopology::new(2, vec!["node-b".to_string()], vec![]));
+
+  assert!(core.expire_grain_handoffs(5).is_empty());
+  assert_eq!(core.resolve_grain(&key, 6), None);
+
+  let completions = core.expire_grain_handoffs(15);
+  assert_eq!(completions.len(), 1);
+  assert!(core.resolve_grain(&key, 16).is_some_and(|pid| pid.starts_with("node-b")));
+}
+
+#[test]
+fn handoff_from_departed_owner_is_force_completed() {
+  let mut core = build_core_with_partition_lookup(PartitionIdentityLookupConfig::default());
+  let key = key_moving_to_node_b();
+
+  core.apply_topology(&ClusterTopology::new(1, vec!["node-a".to_string()], vec![]));
+  core.resolve_grain(&key, 1).expect("initial activation");
+  core.apply_topology(&ClusterTopology::new(2, vec!["node-b".to_string()], vec![]));
+  core.apply_topology(&ClusterTopology::new(3, vec![], vec!["node-a".to_string()]));
+
+  let completions = core.expire_grain_handoffs(4);
+  assert_eq!(completions.len(), 1);
+  assert!(completions[0].pid.starts_with("node-b"));
+  assert!(core.resolve_grain(&key, 5).is_some_and(|pid| pid.starts_with("node-b")));
+}
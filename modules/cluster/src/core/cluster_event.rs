@@ -2,7 +2,7 @@
 
 use alloc::{string::String, vec::Vec};
 
-use crate::core::{ClusterTopology, startup_mode::StartupMode};
+use crate::core::{ClusterTopology, grain_lifecycle_signal::GrainLifecycleSignal, startup_mode::StartupMode};
 
 /// Event payload published via `EventStreamEvent::Extension { name: "cluster", .. }`.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -50,4 +50,12 @@ pub enum ClusterEvent {
     /// Blocked members from BlockListProvider.
     blocked:  Vec<String>,
   },
+  /// Lifecycle signal for a grain hosted by this node.
+  ///
+  /// Grain hosts answer [`GrainLifecycleSignal::Deactivating`] by reporting the final state
+  /// once the grain stopped.
+  GrainLifecycle {
+    /// Signal to deliver to the grain.
+    signal: GrainLifecycleSignal,
+  },
 }
@@ -18,8 +18,8 @@ use fraktor_utils_rs::core::{
 };
 
 use crate::core::{
-  ActivatedKind, ClusterCore, ClusterError, ClusterEvent, ClusterMetricsSnapshot, ClusterTopology, IdentitySetupError,
-  MetricsError,
+  ActivatedKind, ClusterCore, ClusterError, ClusterEvent, ClusterMetricsSnapshot, ClusterTopology, GrainKey,
+  HandoffCompletion, IdentitySetupError, MetricsError, SerializedMessage,
 };
 
 /// Internal subscriber that applies topology updates to ClusterCore.
@@ -147,6 +147,7 @@ impl<TB: RuntimeToolbox + 'static> ClusterExtensionGeneric<TB> {
       let extension_event = EventStreamEvent::Extension { name: String::from("cluster"), payload };
       self.event_stream.publish(&extension_event);
     }
+    self.publish_grain_lifecycle_signals();
   }
 
   /// Returns metrics snapshot if enabled.
@@ -158,6 +159,52 @@ impl<TB: RuntimeToolbox + 'static> ClusterExtensionGeneric<TB> {
     self.core.lock().metrics()
   }
 
+  /// Resolves the PID hosting `key`, or `None` while the grain is being handed off.
+  #[must_use]
+  pub fn resolve_grain(&self, key: &GrainKey, now: u64) -> Option<String> {
+    let pid = self.core.lock().resolve_grain(key, now);
+    self.publish_grain_lifecycle_signals();
+    pid
+  }
+
+  /// Routes a request addressed to `key`, buffering it while the grain is handed off.
+  #[must_use]
+  pub fn route_grain_request(&self, key: &GrainKey, message: SerializedMessage) -> Option<SerializedMessage> {
+    self.core.lock().route_grain_request(key, message)
+  }
+
+  /// Completes the handoff of `key` once the previous owner drained the grain.
+  pub fn complete_grain_handoff(&self, key: &GrainKey, state: Option<Vec<u8>>, now: u64) -> Option<HandoffCompletion> {
+    let completion = self.core.lock().complete_grain_handoff(key, state, now);
+    self.publish_grain_lifecycle_signals();
+    completion
+  }
+
+  /// Reports that a grain stopped after a deactivation signal, saving its final state.
+  pub fn complete_grain_deactivation(&self, key: &GrainKey, state: Option<Vec<u8>>) {
+    self.core.lock().complete_grain_deactivation(key, state);
+  }
+
+  /// Force-completes handoffs whose previous owner left or timed out.
+  ///
+  /// Also flushes lifecycle signals queued by topology updates received through the event
+  /// stream, so the runtime should call this periodically.
+  pub fn expire_grain_handoffs(&self, now: u64) -> Vec<HandoffCompletion> {
+    let completions = self.core.lock().expire_grain_handoffs(now);
+    self.publish_grain_lifecycle_signals();
+    completions
+  }
+
+  // ロック解放後に publish し、購読者からの再入によるデッドロックを避ける
+  fn publish_grain_lifecycle_signals(&self) {
+    let signals = self.core.lock().drain_grain_lifecycle_signals();
+    for signal in signals {
+      let payload = AnyMessageGeneric::new(ClusterEvent::GrainLifecycle { signal });
+      let extension_event = EventStreamEvent::Extension { name: String::from("cluster"), payload };
+      self.event_stream.publish(&extension_event);
+    }
+  }
+
   /// Returns virtual actor count.
   pub fn virtual_actor_count(&self) -> i64 {
     self.core.lock().virtual_actor_count()
@@ -15,7 +15,8 @@ use fraktor_utils_rs::core::{
 
 use crate::core::{
   ActivatedKind, ClusterEvent, ClusterExtensionConfig, ClusterExtensionId, ClusterProvider, ClusterProviderError,
-  ClusterPubSub, ClusterTopology, Gossiper, IdentityLookup, IdentitySetupError, StaticClusterProvider,
+  ClusterPubSub, ClusterTopology, DeactivationReason, Gossiper, GrainKey, GrainLifecycleSignal, IdentityLookup,
+  IdentitySetupError, PartitionIdentityLookup, RendezvousHasher, StaticClusterProvider,
 };
 
 struct StubProvider;
@@ -630,3 +631,45 @@ fn phase2_integration_shutdown_resets_metrics_and_emits_event() {
   // 9. blocked_members がクリアされていることを確認
   assert!(ext_shared.blocked_members().is_empty(), "blocked_members should be cleared after shutdown");
 }
+
+#[test]
+fn grain_lifecycle_signals_are_published_to_event_stream() {
+  let system = ActorSystemGeneric::<NoStdToolbox>::new_empty();
+  let (recorder, _subscription) = subscribe_recorder(&system.event_stream());
+  let ext_id = ClusterExtensionId::<NoStdToolbox>::new(
+    ClusterExtensionConfig::new().with_advertised_address("node-a"),
+    Box::new(StubProvider),
+    ArcShared::new(StubBlockList),
+    Box::new(StubGossiper),
+    Box::new(StubPubSub),
+    Box::new(PartitionIdentityLookup::with_defaults()),
+  );
+  let ext_shared = system.extended().register_extension(&ext_id);
+  ext_shared.start_member().unwrap();
+
+  // node-b の参加で node-a から node-b へ移動するキーを探す
+  let both = vec![String::from("node-a"), String::from("node-b")];
+  let key = (0..)
+    .map(|index| GrainKey::new(alloc::format!("user:{index}")))
+    .find(|key| RendezvousHasher::select(&both, key).is_some_and(|owner| owner == "node-b"))
+    .expect("moving key");
+
+  ext_shared.on_topology(&ClusterTopology::new(1, vec![String::from("node-a")], vec![]));
+  let pid = ext_shared.resolve_grain(&key, 1).expect("activation");
+  ext_shared.on_topology(&ClusterTopology::new(2, vec![String::from("node-b")], vec![]));
+  let completion = ext_shared.complete_grain_handoff(&key, None, 2).expect("handoff completion");
+
+  let signals: Vec<GrainLifecycleSignal> = recorder
+    .events()
+    .into_iter()
+    .filter_map(|event| match event {
+      | ClusterEvent::GrainLifecycle { signal } => Some(signal),
+      | _ => None,
+    })
+    .collect();
+  assert_eq!(signals, vec![
+    GrainLifecycleSignal::Activated { key: key.clone(), pid: pid.clone(), state: None },
+    GrainLifecycleSignal::Deactivating { key: key.clone(), pid, reason: DeactivationReason::OwnerChanged },
+    GrainLifecycleSignal::Activated { key, pid: completion.pid, state: None },
+  ]);
+}
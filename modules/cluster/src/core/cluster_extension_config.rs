@@ -3,7 +3,7 @@
 #[cfg(test)]
 mod tests;
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 
 use crate::core::cluster_topology::ClusterTopology;
 
@@ -13,13 +13,19 @@ pub struct ClusterExtensionConfig {
   advertised_address: String,
   metrics_enabled:    bool,
   static_topology:    Option<ClusterTopology>,
+  roles:              Vec<String>,
 }
 
 impl ClusterExtensionConfig {
   /// Creates a configuration with an empty advertised address and metrics disabled.
   #[must_use]
   pub const fn new() -> Self {
-    Self { advertised_address: String::new(), metrics_enabled: false, static_topology: None }
+    Self {
+      advertised_address: String::new(),
+      metrics_enabled:    false,
+      static_topology:    None,
+      roles:              Vec::new(),
+    }
   }
 
   /// Overrides the advertised address used in cluster events.
@@ -58,6 +64,20 @@ impl ClusterExtensionConfig {
     self
   }
 
+  /// Sets the roles advertised by this node.
+  #[must_use]
+  pub fn with_roles(mut self, roles: Vec<String>) -> Self {
+    self.roles = roles;
+    self
+  }
+
+  /// Returns the roles advertised by this node.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn roles(&self) -> &[String] {
+    &self.roles
+  }
+
   /// Returns the configured static topology.
   #[must_use]
   #[allow(clippy::missing_const_for_fn)]
@@ -142,8 +142,10 @@ impl<TB: RuntimeToolbox + 'static> ClusterExtensionInstaller<TB> {
   #[must_use]
   pub fn new_with_local(config: ClusterExtensionConfig) -> Self {
     let static_topology = config.static_topology().cloned();
+    let roles = config.roles().to_vec();
     Self::new(config, move |event_stream, block_list_provider, advertised_address| {
-      let mut provider = LocalClusterProvider::new(event_stream, block_list_provider, advertised_address);
+      let mut provider =
+        LocalClusterProvider::new(event_stream, block_list_provider, advertised_address).with_roles(roles.clone());
       if let Some(ref topology) = static_topology {
         provider = provider.with_static_topology(topology.clone());
       }
@@ -189,6 +191,81 @@ impl<TB: RuntimeToolbox + 'static> ClusterExtensionInstaller<TB> {
     })
   }
 
+  /// Creates a new installer with `KubernetesClusterProvider`.
+  ///
+  /// Members are discovered by listing and watching the pods that match the configured
+  /// label selector through the Kubernetes API.
+  ///
+  /// Requires the `kubernetes` feature to be enabled.
+  ///
+  /// # Example
+  ///
+  /// ```text
+  /// use fraktor_cluster_rs::core::{ClusterExtensionConfig, ClusterExtensionInstaller};
+  /// use fraktor_cluster_rs::std::KubernetesClusterConfig;
+  ///
+  /// let k8s_config = KubernetesClusterConfig::new()
+  ///     .with_namespace("prod")
+  ///     .with_label_selector("app=my-service");
+  ///
+  /// let installer = ClusterExtensionInstaller::new_with_kubernetes(
+  ///     ClusterExtensionConfig::default().with_advertised_address("10.0.0.1:8080"),
+  ///     k8s_config,
+  /// );
+  /// ```
+  #[cfg(feature = "kubernetes")]
+  #[must_use]
+  pub fn new_with_kubernetes(
+    config: ClusterExtensionConfig,
+    kubernetes_config: crate::std::KubernetesClusterConfig,
+  ) -> ClusterExtensionInstaller<fraktor_utils_rs::std::runtime_toolbox::StdToolbox> {
+    let self_roles = config.roles().to_vec();
+    ClusterExtensionInstaller::new(config, move |event_stream, block_list_provider, advertised_address| {
+      Box::new(
+        crate::std::KubernetesClusterProvider::new(event_stream, block_list_provider, advertised_address)
+          .with_kubernetes_config(kubernetes_config.clone())
+          .with_self_roles(self_roles.clone()),
+      )
+    })
+  }
+
+  /// Creates a new installer with `DnsClusterProvider`.
+  ///
+  /// Members are discovered by resolving `A` or `SRV` records on an interval.
+  ///
+  /// Requires the `dns` feature to be enabled.
+  ///
+  /// # Example
+  ///
+  /// ```text
+  /// use fraktor_cluster_rs::core::{ClusterExtensionConfig, ClusterExtensionInstaller};
+  /// use fraktor_cluster_rs::std::{DnsClusterConfig, DnsRecordType};
+  ///
+  /// let dns_config = DnsClusterConfig::new()
+  ///     .with_query_name("_fraktor._tcp.my-service.prod.svc.cluster.local")
+  ///     .with_record_type(DnsRecordType::Srv);
+  ///
+  /// let installer = ClusterExtensionInstaller::new_with_dns(
+  ///     ClusterExtensionConfig::default().with_advertised_address("10.0.0.1:8080"),
+  ///     dns_config,
+  /// );
+  /// ```
+  #[cfg(feature = "dns")]
+  #[must_use]
+  pub fn new_with_dns(
+    config: ClusterExtensionConfig,
+    dns_config: crate::std::DnsClusterConfig,
+  ) -> ClusterExtensionInstaller<fraktor_utils_rs::std::runtime_toolbox::StdToolbox> {
+    let self_roles = config.roles().to_vec();
+    ClusterExtensionInstaller::new(config, move |event_stream, block_list_provider, advertised_address| {
+      Box::new(
+        crate::std::DnsClusterProvider::new(event_stream, block_list_provider, advertised_address)
+          .with_dns_config(dns_config.clone())
+          .with_self_roles(self_roles.clone()),
+      )
+    })
+  }
+
   /// Sets a custom block list provider.
   #[must_use]
   pub fn with_block_list_provider(mut self, provider: ArcShared<dyn BlockListProvider>) -> Self {
@@ -8,7 +8,7 @@
 //! for TopologyUpdated publishing is implemented via conditional compilation
 //! and only available in std environments.
 
-use alloc::{string::String, vec::Vec};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
 
 use fraktor_actor_rs::core::{
   event_stream::{EventStreamEvent, EventStreamGeneric},
@@ -50,6 +50,10 @@ pub struct LocalClusterProvider<TB: RuntimeToolbox + 'static> {
   seed_nodes:          Vec<String>,
   // 起動モード（Member/Client）を追跡
   startup_mode:        Option<StartupMode>,
+  // 自ノードが広告するロール
+  roles:               Vec<String>,
+  // メンバーごとのロール（authority -> roles）
+  member_roles:        BTreeMap<String, Vec<String>>,
 }
 
 impl<TB: RuntimeToolbox + 'static> LocalClusterProvider<TB> {
@@ -69,6 +73,8 @@ impl<TB: RuntimeToolbox + 'static> LocalClusterProvider<TB> {
       static_topology: None,
       seed_nodes: Vec::new(),
       startup_mode: None,
+      roles: Vec::new(),
+      member_roles: BTreeMap::new(),
     }
   }
 
@@ -91,6 +97,20 @@ impl<TB: RuntimeToolbox + 'static> LocalClusterProvider<TB> {
     self
   }
 
+  /// Sets the roles advertised by this node.
+  #[must_use]
+  pub fn with_roles(mut self, roles: Vec<String>) -> Self {
+    self.roles = roles;
+    self
+  }
+
+  /// Returns the roles advertised by this node.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn roles(&self) -> &[String] {
+    &self.roles
+  }
+
   /// Returns the advertised address.
   #[must_use]
   #[allow(clippy::missing_const_for_fn)]
@@ -110,7 +130,15 @@ impl<TB: RuntimeToolbox + 'static> LocalClusterProvider<TB> {
   /// This will publish a `ClusterEvent::TopologyUpdated` with the joined node
   /// in the `joined` list.
   pub fn on_member_join(&mut self, authority: impl Into<String>) {
+    self.on_member_join_with_roles(authority, Vec::new());
+  }
+
+  /// Notifies the provider that a node advertising `roles` has joined the cluster.
+  ///
+  /// The roles are attached to the published `ClusterTopology`.
+  pub fn on_member_join_with_roles(&mut self, authority: impl Into<String>, roles: Vec<String>) {
     let authority = authority.into();
+    self.member_roles.insert(authority.clone(), roles);
     if !self.members.contains(&authority) {
       self.members.push(authority.clone());
     }
@@ -126,6 +154,7 @@ impl<TB: RuntimeToolbox + 'static> LocalClusterProvider<TB> {
   pub fn on_member_leave(&mut self, authority: impl Into<String>) {
     let authority = authority.into();
     self.members.retain(|m| m != &authority);
+    self.member_roles.remove(&authority);
 
     let version = self.next_version();
     self.publish_topology(version, alloc::vec![], alloc::vec![authority]);
@@ -159,7 +188,11 @@ impl<TB: RuntimeToolbox + 'static> LocalClusterProvider<TB> {
 
   fn publish_topology(&self, version: u64, joined: Vec<String>, left: Vec<String>) {
     let blocked = self.block_list_provider.blocked_members();
-    let topology = ClusterTopology::new(version, joined.clone(), left.clone());
+    let roles = joined
+      .iter()
+      .filter_map(|authority| self.member_roles.get(authority).map(|roles| (authority.clone(), roles.clone())))
+      .collect();
+    let topology = ClusterTopology::new(version, joined.clone(), left.clone()).with_member_roles(roles);
     let event = ClusterEvent::TopologyUpdated { topology, joined, left, blocked };
     let payload = AnyMessageGeneric::new(event);
     let extension_event = EventStreamEvent::Extension { name: String::from("cluster"), payload };
@@ -237,6 +270,7 @@ impl<TB: RuntimeToolbox + 'static> ClusterProvider for LocalClusterProvider<TB>
     if !self.members.contains(&self.advertised_address) {
       self.members.push(self.advertised_address.clone());
     }
+    self.member_roles.insert(self.advertised_address.clone(), self.roles.clone());
 
     // 静的トポロジが設定されている場合は publish
     self.publish_static_topology();
@@ -266,6 +300,7 @@ impl<TB: RuntimeToolbox + 'static> ClusterProvider for LocalClusterProvider<TB>
 
     // メンバーリストをクリア
     self.members.clear();
+    self.member_roles.clear();
 
     // Shutdown イベントを EventStream に発火
     self.publish_shutdown_event(mode);
@@ -540,3 +540,21 @@ fn is_started_returns_correct_state() {
   // shutdown後は false
   assert!(!provider.is_started());
 }
+
+#[test]
+fn on_member_join_with_roles_attaches_roles_to_topology() {
+  let event_stream = ArcShared::new(EventStreamGeneric::<NoStdToolbox>::default());
+  let block_list: ArcShared<dyn BlockListProvider> = ArcShared::new(EmptyBlockList);
+
+  let (subscriber_impl, _subscription) = subscribe_recorder(&event_stream);
+
+  let mut provider = LocalClusterProvider::<NoStdToolbox>::new(event_stream, block_list, "node-a:8080");
+  provider.on_member_join_with_roles("node-b:8080", vec![String::from("payments")]);
+
+  let events = subscriber_impl.events();
+  assert!(matches!(
+    &events[0],
+    ClusterEvent::TopologyUpdated { topology, .. }
+    if topology.member_roles().get("node-b:8080") == Some(&vec![String::from("payments")])
+  ));
+}
@@ -78,6 +78,17 @@ impl<TB: RuntimeToolbox + 'static> ClusterPubSubImpl<TB> {
     result
   }
 
+  /// Publishes to a topic, returning only subscribers hosted on `authorities`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the topic does not exist or publish fails.
+  pub fn publish_to_authorities(&mut self, topic: &str, authorities: &[String]) -> Result<Vec<String>, PubSubError> {
+    let result = self.broker.publish_to_authorities(topic, authorities);
+    self.flush_broker_events_to_stream();
+    result
+  }
+
   /// Drains broker events (for testing).
   #[must_use]
   pub fn drain_events(&mut self) -> Vec<PubSubEvent> {
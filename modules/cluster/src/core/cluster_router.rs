@@ -0,0 +1,84 @@
+//! Cluster-aware router group selecting routees on the members of the cluster.
+
+use alloc::{string::String, vec::Vec};
+
+use fraktor_actor_rs::core::actor_prim::actor_path::ActorPath;
+
+use crate::core::{
+  cluster_router_settings::ClusterRouterSettings, grain_key::GrainKey, member_path::MemberPath,
+  membership_table::MembershipTable, rendezvous_hasher::RendezvousHasher,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Routes to the routee running at the same path on every eligible member.
+///
+/// Eligible members are the active ones advertising every role of the settings, as reported by
+/// [`MembershipTable::authorities_with_roles`]; the local member is skipped unless the settings
+/// allow local routees. Messages are spread round-robin or, for keyed messages, placed with
+/// [`RendezvousHasher`] so the same key keeps reaching the same member.
+pub struct ClusterRouter {
+  settings:        ClusterRouterSettings,
+  system_name:     String,
+  local_authority: String,
+  routees:         Vec<String>,
+  next:            usize,
+}
+
+impl ClusterRouter {
+  /// Creates a router for actor systems named `system_name`, running on `local_authority`.
+  #[must_use]
+  pub fn new(
+    settings: ClusterRouterSettings,
+    system_name: impl Into<String>,
+    local_authority: impl Into<String>,
+  ) -> Self {
+    Self {
+      settings,
+      system_name: system_name.into(),
+      local_authority: local_authority.into(),
+      routees: Vec::new(),
+      next: 0,
+    }
+  }
+
+  /// Recomputes the routee members from the membership view.
+  pub fn on_membership(&mut self, table: &MembershipTable) {
+    let mut routees = table.authorities_with_roles(self.settings.roles());
+    if !self.settings.allow_local_routees() {
+      routees.retain(|authority| *authority != self.local_authority);
+    }
+    routees.sort();
+    self.routees = routees;
+  }
+
+  /// Returns the authorities of the members currently hosting routees.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // Vec の Deref が const でないため const fn にできない
+  pub fn routees(&self) -> &[String] {
+    &self.routees
+  }
+
+  /// Picks the next routee in round-robin order.
+  pub fn route(&mut self) -> Option<ActorPath> {
+    if self.routees.is_empty() {
+      return None;
+    }
+    let authority = &self.routees[self.next % self.routees.len()];
+    self.next = self.next.wrapping_add(1);
+    Some(self.routee_path(authority))
+  }
+
+  /// Picks the routee owning `key`.
+  #[must_use]
+  pub fn route_by_key(&self, key: &GrainKey) -> Option<ActorPath> {
+    RendezvousHasher::select(&self.routees, key).map(|authority| self.routee_path(authority))
+  }
+
+  /// Builds the remote path of the routee running at `authority` (`host:port`).
+  #[must_use]
+  pub fn routee_path(&self, authority: &str) -> ActorPath {
+    MemberPath::of(&self.system_name, authority, self.settings.routee_segments())
+  }
+}
@@ -0,0 +1,58 @@
+use alloc::{string::ToString, vec, vec::Vec};
+
+use fraktor_actor_rs::core::actor_prim::actor_path::ActorPath;
+
+use super::ClusterRouter;
+use crate::core::{ClusterRouterSettings, GrainKey, MembershipTable};
+
+fn table() -> MembershipTable {
+  let mut table = MembershipTable::new(3);
+  table.try_join_with_roles("node-1".to_string(), "n1:4050".to_string(), vec!["backend".to_string()]).expect("join");
+  table.try_join_with_roles("node-2".to_string(), "n2:4051".to_string(), vec!["backend".to_string()]).expect("join");
+  table.try_join_with_roles("node-3".to_string(), "n3:4052".to_string(), vec!["frontend".to_string()]).expect("join");
+  table
+}
+
+fn settings() -> ClusterRouterSettings {
+  ClusterRouterSettings::new(&ActorPath::root().child("workers"))
+}
+
+#[test]
+fn routees_are_filtered_by_roles() {
+  let mut router = ClusterRouter::new(settings().with_role("backend"), "cluster-sys", "n3:4052");
+  router.on_membership(&table());
+
+  assert_eq!(router.routees(), ["n1:4050".to_string(), "n2:4051".to_string()]);
+  let routed: Vec<_> = (0..3).filter_map(|_| router.route()).map(|path| path.to_canonical_uri()).collect();
+  assert_eq!(routed, vec![
+    "fraktor.tcp://cluster-sys@n1:4050/user/workers".to_string(),
+    "fraktor.tcp://cluster-sys@n2:4051/user/workers".to_string(),
+    "fraktor.tcp://cluster-sys@n1:4050/user/workers".to_string(),
+  ]);
+}
+
+#[test]
+fn local_routee_can_be_excluded() {
+  let mut router = ClusterRouter::new(settings().with_allow_local_routees(false), "cluster-sys", "n1:4050");
+  router.on_membership(&table());
+  assert_eq!(router.routees(), ["n2:4051".to_string(), "n3:4052".to_string()]);
+}
+
+#[test]
+fn keyed_routing_is_stable_and_follows_membership() {
+  let mut router = ClusterRouter::new(settings().with_role("backend"), "cluster-sys", "n1:4050");
+  assert!(router.route().is_none());
+  assert!(router.route_by_key(&GrainKey::new("user:va-1".to_string())).is_none());
+
+  let mut table = table();
+  router.on_membership(&table);
+  let key = GrainKey::new("user:va-1".to_string());
+  let owner = router.route_by_key(&key).expect("owner");
+  assert_eq!(router.route_by_key(&key), Some(owner.clone()));
+
+  let authority = owner.parts().authority_endpoint().expect("authority");
+  table.mark_left(&authority).expect("left");
+  router.on_membership(&table);
+  assert_eq!(router.routees().len(), 1);
+  assert_ne!(router.route_by_key(&key), Some(owner));
+}
@@ -0,0 +1,64 @@
+//! Settings of a cluster-aware router group.
+
+use alloc::{string::String, vec::Vec};
+
+use fraktor_actor_rs::core::actor_prim::actor_path::ActorPath;
+
+use crate::core::member_path::MemberPath;
+
+#[cfg(test)]
+mod tests;
+
+/// Identifies the routees of a [`ClusterRouter`](crate::core::ClusterRouter) and constrains the
+/// members they are taken from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterRouterSettings {
+  routee_segments:     Vec<String>,
+  roles:               Vec<String>,
+  allow_local_routees: bool,
+}
+
+impl ClusterRouterSettings {
+  /// Creates settings routing to the actor found at `routee_path` on every member.
+  ///
+  /// Only the segments of `routee_path` are used, so the path of a local routee works.
+  #[must_use]
+  pub fn new(routee_path: &ActorPath) -> Self {
+    let routee_segments = MemberPath::relative_segments(routee_path);
+    Self { routee_segments, roles: Vec::new(), allow_local_routees: true }
+  }
+
+  /// Restricts the routees to members advertising `role`; repeated calls require every role.
+  #[must_use]
+  pub fn with_role(mut self, role: impl Into<String>) -> Self {
+    self.roles.push(role.into());
+    self
+  }
+
+  /// Sets whether the routee of the local member may be used.
+  #[must_use]
+  pub const fn with_allow_local_routees(mut self, allow_local_routees: bool) -> Self {
+    self.allow_local_routees = allow_local_routees;
+    self
+  }
+
+  /// Returns the path segments of the routee below the user guardian.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // Vec の Deref が const でないため const fn にできない
+  pub fn routee_segments(&self) -> &[String] {
+    &self.routee_segments
+  }
+
+  /// Returns the roles every routee member must advertise.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // Vec の Deref が const でないため const fn にできない
+  pub fn roles(&self) -> &[String] {
+    &self.roles
+  }
+
+  /// Returns whether the routee of the local member may be used.
+  #[must_use]
+  pub const fn allow_local_routees(&self) -> bool {
+    self.allow_local_routees
+  }
+}
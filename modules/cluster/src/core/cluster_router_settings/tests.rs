@@ -0,0 +1,23 @@
+use alloc::string::ToString;
+
+use fraktor_actor_rs::core::actor_prim::actor_path::ActorPath;
+
+use super::ClusterRouterSettings;
+
+#[test]
+fn defaults_allow_local_routees_without_roles() {
+  let settings = ClusterRouterSettings::new(&ActorPath::root().child("workers"));
+  assert_eq!(settings.routee_segments(), ["workers".to_string()]);
+  assert!(settings.roles().is_empty());
+  assert!(settings.allow_local_routees());
+}
+
+#[test]
+fn builders_accumulate_roles_and_exclude_local_routees() {
+  let settings = ClusterRouterSettings::new(&ActorPath::root().child("workers"))
+    .with_role("backend")
+    .with_role("eu")
+    .with_allow_local_routees(false);
+  assert_eq!(settings.roles(), ["backend".to_string(), "eu".to_string()]);
+  assert!(!settings.allow_local_routees());
+}
@@ -0,0 +1,19 @@
+//! Side effects requested by the cluster singleton manager.
+
+use alloc::string::String;
+
+/// Instruction the hosting runtime must carry out for the local node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClusterSingletonAction {
+  /// Spawn the singleton instance on this node.
+  StartLocal,
+  /// Stop the singleton instance running on this node.
+  ///
+  /// The runtime must call `on_local_stopped` once the instance has terminated.
+  StopLocal,
+  /// Tell the new oldest member that the previous instance has stopped.
+  HandOver {
+    /// Authority of the member taking over.
+    to: String,
+  },
+}
@@ -0,0 +1,12 @@
+//! Message routed by the cluster singleton proxy.
+
+use fraktor_actor_rs::core::actor_prim::actor_path::ActorPath;
+
+/// Message addressed to the current singleton location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterSingletonDelivery<M> {
+  /// Remote path of the singleton instance.
+  pub path:    ActorPath,
+  /// Message to deliver.
+  pub message: M,
+}
@@ -0,0 +1,61 @@
+//! Events published by cluster singleton managers and proxies.
+
+use alloc::string::String;
+
+/// Event payload published via `EventStreamEvent::Extension { name: "cluster", .. }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClusterSingletonEvent {
+  /// Singleton instance started on the given authority.
+  Started {
+    /// Singleton name.
+    name:      String,
+    /// Hosting authority.
+    authority: String,
+  },
+  /// Singleton instance stopped on the given authority.
+  Stopped {
+    /// Singleton name.
+    name:      String,
+    /// Authority that hosted the instance.
+    authority: String,
+  },
+  /// The oldest member changed and the running instance is being stopped.
+  HandoverStarted {
+    /// Singleton name.
+    name: String,
+    /// Authority currently hosting the instance.
+    from: String,
+    /// Authority that will host the next instance, if known.
+    to:   Option<String>,
+  },
+  /// The new owner received the handover and started the instance.
+  HandoverCompleted {
+    /// Singleton name.
+    name: String,
+    /// Authority that hosted the previous instance.
+    from: String,
+    /// Authority hosting the new instance.
+    to:   String,
+  },
+  /// Proxy buffered a message because the location is unknown.
+  MessageBuffered {
+    /// Singleton name.
+    name:     String,
+    /// Number of buffered messages after the insertion.
+    buffered: usize,
+  },
+  /// Proxy dropped the oldest buffered message because the buffer was full.
+  MessageDropped {
+    /// Singleton name.
+    name: String,
+  },
+  /// Proxy flushed buffered messages to the new location.
+  BufferFlushed {
+    /// Singleton name.
+    name:      String,
+    /// Authority receiving the messages.
+    authority: String,
+    /// Number of flushed messages.
+    count:     usize,
+  },
+}
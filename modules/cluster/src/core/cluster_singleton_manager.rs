@@ -0,0 +1,164 @@
+//! Cluster singleton manager keeping exactly one instance on the oldest member.
+
+use alloc::{
+  string::{String, ToString},
+  vec,
+  vec::Vec,
+};
+
+use fraktor_actor_rs::core::{
+  event_stream::{EventStreamEvent, EventStreamGeneric},
+  messaging::AnyMessageGeneric,
+};
+use fraktor_utils_rs::core::{runtime_toolbox::RuntimeToolbox, sync::ArcShared};
+
+use crate::core::{
+  cluster_singleton_action::ClusterSingletonAction, cluster_singleton_event::ClusterSingletonEvent,
+  cluster_singleton_settings::ClusterSingletonSettings, membership_table::MembershipTable, node_status::NodeStatus,
+};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ManagerState {
+  /// Not hosting the singleton.
+  Idle,
+  /// Hosting the singleton on this node.
+  Active,
+  /// Stopping the local instance before handing over.
+  Stopping { to: Option<String> },
+  /// Oldest member, waiting for the previous owner to stop its instance.
+  AwaitingHandover { from: String },
+}
+
+/// Decides whether the local node hosts the singleton.
+///
+/// Every node runs one manager per singleton. The singleton lives on the oldest `Up` member
+/// (optionally restricted to a role) as ordered by [`MembershipTable::up_members_by_age`].
+/// When ownership moves, the previous owner stops its instance first and then hands over, so
+/// that two instances never run concurrently.
+pub struct ClusterSingletonManager<TB: RuntimeToolbox + 'static> {
+  settings:       ClusterSingletonSettings,
+  self_authority: String,
+  event_stream:   ArcShared<EventStreamGeneric<TB>>,
+  oldest:         Option<String>,
+  state:          ManagerState,
+}
+
+impl<TB: RuntimeToolbox + 'static> ClusterSingletonManager<TB> {
+  /// Creates a manager for the local node.
+  #[must_use]
+  pub fn new(
+    settings: ClusterSingletonSettings,
+    self_authority: impl Into<String>,
+    event_stream: ArcShared<EventStreamGeneric<TB>>,
+  ) -> Self {
+    Self { settings, self_authority: self_authority.into(), event_stream, oldest: None, state: ManagerState::Idle }
+  }
+
+  /// Returns the settings.
+  #[must_use]
+  pub const fn settings(&self) -> &ClusterSingletonSettings {
+    &self.settings
+  }
+
+  /// Returns the authority currently elected to host the singleton.
+  #[must_use]
+  pub fn oldest(&self) -> Option<&str> {
+    self.oldest.as_deref()
+  }
+
+  /// Returns true while the singleton instance runs on this node.
+  #[must_use]
+  pub const fn is_running_locally(&self) -> bool {
+    matches!(self.state, ManagerState::Active | ManagerState::Stopping { .. })
+  }
+
+  /// Re-evaluates ownership against the latest membership view.
+  pub fn on_membership(&mut self, table: &MembershipTable) -> Vec<ClusterSingletonAction> {
+    let previous = self.oldest.take();
+    self.oldest = table.oldest_up(self.settings.role()).map(|record| record.authority.clone());
+    let is_oldest = self.oldest.as_deref() == Some(self.self_authority.as_str());
+
+    match self.state.clone() {
+      | ManagerState::Active if !is_oldest => {
+        self.state = ManagerState::Stopping { to: self.oldest.clone() };
+        self.publish(ClusterSingletonEvent::HandoverStarted {
+          name: self.name(),
+          from: self.self_authority.clone(),
+          to:   self.oldest.clone(),
+        });
+        vec![ClusterSingletonAction::StopLocal]
+      },
+      | ManagerState::Stopping { .. } => {
+        self.state = ManagerState::Stopping { to: self.oldest.clone() };
+        Vec::new()
+      },
+      | ManagerState::Idle if is_oldest => match previous {
+        | Some(from) if from != self.self_authority && Self::still_hosting(table, &from) => {
+          self.state = ManagerState::AwaitingHandover { from };
+          Vec::new()
+        },
+        | _ => self.start_local(),
+      },
+      | ManagerState::AwaitingHandover { .. } if !is_oldest => {
+        self.state = ManagerState::Idle;
+        Vec::new()
+      },
+      | ManagerState::AwaitingHandover { from } if !Self::still_hosting(table, &from) => self.start_local(),
+      | _ => Vec::new(),
+    }
+  }
+
+  /// Notifies the manager that the local instance has terminated.
+  pub fn on_local_stopped(&mut self) -> Vec<ClusterSingletonAction> {
+    let ManagerState::Stopping { to } = core::mem::replace(&mut self.state, ManagerState::Idle) else {
+      return Vec::new();
+    };
+    self.publish(ClusterSingletonEvent::Stopped { name: self.name(), authority: self.self_authority.clone() });
+    match to {
+      | Some(to) if to != self.self_authority => vec![ClusterSingletonAction::HandOver { to }],
+      | Some(_) => self.start_local(),
+      | None => Vec::new(),
+    }
+  }
+
+  /// Handles a handover notification sent by the previous owner.
+  pub fn on_handover_done(&mut self, from: &str) -> Vec<ClusterSingletonAction> {
+    match &self.state {
+      | ManagerState::AwaitingHandover { from: expected } if expected == from => {
+        self.publish(ClusterSingletonEvent::HandoverCompleted {
+          name: self.name(),
+          from: from.to_string(),
+          to:   self.self_authority.clone(),
+        });
+        self.start_local()
+      },
+      | _ => Vec::new(),
+    }
+  }
+
+  fn start_local(&mut self) -> Vec<ClusterSingletonAction> {
+    self.state = ManagerState::Active;
+    self.publish(ClusterSingletonEvent::Started { name: self.name(), authority: self.self_authority.clone() });
+    vec![ClusterSingletonAction::StartLocal]
+  }
+
+  // 離脱中 (`Leaving`) のメンバーも停止完了までは旧インスタンスを保持しているとみなす
+  fn still_hosting(table: &MembershipTable, authority: &str) -> bool {
+    table
+      .record(authority)
+      .is_some_and(|record| matches!(record.status, NodeStatus::Joining | NodeStatus::Up | NodeStatus::Leaving))
+  }
+
+  fn name(&self) -> String {
+    self.settings.singleton_name().to_string()
+  }
+
+  fn publish(&self, event: ClusterSingletonEvent) {
+    let payload = AnyMessageGeneric::new(event);
+    let extension_event = EventStreamEvent::Extension { name: String::from("cluster"), payload };
+    self.event_stream.publish(&extension_event);
+  }
+}
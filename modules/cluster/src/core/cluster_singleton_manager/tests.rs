@@ -0,0 +1,143 @@
+use alloc::{string::ToString, vec, vec::Vec};
+
+use fraktor_actor_rs::core::event_stream::{
+  EventStreamEvent, EventStreamGeneric, EventStreamSubscriber, EventStreamSubscriptionGeneric, subscriber_handle,
+};
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdMutex, NoStdToolbox},
+  sync::ArcShared,
+};
+
+use super::ClusterSingletonManager;
+use crate::core::{ClusterSingletonAction, ClusterSingletonEvent, ClusterSingletonSettings, MembershipTable};
+
+#[derive(Clone)]
+struct RecordingSingletonEvents {
+  events: ArcShared<NoStdMutex<Vec<ClusterSingletonEvent>>>,
+}
+
+impl EventStreamSubscriber<NoStdToolbox> for RecordingSingletonEvents {
+  fn on_event(&mut self, event: &EventStreamEvent<NoStdToolbox>) {
+    if let EventStreamEvent::Extension { name, payload } = event
+      && name == "cluster"
+      && let Some(singleton_event) = payload.payload().downcast_ref::<ClusterSingletonEvent>()
+    {
+      self.events.lock().push(singleton_event.clone());
+    }
+  }
+}
+
+fn event_stream_with_recorder()
+-> (ArcShared<EventStreamGeneric<NoStdToolbox>>, RecordingSingletonEvents, EventStreamSubscriptionGeneric<NoStdToolbox>)
+{
+  let event_stream = ArcShared::new(EventStreamGeneric::<NoStdToolbox>::default());
+  let recorder = RecordingSingletonEvents { events: ArcShared::new(NoStdMutex::new(Vec::new())) };
+  let subscriber = subscriber_handle(recorder.clone());
+  let subscription = EventStreamGeneric::subscribe_arc(&event_stream, &subscriber);
+  (event_stream, recorder, subscription)
+}
+
+fn table_with(members: &[&str]) -> MembershipTable {
+  let mut table = MembershipTable::new(3);
+  for (index, authority) in members.iter().enumerate() {
+    table.try_join(alloc::format!("node-{index}"), authority.to_string()).expect("join");
+  }
+  table
+}
+
+#[test]
+fn oldest_member_starts_singleton() {
+  let (event_stream, recorder, _subscription) = event_stream_with_recorder();
+  let mut manager = ClusterSingletonManager::new(ClusterSingletonSettings::new("scheduler"), "a:1", event_stream);
+
+  let actions = manager.on_membership(&table_with(&["a:1", "b:2"]));
+
+  assert_eq!(actions, vec![ClusterSingletonAction::StartLocal]);
+  assert!(manager.is_running_locally());
+  assert_eq!(manager.oldest(), Some("a:1"));
+  assert_eq!(recorder.events.lock().clone(), vec![ClusterSingletonEvent::Started {
+    name:      "scheduler".to_string(),
+    authority: "a:1".to_string(),
+  }]);
+}
+
+#[test]
+fn younger_member_does_not_start_singleton() {
+  let (event_stream, _recorder, _subscription) = event_stream_with_recorder();
+  let mut manager = ClusterSingletonManager::new(ClusterSingletonSettings::new("scheduler"), "b:2", event_stream);
+
+  let actions = manager.on_membership(&table_with(&["a:1", "b:2"]));
+
+  assert!(actions.is_empty());
+  assert!(!manager.is_running_locally());
+  assert_eq!(manager.oldest(), Some("a:1"));
+}
+
+#[test]
+fn handover_stops_old_instance_before_new_one_starts() {
+  let (event_stream, recorder, _subscription) = event_stream_with_recorder();
+  let settings = ClusterSingletonSettings::new("scheduler");
+  let mut old_owner = ClusterSingletonManager::new(settings.clone(), "a:1", event_stream.clone());
+  let mut new_owner = ClusterSingletonManager::new(settings, "b:2", event_stream);
+
+  let mut table = table_with(&["a:1", "b:2"]);
+  assert_eq!(old_owner.on_membership(&table), vec![ClusterSingletonAction::StartLocal]);
+  assert!(new_owner.on_membership(&table).is_empty());
+
+  // a:1 が Leaving へ遷移したビューを適用する
+  let mut leaving = table.record("a:1").expect("record").clone();
+  leaving.status = crate::core::NodeStatus::Leaving;
+  let next = table.version().next();
+  table.apply_delta(crate::core::MembershipDelta::new(table.version(), next, vec![leaving]));
+
+  assert_eq!(old_owner.on_membership(&table), vec![ClusterSingletonAction::StopLocal]);
+  // 旧インスタンスの停止完了前は新オーナーで起動しない
+  assert!(new_owner.on_membership(&table).is_empty());
+  assert!(!new_owner.is_running_locally());
+
+  assert_eq!(old_owner.on_local_stopped(), vec![ClusterSingletonAction::HandOver { to: "b:2".to_string() }]);
+  assert_eq!(new_owner.on_handover_done("a:1"), vec![ClusterSingletonAction::StartLocal]);
+  assert!(new_owner.is_running_locally());
+  assert!(!old_owner.is_running_locally());
+
+  let events = recorder.events.lock().clone();
+  assert!(events.contains(&ClusterSingletonEvent::HandoverStarted {
+    name: "scheduler".to_string(),
+    from: "a:1".to_string(),
+    to:   Some("b:2".to_string()),
+  }));
+  assert!(events.contains(&ClusterSingletonEvent::HandoverCompleted {
+    name: "scheduler".to_string(),
+    from: "a:1".to_string(),
+    to:   "b:2".to_string(),
+  }));
+}
+
+#[test]
+fn starts_immediately_when_previous_owner_disappeared() {
+  let (event_stream, _recorder, _subscription) = event_stream_with_recorder();
+  let mut manager = ClusterSingletonManager::new(ClusterSingletonSettings::new("scheduler"), "b:2", event_stream);
+
+  let mut table = table_with(&["a:1", "b:2"]);
+  assert!(manager.on_membership(&table).is_empty());
+
+  table.mark_left("a:1").expect("left");
+
+  assert_eq!(manager.on_membership(&table), vec![ClusterSingletonAction::StartLocal]);
+}
+
+#[test]
+fn role_restricts_candidates() {
+  let (event_stream, _recorder, _subscription) = event_stream_with_recorder();
+  let settings = ClusterSingletonSettings::new("scheduler").with_role("backend");
+  let mut manager = ClusterSingletonManager::new(settings, "b:2", event_stream);
+
+  let mut table = MembershipTable::new(3);
+  table.try_join("node-a".to_string(), "a:1".to_string()).expect("join");
+  let delta = table.try_join("node-b".to_string(), "b:2".to_string()).expect("join");
+  let record = delta.entries[0].clone().with_roles(vec!["backend".to_string()]);
+  let next = table.version().next();
+  table.apply_delta(crate::core::MembershipDelta::new(table.version(), next, vec![record]));
+
+  assert_eq!(manager.on_membership(&table), vec![ClusterSingletonAction::StartLocal]);
+}
@@ -0,0 +1,237 @@
+//! Actor running the cluster singleton manager of the local member.
+
+use alloc::{format, string::String, vec::Vec};
+
+use fraktor_actor_rs::core::{
+  actor_prim::{Actor, ActorContextGeneric, ChildRefGeneric, Pid, actor_ref::ActorRefGeneric},
+  error::ActorError,
+  event_stream::{EventStreamEvent, EventStreamGeneric, EventStreamSubscriptionGeneric},
+  logging::LogLevel,
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+  props::PropsGeneric,
+  serialization::SerializationExtensionGeneric,
+};
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
+use crate::core::{
+  cluster_singleton_action::ClusterSingletonAction, cluster_singleton_manager::ClusterSingletonManager,
+  cluster_singleton_pdu::ClusterSingletonPdu, cluster_singleton_serializer::ClusterSingletonSerializer,
+  cluster_singleton_settings::ClusterSingletonSettings, member_path::MemberPath,
+  membership_snapshot::MembershipSnapshot, membership_table::MembershipTable,
+};
+
+/// Hosts the singleton on the local member while it is the oldest one.
+///
+/// Spawn one manager per singleton on every member that may host it, under the same path on
+/// every member. The manager follows the membership view published as [`MembershipSnapshot`]
+/// cluster events on the event stream (or sent to it directly) and drives a
+/// [`ClusterSingletonManager`]: the oldest member spawns the singleton from the given props as a
+/// child named after [`ClusterSingletonSettings::singleton_name`], and when ownership moves the
+/// previous owner stops its child and then tells the manager of the new owner, through remoting,
+/// that the handover is done. Managers also tell
+/// [`ClusterSingletonProxyActor`](crate::core::ClusterSingletonProxyActor)s where the singleton
+/// runs.
+///
+/// The local actor system must have remoting configured with a canonical authority.
+pub struct ClusterSingletonManagerActor<TB: RuntimeToolbox + 'static> {
+  settings:         ClusterSingletonSettings,
+  singleton_props:  PropsGeneric<TB>,
+  authority:        String,
+  manager:          Option<ClusterSingletonManager<TB>>,
+  singleton:        Option<ChildRefGeneric<TB>>,
+  pending_handover: Option<String>,
+  waiting:          Vec<ActorRefGeneric<TB>>,
+  subscription:     Option<EventStreamSubscriptionGeneric<TB>>,
+}
+
+impl<TB: RuntimeToolbox + 'static> ClusterSingletonManagerActor<TB> {
+  /// Creates a manager spawning the singleton from `singleton_props` when the local member owns it.
+  #[must_use]
+  pub const fn new(settings: ClusterSingletonSettings, singleton_props: PropsGeneric<TB>) -> Self {
+    Self {
+      settings,
+      singleton_props,
+      authority: String::new(),
+      manager: None,
+      singleton: None,
+      pending_handover: None,
+      waiting: Vec::new(),
+      subscription: None,
+    }
+  }
+
+  fn on_membership(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    snapshot: &MembershipSnapshot,
+  ) -> Result<(), ActorError> {
+    let table = MembershipTable::from_snapshot(snapshot.clone(), 0);
+    let Some(manager) = self.manager.as_mut() else {
+      return Ok(());
+    };
+    let actions = manager.on_membership(&table);
+    if manager.oldest() != Some(self.authority.as_str()) {
+      // 自ノードが最古でなくなったら、問い合わせ元は新しい最古メンバーへ問い合わせ直す
+      self.waiting.clear();
+    }
+    self.apply(ctx, actions)?;
+    self.take_over(ctx)
+  }
+
+  // 引き継ぎ通知がメンバーシップの更新より先に届いた場合に備え、待機状態になってから適用する
+  fn take_over(&mut self, ctx: &mut ActorContextGeneric<'_, TB>) -> Result<(), ActorError> {
+    let (Some(manager), Some(from)) = (self.manager.as_mut(), self.pending_handover.as_deref()) else {
+      return Ok(());
+    };
+    let actions = manager.on_handover_done(from);
+    if actions.is_empty() {
+      return Ok(());
+    }
+    self.pending_handover = None;
+    self.apply(ctx, actions)
+  }
+
+  fn apply(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    mut actions: Vec<ClusterSingletonAction>,
+  ) -> Result<(), ActorError> {
+    while !actions.is_empty() {
+      let action = actions.remove(0);
+      match action {
+        | ClusterSingletonAction::StartLocal => {
+          let props = self.singleton_props.clone().with_name(self.settings.singleton_name());
+          let child = ctx
+            .spawn_child_watched(&props)
+            .map_err(|error| ActorError::recoverable(format!("failed to start the singleton: {error:?}")))?;
+          self.singleton = Some(child);
+          self.pending_handover = None;
+          self.answer_waiting(ctx);
+        },
+        | ClusterSingletonAction::StopLocal => match &self.singleton {
+          | Some(child) => {
+            let _ = ctx.stop_child(child);
+          },
+          // インスタンスが既に止まっていれば、停止完了として即座に引き継ぐ
+          | None => {
+            actions.extend(self.manager.as_mut().map(ClusterSingletonManager::on_local_stopped).unwrap_or_default())
+          },
+        },
+        | ClusterSingletonAction::HandOver { to } => self.hand_over(ctx, &to),
+      }
+    }
+    Ok(())
+  }
+
+  fn hand_over(&self, ctx: &ActorContextGeneric<'_, TB>, to: &str) {
+    let Some(local) = ctx.self_ref().path() else {
+      return;
+    };
+    let path = MemberPath::rebase(&ctx.system().state().system_name(), to, &local);
+    let pdu = ClusterSingletonPdu::HandOver {
+      name: String::from(self.settings.singleton_name()),
+      from: self.authority.clone(),
+    };
+    let sent = ctx
+      .system()
+      .resolve_actor_ref(path)
+      .map_err(|error| format!("{error:?}"))
+      .and_then(|manager| manager.tell(AnyMessageGeneric::new(pdu)).map_err(|error| format!("{error:?}")));
+    if let Err(reason) = sent {
+      ctx.log(
+        LogLevel::Warn,
+        format!("failed to hand over singleton {} to {to}: {reason}", self.settings.singleton_name()),
+      );
+    }
+  }
+
+  fn answer_waiting(&mut self, ctx: &ActorContextGeneric<'_, TB>) {
+    let owns = self.manager.as_ref().and_then(ClusterSingletonManager::oldest) == Some(self.authority.as_str());
+    if self.singleton.is_none() || !owns {
+      return;
+    }
+    for proxy in self.waiting.drain(..) {
+      let located = ClusterSingletonPdu::Located {
+        name:      String::from(self.settings.singleton_name()),
+        authority: self.authority.clone(),
+      };
+      if proxy.tell(AnyMessageGeneric::new(located)).is_err() {
+        ctx.log(LogLevel::Debug, "dropping the location reply to a stopped proxy");
+      }
+    }
+  }
+
+  fn handle_pdu(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    pdu: &ClusterSingletonPdu,
+    reply_to: Option<&ActorRefGeneric<TB>>,
+  ) -> Result<(), ActorError> {
+    match pdu {
+      | ClusterSingletonPdu::HandOver { from, .. } => {
+        self.pending_handover = Some(from.clone());
+        self.take_over(ctx)
+      },
+      | ClusterSingletonPdu::Identify { .. } => {
+        if let Some(proxy) = reply_to {
+          self.waiting.push(proxy.clone());
+          self.answer_waiting(ctx);
+        }
+        Ok(())
+      },
+      | ClusterSingletonPdu::Located { .. } => Ok(()),
+    }
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> Actor<TB> for ClusterSingletonManagerActor<TB> {
+  fn pre_start(&mut self, ctx: &mut ActorContextGeneric<'_, TB>) -> Result<(), ActorError> {
+    let system = ctx.system().clone();
+    let Some(authority) = system.canonical_authority() else {
+      return Err(ActorError::fatal("cluster singleton manager requires remoting with a canonical authority"));
+    };
+    if let Some(serialization) = system.extended().extension_by_type::<SerializationExtensionGeneric<TB>>() {
+      ClusterSingletonSerializer::register(&serialization);
+    }
+    let event_stream = system.event_stream();
+    self.authority = authority.clone();
+    self.manager = Some(ClusterSingletonManager::new(self.settings.clone(), authority, event_stream.clone()));
+    self.subscription = Some(EventStreamGeneric::subscribe_actor(&event_stream, ctx.self_ref()));
+    Ok(())
+  }
+
+  fn receive(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    message: AnyMessageViewGeneric<'_, TB>,
+  ) -> Result<(), ActorError> {
+    if let Some(EventStreamEvent::Extension { name, payload }) = message.downcast_ref::<EventStreamEvent<TB>>()
+      && name == "cluster"
+      && let Some(snapshot) = payload.payload().downcast_ref::<MembershipSnapshot>()
+    {
+      self.on_membership(ctx, snapshot)
+    } else if let Some(snapshot) = message.downcast_ref::<MembershipSnapshot>() {
+      self.on_membership(ctx, snapshot)
+    } else if let Some(pdu) = message.downcast_ref::<ClusterSingletonPdu>()
+      && pdu.name() == self.settings.singleton_name()
+    {
+      self.handle_pdu(ctx, pdu, message.reply_to())
+    } else {
+      Ok(())
+    }
+  }
+
+  fn post_stop(&mut self, _ctx: &mut ActorContextGeneric<'_, TB>) -> Result<(), ActorError> {
+    self.subscription = None;
+    Ok(())
+  }
+
+  fn on_terminated(&mut self, ctx: &mut ActorContextGeneric<'_, TB>, terminated: Pid) -> Result<(), ActorError> {
+    if self.singleton.as_ref().is_none_or(|child| child.pid() != terminated) {
+      return Ok(());
+    }
+    self.singleton = None;
+    let actions = self.manager.as_mut().map(ClusterSingletonManager::on_local_stopped).unwrap_or_default();
+    self.apply(ctx, actions)
+  }
+}
@@ -0,0 +1,94 @@
+//! Protocol messages exchanged by cluster singleton managers and proxies across nodes.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{string::String, vec::Vec};
+
+const TAG_HAND_OVER: u8 = 0;
+const TAG_IDENTIFY: u8 = 1;
+const TAG_LOCATED: u8 = 2;
+
+/// Message sent between the singleton actors of different members.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ClusterSingletonPdu {
+  /// The previous owner stopped its instance; sent to the manager of the new oldest member.
+  HandOver { name: String, from: String },
+  /// A proxy asks the manager of the oldest member where the singleton runs.
+  Identify { name: String },
+  /// The manager answers an [`Identify`](Self::Identify) once its instance runs.
+  Located { name: String, authority: String },
+}
+
+impl ClusterSingletonPdu {
+  /// Returns the name of the singleton the message refers to.
+  pub(crate) const fn name(&self) -> &str {
+    match self {
+      | Self::HandOver { name, .. } | Self::Identify { name } | Self::Located { name, .. } => name.as_str(),
+    }
+  }
+
+  pub(crate) fn encode(&self) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    match self {
+      | Self::HandOver { name, from } => {
+        buffer.push(TAG_HAND_OVER);
+        put_str(&mut buffer, name);
+        put_str(&mut buffer, from);
+      },
+      | Self::Identify { name } => {
+        buffer.push(TAG_IDENTIFY);
+        put_str(&mut buffer, name);
+      },
+      | Self::Located { name, authority } => {
+        buffer.push(TAG_LOCATED);
+        put_str(&mut buffer, name);
+        put_str(&mut buffer, authority);
+      },
+    }
+    buffer
+  }
+
+  /// Decodes a message, returning `None` when the bytes are malformed.
+  pub(crate) fn decode(bytes: &[u8]) -> Option<Self> {
+    let mut reader = Reader { bytes };
+    let tag = reader.u8()?;
+    let name = reader.string()?;
+    let pdu = match tag {
+      | TAG_HAND_OVER => Self::HandOver { name, from: reader.string()? },
+      | TAG_IDENTIFY => Self::Identify { name },
+      | TAG_LOCATED => Self::Located { name, authority: reader.string()? },
+      | _ => return None,
+    };
+    reader.bytes.is_empty().then_some(pdu)
+  }
+}
+
+struct Reader<'a> {
+  bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+  const fn take(&mut self, count: usize) -> Option<&[u8]> {
+    if self.bytes.len() < count {
+      return None;
+    }
+    let (head, tail) = self.bytes.split_at(count);
+    self.bytes = tail;
+    Some(head)
+  }
+
+  fn u8(&mut self) -> Option<u8> {
+    self.take(1).map(|bytes| bytes[0])
+  }
+
+  fn string(&mut self) -> Option<String> {
+    let len = self.take(4).and_then(|bytes| bytes.try_into().ok()).map(u32::from_be_bytes)? as usize;
+    self.take(len).and_then(|bytes| String::from_utf8(bytes.to_vec()).ok())
+  }
+}
+
+fn put_str(buffer: &mut Vec<u8>, value: &str) {
+  buffer.extend_from_slice(&u32::try_from(value.len()).unwrap_or(u32::MAX).to_be_bytes());
+  buffer.extend_from_slice(value.as_bytes());
+}
@@ -0,0 +1,25 @@
+use alloc::string::ToString;
+
+use super::ClusterSingletonPdu;
+
+#[test]
+fn pdus_round_trip() {
+  let pdus = [
+    ClusterSingletonPdu::HandOver { name: "scheduler".to_string(), from: "node-0:2552".to_string() },
+    ClusterSingletonPdu::Identify { name: "scheduler".to_string() },
+    ClusterSingletonPdu::Located { name: "scheduler".to_string(), authority: "node-1:2552".to_string() },
+  ];
+
+  for pdu in pdus {
+    assert_eq!(ClusterSingletonPdu::decode(&pdu.encode()), Some(pdu));
+  }
+}
+
+#[test]
+fn malformed_pdus_are_rejected() {
+  let mut bytes = ClusterSingletonPdu::Identify { name: "scheduler".to_string() }.encode();
+  assert!(ClusterSingletonPdu::decode(&bytes[..bytes.len() - 1]).is_none());
+  bytes.push(0);
+  assert!(ClusterSingletonPdu::decode(&bytes).is_none());
+  assert!(ClusterSingletonPdu::decode(&[9, 0, 0, 0, 0]).is_none());
+}
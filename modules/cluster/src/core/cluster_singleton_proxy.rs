@@ -0,0 +1,163 @@
+//! Cluster singleton proxy routing messages to the current singleton location.
+
+use alloc::{collections::VecDeque, string::String, vec::Vec};
+
+use fraktor_actor_rs::core::{
+  actor_prim::actor_path::ActorPath,
+  event_stream::{EventStreamEvent, EventStreamGeneric},
+  messaging::AnyMessageGeneric,
+};
+use fraktor_utils_rs::core::{runtime_toolbox::RuntimeToolbox, sync::ArcShared};
+
+use crate::core::{
+  cluster_singleton_delivery::ClusterSingletonDelivery, cluster_singleton_event::ClusterSingletonEvent,
+  cluster_singleton_settings::ClusterSingletonSettings, member_path::MemberPath, membership_table::MembershipTable,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Routes messages to the singleton through remoting, buffering them during handover.
+///
+/// The proxy treats the location as unknown whenever the oldest member changes and keeps
+/// buffering until the new owner reports [`ClusterSingletonEvent::Started`]. When the buffer
+/// is full the oldest message is dropped.
+///
+/// This is the routing state only;
+/// [`ClusterSingletonProxyActor`](crate::core::ClusterSingletonProxyActor) drives it from
+/// membership changes and delivers the messages through remoting.
+pub struct ClusterSingletonProxy<TB: RuntimeToolbox + 'static, M> {
+  settings:         ClusterSingletonSettings,
+  system_name:      String,
+  event_stream:     ArcShared<EventStreamGeneric<TB>>,
+  manager_segments: Vec<String>,
+  oldest:           Option<String>,
+  location:         Option<String>,
+  buffer:           VecDeque<M>,
+}
+
+impl<TB: RuntimeToolbox + 'static, M> ClusterSingletonProxy<TB, M> {
+  /// Creates a proxy for the singleton hosted by actor systems named `system_name`.
+  #[must_use]
+  pub fn new(
+    settings: ClusterSingletonSettings,
+    system_name: impl Into<String>,
+    event_stream: ArcShared<EventStreamGeneric<TB>>,
+  ) -> Self {
+    Self {
+      settings,
+      system_name: system_name.into(),
+      event_stream,
+      manager_segments: Vec::new(),
+      oldest: None,
+      location: None,
+      buffer: VecDeque::new(),
+    }
+  }
+
+  /// Routes to the singleton spawned by the manager found at `path` on every member.
+  ///
+  /// Only the segments of `path` are used, so the path of the local manager works. Without it,
+  /// the singleton is expected directly under the user guardian.
+  #[must_use]
+  pub fn with_manager_path(mut self, path: &ActorPath) -> Self {
+    self.manager_segments = MemberPath::relative_segments(path);
+    self
+  }
+
+  /// Returns the oldest member seen in the latest membership view.
+  #[must_use]
+  pub fn oldest(&self) -> Option<&str> {
+    self.oldest.as_deref()
+  }
+
+  /// Returns the confirmed singleton location.
+  #[must_use]
+  pub fn location(&self) -> Option<&str> {
+    self.location.as_deref()
+  }
+
+  /// Returns the number of buffered messages.
+  #[must_use]
+  pub fn buffered_len(&self) -> usize {
+    self.buffer.len()
+  }
+
+  /// Routes a message to the current location, or buffers it while the location is unknown.
+  pub fn send(&mut self, message: M) -> Option<ClusterSingletonDelivery<M>> {
+    if let Some(authority) = self.location.as_deref() {
+      return Some(ClusterSingletonDelivery { path: self.singleton_path(authority), message });
+    }
+    if self.settings.buffer_size() == 0 {
+      self.publish(ClusterSingletonEvent::MessageDropped { name: self.name() });
+      return None;
+    }
+    if self.buffer.len() >= self.settings.buffer_size() {
+      self.buffer.pop_front();
+      self.publish(ClusterSingletonEvent::MessageDropped { name: self.name() });
+    }
+    self.buffer.push_back(message);
+    self.publish(ClusterSingletonEvent::MessageBuffered { name: self.name(), buffered: self.buffer.len() });
+    None
+  }
+
+  /// Starts buffering when the oldest member changes, as a handover is about to happen.
+  pub fn on_membership(&mut self, table: &MembershipTable) {
+    let oldest = table.oldest_up(self.settings.role()).map(|record| record.authority.clone());
+    if oldest != self.oldest {
+      self.oldest = oldest;
+      self.location = None;
+    }
+  }
+
+  /// Applies a singleton event, flushing the buffer once the new location is confirmed.
+  pub fn handle_event(&mut self, event: &ClusterSingletonEvent) -> Vec<ClusterSingletonDelivery<M>> {
+    match event {
+      | ClusterSingletonEvent::Started { name, authority } if name == self.settings.singleton_name() => {
+        self.update_location(Some(authority.clone()))
+      },
+      | ClusterSingletonEvent::HandoverStarted { name, .. } if name == self.settings.singleton_name() => {
+        self.update_location(None)
+      },
+      | _ => Vec::new(),
+    }
+  }
+
+  /// Sets the singleton location explicitly; `None` switches to buffering.
+  pub fn update_location(&mut self, location: Option<String>) -> Vec<ClusterSingletonDelivery<M>> {
+    self.location = location;
+    let Some(authority) = self.location.clone() else {
+      return Vec::new();
+    };
+    if self.buffer.is_empty() {
+      return Vec::new();
+    }
+    let path = self.singleton_path(&authority);
+    let deliveries: Vec<_> =
+      self.buffer.drain(..).map(|message| ClusterSingletonDelivery { path: path.clone(), message }).collect();
+    self.publish(ClusterSingletonEvent::BufferFlushed { name: self.name(), authority, count: deliveries.len() });
+    deliveries
+  }
+
+  /// Builds the remote path of the singleton hosted at `authority` (`host:port`).
+  #[must_use]
+  pub fn singleton_path(&self, authority: &str) -> ActorPath {
+    self.manager_path(authority).child(self.settings.singleton_name())
+  }
+
+  /// Builds the remote path of the manager running at `authority` (`host:port`).
+  #[must_use]
+  pub fn manager_path(&self, authority: &str) -> ActorPath {
+    MemberPath::of(&self.system_name, authority, &self.manager_segments)
+  }
+
+  fn name(&self) -> String {
+    String::from(self.settings.singleton_name())
+  }
+
+  fn publish(&self, event: ClusterSingletonEvent) {
+    let payload = AnyMessageGeneric::new(event);
+    let extension_event = EventStreamEvent::Extension { name: String::from("cluster"), payload };
+    self.event_stream.publish(&extension_event);
+  }
+}
@@ -0,0 +1,89 @@
+use alloc::{string::ToString, vec::Vec};
+
+use fraktor_actor_rs::core::{actor_prim::actor_path::ActorPath, event_stream::EventStreamGeneric};
+use fraktor_utils_rs::core::{runtime_toolbox::NoStdToolbox, sync::ArcShared};
+
+use super::ClusterSingletonProxy;
+use crate::core::{ClusterSingletonEvent, ClusterSingletonSettings, MembershipTable};
+
+fn proxy(buffer_size: usize) -> ClusterSingletonProxy<NoStdToolbox, u32> {
+  let event_stream = ArcShared::new(EventStreamGeneric::<NoStdToolbox>::default());
+  let settings = ClusterSingletonSettings::new("scheduler").with_buffer_size(buffer_size);
+  ClusterSingletonProxy::new(settings, "cluster-sys", event_stream)
+}
+
+#[test]
+fn buffers_until_location_is_known_then_flushes() {
+  let mut proxy = proxy(8);
+
+  assert!(proxy.send(1).is_none());
+  assert!(proxy.send(2).is_none());
+  assert_eq!(proxy.buffered_len(), 2);
+
+  let flushed = proxy.handle_event(&ClusterSingletonEvent::Started {
+    name:      "scheduler".to_string(),
+    authority: "10.0.0.1:2552".to_string(),
+  });
+
+  let messages: Vec<u32> = flushed.iter().map(|delivery| delivery.message).collect();
+  assert_eq!(messages, alloc::vec![1, 2]);
+  assert_eq!(flushed[0].path.to_canonical_uri(), "fraktor.tcp://cluster-sys@10.0.0.1:2552/user/scheduler");
+  assert_eq!(proxy.buffered_len(), 0);
+
+  let delivery = proxy.send(3).expect("routed directly");
+  assert_eq!(delivery.message, 3);
+}
+
+#[test]
+fn drops_oldest_when_buffer_is_full() {
+  let mut proxy = proxy(2);
+  proxy.send(1);
+  proxy.send(2);
+  proxy.send(3);
+
+  let flushed = proxy.update_location(Some("a:1".to_string()));
+  let messages: Vec<u32> = flushed.iter().map(|delivery| delivery.message).collect();
+  assert_eq!(messages, alloc::vec![2, 3]);
+}
+
+#[test]
+fn oldest_change_switches_to_buffering() {
+  let mut proxy = proxy(4);
+  let mut table = MembershipTable::new(3);
+  table.try_join("node-a".to_string(), "a:1".to_string()).expect("join");
+  proxy.on_membership(&table);
+  proxy.update_location(Some("a:1".to_string()));
+  assert!(proxy.send(1).is_some());
+
+  table.mark_left("a:1").expect("left");
+  table.try_join("node-b".to_string(), "b:2".to_string()).expect("join");
+  proxy.on_membership(&table);
+
+  assert_eq!(proxy.location(), None);
+  assert!(proxy.send(2).is_none());
+}
+
+#[test]
+fn ignores_events_for_other_singletons() {
+  let mut proxy = proxy(4);
+  proxy.send(1);
+  let flushed = proxy
+    .handle_event(&ClusterSingletonEvent::Started { name: "other".to_string(), authority: "a:1".to_string() });
+  assert!(flushed.is_empty());
+  assert_eq!(proxy.buffered_len(), 1);
+}
+
+#[test]
+fn manager_path_places_the_singleton_under_its_manager() {
+  let manager = ActorPath::root().child("testkit").child("scheduler-manager");
+  let proxy = proxy(4).with_manager_path(&manager);
+
+  assert_eq!(
+    proxy.manager_path("a:1").to_canonical_uri(),
+    "fraktor.tcp://cluster-sys@a:1/user/testkit/scheduler-manager"
+  );
+  assert_eq!(
+    proxy.singleton_path("a:1").to_canonical_uri(),
+    "fraktor.tcp://cluster-sys@a:1/user/testkit/scheduler-manager/scheduler"
+  );
+}
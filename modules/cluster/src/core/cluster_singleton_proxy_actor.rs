@@ -0,0 +1,175 @@
+//! Actor forwarding messages to the cluster singleton wherever it runs.
+
+use alloc::{format, string::String, vec::Vec};
+use core::marker::PhantomData;
+
+use fraktor_actor_rs::core::{
+  actor_prim::{Actor, ActorContextGeneric, actor_path::ActorPath, actor_ref::ActorRefGeneric},
+  error::ActorError,
+  event_stream::{EventStreamEvent, EventStreamGeneric, EventStreamSubscriptionGeneric},
+  logging::LogLevel,
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+  serialization::SerializationExtensionGeneric,
+};
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
+use crate::core::{
+  cluster_singleton_delivery::ClusterSingletonDelivery, cluster_singleton_event::ClusterSingletonEvent,
+  cluster_singleton_pdu::ClusterSingletonPdu, cluster_singleton_proxy::ClusterSingletonProxy,
+  cluster_singleton_serializer::ClusterSingletonSerializer, cluster_singleton_settings::ClusterSingletonSettings,
+  membership_snapshot::MembershipSnapshot, membership_table::MembershipTable,
+};
+
+/// Forwards messages of type `M` to the singleton, across nodes when needed.
+///
+/// The proxy follows the membership view published as [`MembershipSnapshot`] cluster events on
+/// the event stream (or sent to it directly). Whenever the oldest member changes it buffers the
+/// messages it receives, asks the
+/// [`ClusterSingletonManagerActor`](crate::core::ClusterSingletonManagerActor) of the new oldest
+/// member where the singleton runs, and flushes the buffer once that manager has started the
+/// singleton. Messages keep their `reply_to` and are delivered through remoting, so `M` must be
+/// serializable for the remote scope.
+///
+/// `manager_path` is the path of the managers, which must be the same on every member; the path
+/// of the local manager works.
+pub struct ClusterSingletonProxyActor<TB: RuntimeToolbox + 'static, M> {
+  settings:     ClusterSingletonSettings,
+  manager_path: ActorPath,
+  proxy:        Option<ClusterSingletonProxy<TB, AnyMessageGeneric<TB>>>,
+  target:       Option<(ActorPath, ActorRefGeneric<TB>)>,
+  subscription: Option<EventStreamSubscriptionGeneric<TB>>,
+  _message:     PhantomData<fn(M)>,
+}
+
+impl<TB: RuntimeToolbox + 'static, M> ClusterSingletonProxyActor<TB, M>
+where
+  M: Clone + Send + Sync + 'static,
+{
+  /// Creates a proxy for the singleton spawned by the managers found at `manager_path`.
+  #[must_use]
+  pub const fn new(settings: ClusterSingletonSettings, manager_path: ActorPath) -> Self {
+    Self { settings, manager_path, proxy: None, target: None, subscription: None, _message: PhantomData }
+  }
+
+  fn on_membership(&mut self, ctx: &ActorContextGeneric<'_, TB>, snapshot: &MembershipSnapshot) {
+    let table = MembershipTable::from_snapshot(snapshot.clone(), 0);
+    if let Some(proxy) = self.proxy.as_mut() {
+      proxy.on_membership(&table);
+    }
+    self.locate(ctx);
+  }
+
+  // 所在が不明な間は、最古メンバーのマネージャーへ問い合わせる
+  fn locate(&self, ctx: &ActorContextGeneric<'_, TB>) {
+    let Some(proxy) = self.proxy.as_ref() else {
+      return;
+    };
+    let Some(oldest) = proxy.oldest().filter(|_| proxy.location().is_none()) else {
+      return;
+    };
+    let identify = ClusterSingletonPdu::Identify { name: String::from(self.settings.singleton_name()) };
+    let sent =
+      ctx.system().resolve_actor_ref(proxy.manager_path(oldest)).map_err(|error| format!("{error:?}")).and_then(
+        |manager| {
+          manager
+            .tell(AnyMessageGeneric::new(identify).with_reply_to(ctx.self_ref()))
+            .map_err(|error| format!("{error:?}"))
+        },
+      );
+    if let Err(reason) = sent {
+      ctx.log(
+        LogLevel::Warn,
+        format!("failed to locate singleton {} on {oldest}: {reason}", self.settings.singleton_name()),
+      );
+    }
+  }
+
+  fn deliver(
+    &mut self,
+    ctx: &ActorContextGeneric<'_, TB>,
+    deliveries: Vec<ClusterSingletonDelivery<AnyMessageGeneric<TB>>>,
+  ) {
+    for ClusterSingletonDelivery { path, message } in deliveries {
+      if self.target.as_ref().is_none_or(|(cached, _)| *cached != path) {
+        match ctx.system().resolve_actor_ref(path.clone()) {
+          | Ok(singleton) => self.target = Some((path, singleton)),
+          | Err(error) => {
+            ctx.log(
+              LogLevel::Warn,
+              format!("failed to resolve singleton {}: {error:?}", self.settings.singleton_name()),
+            );
+            continue;
+          },
+        }
+      }
+      if let Some((_, singleton)) = &self.target
+        && singleton.tell(message).is_err()
+      {
+        ctx.log(LogLevel::Warn, format!("failed to forward a message to singleton {}", self.settings.singleton_name()));
+      }
+    }
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static, M> Actor<TB> for ClusterSingletonProxyActor<TB, M>
+where
+  M: Clone + Send + Sync + 'static,
+{
+  fn pre_start(&mut self, ctx: &mut ActorContextGeneric<'_, TB>) -> Result<(), ActorError> {
+    let system = ctx.system().clone();
+    if let Some(serialization) = system.extended().extension_by_type::<SerializationExtensionGeneric<TB>>() {
+      ClusterSingletonSerializer::register(&serialization);
+    }
+    let event_stream = system.event_stream();
+    let proxy = ClusterSingletonProxy::new(self.settings.clone(), system.state().system_name(), event_stream.clone())
+      .with_manager_path(&self.manager_path);
+    self.proxy = Some(proxy);
+    self.subscription = Some(EventStreamGeneric::subscribe_actor(&event_stream, ctx.self_ref()));
+    Ok(())
+  }
+
+  fn receive(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    message: AnyMessageViewGeneric<'_, TB>,
+  ) -> Result<(), ActorError> {
+    if let Some(event) = message.downcast_ref::<EventStreamEvent<TB>>() {
+      if let EventStreamEvent::Extension { name, payload } = event
+        && name == "cluster"
+      {
+        if let Some(snapshot) = payload.payload().downcast_ref::<MembershipSnapshot>() {
+          self.on_membership(ctx, snapshot);
+        } else if let Some(singleton_event) = payload.payload().downcast_ref::<ClusterSingletonEvent>() {
+          let deliveries = self.proxy.as_mut().map(|proxy| proxy.handle_event(singleton_event)).unwrap_or_default();
+          self.deliver(ctx, deliveries);
+          self.locate(ctx);
+        }
+      }
+    } else if let Some(snapshot) = message.downcast_ref::<MembershipSnapshot>() {
+      self.on_membership(ctx, snapshot);
+    } else if let Some(pdu) = message.downcast_ref::<ClusterSingletonPdu>() {
+      // 最古メンバー以外からの応答は引き継ぎ前の古い所在なので無視する
+      if let ClusterSingletonPdu::Located { name, authority } = pdu
+        && name == self.settings.singleton_name()
+        && let Some(proxy) = self.proxy.as_mut()
+        && proxy.oldest() == Some(authority.as_str())
+      {
+        let deliveries = proxy.update_location(Some(authority.clone()));
+        self.deliver(ctx, deliveries);
+      }
+    } else if let Some(payload) = message.downcast_ref::<M>() {
+      let mut forwarded = AnyMessageGeneric::new(payload.clone());
+      if let Some(reply_to) = message.reply_to() {
+        forwarded = forwarded.with_reply_to(reply_to.clone());
+      }
+      let delivery = self.proxy.as_mut().and_then(|proxy| proxy.send(forwarded));
+      self.deliver(ctx, delivery.into_iter().collect());
+    }
+    Ok(())
+  }
+
+  fn post_stop(&mut self, _ctx: &mut ActorContextGeneric<'_, TB>) -> Result<(), ActorError> {
+    self.subscription = None;
+    Ok(())
+  }
+}
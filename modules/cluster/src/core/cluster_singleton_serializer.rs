@@ -0,0 +1,84 @@
+//! Serializer carrying the cluster singleton protocol across nodes.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+use core::any::{Any, TypeId};
+
+use fraktor_actor_rs::core::serialization::{
+  CLUSTER_SINGLETON_ID, SerializationError, SerializationExtensionGeneric, Serializer, SerializerId,
+  SerializerWithStringManifest,
+};
+use fraktor_utils_rs::core::{runtime_toolbox::RuntimeToolbox, sync::ArcShared};
+
+use crate::core::cluster_singleton_pdu::ClusterSingletonPdu;
+
+const MANIFEST: &str = "fraktor.cluster.ClusterSingletonPdu";
+
+/// Encodes [`ClusterSingletonPdu`] values with their own wire format.
+pub(crate) struct ClusterSingletonSerializer;
+
+impl ClusterSingletonSerializer {
+  /// Registers the serializer and its binding with the provided extension.
+  ///
+  /// Registration is idempotent so that every manager and proxy may call it.
+  pub(crate) fn register<TB: RuntimeToolbox + 'static>(serialization: &SerializationExtensionGeneric<TB>) {
+    let serializer: ArcShared<dyn Serializer> = ArcShared::new(Self);
+    let _ = serialization.register_serializer(CLUSTER_SINGLETON_ID, serializer);
+    let _ = serialization.register_binding(
+      TypeId::of::<ClusterSingletonPdu>(),
+      core::any::type_name::<ClusterSingletonPdu>(),
+      CLUSTER_SINGLETON_ID,
+    );
+  }
+}
+
+impl Serializer for ClusterSingletonSerializer {
+  fn identifier(&self) -> SerializerId {
+    CLUSTER_SINGLETON_ID
+  }
+
+  fn include_manifest(&self) -> bool {
+    true
+  }
+
+  fn to_binary(&self, message: &(dyn Any + Send + Sync)) -> Result<Vec<u8>, SerializationError> {
+    let pdu = message.downcast_ref::<ClusterSingletonPdu>().ok_or(SerializationError::InvalidFormat)?;
+    Ok(pdu.encode())
+  }
+
+  fn from_binary(
+    &self,
+    bytes: &[u8],
+    _type_hint: Option<TypeId>,
+  ) -> Result<Box<dyn Any + Send + Sync>, SerializationError> {
+    let pdu = ClusterSingletonPdu::decode(bytes).ok_or(SerializationError::InvalidFormat)?;
+    Ok(Box::new(pdu))
+  }
+
+  fn as_any(&self) -> &(dyn Any + Send + Sync) {
+    self
+  }
+
+  fn as_string_manifest(&self) -> Option<&dyn SerializerWithStringManifest> {
+    Some(self)
+  }
+}
+
+impl SerializerWithStringManifest for ClusterSingletonSerializer {
+  fn manifest(&self, _message: &(dyn Any + Send + Sync)) -> Cow<'_, str> {
+    Cow::Borrowed(MANIFEST)
+  }
+
+  fn from_binary_with_manifest(
+    &self,
+    bytes: &[u8],
+    manifest: &str,
+  ) -> Result<Box<dyn Any + Send + Sync>, SerializationError> {
+    if manifest != MANIFEST {
+      return Err(SerializationError::UnknownManifest(manifest.into()));
+    }
+    self.from_binary(bytes, None)
+  }
+}
@@ -0,0 +1,55 @@
+//! Settings shared by the cluster singleton manager and proxy.
+
+use alloc::string::String;
+
+#[cfg(test)]
+mod tests;
+
+/// Identifies a cluster singleton and constrains where it may run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterSingletonSettings {
+  singleton_name: String,
+  role:           Option<String>,
+  buffer_size:    usize,
+}
+
+impl ClusterSingletonSettings {
+  /// Creates settings for the named singleton without role restriction.
+  #[must_use]
+  pub fn new(singleton_name: impl Into<String>) -> Self {
+    Self { singleton_name: singleton_name.into(), role: None, buffer_size: 1000 }
+  }
+
+  /// Restricts the singleton to members advertising the given role.
+  #[must_use]
+  pub fn with_role(mut self, role: impl Into<String>) -> Self {
+    self.role = Some(role.into());
+    self
+  }
+
+  /// Sets how many messages the proxy buffers while the location is unknown.
+  #[must_use]
+  pub const fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+    self.buffer_size = buffer_size;
+    self
+  }
+
+  /// Returns the singleton name (also used as the actor name).
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn singleton_name(&self) -> &str {
+    &self.singleton_name
+  }
+
+  /// Returns the role constraint, if any.
+  #[must_use]
+  pub fn role(&self) -> Option<&str> {
+    self.role.as_deref()
+  }
+
+  /// Returns the proxy buffer size.
+  #[must_use]
+  pub const fn buffer_size(&self) -> usize {
+    self.buffer_size
+  }
+}
@@ -0,0 +1,16 @@
+use super::ClusterSingletonSettings;
+
+#[test]
+fn defaults_have_no_role_and_positive_buffer() {
+  let settings = ClusterSingletonSettings::new("scheduler");
+  assert_eq!(settings.singleton_name(), "scheduler");
+  assert_eq!(settings.role(), None);
+  assert!(settings.buffer_size() > 0);
+}
+
+#[test]
+fn builders_override_role_and_buffer_size() {
+  let settings = ClusterSingletonSettings::new("scheduler").with_role("backend").with_buffer_size(4);
+  assert_eq!(settings.role(), Some("backend"));
+  assert_eq!(settings.buffer_size(), 4);
+}
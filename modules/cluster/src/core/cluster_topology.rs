@@ -1,20 +1,28 @@
 //! Snapshot of cluster topology changes used for event publication.
 
-use alloc::{string::String, vec::Vec};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
 
 /// Topology delta communicated to the cluster core.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ClusterTopology {
-  hash:   u64,
-  joined: Vec<String>,
-  left:   Vec<String>,
+  hash:         u64,
+  joined:       Vec<String>,
+  left:         Vec<String>,
+  member_roles: BTreeMap<String, Vec<String>>,
 }
 
 impl ClusterTopology {
   /// Creates a new topology snapshot.
   #[must_use]
   pub const fn new(hash: u64, joined: Vec<String>, left: Vec<String>) -> Self {
-    Self { hash, joined, left }
+    Self { hash, joined, left, member_roles: BTreeMap::new() }
+  }
+
+  /// Attaches the roles advertised by members (keyed by authority).
+  #[must_use]
+  pub fn with_member_roles(mut self, member_roles: BTreeMap<String, Vec<String>>) -> Self {
+    self.member_roles = member_roles;
+    self
   }
 
   /// Topology hash value.
@@ -34,4 +42,10 @@ impl ClusterTopology {
   pub const fn left(&self) -> &Vec<String> {
     &self.left
   }
+
+  /// Roles advertised by members, keyed by authority.
+  #[must_use]
+  pub const fn member_roles(&self) -> &BTreeMap<String, Vec<String>> {
+    &self.member_roles
+  }
 }
@@ -0,0 +1,12 @@
+//! Reasons for deactivating a grain.
+
+/// Why an activation is being deactivated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeactivationReason {
+  /// The activation stayed idle longer than its idle timeout.
+  IdleTimeout,
+  /// The node exceeded the kind's activation limit and this was the least recently used one.
+  CapacityExceeded,
+  /// The rendezvous owner changed and the activation is handed off to the new owner.
+  OwnerChanged,
+}
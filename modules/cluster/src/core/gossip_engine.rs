@@ -66,6 +66,18 @@ impl GossipEngine {
     out
   }
 
+  /// Applies a local membership change and disseminates the resulting delta to all peers.
+  ///
+  /// Returns no outbound gossip when `update` leaves the table unchanged.
+  pub fn update_local<F>(&mut self, update: F) -> Vec<GossipOutbound>
+  where
+    F: FnOnce(&mut MembershipTable) -> Option<MembershipDelta>, {
+    match update(&mut self.table) {
+      | Some(delta) => self.disseminate(&delta),
+      | None => Vec::new(),
+    }
+  }
+
   /// Handles an ack from a peer; returns the new state when it changes.
   pub fn handle_ack(&mut self, peer: &str) -> Option<GossipState> {
     self.outstanding.remove(peer);
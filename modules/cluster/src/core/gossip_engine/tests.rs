@@ -76,3 +76,18 @@ fn missing_range_request_enters_reconciling() {
     local_version: MembershipVersion::new(1),
   }],);
 }
+
+#[test]
+fn local_update_disseminates_only_when_the_table_changes() {
+  let mut table = crate::core::membership_table::MembershipTable::new(3);
+  table.try_join("node-1".to_string(), "n1:4050".to_string()).expect("join succeeds");
+  let mut engine = GossipEngine::new(table, vec!["n2:4050".to_string()]);
+
+  let outbound = engine.update_local(|table| table.mark_unreachable("n1:4050"));
+  assert_eq!(outbound.len(), 1);
+  assert_eq!(outbound[0].delta.entries[0].status, NodeStatus::Unreachable);
+  assert_eq!(engine.state(), GossipState::Diffusing);
+  assert_eq!(engine.table().version(), MembershipVersion::new(2));
+
+  assert!(engine.update_local(|table| table.mark_unreachable("n1:4050")).is_empty());
+}
@@ -0,0 +1,16 @@
+//! Handoff of a grain activation between owners.
+
+use alloc::string::String;
+
+use crate::core::grain_key::GrainKey;
+
+/// Move of an activation from its previous owner to the new rendezvous owner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrainHandoff {
+  /// Grain key.
+  pub key:  GrainKey,
+  /// Authority currently hosting the activation.
+  pub from: String,
+  /// Authority that will host the activation.
+  pub to:   String,
+}
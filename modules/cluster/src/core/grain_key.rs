@@ -24,4 +24,10 @@ impl GrainKey {
   pub fn value(&self) -> &str {
     &self.value
   }
+
+  /// Returns the kind prefix for keys shaped as `kind:identity`, such as `user:va-1`.
+  #[must_use]
+  pub fn kind(&self) -> Option<&str> {
+    self.value.split_once(':').map(|(kind, _)| kind)
+  }
 }
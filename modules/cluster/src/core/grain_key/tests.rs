@@ -5,3 +5,10 @@ fn value_is_preserved() {
   let key = GrainKey::new("user:1".to_string());
   assert_eq!(key.value(), "user:1");
 }
+
+#[test]
+fn kind_is_prefix_before_colon() {
+  assert_eq!(GrainKey::new("payments:42".to_string()).kind(), Some("payments"));
+  assert_eq!(GrainKey::new("user:va-1".to_string()).kind(), Some("user"));
+  assert_eq!(GrainKey::new("session-1".to_string()).kind(), None);
+}
@@ -0,0 +1,33 @@
+//! Lifecycle signals delivered to grains.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::core::{deactivation_reason::DeactivationReason, grain_key::GrainKey};
+
+/// Signal the hosting runtime delivers to a grain activation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrainLifecycleSignal {
+  /// The grain was activated, carrying the state restored by the state provider if any.
+  Activated {
+    /// Grain key.
+    key:   GrainKey,
+    /// Assigned PID string.
+    pid:   String,
+    /// Restored state.
+    state: Option<Vec<u8>>,
+  },
+  /// The grain is about to be deactivated.
+  ///
+  /// The runtime reports the final state through
+  /// [`ClusterCore::complete_grain_deactivation`](crate::core::ClusterCore::complete_grain_deactivation),
+  /// or [`ClusterCore::complete_grain_handoff`](crate::core::ClusterCore::complete_grain_handoff)
+  /// when the reason is [`DeactivationReason::OwnerChanged`].
+  Deactivating {
+    /// Grain key.
+    key:    GrainKey,
+    /// PID being deactivated.
+    pid:    String,
+    /// Why the grain is deactivated.
+    reason: DeactivationReason,
+  },
+}
@@ -1,8 +1,42 @@
+use alloc::vec::Vec;
+
+use fraktor_actor_rs::core::{
+  actor_prim::{Actor, ActorContextGeneric},
+  error::ActorError,
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric, TraceContext},
+  props::PropsGeneric,
+  scheduler::{ManualTestDriver, TickDriverConfig},
+  system::{ActorSystemConfig, ActorSystemGeneric},
+};
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdMutex, NoStdToolbox},
+  sync::ArcShared,
+};
+
 use crate::core::{
   dispatch_drop_policy::DispatchDropPolicy, grain_key::GrainKey, grain_rpc_router::GrainRpcRouter,
   rpc_dispatch::RpcDispatch, rpc_error::RpcError, rpc_event::RpcEvent, serialized_message::SerializedMessage,
 };
 
+type Observed = ArcShared<NoStdMutex<Vec<Option<TraceContext>>>>;
+
+struct GrainProbe {
+  observed: Observed,
+}
+
+impl Actor<NoStdToolbox> for GrainProbe {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    if message.downcast_ref::<Vec<u8>>().is_some() {
+      self.observed.lock().push(message.headers().get::<TraceContext>().cloned());
+    }
+    Ok(())
+  }
+}
+
 fn key(v: &str) -> GrainKey {
   GrainKey::new(v.to_string())
 }
@@ -76,3 +110,44 @@ fn timeout_on_promoted_request_is_reported() {
   let events = router.drain_events();
   assert!(events.iter().any(|e| matches!(e, RpcEvent::TimedOut { .. })));
 }
+
+#[test]
+fn promoted_request_keeps_its_trace_context() {
+  let mut router = GrainRpcRouter::new(1, 1, DispatchDropPolicy::RejectNew, vec![1]);
+  router.negotiate(&[1]);
+  let trace_context = TraceContext::new([1; 16], [2; 8], TraceContext::FLAG_SAMPLED);
+  router.dispatch(key("k"), msg(1, b"first"), 10).expect("dispatch");
+  let queued = router.dispatch(key("k"), msg(1, b"second").with_trace_context(trace_context.clone()), 10);
+  assert!(matches!(queued, Ok(RpcDispatch::Queued { .. })));
+
+  match router.complete(&key("k"), 5) {
+    | Some(RpcDispatch::Immediate { message, .. }) => assert_eq!(message.trace_context, Some(trace_context)),
+    | other => panic!("unexpected dispatch: {other:?}"),
+  }
+}
+
+#[test]
+fn caller_trace_context_reaches_the_grain_through_the_queue() {
+  let observed: Observed = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let probe = observed.clone();
+  let props = PropsGeneric::from_fn(move || GrainProbe { observed: probe.clone() }).with_name("grain");
+  let config = ActorSystemConfig::default().with_tick_driver(TickDriverConfig::manual(ManualTestDriver::new()));
+  let system = ActorSystemGeneric::new_with_config(&props, &config).expect("system builds");
+  let grain = system.user_guardian_ref();
+
+  let trace_context = TraceContext::new([3; 16], [4; 8], TraceContext::FLAG_SAMPLED);
+  let call = AnyMessageGeneric::<NoStdToolbox>::new(b"call".to_vec()).with_header(trace_context.clone());
+  let mut router = GrainRpcRouter::new(1, 1, DispatchDropPolicy::RejectNew, vec![1]);
+  router.negotiate(&[1]);
+  router.dispatch(key("user:va-1"), msg(1, b"first"), 10).expect("dispatch");
+  let issued = msg(1, b"call").with_caller_headers(call.headers());
+  assert!(matches!(router.dispatch(key("user:va-1"), issued, 10), Ok(RpcDispatch::Queued { .. })));
+
+  let Some(RpcDispatch::Immediate { message, .. }) = router.complete(&key("user:va-1"), 5) else {
+    panic!("queued call is promoted");
+  };
+  let delivered = message.restore_headers(AnyMessageGeneric::new(message.bytes.clone()));
+  grain.tell(delivered).expect("deliver");
+
+  assert_eq!(observed.lock().clone(), vec![Some(trace_context)]);
+}
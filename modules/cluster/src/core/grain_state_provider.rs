@@ -0,0 +1,17 @@
+//! Persistence hook for grain state across activations.
+
+use alloc::vec::Vec;
+
+use crate::core::grain_key::GrainKey;
+
+/// Stores grain state between a deactivation and the next activation.
+///
+/// The registry saves the state reported on deactivation and loads it when the grain is
+/// activated again, possibly on another owner after a topology change.
+pub trait GrainStateProvider: Send + Sync {
+  /// Persists the state of a deactivated grain.
+  fn save(&mut self, key: &GrainKey, state: Vec<u8>);
+
+  /// Loads the state of a grain being activated.
+  fn load(&mut self, key: &GrainKey) -> Option<Vec<u8>>;
+}
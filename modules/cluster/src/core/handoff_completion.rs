@@ -0,0 +1,14 @@
+//! Result of a completed grain handoff.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::core::serialized_message::SerializedMessage;
+
+/// New activation and the requests buffered while the grain was moving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandoffCompletion {
+  /// PID of the activation on the new owner.
+  pub pid:      String,
+  /// Requests buffered during the move, in arrival order.
+  pub buffered: Vec<SerializedMessage>,
+}
@@ -3,8 +3,9 @@
 use alloc::{string::String, vec::Vec};
 
 use crate::core::{
-  activated_kind::ActivatedKind, grain_key::GrainKey, identity_setup_error::IdentitySetupError,
-  pid_cache_event::PidCacheEvent, virtual_actor_event::VirtualActorEvent,
+  activated_kind::ActivatedKind, grain_key::GrainKey, grain_lifecycle_signal::GrainLifecycleSignal,
+  handoff_completion::HandoffCompletion, identity_setup_error::IdentitySetupError, pid_cache_event::PidCacheEvent,
+  serialized_message::SerializedMessage, virtual_actor_event::VirtualActorEvent,
 };
 
 /// Provides identity resolution setup and lookup operations.
@@ -65,6 +66,19 @@ pub trait IdentityLookup: Send + Sync {
     let _ = authorities;
   }
 
+  /// Records the roles advertised by a member.
+  ///
+  /// Implementations use the roles to restrict placement of kinds that declare
+  /// [`ActivatedKind::required_roles`].
+  ///
+  /// # Arguments
+  ///
+  /// * `authority` - The member authority
+  /// * `roles` - Roles advertised by the member
+  fn update_member_roles(&mut self, authority: &str, roles: Vec<String>) {
+    let _ = (authority, roles);
+  }
+
   /// Handles a member leaving the cluster.
   ///
   /// Invalidates all activations and cache entries for the given authority.
@@ -86,6 +100,61 @@ pub trait IdentityLookup: Send + Sync {
     let _ = (now, idle_ttl);
   }
 
+  /// Completes a deactivation with the final state reported by the grain.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The grain key that was deactivated
+  /// * `state` - Final grain state, if the grain reported one
+  fn complete_deactivation(&mut self, key: &GrainKey, state: Option<Vec<u8>>) {
+    let _ = (key, state);
+  }
+
+  /// Buffers a request addressed to a grain that is being handed off to a new owner.
+  ///
+  /// Returns the message back when the grain is not moving and can be delivered directly.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The grain key the request is addressed to
+  /// * `message` - The serialized request
+  fn buffer_during_handoff(&mut self, key: &GrainKey, message: SerializedMessage) -> Option<SerializedMessage> {
+    let _ = key;
+    Some(message)
+  }
+
+  /// Completes a handoff once the previous owner drained and stopped the grain.
+  ///
+  /// Returns the PID on the new owner together with the requests buffered during the move,
+  /// or `None` if the key was not being handed off.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The grain key that was handed off
+  /// * `state` - Final grain state reported by the previous activation
+  /// * `now` - Current Unix timestamp in seconds
+  fn complete_handoff(&mut self, key: &GrainKey, state: Option<Vec<u8>>, now: u64) -> Option<HandoffCompletion> {
+    let _ = (key, state, now);
+    None
+  }
+
+  /// Force-completes handoffs whose previous owner left or did not report in time.
+  ///
+  /// Returns the completions so the caller can deliver the buffered requests.
+  ///
+  /// # Arguments
+  ///
+  /// * `now` - Current Unix timestamp in seconds
+  fn expire_handoffs(&mut self, now: u64) -> Vec<HandoffCompletion> {
+    let _ = now;
+    Vec::new()
+  }
+
+  /// Drains pending grain lifecycle signals.
+  fn drain_lifecycle_signals(&mut self) -> Vec<GrainLifecycleSignal> {
+    Vec::new()
+  }
+
   /// Drains pending virtual actor events.
   fn drain_events(&mut self) -> Vec<VirtualActorEvent> {
     Vec::new()
@@ -0,0 +1,63 @@
+//! Remote actor paths on cluster members addressed by their `host:port` authority.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{
+  format,
+  string::{String, ToString},
+  vec::Vec,
+};
+
+use fraktor_actor_rs::core::actor_prim::actor_path::{ActorPath, ActorPathParts};
+
+/// Builds actor paths on the member identified by an authority string.
+///
+/// Authorities follow the `host:port` form used by the membership table. IPv6 hosts may be
+/// bracketed (`[::1]:2552`); unbracketed IPv6 hosts are bracketed so that the resulting path
+/// formats back into a parsable authority.
+pub struct MemberPath;
+
+impl MemberPath {
+  /// Splits `authority` into host and port, or returns `None` when the port is missing or invalid.
+  #[must_use]
+  pub fn endpoint(authority: &str) -> Option<(String, u16)> {
+    let (host, port) = authority.rsplit_once(':')?;
+    let port = port.parse::<u16>().ok()?;
+    let host = match host {
+      | "" => return None,
+      | bracketed if bracketed.starts_with('[') => {
+        if !bracketed.ends_with(']') {
+          return None;
+        }
+        String::from(bracketed)
+      },
+      | ipv6 if ipv6.contains(':') => format!("[{ipv6}]"),
+      | host => String::from(host),
+    };
+    Some((host, port))
+  }
+
+  /// Builds the path made of `segments` below the user guardian of the member at `authority`.
+  #[must_use]
+  pub fn of<I>(system_name: &str, authority: &str, segments: I) -> ActorPath
+  where
+    I: IntoIterator,
+    I::Item: AsRef<str>, {
+    let parts = ActorPathParts::with_authority(system_name, Self::endpoint(authority));
+    segments.into_iter().fold(ActorPath::from_parts(parts), |path, segment| path.child(segment.as_ref()))
+  }
+
+  /// Rebases a local `path` onto the member at `authority`.
+  #[must_use]
+  pub fn rebase(system_name: &str, authority: &str, path: &ActorPath) -> ActorPath {
+    Self::of(system_name, authority, Self::relative_segments(path))
+  }
+
+  /// Returns the segments of `path` below its guardian, as expected by [`MemberPath::of`].
+  #[must_use]
+  pub fn relative_segments(path: &ActorPath) -> Vec<String> {
+    // 先頭のガーディアン区間は from_parts が補うため読み飛ばす
+    path.segments().iter().skip(1).map(|segment| segment.as_str().to_string()).collect()
+  }
+}
@@ -0,0 +1,37 @@
+use alloc::string::String;
+
+use fraktor_actor_rs::core::actor_prim::actor_path::ActorPath;
+
+use super::MemberPath;
+
+#[test]
+fn endpoint_splits_host_and_port() {
+  assert_eq!(MemberPath::endpoint("10.0.0.1:2552"), Some((String::from("10.0.0.1"), 2552)));
+  assert_eq!(MemberPath::endpoint("node-a.local:2552"), Some((String::from("node-a.local"), 2552)));
+  assert_eq!(MemberPath::endpoint("node-a.local"), None);
+  assert_eq!(MemberPath::endpoint(":2552"), None);
+  assert_eq!(MemberPath::endpoint("node-a.local:http"), None);
+}
+
+#[test]
+fn endpoint_keeps_or_adds_ipv6_brackets() {
+  assert_eq!(MemberPath::endpoint("[::1]:2552"), Some((String::from("[::1]"), 2552)));
+  assert_eq!(MemberPath::endpoint("fe80::1:2552"), Some((String::from("[fe80::1]"), 2552)));
+  assert_eq!(MemberPath::endpoint("[::1"), None);
+}
+
+#[test]
+fn of_builds_remote_path_below_user_guardian() {
+  let path = MemberPath::of("cluster", "[::1]:2552", ["manager", "singleton"]);
+  assert_eq!(path.parts().authority_endpoint(), Some(String::from("[::1]:2552")));
+  assert_eq!(path.to_relative_string(), "/user/manager/singleton");
+}
+
+#[test]
+fn rebase_replaces_authority_of_local_path() {
+  let local = ActorPath::root().child("manager");
+  let path = MemberPath::rebase("cluster", "10.0.0.2:2553", &local);
+  assert_eq!(path.parts().authority_endpoint(), Some(String::from("10.0.0.2:2553")));
+  assert_eq!(path.to_relative_string(), "/user/manager");
+  assert_eq!(MemberPath::relative_segments(&local), ["manager"]);
+}
@@ -35,4 +35,11 @@ pub enum MembershipEvent {
     /// Authority of the unreachable node.
     authority: String,
   },
+  /// Node became reachable again after being marked unreachable.
+  MarkedReachable {
+    /// Node id considered reachable again.
+    node_id:   String,
+    /// Authority of the reachable node.
+    authority: String,
+  },
 }
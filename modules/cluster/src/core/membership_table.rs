@@ -39,6 +39,14 @@ impl MembershipTable {
     }
   }
 
+  /// Rebuilds a read-only view of the table another component published as a snapshot.
+  #[must_use]
+  pub fn from_snapshot(snapshot: MembershipSnapshot, max_heartbeat_misses: u32) -> Self {
+    let mut table = Self::new(max_heartbeat_misses);
+    table.apply_delta(MembershipDelta::new(MembershipVersion::zero(), snapshot.version, snapshot.entries));
+    table
+  }
+
   /// Attempts to join the cluster with the given node and authority.
   ///
   /// # Errors
@@ -46,6 +54,21 @@ impl MembershipTable {
   /// Returns `MembershipError::AuthorityConflict` if the authority is already registered with a
   /// different node ID.
   pub fn try_join(&mut self, node_id: String, authority: String) -> Result<MembershipDelta, MembershipError> {
+    self.try_join_with_roles(node_id, authority, Vec::new())
+  }
+
+  /// Attempts to join the cluster advertising the given roles.
+  ///
+  /// # Errors
+  ///
+  /// Returns `MembershipError::AuthorityConflict` if the authority is already registered with a
+  /// different node ID.
+  pub fn try_join_with_roles(
+    &mut self,
+    node_id: String,
+    authority: String,
+    roles: Vec<String>,
+  ) -> Result<MembershipDelta, MembershipError> {
     if let Some(existing) = self.entries.get(&authority) {
       if existing.node_id != node_id {
         self.events.push(MembershipEvent::AuthorityConflict {
@@ -67,7 +90,7 @@ impl MembershipTable {
     let from = self.version;
     self.version = self.version.next();
 
-    let record = NodeRecord::new(node_id.clone(), authority.clone(), NodeStatus::Up, self.version);
+    let record = NodeRecord::new(node_id.clone(), authority.clone(), NodeStatus::Up, self.version).with_roles(roles);
     self.entries.insert(authority.clone(), record.clone());
     self.heartbeat_miss_counters.insert(authority.clone(), 0);
 
@@ -76,6 +99,24 @@ impl MembershipTable {
     Ok(MembershipDelta::new(from, self.version, vec![record]))
   }
 
+  /// Marks an active authority as `Leaving` while it hands over its work before being removed.
+  ///
+  /// Returns `None` when the authority is unknown or not active.
+  pub fn mark_leaving(&mut self, authority: &str) -> Option<MembershipDelta> {
+    let record = self.entries.get_mut(authority)?;
+    if !record.status.is_active() {
+      return None;
+    }
+
+    let from = self.version;
+    self.version = self.version.next();
+
+    record.status = NodeStatus::Leaving;
+    record.version = self.version;
+
+    Some(MembershipDelta::new(from, self.version, vec![record.clone()]))
+  }
+
   /// Marks the authority as leaving and then removed.
   ///
   /// # Errors
@@ -126,6 +167,57 @@ impl MembershipTable {
     Some(MembershipDelta::new(from, self.version, vec![record.clone()]))
   }
 
+  /// Marks the authority unreachable at once, e.g. when a failure detector suspects it.
+  ///
+  /// Returns `None` when the authority is unknown or not active.
+  pub fn mark_unreachable(&mut self, authority: &str) -> Option<MembershipDelta> {
+    let record = self.entries.get_mut(authority)?;
+    if !record.status.is_active() {
+      return None;
+    }
+
+    let from = self.version;
+    self.version = self.version.next();
+
+    record.status = NodeStatus::Unreachable;
+    record.version = self.version;
+
+    self.events.push(MembershipEvent::MarkedUnreachable {
+      node_id:   record.node_id.clone(),
+      authority: record.authority.clone(),
+    });
+
+    Some(MembershipDelta::new(from, self.version, vec![record.clone()]))
+  }
+
+  /// Brings an unreachable authority back `Up` and resets its heartbeat misses.
+  ///
+  /// Returns `None` when the authority is unknown or not unreachable.
+  pub fn mark_reachable(&mut self, authority: &str) -> Option<MembershipDelta> {
+    let record = self.entries.get_mut(authority)?;
+    if record.status != NodeStatus::Unreachable {
+      return None;
+    }
+
+    let from = self.version;
+    self.version = self.version.next();
+
+    record.status = NodeStatus::Up;
+    record.version = self.version;
+    // 到達不能から戻っただけなら年齢は変えない
+    if record.up_number == MembershipVersion::zero() {
+      record.up_number = self.version;
+    }
+    self.heartbeat_miss_counters.insert(authority.to_string(), 0);
+
+    self.events.push(MembershipEvent::MarkedReachable {
+      node_id:   record.node_id.clone(),
+      authority: record.authority.clone(),
+    });
+
+    Some(MembershipDelta::new(from, self.version, vec![record.clone()]))
+  }
+
   /// Applies a received membership delta.
   pub fn apply_delta(&mut self, delta: MembershipDelta) {
     if delta.to <= self.version {
@@ -158,6 +250,43 @@ impl MembershipTable {
     self.entries.get(authority)
   }
 
+  /// Returns `Up` members ordered from oldest to youngest.
+  ///
+  /// Age is derived from the up number, the version at which a record first became `Up`, so
+  /// reachability changes do not reorder members; ties are broken by authority so that every
+  /// node computes the same ordering. When `role` is given, only
+  /// members advertising that role are returned.
+  #[must_use]
+  pub fn up_members_by_age(&self, role: Option<&str>) -> Vec<&NodeRecord> {
+    let mut members: Vec<&NodeRecord> = self
+      .entries
+      .values()
+      .filter(|record| record.status == NodeStatus::Up)
+      .filter(|record| role.is_none_or(|role| record.has_role(role)))
+      .collect();
+    members
+      .sort_by(|left, right| left.up_number.cmp(&right.up_number).then_with(|| left.authority.cmp(&right.authority)));
+    members
+  }
+
+  /// Returns authorities of active members advertising every role in `required`.
+  #[must_use]
+  pub fn authorities_with_roles(&self, required: &[String]) -> Vec<String> {
+    self
+      .entries
+      .values()
+      .filter(|record| record.status.is_active())
+      .filter(|record| required.iter().all(|role| record.has_role(role)))
+      .map(|record| record.authority.clone())
+      .collect()
+  }
+
+  /// Returns the oldest `Up` member, optionally restricted to a role.
+  #[must_use]
+  pub fn oldest_up(&self, role: Option<&str>) -> Option<&NodeRecord> {
+    self.up_members_by_age(role).into_iter().next()
+  }
+
   /// Drains buffered events.
   pub fn drain_events(&mut self) -> Vec<MembershipEvent> {
     core::mem::take(&mut self.events)
@@ -77,6 +77,33 @@ fn leaving_transitions_to_removed_and_updates_version() {
   assert_eq!(events, vec![MembershipEvent::Left { node_id: "node-1".to_string(), authority: "n1:4050".to_string() }]);
 }
 
+#[test]
+fn leaving_member_is_no_longer_oldest() {
+  let mut table = MembershipTable::new(3);
+  table.try_join("node-1".to_string(), "n1:4050".to_string()).expect("join succeeds");
+  table.try_join("node-2".to_string(), "n2:4050".to_string()).expect("join succeeds");
+
+  let delta = table.mark_leaving("n1:4050").expect("leaving");
+
+  assert_eq!(delta.entries[0].status, NodeStatus::Leaving);
+  assert_eq!(table.oldest_up(None).map(|record| record.authority.as_str()), Some("n2:4050"));
+  assert!(table.mark_leaving("n1:4050").is_none());
+  assert!(table.mark_leaving("unknown:1").is_none());
+}
+
+#[test]
+fn snapshot_rebuilds_the_same_view() {
+  let mut table = MembershipTable::new(3);
+  table.try_join("node-1".to_string(), "n1:4050".to_string()).expect("join succeeds");
+  table.try_join("node-2".to_string(), "n2:4050".to_string()).expect("join succeeds");
+  table.mark_unreachable("n1:4050");
+
+  let rebuilt = MembershipTable::from_snapshot(table.snapshot(), 3);
+
+  assert_eq!(rebuilt.snapshot(), table.snapshot());
+  assert_eq!(rebuilt.oldest_up(None).map(|record| record.authority.as_str()), Some("n2:4050"));
+}
+
 #[test]
 fn heartbeat_miss_marks_unreachable_after_threshold() {
   let mut table = MembershipTable::new(2);
@@ -100,3 +127,81 @@ fn heartbeat_miss_marks_unreachable_after_threshold() {
     authority: "n1:4050".to_string(),
   }],);
 }
+
+#[test]
+fn oldest_up_follows_join_order_and_role() {
+  let mut table = MembershipTable::new(3);
+  table.try_join("node-1".to_string(), "n1:4050".to_string()).expect("join");
+  let delta = table.try_join("node-2".to_string(), "n2:4051".to_string()).expect("join");
+
+  assert_eq!(table.oldest_up(None).map(|record| record.authority.as_str()), Some("n1:4050"));
+  assert_eq!(table.oldest_up(Some("backend")), None);
+
+  let record = delta.entries[0].clone().with_roles(vec!["backend".to_string()]);
+  let next = table.version().next();
+  table.apply_delta(crate::core::membership_delta::MembershipDelta::new(table.version(), next, vec![record]));
+  assert_eq!(table.oldest_up(Some("backend")).map(|record| record.authority.as_str()), Some("n2:4051"));
+
+  table.mark_left("n1:4050").expect("left");
+  let ordered: Vec<_> = table.up_members_by_age(None).into_iter().map(|record| record.authority.clone()).collect();
+  assert_eq!(ordered, vec!["n2:4051".to_string()]);
+}
+
+#[test]
+fn flapping_oldest_member_keeps_its_age() {
+  let mut table = MembershipTable::new(3);
+  table.try_join("node-1".to_string(), "n1:4050".to_string()).expect("join");
+  table.try_join("node-2".to_string(), "n2:4051".to_string()).expect("join");
+  table.try_join("node-3".to_string(), "n3:4052".to_string()).expect("join");
+
+  table.mark_unreachable("n1:4050").expect("unreachable");
+  assert_eq!(table.oldest_up(None).map(|record| record.authority.as_str()), Some("n2:4051"));
+  let delta = table.mark_reachable("n1:4050").expect("reachable");
+
+  assert_eq!(table.oldest_up(None).map(|record| record.authority.as_str()), Some("n1:4050"));
+  assert_eq!(delta.entries[0].up_number, MembershipVersion::new(1));
+  assert_eq!(delta.entries[0].version, MembershipVersion::new(5));
+  let ordered: Vec<_> = table.up_members_by_age(None).into_iter().map(|record| record.authority.clone()).collect();
+  assert_eq!(ordered, vec!["n1:4050".to_string(), "n2:4051".to_string(), "n3:4052".to_string()]);
+}
+
+#[test]
+fn authorities_with_roles_requires_every_role() {
+  let mut table = MembershipTable::new(3);
+  table
+    .try_join_with_roles("node-1".to_string(), "n1:4050".to_string(), vec!["payments".to_string(), "eu".to_string()])
+    .expect("join");
+  table.try_join_with_roles("node-2".to_string(), "n2:4051".to_string(), vec!["payments".to_string()]).expect("join");
+  table.try_join("node-3".to_string(), "n3:4052".to_string()).expect("join");
+
+  assert_eq!(table.authorities_with_roles(&["payments".to_string()]), vec![
+    "n1:4050".to_string(),
+    "n2:4051".to_string()
+  ]);
+  assert_eq!(table.authorities_with_roles(&["payments".to_string(), "eu".to_string()]), vec!["n1:4050".to_string()]);
+  assert_eq!(table.authorities_with_roles(&[]).len(), 3);
+  assert_eq!(table.snapshot().entries[0].roles, vec!["payments".to_string(), "eu".to_string()]);
+}
+
+#[test]
+fn failure_detector_verdicts_toggle_reachability() {
+  let mut table = MembershipTable::new(3);
+  table.try_join("node-1".to_string(), "n1:4050".to_string()).expect("join succeeds");
+  table.drain_events();
+
+  assert!(table.mark_reachable("n1:4050").is_none());
+  let delta = table.mark_unreachable("n1:4050").expect("suspect marks unreachable");
+  assert_eq!(delta.to, MembershipVersion::new(2));
+  assert_eq!(delta.entries[0].status, NodeStatus::Unreachable);
+  assert!(table.mark_unreachable("n1:4050").is_none());
+
+  let delta = table.mark_reachable("n1:4050").expect("recovery marks up");
+  assert_eq!(delta.from, MembershipVersion::new(2));
+  assert_eq!(delta.entries[0].status, NodeStatus::Up);
+
+  let events = table.drain_events();
+  assert_eq!(events, vec![
+    MembershipEvent::MarkedUnreachable { node_id: "node-1".to_string(), authority: "n1:4050".to_string() },
+    MembershipEvent::MarkedReachable { node_id: "node-1".to_string(), authority: "n1:4050".to_string() },
+  ]);
+}
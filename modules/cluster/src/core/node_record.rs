@@ -1,6 +1,6 @@
 //! Node record stored in the membership table.
 
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 
 use crate::core::{membership_version::MembershipVersion, node_status::NodeStatus};
 
@@ -15,12 +15,42 @@ pub struct NodeRecord {
   pub status:    NodeStatus,
   /// Version the record was last updated at.
   pub version:   MembershipVersion,
+  /// Version at which the node first became `Up`, or zero while it never was.
+  ///
+  /// Unlike [`version`](Self::version) it does not change on later status changes, so it orders
+  /// members by age.
+  pub up_number: MembershipVersion,
+  /// Roles advertised by the node (e.g. `payments`, `frontend`).
+  pub roles:     Vec<String>,
 }
 
 impl NodeRecord {
   /// Creates a new record with the given parameters.
+  ///
+  /// A record created `Up` takes `version` as its up number.
   #[must_use]
   pub const fn new(node_id: String, authority: String, status: NodeStatus, version: MembershipVersion) -> Self {
-    Self { node_id, authority, status, version }
+    let up_number = if matches!(status, NodeStatus::Up) { version } else { MembershipVersion::zero() };
+    Self { node_id, authority, status, version, up_number, roles: Vec::new() }
+  }
+
+  /// Replaces the up number, e.g. when decoding a record received from a peer.
+  #[must_use]
+  pub const fn with_up_number(mut self, up_number: MembershipVersion) -> Self {
+    self.up_number = up_number;
+    self
+  }
+
+  /// Replaces the advertised roles.
+  #[must_use]
+  pub fn with_roles(mut self, roles: Vec<String>) -> Self {
+    self.roles = roles;
+    self
+  }
+
+  /// Returns true when the node advertises the given role.
+  #[must_use]
+  pub fn has_role(&self, role: &str) -> bool {
+    self.roles.iter().any(|candidate| candidate == role)
   }
 }
@@ -1,11 +1,20 @@
 //! Partition-based identity lookup using distributed hashing.
 
-use alloc::{string::String, vec::Vec};
+use alloc::{
+  boxed::Box,
+  collections::BTreeMap,
+  string::{String, ToString},
+  vec::Vec,
+};
 
 use crate::core::{
-  activated_kind::ActivatedKind, grain_key::GrainKey, identity_lookup::IdentityLookup,
-  identity_setup_error::IdentitySetupError, partition_identity_lookup_config::PartitionIdentityLookupConfig,
-  pid_cache_event::PidCacheEvent, virtual_actor_event::VirtualActorEvent, virtual_actor_registry::VirtualActorRegistry,
+  activated_kind::ActivatedKind, grain_handoff::GrainHandoff, grain_key::GrainKey,
+  grain_lifecycle_signal::GrainLifecycleSignal, grain_state_provider::GrainStateProvider,
+  handoff_completion::HandoffCompletion, identity_lookup::IdentityLookup, identity_setup_error::IdentitySetupError,
+  partition_identity_lookup_config::PartitionIdentityLookupConfig, pid_cache_event::PidCacheEvent,
+  rebalance_coordinator::RebalanceCoordinator, rendezvous_hasher::RendezvousHasher,
+  serialized_message::SerializedMessage, virtual_actor_event::VirtualActorEvent,
+  virtual_actor_registry::VirtualActorRegistry,
 };
 
 #[cfg(test)]
@@ -17,6 +26,9 @@ mod tests;
 /// to select owner nodes. All methods that modify state use `&mut self`,
 /// and callers should wrap the instance in `ToolboxMutex<Box<dyn IdentityLookup>>`
 /// for thread-safe access.
+///
+/// When the topology changes, activations whose rendezvous owner moved are handed off to
+/// the new owner through a [`RebalanceCoordinator`].
 pub struct PartitionIdentityLookup {
   /// Virtual actor registry for activation management (includes PidCache).
   registry:     VirtualActorRegistry,
@@ -26,6 +38,10 @@ pub struct PartitionIdentityLookup {
   member_kinds: Vec<ActivatedKind>,
   /// Registered activated kinds for client mode.
   client_kinds: Vec<ActivatedKind>,
+  /// Roles advertised by each authority.
+  member_roles: BTreeMap<String, Vec<String>>,
+  /// Coordinator moving activations whose owner changed.
+  rebalancer:   RebalanceCoordinator,
   /// Configuration parameters.
   config:       PartitionIdentityLookupConfig,
 }
@@ -36,11 +52,15 @@ impl PartitionIdentityLookup {
   pub const fn new(config: PartitionIdentityLookupConfig) -> Self {
     let cache_capacity = config.cache_capacity();
     let pid_ttl_secs = config.pid_ttl_secs();
+    let rebalancer = RebalanceCoordinator::new(config.max_concurrent_handoffs(), config.handoff_buffer_capacity())
+      .with_handoff_timeout_secs(config.handoff_timeout_secs());
     Self {
       registry: VirtualActorRegistry::new(cache_capacity, pid_ttl_secs),
       authorities: Vec::new(),
       member_kinds: Vec::new(),
       client_kinds: Vec::new(),
+      member_roles: BTreeMap::new(),
+      rebalancer,
       config,
     }
   }
@@ -51,6 +71,13 @@ impl PartitionIdentityLookup {
     Self::new(PartitionIdentityLookupConfig::default())
   }
 
+  /// Installs the provider used to save and restore grain state across activations.
+  #[must_use]
+  pub fn with_state_provider(mut self, provider: Box<dyn GrainStateProvider>) -> Self {
+    self.registry.set_state_provider(provider);
+    self
+  }
+
   /// Returns the current authority list.
   #[must_use]
   #[allow(clippy::missing_const_for_fn)]
@@ -71,22 +98,56 @@ impl PartitionIdentityLookup {
     &self.member_kinds
   }
 
+  /// Returns the authorities allowed to host the kind of `key`.
+  ///
+  /// Keys without a kind prefix, or kinds without role requirements, may be placed on any
+  /// authority.
+  #[must_use]
+  pub fn eligible_authorities(&self, key: &GrainKey) -> Vec<String> {
+    let kind = key
+      .kind()
+      .and_then(|name| self.member_kinds.iter().chain(self.client_kinds.iter()).find(|kind| kind.name() == name));
+    match kind {
+      | Some(kind) if !kind.required_roles().is_empty() => self
+        .authorities
+        .iter()
+        .filter(|authority| self.member_roles.get(authority.as_str()).is_some_and(|roles| kind.accepts_roles(roles)))
+        .cloned()
+        .collect(),
+      | _ => self.authorities.clone(),
+    }
+  }
+
   /// Returns the registered client kinds.
   #[must_use]
   #[allow(clippy::missing_const_for_fn)]
   pub fn client_kinds(&self) -> &[ActivatedKind] {
     &self.client_kinds
   }
+
+  /// Returns the number of handoffs queued or in flight.
+  #[must_use]
+  pub fn pending_handoffs(&self) -> usize {
+    self.rebalancer.queued_len() + self.rebalancer.in_flight_len()
+  }
+
+  fn register_policies(&mut self, kinds: &[ActivatedKind]) {
+    for kind in kinds {
+      self.registry.set_passivation_policy(kind.name(), *kind.passivation_policy());
+    }
+  }
 }
 
 impl IdentityLookup for PartitionIdentityLookup {
   fn setup_member(&mut self, kinds: &[ActivatedKind]) -> Result<(), IdentitySetupError> {
     self.member_kinds = kinds.to_vec();
+    self.register_policies(kinds);
     Ok(())
   }
 
   fn setup_client(&mut self, kinds: &[ActivatedKind]) -> Result<(), IdentitySetupError> {
     self.client_kinds = kinds.to_vec();
+    self.register_policies(kinds);
     Ok(())
   }
 
@@ -98,7 +159,8 @@ impl IdentityLookup for PartitionIdentityLookup {
 
     // Step 2: VirtualActorRegistry 経由でアクティベーションを確保
     // snapshot_required は false、snapshot は None で基本的なルックアップを行う
-    match self.registry.ensure_activation(key, &self.authorities, now, false, None) {
+    let authorities = self.eligible_authorities(key);
+    match self.registry.ensure_activation(key, &authorities, now, false, None) {
       | Ok(pid) => Some(pid),
       | Err(_) => None,
     }
@@ -113,9 +175,26 @@ impl IdentityLookup for PartitionIdentityLookup {
     self.registry.invalidate_absent_authorities(&authorities);
     // 内部の authority リストを更新
     self.authorities = authorities;
+    // 所有者が変わったアクティベーションを新しい所有者へ移す
+    let moves: Vec<GrainHandoff> = self
+      .registry
+      .activation_owners()
+      .into_iter()
+      .filter_map(|(key, from)| {
+        let to = RendezvousHasher::select(&self.eligible_authorities(&key), &key)?.clone();
+        (to != from).then_some(GrainHandoff { key, from, to })
+      })
+      .collect();
+    self.rebalancer.enqueue(moves);
+    self.rebalancer.start_ready(&mut self.registry);
+  }
+
+  fn update_member_roles(&mut self, authority: &str, roles: Vec<String>) {
+    self.member_roles.insert(authority.to_string(), roles);
   }
 
   fn on_member_left(&mut self, authority: &str) {
+    self.member_roles.remove(authority);
     // 指定された authority のエントリをすべて無効化
     self.registry.invalidate_authority(authority);
   }
@@ -124,8 +203,30 @@ impl IdentityLookup for PartitionIdentityLookup {
     self.registry.passivate_idle(now, idle_ttl);
   }
 
+  fn complete_deactivation(&mut self, key: &GrainKey, state: Option<Vec<u8>>) {
+    self.registry.complete_deactivation(key, state);
+  }
+
+  fn buffer_during_handoff(&mut self, key: &GrainKey, message: SerializedMessage) -> Option<SerializedMessage> {
+    self.rebalancer.buffer_if_moving(key, message)
+  }
+
+  fn complete_handoff(&mut self, key: &GrainKey, state: Option<Vec<u8>>, now: u64) -> Option<HandoffCompletion> {
+    self.rebalancer.complete_handoff(&mut self.registry, key, state, now)
+  }
+
+  fn expire_handoffs(&mut self, now: u64) -> Vec<HandoffCompletion> {
+    self.rebalancer.expire_handoffs(&mut self.registry, &self.authorities, now)
+  }
+
+  fn drain_lifecycle_signals(&mut self) -> Vec<GrainLifecycleSignal> {
+    self.registry.drain_lifecycle_signals()
+  }
+
   fn drain_events(&mut self) -> Vec<VirtualActorEvent> {
-    self.registry.drain_events()
+    let mut events = self.registry.drain_events();
+    events.extend(self.rebalancer.drain_events());
+    events
   }
 
   fn drain_cache_events(&mut self) -> Vec<PidCacheEvent> {
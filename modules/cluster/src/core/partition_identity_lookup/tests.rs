@@ -5,7 +5,7 @@ use alloc::{string::ToString, vec};
 use crate::core::{
   activated_kind::ActivatedKind, grain_key::GrainKey, identity_lookup::IdentityLookup,
   partition_identity_lookup::PartitionIdentityLookup, partition_identity_lookup_config::PartitionIdentityLookupConfig,
-  virtual_actor_event::VirtualActorEvent,
+  passivation_policy::PassivationPolicy, serialized_message::SerializedMessage, virtual_actor_event::VirtualActorEvent,
 };
 
 // ============================================================================
@@ -160,7 +160,7 @@ fn test_setup_member_overwrites_previous_kinds() {
 fn test_get_returns_none_without_authorities() {
   // authorities がない場合は None を返すことを検証
   let mut lookup = PartitionIdentityLookup::with_defaults();
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
 
   let result = lookup.get(&key, now);
@@ -174,14 +174,14 @@ fn test_get_activates_and_returns_pid_with_authorities() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
 
   let result = lookup.get(&key, now);
 
   assert!(result.is_some());
   let pid = result.unwrap();
-  assert!(pid.contains("user/123"));
+  assert!(pid.contains("user:123"));
 }
 
 #[test]
@@ -190,7 +190,7 @@ fn test_get_cache_hit_returns_same_pid() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
 
   let pid1 = lookup.get(&key, now).unwrap();
@@ -205,7 +205,7 @@ fn test_get_generates_activated_event_on_first_call() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
 
   let _ = lookup.get(&key, now);
@@ -222,7 +222,7 @@ fn test_get_generates_hit_event_on_subsequent_calls() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
 
   // 初回: Activated イベント
@@ -232,7 +232,7 @@ fn test_get_generates_hit_event_on_subsequent_calls() {
   // キャッシュを無効化して ensure_activation を再度通過させる
   // （通常は TTL 経過後や topology 変更時に発生）
   // ここではキャッシュを手動で無効化できないため、新しいキーでテスト
-  let key2 = GrainKey::new("user/456".to_string());
+  let key2 = GrainKey::new("user:456".to_string());
   let _ = lookup.get(&key2, now);
   let events = lookup.drain_events();
 
@@ -250,7 +250,7 @@ fn test_remove_pid_removes_activation() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
 
   // アクティベーションを作成
@@ -285,7 +285,7 @@ fn test_remove_pid_invalidates_cache() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
 
   // アクティベーションを作成
@@ -339,7 +339,7 @@ fn test_update_topology_invalidates_absent_authorities() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
   let _ = lookup.get(&key, now);
   let _ = lookup.drain_events();
@@ -362,7 +362,7 @@ fn test_on_member_left_invalidates_authority_entries() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
   let _ = lookup.get(&key, now);
   let _ = lookup.drain_events();
@@ -381,7 +381,7 @@ fn test_on_member_left_with_unknown_authority_does_nothing() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
   let _ = lookup.get(&key, now);
   let _ = lookup.drain_events();
@@ -404,7 +404,7 @@ fn test_passivate_idle_removes_expired_activations() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
   let _ = lookup.get(&key, now);
   let _ = lookup.drain_events();
@@ -424,7 +424,7 @@ fn test_passivate_idle_keeps_recent_activations() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
   let _ = lookup.get(&key, now);
   let _ = lookup.drain_events();
@@ -448,7 +448,7 @@ fn test_drain_events_returns_and_clears_events() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
   let _ = lookup.get(&key, now);
 
@@ -467,7 +467,7 @@ fn test_drain_cache_events_returns_cache_events_on_invalidation() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
   let _ = lookup.get(&key, now);
 
@@ -485,7 +485,7 @@ fn test_drain_cache_events_clears_after_drain() {
   let mut lookup = PartitionIdentityLookup::with_defaults();
   lookup.update_topology(vec!["node1:8080".to_string()]);
 
-  let key = GrainKey::new("user/123".to_string());
+  let key = GrainKey::new("user:123".to_string());
   let now = 1000;
   let _ = lookup.get(&key, now);
 
@@ -500,3 +500,95 @@ fn test_drain_cache_events_clears_after_drain() {
   let events2 = lookup.drain_cache_events();
   assert!(events2.is_empty());
 }
+
+// ============================================================================
+// ロール制約付き配置
+// ============================================================================
+
+#[test]
+fn test_get_places_kind_only_on_members_with_required_roles() {
+  let mut lookup = PartitionIdentityLookup::with_defaults();
+  let payments = ActivatedKind::new("payments").with_required_roles(vec!["payments".to_string()]);
+  lookup.setup_member(&[payments, ActivatedKind::new("user")]).expect("setup");
+  lookup.update_topology(vec!["a:1".to_string(), "b:2".to_string(), "c:3".to_string()]);
+  lookup.update_member_roles("b:2", vec!["payments".to_string()]);
+  lookup.update_member_roles("c:3", vec!["frontend".to_string()]);
+
+  for id in 0..16 {
+    let key = GrainKey::new(alloc::format!("payments:{id}"));
+    let pid = lookup.get(&key, 0).expect("pid");
+    assert!(pid.starts_with("b:2::"), "unexpected placement {pid}");
+  }
+
+  // ロール制約のない kind はすべてのメンバーに配置され得る
+  let eligible = lookup.eligible_authorities(&GrainKey::new("user:1".to_string()));
+  assert_eq!(eligible.len(), 3);
+}
+
+#[test]
+fn test_get_returns_none_when_no_member_has_required_roles() {
+  let mut lookup = PartitionIdentityLookup::with_defaults();
+  let payments = ActivatedKind::new("payments").with_required_roles(vec!["payments".to_string()]);
+  lookup.setup_member(&[payments]).expect("setup");
+  lookup.update_topology(vec!["a:1".to_string()]);
+  lookup.update_member_roles("a:1", vec!["frontend".to_string()]);
+
+  assert!(lookup.get(&GrainKey::new("payments:1".to_string()), 0).is_none());
+
+  lookup.update_member_roles("a:1", vec!["payments".to_string()]);
+  assert!(lookup.get(&GrainKey::new("payments:1".to_string()), 0).is_some());
+}
+
+#[test]
+fn setup_member_applies_kind_passivation_policies() {
+  let mut lookup = PartitionIdentityLookup::with_defaults();
+  let kind = ActivatedKind::new("session").with_passivation_policy(PassivationPolicy::new().with_idle_timeout_secs(5));
+  lookup.setup_member(&[kind]).expect("setup");
+  lookup.update_topology(vec!["node1:8080".to_string()]);
+  let key = GrainKey::new("session:1".to_string());
+  lookup.get(&key, 0).expect("activation");
+  lookup.drain_events();
+
+  lookup.passivate_idle(6, 3600);
+
+  assert_eq!(lookup.drain_events(), vec![VirtualActorEvent::Passivated { key }]);
+  assert_eq!(lookup.drain_lifecycle_signals().len(), 2);
+}
+
+#[test]
+fn update_topology_hands_off_activations_whose_owner_changed() {
+  let config = PartitionIdentityLookupConfig::default().with_max_concurrent_handoffs(64);
+  let mut lookup = PartitionIdentityLookup::new(config);
+  lookup.update_topology(vec!["a:1".to_string()]);
+  let keys: alloc::vec::Vec<GrainKey> = (0..16).map(|id| GrainKey::new(alloc::format!("user:{id}"))).collect();
+  for key in &keys {
+    lookup.get(key, 0).expect("activation");
+  }
+  lookup.drain_events();
+
+  lookup.update_topology(vec!["a:1".to_string(), "b:2".to_string()]);
+
+  let moved: alloc::vec::Vec<GrainKey> = lookup
+    .drain_events()
+    .into_iter()
+    .filter_map(|event| match event {
+      | VirtualActorEvent::HandoffStarted { key, from, to } => {
+        assert_eq!((from.as_str(), to.as_str()), ("a:1", "b:2"));
+        Some(key)
+      },
+      | _ => None,
+    })
+    .collect();
+  assert!(!moved.is_empty() && moved.len() < keys.len());
+  assert_eq!(lookup.pending_handoffs(), moved.len());
+
+  let key = &moved[0];
+  let message = SerializedMessage::new(vec![1], 1);
+  assert!(lookup.get(key, 1).is_none());
+  assert!(lookup.buffer_during_handoff(key, message.clone()).is_none());
+
+  let completion = lookup.complete_handoff(key, None, 2).expect("completion");
+  assert!(completion.pid.starts_with("b:2::"));
+  assert_eq!(completion.buffered, vec![message]);
+  assert_eq!(lookup.get(key, 3), Some(completion.pid));
+}
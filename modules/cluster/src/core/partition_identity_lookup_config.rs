@@ -3,15 +3,25 @@
 #[cfg(test)]
 mod tests;
 
+const DEFAULT_MAX_CONCURRENT_HANDOFFS: usize = 16;
+const DEFAULT_HANDOFF_BUFFER_CAPACITY: usize = 1000;
+const DEFAULT_HANDOFF_TIMEOUT_SECS: u64 = 30;
+
 /// Configuration for the partition identity lookup component.
 #[derive(Debug, Clone)]
 pub struct PartitionIdentityLookupConfig {
   /// Maximum number of entries in the PID cache.
-  cache_capacity: usize,
+  cache_capacity:          usize,
   /// Time-to-live for cached PIDs in seconds.
-  pid_ttl_secs:   u64,
+  pid_ttl_secs:            u64,
   /// Time-to-live for idle activations in seconds.
-  idle_ttl_secs:  u64,
+  idle_ttl_secs:           u64,
+  /// Maximum number of grain handoffs in flight during rebalancing.
+  max_concurrent_handoffs: usize,
+  /// Maximum number of requests buffered per grain while it is handed off.
+  handoff_buffer_capacity: usize,
+  /// Seconds after which a handoff is force-completed if the old owner never reports.
+  handoff_timeout_secs:    u64,
 }
 
 impl PartitionIdentityLookupConfig {
@@ -24,7 +34,35 @@ impl PartitionIdentityLookupConfig {
   /// * `idle_ttl_secs` - Time-to-live for idle activations in seconds
   #[must_use]
   pub const fn new(cache_capacity: usize, pid_ttl_secs: u64, idle_ttl_secs: u64) -> Self {
-    Self { cache_capacity, pid_ttl_secs, idle_ttl_secs }
+    Self {
+      cache_capacity,
+      pid_ttl_secs,
+      idle_ttl_secs,
+      max_concurrent_handoffs: DEFAULT_MAX_CONCURRENT_HANDOFFS,
+      handoff_buffer_capacity: DEFAULT_HANDOFF_BUFFER_CAPACITY,
+      handoff_timeout_secs: DEFAULT_HANDOFF_TIMEOUT_SECS,
+    }
+  }
+
+  /// Sets the maximum number of grain handoffs in flight during rebalancing.
+  #[must_use]
+  pub const fn with_max_concurrent_handoffs(mut self, max: usize) -> Self {
+    self.max_concurrent_handoffs = max;
+    self
+  }
+
+  /// Sets the maximum number of requests buffered per grain while it is handed off.
+  #[must_use]
+  pub const fn with_handoff_buffer_capacity(mut self, capacity: usize) -> Self {
+    self.handoff_buffer_capacity = capacity;
+    self
+  }
+
+  /// Sets the seconds after which a handoff is force-completed if the old owner never reports.
+  #[must_use]
+  pub const fn with_handoff_timeout_secs(mut self, secs: u64) -> Self {
+    self.handoff_timeout_secs = secs;
+    self
   }
 
   /// Returns the cache capacity.
@@ -44,14 +82,35 @@ impl PartitionIdentityLookupConfig {
   pub const fn idle_ttl_secs(&self) -> u64 {
     self.idle_ttl_secs
   }
+
+  /// Returns the maximum number of concurrent handoffs.
+  #[must_use]
+  pub const fn max_concurrent_handoffs(&self) -> usize {
+    self.max_concurrent_handoffs
+  }
+
+  /// Returns the handoff buffer capacity per grain.
+  #[must_use]
+  pub const fn handoff_buffer_capacity(&self) -> usize {
+    self.handoff_buffer_capacity
+  }
+
+  /// Returns the handoff timeout in seconds.
+  #[must_use]
+  pub const fn handoff_timeout_secs(&self) -> u64 {
+    self.handoff_timeout_secs
+  }
 }
 
 impl Default for PartitionIdentityLookupConfig {
   fn default() -> Self {
     Self {
-      cache_capacity: 1024,
-      pid_ttl_secs:   300,  // 5 minutes
-      idle_ttl_secs:  3600, // 1 hour
+      cache_capacity:          1024,
+      pid_ttl_secs:            300,  // 5 minutes
+      idle_ttl_secs:           3600, // 1 hour
+      max_concurrent_handoffs: DEFAULT_MAX_CONCURRENT_HANDOFFS,
+      handoff_buffer_capacity: DEFAULT_HANDOFF_BUFFER_CAPACITY,
+      handoff_timeout_secs:    DEFAULT_HANDOFF_TIMEOUT_SECS,
     }
   }
 }
@@ -80,3 +80,17 @@ fn max_values_are_allowed() {
   assert_eq!(config.pid_ttl_secs(), u64::MAX);
   assert_eq!(config.idle_ttl_secs(), u64::MAX);
 }
+
+#[test]
+fn handoff_settings_have_defaults_and_builders() {
+  // ハンドオフ設定のデフォルト値とビルダーを確認
+  let config = PartitionIdentityLookupConfig::default();
+  assert_eq!(config.max_concurrent_handoffs(), 16);
+  assert_eq!(config.handoff_buffer_capacity(), 1000);
+  assert_eq!(config.handoff_timeout_secs(), 30);
+
+  let config = config.with_max_concurrent_handoffs(4).with_handoff_buffer_capacity(32).with_handoff_timeout_secs(5);
+  assert_eq!(config.max_concurrent_handoffs(), 4);
+  assert_eq!(config.handoff_buffer_capacity(), 32);
+  assert_eq!(config.handoff_timeout_secs(), 5);
+}
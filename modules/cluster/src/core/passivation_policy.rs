@@ -0,0 +1,59 @@
+//! Per-kind passivation policy for grains.
+
+#[cfg(test)]
+mod tests;
+
+/// Controls when activations of a kind are passivated.
+///
+/// Activations idle for longer than the idle timeout are passivated by
+/// [`VirtualActorRegistry::passivate_idle`](crate::core::VirtualActorRegistry::passivate_idle).
+/// When a node would host more than `max_active_per_node` activations of the kind, the least
+/// recently used one is evicted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PassivationPolicy {
+  idle_timeout_secs:   Option<u64>,
+  max_active_per_node: Option<usize>,
+}
+
+impl PassivationPolicy {
+  /// Creates a policy that falls back to the registry-wide idle TTL and sets no capacity limit.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { idle_timeout_secs: None, max_active_per_node: None }
+  }
+
+  /// Overrides the idle timeout for this kind.
+  #[must_use]
+  pub const fn with_idle_timeout_secs(mut self, secs: u64) -> Self {
+    self.idle_timeout_secs = Some(secs);
+    self
+  }
+
+  /// Limits the number of activations per node, evicting the least recently used one.
+  #[must_use]
+  pub const fn with_max_active_per_node(mut self, max: usize) -> Self {
+    self.max_active_per_node = Some(max);
+    self
+  }
+
+  /// Returns the idle timeout override.
+  #[must_use]
+  pub const fn idle_timeout_secs(&self) -> Option<u64> {
+    self.idle_timeout_secs
+  }
+
+  /// Returns the per-node activation limit.
+  #[must_use]
+  pub const fn max_active_per_node(&self) -> Option<usize> {
+    self.max_active_per_node
+  }
+
+  /// Returns the idle timeout to apply, falling back to `default_secs`.
+  #[must_use]
+  pub const fn effective_idle_timeout(&self, default_secs: u64) -> u64 {
+    match self.idle_timeout_secs {
+      | Some(secs) => secs,
+      | None => default_secs,
+    }
+  }
+}
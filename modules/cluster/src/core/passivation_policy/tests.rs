@@ -0,0 +1,16 @@
+use super::PassivationPolicy;
+
+#[test]
+fn default_policy_uses_fallback_idle_timeout() {
+  let policy = PassivationPolicy::new();
+  assert_eq!(policy.idle_timeout_secs(), None);
+  assert_eq!(policy.max_active_per_node(), None);
+  assert_eq!(policy.effective_idle_timeout(30), 30);
+}
+
+#[test]
+fn builder_overrides_idle_timeout_and_capacity() {
+  let policy = PassivationPolicy::new().with_idle_timeout_secs(5).with_max_active_per_node(2);
+  assert_eq!(policy.effective_idle_timeout(30), 5);
+  assert_eq!(policy.max_active_per_node(), Some(2));
+}
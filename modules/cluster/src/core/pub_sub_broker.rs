@@ -144,6 +144,26 @@ impl PubSubBroker {
     Ok(entry.subscribers.iter().cloned().collect())
   }
 
+  /// Like [`publish`](Self::publish) but keeps only subscribers hosted on `authorities`.
+  ///
+  /// Subscribers are matched by their `authority::` PID prefix, so role-restricted delivery
+  /// can pass [`MembershipTable::authorities_with_roles`](crate::core::MembershipTable::authorities_with_roles).
+  ///
+  /// # Errors
+  ///
+  /// Returns the same errors as [`publish`](Self::publish).
+  pub fn publish_to_authorities(&mut self, topic: &str, authorities: &[String]) -> Result<Vec<String>, PubSubError> {
+    let subscribers = self.publish(topic)?;
+    Ok(
+      subscribers
+        .into_iter()
+        .filter(|subscriber| {
+          subscriber.split_once("::").is_some_and(|(authority, _)| authorities.iter().any(|a| a == authority))
+        })
+        .collect(),
+    )
+  }
+
   /// Marks or clears partition state. Returns the number of flushed queued messages when recovered.
   ///
   /// # Errors
@@ -164,3 +164,15 @@ fn metrics_snapshot_is_emitted_and_counters_reset() {
   assert_eq!(metrics_after.dropped_messages, 0);
   assert_eq!(metrics_after.redelivered_messages, 0);
 }
+
+#[test]
+fn publish_to_authorities_filters_subscribers_by_host() {
+  let mut broker = PubSubBroker::new();
+  broker.create_topic("orders".to_string()).expect("topic");
+  broker.subscribe("orders", "a:1::sub-1".to_string()).expect("subscribe");
+  broker.subscribe("orders", "b:2::sub-2".to_string()).expect("subscribe");
+
+  let subscribers = broker.publish_to_authorities("orders", &["b:2".to_string()]).expect("publish");
+
+  assert_eq!(subscribers, vec!["b:2::sub-2".to_string()]);
+}
@@ -0,0 +1,211 @@
+//! Coordinates grain handoffs when the rendezvous owner of activations changes.
+
+use alloc::{
+  collections::{BTreeMap, VecDeque},
+  string::String,
+  vec::Vec,
+};
+
+use crate::core::{
+  grain_handoff::GrainHandoff, grain_key::GrainKey, handoff_completion::HandoffCompletion,
+  serialized_message::SerializedMessage, virtual_actor_event::VirtualActorEvent,
+  virtual_actor_registry::VirtualActorRegistry,
+};
+
+#[cfg(test)]
+mod tests;
+
+struct InFlightHandoff {
+  handoff:    GrainHandoff,
+  buffered:   VecDeque<SerializedMessage>,
+  started_at: Option<u64>,
+}
+
+/// Moves activations to their new owners with bounded concurrency.
+///
+/// A started handoff deactivates the grain on the old owner via
+/// [`VirtualActorRegistry::begin_handoff`]. Requests addressed to the grain are buffered until
+/// the old owner reports the drained state through [`complete_handoff`](Self::complete_handoff),
+/// at which point the grain is activated on the new owner and the buffered requests are
+/// handed back for delivery. Handoffs whose old owner left the cluster or did not report
+/// within the timeout are force-completed by [`expire_handoffs`](Self::expire_handoffs).
+pub struct RebalanceCoordinator {
+  max_concurrent_handoffs: usize,
+  buffer_capacity:         usize,
+  handoff_timeout_secs:    u64,
+  queued:                  VecDeque<GrainHandoff>,
+  in_flight:               BTreeMap<GrainKey, InFlightHandoff>,
+  events:                  Vec<VirtualActorEvent>,
+}
+
+impl RebalanceCoordinator {
+  /// Creates a new coordinator.
+  ///
+  /// # Arguments
+  ///
+  /// * `max_concurrent_handoffs` - Maximum number of handoffs in flight at once (at least 1)
+  /// * `buffer_capacity` - Maximum number of requests buffered per moving grain
+  #[must_use]
+  pub const fn new(max_concurrent_handoffs: usize, buffer_capacity: usize) -> Self {
+    Self {
+      max_concurrent_handoffs: if max_concurrent_handoffs == 0 { 1 } else { max_concurrent_handoffs },
+      buffer_capacity,
+      handoff_timeout_secs: u64::MAX,
+      queued: VecDeque::new(),
+      in_flight: BTreeMap::new(),
+      events: Vec::new(),
+    }
+  }
+
+  /// Sets the seconds after which an unreported handoff is force-completed.
+  #[must_use]
+  pub const fn with_handoff_timeout_secs(mut self, secs: u64) -> Self {
+    self.handoff_timeout_secs = secs;
+    self
+  }
+
+  /// Queues handoffs; keys already queued or in flight are ignored.
+  pub fn enqueue(&mut self, handoffs: Vec<GrainHandoff>) {
+    for handoff in handoffs {
+      let known =
+        self.in_flight.contains_key(&handoff.key) || self.queued.iter().any(|queued| queued.key == handoff.key);
+      if !known {
+        self.queued.push_back(handoff);
+      }
+    }
+  }
+
+  /// Starts queued handoffs up to the concurrency limit and returns the started ones.
+  pub fn start_ready(&mut self, registry: &mut VirtualActorRegistry) -> Vec<GrainHandoff> {
+    let mut started = Vec::new();
+    while self.in_flight.len() < self.max_concurrent_handoffs {
+      let Some(handoff) = self.queued.pop_front() else {
+        break;
+      };
+      // 既に停止済みのアクティベーションは移動対象にしない
+      if !registry.begin_handoff(&handoff.key) {
+        continue;
+      }
+      self.events.push(VirtualActorEvent::HandoffStarted {
+        key:  handoff.key.clone(),
+        from: handoff.from.clone(),
+        to:   handoff.to.clone(),
+      });
+      self.in_flight.insert(handoff.key.clone(), InFlightHandoff {
+        handoff:    handoff.clone(),
+        buffered:   VecDeque::new(),
+        started_at: None,
+      });
+      started.push(handoff);
+    }
+    started
+  }
+
+  /// Buffers a request for a grain that is being moved.
+  ///
+  /// Returns the message back when the grain is not moving. When the buffer is full the
+  /// oldest request is dropped and [`VirtualActorEvent::HandoffMessageDropped`] is emitted.
+  pub fn buffer_if_moving(&mut self, key: &GrainKey, message: SerializedMessage) -> Option<SerializedMessage> {
+    let Some(in_flight) = self.in_flight.get_mut(key) else {
+      return Some(message);
+    };
+    if in_flight.buffered.len() >= self.buffer_capacity {
+      in_flight.buffered.pop_front();
+      self.events.push(VirtualActorEvent::HandoffMessageDropped { key: key.clone() });
+      if self.buffer_capacity == 0 {
+        return None;
+      }
+    }
+    in_flight.buffered.push_back(message);
+    None
+  }
+
+  /// Completes the handoff of `key` once the old owner has drained and stopped the grain.
+  ///
+  /// Saves `state` through the registry, activates the grain on the new owner and starts the
+  /// next queued handoffs. Returns `None` if the key is not being handed off or the activation
+  /// on the new owner failed.
+  pub fn complete_handoff(
+    &mut self,
+    registry: &mut VirtualActorRegistry,
+    key: &GrainKey,
+    state: Option<Vec<u8>>,
+    now: u64,
+  ) -> Option<HandoffCompletion> {
+    let InFlightHandoff { handoff, buffered, .. } = self.in_flight.remove(key)?;
+    registry.complete_deactivation(key, state);
+    registry.finish_handoff(key);
+    let activated = registry.ensure_activation(key, core::slice::from_ref(&handoff.to), now, false, None);
+    let completion = activated.ok().map(|pid| {
+      self.events.push(VirtualActorEvent::HandoffCompleted {
+        key:      handoff.key.clone(),
+        from:     handoff.from.clone(),
+        to:       handoff.to.clone(),
+        buffered: buffered.len(),
+      });
+      HandoffCompletion { pid, buffered: buffered.into_iter().collect() }
+    });
+    self.start_ready(registry);
+    completion
+  }
+
+  /// Force-completes handoffs whose old owner is no longer in `authorities` or has not
+  /// reported within the timeout.
+  ///
+  /// The timeout of a handoff starts at the first call that observes it. The grain is
+  /// activated on the new owner from the last saved snapshot; the completions carry the
+  /// buffered requests for delivery.
+  pub fn expire_handoffs(
+    &mut self,
+    registry: &mut VirtualActorRegistry,
+    authorities: &[String],
+    now: u64,
+  ) -> Vec<HandoffCompletion> {
+    let timeout = self.handoff_timeout_secs;
+    let expired: Vec<GrainHandoff> = self
+      .in_flight
+      .values_mut()
+      .filter_map(|in_flight| {
+        let started_at = *in_flight.started_at.get_or_insert(now);
+        let owner_left = !authorities.contains(&in_flight.handoff.from);
+        (owner_left || now.saturating_sub(started_at) >= timeout).then(|| in_flight.handoff.clone())
+      })
+      .collect();
+
+    let mut completions = Vec::new();
+    for handoff in expired {
+      self.events.push(VirtualActorEvent::HandoffTimedOut {
+        key:  handoff.key.clone(),
+        from: handoff.from.clone(),
+        to:   handoff.to.clone(),
+      });
+      if let Some(completion) = self.complete_handoff(registry, &handoff.key, None, now) {
+        completions.push(completion);
+      }
+    }
+    completions
+  }
+
+  /// Returns the number of handoffs waiting for a free slot.
+  #[must_use]
+  pub fn queued_len(&self) -> usize {
+    self.queued.len()
+  }
+
+  /// Returns the number of handoffs in flight.
+  #[must_use]
+  pub fn in_flight_len(&self) -> usize {
+    self.in_flight.len()
+  }
+
+  /// Returns whether the key is being handed off.
+  #[must_use]
+  pub fn is_moving(&self, key: &GrainKey) -> bool {
+    self.in_flight.contains_key(key)
+  }
+
+  /// Drains handoff events.
+  pub fn drain_events(&mut self) -> Vec<VirtualActorEvent> {
+    core::mem::take(&mut self.events)
+  }
+}
@@ -0,0 +1,210 @@
+use alloc::{boxed::Box, collections::BTreeMap, string::ToString, vec::Vec};
+
+use fraktor_utils_rs::core::{runtime_toolbox::NoStdMutex, sync::ArcShared};
+
+use crate::core::{
+  deactivation_reason::DeactivationReason, grain_handoff::GrainHandoff, grain_key::GrainKey,
+  grain_lifecycle_signal::GrainLifecycleSignal, grain_state_provider::GrainStateProvider,
+  rebalance_coordinator::RebalanceCoordinator, serialized_message::SerializedMessage,
+  virtual_actor_event::VirtualActorEvent, virtual_actor_registry::VirtualActorRegistry,
+};
+
+type StoredStates = ArcShared<NoStdMutex<BTreeMap<GrainKey, Vec<u8>>>>;
+
+struct InMemoryStateProvider {
+  states: StoredStates,
+}
+
+impl GrainStateProvider for InMemoryStateProvider {
+  fn save(&mut self, key: &GrainKey, state: Vec<u8>) {
+    self.states.lock().insert(key.clone(), state);
+  }
+
+  fn load(&mut self, key: &GrainKey) -> Option<Vec<u8>> {
+    self.states.lock().get(key).cloned()
+  }
+}
+
+fn key(v: &str) -> GrainKey {
+  GrainKey::new(v.to_string())
+}
+
+fn msg(byte: u8) -> SerializedMessage {
+  SerializedMessage::new(vec![byte], 1)
+}
+
+fn handoff(k: &GrainKey) -> GrainHandoff {
+  GrainHandoff { key: k.clone(), from: "a:1".to_string(), to: "b:2".to_string() }
+}
+
+fn registry_with(keys: &[GrainKey]) -> VirtualActorRegistry {
+  let mut registry = VirtualActorRegistry::new(16, 60);
+  for k in keys {
+    registry.ensure_activation(k, &["a:1".to_string()], 1, false, None).expect("activation");
+  }
+  registry.drain_events();
+  registry.drain_lifecycle_signals();
+  registry
+}
+
+#[test]
+fn start_ready_respects_concurrency_limit() {
+  let keys = [key("user:1"), key("user:2"), key("user:3")];
+  let mut registry = registry_with(&keys);
+  let mut coordinator = RebalanceCoordinator::new(2, 10);
+
+  coordinator.enqueue(keys.iter().map(handoff).collect());
+  let started = coordinator.start_ready(&mut registry);
+
+  assert_eq!(started.len(), 2);
+  assert_eq!(coordinator.in_flight_len(), 2);
+  assert_eq!(coordinator.queued_len(), 1);
+  let signals = registry.drain_lifecycle_signals();
+  assert!(signals.iter().all(|signal| matches!(signal, GrainLifecycleSignal::Deactivating {
+    reason: DeactivationReason::OwnerChanged,
+    ..
+  })));
+  assert_eq!(signals.len(), 2);
+  // ハンドオフによる停止では Passivated は出ない
+  assert!(registry.drain_events().is_empty());
+  let events = coordinator.drain_events();
+  assert_eq!(events.iter().filter(|e| matches!(e, VirtualActorEvent::HandoffStarted { .. })).count(), 2);
+}
+
+#[test]
+fn enqueue_ignores_duplicate_keys() {
+  let k = key("user:1");
+  let mut registry = registry_with(core::slice::from_ref(&k));
+  let mut coordinator = RebalanceCoordinator::new(1, 10);
+
+  coordinator.enqueue(vec![handoff(&k), handoff(&k)]);
+  assert_eq!(coordinator.queued_len(), 1);
+  coordinator.start_ready(&mut registry);
+  coordinator.enqueue(vec![handoff(&k)]);
+  assert_eq!(coordinator.queued_len(), 0);
+}
+
+#[test]
+fn activation_is_rejected_while_handoff_is_in_flight() {
+  let k = key("user:1");
+  let mut registry = registry_with(core::slice::from_ref(&k));
+  let mut coordinator = RebalanceCoordinator::new(1, 10);
+  coordinator.enqueue(vec![handoff(&k)]);
+  coordinator.start_ready(&mut registry);
+
+  assert!(registry.ensure_activation(&k, &["b:2".to_string()], 2, false, None).is_err());
+}
+
+#[test]
+fn buffered_messages_are_returned_on_completion() {
+  let k = key("user:1");
+  let mut registry = registry_with(core::slice::from_ref(&k));
+  let mut coordinator = RebalanceCoordinator::new(1, 10);
+
+  assert_eq!(coordinator.buffer_if_moving(&k, msg(0)), Some(msg(0)));
+
+  coordinator.enqueue(vec![handoff(&k)]);
+  coordinator.start_ready(&mut registry);
+  assert_eq!(coordinator.buffer_if_moving(&k, msg(1)), None);
+  assert_eq!(coordinator.buffer_if_moving(&k, msg(2)), None);
+
+  let completion = coordinator.complete_handoff(&mut registry, &k, None, 3).expect("completion");
+  assert_eq!(completion.pid, "b:2::user:1");
+  assert_eq!(completion.buffered, vec![msg(1), msg(2)]);
+  assert!(coordinator.drain_events().iter().any(|e| matches!(
+    e,
+    VirtualActorEvent::HandoffCompleted { buffered: 2, to, .. } if to == "b:2"
+  )));
+  assert!(coordinator.complete_handoff(&mut registry, &k, None, 4).is_none());
+}
+
+#[test]
+fn full_buffer_drops_oldest_message() {
+  let k = key("user:1");
+  let mut registry = registry_with(core::slice::from_ref(&k));
+  let mut coordinator = RebalanceCoordinator::new(1, 2);
+  coordinator.enqueue(vec![handoff(&k)]);
+  coordinator.start_ready(&mut registry);
+  coordinator.drain_events();
+
+  for byte in 1..=3 {
+    coordinator.buffer_if_moving(&k, msg(byte));
+  }
+
+  assert_eq!(coordinator.drain_events(), vec![VirtualActorEvent::HandoffMessageDropped { key: k.clone() }]);
+  let completion = coordinator.complete_handoff(&mut registry, &k, None, 2).expect("completion");
+  assert_eq!(completion.buffered, vec![msg(2), msg(3)]);
+}
+
+#[test]
+fn completion_starts_next_queued_handoff() {
+  let keys = [key("user:1"), key("user:2")];
+  let mut registry = registry_with(&keys);
+  let mut coordinator = RebalanceCoordinator::new(1, 10);
+  coordinator.enqueue(keys.iter().map(handoff).collect());
+  coordinator.start_ready(&mut registry);
+
+  coordinator.complete_handoff(&mut registry, &keys[0], None, 2).expect("completion");
+
+  assert!(coordinator.is_moving(&keys[1]));
+  assert_eq!(coordinator.queued_len(), 0);
+}
+
+#[test]
+fn drained_state_is_restored_on_new_owner() {
+  let k = key("user:1");
+  let states: StoredStates = ArcShared::new(NoStdMutex::new(BTreeMap::new()));
+  let mut registry = VirtualActorRegistry::new(16, 60);
+  registry.set_state_provider(Box::new(InMemoryStateProvider { states: states.clone() }));
+  registry.ensure_activation(&k, &["a:1".to_string()], 1, false, None).expect("activation");
+  registry.drain_lifecycle_signals();
+  let mut coordinator = RebalanceCoordinator::new(1, 10);
+  coordinator.enqueue(vec![handoff(&k)]);
+  coordinator.start_ready(&mut registry);
+
+  coordinator.complete_handoff(&mut registry, &k, Some(vec![7]), 2).expect("completion");
+
+  assert_eq!(states.lock().get(&k), Some(&vec![7]));
+  let restored = registry.drain_lifecycle_signals().into_iter().find_map(|signal| match signal {
+    | GrainLifecycleSignal::Activated { state, .. } => state,
+    | _ => None,
+  });
+  assert_eq!(restored, Some(vec![7]));
+}
+
+#[test]
+fn handoff_is_force_completed_after_timeout() {
+  let k = key("user:1");
+  let mut registry = registry_with(core::slice::from_ref(&k));
+  let mut coordinator = RebalanceCoordinator::new(1, 10).with_handoff_timeout_secs(30);
+  let authorities = ["a:1".to_string(), "b:2".to_string()];
+
+  coordinator.enqueue(vec![handoff(&k)]);
+  coordinator.start_ready(&mut registry);
+  assert!(coordinator.buffer_if_moving(&k, msg(1)).is_none());
+
+  // 初回観測でタイマーが開始し、タイムアウト前は完了しない
+  assert!(coordinator.expire_handoffs(&mut registry, &authorities, 10).is_empty());
+  assert!(coordinator.expire_handoffs(&mut registry, &authorities, 39).is_empty());
+
+  let completions = coordinator.expire_handoffs(&mut registry, &authorities, 40);
+  assert_eq!(completions.len(), 1);
+  assert_eq!(completions[0].buffered, vec![msg(1)]);
+  assert!(!coordinator.is_moving(&k));
+  assert!(coordinator.drain_events().iter().any(|event| matches!(event, VirtualActorEvent::HandoffTimedOut { .. })));
+}
+
+#[test]
+fn handoff_is_force_completed_when_old_owner_left() {
+  let k = key("user:1");
+  let mut registry = registry_with(core::slice::from_ref(&k));
+  let mut coordinator = RebalanceCoordinator::new(1, 10);
+
+  coordinator.enqueue(vec![handoff(&k)]);
+  coordinator.start_ready(&mut registry);
+
+  let completions = coordinator.expire_handoffs(&mut registry, &["b:2".to_string()], 5);
+  assert_eq!(completions.len(), 1);
+  assert!(completions[0].pid.starts_with("b:2"));
+  assert_eq!(coordinator.in_flight_len(), 0);
+}
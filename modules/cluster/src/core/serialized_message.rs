@@ -2,6 +2,9 @@
 
 use alloc::vec::Vec;
 
+use fraktor_actor_rs::core::messaging::{AnyMessageGeneric, MessageHeaders, TraceContext};
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
 #[cfg(test)]
 mod tests;
 
@@ -12,13 +15,42 @@ pub struct SerializedMessage {
   pub bytes:          Vec<u8>,
   /// Schema version of the payload.
   pub schema_version: u32,
+  /// W3C trace context of the caller, carried along while the request is queued or handed off.
+  pub trace_context:  Option<TraceContext>,
 }
 
 impl SerializedMessage {
   /// Creates a new serialized message.
   #[must_use]
   pub const fn new(bytes: Vec<u8>, schema_version: u32) -> Self {
-    Self { bytes, schema_version }
+    Self { bytes, schema_version, trace_context: None }
+  }
+
+  /// Attaches the caller's trace context.
+  #[must_use]
+  pub fn with_trace_context(mut self, trace_context: TraceContext) -> Self {
+    self.trace_context = Some(trace_context);
+    self
+  }
+
+  /// Copies the trace context header of the caller's message, when present.
+  ///
+  /// Call it when the RPC is issued, so that the trace survives queueing and handoffs.
+  #[must_use]
+  pub fn with_caller_headers(mut self, headers: &MessageHeaders) -> Self {
+    if let Some(trace_context) = headers.get::<TraceContext>() {
+      self.trace_context = Some(trace_context.clone());
+    }
+    self
+  }
+
+  /// Restores the carried trace context into the headers of the message delivered to the grain.
+  #[must_use]
+  pub fn restore_headers<TB: RuntimeToolbox>(&self, mut message: AnyMessageGeneric<TB>) -> AnyMessageGeneric<TB> {
+    if let Some(trace_context) = &self.trace_context {
+      message.headers_mut().insert(trace_context.clone());
+    }
+    message
   }
 
   /// Returns true when the payload is empty.
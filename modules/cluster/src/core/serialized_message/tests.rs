@@ -1,3 +1,6 @@
+use fraktor_actor_rs::core::messaging::{AnyMessageGeneric, MessageHeaders, TraceContext};
+use fraktor_utils_rs::core::runtime_toolbox::NoStdToolbox;
+
 use crate::core::serialized_message::SerializedMessage;
 
 #[test]
@@ -7,3 +10,11 @@ fn emptiness_is_detected() {
   let filled = SerializedMessage::new(vec![1], 1);
   assert!(!filled.is_empty());
 }
+
+#[test]
+fn caller_headers_without_trace_context_leave_it_unset() {
+  let message = SerializedMessage::new(vec![1], 1).with_caller_headers(&MessageHeaders::new());
+  assert_eq!(message.trace_context, None);
+  let delivered = message.restore_headers(AnyMessageGeneric::<NoStdToolbox>::new(1_u8));
+  assert!(!delivered.headers().contains::<TraceContext>());
+}
@@ -45,4 +45,43 @@ pub enum VirtualActorEvent {
     /// Grain key.
     key: GrainKey,
   },
+  /// Handoff to a new owner started; the old owner is draining the activation.
+  HandoffStarted {
+    /// Grain key.
+    key:  GrainKey,
+    /// Previous owner authority.
+    from: String,
+    /// New owner authority.
+    to:   String,
+  },
+  /// Handoff completed and the grain was activated on the new owner.
+  HandoffCompleted {
+    /// Grain key.
+    key:      GrainKey,
+    /// Previous owner authority.
+    from:     String,
+    /// New owner authority.
+    to:       String,
+    /// Number of requests buffered during the move.
+    buffered: usize,
+  },
+  /// Handoff was force-completed because the old owner left or did not report in time.
+  HandoffTimedOut {
+    /// Grain key.
+    key:  GrainKey,
+    /// Previous owner authority.
+    from: String,
+    /// New owner authority.
+    to:   String,
+  },
+  /// An undrained lifecycle signal was dropped because the signal queue was full.
+  LifecycleSignalDropped {
+    /// Grain key.
+    key: GrainKey,
+  },
+  /// A buffered request was dropped because the handoff buffer was full.
+  HandoffMessageDropped {
+    /// Grain key.
+    key: GrainKey,
+  },
 }
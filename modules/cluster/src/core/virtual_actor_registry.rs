@@ -1,20 +1,26 @@
 //! Manages virtual actor activations and passivation.
 
 use alloc::{
-  collections::BTreeMap,
+  boxed::Box,
+  collections::{BTreeMap, BTreeSet, VecDeque},
   format,
   string::{String, ToString},
   vec::Vec,
 };
 
 use crate::core::{
-  activation_error::ActivationError, activation_record::ActivationRecord, grain_key::GrainKey, pid_cache::PidCache,
-  pid_cache_event::PidCacheEvent, rendezvous_hasher::RendezvousHasher, virtual_actor_event::VirtualActorEvent,
+  activation_error::ActivationError, activation_record::ActivationRecord, deactivation_reason::DeactivationReason,
+  grain_key::GrainKey, grain_lifecycle_signal::GrainLifecycleSignal, grain_state_provider::GrainStateProvider,
+  passivation_policy::PassivationPolicy, pid_cache::PidCache, pid_cache_event::PidCacheEvent,
+  rendezvous_hasher::RendezvousHasher, virtual_actor_event::VirtualActorEvent,
 };
 
 #[cfg(test)]
 mod tests;
 
+const MAX_PENDING_LIFECYCLE_SIGNALS: usize = 4096;
+const MAX_PENDING_DEACTIVATIONS: usize = 4096;
+
 struct ActivationEntry {
   record:    ActivationRecord,
   authority: String,
@@ -22,18 +28,54 @@ struct ActivationEntry {
 }
 
 /// Registry that keeps track of active grains.
+///
+/// Deactivations triggered by passivation emit [`GrainLifecycleSignal::Deactivating`]. When a
+/// [`GrainStateProvider`] is installed, the state reported through
+/// [`complete_deactivation`](Self::complete_deactivation) is saved and handed back in
+/// [`GrainLifecycleSignal::Activated`] on the next activation.
+///
+/// Undrained signals and unreported deactivations are bounded: the oldest signal is dropped
+/// with [`VirtualActorEvent::LifecycleSignalDropped`], and the oldest pending deactivation is
+/// completed with its last snapshot.
 pub struct VirtualActorRegistry {
-  activations:  BTreeMap<GrainKey, ActivationEntry>,
-  pid_cache:    PidCache,
-  pid_ttl_secs: u64,
-  events:       Vec<VirtualActorEvent>,
+  activations:        BTreeMap<GrainKey, ActivationEntry>,
+  pid_cache:          PidCache,
+  pid_ttl_secs:       u64,
+  events:             Vec<VirtualActorEvent>,
+  policies:           BTreeMap<String, PassivationPolicy>,
+  state_provider:     Option<Box<dyn GrainStateProvider>>,
+  deactivating:       BTreeMap<GrainKey, Option<Vec<u8>>>,
+  deactivation_order: VecDeque<GrainKey>,
+  signals:            VecDeque<GrainLifecycleSignal>,
+  handoffs:           BTreeSet<GrainKey>,
 }
 
 impl VirtualActorRegistry {
   /// Creates a new registry.
   #[must_use]
   pub const fn new(cache_capacity: usize, pid_ttl_secs: u64) -> Self {
-    Self { activations: BTreeMap::new(), pid_cache: PidCache::new(cache_capacity), pid_ttl_secs, events: Vec::new() }
+    Self {
+      activations: BTreeMap::new(),
+      pid_cache: PidCache::new(cache_capacity),
+      pid_ttl_secs,
+      events: Vec::new(),
+      policies: BTreeMap::new(),
+      state_provider: None,
+      deactivating: BTreeMap::new(),
+      deactivation_order: VecDeque::new(),
+      signals: VecDeque::new(),
+      handoffs: BTreeSet::new(),
+    }
+  }
+
+  /// Sets the passivation policy for activations of `kind`.
+  pub fn set_passivation_policy(&mut self, kind: impl Into<String>, policy: PassivationPolicy) {
+    self.policies.insert(kind.into(), policy);
+  }
+
+  /// Installs the provider used to save and restore grain state.
+  pub fn set_state_provider(&mut self, provider: Box<dyn GrainStateProvider>) {
+    self.state_provider = Some(provider);
   }
 
   /// Ensures an activation exists and returns its PID.
@@ -42,6 +84,7 @@ impl VirtualActorRegistry {
   ///
   /// Returns `ActivationError::NoAuthority` if no authorities are provided.
   /// Returns `ActivationError::SnapshotMissing` if a snapshot is required but not provided.
+  /// Returns `ActivationError::HandoffInProgress` while the grain is moving to a new owner.
   pub fn ensure_activation(
     &mut self,
     key: &GrainKey,
@@ -50,10 +93,17 @@ impl VirtualActorRegistry {
     snapshot_required: bool,
     snapshot: Option<Vec<u8>>,
   ) -> Result<String, ActivationError> {
+    if self.handoffs.contains(key) {
+      return Err(ActivationError::HandoffInProgress { key: key.value().to_string() });
+    }
     let Some(owner) = RendezvousHasher::select(authorities, key) else {
       return Err(ActivationError::NoAuthority);
     };
 
+    let mut snapshot = snapshot;
+    if snapshot_required && snapshot.is_none() {
+      snapshot = self.load_state(key);
+    }
     if snapshot_required && snapshot.is_none() {
       self.events.push(VirtualActorEvent::SnapshotMissing { key: key.clone() });
       return Err(ActivationError::SnapshotMissing { key: key.value().to_string() });
@@ -68,11 +118,16 @@ impl VirtualActorRegistry {
       return Ok(entry.record.pid.clone());
     }
 
+    let snapshot = match snapshot {
+      | Some(snapshot) => Some(snapshot),
+      | None => self.load_state(key),
+    };
     let pid = format!("{}::{}", owner, key.value());
-    let record = ActivationRecord::new(pid.clone(), snapshot, 0);
+    let record = ActivationRecord::new(pid.clone(), snapshot.clone(), 0);
     let entry = ActivationEntry { record, authority: owner.clone(), last_seen: now };
     let replaced = self.activations.insert(key.clone(), entry);
     self.pid_cache.put(key.clone(), pid.clone(), owner.clone(), now, self.pid_ttl_secs);
+    self.push_signal(GrainLifecycleSignal::Activated { key: key.clone(), pid: pid.clone(), state: snapshot });
 
     if replaced.is_some() {
       self.events.push(VirtualActorEvent::Reactivated {
@@ -88,6 +143,7 @@ impl VirtualActorRegistry {
       });
     }
 
+    self.evict_over_capacity(key, owner);
     Ok(pid)
   }
 
@@ -123,21 +179,73 @@ impl VirtualActorRegistry {
   }
 
   /// Passivates idle activations.
+  ///
+  /// `idle_ttl` applies to kinds whose [`PassivationPolicy`] does not override the idle timeout.
   pub fn passivate_idle(&mut self, now: u64, idle_ttl: u64) {
     let to_passivate: Vec<_> = self
       .activations
       .iter()
-      .filter(|(_, entry)| now.saturating_sub(entry.last_seen) >= idle_ttl)
+      .filter(|(key, entry)| now.saturating_sub(entry.last_seen) >= self.policy(key).effective_idle_timeout(idle_ttl))
       .map(|(key, _)| key.clone())
       .collect();
 
     for key in to_passivate {
-      self.activations.remove(&key);
-      self.pid_cache.invalidate_key(&key);
-      self.events.push(VirtualActorEvent::Passivated { key });
+      self.deactivate(&key, DeactivationReason::IdleTimeout);
+    }
+  }
+
+  /// Returns the key and hosting authority of every activation.
+  #[must_use]
+  pub fn activation_owners(&self) -> Vec<(GrainKey, String)> {
+    self.activations.iter().map(|(key, entry)| (key.clone(), entry.authority.clone())).collect()
+  }
+
+  /// Starts handing off an activation to a new owner.
+  ///
+  /// The activation is deactivated with [`DeactivationReason::OwnerChanged`] and further
+  /// activations of the key are rejected until [`finish_handoff`](Self::finish_handoff).
+  /// Returns `false` if the key is not active or already being handed off.
+  pub fn begin_handoff(&mut self, key: &GrainKey) -> bool {
+    if self.handoffs.contains(key) || !self.activations.contains_key(key) {
+      return false;
+    }
+    self.handoffs.insert(key.clone());
+    self.deactivate(key, DeactivationReason::OwnerChanged);
+    true
+  }
+
+  /// Finishes a handoff started by [`begin_handoff`](Self::begin_handoff), allowing activation
+  /// again.
+  pub fn finish_handoff(&mut self, key: &GrainKey) {
+    self.handoffs.remove(key);
+  }
+
+  /// Returns whether the key is being handed off.
+  #[must_use]
+  pub fn is_handing_off(&self, key: &GrainKey) -> bool {
+    self.handoffs.contains(key)
+  }
+
+  /// Completes a deactivation announced by [`GrainLifecycleSignal::Deactivating`].
+  ///
+  /// `state` is the final state reported by the grain; when it is `None` the snapshot held by
+  /// the activation record is saved instead. Does nothing unless a state provider is installed.
+  pub fn complete_deactivation(&mut self, key: &GrainKey, state: Option<Vec<u8>>) {
+    let Some(snapshot) = self.deactivating.remove(key) else {
+      return;
+    };
+    self.deactivation_order.retain(|pending| pending != key);
+    if let (Some(provider), Some(state)) = (self.state_provider.as_mut(), state.or(snapshot)) {
+      provider.save(key, state);
     }
   }
 
+  /// Returns the keys whose deactivation has not been completed yet.
+  #[must_use]
+  pub fn pending_deactivations(&self) -> Vec<GrainKey> {
+    self.deactivating.keys().cloned().collect()
+  }
+
   /// Removes an activation and its cache entry for the given key.
   ///
   /// If the key exists, generates a [`VirtualActorEvent::Passivated`] event.
@@ -152,6 +260,11 @@ impl VirtualActorRegistry {
     }
   }
 
+  /// Drains lifecycle signals to be delivered to grains.
+  pub fn drain_lifecycle_signals(&mut self) -> Vec<GrainLifecycleSignal> {
+    core::mem::take(&mut self.signals).into_iter().collect()
+  }
+
   /// Drains virtual actor events.
   pub fn drain_events(&mut self) -> Vec<VirtualActorEvent> {
     core::mem::take(&mut self.events)
@@ -163,4 +276,66 @@ impl VirtualActorRegistry {
   pub fn drain_cache_events(&mut self) -> Vec<PidCacheEvent> {
     self.pid_cache.drain_events()
   }
+
+  fn policy(&self, key: &GrainKey) -> PassivationPolicy {
+    key.kind().and_then(|kind| self.policies.get(kind)).copied().unwrap_or_default()
+  }
+
+  fn load_state(&mut self, key: &GrainKey) -> Option<Vec<u8>> {
+    self.state_provider.as_mut().and_then(|provider| provider.load(key))
+  }
+
+  // 同じ kind・同じノード上のアクティベーションが上限を超えた場合、最も古く使われたものから退避する
+  fn evict_over_capacity(&mut self, activated: &GrainKey, authority: &str) {
+    let Some(max) = self.policy(activated).max_active_per_node() else {
+      return;
+    };
+    let kind = activated.kind();
+    let mut candidates: Vec<(u64, GrainKey)> = self
+      .activations
+      .iter()
+      .filter(|(key, entry)| entry.authority == authority && key.kind() == kind && *key != activated)
+      .map(|(key, entry)| (entry.last_seen, key.clone()))
+      .collect();
+    candidates.sort();
+    let excess = (candidates.len() + 1).saturating_sub(max);
+    for (_, key) in candidates.into_iter().take(excess) {
+      self.deactivate(&key, DeactivationReason::CapacityExceeded);
+    }
+  }
+
+  fn deactivate(&mut self, key: &GrainKey, reason: DeactivationReason) {
+    let Some(entry) = self.activations.remove(key) else {
+      return;
+    };
+    self.pid_cache.invalidate_key(key);
+    if self.state_provider.is_some() {
+      // 報告されないまま溜まった停止待ちは、最古のものをスナップショットで完了させる
+      if self.deactivating.len() >= MAX_PENDING_DEACTIVATIONS
+        && let Some(oldest) = self.deactivation_order.front().cloned()
+      {
+        self.complete_deactivation(&oldest, None);
+      }
+      if self.deactivating.insert(key.clone(), entry.record.snapshot).is_none() {
+        self.deactivation_order.push_back(key.clone());
+      }
+    }
+    self.push_signal(GrainLifecycleSignal::Deactivating { key: key.clone(), pid: entry.record.pid, reason });
+    // ハンドオフは HandoffStarted/HandoffCompleted で通知されるため Passivated は出さない
+    if !matches!(reason, DeactivationReason::OwnerChanged) {
+      self.events.push(VirtualActorEvent::Passivated { key: key.clone() });
+    }
+  }
+
+  fn push_signal(&mut self, signal: GrainLifecycleSignal) {
+    if self.signals.len() >= MAX_PENDING_LIFECYCLE_SIGNALS
+      && let Some(dropped) = self.signals.pop_front()
+    {
+      let key = match dropped {
+        | GrainLifecycleSignal::Activated { key, .. } | GrainLifecycleSignal::Deactivating { key, .. } => key,
+      };
+      self.events.push(VirtualActorEvent::LifecycleSignalDropped { key });
+    }
+    self.signals.push_back(signal);
+  }
 }
@@ -1,10 +1,30 @@
-use alloc::string::ToString;
+use alloc::{boxed::Box, collections::BTreeMap, string::ToString, vec::Vec};
+
+use fraktor_utils_rs::core::{runtime_toolbox::NoStdMutex, sync::ArcShared};
 
 use crate::core::{
-  activation_error::ActivationError, grain_key::GrainKey, pid_cache_event::PidCacheEvent,
-  virtual_actor_event::VirtualActorEvent, virtual_actor_registry::VirtualActorRegistry,
+  activation_error::ActivationError, deactivation_reason::DeactivationReason, grain_key::GrainKey,
+  grain_lifecycle_signal::GrainLifecycleSignal, grain_state_provider::GrainStateProvider,
+  passivation_policy::PassivationPolicy, pid_cache_event::PidCacheEvent, virtual_actor_event::VirtualActorEvent,
+  virtual_actor_registry::VirtualActorRegistry,
 };
 
+type StoredStates = ArcShared<NoStdMutex<BTreeMap<GrainKey, Vec<u8>>>>;
+
+struct InMemoryStateProvider {
+  states: StoredStates,
+}
+
+impl GrainStateProvider for InMemoryStateProvider {
+  fn save(&mut self, key: &GrainKey, state: Vec<u8>) {
+    self.states.lock().insert(key.clone(), state);
+  }
+
+  fn load(&mut self, key: &GrainKey) -> Option<Vec<u8>> {
+    self.states.lock().get(key).cloned()
+  }
+}
+
 fn key(v: &str) -> GrainKey {
   GrainKey::new(v.to_string())
 }
@@ -220,3 +240,131 @@ fn drain_cache_events_captures_ttl_expiration() {
     "TTL 期限切れ時に Dropped イベントが生成されるべき"
   );
 }
+
+// ============================================
+// パッシベーションポリシー・ライフサイクルシグナルのテスト
+// ============================================
+
+#[test]
+fn lifecycle_signals_bracket_activation_and_idle_passivation() {
+  let mut registry = VirtualActorRegistry::new(8, 60);
+  let k = key("user:1");
+  let pid = registry.ensure_activation(&k, &["a1:4000".to_string()], 0, false, None).expect("activation");
+
+  registry.passivate_idle(20, 10);
+
+  assert_eq!(registry.drain_lifecycle_signals(), vec![
+    GrainLifecycleSignal::Activated { key: k.clone(), pid: pid.clone(), state: None },
+    GrainLifecycleSignal::Deactivating { key: k, pid, reason: DeactivationReason::IdleTimeout },
+  ]);
+}
+
+#[test]
+fn per_kind_idle_timeout_overrides_registry_ttl() {
+  let mut registry = VirtualActorRegistry::new(8, 60);
+  registry.set_passivation_policy("session", PassivationPolicy::new().with_idle_timeout_secs(5));
+  let authorities = ["a1:4000".to_string()];
+  let short = key("session:1");
+  let long = key("user:1");
+  registry.ensure_activation(&short, &authorities, 0, false, None).expect("activation");
+  registry.ensure_activation(&long, &authorities, 0, false, None).expect("activation");
+  registry.drain_events();
+
+  registry.passivate_idle(6, 100);
+
+  assert_eq!(registry.drain_events(), vec![VirtualActorEvent::Passivated { key: short }]);
+}
+
+#[test]
+fn max_active_per_node_evicts_least_recently_used() {
+  let mut registry = VirtualActorRegistry::new(8, 60);
+  registry.set_passivation_policy("cart", PassivationPolicy::new().with_max_active_per_node(2));
+  let authorities = ["a1:4000".to_string()];
+  let first = key("cart:1");
+  let second = key("cart:2");
+  registry.ensure_activation(&first, &authorities, 0, false, None).expect("activation");
+  registry.ensure_activation(&second, &authorities, 1, false, None).expect("activation");
+  // first を再利用して second を最も古いものにする
+  registry.ensure_activation(&first, &authorities, 2, false, None).expect("activation");
+  registry.drain_lifecycle_signals();
+
+  registry.ensure_activation(&key("cart:3"), &authorities, 3, false, None).expect("activation");
+
+  let signals = registry.drain_lifecycle_signals();
+  assert!(signals.iter().any(|signal| matches!(
+    signal,
+    GrainLifecycleSignal::Deactivating { key, reason: DeactivationReason::CapacityExceeded, .. } if *key == second
+  )));
+  assert!(registry.cached_pid(&first, 3).is_some());
+  assert!(registry.cached_pid(&second, 3).is_none());
+}
+
+#[test]
+fn state_provider_restores_state_saved_on_deactivation() {
+  let states: StoredStates = ArcShared::new(NoStdMutex::new(BTreeMap::new()));
+  let mut registry = VirtualActorRegistry::new(8, 60);
+  registry.set_state_provider(Box::new(InMemoryStateProvider { states: states.clone() }));
+  let k = key("user:42");
+  registry.ensure_activation(&k, &["a1:4000".to_string()], 0, false, None).expect("activation");
+
+  registry.passivate_idle(20, 10);
+  assert_eq!(registry.pending_deactivations(), vec![k.clone()]);
+  registry.complete_deactivation(&k, Some(vec![7, 7]));
+  assert!(registry.pending_deactivations().is_empty());
+  assert_eq!(states.lock().get(&k), Some(&vec![7, 7]));
+  registry.drain_lifecycle_signals();
+
+  // 別のオーナーで再アクティベートすると保存済みの状態が渡される
+  let pid = registry.ensure_activation(&k, &["a2:4001".to_string()], 30, false, None).expect("activation");
+  assert_eq!(registry.drain_lifecycle_signals(), vec![GrainLifecycleSignal::Activated {
+    key: k,
+    pid,
+    state: Some(vec![7, 7]),
+  }]);
+}
+
+#[test]
+fn state_provider_satisfies_required_snapshot() {
+  let states: StoredStates = ArcShared::new(NoStdMutex::new(BTreeMap::new()));
+  let k = key("user:43");
+  states.lock().insert(k.clone(), vec![1]);
+  let mut registry = VirtualActorRegistry::new(8, 60);
+  registry.set_state_provider(Box::new(InMemoryStateProvider { states }));
+
+  assert!(registry.ensure_activation(&k, &["a1:4000".to_string()], 0, true, None).is_ok());
+}
+
+#[test]
+fn undrained_lifecycle_signals_are_bounded() {
+  let mut registry = VirtualActorRegistry::new(16, 60);
+  let authority = ["a:1".to_string()];
+  for index in 0..=4096 {
+    registry.ensure_activation(&key(&alloc::format!("user:{index}")), &authority, 1, false, None).expect("activation");
+  }
+
+  let signals = registry.drain_lifecycle_signals();
+  assert_eq!(signals.len(), 4096);
+  assert!(matches!(&signals[0], GrainLifecycleSignal::Activated { key: k, .. } if *k == key("user:1")));
+  assert!(
+    registry
+      .drain_events()
+      .iter()
+      .any(|event| matches!(event, VirtualActorEvent::LifecycleSignalDropped { key: k } if *k == key("user:0")))
+  );
+}
+
+#[test]
+fn unreported_deactivations_are_completed_with_snapshot_when_full() {
+  let states: StoredStates = ArcShared::new(NoStdMutex::new(BTreeMap::new()));
+  let mut registry = VirtualActorRegistry::new(16, 60);
+  registry.set_state_provider(Box::new(InMemoryStateProvider { states: states.clone() }));
+  let authority = ["a:1".to_string()];
+  for index in 0..=4096 {
+    let k = key(&alloc::format!("user:{index}"));
+    registry.ensure_activation(&k, &authority, 1, false, Some(vec![1])).expect("activation");
+  }
+  registry.passivate_idle(100, 10);
+
+  assert_eq!(registry.pending_deactivations().len(), 4096);
+  assert_eq!(states.lock().get(&key("user:0")), Some(&vec![1]));
+}
@@ -2,10 +2,56 @@
 
 #[cfg(feature = "aws-ecs")]
 mod aws_ecs_cluster_provider;
+mod cluster_metrics_collector;
+#[cfg(any(feature = "kubernetes", feature = "dns"))]
+mod discovered_members;
+#[cfg(feature = "dns")]
+mod dns_cluster_config;
+#[cfg(feature = "dns")]
+mod dns_cluster_provider;
+#[cfg(feature = "dns")]
+mod dns_record_type;
+#[cfg(feature = "dns")]
+mod dns_resolver;
+#[cfg(feature = "kubernetes")]
+mod kubernetes_api_client;
+#[cfg(feature = "kubernetes")]
+mod kubernetes_cluster_config;
+#[cfg(feature = "kubernetes")]
+mod kubernetes_cluster_provider;
 mod local_cluster_provider_ext;
+#[cfg(feature = "test-support")]
+mod multi_node_config;
+#[cfg(feature = "test-support")]
+mod multi_node_error;
+#[cfg(feature = "test-support")]
+mod multi_node_frame;
+#[cfg(feature = "test-support")]
+mod multi_node_harness;
+#[cfg(feature = "test-support")]
+mod multi_node_member;
 
 #[cfg(feature = "aws-ecs")]
 pub use aws_ecs_cluster_provider::{AwsEcsClusterProvider, EcsClusterConfig, EcsPollerError};
+pub use cluster_metrics_collector::ClusterMetricsCollector;
+#[cfg(feature = "dns")]
+pub use dns_cluster_config::DnsClusterConfig;
+#[cfg(feature = "dns")]
+pub use dns_cluster_provider::DnsClusterProvider;
+#[cfg(feature = "dns")]
+pub use dns_record_type::DnsRecordType;
+#[cfg(feature = "kubernetes")]
+pub use kubernetes_cluster_config::KubernetesClusterConfig;
+#[cfg(feature = "kubernetes")]
+pub use kubernetes_cluster_provider::KubernetesClusterProvider;
 pub use local_cluster_provider_ext::{
   SharedLocalClusterProvider, subscribe_remoting_events, wrap_local_cluster_provider,
 };
+#[cfg(feature = "test-support")]
+pub use multi_node_config::MultiNodeConfig;
+#[cfg(feature = "test-support")]
+pub use multi_node_error::MultiNodeError;
+#[cfg(feature = "test-support")]
+pub use multi_node_harness::MultiNodeHarness;
+#[cfg(feature = "test-support")]
+pub use multi_node_member::MultiNodeMember;
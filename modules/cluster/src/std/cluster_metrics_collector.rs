@@ -0,0 +1,47 @@
+//! Prometheus collector exposing cluster membership and activation counts.
+
+#[cfg(test)]
+mod tests;
+
+use fraktor_actor_rs::std::metrics::{MetricsCollector, MetricsRegistry};
+use fraktor_utils_rs::{core::sync::ArcShared, std::runtime_toolbox::StdToolbox};
+
+use crate::core::ClusterExtensionGeneric;
+
+/// Copies [`ClusterMetricsSnapshot`](crate::core::ClusterMetricsSnapshot) values into
+/// `fraktor_cluster_members` and `fraktor_cluster_activations` on every scrape.
+///
+/// Register it with
+/// [`PrometheusMetrics::register_collector`](fraktor_actor_rs::std::metrics::PrometheusMetrics::register_collector).
+/// Nothing is reported while cluster metrics are disabled.
+pub struct ClusterMetricsCollector {
+  extension: ArcShared<ClusterExtensionGeneric<StdToolbox>>,
+}
+
+impl ClusterMetricsCollector {
+  /// Creates a collector reading the metrics of `extension`.
+  #[must_use]
+  pub const fn new(extension: ArcShared<ClusterExtensionGeneric<StdToolbox>>) -> Self {
+    Self { extension }
+  }
+}
+
+impl MetricsCollector for ClusterMetricsCollector {
+  fn collect(&self, registry: &mut MetricsRegistry) {
+    let Ok(snapshot) = self.extension.metrics() else {
+      return;
+    };
+    registry.set_gauge(
+      "fraktor_cluster_members",
+      "Cluster members known to this node.",
+      &[],
+      snapshot.members() as f64,
+    );
+    registry.set_gauge(
+      "fraktor_cluster_activations",
+      "Virtual actors activated on this node.",
+      &[],
+      snapshot.virtual_actors() as f64,
+    );
+  }
+}
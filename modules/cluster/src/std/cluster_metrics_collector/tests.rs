@@ -0,0 +1,104 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use fraktor_actor_rs::{
+  core::system::ActorSystemGeneric,
+  std::metrics::{PrometheusMetrics, PrometheusMetricsConfig},
+};
+use fraktor_utils_rs::{core::sync::ArcShared, std::runtime_toolbox::StdToolbox};
+
+use super::ClusterMetricsCollector;
+use crate::core::{
+  ActivatedKind, ClusterExtensionConfig, ClusterExtensionId, ClusterProvider, ClusterProviderError, ClusterPubSub,
+  Gossiper, IdentityLookup, IdentitySetupError, PubSubError,
+};
+
+struct StubProvider;
+
+impl ClusterProvider for StubProvider {
+  fn start_member(&mut self) -> Result<(), ClusterProviderError> {
+    Ok(())
+  }
+
+  fn start_client(&mut self) -> Result<(), ClusterProviderError> {
+    Ok(())
+  }
+
+  fn shutdown(&mut self, _graceful: bool) -> Result<(), ClusterProviderError> {
+    Ok(())
+  }
+}
+
+struct StubGossiper;
+
+impl Gossiper for StubGossiper {
+  fn start(&mut self) -> Result<(), &'static str> {
+    Ok(())
+  }
+
+  fn stop(&mut self) -> Result<(), &'static str> {
+    Ok(())
+  }
+}
+
+struct StubPubSub;
+
+impl ClusterPubSub for StubPubSub {
+  fn start(&mut self) -> Result<(), PubSubError> {
+    Ok(())
+  }
+
+  fn stop(&mut self) -> Result<(), PubSubError> {
+    Ok(())
+  }
+}
+
+struct StubIdentity;
+
+impl IdentityLookup for StubIdentity {
+  fn setup_member(&mut self, _kinds: &[ActivatedKind]) -> Result<(), IdentitySetupError> {
+    Ok(())
+  }
+
+  fn setup_client(&mut self, _kinds: &[ActivatedKind]) -> Result<(), IdentitySetupError> {
+    Ok(())
+  }
+}
+
+struct StubBlockList;
+
+impl fraktor_remote_rs::core::BlockListProvider for StubBlockList {
+  fn blocked_members(&self) -> Vec<String> {
+    Vec::new()
+  }
+}
+
+fn render_with(metrics_enabled: bool) -> String {
+  let system = ActorSystemGeneric::<StdToolbox>::new_empty();
+  let ext_id = ClusterExtensionId::<StdToolbox>::new(
+    ClusterExtensionConfig::new().with_advertised_address("node-a").with_metrics_enabled(metrics_enabled),
+    Box::new(StubProvider),
+    ArcShared::new(StubBlockList),
+    Box::new(StubGossiper),
+    Box::new(StubPubSub),
+    Box::new(StubIdentity),
+  );
+  let extension = system.extended().register_extension(&ext_id);
+  extension.start_member().expect("start member");
+
+  let metrics = PrometheusMetrics::new(PrometheusMetricsConfig::new());
+  metrics.register_collector(ClusterMetricsCollector::new(extension));
+  metrics.render()
+}
+
+#[test]
+fn membership_and_activations_are_exported() {
+  let rendered = render_with(true);
+
+  assert!(rendered.contains("# TYPE fraktor_cluster_members gauge\nfraktor_cluster_members 1\n"));
+  assert!(rendered.contains("fraktor_cluster_activations 0\n"));
+}
+
+#[test]
+fn nothing_is_exported_while_metrics_are_disabled() {
+  assert!(!render_with(false).contains("fraktor_cluster"));
+}
@@ -0,0 +1,101 @@
+//! Membership bookkeeping shared by discovery-based providers.
+
+use std::collections::BTreeMap;
+
+use fraktor_actor_rs::core::{
+  event_stream::{EventStreamEvent, EventStreamGeneric},
+  messaging::AnyMessageGeneric,
+};
+use fraktor_remote_rs::core::BlockListProvider;
+use fraktor_utils_rs::{core::sync::ArcShared, std::runtime_toolbox::StdToolbox};
+
+use crate::core::{ClusterEvent, ClusterTopology};
+
+#[cfg(test)]
+mod tests;
+
+/// Authority (`host:port`) to advertised roles.
+pub(crate) type MemberRoles = BTreeMap<String, Vec<String>>;
+
+/// Tracks the discovered member set and publishes topology deltas.
+pub(crate) struct DiscoveredMembers {
+  event_stream:        ArcShared<EventStreamGeneric<StdToolbox>>,
+  block_list_provider: ArcShared<dyn BlockListProvider>,
+  self_authority:      Option<String>,
+  members:             MemberRoles,
+  version:             u64,
+}
+
+impl DiscoveredMembers {
+  pub(crate) fn new(
+    event_stream: ArcShared<EventStreamGeneric<StdToolbox>>,
+    block_list_provider: ArcShared<dyn BlockListProvider>,
+  ) -> Self {
+    Self { event_stream, block_list_provider, self_authority: None, members: BTreeMap::new(), version: 0 }
+  }
+
+  /// Registers the local member with its configured roles, publishing its join or role change.
+  pub(crate) fn add_self(&mut self, authority: &str, roles: Vec<String>) {
+    self.self_authority = Some(authority.to_string());
+    if self.members.get(authority) != Some(&roles) {
+      let mut next = self.members.clone();
+      next.insert(authority.to_string(), roles);
+      self.replace(next);
+    }
+  }
+
+  /// Replaces the member set with the latest discovery result and publishes the delta.
+  ///
+  /// Returns `true` when a topology update was published.
+  pub(crate) fn update(&mut self, discovered: MemberRoles) -> bool {
+    let mut next = discovered;
+    if let Some(authority) = &self.self_authority {
+      // 自ノードは検出結果に現れなくても、ロールを持たずに検出されても設定済みのロールで保持する
+      let roles = self.members.get(authority).cloned().unwrap_or_default();
+      let entry = next.entry(authority.clone()).or_default();
+      if entry.is_empty() {
+        *entry = roles;
+      }
+    }
+    self.replace(next)
+  }
+
+  pub(crate) fn clear(&mut self) {
+    self.self_authority = None;
+    self.members.clear();
+  }
+
+  pub(crate) fn len(&self) -> usize {
+    self.members.len()
+  }
+
+  /// Publishes a cluster event on the `cluster` extension channel.
+  pub(crate) fn publish(&self, event: ClusterEvent) {
+    let payload = AnyMessageGeneric::new(event);
+    let extension_event = EventStreamEvent::Extension { name: String::from("cluster"), payload };
+    self.event_stream.publish(&extension_event);
+  }
+
+  fn replace(&mut self, next: MemberRoles) -> bool {
+    let joined: Vec<String> = next.keys().filter(|member| !self.members.contains_key(*member)).cloned().collect();
+    let left: Vec<String> = self.members.keys().filter(|member| !next.contains_key(*member)).cloned().collect();
+    // 残留メンバーのロール変更は空のロールも含めて伝播する
+    let mut member_roles: MemberRoles = next
+      .iter()
+      .filter(|(member, roles)| self.members.get(*member).is_some_and(|previous| previous != *roles))
+      .map(|(member, roles)| (member.clone(), roles.clone()))
+      .collect();
+    self.members = next;
+    if joined.is_empty() && left.is_empty() && member_roles.is_empty() {
+      return false;
+    }
+    self.version += 1;
+    member_roles.extend(joined.iter().filter_map(|member| {
+      self.members.get(member).filter(|roles| !roles.is_empty()).map(|r| (member.clone(), r.clone()))
+    }));
+    let blocked = self.block_list_provider.blocked_members();
+    let topology = ClusterTopology::new(self.version, joined.clone(), left.clone()).with_member_roles(member_roles);
+    self.publish(ClusterEvent::TopologyUpdated { topology, joined, left, blocked });
+    true
+  }
+}
@@ -0,0 +1,82 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use fraktor_actor_rs::core::event_stream::{
+  EventStreamEvent, EventStreamGeneric, EventStreamSubscriber, EventStreamSubscriptionGeneric, subscriber_handle,
+};
+use fraktor_remote_rs::core::BlockListProvider;
+use fraktor_utils_rs::{core::sync::ArcShared, std::runtime_toolbox::StdToolbox};
+
+use super::{DiscoveredMembers, MemberRoles};
+use crate::core::{ClusterEvent, ClusterTopology};
+
+struct EmptyBlockList;
+
+impl BlockListProvider for EmptyBlockList {
+  fn blocked_members(&self) -> Vec<String> {
+    Vec::new()
+  }
+}
+
+#[derive(Clone)]
+struct RecordingTopologies {
+  topologies: ArcShared<Mutex<Vec<ClusterTopology>>>,
+}
+
+impl EventStreamSubscriber<StdToolbox> for RecordingTopologies {
+  fn on_event(&mut self, event: &EventStreamEvent<StdToolbox>) {
+    if let EventStreamEvent::Extension { name, payload } = event
+      && name == "cluster"
+      && let Some(ClusterEvent::TopologyUpdated { topology, .. }) = payload.payload().downcast_ref::<ClusterEvent>()
+    {
+      self.topologies.lock().unwrap().push(topology.clone());
+    }
+  }
+}
+
+fn members_with_recorder() -> (DiscoveredMembers, RecordingTopologies, EventStreamSubscriptionGeneric<StdToolbox>) {
+  let event_stream = ArcShared::new(EventStreamGeneric::<StdToolbox>::default());
+  let recorder = RecordingTopologies { topologies: ArcShared::new(Mutex::new(Vec::new())) };
+  let subscription = EventStreamGeneric::subscribe_arc(&event_stream, &subscriber_handle(recorder.clone()));
+  (DiscoveredMembers::new(event_stream, ArcShared::new(EmptyBlockList)), recorder, subscription)
+}
+
+fn roles(values: &[&str]) -> Vec<String> {
+  values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn self_is_published_with_configured_roles() {
+  let (mut members, recorder, _subscription) = members_with_recorder();
+
+  members.add_self("10.0.0.9:2552", roles(&["backend"]));
+
+  let topologies = recorder.topologies.lock().unwrap().clone();
+  assert_eq!(topologies.len(), 1);
+  assert_eq!(topologies[0].member_roles().get("10.0.0.9:2552"), Some(&roles(&["backend"])));
+}
+
+#[test]
+fn self_keeps_configured_roles_when_discovered_without_roles() {
+  let (mut members, recorder, _subscription) = members_with_recorder();
+  members.add_self("10.0.0.9:2552", roles(&["backend"]));
+
+  let discovered: MemberRoles = BTreeMap::from([("10.0.0.9:2552".to_string(), Vec::new())]);
+  assert!(!members.update(discovered));
+  assert_eq!(recorder.topologies.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn role_changes_of_remaining_members_are_published() {
+  let (mut members, recorder, _subscription) = members_with_recorder();
+  members.update(BTreeMap::from([("10.0.0.1:2552".to_string(), roles(&["frontend"]))]));
+
+  assert!(members.update(BTreeMap::from([("10.0.0.1:2552".to_string(), roles(&["frontend", "backend"]))])));
+  assert!(members.update(BTreeMap::from([("10.0.0.1:2552".to_string(), Vec::new())])));
+  assert!(!members.update(BTreeMap::from([("10.0.0.1:2552".to_string(), Vec::new())])));
+
+  let topologies = recorder.topologies.lock().unwrap().clone();
+  assert_eq!(topologies.len(), 3);
+  assert!(topologies[1].joined().is_empty() && topologies[1].left().is_empty());
+  assert_eq!(topologies[1].member_roles().get("10.0.0.1:2552"), Some(&roles(&["frontend", "backend"])));
+  assert_eq!(topologies[2].member_roles().get("10.0.0.1:2552"), Some(&Vec::new()));
+}
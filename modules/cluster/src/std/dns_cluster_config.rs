@@ -0,0 +1,119 @@
+//! Configuration for the DNS cluster provider.
+
+#[cfg(test)]
+mod tests;
+
+use std::{net::SocketAddr, time::Duration};
+
+use super::dns_record_type::DnsRecordType;
+
+/// Configuration for [`DnsClusterProvider`](super::DnsClusterProvider).
+#[derive(Clone, Debug)]
+pub struct DnsClusterConfig {
+  query_name:    String,
+  record_type:   DnsRecordType,
+  port:          u16,
+  nameserver:    Option<SocketAddr>,
+  poll_interval: Duration,
+  query_timeout: Duration,
+}
+
+impl Default for DnsClusterConfig {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl DnsClusterConfig {
+  /// Creates a new DNS cluster configuration with default values.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self {
+      query_name:    String::new(),
+      record_type:   DnsRecordType::A,
+      port:          8080,
+      nameserver:    None,
+      poll_interval: Duration::from_secs(10),
+      query_timeout: Duration::from_secs(2),
+    }
+  }
+
+  /// Sets the name to resolve (e.g. `my-service.prod.svc.cluster.local`).
+  #[must_use]
+  pub fn with_query_name(mut self, name: impl Into<String>) -> Self {
+    self.query_name = name.into();
+    self
+  }
+
+  /// Sets the record type to query.
+  #[must_use]
+  pub const fn with_record_type(mut self, record_type: DnsRecordType) -> Self {
+    self.record_type = record_type;
+    self
+  }
+
+  /// Sets the port combined with `A` record addresses.
+  #[must_use]
+  pub const fn with_port(mut self, port: u16) -> Self {
+    self.port = port;
+    self
+  }
+
+  /// Sets the nameserver; by default the first entry of `/etc/resolv.conf` is used.
+  #[must_use]
+  pub const fn with_nameserver(mut self, nameserver: SocketAddr) -> Self {
+    self.nameserver = Some(nameserver);
+    self
+  }
+
+  /// Sets the resolution interval.
+  #[must_use]
+  pub const fn with_poll_interval(mut self, interval: Duration) -> Self {
+    self.poll_interval = interval;
+    self
+  }
+
+  /// Sets the timeout of a single query.
+  #[must_use]
+  pub const fn with_query_timeout(mut self, timeout: Duration) -> Self {
+    self.query_timeout = timeout;
+    self
+  }
+
+  /// Returns the query name.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // String の Deref が const でないため const fn にできない
+  pub fn query_name(&self) -> &str {
+    &self.query_name
+  }
+
+  /// Returns the record type.
+  #[must_use]
+  pub const fn record_type(&self) -> DnsRecordType {
+    self.record_type
+  }
+
+  /// Returns the port.
+  #[must_use]
+  pub const fn port(&self) -> u16 {
+    self.port
+  }
+
+  /// Returns the configured nameserver.
+  #[must_use]
+  pub const fn nameserver(&self) -> Option<SocketAddr> {
+    self.nameserver
+  }
+
+  /// Returns the resolution interval.
+  #[must_use]
+  pub const fn poll_interval(&self) -> Duration {
+    self.poll_interval
+  }
+
+  /// Returns the query timeout.
+  #[must_use]
+  pub const fn query_timeout(&self) -> Duration {
+    self.query_timeout
+  }
+}
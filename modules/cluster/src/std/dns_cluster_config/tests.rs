@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use super::DnsClusterConfig;
+use crate::std::DnsRecordType;
+
+#[test]
+fn dns_cluster_config_default_values() {
+  let config = DnsClusterConfig::new();
+  assert_eq!(config.query_name(), "");
+  assert_eq!(config.record_type(), DnsRecordType::A);
+  assert_eq!(config.port(), 8080);
+  assert!(config.nameserver().is_none());
+  assert_eq!(config.poll_interval().as_secs(), 10);
+}
+
+#[test]
+fn dns_cluster_config_builder_pattern() {
+  let nameserver = "10.0.0.10:53".parse().unwrap();
+  let config = DnsClusterConfig::new()
+    .with_query_name("_fraktor._tcp.example.local")
+    .with_record_type(DnsRecordType::Srv)
+    .with_port(9090)
+    .with_nameserver(nameserver)
+    .with_poll_interval(Duration::from_secs(3))
+    .with_query_timeout(Duration::from_millis(500));
+
+  assert_eq!(config.query_name(), "_fraktor._tcp.example.local");
+  assert_eq!(config.record_type(), DnsRecordType::Srv);
+  assert_eq!(config.port(), 9090);
+  assert_eq!(config.nameserver(), Some(nameserver));
+  assert_eq!(config.poll_interval(), Duration::from_secs(3));
+  assert_eq!(config.query_timeout(), Duration::from_millis(500));
+}
@@ -0,0 +1,154 @@
+//! DNS cluster provider discovering members from `A` or `SRV` records.
+//!
+//! The provider resolves a name on a fixed interval and publishes topology updates when
+//! the resolved set of `ip:port` authorities changes. With `A` records every address is
+//! combined with the configured port; with `SRV` records the port and target come from the
+//! records and the targets are resolved through the additional section or a follow-up query.
+//! Kubernetes headless services expose both forms.
+//!
+//! # Example
+//!
+//! ```text
+//! use fraktor_cluster_rs::std::{DnsClusterConfig, DnsRecordType};
+//! use fraktor_cluster_rs::core::ClusterExtensionInstaller;
+//!
+//! let dns_config = DnsClusterConfig::new()
+//!     .with_query_name("_fraktor._tcp.my-service.prod.svc.cluster.local")
+//!     .with_record_type(DnsRecordType::Srv)
+//!     .with_poll_interval(Duration::from_secs(5));
+//!
+//! let installer = ClusterExtensionInstaller::new_with_dns(config, dns_config);
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+
+use fraktor_actor_rs::core::event_stream::EventStreamGeneric;
+use fraktor_remote_rs::core::BlockListProvider;
+use fraktor_utils_rs::{
+  core::{
+    runtime_toolbox::{RuntimeToolbox, SyncMutexFamily, ToolboxMutex},
+    sync::ArcShared,
+  },
+  std::runtime_toolbox::StdToolbox,
+};
+use tokio::task::JoinHandle;
+
+use super::{discovered_members::DiscoveredMembers, dns_cluster_config::DnsClusterConfig, dns_resolver};
+use crate::core::{ClusterEvent, ClusterProvider, ClusterProviderError, StartupMode};
+
+type SharedMembers = ArcShared<ToolboxMutex<DiscoveredMembers, StdToolbox>>;
+
+/// DNS cluster provider that resolves members on an interval.
+///
+/// The resolution loop runs on the ambient tokio runtime, so `start_member` and
+/// `start_client` must be called from within one.
+pub struct DnsClusterProvider {
+  advertised_address: String,
+  self_roles:         Vec<String>,
+  config:             DnsClusterConfig,
+  members:            SharedMembers,
+  startup_mode:       Option<StartupMode>,
+  poller_handle:      Option<JoinHandle<()>>,
+}
+
+impl DnsClusterProvider {
+  /// Creates a new DNS cluster provider.
+  #[must_use]
+  pub fn new(
+    event_stream: ArcShared<EventStreamGeneric<StdToolbox>>,
+    block_list_provider: ArcShared<dyn BlockListProvider>,
+    advertised_address: impl Into<String>,
+  ) -> Self {
+    let members = DiscoveredMembers::new(event_stream, block_list_provider);
+    Self {
+      advertised_address: advertised_address.into(),
+      self_roles:         Vec::new(),
+      config:             DnsClusterConfig::new(),
+      members:            ArcShared::new(<StdToolbox as RuntimeToolbox>::MutexFamily::create(members)),
+      startup_mode:       None,
+      poller_handle:      None,
+    }
+  }
+
+  /// Sets the DNS configuration.
+  #[must_use]
+  pub fn with_dns_config(mut self, config: DnsClusterConfig) -> Self {
+    self.config = config;
+    self
+  }
+
+  /// Sets the roles advertised for the local member.
+  #[must_use]
+  pub fn with_self_roles(mut self, roles: Vec<String>) -> Self {
+    self.self_roles = roles;
+    self
+  }
+
+  /// Returns the advertised address.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // String の Deref が const でないため const fn にできない
+  pub fn advertised_address(&self) -> &str {
+    &self.advertised_address
+  }
+
+  /// Returns whether the provider has been started.
+  #[must_use]
+  pub const fn is_started(&self) -> bool {
+    self.startup_mode.is_some()
+  }
+
+  /// Returns the current member count.
+  #[must_use]
+  pub fn member_count(&self) -> usize {
+    self.members.lock().len()
+  }
+
+  fn start(&mut self, mode: StartupMode) {
+    self.startup_mode = Some(mode);
+    if matches!(mode, StartupMode::Member) {
+      // 初回トポロジとして自ノードを publish する
+      self.members.lock().add_self(&self.advertised_address, self.self_roles.clone());
+    }
+
+    let members = ArcShared::clone(&self.members);
+    let config = self.config.clone();
+    self.poller_handle = Some(tokio::spawn(async move {
+      loop {
+        // 解決に失敗した場合は直前のメンバー構成を維持して次の周期を待つ
+        if let Ok(resolved) = dns_resolver::resolve_members(&config).await {
+          let discovered = resolved.into_iter().map(|member| (member, Vec::new())).collect::<BTreeMap<_, _>>();
+          members.lock().update(discovered);
+        }
+        tokio::time::sleep(config.poll_interval()).await;
+      }
+    }));
+
+    self.members.lock().publish(ClusterEvent::Startup { address: self.advertised_address.clone(), mode });
+  }
+}
+
+impl ClusterProvider for DnsClusterProvider {
+  fn start_member(&mut self) -> Result<(), ClusterProviderError> {
+    self.start(StartupMode::Member);
+    Ok(())
+  }
+
+  fn start_client(&mut self) -> Result<(), ClusterProviderError> {
+    self.start(StartupMode::Client);
+    Ok(())
+  }
+
+  fn shutdown(&mut self, _graceful: bool) -> Result<(), ClusterProviderError> {
+    if let Some(handle) = self.poller_handle.take() {
+      handle.abort();
+    }
+    let mode = self.startup_mode.take().unwrap_or(StartupMode::Member);
+    let mut members = self.members.lock();
+    members.clear();
+    members.publish(ClusterEvent::Shutdown { address: self.advertised_address.clone(), mode });
+    Ok(())
+  }
+}
@@ -0,0 +1,174 @@
+//! Tests for DnsClusterProvider against a local DNS stub.
+
+use std::{
+  net::SocketAddr,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+use fraktor_actor_rs::core::event_stream::{
+  EventStreamEvent, EventStreamGeneric, EventStreamSubscriber, EventStreamSubscriptionGeneric, subscriber_handle,
+};
+use fraktor_remote_rs::core::BlockListProvider;
+use fraktor_utils_rs::{core::sync::ArcShared, std::runtime_toolbox::StdToolbox};
+use tokio::net::UdpSocket;
+
+use super::DnsClusterProvider;
+use crate::{
+  core::{ClusterEvent, ClusterProvider, StartupMode},
+  std::DnsClusterConfig,
+};
+
+struct EmptyBlockList;
+
+impl BlockListProvider for EmptyBlockList {
+  fn blocked_members(&self) -> Vec<String> {
+    Vec::new()
+  }
+}
+
+#[derive(Clone)]
+struct RecordingClusterEvents {
+  events: ArcShared<Mutex<Vec<ClusterEvent>>>,
+}
+
+impl RecordingClusterEvents {
+  fn new() -> Self {
+    Self { events: ArcShared::new(Mutex::new(Vec::new())) }
+  }
+
+  fn events(&self) -> Vec<ClusterEvent> {
+    self.events.lock().unwrap().clone()
+  }
+
+  fn topology_updates(&self) -> Vec<(Vec<String>, Vec<String>)> {
+    self
+      .events()
+      .into_iter()
+      .filter_map(|event| match event {
+        | ClusterEvent::TopologyUpdated { joined, left, .. } => Some((joined, left)),
+        | _ => None,
+      })
+      .collect()
+  }
+}
+
+impl EventStreamSubscriber<StdToolbox> for RecordingClusterEvents {
+  fn on_event(&mut self, event: &EventStreamEvent<StdToolbox>) {
+    if let EventStreamEvent::Extension { name, payload } = event {
+      if name == "cluster" {
+        if let Some(cluster_event) = payload.payload().downcast_ref::<ClusterEvent>() {
+          self.events.lock().unwrap().push(cluster_event.clone());
+        }
+      }
+    }
+  }
+}
+
+fn subscribe_recorder(
+  event_stream: &ArcShared<EventStreamGeneric<StdToolbox>>,
+) -> (RecordingClusterEvents, EventStreamSubscriptionGeneric<StdToolbox>) {
+  let subscriber_impl = RecordingClusterEvents::new();
+  let subscriber = subscriber_handle(subscriber_impl.clone());
+  let subscription = EventStreamGeneric::subscribe_arc(event_stream, &subscriber);
+  (subscriber_impl, subscription)
+}
+
+// 問い合わせごとに応答するアドレスを順に切り替える A レコード専用スタブ
+async fn spawn_dns_stub(rounds: Vec<Vec<[u8; 4]>>) -> SocketAddr {
+  let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+  let address = socket.local_addr().unwrap();
+  tokio::spawn(async move {
+    let mut buffer = [0_u8; 512];
+    let mut round = 0;
+    loop {
+      let Ok((received, peer)) = socket.recv_from(&mut buffer).await else {
+        return;
+      };
+      let addresses = &rounds[round.min(rounds.len() - 1)];
+      round += 1;
+      let mut response = buffer[..2].to_vec();
+      response.extend_from_slice(&[0x81, 0x80, 0, 1]);
+      response.extend_from_slice(&(addresses.len() as u16).to_be_bytes());
+      response.extend_from_slice(&[0, 0, 0, 0]);
+      response.extend_from_slice(&buffer[12..received]);
+      for octets in addresses {
+        response.extend_from_slice(&[0xc0, 0x0c, 0, 1, 0, 1, 0, 0, 0, 30, 0, 4]);
+        response.extend_from_slice(octets);
+      }
+      socket.send_to(&response, peer).await.unwrap();
+    }
+  });
+  address
+}
+
+async fn wait_for_updates(recorder: &RecordingClusterEvents, count: usize) -> Vec<(Vec<String>, Vec<String>)> {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  loop {
+    let updates = recorder.topology_updates();
+    if updates.len() >= count || Instant::now() >= deadline {
+      return updates;
+    }
+    tokio::time::sleep(Duration::from_millis(10)).await;
+  }
+}
+
+#[test]
+fn provider_new_creates_instance() {
+  let event_stream = ArcShared::new(EventStreamGeneric::<StdToolbox>::default());
+  let block_list: ArcShared<dyn BlockListProvider> = ArcShared::new(EmptyBlockList);
+
+  let provider = DnsClusterProvider::new(event_stream, block_list, "127.0.0.1:8080");
+
+  assert_eq!(provider.advertised_address(), "127.0.0.1:8080");
+  assert!(!provider.is_started());
+  assert_eq!(provider.member_count(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn start_member_publishes_resolved_members() {
+  let nameserver = spawn_dns_stub(vec![vec![[10, 0, 0, 1], [10, 0, 0, 2]], vec![[10, 0, 0, 2]]]).await;
+  let event_stream = ArcShared::new(EventStreamGeneric::<StdToolbox>::default());
+  let block_list: ArcShared<dyn BlockListProvider> = ArcShared::new(EmptyBlockList);
+  let (recorder, _subscription) = subscribe_recorder(&event_stream);
+
+  let config = DnsClusterConfig::new()
+    .with_query_name("members.example.local")
+    .with_port(2552)
+    .with_nameserver(nameserver)
+    .with_poll_interval(Duration::from_millis(50));
+  let mut provider = DnsClusterProvider::new(event_stream, block_list, "10.0.0.9:2552").with_dns_config(config);
+  provider.start_member().unwrap();
+
+  let updates = wait_for_updates(&recorder, 3).await;
+  assert_eq!(updates, vec![
+    (vec!["10.0.0.9:2552".to_string()], vec![]),
+    (vec!["10.0.0.1:2552".to_string(), "10.0.0.2:2552".to_string()], vec![]),
+    (vec![], vec!["10.0.0.1:2552".to_string()]),
+  ]);
+  assert_eq!(provider.member_count(), 2);
+
+  provider.shutdown(true).unwrap();
+  assert!(!provider.is_started());
+  assert!(matches!(recorder.events().last(), Some(ClusterEvent::Shutdown { mode: StartupMode::Member, .. })));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn start_client_does_not_add_self() {
+  let nameserver = spawn_dns_stub(vec![vec![[10, 0, 0, 1]]]).await;
+  let event_stream = ArcShared::new(EventStreamGeneric::<StdToolbox>::default());
+  let block_list: ArcShared<dyn BlockListProvider> = ArcShared::new(EmptyBlockList);
+  let (recorder, _subscription) = subscribe_recorder(&event_stream);
+
+  let config =
+    DnsClusterConfig::new().with_query_name("members.example.local").with_port(2552).with_nameserver(nameserver);
+  let mut provider = DnsClusterProvider::new(event_stream, block_list, "10.0.0.9:2552").with_dns_config(config);
+  provider.start_client().unwrap();
+
+  let updates = wait_for_updates(&recorder, 1).await;
+  assert_eq!(updates, vec![(vec!["10.0.0.1:2552".to_string()], vec![])]);
+  assert!(
+    recorder.events().iter().any(|event| matches!(event, ClusterEvent::Startup { mode: StartupMode::Client, .. }))
+  );
+  provider.shutdown(true).unwrap();
+}
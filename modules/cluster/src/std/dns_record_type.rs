@@ -0,0 +1,21 @@
+//! DNS record types understood by the DNS cluster provider.
+
+/// Record type queried by [`DnsClusterProvider`](super::DnsClusterProvider).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DnsRecordType {
+  /// `A` records; each address is combined with the configured port.
+  A,
+  /// `SRV` records; the target host and port come from the record itself.
+  Srv,
+}
+
+impl DnsRecordType {
+  /// Returns the record type code used on the wire.
+  #[must_use]
+  pub const fn code(self) -> u16 {
+    match self {
+      | Self::A => 1,
+      | Self::Srv => 33,
+    }
+  }
+}
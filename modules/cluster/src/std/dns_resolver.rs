@@ -0,0 +1,301 @@
+//! Minimal DNS client resolving `A` and `SRV` records over UDP, retrying truncated answers over
+//! TCP.
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+  collections::BTreeMap,
+  fmt,
+  hash::{BuildHasher, RandomState},
+  io,
+  net::{Ipv4Addr, SocketAddr},
+  sync::atomic::{AtomicU64, Ordering},
+  time::Duration,
+};
+
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::{TcpStream, UdpSocket},
+};
+
+use super::{dns_cluster_config::DnsClusterConfig, dns_record_type::DnsRecordType};
+
+const CLASS_IN: u16 = 1;
+const MAX_MESSAGE_SIZE: usize = 4096;
+const MAX_LABEL_LEN: usize = 63;
+const MAX_NAME_LEN: usize = 255;
+
+static QUERY_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Errors raised while resolving members.
+#[derive(Debug)]
+pub(crate) enum DnsQueryError {
+  /// Socket level failure.
+  Io(io::Error),
+  /// No answer arrived within the query timeout.
+  Timeout,
+  /// No nameserver is configured and none could be read from `/etc/resolv.conf`.
+  NoNameserver,
+  /// The response was malformed or did not match the query.
+  Malformed,
+  /// The server answered with a non-zero response code.
+  ResponseCode(u8),
+  /// The answer did not fit into a UDP datagram and has the TC bit set.
+  Truncated,
+  /// The queried name has a label longer than 63 bytes or exceeds 255 bytes in total.
+  InvalidName(String),
+}
+
+impl fmt::Display for DnsQueryError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Self::Io(error) => write!(f, "io error: {error}"),
+      | Self::Timeout => f.write_str("query timed out"),
+      | Self::NoNameserver => f.write_str("no nameserver configured"),
+      | Self::Malformed => f.write_str("malformed response"),
+      | Self::ResponseCode(code) => write!(f, "response code {code}"),
+      | Self::Truncated => f.write_str("truncated response"),
+      | Self::InvalidName(name) => write!(f, "invalid name: {name}"),
+    }
+  }
+}
+
+impl From<io::Error> for DnsQueryError {
+  fn from(error: io::Error) -> Self {
+    Self::Io(error)
+  }
+}
+
+/// Resource record extracted from a response.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum DnsRecord {
+  /// IPv4 address record.
+  A { name: String, address: Ipv4Addr },
+  /// Service record.
+  Srv { port: u16, target: String },
+}
+
+/// Resolves the configured name into sorted, de-duplicated `ip:port` authorities.
+pub(crate) async fn resolve_members(config: &DnsClusterConfig) -> Result<Vec<String>, DnsQueryError> {
+  let nameserver = match config.nameserver() {
+    | Some(nameserver) => nameserver,
+    | None => system_nameserver().ok_or(DnsQueryError::NoNameserver)?,
+  };
+  let records = query(nameserver, config.query_name(), config.record_type(), config.query_timeout()).await?;
+  let mut members: Vec<String> = match config.record_type() {
+    | DnsRecordType::A => records
+      .iter()
+      .filter_map(|record| match record {
+        | DnsRecord::A { address, .. } => Some(format!("{address}:{}", config.port())),
+        | DnsRecord::Srv { .. } => None,
+      })
+      .collect(),
+    | DnsRecordType::Srv => {
+      // 追加セクションに含まれる A レコードを優先し、無いターゲットだけ個別に問い合わせる
+      let mut addresses: BTreeMap<String, Vec<Ipv4Addr>> = BTreeMap::new();
+      for record in &records {
+        if let DnsRecord::A { name, address } = record {
+          addresses.entry(normalize(name)).or_default().push(*address);
+        }
+      }
+      let mut members = Vec::new();
+      for record in &records {
+        let DnsRecord::Srv { port, target } = record else {
+          continue;
+        };
+        let target = normalize(target);
+        if !addresses.contains_key(&target) {
+          // 解決できないターゲットが 1 つあっても、残りのメンバーは返す
+          let resolved = match query(nameserver, &target, DnsRecordType::A, config.query_timeout()).await {
+            | Ok(resolved) => resolved,
+            | Err(error) => {
+              tracing::warn!(target = %target, %error, "skipping SRV target that could not be resolved");
+              Vec::new()
+            },
+          };
+          let resolved = resolved.into_iter().filter_map(|record| match record {
+            | DnsRecord::A { address, .. } => Some(address),
+            | DnsRecord::Srv { .. } => None,
+          });
+          addresses.insert(target.clone(), resolved.collect());
+        }
+        for address in addresses.get(&target).into_iter().flatten() {
+          members.push(format!("{address}:{port}"));
+        }
+      }
+      members
+    },
+  };
+  members.sort();
+  members.dedup();
+  Ok(members)
+}
+
+async fn query(
+  nameserver: SocketAddr,
+  name: &str,
+  record_type: DnsRecordType,
+  timeout: Duration,
+) -> Result<Vec<DnsRecord>, DnsQueryError> {
+  let bind_address: SocketAddr = if nameserver.is_ipv4() { ([0, 0, 0, 0], 0).into() } else { ([0; 16], 0).into() };
+  let socket = UdpSocket::bind(bind_address).await?;
+  socket.connect(nameserver).await?;
+  let id = next_query_id();
+  let request = encode_query(id, name, record_type)?;
+  socket.send(&request).await?;
+  let mut buffer = vec![0; MAX_MESSAGE_SIZE];
+  let received =
+    tokio::time::timeout(timeout, socket.recv(&mut buffer)).await.map_err(|_| DnsQueryError::Timeout)??;
+  match decode_response(id, &buffer[..received]) {
+    // UDP に収まらなかった応答は TCP で問い合わせ直す (RFC 7766)
+    | Err(DnsQueryError::Truncated) => {
+      let response = tokio::time::timeout(timeout, exchange_tcp(nameserver, &request))
+        .await
+        .map_err(|_| DnsQueryError::Timeout)??;
+      decode_response(id, &response)
+    },
+    | result => result,
+  }
+}
+
+// TCP では各メッセージの前に 2 バイトの長さを付ける
+async fn exchange_tcp(nameserver: SocketAddr, request: &[u8]) -> Result<Vec<u8>, DnsQueryError> {
+  let length = u16::try_from(request.len()).map_err(|_| DnsQueryError::Malformed)?;
+  let mut stream = TcpStream::connect(nameserver).await?;
+  let mut framed = Vec::with_capacity(request.len() + 2);
+  framed.extend_from_slice(&length.to_be_bytes());
+  framed.extend_from_slice(request);
+  stream.write_all(&framed).await?;
+  let mut length = [0_u8; 2];
+  stream.read_exact(&mut length).await?;
+  let mut response = vec![0; usize::from(u16::from_be_bytes(length))];
+  stream.read_exact(&mut response).await?;
+  Ok(response)
+}
+
+// 応答の偽装を難しくするため、ID は問い合わせごとにランダムに選ぶ
+fn next_query_id() -> u16 {
+  let hash = RandomState::new().hash_one(QUERY_SEQUENCE.fetch_add(1, Ordering::Relaxed)).to_le_bytes();
+  u16::from_le_bytes([hash[0], hash[1]])
+}
+
+fn encode_query(id: u16, name: &str, record_type: DnsRecordType) -> Result<Vec<u8>, DnsQueryError> {
+  let mut message = Vec::with_capacity(32 + name.len());
+  message.extend_from_slice(&id.to_be_bytes());
+  // RD (再帰要求) のみを立てる
+  message.extend_from_slice(&0x0100_u16.to_be_bytes());
+  message.extend_from_slice(&1_u16.to_be_bytes());
+  message.extend_from_slice(&[0; 6]);
+  let mut encoded_len = 1;
+  for label in name.trim_end_matches('.').split('.').filter(|label| !label.is_empty()) {
+    let length = u8::try_from(label.len())
+      .ok()
+      .filter(|length| usize::from(*length) <= MAX_LABEL_LEN)
+      .ok_or_else(|| DnsQueryError::InvalidName(name.to_string()))?;
+    encoded_len += label.len() + 1;
+    if encoded_len > MAX_NAME_LEN {
+      return Err(DnsQueryError::InvalidName(name.to_string()));
+    }
+    message.push(length);
+    message.extend_from_slice(label.as_bytes());
+  }
+  message.push(0);
+  message.extend_from_slice(&record_type.code().to_be_bytes());
+  message.extend_from_slice(&CLASS_IN.to_be_bytes());
+  Ok(message)
+}
+
+fn decode_response(id: u16, message: &[u8]) -> Result<Vec<DnsRecord>, DnsQueryError> {
+  if message.len() < 12 || read_u16(message, 0)? != id || message[2] & 0x80 == 0 {
+    return Err(DnsQueryError::Malformed);
+  }
+  let response_code = message[3] & 0x0f;
+  if response_code != 0 {
+    return Err(DnsQueryError::ResponseCode(response_code));
+  }
+  if message[2] & 0x02 != 0 {
+    return Err(DnsQueryError::Truncated);
+  }
+  let questions = read_u16(message, 4)?;
+  let records =
+    usize::from(read_u16(message, 6)?) + usize::from(read_u16(message, 8)?) + usize::from(read_u16(message, 10)?);
+
+  let mut offset = 12;
+  for _ in 0..questions {
+    offset = read_name(message, offset)?.1 + 4;
+  }
+  let mut decoded = Vec::new();
+  for _ in 0..records {
+    let (name, next) = read_name(message, offset)?;
+    let record_type = read_u16(message, next)?;
+    let class = read_u16(message, next + 2)?;
+    let length = usize::from(read_u16(message, next + 8)?);
+    let data = next + 10;
+    let end = data + length;
+    if end > message.len() {
+      return Err(DnsQueryError::Malformed);
+    }
+    if class == CLASS_IN {
+      if record_type == DnsRecordType::A.code() && length == 4 {
+        let address = Ipv4Addr::new(message[data], message[data + 1], message[data + 2], message[data + 3]);
+        decoded.push(DnsRecord::A { name, address });
+      } else if record_type == DnsRecordType::Srv.code() && length >= 7 {
+        let port = read_u16(message, data + 4)?;
+        let (target, _) = read_name(message, data + 6)?;
+        decoded.push(DnsRecord::Srv { port, target });
+      }
+    }
+    offset = end;
+  }
+  Ok(decoded)
+}
+
+// 圧縮ポインタを辿りながら名前を読み取り、ポインタを含まない位置での次オフセットを返す
+fn read_name(message: &[u8], start: usize) -> Result<(String, usize), DnsQueryError> {
+  let mut labels: Vec<String> = Vec::new();
+  let mut offset = start;
+  let mut next = None;
+  let mut jumps = 0;
+  loop {
+    let length = *message.get(offset).ok_or(DnsQueryError::Malformed)?;
+    match length {
+      | 0 => {
+        let next = next.unwrap_or(offset + 1);
+        return Ok((labels.join("."), next));
+      },
+      | length if length & 0xc0 == 0xc0 => {
+        jumps += 1;
+        if jumps > 16 {
+          return Err(DnsQueryError::Malformed);
+        }
+        let pointer = usize::from(read_u16(message, offset)? & 0x3fff);
+        next.get_or_insert(offset + 2);
+        offset = pointer;
+      },
+      | length => {
+        let end = offset + 1 + usize::from(length);
+        let label = message.get(offset + 1..end).ok_or(DnsQueryError::Malformed)?;
+        labels.push(String::from_utf8_lossy(label).into_owned());
+        offset = end;
+      },
+    }
+  }
+}
+
+fn read_u16(message: &[u8], offset: usize) -> Result<u16, DnsQueryError> {
+  let bytes = message.get(offset..offset + 2).ok_or(DnsQueryError::Malformed)?;
+  Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn normalize(name: &str) -> String {
+  name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+fn system_nameserver() -> Option<SocketAddr> {
+  let resolv_conf = std::fs::read_to_string("/etc/resolv.conf").ok()?;
+  resolv_conf.lines().find_map(|line| {
+    let address = line.trim().strip_prefix("nameserver")?.trim();
+    address.parse().ok().map(|ip| SocketAddr::new(ip, 53))
+  })
+}
@@ -0,0 +1,204 @@
+use std::net::{Ipv4Addr, SocketAddr};
+
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::{TcpListener, UdpSocket},
+};
+
+use super::{DnsQueryError, DnsRecord, decode_response, encode_query, read_name, resolve_members};
+use crate::std::{DnsClusterConfig, DnsRecordType};
+
+fn encode_name(name: &str) -> Vec<u8> {
+  let mut bytes = Vec::new();
+  for label in name.split('.') {
+    bytes.push(label.len() as u8);
+    bytes.extend_from_slice(label.as_bytes());
+  }
+  bytes.push(0);
+  bytes
+}
+
+fn a_record(name: &[u8], address: Ipv4Addr) -> Vec<u8> {
+  let mut record = name.to_vec();
+  record.extend_from_slice(&[0, 1, 0, 1, 0, 0, 0, 30, 0, 4]);
+  record.extend_from_slice(&address.octets());
+  record
+}
+
+fn srv_record(name: &[u8], port: u16, target: &str) -> Vec<u8> {
+  let target = encode_name(target);
+  let mut record = name.to_vec();
+  record.extend_from_slice(&[0, 33, 0, 1, 0, 0, 0, 30]);
+  record.extend_from_slice(&((6 + target.len()) as u16).to_be_bytes());
+  record.extend_from_slice(&[0, 10, 0, 5]);
+  record.extend_from_slice(&port.to_be_bytes());
+  record.extend_from_slice(&target);
+  record
+}
+
+// 問い合わせのヘッダと質問部をそのまま返し、回答・追加レコードを付け加える
+fn build_response(query: &[u8], answers: &[Vec<u8>], additionals: &[Vec<u8>]) -> Vec<u8> {
+  let (_, question_end) = read_name(query, 12).unwrap();
+  let mut response = query[..2].to_vec();
+  response.extend_from_slice(&[0x81, 0x80, 0, 1]);
+  response.extend_from_slice(&(answers.len() as u16).to_be_bytes());
+  response.extend_from_slice(&[0, 0]);
+  response.extend_from_slice(&(additionals.len() as u16).to_be_bytes());
+  response.extend_from_slice(&query[12..question_end + 4]);
+  for record in answers.iter().chain(additionals) {
+    response.extend_from_slice(record);
+  }
+  response
+}
+
+// 名前とレコード種別に応じて応答する DNS スタブ
+async fn spawn_dns_stub() -> SocketAddr {
+  let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+  let address = socket.local_addr().unwrap();
+  tokio::spawn(async move {
+    let mut buffer = [0_u8; 512];
+    loop {
+      let Ok((received, peer)) = socket.recv_from(&mut buffer).await else {
+        return;
+      };
+      let query = &buffer[..received];
+      let (name, question_end) = read_name(query, 12).unwrap();
+      let record_type = u16::from_be_bytes([query[question_end], query[question_end + 1]]);
+      let pointer = vec![0xc0, 0x0c];
+      let response = match (name.as_str(), record_type) {
+        | ("members.example.local", 1) => build_response(
+          query,
+          &[a_record(&pointer, Ipv4Addr::new(10, 0, 0, 2)), a_record(&pointer, Ipv4Addr::new(10, 0, 0, 1))],
+          &[],
+        ),
+        | ("_fraktor._tcp.example.local", 33) => build_response(
+          query,
+          &[srv_record(&pointer, 2552, "node-a.example.local"), srv_record(&pointer, 2553, "node-b.example.local")],
+          &[a_record(&encode_name("node-a.example.local"), Ipv4Addr::new(10, 0, 1, 1))],
+        ),
+        | ("_partial._tcp.example.local", 33) => build_response(
+          query,
+          &[srv_record(&pointer, 2552, "missing.example.local"), srv_record(&pointer, 2553, "node-b.example.local")],
+          &[],
+        ),
+        | ("node-b.example.local", 1) => build_response(query, &[a_record(&pointer, Ipv4Addr::new(10, 0, 1, 2))], &[]),
+        | _ => {
+          let mut response = build_response(query, &[], &[]);
+          response[3] = 0x83;
+          response
+        },
+      };
+      socket.send_to(&response, peer).await.unwrap();
+    }
+  });
+  address
+}
+
+// UDP では TC ビットだけを返し、同じポートの TCP で完全な応答を返す DNS スタブ
+async fn spawn_truncating_dns_stub() -> SocketAddr {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let address = listener.local_addr().unwrap();
+  let socket = UdpSocket::bind(address).await.unwrap();
+  tokio::spawn(async move {
+    let mut buffer = [0_u8; 512];
+    while let Ok((received, peer)) = socket.recv_from(&mut buffer).await {
+      let mut response = build_response(&buffer[..received], &[], &[]);
+      response[2] |= 0x02;
+      socket.send_to(&response, peer).await.unwrap();
+    }
+  });
+  tokio::spawn(async move {
+    while let Ok((mut stream, _)) = listener.accept().await {
+      let mut length = [0_u8; 2];
+      stream.read_exact(&mut length).await.unwrap();
+      let mut query = vec![0; usize::from(u16::from_be_bytes(length))];
+      stream.read_exact(&mut query).await.unwrap();
+      let answers: Vec<Vec<u8>> = (1..=40).map(|host| a_record(&[0xc0, 0x0c], Ipv4Addr::new(10, 0, 2, host))).collect();
+      let response = build_response(&query, &answers, &[]);
+      stream.write_all(&(response.len() as u16).to_be_bytes()).await.unwrap();
+      stream.write_all(&response).await.unwrap();
+    }
+  });
+  address
+}
+
+#[test]
+fn encode_query_writes_labels_and_type() {
+  let query = encode_query(7, "a.example.", DnsRecordType::Srv).unwrap();
+  assert_eq!(&query[..6], &[0, 7, 1, 0, 0, 1]);
+  assert_eq!(&query[12..], &[1, b'a', 7, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 0, 0, 33, 0, 1]);
+}
+
+#[test]
+fn encode_query_rejects_overlong_labels_and_names() {
+  let label = "a".repeat(64);
+  assert!(matches!(encode_query(1, &format!("{label}.example"), DnsRecordType::A), Err(DnsQueryError::InvalidName(_))));
+  let name = vec!["a".repeat(63); 4].join(".");
+  assert!(matches!(encode_query(1, &name, DnsRecordType::A), Err(DnsQueryError::InvalidName(_))));
+  assert!(encode_query(1, &format!("{}.example", "a".repeat(63)), DnsRecordType::A).is_ok());
+}
+
+#[test]
+fn decode_response_follows_compression_pointers() {
+  let query = encode_query(9, "svc.local", DnsRecordType::Srv).unwrap();
+  let response = build_response(&query, &[srv_record(&[0xc0, 0x0c], 2552, "node.local")], &[]);
+  let records = decode_response(9, &response).unwrap();
+  assert_eq!(records, vec![DnsRecord::Srv { port: 2552, target: "node.local".to_string() }]);
+  assert!(matches!(decode_response(10, &response), Err(DnsQueryError::Malformed)));
+}
+
+#[test]
+fn decode_response_reports_truncation() {
+  let query = encode_query(11, "svc.local", DnsRecordType::A).unwrap();
+  let mut response = build_response(&query, &[], &[]);
+  response[2] |= 0x02;
+  assert!(matches!(decode_response(11, &response), Err(DnsQueryError::Truncated)));
+}
+
+#[tokio::test]
+async fn resolve_members_retries_truncated_answers_over_tcp() {
+  let nameserver = spawn_truncating_dns_stub().await;
+  let config =
+    DnsClusterConfig::new().with_query_name("large.example.local").with_port(2552).with_nameserver(nameserver);
+  let members = resolve_members(&config).await.unwrap();
+  assert_eq!(members.len(), 40);
+  assert!(members.contains(&"10.0.2.40:2552".to_string()));
+}
+
+#[tokio::test]
+async fn resolve_members_combines_a_records_with_port() {
+  let nameserver = spawn_dns_stub().await;
+  let config =
+    DnsClusterConfig::new().with_query_name("members.example.local").with_port(2552).with_nameserver(nameserver);
+  let members = resolve_members(&config).await.unwrap();
+  assert_eq!(members, vec!["10.0.0.1:2552".to_string(), "10.0.0.2:2552".to_string()]);
+}
+
+#[tokio::test]
+async fn resolve_members_uses_srv_targets_and_additional_records() {
+  let nameserver = spawn_dns_stub().await;
+  let config = DnsClusterConfig::new()
+    .with_query_name("_fraktor._tcp.example.local")
+    .with_record_type(DnsRecordType::Srv)
+    .with_nameserver(nameserver);
+  let members = resolve_members(&config).await.unwrap();
+  assert_eq!(members, vec!["10.0.1.1:2552".to_string(), "10.0.1.2:2553".to_string()]);
+}
+
+#[tokio::test]
+async fn resolve_members_skips_srv_targets_that_fail_to_resolve() {
+  let nameserver = spawn_dns_stub().await;
+  let config = DnsClusterConfig::new()
+    .with_query_name("_partial._tcp.example.local")
+    .with_record_type(DnsRecordType::Srv)
+    .with_nameserver(nameserver);
+  let members = resolve_members(&config).await.unwrap();
+  assert_eq!(members, vec!["10.0.1.2:2553".to_string()]);
+}
+
+#[tokio::test]
+async fn resolve_members_reports_name_errors() {
+  let nameserver = spawn_dns_stub().await;
+  let config = DnsClusterConfig::new().with_query_name("unknown.example.local").with_nameserver(nameserver);
+  assert!(matches!(resolve_members(&config).await, Err(DnsQueryError::ResponseCode(3))));
+}
@@ -0,0 +1,477 @@
+//! Minimal HTTPS (HTTP/1.1 over TLS) client for the pod list and watch endpoints of the Kubernetes
+//! API.
+
+#[cfg(test)]
+mod tests;
+
+#[allow(clippy::disallowed_types)] // rustls の API が Arc を要求するため
+use alloc::sync::Arc;
+use std::{
+  collections::BTreeMap,
+  fmt::{self, Write as _},
+  future::Future,
+  io,
+  time::Duration,
+};
+
+use rustls::{
+  ClientConfig, RootCertStore,
+  crypto::ring,
+  pki_types::{CertificateDer, ServerName, pem::PemObject},
+};
+use serde_json::Value;
+use tokio::{
+  io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+  net::TcpStream,
+};
+use tokio_rustls::TlsConnector;
+
+use super::{discovered_members::MemberRoles, kubernetes_cluster_config::KubernetesClusterConfig};
+
+/// Maximum length of the status line, a header line or a chunk-size line.
+const MAX_LINE_LEN: usize = 8 * 1024;
+/// Maximum number of response headers.
+const MAX_HEADER_COUNT: usize = 128;
+/// Maximum size of a pod list response body.
+const MAX_BODY_LEN: usize = 64 * 1024 * 1024;
+/// Maximum size of a single watch event line.
+const MAX_WATCH_EVENT_LEN: usize = 4 * 1024 * 1024;
+
+/// Errors raised while talking to the Kubernetes API.
+#[derive(Debug)]
+pub(crate) enum KubernetesApiError {
+  /// The API URL is not an `https://` URL.
+  UnsupportedUrl(String),
+  /// The API URL is a plain `http://` URL, which would expose the bearer token.
+  InsecureUrl(String),
+  /// The trusted CA certificates could not be loaded or the TLS handshake failed.
+  Tls(String),
+  /// Socket level failure.
+  Io(io::Error),
+  /// The server answered with a non-success status.
+  Status(u16),
+  /// The response body could not be interpreted.
+  InvalidResponse(String),
+  /// The server did not answer within the configured timeout.
+  Timeout(&'static str),
+}
+
+impl fmt::Display for KubernetesApiError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Self::UnsupportedUrl(url) => write!(f, "unsupported api url: {url}"),
+      | Self::InsecureUrl(url) => {
+        write!(f, "refusing plain http api url {url}: the bearer token would be sent unencrypted")
+      },
+      | Self::Tls(reason) => write!(f, "tls error: {reason}"),
+      | Self::Io(error) => write!(f, "io error: {error}"),
+      | Self::Status(status) => write!(f, "unexpected status: {status}"),
+      | Self::InvalidResponse(reason) => write!(f, "invalid response: {reason}"),
+      | Self::Timeout(operation) => write!(f, "timed out while {operation}"),
+    }
+  }
+}
+
+impl From<io::Error> for KubernetesApiError {
+  fn from(error: io::Error) -> Self {
+    Self::Io(error)
+  }
+}
+
+/// Pods matching the selector, keyed by pod name.
+///
+/// A pod that exists but is not ready to serve (not running, no IP, terminating) maps to `None`.
+pub(crate) type PodMembers = BTreeMap<String, Option<(String, Vec<String>)>>;
+
+/// Result of a pod list call.
+pub(crate) struct PodList {
+  pub(crate) resource_version: String,
+  pub(crate) pods:             PodMembers,
+}
+
+/// A single event of a pod watch stream.
+pub(crate) enum PodWatchEvent {
+  /// The pod was added or modified.
+  Applied { name: String, member: Option<(String, Vec<String>)>, resource_version: String },
+  /// The pod was deleted.
+  Deleted { name: String, resource_version: String },
+  /// Progress notification carrying only a resource version.
+  Bookmark { resource_version: String },
+  /// The watch must be restarted with a fresh list (e.g. `410 Gone`).
+  Expired,
+}
+
+// TLS 接続と (テスト用の) 平文接続を同じ読み書き経路で扱うための抽象
+trait ApiStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> ApiStream for T {}
+
+/// Client for `/api/v1/namespaces/{namespace}/pods`.
+pub(crate) struct KubernetesApiClient {
+  config:    KubernetesClusterConfig,
+  authority: String,
+  tls:       Option<(TlsConnector, ServerName<'static>)>,
+}
+
+impl KubernetesApiClient {
+  pub(crate) fn new(config: KubernetesClusterConfig) -> Result<Self, KubernetesApiError> {
+    let url = config.api_url();
+    let (rest, default_port, secure) = if let Some(rest) = url.strip_prefix("https://") {
+      (rest, 443, true)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+      if !config.plain_http_allowed() {
+        return Err(KubernetesApiError::InsecureUrl(url.to_string()));
+      }
+      (rest, 80, false)
+    } else {
+      return Err(KubernetesApiError::UnsupportedUrl(url.to_string()));
+    };
+    let authority = rest.split('/').next().unwrap_or_default();
+    if authority.is_empty() {
+      return Err(KubernetesApiError::UnsupportedUrl(url.to_string()));
+    }
+    let authority = if authority.contains(':') { authority.to_string() } else { format!("{authority}:{default_port}") };
+    let tls = if secure { Some(tls_connector(&config, &authority)?) } else { None };
+    Ok(Self { config, authority, tls })
+  }
+
+  /// Lists the pods matching the selector.
+  pub(crate) async fn list_pods(&self) -> Result<PodList, KubernetesApiError> {
+    let bytes = with_timeout(self.config.request_timeout(), "listing pods", async {
+      let mut body = self.get(&self.pods_path(None)).await?;
+      Ok(body.read_to_end(MAX_BODY_LEN).await?)
+    })
+    .await?;
+    let value: Value =
+      serde_json::from_slice(&bytes).map_err(|error| KubernetesApiError::InvalidResponse(error.to_string()))?;
+    let resource_version = value["metadata"]["resourceVersion"].as_str().unwrap_or_default().to_string();
+    let mut pods = BTreeMap::new();
+    for item in value["items"].as_array().into_iter().flatten() {
+      if let Some(name) = item["metadata"]["name"].as_str() {
+        pods.insert(name.to_string(), self.pod_member(item));
+      }
+    }
+    Ok(PodList { resource_version, pods })
+  }
+
+  /// Opens a watch stream starting after `resource_version`.
+  pub(crate) async fn watch_pods(&self, resource_version: &str) -> Result<PodWatch<'_>, KubernetesApiError> {
+    let path = self.pods_path(Some(resource_version));
+    let body = with_timeout(self.config.request_timeout(), "opening the pod watch", self.get(&path)).await?;
+    Ok(PodWatch { client: self, body })
+  }
+
+  fn pods_path(&self, watch_from: Option<&str>) -> String {
+    let mut path = format!("/api/v1/namespaces/{}/pods", percent_encode(self.config.namespace()));
+    let mut separator = '?';
+    if !self.config.label_selector().is_empty() {
+      let _ = write!(path, "{separator}labelSelector={}", percent_encode(self.config.label_selector()));
+      separator = '&';
+    }
+    if let Some(resource_version) = watch_from {
+      let _ = write!(
+        path,
+        "{separator}watch=true&allowWatchBookmarks=true&resourceVersion={}&timeoutSeconds={}",
+        percent_encode(resource_version),
+        self.config.watch_timeout().as_secs()
+      );
+    }
+    path
+  }
+
+  fn pod_member(&self, pod: &Value) -> Option<(String, Vec<String>)> {
+    if !pod["metadata"]["deletionTimestamp"].is_null() || pod["status"]["phase"].as_str() != Some("Running") {
+      return None;
+    }
+    let ip = pod["status"]["podIP"].as_str().filter(|ip| !ip.is_empty())?;
+    let roles = self
+      .config
+      .roles_label()
+      .and_then(|label| pod["metadata"]["labels"][label].as_str())
+      .map(|value| value.split(',').map(str::trim).filter(|role| !role.is_empty()).map(String::from).collect())
+      .unwrap_or_default();
+    Some((format!("{ip}:{}", self.config.port()), roles))
+  }
+
+  async fn get(&self, path: &str) -> Result<HttpBody, KubernetesApiError> {
+    let stream = TcpStream::connect(&self.authority).await?;
+    let mut stream: Box<dyn ApiStream> = match &self.tls {
+      | Some((connector, server_name)) => Box::new(
+        connector
+          .connect(server_name.clone(), stream)
+          .await
+          .map_err(|error| KubernetesApiError::Tls(format!("handshake with {}: {error}", self.authority)))?,
+      ),
+      | None => Box::new(stream),
+    };
+    let mut request =
+      format!("GET {path} HTTP/1.1\r\nHost: {}\r\nAccept: application/json\r\nConnection: close\r\n", self.authority);
+    if let Some(token) = self.config.bearer_token() {
+      let _ = write!(request, "Authorization: Bearer {token}\r\n");
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut reader = BufReader::new(stream);
+    let mut status_line = String::new();
+    read_bounded_line(&mut reader, &mut status_line).await?;
+    let status = status_line
+      .split_whitespace()
+      .nth(1)
+      .and_then(|code| code.parse::<u16>().ok())
+      .ok_or_else(|| KubernetesApiError::InvalidResponse(status_line.trim().to_string()))?;
+
+    let mut mode = BodyMode::UntilClose;
+    for index in 0.. {
+      let mut line = String::new();
+      if read_bounded_line(&mut reader, &mut line).await? == 0 {
+        break;
+      }
+      if index == MAX_HEADER_COUNT {
+        return Err(KubernetesApiError::InvalidResponse(format!("more than {MAX_HEADER_COUNT} headers")));
+      }
+      let line = line.trim_end();
+      if line.is_empty() {
+        break;
+      }
+      let Some((name, value)) = line.split_once(':') else {
+        continue;
+      };
+      let value = value.trim();
+      if name.eq_ignore_ascii_case("transfer-encoding") && value.eq_ignore_ascii_case("chunked") {
+        mode = BodyMode::Chunked { remaining: 0, finished: false };
+      } else if name.eq_ignore_ascii_case("content-length") && matches!(mode, BodyMode::UntilClose) {
+        let length = value
+          .parse::<usize>()
+          .map_err(|_| KubernetesApiError::InvalidResponse(format!("content-length: {value}")))?;
+        mode = BodyMode::Length(length);
+      }
+    }
+    if !(200..300).contains(&status) {
+      return Err(KubernetesApiError::Status(status));
+    }
+    Ok(HttpBody { reader, mode, pending: Vec::new() })
+  }
+}
+
+/// Open watch stream yielding pod events line by line.
+pub(crate) struct PodWatch<'a> {
+  client: &'a KubernetesApiClient,
+  body:   HttpBody,
+}
+
+impl PodWatch<'_> {
+  /// Returns the next event, or `None` once the server closes the stream.
+  ///
+  /// Each read must complete within the server-side watch timeout plus the request timeout,
+  /// because the server closes the stream once its own timeout has passed.
+  pub(crate) async fn next_event(&mut self) -> Result<Option<PodWatchEvent>, KubernetesApiError> {
+    let read_timeout = self.client.config.watch_timeout() + self.client.config.request_timeout();
+    loop {
+      let line = with_timeout(read_timeout, "reading the pod watch", async {
+        Ok(self.body.next_line(MAX_WATCH_EVENT_LEN).await?)
+      })
+      .await?;
+      let Some(line) = line else {
+        return Ok(None);
+      };
+      if line.trim().is_empty() {
+        continue;
+      }
+      let value: Value =
+        serde_json::from_str(&line).map_err(|error| KubernetesApiError::InvalidResponse(error.to_string()))?;
+      let object = &value["object"];
+      let resource_version = object["metadata"]["resourceVersion"].as_str().unwrap_or_default().to_string();
+      let name = object["metadata"]["name"].as_str().unwrap_or_default().to_string();
+      let event = match value["type"].as_str() {
+        | Some("ADDED" | "MODIFIED") => {
+          PodWatchEvent::Applied { member: self.client.pod_member(object), name, resource_version }
+        },
+        | Some("DELETED") => PodWatchEvent::Deleted { name, resource_version },
+        | Some("BOOKMARK") => PodWatchEvent::Bookmark { resource_version },
+        | Some("ERROR") => PodWatchEvent::Expired,
+        | _ => continue,
+      };
+      return Ok(Some(event));
+    }
+  }
+}
+
+/// Collects the members of the ready pods.
+pub(crate) fn ready_members(pods: &PodMembers) -> MemberRoles {
+  pods.values().flatten().map(|(authority, roles)| (authority.clone(), roles.clone())).collect()
+}
+
+enum BodyMode {
+  Length(usize),
+  Chunked { remaining: usize, finished: bool },
+  UntilClose,
+}
+
+struct HttpBody {
+  reader:  BufReader<Box<dyn ApiStream>>,
+  mode:    BodyMode,
+  pending: Vec<u8>,
+}
+
+impl HttpBody {
+  async fn read_to_end(&mut self, limit: usize) -> io::Result<Vec<u8>> {
+    if let BodyMode::Length(length) = self.mode
+      && length > limit
+    {
+      return Err(body_too_large(limit));
+    }
+    let mut bytes = core::mem::take(&mut self.pending);
+    while let Some(chunk) = self.read_chunk().await? {
+      if bytes.len() + chunk.len() > limit {
+        return Err(body_too_large(limit));
+      }
+      bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+  }
+
+  async fn next_line(&mut self, limit: usize) -> io::Result<Option<String>> {
+    loop {
+      if let Some(position) = self.pending.iter().position(|byte| *byte == b'\n') {
+        let line: Vec<u8> = self.pending.drain(..=position).collect();
+        return Ok(Some(String::from_utf8_lossy(&line).into_owned()));
+      }
+      if self.pending.len() > limit {
+        return Err(body_too_large(limit));
+      }
+      match self.read_chunk().await? {
+        | Some(chunk) => self.pending.extend_from_slice(&chunk),
+        | None if self.pending.is_empty() => return Ok(None),
+        | None => return Ok(Some(String::from_utf8_lossy(&core::mem::take(&mut self.pending)).into_owned())),
+      }
+    }
+  }
+
+  // 本文の次の断片を返す。チャンク転送の場合はチャンク境界を取り除く
+  async fn read_chunk(&mut self) -> io::Result<Option<Vec<u8>>> {
+    match &mut self.mode {
+      | BodyMode::Length(0) | BodyMode::Chunked { finished: true, .. } => Ok(None),
+      | BodyMode::Length(remaining) => {
+        let mut buffer = vec![0; (*remaining).min(8192)];
+        let read = self.reader.read(&mut buffer).await?;
+        if read == 0 {
+          return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+        *remaining -= read;
+        buffer.truncate(read);
+        Ok(Some(buffer))
+      },
+      | BodyMode::UntilClose => {
+        let mut buffer = vec![0; 8192];
+        let read = self.reader.read(&mut buffer).await?;
+        if read == 0 {
+          self.mode = BodyMode::Length(0);
+          return Ok(None);
+        }
+        buffer.truncate(read);
+        Ok(Some(buffer))
+      },
+      | BodyMode::Chunked { remaining, finished } => {
+        if *remaining == 0 {
+          let mut size_line = String::new();
+          if read_bounded_line(&mut self.reader, &mut size_line).await? == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+          }
+          let size = size_line.split(';').next().unwrap_or_default().trim();
+          *remaining =
+            usize::from_str_radix(size, 16).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+          if *remaining == 0 {
+            *finished = true;
+            // トレーラーを読み捨てる
+            loop {
+              let mut trailer = String::new();
+              if read_bounded_line(&mut self.reader, &mut trailer).await? == 0 || trailer.trim().is_empty() {
+                break;
+              }
+            }
+            return Ok(None);
+          }
+        }
+        let mut buffer = vec![0; (*remaining).min(8192)];
+        let read = self.reader.read(&mut buffer).await?;
+        if read == 0 {
+          return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+        *remaining -= read;
+        if *remaining == 0 {
+          let mut crlf = String::new();
+          read_bounded_line(&mut self.reader, &mut crlf).await?;
+        }
+        buffer.truncate(read);
+        Ok(Some(buffer))
+      },
+    }
+  }
+}
+
+// 行末が見つからないまま上限に達した行は、メモリを食い潰されないよう不正な応答として扱う
+async fn read_bounded_line<R: AsyncBufRead + Unpin>(reader: &mut R, line: &mut String) -> io::Result<usize> {
+  let read = (&mut *reader).take(MAX_LINE_LEN as u64).read_line(line).await?;
+  if read == MAX_LINE_LEN && !line.ends_with('\n') {
+    return Err(io::Error::new(io::ErrorKind::InvalidData, format!("line exceeds {MAX_LINE_LEN} bytes")));
+  }
+  Ok(read)
+}
+
+fn body_too_large(limit: usize) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, format!("response exceeds {limit} bytes"))
+}
+
+async fn with_timeout<T>(
+  timeout: Duration,
+  operation: &'static str,
+  future: impl Future<Output = Result<T, KubernetesApiError>>,
+) -> Result<T, KubernetesApiError> {
+  tokio::time::timeout(timeout, future).await.map_err(|_| KubernetesApiError::Timeout(operation))?
+}
+
+fn percent_encode(value: &str) -> String {
+  let mut encoded = String::with_capacity(value.len());
+  for byte in value.bytes() {
+    if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+      encoded.push(char::from(byte));
+    } else {
+      let _ = write!(encoded, "%{byte:02X}");
+    }
+  }
+  encoded
+}
+
+// サービスアカウントの CA 証明書だけを信頼し、API サーバの証明書をホスト名と照合する
+#[allow(clippy::disallowed_types)]
+fn tls_connector(
+  config: &KubernetesClusterConfig,
+  authority: &str,
+) -> Result<(TlsConnector, ServerName<'static>), KubernetesApiError> {
+  let pem = match config.ca_certificate_pem() {
+    | Some(pem) => pem.to_vec(),
+    | None => std::fs::read(config.ca_certificate_path()).map_err(|error| {
+      KubernetesApiError::Tls(format!("failed to read {}: {error}", config.ca_certificate_path().display()))
+    })?,
+  };
+  let mut roots = RootCertStore::empty();
+  for certificate in CertificateDer::pem_slice_iter(&pem) {
+    let certificate =
+      certificate.map_err(|error| KubernetesApiError::Tls(format!("invalid ca certificate: {error}")))?;
+    roots.add(certificate).map_err(|error| KubernetesApiError::Tls(format!("invalid ca certificate: {error}")))?;
+  }
+  if roots.is_empty() {
+    return Err(KubernetesApiError::Tls("no ca certificate configured".into()));
+  }
+  let client = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+    .with_safe_default_protocol_versions()
+    .map_err(|error| KubernetesApiError::Tls(error.to_string()))?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+  let host = authority.rsplit_once(':').map_or(authority, |(host, _)| host);
+  let host = host.trim_start_matches('[').trim_end_matches(']');
+  let server_name = ServerName::try_from(host.to_string())
+    .map_err(|error| KubernetesApiError::Tls(format!("invalid server name {host}: {error}")))?;
+  Ok((TlsConnector::from(Arc::new(client)), server_name))
+}
@@ -0,0 +1,217 @@
+use std::sync::Arc;
+
+use rustls::{
+  ServerConfig,
+  crypto::ring,
+  pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+};
+use serde_json::json;
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::TcpListener,
+};
+use tokio_rustls::TlsAcceptor;
+
+use super::{KubernetesApiClient, KubernetesApiError, percent_encode};
+use crate::std::KubernetesClusterConfig;
+
+const CA_PEM: &[u8] = include_bytes!("../../../tests/fixtures/tls/ca.pem");
+const NODE_PEM: &[u8] = include_bytes!("../../../tests/fixtures/tls/node.pem");
+const NODE_KEY_PEM: &[u8] = include_bytes!("../../../tests/fixtures/tls/node-key.pem");
+
+fn client() -> KubernetesApiClient {
+  let config = KubernetesClusterConfig::new()
+    .with_api_url("http://10.0.0.1:8001")
+    .with_plain_http_for_tests()
+    .with_namespace("prod")
+    .with_label_selector("app=fraktor,tier in (a,b)")
+    .with_port(2552)
+    .with_roles_label("roles");
+  KubernetesApiClient::new(config).unwrap()
+}
+
+#[test]
+fn percent_encode_escapes_reserved_characters() {
+  assert_eq!(percent_encode("app=fraktor,tier in (a)"), "app%3Dfraktor%2Ctier%20in%20%28a%29");
+  assert_eq!(percent_encode("a-b_c.d~"), "a-b_c.d~");
+}
+
+#[test]
+fn pods_path_includes_selector_and_watch_parameters() {
+  let client = client();
+  assert_eq!(
+    client.pods_path(None),
+    "/api/v1/namespaces/prod/pods?labelSelector=app%3Dfraktor%2Ctier%20in%20%28a%2Cb%29"
+  );
+  assert!(
+    client
+      .pods_path(Some("42"))
+      .ends_with("&watch=true&allowWatchBookmarks=true&resourceVersion=42&timeoutSeconds=300")
+  );
+}
+
+#[test]
+fn new_defaults_https_port_443_with_configured_ca() {
+  let config =
+    KubernetesClusterConfig::new().with_api_url("https://kubernetes.default.svc").with_ca_certificate_pem(CA_PEM);
+  let client = KubernetesApiClient::new(config).unwrap();
+  assert_eq!(client.authority, "kubernetes.default.svc:443");
+  assert!(client.tls.is_some());
+}
+
+#[test]
+fn new_rejects_plain_http_without_test_override() {
+  let config = KubernetesClusterConfig::new().with_api_url("http://kube-proxy/");
+  assert!(matches!(KubernetesApiClient::new(config), Err(KubernetesApiError::InsecureUrl(_))));
+  let config = KubernetesClusterConfig::new().with_api_url("http://kube-proxy/").with_plain_http_for_tests();
+  assert_eq!(KubernetesApiClient::new(config).unwrap().authority, "kube-proxy:80");
+  let config = KubernetesClusterConfig::new().with_api_url("ftp://kube-proxy/");
+  assert!(matches!(KubernetesApiClient::new(config), Err(KubernetesApiError::UnsupportedUrl(_))));
+}
+
+#[test]
+fn new_fails_when_ca_certificate_is_missing() {
+  let config = KubernetesClusterConfig::new()
+    .with_api_url("https://kubernetes.default.svc")
+    .with_ca_certificate_path("/nonexistent/fraktor/ca.crt");
+  assert!(matches!(KubernetesApiClient::new(config), Err(KubernetesApiError::Tls(_))));
+}
+
+// 1 リクエストだけ TLS で受け付け、Authorization ヘッダを返却値として渡すモック API サーバ
+async fn serve_tls_once(listener: TcpListener, body: &'static str) -> String {
+  let certificates = CertificateDer::pem_slice_iter(NODE_PEM).collect::<Result<Vec<_>, _>>().unwrap();
+  let key = PrivateKeyDer::from_pem_slice(NODE_KEY_PEM).unwrap();
+  let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+    .with_safe_default_protocol_versions()
+    .unwrap()
+    .with_no_client_auth()
+    .with_single_cert(certificates, key)
+    .unwrap();
+  let (stream, _) = listener.accept().await.unwrap();
+  let mut stream = TlsAcceptor::from(Arc::new(config)).accept(stream).await.unwrap();
+  let mut request = Vec::new();
+  let mut buffer = [0_u8; 1024];
+  while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+    let read = stream.read(&mut buffer).await.unwrap();
+    assert!(read > 0);
+    request.extend_from_slice(&buffer[..read]);
+  }
+  let response =
+    format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+  stream.write_all(response.as_bytes()).await.unwrap();
+  stream.shutdown().await.unwrap();
+  let request = String::from_utf8(request).unwrap();
+  request.lines().find(|line| line.starts_with("Authorization:")).unwrap_or_default().to_string()
+}
+
+#[tokio::test]
+async fn list_pods_over_tls_trusting_configured_ca() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let port = listener.local_addr().unwrap().port();
+  let server = tokio::spawn(serve_tls_once(listener, r#"{"metadata":{"resourceVersion":"7"},"items":[]}"#));
+  let config = KubernetesClusterConfig::new()
+    .with_api_url(format!("https://localhost:{port}"))
+    .with_ca_certificate_pem(CA_PEM)
+    .with_bearer_token("secret-token");
+
+  let list = KubernetesApiClient::new(config).unwrap().list_pods().await.unwrap();
+
+  assert_eq!(list.resource_version, "7");
+  assert_eq!(server.await.unwrap(), "Authorization: Bearer secret-token");
+}
+
+#[tokio::test]
+async fn list_pods_rejects_server_not_signed_by_configured_ca() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let port = listener.local_addr().unwrap().port();
+  let server = tokio::spawn(async move {
+    let (_stream, _) = listener.accept().await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+  });
+  // サーバ証明書ではなく自身の葉証明書を CA として信頼させるため、検証は失敗する
+  let config =
+    KubernetesClusterConfig::new().with_api_url(format!("https://localhost:{port}")).with_ca_certificate_pem(NODE_PEM);
+
+  let result = KubernetesApiClient::new(config).unwrap().list_pods().await;
+
+  assert!(result.is_err());
+  server.abort();
+}
+
+// 1 リクエストだけ平文で受け付け、渡された応答を書き込んだ後は接続を保持し続けるモック API サーバ
+async fn serve_plain_once(listener: TcpListener, response: Vec<u8>) {
+  let (mut stream, _) = listener.accept().await.unwrap();
+  let mut buffer = [0_u8; 1024];
+  let _ = stream.read(&mut buffer).await.unwrap();
+  stream.write_all(&response).await.unwrap();
+  tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+}
+
+fn plain_config(port: u16) -> KubernetesClusterConfig {
+  KubernetesClusterConfig::new()
+    .with_api_url(format!("http://127.0.0.1:{port}"))
+    .with_plain_http_for_tests()
+    .with_request_timeout(std::time::Duration::from_millis(200))
+}
+
+#[tokio::test]
+async fn list_pods_rejects_oversized_header_line() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let port = listener.local_addr().unwrap().port();
+  let mut response = b"HTTP/1.1 200 OK\r\nX-Padding: ".to_vec();
+  response.extend(std::iter::repeat_n(b'a', 16 * 1024));
+  let server = tokio::spawn(serve_plain_once(listener, response));
+
+  let result = KubernetesApiClient::new(plain_config(port)).unwrap().list_pods().await;
+
+  assert!(matches!(result, Err(KubernetesApiError::Io(_))));
+  server.abort();
+}
+
+#[tokio::test]
+async fn list_pods_rejects_oversized_content_length() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let port = listener.local_addr().unwrap().port();
+  let response = b"HTTP/1.1 200 OK\r\nContent-Length: 1099511627776\r\n\r\n".to_vec();
+  let server = tokio::spawn(serve_plain_once(listener, response));
+
+  let result = KubernetesApiClient::new(plain_config(port)).unwrap().list_pods().await;
+
+  assert!(matches!(result, Err(KubernetesApiError::Io(_))));
+  server.abort();
+}
+
+#[tokio::test]
+async fn list_pods_times_out_when_server_stalls() {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let port = listener.local_addr().unwrap().port();
+  let response = b"HTTP/1.1 200 OK\r\nContent-Length: 64\r\n\r\n{".to_vec();
+  let server = tokio::spawn(serve_plain_once(listener, response));
+
+  let result = KubernetesApiClient::new(plain_config(port)).unwrap().list_pods().await;
+
+  assert!(matches!(result, Err(KubernetesApiError::Timeout(_))));
+  server.abort();
+}
+
+#[test]
+fn pod_member_requires_running_pod_with_ip() {
+  let client = client();
+  let running = json!({
+    "metadata": {"name": "a", "labels": {"roles": "frontend, backend"}},
+    "status": {"phase": "Running", "podIP": "10.1.0.5"}
+  });
+  assert_eq!(
+    client.pod_member(&running),
+    Some(("10.1.0.5:2552".to_string(), vec!["frontend".to_string(), "backend".to_string()]))
+  );
+
+  let pending = json!({"metadata": {"name": "b"}, "status": {"phase": "Pending", "podIP": "10.1.0.6"}});
+  assert_eq!(client.pod_member(&pending), None);
+
+  let terminating = json!({
+    "metadata": {"name": "c", "deletionTimestamp": "2024-01-01T00:00:00Z"},
+    "status": {"phase": "Running", "podIP": "10.1.0.7"}
+  });
+  assert_eq!(client.pod_member(&terminating), None);
+}
@@ -0,0 +1,236 @@
+//! Configuration for the Kubernetes cluster provider.
+
+#[cfg(test)]
+mod tests;
+
+use std::{path::PathBuf, time::Duration};
+
+/// Configuration for [`KubernetesClusterProvider`](super::KubernetesClusterProvider).
+///
+/// The provider talks HTTPS to the API server and trusts the CA certificate mounted into every
+/// pod with its service account (`/var/run/secrets/kubernetes.io/serviceaccount/ca.crt`), so the
+/// defaults work in-cluster once the service-account token is set with
+/// [`with_bearer_token`](Self::with_bearer_token). Plain `http://` endpoints are rejected.
+#[derive(Clone, Debug)]
+pub struct KubernetesClusterConfig {
+  api_url:         String,
+  namespace:       String,
+  label_selector:  String,
+  port:            u16,
+  roles_label:     Option<String>,
+  bearer_token:    Option<String>,
+  ca_path:         PathBuf,
+  ca_pem:          Option<Vec<u8>>,
+  plain_http:      bool,
+  watch:           bool,
+  retry_interval:  Duration,
+  watch_timeout:   Duration,
+  request_timeout: Duration,
+}
+
+impl Default for KubernetesClusterConfig {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl KubernetesClusterConfig {
+  /// Path of the CA certificate mounted with the pod's service account.
+  pub const SERVICE_ACCOUNT_CA_PATH: &'static str = "/var/run/secrets/kubernetes.io/serviceaccount/ca.crt";
+
+  /// Creates a new configuration with default values.
+  #[must_use]
+  pub fn new() -> Self {
+    Self {
+      api_url:         String::from("https://kubernetes.default.svc"),
+      namespace:       String::from("default"),
+      label_selector:  String::new(),
+      port:            8080,
+      roles_label:     None,
+      bearer_token:    None,
+      ca_path:         PathBuf::from(Self::SERVICE_ACCOUNT_CA_PATH),
+      ca_pem:          None,
+      plain_http:      false,
+      watch:           true,
+      retry_interval:  Duration::from_secs(5),
+      watch_timeout:   Duration::from_secs(300),
+      request_timeout: Duration::from_secs(10),
+    }
+  }
+
+  /// Sets the base URL of the Kubernetes API (`https://host[:port]`).
+  #[must_use]
+  pub fn with_api_url(mut self, url: impl Into<String>) -> Self {
+    self.api_url = url.into();
+    self
+  }
+
+  /// Sets the namespace the pods are listed from.
+  #[must_use]
+  pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+    self.namespace = namespace.into();
+    self
+  }
+
+  /// Sets the label selector identifying cluster members (e.g. `app=my-service`).
+  #[must_use]
+  pub fn with_label_selector(mut self, selector: impl Into<String>) -> Self {
+    self.label_selector = selector.into();
+    self
+  }
+
+  /// Sets the port used for cluster communication.
+  #[must_use]
+  pub const fn with_port(mut self, port: u16) -> Self {
+    self.port = port;
+    self
+  }
+
+  /// Sets the pod label holding comma-separated member roles.
+  #[must_use]
+  pub fn with_roles_label(mut self, label: impl Into<String>) -> Self {
+    self.roles_label = Some(label.into());
+    self
+  }
+
+  /// Sets the bearer token sent in the `Authorization` header.
+  #[must_use]
+  pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+    self.bearer_token = Some(token.into());
+    self
+  }
+
+  /// Sets the file holding the PEM-encoded CA certificates trusted for the API server.
+  #[must_use]
+  pub fn with_ca_certificate_path(mut self, path: impl Into<PathBuf>) -> Self {
+    self.ca_path = path.into();
+    self.ca_pem = None;
+    self
+  }
+
+  /// Sets the PEM-encoded CA certificates trusted for the API server.
+  #[must_use]
+  pub fn with_ca_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+    self.ca_pem = Some(pem.into());
+    self
+  }
+
+  /// Accepts plain `http://` API URLs, sending the bearer token unencrypted. Test use only.
+  #[cfg(any(test, feature = "test-support"))]
+  #[must_use]
+  pub const fn with_plain_http_for_tests(mut self) -> Self {
+    self.plain_http = true;
+    self
+  }
+
+  /// Enables or disables watching; when disabled the pods are re-listed every retry interval.
+  #[must_use]
+  pub const fn with_watch(mut self, watch: bool) -> Self {
+    self.watch = watch;
+    self
+  }
+
+  /// Sets the delay before re-listing after a failure (or between polls when watching is off).
+  #[must_use]
+  pub const fn with_retry_interval(mut self, interval: Duration) -> Self {
+    self.retry_interval = interval;
+    self
+  }
+
+  /// Sets the server-side timeout of a single watch request.
+  #[must_use]
+  pub const fn with_watch_timeout(mut self, timeout: Duration) -> Self {
+    self.watch_timeout = timeout;
+    self
+  }
+
+  /// Sets the client-side timeout of connecting, listing the pods and each read of a watch
+  /// beyond the server-side watch timeout.
+  #[must_use]
+  pub const fn with_request_timeout(mut self, timeout: Duration) -> Self {
+    self.request_timeout = timeout;
+    self
+  }
+
+  /// Returns the API base URL.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // String の Deref が const でないため const fn にできない
+  pub fn api_url(&self) -> &str {
+    &self.api_url
+  }
+
+  /// Returns the namespace.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // String の Deref が const でないため const fn にできない
+  pub fn namespace(&self) -> &str {
+    &self.namespace
+  }
+
+  /// Returns the label selector.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // String の Deref が const でないため const fn にできない
+  pub fn label_selector(&self) -> &str {
+    &self.label_selector
+  }
+
+  /// Returns the port.
+  #[must_use]
+  pub const fn port(&self) -> u16 {
+    self.port
+  }
+
+  /// Returns the roles label.
+  #[must_use]
+  pub fn roles_label(&self) -> Option<&str> {
+    self.roles_label.as_deref()
+  }
+
+  /// Returns the bearer token.
+  #[must_use]
+  pub fn bearer_token(&self) -> Option<&str> {
+    self.bearer_token.as_deref()
+  }
+
+  /// Returns the path of the trusted CA certificates, used unless PEM data was set inline.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // PathBuf の Deref が const でないため const fn にできない
+  pub fn ca_certificate_path(&self) -> &std::path::Path {
+    &self.ca_path
+  }
+
+  /// Returns the inline PEM-encoded CA certificates, if set.
+  #[must_use]
+  pub fn ca_certificate_pem(&self) -> Option<&[u8]> {
+    self.ca_pem.as_deref()
+  }
+
+  /// Returns whether plain `http://` API URLs are accepted.
+  #[must_use]
+  pub const fn plain_http_allowed(&self) -> bool {
+    self.plain_http
+  }
+
+  /// Returns whether watching is enabled.
+  #[must_use]
+  pub const fn watch(&self) -> bool {
+    self.watch
+  }
+
+  /// Returns the retry interval.
+  #[must_use]
+  pub const fn retry_interval(&self) -> Duration {
+    self.retry_interval
+  }
+
+  /// Returns the watch timeout.
+  #[must_use]
+  pub const fn watch_timeout(&self) -> Duration {
+    self.watch_timeout
+  }
+
+  /// Returns the request timeout.
+  #[must_use]
+  pub const fn request_timeout(&self) -> Duration {
+    self.request_timeout
+  }
+}
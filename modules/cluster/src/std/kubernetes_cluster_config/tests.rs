@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use super::KubernetesClusterConfig;
+
+#[test]
+fn kubernetes_cluster_config_default_values() {
+  let config = KubernetesClusterConfig::new();
+  assert_eq!(config.api_url(), "https://kubernetes.default.svc");
+  assert_eq!(config.ca_certificate_path(), std::path::Path::new(KubernetesClusterConfig::SERVICE_ACCOUNT_CA_PATH));
+  assert!(config.ca_certificate_pem().is_none());
+  assert!(!config.plain_http_allowed());
+  assert_eq!(config.namespace(), "default");
+  assert_eq!(config.label_selector(), "");
+  assert_eq!(config.port(), 8080);
+  assert!(config.roles_label().is_none());
+  assert!(config.bearer_token().is_none());
+  assert!(config.watch());
+  assert_eq!(config.request_timeout(), Duration::from_secs(10));
+}
+
+#[test]
+fn kubernetes_cluster_config_builder_pattern() {
+  let config = KubernetesClusterConfig::new()
+    .with_api_url("http://10.0.0.1:6443")
+    .with_namespace("prod")
+    .with_label_selector("app=fraktor")
+    .with_port(9090)
+    .with_roles_label("fraktor.io/roles")
+    .with_bearer_token("token")
+    .with_watch(false)
+    .with_retry_interval(Duration::from_secs(1))
+    .with_watch_timeout(Duration::from_secs(60))
+    .with_request_timeout(Duration::from_secs(3));
+
+  assert_eq!(config.api_url(), "http://10.0.0.1:6443");
+  assert_eq!(config.namespace(), "prod");
+  assert_eq!(config.label_selector(), "app=fraktor");
+  assert_eq!(config.port(), 9090);
+  assert_eq!(config.roles_label(), Some("fraktor.io/roles"));
+  assert_eq!(config.bearer_token(), Some("token"));
+  assert!(!config.watch());
+  assert_eq!(config.retry_interval(), Duration::from_secs(1));
+  assert_eq!(config.watch_timeout(), Duration::from_secs(60));
+  assert_eq!(config.request_timeout(), Duration::from_secs(3));
+}
@@ -0,0 +1,213 @@
+//! Kubernetes cluster provider discovering members from labelled pods.
+//!
+//! The provider lists the pods matching a label selector through the Kubernetes API and then
+//! follows them with a watch request, publishing topology updates whenever a ready pod appears
+//! or disappears. A pod counts as a member while it is `Running`, has a pod IP and is not
+//! terminating; its authority is `podIP:port`.
+//!
+//! # Required RBAC Permissions
+//!
+//! ```yaml
+//! rules:
+//!   - apiGroups: [""]
+//!     resources: ["pods"]
+//!     verbs: ["list", "watch"]
+//! ```
+//!
+//! # Example
+//!
+//! ```text
+//! use fraktor_cluster_rs::std::KubernetesClusterConfig;
+//! use fraktor_cluster_rs::core::ClusterExtensionInstaller;
+//!
+//! let k8s_config = KubernetesClusterConfig::new()
+//!     .with_namespace("prod")
+//!     .with_label_selector("app=my-service")
+//!     .with_port(8080);
+//!
+//! let installer = ClusterExtensionInstaller::new_with_kubernetes(config, k8s_config);
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use fraktor_actor_rs::core::event_stream::EventStreamGeneric;
+use fraktor_remote_rs::core::BlockListProvider;
+use fraktor_utils_rs::{
+  core::{
+    runtime_toolbox::{RuntimeToolbox, SyncMutexFamily, ToolboxMutex},
+    sync::ArcShared,
+  },
+  std::runtime_toolbox::StdToolbox,
+};
+use tokio::task::JoinHandle;
+
+use super::{
+  discovered_members::DiscoveredMembers,
+  kubernetes_api_client::{KubernetesApiClient, PodList, PodWatchEvent, ready_members},
+  kubernetes_cluster_config::KubernetesClusterConfig,
+};
+use crate::core::{ClusterEvent, ClusterProvider, ClusterProviderError, StartupMode};
+
+type SharedMembers = ArcShared<ToolboxMutex<DiscoveredMembers, StdToolbox>>;
+
+/// Kubernetes cluster provider that discovers pods via the Kubernetes API.
+///
+/// The discovery loop runs on the ambient tokio runtime, so `start_member` and
+/// `start_client` must be called from within one.
+pub struct KubernetesClusterProvider {
+  advertised_address: String,
+  self_roles:         Vec<String>,
+  config:             KubernetesClusterConfig,
+  members:            SharedMembers,
+  startup_mode:       Option<StartupMode>,
+  watcher_handle:     Option<JoinHandle<()>>,
+}
+
+impl KubernetesClusterProvider {
+  /// Creates a new Kubernetes cluster provider.
+  #[must_use]
+  pub fn new(
+    event_stream: ArcShared<EventStreamGeneric<StdToolbox>>,
+    block_list_provider: ArcShared<dyn BlockListProvider>,
+    advertised_address: impl Into<String>,
+  ) -> Self {
+    let members = DiscoveredMembers::new(event_stream, block_list_provider);
+    Self {
+      advertised_address: advertised_address.into(),
+      self_roles:         Vec::new(),
+      config:             KubernetesClusterConfig::new(),
+      members:            ArcShared::new(<StdToolbox as RuntimeToolbox>::MutexFamily::create(members)),
+      startup_mode:       None,
+      watcher_handle:     None,
+    }
+  }
+
+  /// Sets the Kubernetes configuration.
+  #[must_use]
+  pub fn with_kubernetes_config(mut self, config: KubernetesClusterConfig) -> Self {
+    self.config = config;
+    self
+  }
+
+  /// Sets the roles advertised for the local member.
+  #[must_use]
+  pub fn with_self_roles(mut self, roles: Vec<String>) -> Self {
+    self.self_roles = roles;
+    self
+  }
+
+  /// Returns the advertised address.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // String の Deref が const でないため const fn にできない
+  pub fn advertised_address(&self) -> &str {
+    &self.advertised_address
+  }
+
+  /// Returns whether the provider has been started.
+  #[must_use]
+  pub const fn is_started(&self) -> bool {
+    self.startup_mode.is_some()
+  }
+
+  /// Returns the current member count.
+  #[must_use]
+  pub fn member_count(&self) -> usize {
+    self.members.lock().len()
+  }
+
+  fn start(&mut self, mode: StartupMode) -> Result<(), ClusterProviderError> {
+    let client = KubernetesApiClient::new(self.config.clone()).map_err(|error| {
+      let reason = format!("kubernetes api client: {error}");
+      match mode {
+        | StartupMode::Member => ClusterProviderError::start_member(reason),
+        | StartupMode::Client => ClusterProviderError::start_client(reason),
+      }
+    })?;
+    self.startup_mode = Some(mode);
+    if matches!(mode, StartupMode::Member) {
+      // 初回トポロジとして自ノードを publish する
+      self.members.lock().add_self(&self.advertised_address, self.self_roles.clone());
+    }
+
+    let members = ArcShared::clone(&self.members);
+    let config = self.config.clone();
+    self.watcher_handle = Some(tokio::spawn(async move { run_discovery(client, config, members).await }));
+
+    self.members.lock().publish(ClusterEvent::Startup { address: self.advertised_address.clone(), mode });
+    Ok(())
+  }
+}
+
+async fn run_discovery(client: KubernetesApiClient, config: KubernetesClusterConfig, members: SharedMembers) {
+  loop {
+    let relist_now = match client.list_pods().await {
+      | Ok(list) => {
+        members.lock().update(ready_members(&list.pods));
+        if config.watch() { follow_watch(&client, list, &members).await } else { false }
+      },
+      | Err(_) => false,
+    };
+    if !relist_now {
+      tokio::time::sleep(config.retry_interval()).await;
+    }
+  }
+}
+
+// watch が期限切れ (410 Gone) になった場合は true を返し、即座に再 list させる
+async fn follow_watch(client: &KubernetesApiClient, list: PodList, members: &SharedMembers) -> bool {
+  let mut pods = list.pods;
+  let mut resource_version = list.resource_version;
+  loop {
+    let Ok(mut watch) = client.watch_pods(&resource_version).await else {
+      return false;
+    };
+    // サーバ側のタイムアウトで閉じられた場合は最後の resourceVersion から watch を再開する。
+    // 一件も受信せずに閉じられた場合は待機してから list し直す
+    let mut received = false;
+    loop {
+      let event = watch.next_event().await;
+      received |= matches!(event, Ok(Some(_)));
+      match event {
+        | Ok(Some(PodWatchEvent::Applied { name, member, resource_version: version })) => {
+          pods.insert(name, member);
+          resource_version = version;
+        },
+        | Ok(Some(PodWatchEvent::Deleted { name, resource_version: version })) => {
+          pods.remove(&name);
+          resource_version = version;
+        },
+        | Ok(Some(PodWatchEvent::Bookmark { resource_version: version })) => {
+          resource_version = version;
+          continue;
+        },
+        | Ok(Some(PodWatchEvent::Expired)) => return true,
+        | Ok(None) if received => break,
+        | Ok(None) => return false,
+        | Err(_) => return false,
+      }
+      members.lock().update(ready_members(&pods));
+    }
+  }
+}
+
+impl ClusterProvider for KubernetesClusterProvider {
+  fn start_member(&mut self) -> Result<(), ClusterProviderError> {
+    self.start(StartupMode::Member)
+  }
+
+  fn start_client(&mut self) -> Result<(), ClusterProviderError> {
+    self.start(StartupMode::Client)
+  }
+
+  fn shutdown(&mut self, _graceful: bool) -> Result<(), ClusterProviderError> {
+    if let Some(handle) = self.watcher_handle.take() {
+      handle.abort();
+    }
+    let mode = self.startup_mode.take().unwrap_or(StartupMode::Member);
+    let mut members = self.members.lock();
+    members.clear();
+    members.publish(ClusterEvent::Shutdown { address: self.advertised_address.clone(), mode });
+    Ok(())
+  }
+}
@@ -0,0 +1,230 @@
+//! Tests for KubernetesClusterProvider against a local mock API server.
+
+use std::{
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+use fraktor_actor_rs::core::event_stream::{
+  EventStreamEvent, EventStreamGeneric, EventStreamSubscriber, EventStreamSubscriptionGeneric, subscriber_handle,
+};
+use fraktor_remote_rs::core::BlockListProvider;
+use fraktor_utils_rs::{core::sync::ArcShared, std::runtime_toolbox::StdToolbox};
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::{TcpListener, TcpStream},
+};
+
+use super::KubernetesClusterProvider;
+use crate::{
+  core::{ClusterEvent, ClusterProvider, StartupMode},
+  std::KubernetesClusterConfig,
+};
+
+struct EmptyBlockList;
+
+impl BlockListProvider for EmptyBlockList {
+  fn blocked_members(&self) -> Vec<String> {
+    Vec::new()
+  }
+}
+
+#[derive(Clone)]
+struct RecordingClusterEvents {
+  events: ArcShared<Mutex<Vec<ClusterEvent>>>,
+}
+
+impl RecordingClusterEvents {
+  fn new() -> Self {
+    Self { events: ArcShared::new(Mutex::new(Vec::new())) }
+  }
+
+  fn events(&self) -> Vec<ClusterEvent> {
+    self.events.lock().unwrap().clone()
+  }
+
+  fn topology_updates(&self) -> Vec<(Vec<String>, Vec<String>)> {
+    self
+      .events()
+      .into_iter()
+      .filter_map(|event| match event {
+        | ClusterEvent::TopologyUpdated { joined, left, .. } => Some((joined, left)),
+        | _ => None,
+      })
+      .collect()
+  }
+}
+
+impl EventStreamSubscriber<StdToolbox> for RecordingClusterEvents {
+  fn on_event(&mut self, event: &EventStreamEvent<StdToolbox>) {
+    if let EventStreamEvent::Extension { name, payload } = event {
+      if name == "cluster" {
+        if let Some(cluster_event) = payload.payload().downcast_ref::<ClusterEvent>() {
+          self.events.lock().unwrap().push(cluster_event.clone());
+        }
+      }
+    }
+  }
+}
+
+fn subscribe_recorder(
+  event_stream: &ArcShared<EventStreamGeneric<StdToolbox>>,
+) -> (RecordingClusterEvents, EventStreamSubscriptionGeneric<StdToolbox>) {
+  let subscriber_impl = RecordingClusterEvents::new();
+  let subscriber = subscriber_handle(subscriber_impl.clone());
+  let subscription = EventStreamGeneric::subscribe_arc(event_stream, &subscriber);
+  (subscriber_impl, subscription)
+}
+
+fn pod(name: &str, ip: &str, phase: &str, roles: Option<&str>) -> String {
+  let labels = roles.map(|roles| format!(r#","labels":{{"fraktor.io/roles":"{roles}"}}"#)).unwrap_or_default();
+  format!(
+    r#"{{"metadata":{{"name":"{name}","resourceVersion":"1"{labels}}},"status":{{"phase":"{phase}","podIP":"{ip}"}}}}"#
+  )
+}
+
+async fn read_request_head(stream: &mut TcpStream) -> String {
+  let mut head = Vec::new();
+  let mut byte = [0_u8; 1];
+  while !head.ends_with(b"\r\n\r\n") {
+    if stream.read(&mut byte).await.unwrap() == 0 {
+      break;
+    }
+    head.push(byte[0]);
+  }
+  String::from_utf8(head).unwrap()
+}
+
+// list には Content-Length で、watch にはチャンク転送でイベントを一件ずつ返すモックサーバ
+async fn spawn_mock_api(list_body: String, watch_events: Vec<String>) -> String {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let address = listener.local_addr().unwrap();
+  tokio::spawn(async move {
+    loop {
+      let Ok((mut stream, _)) = listener.accept().await else {
+        return;
+      };
+      let head = read_request_head(&mut stream).await;
+      let watch_events = watch_events.clone();
+      let list_body = list_body.clone();
+      tokio::spawn(async move {
+        if head.contains("watch=true") {
+          let header = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n";
+          stream.write_all(header.as_bytes()).await.unwrap();
+          for event in watch_events {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let line = format!("{event}\n");
+            let chunk = format!("{:x}\r\n{line}\r\n", line.len());
+            stream.write_all(chunk.as_bytes()).await.unwrap();
+          }
+          tokio::time::sleep(Duration::from_secs(10)).await;
+        } else {
+          assert!(head.starts_with("GET /api/v1/namespaces/prod/pods?labelSelector=app%3Dfraktor "));
+          let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{list_body}",
+            list_body.len()
+          );
+          stream.write_all(response.as_bytes()).await.unwrap();
+        }
+      });
+    }
+  });
+  format!("http://{address}")
+}
+
+async fn wait_for_updates(recorder: &RecordingClusterEvents, count: usize) -> Vec<(Vec<String>, Vec<String>)> {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  loop {
+    let updates = recorder.topology_updates();
+    if updates.len() >= count || Instant::now() >= deadline {
+      return updates;
+    }
+    tokio::time::sleep(Duration::from_millis(10)).await;
+  }
+}
+
+fn config(api_url: String) -> KubernetesClusterConfig {
+  KubernetesClusterConfig::new()
+    .with_api_url(api_url)
+    .with_plain_http_for_tests()
+    .with_namespace("prod")
+    .with_label_selector("app=fraktor")
+    .with_port(2552)
+    .with_roles_label("fraktor.io/roles")
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn start_member_lists_and_watches_pods() {
+  let list_body = format!(
+    r#"{{"metadata":{{"resourceVersion":"10"}},"items":[{},{}]}}"#,
+    pod("a", "10.0.0.1", "Running", Some("frontend,backend")),
+    pod("b", "10.0.0.2", "Pending", None)
+  );
+  let watch_events = vec![
+    format!(r#"{{"type":"MODIFIED","object":{}}}"#, pod("b", "10.0.0.2", "Running", None)),
+    format!(r#"{{"type":"DELETED","object":{}}}"#, pod("a", "10.0.0.1", "Running", None)),
+  ];
+  let api_url = spawn_mock_api(list_body, watch_events).await;
+
+  let event_stream = ArcShared::new(EventStreamGeneric::<StdToolbox>::default());
+  let block_list: ArcShared<dyn BlockListProvider> = ArcShared::new(EmptyBlockList);
+  let (recorder, _subscription) = subscribe_recorder(&event_stream);
+
+  let mut provider =
+    KubernetesClusterProvider::new(event_stream, block_list, "10.0.0.9:2552").with_kubernetes_config(config(api_url));
+  provider.start_member().unwrap();
+  assert!(provider.is_started());
+
+  let updates = wait_for_updates(&recorder, 4).await;
+  assert_eq!(updates, vec![
+    (vec!["10.0.0.9:2552".to_string()], vec![]),
+    (vec!["10.0.0.1:2552".to_string()], vec![]),
+    (vec!["10.0.0.2:2552".to_string()], vec![]),
+    (vec![], vec!["10.0.0.1:2552".to_string()]),
+  ]);
+  assert_eq!(provider.member_count(), 2);
+
+  let roles = recorder.events().into_iter().find_map(|event| match event {
+    | ClusterEvent::TopologyUpdated { topology, .. } => topology.member_roles().get("10.0.0.1:2552").cloned(),
+    | _ => None,
+  });
+  assert_eq!(roles, Some(vec!["frontend".to_string(), "backend".to_string()]));
+
+  provider.shutdown(true).unwrap();
+  assert!(!provider.is_started());
+  assert!(matches!(recorder.events().last(), Some(ClusterEvent::Shutdown { mode: StartupMode::Member, .. })));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn start_client_does_not_add_self() {
+  let list_body =
+    format!(r#"{{"metadata":{{"resourceVersion":"10"}},"items":[{}]}}"#, pod("a", "10.0.0.1", "Running", None));
+  let api_url = spawn_mock_api(list_body, Vec::new()).await;
+
+  let event_stream = ArcShared::new(EventStreamGeneric::<StdToolbox>::default());
+  let block_list: ArcShared<dyn BlockListProvider> = ArcShared::new(EmptyBlockList);
+  let (recorder, _subscription) = subscribe_recorder(&event_stream);
+
+  let mut provider = KubernetesClusterProvider::new(event_stream, block_list, "10.0.0.9:2552")
+    .with_kubernetes_config(config(api_url).with_watch(false));
+  provider.start_client().unwrap();
+
+  let updates = wait_for_updates(&recorder, 1).await;
+  assert_eq!(updates, vec![(vec!["10.0.0.1:2552".to_string()], vec![])]);
+  assert!(
+    recorder.events().iter().any(|event| matches!(event, ClusterEvent::Startup { mode: StartupMode::Client, .. }))
+  );
+  provider.shutdown(true).unwrap();
+}
+
+#[tokio::test]
+async fn start_member_rejects_plain_http_api_url() {
+  let event_stream = ArcShared::new(EventStreamGeneric::<StdToolbox>::default());
+  let block_list: ArcShared<dyn BlockListProvider> = ArcShared::new(EmptyBlockList);
+
+  let mut provider = KubernetesClusterProvider::new(event_stream, block_list, "10.0.0.9:2552")
+    .with_kubernetes_config(KubernetesClusterConfig::new().with_api_url("http://kubernetes.default.svc"));
+
+  assert!(provider.start_member().is_err());
+  assert!(!provider.is_started());
+}
@@ -67,6 +67,7 @@ pub fn subscribe_remoting_events(provider: &SharedLocalClusterProvider) {
 }
 
 /// Creates a shared, thread-safe LocalClusterProvider wrapped in a mutex.
+#[must_use]
 pub fn wrap_local_cluster_provider(provider: LocalClusterProvider<StdToolbox>) -> SharedLocalClusterProvider {
   ArcShared::new(<StdToolbox as fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox>::MutexFamily::create(provider))
 }
@@ -0,0 +1,119 @@
+//! Configuration for the multi-node test harness.
+
+use std::time::Duration;
+
+use fraktor_remote_rs::core::PhiFailureDetectorConfig;
+
+/// Configuration for [`MultiNodeHarness`](super::MultiNodeHarness).
+#[derive(Clone, Debug)]
+pub struct MultiNodeConfig {
+  nodes:              usize,
+  port:               u16,
+  resolution:         Duration,
+  heartbeat_interval: Duration,
+  gossip_interval:    Duration,
+  failure_detector:   PhiFailureDetectorConfig,
+  seed:               u64,
+}
+
+impl MultiNodeConfig {
+  /// Creates a configuration for `nodes` members with default timings.
+  ///
+  /// Heartbeats are sent every 100 ms and a member is suspected after about 500 ms of silence.
+  #[must_use]
+  pub const fn new(nodes: usize) -> Self {
+    Self {
+      nodes,
+      port: 2552,
+      resolution: Duration::from_millis(10),
+      heartbeat_interval: Duration::from_millis(100),
+      gossip_interval: Duration::from_millis(200),
+      failure_detector: PhiFailureDetectorConfig::new(5.0, 100, 100),
+      seed: 1,
+    }
+  }
+
+  /// Sets the port shared by the member authorities (`node-<index>:<port>`).
+  #[must_use]
+  pub const fn with_port(mut self, port: u16) -> Self {
+    self.port = port;
+    self
+  }
+
+  /// Sets the virtual time advanced per harness step; it is clamped to at least 1 ms.
+  #[must_use]
+  pub const fn with_resolution(mut self, resolution: Duration) -> Self {
+    self.resolution = resolution;
+    self
+  }
+
+  /// Sets the interval between heartbeats sent to every peer.
+  #[must_use]
+  pub const fn with_heartbeat_interval(mut self, interval: Duration) -> Self {
+    self.heartbeat_interval = interval;
+    self
+  }
+
+  /// Sets the interval at which unconfirmed membership deltas are gossiped again.
+  #[must_use]
+  pub const fn with_gossip_interval(mut self, interval: Duration) -> Self {
+    self.gossip_interval = interval;
+    self
+  }
+
+  /// Sets the failure detector configuration of every member.
+  #[must_use]
+  pub const fn with_failure_detector(mut self, config: PhiFailureDetectorConfig) -> Self {
+    self.failure_detector = config;
+    self
+  }
+
+  /// Sets the seed of the network's drop and reorder decisions.
+  #[must_use]
+  pub const fn with_seed(mut self, seed: u64) -> Self {
+    self.seed = seed;
+    self
+  }
+
+  /// Returns the number of members.
+  #[must_use]
+  pub const fn nodes(&self) -> usize {
+    self.nodes
+  }
+
+  /// Returns the port shared by the member authorities.
+  #[must_use]
+  pub const fn port(&self) -> u16 {
+    self.port
+  }
+
+  /// Returns the virtual time advanced per harness step.
+  #[must_use]
+  pub fn resolution(&self) -> Duration {
+    self.resolution.max(Duration::from_millis(1))
+  }
+
+  /// Returns the heartbeat interval.
+  #[must_use]
+  pub const fn heartbeat_interval(&self) -> Duration {
+    self.heartbeat_interval
+  }
+
+  /// Returns the gossip retransmission interval.
+  #[must_use]
+  pub const fn gossip_interval(&self) -> Duration {
+    self.gossip_interval
+  }
+
+  /// Returns the failure detector configuration.
+  #[must_use]
+  pub const fn failure_detector(&self) -> &PhiFailureDetectorConfig {
+    &self.failure_detector
+  }
+
+  /// Returns the network seed.
+  #[must_use]
+  pub const fn seed(&self) -> u64 {
+    self.seed
+  }
+}
@@ -0,0 +1,64 @@
+//! Errors raised while building a multi-node harness.
+
+use core::fmt;
+use std::io;
+
+use fraktor_actor_rs::core::{
+  serialization::{SerializationBuilderError, SerializerIdError},
+  spawn::SpawnError,
+  system::ActorRefResolveError,
+};
+
+/// Failure while starting the members of a [`MultiNodeHarness`](super::MultiNodeHarness).
+#[derive(Debug)]
+pub enum MultiNodeError {
+  /// The runtime driving the remoting endpoints could not be created.
+  Runtime(io::Error),
+  /// The serialization setup of the members could not be built.
+  Serialization(String),
+  /// The actor system of a member, including its remoting extension, could not be started.
+  Spawn(SpawnError),
+  /// A member could not resolve the remote reference of a peer.
+  Resolve(ActorRefResolveError),
+}
+
+impl fmt::Display for MultiNodeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Self::Runtime(error) => write!(f, "failed to start remoting runtime: {error}"),
+      | Self::Serialization(reason) => write!(f, "failed to build member serialization: {reason}"),
+      | Self::Spawn(error) => write!(f, "failed to start member actor system: {error:?}"),
+      | Self::Resolve(error) => write!(f, "failed to resolve peer reference: {error:?}"),
+    }
+  }
+}
+
+impl From<io::Error> for MultiNodeError {
+  fn from(error: io::Error) -> Self {
+    Self::Runtime(error)
+  }
+}
+
+impl From<SerializationBuilderError> for MultiNodeError {
+  fn from(error: SerializationBuilderError) -> Self {
+    Self::Serialization(format!("{error:?}"))
+  }
+}
+
+impl From<SerializerIdError> for MultiNodeError {
+  fn from(error: SerializerIdError) -> Self {
+    Self::Serialization(format!("{error:?}"))
+  }
+}
+
+impl From<SpawnError> for MultiNodeError {
+  fn from(error: SpawnError) -> Self {
+    Self::Spawn(error)
+  }
+}
+
+impl From<ActorRefResolveError> for MultiNodeError {
+  fn from(error: ActorRefResolveError) -> Self {
+    Self::Resolve(error)
+  }
+}
@@ -0,0 +1,152 @@
+//! Wire format of the heartbeat and gossip frames exchanged by harness members.
+
+#[cfg(test)]
+mod tests;
+
+use crate::core::{MembershipDelta, MembershipVersion, NodeRecord, NodeStatus};
+
+const TAG_HEARTBEAT: u8 = 0;
+const TAG_GOSSIP: u8 = 1;
+const TAG_GOSSIP_ACK: u8 = 2;
+
+/// Frame exchanged between the members of a multi-node harness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MultiNodeFrame {
+  /// Liveness signal feeding the failure detector of the receiver.
+  Heartbeat { from: String },
+  /// Membership delta disseminated by the gossip engine of the sender.
+  Gossip { from: String, delta: MembershipDelta },
+  /// Confirmation that a gossiped delta was applied.
+  GossipAck { from: String },
+}
+
+impl MultiNodeFrame {
+  pub(crate) fn encode(&self) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    match self {
+      | Self::Heartbeat { from } => {
+        buffer.push(TAG_HEARTBEAT);
+        put_str(&mut buffer, from);
+      },
+      | Self::Gossip { from, delta } => {
+        buffer.push(TAG_GOSSIP);
+        put_str(&mut buffer, from);
+        buffer.extend_from_slice(&delta.from.value().to_be_bytes());
+        buffer.extend_from_slice(&delta.to.value().to_be_bytes());
+        put_len(&mut buffer, delta.entries.len());
+        for record in &delta.entries {
+          put_str(&mut buffer, &record.node_id);
+          put_str(&mut buffer, &record.authority);
+          buffer.push(status_code(record.status));
+          buffer.extend_from_slice(&record.version.value().to_be_bytes());
+          buffer.extend_from_slice(&record.up_number.value().to_be_bytes());
+          put_len(&mut buffer, record.roles.len());
+          for role in &record.roles {
+            put_str(&mut buffer, role);
+          }
+        }
+      },
+      | Self::GossipAck { from } => {
+        buffer.push(TAG_GOSSIP_ACK);
+        put_str(&mut buffer, from);
+      },
+    }
+    buffer
+  }
+
+  /// Decodes a frame, returning `None` when the bytes are malformed.
+  pub(crate) fn decode(bytes: &[u8]) -> Option<Self> {
+    let mut reader = Reader { bytes };
+    let tag = reader.u8()?;
+    let from = reader.string()?;
+    let frame = match tag {
+      | TAG_HEARTBEAT => Self::Heartbeat { from },
+      | TAG_GOSSIP => {
+        let delta_from = MembershipVersion::new(reader.u64()?);
+        let delta_to = MembershipVersion::new(reader.u64()?);
+        let count = reader.len()?;
+        let mut entries = Vec::with_capacity(count.min(64));
+        for _ in 0..count {
+          let node_id = reader.string()?;
+          let authority = reader.string()?;
+          let status = status_from_code(reader.u8()?)?;
+          let version = MembershipVersion::new(reader.u64()?);
+          let up_number = MembershipVersion::new(reader.u64()?);
+          let role_count = reader.len()?;
+          let mut roles = Vec::with_capacity(role_count.min(16));
+          for _ in 0..role_count {
+            roles.push(reader.string()?);
+          }
+          entries
+            .push(NodeRecord::new(node_id, authority, status, version).with_up_number(up_number).with_roles(roles));
+        }
+        Self::Gossip { from, delta: MembershipDelta::new(delta_from, delta_to, entries) }
+      },
+      | TAG_GOSSIP_ACK => Self::GossipAck { from },
+      | _ => return None,
+    };
+    reader.bytes.is_empty().then_some(frame)
+  }
+}
+
+struct Reader<'a> {
+  bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+  const fn take(&mut self, count: usize) -> Option<&[u8]> {
+    if self.bytes.len() < count {
+      return None;
+    }
+    let (head, tail) = self.bytes.split_at(count);
+    self.bytes = tail;
+    Some(head)
+  }
+
+  fn u8(&mut self) -> Option<u8> {
+    self.take(1).map(|bytes| bytes[0])
+  }
+
+  fn u64(&mut self) -> Option<u64> {
+    self.take(8).and_then(|bytes| bytes.try_into().ok()).map(u64::from_be_bytes)
+  }
+
+  fn len(&mut self) -> Option<usize> {
+    self.take(4).and_then(|bytes| bytes.try_into().ok()).map(u32::from_be_bytes).map(|len| len as usize)
+  }
+
+  fn string(&mut self) -> Option<String> {
+    let len = self.len()?;
+    self.take(len).and_then(|bytes| String::from_utf8(bytes.to_vec()).ok())
+  }
+}
+
+fn put_len(buffer: &mut Vec<u8>, len: usize) {
+  buffer.extend_from_slice(&u32::try_from(len).unwrap_or(u32::MAX).to_be_bytes());
+}
+
+fn put_str(buffer: &mut Vec<u8>, value: &str) {
+  put_len(buffer, value.len());
+  buffer.extend_from_slice(value.as_bytes());
+}
+
+const fn status_code(status: NodeStatus) -> u8 {
+  match status {
+    | NodeStatus::Joining => 0,
+    | NodeStatus::Up => 1,
+    | NodeStatus::Leaving => 2,
+    | NodeStatus::Removed => 3,
+    | NodeStatus::Unreachable => 4,
+  }
+}
+
+const fn status_from_code(code: u8) -> Option<NodeStatus> {
+  match code {
+    | 0 => Some(NodeStatus::Joining),
+    | 1 => Some(NodeStatus::Up),
+    | 2 => Some(NodeStatus::Leaving),
+    | 3 => Some(NodeStatus::Removed),
+    | 4 => Some(NodeStatus::Unreachable),
+    | _ => None,
+  }
+}
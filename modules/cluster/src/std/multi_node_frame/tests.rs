@@ -0,0 +1,31 @@
+use super::MultiNodeFrame;
+use crate::core::{MembershipDelta, MembershipVersion, NodeRecord, NodeStatus};
+
+#[test]
+fn frames_round_trip() {
+  let record =
+    NodeRecord::new("node-1".into(), "node-1:2552".into(), NodeStatus::Unreachable, MembershipVersion::new(4))
+      .with_up_number(MembershipVersion::new(2))
+      .with_roles(vec!["frontend".into()]);
+  let frames = [
+    MultiNodeFrame::Heartbeat { from: "node-0:2552".into() },
+    MultiNodeFrame::Gossip {
+      from:  "node-0:2552".into(),
+      delta: MembershipDelta::new(MembershipVersion::new(3), MembershipVersion::new(4), vec![record]),
+    },
+    MultiNodeFrame::GossipAck { from: "node-2:2552".into() },
+  ];
+
+  for frame in frames {
+    assert_eq!(MultiNodeFrame::decode(&frame.encode()), Some(frame));
+  }
+}
+
+#[test]
+fn malformed_frames_are_rejected() {
+  let mut bytes = MultiNodeFrame::Heartbeat { from: "node-0:2552".into() }.encode();
+  assert!(MultiNodeFrame::decode(&bytes[..bytes.len() - 1]).is_none());
+  bytes.push(0);
+  assert!(MultiNodeFrame::decode(&bytes).is_none());
+  assert!(MultiNodeFrame::decode(&[9, 0, 0, 0, 0]).is_none());
+}
@@ -0,0 +1,234 @@
+//! Deterministic multi-node harness for remoting and cluster scenarios.
+
+#[cfg(test)]
+mod tests;
+
+use std::time::Duration;
+
+use fraktor_remote_rs::std::transport::{InProcessNetwork, LinkConditions};
+use fraktor_utils_rs::core::time::{ManualClock, MonotonicClock};
+use tokio::runtime::{Builder, Runtime};
+
+use super::{multi_node_config::MultiNodeConfig, multi_node_error::MultiNodeError, multi_node_member::MultiNodeMember};
+use crate::core::MembershipTable;
+
+/// Runs several members in one process on a shared virtual clock.
+///
+/// Members are named `node-<index>:<port>`. Each one runs the remoting extension over the
+/// `fraktor.inproc` transport of a shared [`InProcessNetwork`] whose links can delay, drop,
+/// reorder or cut frames, so actor messages between members and the harness traffic below are
+/// both exposed to the injected faults. Every member starts with the same membership view in
+/// which all members are `Up`; it then exchanges heartbeats feeding its
+/// [`PhiFailureDetector`](fraktor_remote_rs::core::PhiFailureDetector) and gossips the
+/// reachability changes detected through its [`GossipEngine`](crate::core::GossipEngine).
+///
+/// Each member publishes the membership views it adopts on its event stream, as the cluster
+/// singleton actors expect. Nothing happens between calls: [`advance`](Self::advance) moves the
+/// shared [`ManualClock`], the network, the paused-clock runtime driving the remoting endpoints and
+/// the tick driver of every running member in lockstep, so failure detection, gossip convergence
+/// and split-brain scenarios replay identically.
+pub struct MultiNodeHarness {
+  config:  MultiNodeConfig,
+  clock:   ManualClock,
+  network: InProcessNetwork,
+  members: Vec<MultiNodeMember>,
+  // エンドポイントのタスクを止めてから破棄するよう、メンバーより後に宣言する
+  runtime: Runtime,
+}
+
+impl MultiNodeHarness {
+  /// Starts the members described by `config` and connects them to each other.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`MultiNodeError`] when a member actor system or its remoting cannot be started.
+  pub fn new(config: MultiNodeConfig) -> Result<Self, MultiNodeError> {
+    // 仮想時間で駆動するため、時計を止めた単一スレッドのランタイムでエンドポイントを動かす
+    let runtime = Builder::new_current_thread().enable_time().start_paused(true).build()?;
+    let guard = runtime.enter();
+    let network = InProcessNetwork::with_seed(config.seed());
+    let authorities: Vec<String> = (0..config.nodes()).map(|index| format!("node-{index}:{}", config.port())).collect();
+    let mut members = Vec::with_capacity(config.nodes());
+    for (index, authority) in authorities.iter().enumerate() {
+      let mut table = MembershipTable::new(1);
+      for (node, peer) in authorities.iter().enumerate() {
+        // 全メンバーが同じ順序で参加させ、初期ビューとバージョンを揃える
+        let _ = table.try_join(format!("node-{node}"), peer.clone());
+      }
+      table.drain_events();
+      let peers = authorities.iter().filter(|peer| *peer != authority).cloned().collect();
+      let host = format!("node-{index}");
+      members.push(MultiNodeMember::start(&network, &host, config.port(), table, peers, config.failure_detector())?);
+    }
+    for member in &mut members {
+      let peers: Vec<String> = authorities.iter().filter(|peer| *peer != member.authority()).cloned().collect();
+      member.connect(&peers)?;
+    }
+    let clock = ManualClock::new(config.resolution());
+    drop(guard);
+    Ok(Self { config, clock, network, members, runtime })
+  }
+
+  /// Returns the virtual time elapsed since the harness started.
+  #[must_use]
+  pub fn now(&self) -> Duration {
+    let now = self.clock.now();
+    now.resolution().saturating_mul(u32::try_from(now.ticks()).unwrap_or(u32::MAX))
+  }
+
+  /// Returns the network connecting the members.
+  #[must_use]
+  pub const fn network(&self) -> &InProcessNetwork {
+    &self.network
+  }
+
+  /// Returns the members in index order.
+  #[must_use]
+  pub const fn members(&self) -> &[MultiNodeMember] {
+    self.members.as_slice()
+  }
+
+  /// Returns the member at `index`.
+  ///
+  /// # Panics
+  ///
+  /// Panics when `index` is out of range.
+  #[must_use]
+  pub fn member(&self, index: usize) -> &MultiNodeMember {
+    &self.members[index]
+  }
+
+  /// Returns the member at `index` mutably, e.g. to drain its events.
+  ///
+  /// # Panics
+  ///
+  /// Panics when `index` is out of range.
+  pub fn member_mut(&mut self, index: usize) -> &mut MultiNodeMember {
+    &mut self.members[index]
+  }
+
+  /// Returns the authority of the member at `index`.
+  ///
+  /// # Panics
+  ///
+  /// Panics when `index` is out of range.
+  #[must_use]
+  pub fn authority(&self, index: usize) -> &str {
+    self.members[index].authority()
+  }
+
+  /// Applies `conditions` to the frames sent from member `from` to member `to`.
+  pub fn set_link_conditions(&self, from: usize, to: usize, conditions: LinkConditions) {
+    self.network.set_link_conditions(self.authority(from), self.authority(to), conditions);
+  }
+
+  /// Cuts every link between the members of `side_a` and those of `side_b`.
+  pub fn partition(&self, side_a: &[usize], side_b: &[usize]) {
+    for a in side_a {
+      for b in side_b {
+        self.network.partition(self.authority(*a), self.authority(*b));
+      }
+    }
+  }
+
+  /// Restores the links between the members of `side_a` and those of `side_b`.
+  pub fn heal(&self, side_a: &[usize], side_b: &[usize]) {
+    for a in side_a {
+      for b in side_b {
+        self.network.heal(self.authority(*a), self.authority(*b));
+      }
+    }
+  }
+
+  /// Restores every cut link.
+  pub fn heal_all(&self) {
+    self.network.heal_all();
+  }
+
+  /// Freezes the member at `index`: its actor system stops ticking, it sends no heartbeats and
+  /// the frames addressed to it are held until it is resumed.
+  pub fn pause(&mut self, index: usize) {
+    self.network.pause(self.members[index].authority());
+    self.members[index].set_paused(true);
+  }
+
+  /// Resumes the member at `index`.
+  pub fn resume(&mut self, index: usize) {
+    self.network.resume(self.members[index].authority());
+    self.members[index].set_paused(false);
+  }
+
+  /// Makes the member at `index` leave gracefully: it marks itself `Leaving` and gossips it, so
+  /// the work it owns, such as cluster singletons, is handed over to the other members.
+  pub fn leave(&mut self, index: usize) {
+    let _guard = self.runtime.enter();
+    self.members[index].leave();
+  }
+
+  /// Advances the shared clock by `duration`, rounded up to whole steps.
+  pub fn advance(&mut self, duration: Duration) {
+    let resolution = self.config.resolution();
+    let steps = duration.as_nanos().div_ceil(resolution.as_nanos());
+    for _ in 0..steps {
+      self.step();
+    }
+  }
+
+  /// Advances the shared clock step by step until `condition` holds or `timeout` elapses.
+  ///
+  /// Returns `true` when the condition was met.
+  pub fn advance_until<F>(&mut self, timeout: Duration, mut condition: F) -> bool
+  where
+    F: FnMut(&Self) -> bool, {
+    let deadline = self.now().saturating_add(timeout);
+    while !condition(self) {
+      if self.now() >= deadline {
+        return false;
+      }
+      self.step();
+    }
+    true
+  }
+
+  /// Returns `true` when every running member has the same membership view.
+  #[must_use]
+  pub fn is_converged(&self) -> bool {
+    let mut snapshots = self.members.iter().filter(|member| !member.is_paused()).map(|member| {
+      let mut entries = member.membership().snapshot().entries;
+      entries.sort_by(|left, right| left.authority.cmp(&right.authority));
+      entries.into_iter().map(|record| (record.authority, record.status)).collect::<Vec<_>>()
+    });
+    let Some(first) = snapshots.next() else {
+      return true;
+    };
+    snapshots.all(|snapshot| snapshot == first)
+  }
+
+  fn step(&mut self) {
+    let resolution = self.config.resolution();
+    self.clock.advance(resolution);
+    // 送信待ちの封筒を流し、届いたフレームを受信側のエンドポイントまで処理させる
+    self.runtime.block_on(async move { tokio::time::sleep(resolution).await });
+    let _guard = self.runtime.enter();
+    self.network.advance(resolution);
+    let now = self.now();
+    let now_ms = u64::try_from(now.as_millis()).unwrap_or(u64::MAX);
+    let heartbeat = is_boundary(now, self.config.heartbeat_interval());
+    let gossip = is_boundary(now, self.config.gossip_interval());
+    for member in self.members.iter_mut().filter(|member| !member.is_paused()) {
+      member.step(resolution, now_ms);
+      if heartbeat {
+        member.send_heartbeats();
+      }
+      if gossip {
+        member.regossip();
+      }
+      member.poll_detector(now_ms);
+      member.publish_membership();
+    }
+  }
+}
+
+const fn is_boundary(now: Duration, interval: Duration) -> bool {
+  !interval.is_zero() && now.as_nanos() % interval.as_nanos() == 0
+}
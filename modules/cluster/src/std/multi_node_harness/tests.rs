@@ -0,0 +1,287 @@
+use std::time::Duration;
+
+use fraktor_actor_rs::core::{
+  actor_prim::{Actor, ActorContextGeneric, actor_ref::ActorRefGeneric},
+  error::ActorError,
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+  props::PropsGeneric,
+  scheduler::SchedulerCommand,
+};
+use fraktor_remote_rs::{core::PhiFailureDetectorEffect, std::transport::LinkConditions};
+use fraktor_utils_rs::{
+  core::{runtime_toolbox::NoStdMutex, sync::ArcShared},
+  std::runtime_toolbox::StdToolbox,
+};
+
+use super::MultiNodeHarness;
+use crate::{
+  core::{
+    ClusterSingletonManagerActor, ClusterSingletonProxyActor, ClusterSingletonSettings, GossipEvent, GossipState,
+    NodeStatus,
+  },
+  std::MultiNodeConfig,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Forwarder {
+  target: ActorRefGeneric<StdToolbox>,
+}
+
+impl Actor<StdToolbox> for Forwarder {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    message: AnyMessageViewGeneric<'_, StdToolbox>,
+  ) -> Result<(), ActorError> {
+    if let Some(payload) = message.downcast_ref::<Vec<u8>>() {
+      self.target.tell(AnyMessageGeneric::new(payload.clone())).map_err(|error| ActorError::from_send_error(&error))?;
+    }
+    Ok(())
+  }
+}
+
+type SingletonLog = ArcShared<NoStdMutex<Vec<String>>>;
+
+struct SingletonProbe {
+  authority: String,
+  log:       SingletonLog,
+}
+
+impl Actor<StdToolbox> for SingletonProbe {
+  fn pre_start(&mut self, _ctx: &mut ActorContextGeneric<'_, StdToolbox>) -> Result<(), ActorError> {
+    self.log.lock().push(format!("start {}", self.authority));
+    Ok(())
+  }
+
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    message: AnyMessageViewGeneric<'_, StdToolbox>,
+  ) -> Result<(), ActorError> {
+    if let Some(payload) = message.downcast_ref::<Vec<u8>>() {
+      self.log.lock().push(format!("{} received {payload:?}", self.authority));
+    }
+    Ok(())
+  }
+
+  fn post_stop(&mut self, _ctx: &mut ActorContextGeneric<'_, StdToolbox>) -> Result<(), ActorError> {
+    self.log.lock().push(format!("stop {}", self.authority));
+    Ok(())
+  }
+}
+
+fn harness(nodes: usize) -> MultiNodeHarness {
+  MultiNodeHarness::new(MultiNodeConfig::new(nodes)).expect("harness")
+}
+
+#[test]
+fn healthy_members_stay_up_and_converged() {
+  let mut harness = harness(3);
+  harness.advance(Duration::from_secs(2));
+
+  assert_eq!(harness.now(), Duration::from_secs(2));
+  assert!(harness.is_converged());
+  for index in 0..3 {
+    let member = harness.member_mut(index);
+    assert!(member.drain_detector_effects().is_empty());
+    assert!(member.membership().snapshot().entries.iter().all(|record| record.status == NodeStatus::Up));
+  }
+}
+
+#[test]
+fn partition_splits_the_view_and_heal_restores_it() {
+  let mut harness = harness(3);
+  harness.advance(Duration::from_secs(1));
+  let isolated = harness.authority(2).to_string();
+  let majority = harness.authority(0).to_string();
+
+  harness.partition(&[0, 1], &[2]);
+  assert!(harness.advance_until(TIMEOUT, |harness| {
+    harness.member(0).status_of(&isolated) == Some(NodeStatus::Unreachable)
+      && harness.member(1).status_of(&isolated) == Some(NodeStatus::Unreachable)
+      && harness.member(2).status_of(&majority) == Some(NodeStatus::Unreachable)
+  }));
+  // 両側が相手を到達不能とみなす split-brain 状態
+  assert!(!harness.is_converged());
+  assert_eq!(harness.member(2).status_of(harness.authority(2)), Some(NodeStatus::Up));
+
+  harness.heal_all();
+  assert!(harness.advance_until(TIMEOUT, |harness| {
+    harness.is_converged() && harness.member(0).status_of(&isolated) == Some(NodeStatus::Up)
+  }));
+  let effects = harness.member_mut(0).drain_detector_effects();
+  assert!(effects.contains(&PhiFailureDetectorEffect::Reachable { authority: isolated.clone() }));
+  assert!(
+    effects
+      .iter()
+      .any(|effect| matches!(effect, PhiFailureDetectorEffect::Suspect { authority, .. } if *authority == isolated))
+  );
+}
+
+#[test]
+fn paused_member_is_detected_and_rejoins_after_resume() {
+  let mut harness = harness(3);
+  harness.advance(Duration::from_secs(1));
+  let paused = harness.authority(1).to_string();
+
+  harness.pause(1);
+  assert!(harness.advance_until(TIMEOUT, |harness| {
+    harness.member(0).status_of(&paused) == Some(NodeStatus::Unreachable)
+      && harness.member(2).status_of(&paused) == Some(NodeStatus::Unreachable)
+  }));
+  assert!(harness.is_converged());
+
+  harness.resume(1);
+  assert!(harness.advance_until(TIMEOUT, |harness| {
+    harness.is_converged() && harness.member(0).status_of(&paused) == Some(NodeStatus::Up)
+  }));
+}
+
+#[test]
+fn latency_below_the_detection_threshold_keeps_members_up() {
+  let mut harness = harness(2);
+  harness.set_link_conditions(0, 1, LinkConditions::new().with_latency(Duration::from_millis(150)));
+  harness.set_link_conditions(1, 0, LinkConditions::new().with_latency(Duration::from_millis(150)));
+  harness.advance(Duration::from_secs(3));
+
+  assert!(harness.member_mut(0).drain_detector_effects().is_empty());
+  assert!(harness.member_mut(1).drain_detector_effects().is_empty());
+}
+
+#[test]
+fn lossy_scenarios_replay_identically_for_the_same_seed() {
+  let run = |seed: u64| {
+    let mut harness = MultiNodeHarness::new(MultiNodeConfig::new(3).with_seed(seed)).expect("harness");
+    harness.network().set_default_conditions(
+      LinkConditions::new().with_drop_probability(0.3).with_reorder_window(Duration::from_millis(40)),
+    );
+    harness.advance(Duration::from_secs(3));
+    let mut log = Vec::new();
+    for index in 0..3 {
+      let member = harness.member_mut(index);
+      log.push(format!("{:?}", member.drain_detector_effects()));
+      log.push(format!("{:?}", member.drain_gossip_events()));
+    }
+    (log, harness.network().dropped())
+  };
+
+  let first = run(42);
+  assert!(first.1 > 0);
+  assert_eq!(run(42), first);
+}
+
+#[test]
+fn gossip_confirms_reachability_changes() {
+  let mut harness = harness(3);
+  harness.advance(Duration::from_secs(1));
+  harness.partition(&[0], &[1, 2]);
+  harness.advance(Duration::from_secs(2));
+  harness.heal_all();
+  // 確認応答もリモーティング経由で届くため、収束後に応答が揃うまで進める
+  assert!(harness.advance_until(TIMEOUT, |harness| {
+    harness.is_converged() && harness.member(1).gossip_state() == GossipState::Confirmed
+  }));
+
+  let events = harness.member_mut(1).drain_gossip_events();
+  assert!(events.iter().any(|event| matches!(event, GossipEvent::Confirmed { .. })));
+}
+
+#[test]
+fn member_actor_systems_follow_the_shared_clock() {
+  let mut harness = harness(2);
+  let probe = harness.member(0).kit().create_probe().expect("probe");
+  let context = harness.member(0).system().scheduler_context().expect("scheduler");
+  context
+    .scheduler()
+    .lock()
+    .schedule_once(Duration::from_millis(300), SchedulerCommand::SendMessage {
+      receiver:   probe.actor_ref().clone(),
+      message:    AnyMessageGeneric::new(1_u32),
+      dispatcher: None,
+      sender:     None,
+    })
+    .expect("schedule");
+
+  harness.advance(Duration::from_millis(290));
+  assert_eq!(probe.pending(), 0);
+  harness.advance(Duration::from_millis(20));
+  assert_eq!(probe.pending(), 1);
+}
+
+#[test]
+fn actor_messages_travel_over_remoting_and_respect_partitions() {
+  let mut harness = harness(2);
+  harness.advance(Duration::from_millis(100));
+  let probe = harness.member(1).kit().create_probe().expect("probe");
+  let target = probe.actor_ref().clone();
+  let receiver = harness
+    .member(1)
+    .kit()
+    .spawn(&PropsGeneric::from_fn(move || Forwarder { target: target.clone() }).with_name("receiver"))
+    .expect("receiver");
+  let path = receiver.actor_ref().path().expect("receiver path");
+  let remote = harness.member(0).resolve_remote(harness.authority(1), &path).expect("remote ref");
+
+  remote.tell(AnyMessageGeneric::new(vec![1_u8, 2, 3])).expect("tell");
+  harness.advance(Duration::from_millis(50));
+  assert_eq!(probe.pending(), 1);
+
+  harness.partition(&[0], &[1]);
+  let dropped = harness.network().dropped();
+  remote.tell(AnyMessageGeneric::new(vec![4_u8])).expect("tell");
+  harness.advance(Duration::from_millis(50));
+  assert_eq!(probe.pending(), 1);
+  assert!(harness.network().dropped() > dropped);
+
+  harness.heal_all();
+  remote.tell(AnyMessageGeneric::new(vec![5_u8])).expect("tell");
+  harness.advance(Duration::from_millis(50));
+  assert_eq!(probe.pending(), 2);
+}
+
+#[test]
+fn singleton_runs_on_the_oldest_member_and_is_handed_over_on_leave() {
+  let mut harness = harness(3);
+  let log: SingletonLog = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let settings = ClusterSingletonSettings::new("scheduler");
+  let mut manager_path = None;
+  for index in 0..3 {
+    let (authority, log) = (harness.authority(index).to_string(), log.clone());
+    let singleton =
+      PropsGeneric::from_fn(move || SingletonProbe { authority: authority.clone(), log: log.clone() });
+    let settings = settings.clone();
+    let props = PropsGeneric::from_fn(move || ClusterSingletonManagerActor::new(settings.clone(), singleton.clone()))
+      .with_name("scheduler-manager");
+    let manager = harness.member(index).kit().spawn(&props).expect("manager");
+    manager_path = manager.actor_ref().path();
+  }
+  let manager_path = manager_path.expect("manager path");
+  let proxy = harness
+    .member(2)
+    .kit()
+    .spawn(
+      &PropsGeneric::from_fn(move || {
+        ClusterSingletonProxyActor::<StdToolbox, Vec<u8>>::new(settings.clone(), manager_path.clone())
+      })
+      .with_name("scheduler-proxy"),
+    )
+    .expect("proxy")
+    .actor_ref()
+    .clone();
+  let (oldest, next) = (harness.authority(0).to_string(), harness.authority(1).to_string());
+
+  // 所在が分かるまでプロキシがバッファし、最古メンバーへリモーティングで転送する
+  proxy.tell(AnyMessageGeneric::new(vec![1_u8])).expect("tell");
+  assert!(harness.advance_until(TIMEOUT, |_| log.lock().contains(&format!("{oldest} received [1]"))));
+  assert_eq!(log.lock().clone(), vec![format!("start {oldest}"), format!("{oldest} received [1]")]);
+
+  harness.leave(0);
+  assert!(harness.advance_until(TIMEOUT, |_| log.lock().contains(&format!("start {next}"))));
+  // 旧インスタンスの停止を待ってから次の最古メンバーで起動する
+  assert_eq!(log.lock()[2..], [format!("stop {oldest}"), format!("start {next}")]);
+
+  proxy.tell(AnyMessageGeneric::new(vec![2_u8])).expect("tell");
+  assert!(harness.advance_until(TIMEOUT, |_| log.lock().contains(&format!("{next} received [2]"))));
+  assert_eq!(log.lock().len(), 5);
+}
@@ -0,0 +1,326 @@
+//! Member of the multi-node test harness.
+
+use std::{
+  collections::{BTreeMap, VecDeque},
+  time::Duration,
+};
+
+use fraktor_actor_rs::core::{
+  actor_prim::{Actor, ActorContextGeneric, actor_path::ActorPath, actor_ref::ActorRefGeneric},
+  error::ActorError,
+  event_stream::EventStreamEvent,
+  extension::ExtensionInstallers,
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+  props::PropsGeneric,
+  serialization::{
+    BytesSerializer, SerializationCallScope, SerializationExtensionInstaller, SerializationSetup,
+    SerializationSetupBuilder, Serializer, SerializerId,
+  },
+  system::{ActorRefResolveError, ActorSystemConfigGeneric, ActorSystemGeneric, RemotingConfig},
+  testkit::ActorTestKitGeneric,
+};
+use fraktor_remote_rs::{
+  core::{
+    PhiFailureDetector, PhiFailureDetectorConfig, PhiFailureDetectorEffect, RemotingExtensionConfig,
+    RemotingExtensionInstaller, TokioActorRefProviderInstaller,
+  },
+  std::transport::InProcessNetwork,
+};
+use fraktor_utils_rs::{
+  core::{runtime_toolbox::NoStdMutex, sync::ArcShared},
+  std::runtime_toolbox::StdToolbox,
+};
+
+use super::{multi_node_error::MultiNodeError, multi_node_frame::MultiNodeFrame};
+use crate::core::{
+  GossipEngine, GossipEvent, GossipOutbound, GossipState, MemberPath, MembershipDelta, MembershipTable,
+  MembershipVersion, NodeStatus,
+};
+
+/// Actor system name shared by every member, so peers resolve each other's paths.
+const SYSTEM_NAME: &str = "multi-node";
+/// Name of the actor receiving the heartbeat and gossip frames of the peers.
+const INBOX_NAME: &str = "multi-node-inbox";
+const FRAME_MANIFEST: &str = "fraktor.multi-node.Frame";
+const FRAME_SERIALIZER_ID: u32 = 82;
+
+type Inbox = ArcShared<NoStdMutex<VecDeque<Vec<u8>>>>;
+
+struct FrameInbox {
+  inbox: Inbox,
+}
+
+impl Actor<StdToolbox> for FrameInbox {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, StdToolbox>,
+    message: AnyMessageViewGeneric<'_, StdToolbox>,
+  ) -> Result<(), ActorError> {
+    if let Some(payload) = message.downcast_ref::<Vec<u8>>() {
+      self.inbox.lock().push_back(payload.clone());
+    }
+    Ok(())
+  }
+}
+
+/// Actor system of a [`MultiNodeHarness`](super::MultiNodeHarness) together with its membership
+/// view, failure detector and gossip engine.
+///
+/// The member's actor system runs on a manual tick driver advanced by the harness, so probes
+/// created from [`kit`](Self::kit) observe the same virtual time as the failure detector. It has
+/// the remoting extension installed over the `fraktor.inproc` transport of the harness network:
+/// actor messages between members, and the heartbeats and gossip of the harness itself, travel
+/// as remoting envelopes and are therefore subject to the partitions, delays and drops of the
+/// network.
+///
+/// Every membership view the member adopts is published on its event stream as a
+/// [`MembershipSnapshot`](crate::core::MembershipSnapshot) cluster event, so actors such as the
+/// cluster singleton manager follow it.
+pub struct MultiNodeMember {
+  authority:        String,
+  kit:              ActorTestKitGeneric<StdToolbox>,
+  peers:            BTreeMap<String, ActorRefGeneric<StdToolbox>>,
+  inbox:            Inbox,
+  detector:         PhiFailureDetector,
+  gossip:           GossipEngine,
+  last_delta:       Option<MembershipDelta>,
+  published:        Option<MembershipVersion>,
+  detector_effects: Vec<PhiFailureDetectorEffect>,
+  gossip_events:    Vec<GossipEvent>,
+  paused:           bool,
+}
+
+impl MultiNodeMember {
+  /// Starts the member's actor system; the caller must be inside the harness runtime.
+  pub(crate) fn start(
+    network: &InProcessNetwork,
+    host: &str,
+    port: u16,
+    table: MembershipTable,
+    peers: Vec<String>,
+    detector: &PhiFailureDetectorConfig,
+  ) -> Result<Self, MultiNodeError> {
+    let remoting = RemotingExtensionConfig::default()
+      .with_transport_scheme("fraktor.inproc")
+      .with_transport_extension(network.clone());
+    let config = ActorSystemConfigGeneric::<StdToolbox>::default()
+      .with_system_name(SYSTEM_NAME)
+      .with_actor_ref_provider_installer(TokioActorRefProviderInstaller::default())
+      .with_remoting_config(RemotingConfig::default().with_canonical_host(host).with_canonical_port(port))
+      .with_extension_installers(
+        ExtensionInstallers::default()
+          .with_extension_installer(SerializationExtensionInstaller::new(serialization_setup()?))
+          .with_extension_installer(RemotingExtensionInstaller::new(remoting)),
+      );
+    let kit = ActorTestKitGeneric::with_config(config)?;
+    let inbox: Inbox = ArcShared::new(NoStdMutex::new(VecDeque::new()));
+    let receiver = inbox.clone();
+    kit.spawn(&PropsGeneric::from_fn(move || FrameInbox { inbox: receiver.clone() }).with_name(INBOX_NAME))?;
+    let mut member = Self {
+      authority: format!("{host}:{port}"),
+      kit,
+      peers: BTreeMap::new(),
+      inbox,
+      detector: PhiFailureDetector::new(detector.clone()),
+      gossip: GossipEngine::new(table, peers),
+      last_delta: None,
+      published: None,
+      detector_effects: Vec::new(),
+      gossip_events: Vec::new(),
+      paused: false,
+    };
+    member.publish_membership();
+    Ok(member)
+  }
+
+  /// Returns the authority (`host:port`) of the member.
+  #[must_use]
+  pub const fn authority(&self) -> &str {
+    self.authority.as_str()
+  }
+
+  /// Returns the actor test kit hosting the member's actor system.
+  #[must_use]
+  pub const fn kit(&self) -> &ActorTestKitGeneric<StdToolbox> {
+    &self.kit
+  }
+
+  /// Returns the member's actor system.
+  #[must_use]
+  pub const fn system(&self) -> &ActorSystemGeneric<StdToolbox> {
+    self.kit.system()
+  }
+
+  /// Returns the membership view of the member.
+  #[must_use]
+  pub const fn membership(&self) -> &MembershipTable {
+    self.gossip.table()
+  }
+
+  /// Returns the status the member sees for `authority`.
+  #[must_use]
+  pub fn status_of(&self, authority: &str) -> Option<NodeStatus> {
+    self.membership().record(authority).map(|record| record.status)
+  }
+
+  /// Returns the convergence phase of the member's gossip engine.
+  #[must_use]
+  pub const fn gossip_state(&self) -> GossipState {
+    self.gossip.state()
+  }
+
+  /// Returns `true` while the member is paused.
+  #[must_use]
+  pub const fn is_paused(&self) -> bool {
+    self.paused
+  }
+
+  /// Removes and returns the verdicts of the member's failure detector.
+  pub fn drain_detector_effects(&mut self) -> Vec<PhiFailureDetectorEffect> {
+    core::mem::take(&mut self.detector_effects)
+  }
+
+  /// Removes and returns the events of the member's gossip engine.
+  pub fn drain_gossip_events(&mut self) -> Vec<GossipEvent> {
+    self.gossip_events.extend(self.gossip.drain_events());
+    core::mem::take(&mut self.gossip_events)
+  }
+
+  /// Resolves, through the member's remoting, the actor found at `path` on the member listening
+  /// on `authority`.
+  ///
+  /// Only the segments of `path` are used, so a path taken from the target member's own actor
+  /// references works.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`ActorRefResolveError`] when the remote reference cannot be created.
+  pub fn resolve_remote(
+    &self,
+    authority: &str,
+    path: &ActorPath,
+  ) -> Result<ActorRefGeneric<StdToolbox>, ActorRefResolveError> {
+    let remote = MemberPath::rebase(SYSTEM_NAME, authority, path);
+    self.kit.system().resolve_actor_ref(remote)
+  }
+
+  pub(crate) fn connect(&mut self, peers: &[String]) -> Result<(), MultiNodeError> {
+    let inbox = ActorPath::root().child(ActorTestKitGeneric::<StdToolbox>::GUARDIAN_NAME).child(INBOX_NAME);
+    for peer in peers {
+      let actor_ref = self.resolve_remote(peer, &inbox)?;
+      self.peers.insert(peer.clone(), actor_ref);
+    }
+    Ok(())
+  }
+
+  pub(crate) const fn set_paused(&mut self, paused: bool) {
+    self.paused = paused;
+  }
+
+  /// Marks the member itself as `Leaving` and gossips it.
+  pub(crate) fn leave(&mut self) {
+    let authority = self.authority.clone();
+    let outbound = self.gossip.update_local(|table| table.mark_leaving(&authority));
+    self.send_gossip(outbound);
+    self.publish_membership();
+  }
+
+  /// Publishes the membership view on the event stream when it changed since the last call.
+  pub(crate) fn publish_membership(&mut self) {
+    let version = self.membership().version();
+    if self.published == Some(version) {
+      return;
+    }
+    self.published = Some(version);
+    let payload = AnyMessageGeneric::new(self.membership().snapshot());
+    self.system().event_stream().publish(&EventStreamEvent::Extension { name: String::from("cluster"), payload });
+  }
+
+  /// Advances the member's actor system and handles the frames received so far.
+  pub(crate) fn step(&mut self, resolution: Duration, now_ms: u64) {
+    self.kit.advance(resolution);
+    loop {
+      // 受信処理中に送信したフレームが同期的に戻ってきてもよいよう、1 件ずつ取り出す
+      let Some(payload) = self.inbox.lock().pop_front() else {
+        break;
+      };
+      match MultiNodeFrame::decode(&payload) {
+        | Some(MultiNodeFrame::Heartbeat { from }) => {
+          if let Some(effect) = self.detector.record_heartbeat(&from, now_ms) {
+            self.detector_effects.push(effect);
+            let outbound = self.gossip.update_local(|table| table.mark_reachable(&from));
+            self.send_gossip(outbound);
+          }
+        },
+        | Some(MultiNodeFrame::Gossip { from, delta }) => {
+          self.gossip.apply_incoming(&delta, &from);
+          self.send(&from, &MultiNodeFrame::GossipAck { from: self.authority.clone() });
+        },
+        | Some(MultiNodeFrame::GossipAck { from }) => {
+          self.gossip.handle_ack(&from);
+        },
+        | None => {},
+      }
+    }
+  }
+
+  pub(crate) fn send_heartbeats(&mut self) {
+    let peers: Vec<String> = self.peers.keys().cloned().collect();
+    let heartbeat = MultiNodeFrame::Heartbeat { from: self.authority.clone() };
+    for peer in peers {
+      self.send(&peer, &heartbeat);
+    }
+  }
+
+  /// Polls the failure detector and gossips the members it suspects as unreachable.
+  pub(crate) fn poll_detector(&mut self, now_ms: u64) {
+    for effect in self.detector.poll(now_ms) {
+      if let PhiFailureDetectorEffect::Suspect { authority, .. } = &effect {
+        let outbound = self.gossip.update_local(|table| table.mark_unreachable(authority));
+        self.send_gossip(outbound);
+      }
+      self.detector_effects.push(effect);
+    }
+  }
+
+  /// Gossips the last local delta again while some peers have not confirmed it.
+  pub(crate) fn regossip(&mut self) {
+    if self.gossip.state() == GossipState::Confirmed {
+      return;
+    }
+    if let Some(delta) = self.last_delta.clone() {
+      let outbound = self.gossip.disseminate(&delta);
+      self.send_gossip(outbound);
+    }
+  }
+
+  fn send_gossip(&mut self, outbound: Vec<GossipOutbound>) {
+    for GossipOutbound { target, delta } in outbound {
+      self.send(&target, &MultiNodeFrame::Gossip { from: self.authority.clone(), delta: delta.clone() });
+      self.last_delta = Some(delta);
+    }
+  }
+
+  fn send(&self, peer: &str, frame: &MultiNodeFrame) {
+    let Some(actor_ref) = self.peers.get(peer) else {
+      return;
+    };
+    // 分断や欠落はネットワーク側で表現されるため、送信エラーは無視する
+    let _ = actor_ref.tell(AnyMessageGeneric::new(frame.encode()));
+  }
+}
+
+// ハーネスのフレームは Vec<u8> としてリモーティングの封筒に載せる
+fn serialization_setup() -> Result<SerializationSetup, MultiNodeError> {
+  let id = SerializerId::try_from(FRAME_SERIALIZER_ID)?;
+  let serializer: ArcShared<dyn Serializer> = ArcShared::new(BytesSerializer::new(id));
+  Ok(
+    SerializationSetupBuilder::new()
+      .register_serializer("bytes", id, serializer)?
+      .bind::<Vec<u8>>("bytes")?
+      .bind_remote_manifest::<Vec<u8>>(FRAME_MANIFEST)?
+      .set_fallback("bytes")?
+      .require_manifest_for_scope(SerializationCallScope::Remote)
+      .build()?,
+  )
+}
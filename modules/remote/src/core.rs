@@ -4,6 +4,9 @@
 mod actor_ref_field_normalizer;
 mod association_state;
 mod block_list_provider;
+mod compression_table;
+mod compression_table_ack;
+mod compression_table_settings;
 mod deferred_envelope;
 mod endpoint_manager;
 mod endpoint_manager_command;
@@ -20,21 +23,39 @@ mod flight_recorder;
 mod fn_remoting_backpressure_listener;
 mod handshake_frame;
 mod handshake_kind;
+mod header_serializer_error;
+mod header_serializer_registry;
+mod heavy_hitters;
+mod inbound_access_rules;
+mod inbound_compression;
 mod inbound_envelope;
+mod inbound_filter;
+mod inbound_rejection;
 mod loopback_actor_ref_provider;
 mod loopback_actor_ref_provider_installer;
 mod loopback_router;
+mod metrics;
 mod outbound_message;
+mod outbound_offer_future;
 mod outbound_priority;
+mod outbound_queue_policy;
+mod payload_compression;
 mod quarantine_reason;
+mod reconnect_backoff;
 mod remote_actor_ref_provider;
 mod remote_actor_ref_provider_error;
 mod remote_actor_ref_provider_installer;
 mod remote_authority_snapshot;
+mod remote_deployment_command;
+mod remote_deployment_daemon;
+mod remote_deployment_pdu;
+mod remote_deployment_serializer;
+mod remote_deployment_whitelist;
 mod remote_node_id;
 mod remote_watcher_command;
 mod remote_watcher_daemon;
 mod remoting_backpressure_listener;
+mod remoting_capabilities;
 mod remoting_control;
 mod remoting_control_handle;
 mod remoting_envelope;
@@ -46,13 +67,21 @@ mod remoting_extension_id;
 #[cfg(feature = "std")]
 mod remoting_extension_installer;
 mod serialization_utils;
+mod serialized_header;
+mod system_ack;
+mod system_message_delivery;
+mod system_message_receiver;
 mod tokio_actor_ref_provider;
 mod tokio_actor_ref_provider_installer;
 pub mod transport;
+mod unverified_system_guard;
 mod wire_error;
 
 pub use association_state::AssociationState;
 pub use block_list_provider::BlockListProvider;
+pub use compression_table::CompressionTable;
+pub use compression_table_ack::CompressionTableAck;
+pub use compression_table_settings::CompressionTableSettings;
 pub use deferred_envelope::DeferredEnvelope;
 pub use endpoint_manager::EndpointManager;
 pub use endpoint_manager_command::EndpointManagerCommand;
@@ -64,23 +93,40 @@ pub use endpoint_writer::{EndpointWriter, EndpointWriterGeneric, EndpointWriterS
 pub use endpoint_writer_error::EndpointWriterError;
 pub use event_publisher::{EventPublisher, EventPublisherGeneric};
 pub use failure_detector::{PhiFailureDetector, PhiFailureDetectorConfig, PhiFailureDetectorEffect};
-pub use flight_recorder::{FlightMetricKind, RemotingFlightRecorder, RemotingFlightRecorderSnapshot, RemotingMetric};
+pub use flight_recorder::{
+  CompressionSample, FlightMetricKind, RemotingFlightRecorder, RemotingFlightRecorderSnapshot, RemotingMetric,
+};
 pub use fn_remoting_backpressure_listener::FnRemotingBackpressureListener;
 pub use handshake_frame::HandshakeFrame;
 pub use handshake_kind::HandshakeKind;
+pub use header_serializer_error::HeaderSerializerError;
+pub use header_serializer_registry::HeaderSerializerRegistry;
+pub use heavy_hitters::HeavyHitters;
+pub use inbound_access_rules::InboundAccessRules;
+pub use inbound_compression::InboundCompression;
 pub use inbound_envelope::InboundEnvelope;
+pub use inbound_filter::InboundFilter;
+pub use inbound_rejection::InboundRejection;
 pub use loopback_actor_ref_provider::{LoopbackActorRefProvider, LoopbackActorRefProviderGeneric};
 pub use loopback_actor_ref_provider_installer::LoopbackActorRefProviderInstaller;
+pub use metrics::{AssociationMetrics, LatencyHistogram, RemotingMetrics};
 pub use outbound_message::OutboundMessage;
+pub use outbound_offer_future::{OutboundOfferFuture, OutboundOfferFutureGeneric};
 pub use outbound_priority::OutboundPriority;
+pub use outbound_queue_policy::OutboundQueuePolicy;
+pub use payload_compression::PayloadCompression;
 pub use quarantine_reason::QuarantineReason;
+pub use reconnect_backoff::ReconnectBackoff;
 pub use remote_actor_ref_provider::{RemoteActorRefProvider, RemoteActorRefProviderGeneric};
 pub use remote_actor_ref_provider_error::RemoteActorRefProviderError;
 pub use remote_actor_ref_provider_installer::RemoteActorRefProviderInstaller;
 pub use remote_authority_snapshot::RemoteAuthoritySnapshot;
+pub use remote_deployment_pdu::RemoteDeploymentPdu;
+pub use remote_deployment_whitelist::RemoteDeploymentWhitelist;
 pub use remote_node_id::RemoteNodeId;
 pub use remote_watcher_command::RemoteWatcherCommand;
 pub use remoting_backpressure_listener::RemotingBackpressureListener;
+pub use remoting_capabilities::RemotingCapabilities;
 pub use remoting_control::RemotingControl;
 pub use remoting_control_handle::RemotingControlHandle;
 pub use remoting_envelope::RemotingEnvelope;
@@ -92,11 +138,16 @@ pub use remoting_extension_id::RemotingExtensionId;
 #[cfg(feature = "std")]
 pub use remoting_extension_installer::RemotingExtensionInstaller;
 pub use serialization_utils::default_loopback_setup;
+pub use serialized_header::SerializedHeader;
+pub use system_ack::SystemAck;
+pub use system_message_delivery::SystemMessageDelivery;
+pub use system_message_receiver::SystemMessageReceiver;
 pub use tokio_actor_ref_provider::{TokioActorRefProvider, TokioActorRefProviderGeneric};
 pub use tokio_actor_ref_provider_installer::TokioActorRefProviderInstaller;
 pub use transport::{
   InboundFrame, LoopbackTransport, RemoteTransport, RemoteTransportShared, TokioTransportConfig,
   TransportBackpressureHook, TransportBackpressureHookShared, TransportBind, TransportChannel, TransportEndpoint,
-  TransportError, TransportFactory, TransportHandle, TransportInbound, TransportInboundShared,
+  TransportError, TransportFactory, TransportHandle, TransportInbound, TransportInboundShared, TransportLifecycleHook,
+  TransportLifecycleHookShared,
 };
 pub use wire_error::WireError;
@@ -0,0 +1,159 @@
+//! Versioned table of heavy-hitter actor paths and manifests advertised to a peer.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{string::String, vec::Vec};
+use core::convert::TryInto;
+
+use crate::core::wire_error::WireError;
+
+const VERSION: u8 = 1;
+const KIND_ADVERTISEMENT: u8 = 0x30;
+
+/// Table mapping small integer ids to actor paths and manifests.
+///
+/// The receiving side of an association builds the table from the values it observes most often
+/// and advertises it to the sender, which then replaces those strings in envelope headers with
+/// their index in the table.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompressionTable {
+  version:    u32,
+  actor_refs: Vec<String>,
+  manifests:  Vec<String>,
+}
+
+impl CompressionTable {
+  /// Creates a table; ids are the positions of the entries.
+  ///
+  /// Entries beyond `u16::MAX` are dropped because ids are encoded as 16-bit integers.
+  #[must_use]
+  pub fn new(version: u32, mut actor_refs: Vec<String>, mut manifests: Vec<String>) -> Self {
+    actor_refs.truncate(usize::from(u16::MAX));
+    manifests.truncate(usize::from(u16::MAX));
+    Self { version, actor_refs, manifests }
+  }
+
+  /// Returns the table version.
+  #[must_use]
+  pub const fn version(&self) -> u32 {
+    self.version
+  }
+
+  /// Returns the compressed actor paths in id order.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // Vec の Deref が const でないため const fn にできない
+  pub fn actor_refs(&self) -> &[String] {
+    &self.actor_refs
+  }
+
+  /// Returns the compressed manifests in id order.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // Vec の Deref が const でないため const fn にできない
+  pub fn manifests(&self) -> &[String] {
+    &self.manifests
+  }
+
+  /// Returns `true` when the table has no entries.
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.actor_refs.is_empty() && self.manifests.is_empty()
+  }
+
+  /// Returns the id of an actor path (canonical URI), if compressed.
+  #[must_use]
+  pub fn actor_ref_id(&self, path: &str) -> Option<u16> {
+    Self::position(&self.actor_refs, path)
+  }
+
+  /// Returns the actor path registered under `id`.
+  #[must_use]
+  pub fn actor_ref(&self, id: u16) -> Option<&str> {
+    self.actor_refs.get(usize::from(id)).map(String::as_str)
+  }
+
+  /// Returns the id of a manifest, if compressed.
+  #[must_use]
+  pub fn manifest_id(&self, manifest: &str) -> Option<u16> {
+    Self::position(&self.manifests, manifest)
+  }
+
+  /// Returns the manifest registered under `id`.
+  #[must_use]
+  pub fn manifest(&self, id: u16) -> Option<&str> {
+    self.manifests.get(usize::from(id)).map(String::as_str)
+  }
+
+  /// Returns `true` when both tables hold the same entries, ignoring the version.
+  #[must_use]
+  pub fn same_entries(&self, other: &Self) -> bool {
+    self.actor_refs == other.actor_refs && self.manifests == other.manifests
+  }
+
+  /// Encodes the table into an advertisement payload.
+  #[must_use]
+  pub fn encode(&self) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    buffer.push(VERSION);
+    buffer.push(KIND_ADVERTISEMENT);
+    buffer.extend_from_slice(&self.version.to_le_bytes());
+    write_strings(&mut buffer, &self.actor_refs);
+    write_strings(&mut buffer, &self.manifests);
+    buffer
+  }
+
+  /// Decodes an advertisement payload.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError`] when the payload is malformed.
+  pub fn decode(bytes: &[u8]) -> Result<Self, WireError> {
+    if bytes.len() < 6 || bytes[0] != VERSION || bytes[1] != KIND_ADVERTISEMENT {
+      return Err(WireError::InvalidFormat);
+    }
+    let version = u32::from_le_bytes(bytes[2..6].try_into().map_err(|_| WireError::InvalidFormat)?);
+    let mut cursor = 6;
+    let actor_refs = read_strings(bytes, &mut cursor)?;
+    let manifests = read_strings(bytes, &mut cursor)?;
+    if cursor != bytes.len() || actor_refs.len() > usize::from(u16::MAX) || manifests.len() > usize::from(u16::MAX) {
+      return Err(WireError::InvalidFormat);
+    }
+    Ok(Self { version, actor_refs, manifests })
+  }
+
+  fn position(entries: &[String], value: &str) -> Option<u16> {
+    entries.iter().position(|entry| entry == value).and_then(|index| u16::try_from(index).ok())
+  }
+}
+
+fn write_strings(buffer: &mut Vec<u8>, values: &[String]) {
+  buffer.extend_from_slice(&(values.len() as u32).to_le_bytes());
+  for value in values {
+    let bytes = value.as_bytes();
+    buffer.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    buffer.extend_from_slice(bytes);
+  }
+}
+
+fn read_strings(bytes: &[u8], cursor: &mut usize) -> Result<Vec<String>, WireError> {
+  let count = read_u32(bytes, cursor)? as usize;
+  let mut values = Vec::with_capacity(count.min(usize::from(u16::MAX)));
+  for _ in 0..count {
+    let len = read_u32(bytes, cursor)? as usize;
+    if bytes.len() < *cursor + len {
+      return Err(WireError::InvalidFormat);
+    }
+    values.push(String::from_utf8(bytes[*cursor..*cursor + len].to_vec())?);
+    *cursor += len;
+  }
+  Ok(values)
+}
+
+fn read_u32(bytes: &[u8], cursor: &mut usize) -> Result<u32, WireError> {
+  if bytes.len() < *cursor + 4 {
+    return Err(WireError::InvalidFormat);
+  }
+  let value = u32::from_le_bytes(bytes[*cursor..*cursor + 4].try_into().map_err(|_| WireError::InvalidFormat)?);
+  *cursor += 4;
+  Ok(value)
+}
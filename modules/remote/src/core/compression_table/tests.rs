@@ -0,0 +1,44 @@
+use alloc::{string::ToString, vec, vec::Vec};
+
+use super::CompressionTable;
+use crate::core::wire_error::WireError;
+
+fn table() -> CompressionTable {
+  CompressionTable::new(3, vec!["fraktor.tcp://app@127.0.0.1:4100/user/user/svc".to_string()], vec![
+    "app.Ping".to_string(),
+    "app.Pong".to_string(),
+  ])
+}
+
+#[test]
+fn ids_resolve_in_both_directions() {
+  let table = table();
+  assert_eq!(table.actor_ref_id("fraktor.tcp://app@127.0.0.1:4100/user/user/svc"), Some(0));
+  assert_eq!(table.actor_ref(0), Some("fraktor.tcp://app@127.0.0.1:4100/user/user/svc"));
+  assert_eq!(table.manifest_id("app.Pong"), Some(1));
+  assert_eq!(table.manifest(1), Some("app.Pong"));
+  assert_eq!(table.manifest_id("app.Other"), None);
+  assert_eq!(table.actor_ref(5), None);
+}
+
+#[test]
+fn advertisement_round_trips() {
+  let table = table();
+  let decoded = CompressionTable::decode(&table.encode()).expect("decode");
+  assert_eq!(decoded, table);
+  assert_eq!(decoded.version(), 3);
+}
+
+#[test]
+fn same_entries_ignores_version() {
+  let table = table();
+  let next = CompressionTable::new(4, table.actor_refs().to_vec(), table.manifests().to_vec());
+  assert!(table.same_entries(&next));
+  assert!(!table.same_entries(&CompressionTable::new(4, Vec::new(), Vec::new())));
+}
+
+#[test]
+fn truncated_advertisement_is_rejected() {
+  let encoded = table().encode();
+  assert!(matches!(CompressionTable::decode(&encoded[..encoded.len() - 1]), Err(WireError::InvalidFormat)));
+}
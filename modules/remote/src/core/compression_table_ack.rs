@@ -0,0 +1,56 @@
+//! Acknowledgement of an advertised compression table.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::core::wire_error::WireError;
+
+const VERSION: u8 = 1;
+const KIND_ACK: u8 = 0x31;
+
+/// Confirms that the sender received the compression table with the given version.
+///
+/// The advertising side keeps resending a table until it is acknowledged and only then rolls
+/// out the next version, so at most two versions are ever in flight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressionTableAck(u32);
+
+impl CompressionTableAck {
+  /// Creates an acknowledgement for the table `version`.
+  #[must_use]
+  pub const fn new(version: u32) -> Self {
+    Self(version)
+  }
+
+  /// Returns the acknowledged table version.
+  #[must_use]
+  pub const fn version(self) -> u32 {
+    self.0
+  }
+
+  /// Encodes the acknowledgement into a transport payload.
+  #[must_use]
+  pub fn encode(self) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(6);
+    buffer.push(VERSION);
+    buffer.push(KIND_ACK);
+    buffer.extend_from_slice(&self.0.to_le_bytes());
+    buffer
+  }
+
+  /// Decodes an acknowledgement payload.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::InvalidFormat`] when the payload is malformed.
+  pub fn decode(bytes: &[u8]) -> Result<Self, WireError> {
+    if bytes.len() != 6 || bytes[0] != VERSION || bytes[1] != KIND_ACK {
+      return Err(WireError::InvalidFormat);
+    }
+    let version = u32::from_le_bytes(bytes[2..6].try_into().map_err(|_| WireError::InvalidFormat)?);
+    Ok(Self(version))
+  }
+}
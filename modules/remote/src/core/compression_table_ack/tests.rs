@@ -0,0 +1,17 @@
+use super::CompressionTableAck;
+use crate::core::wire_error::WireError;
+
+#[test]
+fn ack_round_trips() {
+  let ack = CompressionTableAck::new(42);
+  assert_eq!(CompressionTableAck::decode(&ack.encode()).expect("decode"), ack);
+  assert_eq!(ack.version(), 42);
+}
+
+#[test]
+fn malformed_ack_is_rejected() {
+  let mut encoded = CompressionTableAck::new(1).encode();
+  encoded.push(0);
+  assert!(matches!(CompressionTableAck::decode(&encoded), Err(WireError::InvalidFormat)));
+  assert!(matches!(CompressionTableAck::decode(&[1, 0x20, 0, 0, 0, 0]), Err(WireError::InvalidFormat)));
+}
@@ -0,0 +1,71 @@
+//! Settings for actor path and manifest compression tables.
+
+use core::time::Duration;
+
+const DEFAULT_MAX_ENTRIES: usize = 256;
+const DEFAULT_ADVERTISEMENT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Controls the size of compression tables and how often they are advertised.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressionTableSettings {
+  max_actor_refs:         usize,
+  max_manifests:          usize,
+  advertisement_interval: Duration,
+}
+
+impl CompressionTableSettings {
+  /// Creates settings with 256 entries per table, advertised every second.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self {
+      max_actor_refs:         DEFAULT_MAX_ENTRIES,
+      max_manifests:          DEFAULT_MAX_ENTRIES,
+      advertisement_interval: DEFAULT_ADVERTISEMENT_INTERVAL,
+    }
+  }
+
+  /// Overrides the number of actor paths kept in a table.
+  #[must_use]
+  pub const fn with_max_actor_refs(mut self, max: usize) -> Self {
+    self.max_actor_refs = max;
+    self
+  }
+
+  /// Overrides the number of manifests kept in a table.
+  #[must_use]
+  pub const fn with_max_manifests(mut self, max: usize) -> Self {
+    self.max_manifests = max;
+    self
+  }
+
+  /// Overrides the interval between table advertisements.
+  #[must_use]
+  pub const fn with_advertisement_interval(mut self, interval: Duration) -> Self {
+    self.advertisement_interval = interval;
+    self
+  }
+
+  /// Returns the number of actor paths kept in a table.
+  #[must_use]
+  pub const fn max_actor_refs(&self) -> usize {
+    self.max_actor_refs
+  }
+
+  /// Returns the number of manifests kept in a table.
+  #[must_use]
+  pub const fn max_manifests(&self) -> usize {
+    self.max_manifests
+  }
+
+  /// Returns the interval between table advertisements.
+  #[must_use]
+  pub const fn advertisement_interval(&self) -> Duration {
+    self.advertisement_interval
+  }
+}
+
+impl Default for CompressionTableSettings {
+  fn default() -> Self {
+    Self::new()
+  }
+}
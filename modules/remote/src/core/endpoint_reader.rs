@@ -3,7 +3,12 @@
 #[cfg(test)]
 mod tests;
 
-use alloc::sync::Arc;
+use alloc::{
+  boxed::Box,
+  string::{String, ToString},
+  sync::Arc,
+  vec::Vec,
+};
 
 use fraktor_actor_rs::core::{
   actor_prim::{actor_path::ActorPath, actor_ref::ActorRefGeneric},
@@ -21,14 +26,23 @@ use fraktor_utils_rs::core::{
 #[cfg(feature = "tokio-transport")]
 use crate::core::tokio_actor_ref_provider::TokioActorRefProviderGeneric;
 use crate::core::{
-  endpoint_reader_error::EndpointReaderError, inbound_envelope::InboundEnvelope,
-  remote_actor_ref_provider::RemoteActorRefProviderGeneric, remoting_envelope::RemotingEnvelope,
+  endpoint_reader_error::EndpointReaderError, flight_recorder::RemotingFlightRecorder,
+  header_serializer_registry::HeaderSerializerRegistry, inbound_envelope::InboundEnvelope,
+  inbound_filter::InboundFilter, inbound_rejection::InboundRejection,
+  remote_actor_ref_provider::RemoteActorRefProviderGeneric, remote_deployment_serializer::RemoteDeploymentSerializer,
+  remoting_envelope::RemotingEnvelope,
 };
 
 /// Deserializes inbound transport envelopes into runtime messages.
+///
+/// Envelopes refused by an [`InboundFilter`] are never deserialized; they are recorded as
+/// [`InboundRejection`] dead letters instead.
 pub struct EndpointReaderGeneric<TB: RuntimeToolbox + 'static> {
   system:        ActorSystemGeneric<TB>,
   serialization: ArcShared<SerializationExtensionGeneric<TB>>,
+  filters:       Vec<ArcShared<dyn InboundFilter>>,
+  recorder:      Option<RemotingFlightRecorder>,
+  headers:       HeaderSerializerRegistry,
 }
 
 /// Type alias for `EndpointReaderGeneric` with the default `NoStdToolbox`.
@@ -36,7 +50,13 @@ pub type EndpointReader = EndpointReaderGeneric<NoStdToolbox>;
 
 impl<TB: RuntimeToolbox + 'static> Clone for EndpointReaderGeneric<TB> {
   fn clone(&self) -> Self {
-    Self { system: self.system.clone(), serialization: self.serialization.clone() }
+    Self {
+      system:        self.system.clone(),
+      serialization: self.serialization.clone(),
+      filters:       self.filters.clone(),
+      recorder:      self.recorder.clone(),
+      headers:       self.headers.clone(),
+    }
   }
 }
 
@@ -44,11 +64,55 @@ impl<TB: RuntimeToolbox + 'static> EndpointReaderGeneric<TB> {
   /// Creates a new reader bound to the provided actor system.
   #[must_use]
   pub fn new(system: ActorSystemGeneric<TB>, serialization: ArcShared<SerializationExtensionGeneric<TB>>) -> Self {
-    Self { system, serialization }
+    RemoteDeploymentSerializer::register(&serialization);
+    Self { system, serialization, filters: Vec::new(), recorder: None, headers: HeaderSerializerRegistry::new() }
+  }
+
+  /// Adds a filter consulted before envelopes are deserialized.
+  #[must_use]
+  pub fn with_inbound_filter(mut self, filter: ArcShared<dyn InboundFilter>) -> Self {
+    self.filters.push(filter);
+    self
   }
 
-  /// Decodes a remoting envelope into an inbound representation.
+  /// Records inbound rejections into `recorder`.
+  #[must_use]
+  pub fn with_flight_recorder(mut self, recorder: RemotingFlightRecorder) -> Self {
+    self.recorder = Some(recorder);
+    self
+  }
+
+  /// Replaces the serializers of the message headers received from remote nodes.
+  #[must_use]
+  pub fn with_header_serializers(mut self, registry: HeaderSerializerRegistry) -> Self {
+    self.headers = registry;
+    self
+  }
+
+  /// Decodes a remoting envelope whose sender is unknown into an inbound representation.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`EndpointReaderError`] when a filter rejects the envelope or the payload cannot be
+  /// deserialized.
   pub fn decode(&self, envelope: RemotingEnvelope) -> Result<InboundEnvelope<TB>, EndpointReaderError> {
+    self.decode_from(None, envelope)
+  }
+
+  /// Decodes a remoting envelope sent by `authority` into an inbound representation.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`EndpointReaderError`] when a filter rejects the envelope or the payload cannot be
+  /// deserialized.
+  pub fn decode_from(
+    &self,
+    authority: Option<&str>,
+    envelope: RemotingEnvelope,
+  ) -> Result<InboundEnvelope<TB>, EndpointReaderError> {
+    if let Err(reason) = self.filters.iter().try_for_each(|filter| filter.check(authority, &envelope)) {
+      return Err(EndpointReaderError::Rejected(Box::new(self.record_rejection(authority, &envelope, reason))));
+    }
     let recipient = envelope.recipient().clone();
     let remote_node = envelope.remote_node().clone();
     let reply_to = envelope.reply_to().cloned();
@@ -56,7 +120,13 @@ impl<TB: RuntimeToolbox + 'static> EndpointReaderGeneric<TB> {
     let priority = envelope.priority();
     let serialized = envelope.serialized_message().clone();
     match self.deserialize_message(&serialized) {
-      | Ok(message) => Ok(InboundEnvelope::new(recipient, remote_node, message, reply_to, correlation, priority)),
+      | Ok(mut message) => {
+        if let Some(trace_context) = envelope.trace_context() {
+          message.headers_mut().insert(trace_context.clone());
+        }
+        self.headers.decode_into(envelope.headers(), message.headers_mut());
+        Ok(InboundEnvelope::new(recipient, remote_node, message, reply_to, correlation, priority))
+      },
       | Err(error) => {
         self.record_deserialization_failure(&recipient);
         Err(EndpointReaderError::Deserialization(error))
@@ -77,6 +147,29 @@ impl<TB: RuntimeToolbox + 'static> EndpointReaderGeneric<TB> {
     Ok(AnyMessageGeneric::from_erased(shared, None))
   }
 
+  fn record_rejection(&self, authority: Option<&str>, envelope: &RemotingEnvelope, reason: String) -> InboundRejection {
+    let serialized = envelope.serialized_message();
+    let rejection = InboundRejection::new(
+      authority.map(ToString::to_string),
+      envelope.recipient().clone(),
+      serialized.manifest().map(ToString::to_string),
+      serialized.serializer_id(),
+      reason,
+    );
+    if let Some(recorder) = &self.recorder {
+      let millis = self.system.state().monotonic_now().as_millis() as u64;
+      recorder.record_inbound_rejected(
+        authority.unwrap_or("unknown"),
+        rejection.reason(),
+        envelope.correlation_id(),
+        millis,
+      );
+    }
+    let message = AnyMessageGeneric::new(rejection.clone());
+    self.system.record_dead_letter(message, DeadLetterReason::AccessDenied, None);
+    rejection
+  }
+
   fn record_deserialization_failure(&self, recipient: &ActorPath) {
     let message = AnyMessageGeneric::new(recipient.clone());
     self.system.record_dead_letter(message, DeadLetterReason::SerializationError, None);
@@ -123,6 +216,7 @@ impl<TB: RuntimeToolbox + 'static> EndpointReaderGeneric<TB> {
       return Some(reply_ref);
     }
 
-    None
+    // 監視フック越しに登録されたプロバイダーはスキーム経由でしか引けない
+    self.system.resolve_actor_ref(path.clone()).ok()
   }
 }
@@ -8,7 +8,7 @@ use fraktor_actor_rs::core::{
     actor_path::{ActorPath, ActorPathParts, GuardianKind},
   },
   error::ActorError,
-  messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric, TraceContext},
   props::PropsGeneric,
   scheduler::{ManualTestDriver, TickDriverConfig},
   serialization::{
@@ -122,6 +122,55 @@ fn decode_round_trip_returns_inbound_envelope() {
   assert!(inbound.reply_to_path().is_none());
 }
 
+#[test]
+fn trace_context_header_survives_the_round_trip() {
+  let system = build_system();
+  let serialization = serialization_extension(&system);
+  let reader = EndpointReader::new(system.clone(), serialization.clone());
+  let recipient = recipient_path("remote-app", GuardianKind::User, &["user", "svc"]);
+  let trace_context = TraceContext::new([7; 16], [8; 8], TraceContext::FLAG_SAMPLED);
+  let message = AnyMessageGeneric::new("ping".to_string()).with_header(trace_context.clone());
+  let mut writer = crate::core::EndpointWriter::new(system.clone(), serialization.clone());
+  writer.enqueue(OutboundMessage::user(message, recipient, remote_node())).expect("enqueue");
+  let remoting_envelope = writer.try_next().expect("serialize").expect("envelope");
+  assert_eq!(remoting_envelope.trace_context(), Some(&trace_context));
+
+  let inbound = reader.decode(remoting_envelope).expect("decode succeeds");
+
+  assert_eq!(inbound.message().headers().get::<TraceContext>(), Some(&trace_context));
+}
+
+#[test]
+fn registered_headers_survive_the_round_trip() {
+  let system = build_system();
+  let serialization = serialization_extension(&system);
+  let mut registry = crate::core::HeaderSerializerRegistry::new();
+  registry
+    .register(
+      "tenant-id",
+      |tenant: &u64| tenant.to_le_bytes().to_vec(),
+      |bytes: &[u8]| bytes.try_into().ok().map(u64::from_le_bytes),
+    )
+    .expect("register");
+  let reader = EndpointReader::new(system.clone(), serialization.clone()).with_header_serializers(registry.clone());
+  let recipient = recipient_path("remote-app", GuardianKind::User, &["user", "svc"]);
+  let mut message = AnyMessageGeneric::new("ping".to_string());
+  message.headers_mut().insert_propagated(42_u64);
+  message.headers_mut().insert(7_u32);
+  let mut writer =
+    crate::core::EndpointWriter::new(system.clone(), serialization.clone()).with_header_serializers(registry);
+  writer.enqueue(OutboundMessage::user(message, recipient, remote_node())).expect("enqueue");
+  let remoting_envelope = writer.try_next().expect("serialize").expect("envelope");
+  assert_eq!(remoting_envelope.headers().len(), 1);
+
+  let inbound = reader.decode(remoting_envelope).expect("decode succeeds");
+
+  let headers = inbound.message().headers();
+  assert_eq!(headers.get::<u64>(), Some(&42));
+  assert!(headers.is_propagated(core::any::TypeId::of::<u64>()));
+  assert!(!headers.contains::<u32>());
+}
+
 #[test]
 fn deserialization_failure_produces_dead_letter_error() {
   let system = build_system();
@@ -175,3 +224,39 @@ fn deliver_routes_message_to_local_actor() {
   reader.deliver(inbound).expect("deliver succeeds");
   assert_eq!(events.lock().as_slice(), &["delivered".to_string()]);
 }
+
+#[test]
+fn rejected_envelope_goes_to_dead_letters_without_deserialization() {
+  let system = build_system();
+  let serialization = serialization_extension(&system);
+  let recorder = crate::core::RemotingFlightRecorder::new(8);
+  let rules = crate::core::InboundAccessRules::new().with_allowed_path("/user/public");
+  let filter: ArcShared<dyn crate::core::InboundFilter> = ArcShared::new(rules);
+  let reader = EndpointReader::new(system.clone(), serialization.clone())
+    .with_inbound_filter(filter)
+    .with_flight_recorder(recorder.clone());
+  let recipient = recipient_path("remote-app", GuardianKind::User, &["user", "admin"]);
+  let mut writer = crate::core::EndpointWriter::new(system.clone(), serialization);
+  writer.enqueue(outbound_message(&recipient)).expect("enqueue");
+  let envelope = writer.try_next().expect("serialize").expect("envelope");
+
+  let result = reader.decode_from(Some("10.0.0.9:2552"), envelope);
+
+  let Err(crate::core::EndpointReaderError::Rejected(rejection)) = result else {
+    panic!("expected rejection");
+  };
+  assert_eq!(rejection.authority(), Some("10.0.0.9:2552"));
+  assert_eq!(rejection.manifest(), Some("tests.String"));
+  assert_eq!(rejection.reason(), "recipient `/user/admin` is not remotely addressable");
+  let dead_letters = system.dead_letters();
+  let entry = dead_letters
+    .iter()
+    .find(|entry| entry.reason() == fraktor_actor_rs::core::dead_letter::DeadLetterReason::AccessDenied)
+    .expect("dead letter recorded");
+  assert_eq!(entry.message().as_view().downcast_ref::<crate::core::InboundRejection>(), Some(&*rejection));
+  let metrics = recorder.snapshot();
+  assert!(matches!(
+    metrics.records().first().map(|metric| (metric.authority(), metric.kind())),
+    Some(("10.0.0.9:2552", crate::core::FlightMetricKind::InboundRejected { reason })) if reason == rejection.reason()
+  ));
+}
@@ -1,18 +1,25 @@
 //! Error variants emitted when decoding inbound transport frames.
 
+use alloc::boxed::Box;
+
 use fraktor_actor_rs::core::serialization::SerializationError;
 
+use crate::core::inbound_rejection::InboundRejection;
+
 /// Represents failures that can occur while decoding inbound envelopes.
 #[derive(Debug)]
 pub enum EndpointReaderError {
   /// The payload could not be deserialized into a runtime message.
   Deserialization(SerializationError),
+  /// An inbound filter refused the envelope.
+  Rejected(Box<InboundRejection>),
 }
 
 impl core::fmt::Display for EndpointReaderError {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     match self {
       | Self::Deserialization(error) => write!(f, "deserialization failed: {error:?}"),
+      | Self::Rejected(rejection) => write!(f, "inbound envelope rejected: {}", rejection.reason()),
     }
   }
 }
@@ -3,14 +3,24 @@
 #[cfg(test)]
 mod tests;
 
-use alloc::string::String;
+use alloc::{
+  collections::BTreeMap,
+  format,
+  string::{String, ToString},
+  vec::Vec,
+};
 use core::{
   marker::PhantomData,
-  sync::atomic::{AtomicBool, AtomicU64, Ordering},
+  ops::Bound,
+  sync::atomic::{AtomicU64, Ordering},
+  task::{Poll, Waker},
 };
 
 use fraktor_actor_rs::core::{
+  dead_letter::DeadLetterReason,
   event_stream::BackpressureSignal,
+  mailbox::{MailboxCapacity, MailboxOverflowStrategy},
+  messaging::TraceContext,
   serialization::{SerializationCallScope, SerializationExtensionGeneric},
   system::ActorSystemGeneric,
 };
@@ -24,28 +34,57 @@ use fraktor_utils_rs::core::{
 };
 
 use crate::core::{
-  endpoint_writer_error::EndpointWriterError, outbound_message::OutboundMessage, outbound_priority::OutboundPriority,
+  endpoint_writer_error::EndpointWriterError, header_serializer_registry::HeaderSerializerRegistry,
+  outbound_message::OutboundMessage, outbound_priority::OutboundPriority, outbound_queue_policy::OutboundQueuePolicy,
+  remote_deployment_serializer::RemoteDeploymentSerializer, remote_node_id::RemoteNodeId,
   remoting_envelope::RemotingEnvelope,
 };
 
 const DEFAULT_QUEUE_CAPACITY: usize = 128;
 
+type OutboundQueue<TB> = SyncFifoQueue<OutboundMessage<TB>, VecDequeBackend<OutboundMessage<TB>>>;
+
 /// Shared writer handle protected by the toolbox mutex family.
 pub type EndpointWriterShared<TB> =
   ArcShared<<<TB as RuntimeToolbox>::MutexFamily as SyncMutexFamily>::Mutex<EndpointWriterGeneric<TB>>>;
 
 /// Serializes outbound messages, enforcing priority and backpressure.
+///
+/// Messages are queued per remote association according to the [`OutboundQueuePolicy`]. System
+/// messages of every association are drained before user messages, and user messages are drained
+/// round-robin across associations so that one busy peer cannot starve the others.
 pub struct EndpointWriterGeneric<TB: RuntimeToolbox + 'static> {
-  #[allow(dead_code)]
   system:        ActorSystemGeneric<TB>,
   serialization: ArcShared<SerializationExtensionGeneric<TB>>,
-  system_queue:  SyncFifoQueue<OutboundMessage<TB>, VecDequeBackend<OutboundMessage<TB>>>,
-  user_queue:    SyncFifoQueue<OutboundMessage<TB>, VecDequeBackend<OutboundMessage<TB>>>,
-  user_paused:   AtomicBool,
+  policy:        OutboundQueuePolicy,
+  headers:       HeaderSerializerRegistry,
+  lanes:         BTreeMap<String, AssociationLanes<TB>>,
+  // ラウンドロビンで最後にユーザーメッセージを取り出した authority
+  last_served:   Option<String>,
+  // 容量の空きや backpressure の解除を待つ offer
+  waiters:       Vec<Waker>,
   correlation:   AtomicU64,
   _marker:       PhantomData<TB>,
 }
 
+struct AssociationLanes<TB: RuntimeToolbox + 'static> {
+  system: OutboundQueue<TB>,
+  user:   OutboundQueue<TB>,
+  paused: bool,
+}
+
+impl<TB: RuntimeToolbox + 'static> AssociationLanes<TB> {
+  fn new() -> Self {
+    Self { system: Self::new_queue(), user: Self::new_queue(), paused: false }
+  }
+
+  fn new_queue() -> OutboundQueue<TB> {
+    // 容量の上限は OutboundQueuePolicy に従って writer 側で判定する
+    let backend = VecDequeBackend::with_capacity(DEFAULT_QUEUE_CAPACITY, OverflowPolicy::Grow);
+    SyncQueue::new(backend)
+  }
+}
+
 /// Type alias for `EndpointWriterGeneric` with the default `NoStdToolbox`.
 pub type EndpointWriter = EndpointWriterGeneric<NoStdToolbox>;
 
@@ -53,17 +92,40 @@ impl<TB: RuntimeToolbox + 'static> EndpointWriterGeneric<TB> {
   /// Creates a writer bound to the provided actor system and serialization extension.
   #[must_use]
   pub fn new(system: ActorSystemGeneric<TB>, serialization: ArcShared<SerializationExtensionGeneric<TB>>) -> Self {
+    RemoteDeploymentSerializer::register(&serialization);
     Self {
       system,
       serialization,
-      system_queue: Self::new_queue(),
-      user_queue: Self::new_queue(),
-      user_paused: AtomicBool::new(false),
+      policy: OutboundQueuePolicy::unbounded(),
+      headers: HeaderSerializerRegistry::new(),
+      lanes: BTreeMap::new(),
+      last_served: None,
+      waiters: Vec::new(),
       correlation: AtomicU64::new(1),
       _marker: PhantomData,
     }
   }
 
+  /// Replaces the capacity and overflow policy of the per-association queues.
+  #[must_use]
+  pub const fn with_queue_policy(mut self, policy: OutboundQueuePolicy) -> Self {
+    self.policy = policy;
+    self
+  }
+
+  /// Replaces the serializers of the message headers sent to remote nodes.
+  #[must_use]
+  pub fn with_header_serializers(mut self, registry: HeaderSerializerRegistry) -> Self {
+    self.headers = registry;
+    self
+  }
+
+  /// Returns the queue policy.
+  #[must_use]
+  pub const fn queue_policy(&self) -> OutboundQueuePolicy {
+    self.policy
+  }
+
   /// Returns the canonical authority (host[:port]) of the bound actor system when available.
   #[must_use]
   pub fn canonical_authority_components(&self) -> Option<(String, Option<u16>)> {
@@ -77,78 +139,223 @@ impl<TB: RuntimeToolbox + 'static> EndpointWriterGeneric<TB> {
   }
 
   /// Enqueues an outbound message using its declared priority.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`EndpointWriterError::Backpressure`] when user messages to the association are
+  /// paused, and [`EndpointWriterError::QueueFull`] when the lane is full and the overflow
+  /// strategy rejects the message.
   pub fn enqueue(&mut self, message: OutboundMessage<TB>) -> Result<(), EndpointWriterError> {
+    let authority = Self::authority_of(message.remote_node());
     let priority = message.priority();
+    let policy = self.policy;
+    let lanes = self.lanes.entry(authority.clone()).or_insert_with(AssociationLanes::new);
     match priority {
-      | OutboundPriority::System => self.offer_system(message),
-      | OutboundPriority::User => self.offer_user(message),
+      | OutboundPriority::System => {
+        if Self::is_full(policy.system_capacity(), lanes.system.len()) {
+          return Err(EndpointWriterError::QueueFull(priority));
+        }
+        Self::offer(&mut lanes.system, priority, message)
+      },
+      | OutboundPriority::User => {
+        if lanes.paused {
+          return Err(EndpointWriterError::Backpressure(authority));
+        }
+        if Self::is_full(policy.user_capacity(), lanes.user.len()) {
+          match policy.overflow() {
+            | MailboxOverflowStrategy::DropNewest | MailboxOverflowStrategy::Block => {
+              return Err(EndpointWriterError::QueueFull(priority));
+            },
+            | MailboxOverflowStrategy::DropOldest => {
+              if let Ok(evicted) = lanes.user.poll() {
+                let (payload, ..) = evicted.into_parts();
+                self.system.record_dead_letter(payload, DeadLetterReason::MailboxFull, None);
+              }
+            },
+            | MailboxOverflowStrategy::Grow => {},
+          }
+        }
+        Self::offer(&mut lanes.user, priority, message)
+      },
     }
   }
 
   /// Returns the next serialized envelope if available.
   pub fn try_next(&mut self) -> Result<Option<RemotingEnvelope>, EndpointWriterError> {
-    if let Some(message) = self.poll_system()? {
-      return self.serialize(message, OutboundPriority::System).map(Some);
+    match self.try_next_message()? {
+      | Some(message) => self.serialize_outbound(message).map(Some),
+      | None => Ok(None),
     }
+  }
 
-    if self.user_paused.load(Ordering::Relaxed) {
-      return Ok(None);
+  /// Dequeues the next outbound message without serializing it, system messages first.
+  ///
+  /// Pair it with [`serialize_outbound`](Self::serialize_outbound) to time the serialization
+  /// apart from the queue handling.
+  pub fn try_next_message(&mut self) -> Result<Option<OutboundMessage<TB>>, EndpointWriterError> {
+    if let Some(message) = self.poll_next(OutboundPriority::System)? {
+      return Ok(Some(message));
     }
+    self.poll_next(OutboundPriority::User)
+  }
 
-    if let Some(message) = self.poll_user()? {
-      return self.serialize(message, OutboundPriority::User).map(Some);
-    }
+  /// Serializes a message dequeued with [`try_next_message`](Self::try_next_message).
+  pub fn serialize_outbound(&self, message: OutboundMessage<TB>) -> Result<RemotingEnvelope, EndpointWriterError> {
+    let priority = message.priority();
+    self.serialize(message, priority)
+  }
 
-    Ok(None)
+  /// Returns the number of messages queued for `authority` (`host:port`).
+  #[must_use]
+  pub fn queue_depth(&self, authority: &str) -> usize {
+    self.lanes.get(authority).map_or(0, |lanes| lanes.system.len() + lanes.user.len())
+  }
+
+  /// Returns the number of queued messages of every authority that used the writer.
+  pub fn queue_depths(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+    self.lanes.iter().map(|(authority, lanes)| (authority.as_str(), lanes.system.len() + lanes.user.len()))
   }
 
   /// Serializes the outbound message immediately (used by loopback routing).
   pub fn serialize_for_loopback(&self, message: OutboundMessage<TB>) -> Result<RemotingEnvelope, EndpointWriterError> {
-    let priority = message.priority();
-    self.serialize(message, priority)
+    self.serialize_outbound(message)
   }
 
-  /// Applies the provided backpressure signal.
-  pub fn handle_backpressure(&self, signal: BackpressureSignal) {
+  /// Applies the backpressure signal emitted for `authority`.
+  ///
+  /// While backpressure is applied, user messages to the association are neither accepted nor
+  /// drained; system messages keep flowing.
+  pub fn handle_backpressure(&mut self, authority: &str, signal: BackpressureSignal) {
     match signal {
-      | BackpressureSignal::Apply => self.user_paused.store(true, Ordering::Relaxed),
-      | BackpressureSignal::Release => self.user_paused.store(false, Ordering::Relaxed),
+      | BackpressureSignal::Apply => {
+        self.lanes.entry(authority.to_string()).or_insert_with(AssociationLanes::new).paused = true;
+      },
+      | BackpressureSignal::Release => {
+        if let Some(lanes) = self.lanes.get_mut(authority) {
+          lanes.paused = false;
+        }
+        self.wake_waiters();
+      },
     }
   }
 
-  fn offer_system(&mut self, message: OutboundMessage<TB>) -> Result<(), EndpointWriterError> {
-    match self.system_queue.offer(message) {
-      | Ok(OfferOutcome::Enqueued)
-      | Ok(OfferOutcome::DroppedNewest { .. })
-      | Ok(OfferOutcome::DroppedOldest { .. }) => Ok(()),
-      | Ok(OfferOutcome::GrewTo { .. }) => Ok(()),
-      | Err(error) => Err(Self::map_offer_error(OutboundPriority::System, error)),
+  /// Returns `true` while backpressure is applied to `authority`.
+  #[must_use]
+  pub fn is_backpressured(&self, authority: &str) -> bool {
+    self.lanes.get(authority).is_some_and(|lanes| lanes.paused)
+  }
+
+  /// Enqueues the message held in `slot` once its association accepts it, registering `waker`
+  /// otherwise.
+  pub(crate) fn poll_offer(
+    &mut self,
+    slot: &mut Option<OutboundMessage<TB>>,
+    waker: &Waker,
+  ) -> Poll<Result<(), EndpointWriterError>> {
+    let Some(message) = slot.take() else {
+      return Poll::Ready(Ok(()));
+    };
+    if self.must_wait(&message) {
+      *slot = Some(message);
+      // 同じタスクが繰り返し poll しても waker を積み増さない
+      if !self.waiters.iter().any(|waiter| waiter.will_wake(waker)) {
+        self.waiters.push(waker.clone());
+      }
+      return Poll::Pending;
     }
+    Poll::Ready(self.enqueue(message))
   }
 
-  fn offer_user(&mut self, message: OutboundMessage<TB>) -> Result<(), EndpointWriterError> {
-    match self.user_queue.offer(message) {
-      | Ok(OfferOutcome::Enqueued)
-      | Ok(OfferOutcome::DroppedNewest { .. })
-      | Ok(OfferOutcome::DroppedOldest { .. }) => Ok(()),
-      | Ok(OfferOutcome::GrewTo { .. }) => Ok(()),
-      | Err(error) => Err(Self::map_offer_error(OutboundPriority::User, error)),
+  fn must_wait(&self, message: &OutboundMessage<TB>) -> bool {
+    let Some(lanes) = self.lanes.get(&Self::authority_of(message.remote_node())) else {
+      return false;
+    };
+    match message.priority() {
+      | OutboundPriority::System => Self::is_full(self.policy.system_capacity(), lanes.system.len()),
+      | OutboundPriority::User => {
+        lanes.paused
+          || (Self::is_full(self.policy.user_capacity(), lanes.user.len())
+            && matches!(self.policy.overflow(), MailboxOverflowStrategy::DropNewest | MailboxOverflowStrategy::Block))
+      },
     }
   }
 
-  fn poll_system(&mut self) -> Result<Option<OutboundMessage<TB>>, EndpointWriterError> {
-    match self.system_queue.poll() {
-      | Ok(message) => Ok(Some(message)),
+  fn poll_next(&mut self, priority: OutboundPriority) -> Result<Option<OutboundMessage<TB>>, EndpointWriterError> {
+    // 前回取り出した authority の次から順に探し、末尾まで進んだら先頭に戻る
+    let start = match (&priority, &self.last_served) {
+      | (OutboundPriority::User, Some(last)) => Bound::Excluded(last.clone()),
+      | _ => Bound::Unbounded,
+    };
+    let found = self
+      .lanes
+      .range::<String, _>((start.clone(), Bound::Unbounded))
+      .chain(self.lanes.range::<String, _>((Bound::Unbounded, Self::wrap_end(&start))))
+      .find(|(_, lanes)| match priority {
+        | OutboundPriority::System => !lanes.system.is_empty(),
+        | OutboundPriority::User => !lanes.paused && !lanes.user.is_empty(),
+      })
+      .map(|(authority, _)| authority.clone());
+    let Some(authority) = found else {
+      return Ok(None);
+    };
+    let Some(lanes) = self.lanes.get_mut(&authority) else {
+      return Ok(None);
+    };
+    let queue = match priority {
+      | OutboundPriority::System => &mut lanes.system,
+      | OutboundPriority::User => &mut lanes.user,
+    };
+    match queue.poll() {
+      | Ok(message) => {
+        if priority == OutboundPriority::User {
+          self.last_served = Some(authority);
+        }
+        self.wake_waiters();
+        Ok(Some(message))
+      },
       | Err(QueueError::Empty) => Ok(None),
-      | Err(error) => Err(Self::map_poll_error(OutboundPriority::System, error)),
+      | Err(error) => Err(Self::map_poll_error(priority, error)),
     }
   }
 
-  fn poll_user(&mut self) -> Result<Option<OutboundMessage<TB>>, EndpointWriterError> {
-    match self.user_queue.poll() {
-      | Ok(message) => Ok(Some(message)),
-      | Err(QueueError::Empty) => Ok(None),
-      | Err(error) => Err(Self::map_poll_error(OutboundPriority::User, error)),
+  fn wrap_end(start: &Bound<String>) -> Bound<String> {
+    match start {
+      | Bound::Excluded(last) => Bound::Included(last.clone()),
+      | _ => Bound::Excluded(String::new()),
+    }
+  }
+
+  fn wake_waiters(&mut self) {
+    for waker in self.waiters.drain(..) {
+      waker.wake();
+    }
+  }
+
+  const fn is_full(capacity: MailboxCapacity, len: usize) -> bool {
+    match capacity {
+      | MailboxCapacity::Bounded { capacity } => len >= capacity.get(),
+      | MailboxCapacity::Unbounded => false,
+    }
+  }
+
+  fn offer(
+    queue: &mut OutboundQueue<TB>,
+    priority: OutboundPriority,
+    message: OutboundMessage<TB>,
+  ) -> Result<(), EndpointWriterError> {
+    match queue.offer(message) {
+      | Ok(OfferOutcome::Enqueued)
+      | Ok(OfferOutcome::DroppedNewest { .. })
+      | Ok(OfferOutcome::DroppedOldest { .. }) => Ok(()),
+      | Ok(OfferOutcome::GrewTo { .. }) => Ok(()),
+      | Err(error) => Err(Self::map_offer_error(priority, error)),
+    }
+  }
+
+  fn authority_of(node: &RemoteNodeId) -> String {
+    match node.port() {
+      | Some(port) => format!("{}:{port}", node.host()),
+      | None => node.host().into(),
     }
   }
 
@@ -163,7 +370,12 @@ impl<TB: RuntimeToolbox + 'static> EndpointWriterGeneric<TB> {
       .serialize(payload.payload(), SerializationCallScope::Remote)
       .map_err(EndpointWriterError::Serialization)?;
     let correlation_id = self.next_correlation_id();
-    Ok(RemotingEnvelope::new(recipient, remote_node, reply_to, serialized, correlation_id, priority))
+    let envelope = RemotingEnvelope::new(recipient, remote_node, reply_to, serialized, correlation_id, priority)
+      .with_headers(self.headers.encode(payload.headers()));
+    Ok(match payload.headers().get::<TraceContext>() {
+      | Some(trace_context) => envelope.with_trace_context(trace_context.clone()),
+      | None => envelope,
+    })
   }
 
   fn next_correlation_id(&self) -> fraktor_actor_rs::core::event_stream::CorrelationId {
@@ -171,11 +383,6 @@ impl<TB: RuntimeToolbox + 'static> EndpointWriterGeneric<TB> {
     fraktor_actor_rs::core::event_stream::CorrelationId::from_u128(value)
   }
 
-  fn new_queue() -> SyncFifoQueue<OutboundMessage<TB>, VecDequeBackend<OutboundMessage<TB>>> {
-    let backend = VecDequeBackend::with_capacity(DEFAULT_QUEUE_CAPACITY, OverflowPolicy::Grow);
-    SyncQueue::new(backend)
-  }
-
   fn map_offer_error(priority: OutboundPriority, error: QueueError<OutboundMessage<TB>>) -> EndpointWriterError {
     match error {
       | QueueError::Full(_) => EndpointWriterError::QueueFull(priority),
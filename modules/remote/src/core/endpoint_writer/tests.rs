@@ -1,11 +1,17 @@
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
+use core::{
+  num::NonZeroUsize,
+  task::{RawWaker, RawWakerVTable, Waker},
+};
 
 use fraktor_actor_rs::core::{
   actor_prim::{
     Actor, ActorContextGeneric,
     actor_path::{ActorPath, ActorPathParts, GuardianKind},
   },
+  dead_letter::DeadLetterReason,
   error::ActorError,
+  mailbox::MailboxOverflowStrategy,
   messaging::AnyMessageGeneric,
   props::PropsGeneric,
   scheduler::{ManualTestDriver, TickDriverConfig},
@@ -19,7 +25,8 @@ use fraktor_utils_rs::core::{runtime_toolbox::NoStdToolbox, sync::ArcShared};
 
 use super::*;
 use crate::core::{
-  outbound_message::OutboundMessage, outbound_priority::OutboundPriority, remote_node_id::RemoteNodeId,
+  outbound_message::OutboundMessage, outbound_priority::OutboundPriority, outbound_queue_policy::OutboundQueuePolicy,
+  remote_node_id::RemoteNodeId,
 };
 
 struct NoopActor;
@@ -76,6 +83,40 @@ fn actor_path(system: &str, guardian: GuardianKind, segments: &[&str]) -> ActorP
   path
 }
 
+unsafe fn noop_clone(_: *const ()) -> RawWaker {
+  noop_raw_waker()
+}
+
+unsafe fn noop_wake(_: *const ()) {}
+
+unsafe fn noop_wake_by_ref(_: *const ()) {}
+
+unsafe fn noop_drop(_: *const ()) {}
+
+const NOOP_WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(noop_clone, noop_wake, noop_wake_by_ref, noop_drop);
+
+fn noop_raw_waker() -> RawWaker {
+  RawWaker::new(core::ptr::null(), &NOOP_WAKER_VTABLE)
+}
+
+fn noop_waker() -> Waker {
+  unsafe { Waker::from_raw(noop_raw_waker()) }
+}
+
+fn bounded_writer(
+  capacity: usize,
+  overflow: MailboxOverflowStrategy,
+) -> (EndpointWriterGeneric<NoStdToolbox>, ActorSystemGeneric<NoStdToolbox>) {
+  let (writer, system) = build_writer();
+  let policy = OutboundQueuePolicy::bounded(NonZeroUsize::new(capacity).expect("capacity"), overflow);
+  (writer.with_queue_policy(policy), system)
+}
+
+fn user_message_to(content: &str, recipient: &ActorPath, port: u16) -> OutboundMessage<NoStdToolbox> {
+  let message = AnyMessageGeneric::new(content.to_string());
+  OutboundMessage::user(message, recipient.clone(), RemoteNodeId::new("remote-system", "127.0.0.1", Some(port), 42))
+}
+
 fn user_message(content: &str, recipient: &ActorPath, reply_to: Option<ActorPath>) -> OutboundMessage<NoStdToolbox> {
   let message = AnyMessageGeneric::new(content.to_string());
   let remote = remote_node();
@@ -107,6 +148,25 @@ fn serialize_user_message_includes_manifest_and_reply_to() {
   assert!(serialized.bytes().ends_with(b"ping"));
 }
 
+#[test]
+fn dequeued_messages_are_serialized_separately() {
+  let (mut writer, _system) = build_writer();
+  let recipient = actor_path("remote-app", GuardianKind::User, &["user", "service"]);
+  writer.enqueue(user_message("user-1", &recipient, None)).expect("enqueue user");
+  writer.enqueue(system_message("sys-1", &recipient)).expect("enqueue system");
+
+  let first = writer.try_next_message().expect("poll").expect("message");
+  assert_eq!(first.priority(), OutboundPriority::System);
+  assert_eq!(writer.queue_depth("127.0.0.1:2552"), 1);
+
+  let envelope = writer.serialize_outbound(first).expect("serialize");
+  assert!(envelope.is_system());
+  assert!(envelope.serialized_message().bytes().ends_with(b"sys-1"));
+  let second = writer.try_next_message().expect("poll").expect("message");
+  assert_eq!(second.priority(), OutboundPriority::User);
+  assert!(writer.try_next_message().expect("poll").is_none());
+}
+
 #[test]
 fn system_priority_and_backpressure_control() {
   let (mut writer, _system) = build_writer();
@@ -117,8 +177,13 @@ fn system_priority_and_backpressure_control() {
   let first = writer.try_next().expect("poll").expect("envelope");
   assert!(first.is_system());
 
-  writer.handle_backpressure(BackpressureSignal::Apply);
   writer.enqueue(user_message("user-2", &recipient, None)).expect("enqueue user");
+  writer.handle_backpressure("127.0.0.1:2552", BackpressureSignal::Apply);
+  assert!(writer.is_backpressured("127.0.0.1:2552"));
+  assert!(matches!(
+    writer.enqueue(user_message("user-3", &recipient, None)),
+    Err(EndpointWriterError::Backpressure(authority)) if authority == "127.0.0.1:2552"
+  ));
   let blocked = writer.try_next().expect("poll");
   assert!(blocked.is_none());
 
@@ -126,7 +191,99 @@ fn system_priority_and_backpressure_control() {
   let sys_delivery = writer.try_next().expect("poll").expect("envelope");
   assert!(sys_delivery.is_system());
 
-  writer.handle_backpressure(BackpressureSignal::Release);
+  writer.handle_backpressure("127.0.0.1:2552", BackpressureSignal::Release);
   let resumed = writer.try_next().expect("poll").expect("envelope");
   assert_eq!(resumed.priority(), OutboundPriority::User);
+  let resumed = writer.try_next().expect("poll").expect("envelope");
+  assert!(resumed.serialized_message().bytes().ends_with(b"user-2"));
+}
+
+#[test]
+fn queue_depth_is_tracked_per_authority() {
+  let (mut writer, _system) = build_writer();
+  let recipient = actor_path("remote-app", GuardianKind::User, &["user", "service"]);
+  writer.enqueue(user_message("user-1", &recipient, None)).expect("enqueue user");
+  writer.enqueue(system_message("sys-1", &recipient)).expect("enqueue system");
+  assert_eq!(writer.queue_depth("127.0.0.1:2552"), 2);
+  assert_eq!(writer.queue_depth("127.0.0.1:2553"), 0);
+
+  writer.try_next().expect("poll").expect("envelope");
+  writer.try_next().expect("poll").expect("envelope");
+  assert_eq!(writer.queue_depths().collect::<alloc::vec::Vec<_>>(), [("127.0.0.1:2552", 0)]);
+}
+
+#[test]
+fn drop_newest_rejects_messages_beyond_the_user_capacity() {
+  let (mut writer, _system) = bounded_writer(1, MailboxOverflowStrategy::DropNewest);
+  let recipient = actor_path("remote-app", GuardianKind::User, &["user", "service"]);
+  writer.enqueue(user_message("user-1", &recipient, None)).expect("enqueue user");
+  assert!(matches!(
+    writer.enqueue(user_message("user-2", &recipient, None)),
+    Err(EndpointWriterError::QueueFull(OutboundPriority::User))
+  ));
+  // 容量はユーザーレーンのみに適用され、システムメッセージは受け付ける
+  writer.enqueue(system_message("sys-1", &recipient)).expect("enqueue system");
+  // 他の association の容量は独立している
+  writer.enqueue(user_message_to("other", &recipient, 2553)).expect("enqueue other association");
+  assert_eq!(writer.queue_depth("127.0.0.1:2552"), 2);
+}
+
+#[test]
+fn drop_oldest_evicts_the_oldest_message_as_a_dead_letter() {
+  let (mut writer, system) = bounded_writer(1, MailboxOverflowStrategy::DropOldest);
+  let recipient = actor_path("remote-app", GuardianKind::User, &["user", "service"]);
+  writer.enqueue(user_message("user-1", &recipient, None)).expect("enqueue user");
+  writer.enqueue(user_message("user-2", &recipient, None)).expect("enqueue user");
+
+  let envelope = writer.try_next().expect("poll").expect("envelope");
+  assert!(envelope.serialized_message().bytes().ends_with(b"user-2"));
+  let dead_letters = system.dead_letters();
+  assert_eq!(dead_letters.len(), 1);
+  assert_eq!(dead_letters[0].reason(), DeadLetterReason::MailboxFull);
+  assert_eq!(dead_letters[0].message().payload().downcast_ref::<String>().map(String::as_str), Some("user-1"));
+}
+
+#[test]
+fn user_messages_are_drained_round_robin_across_associations() {
+  let (mut writer, _system) = build_writer();
+  let recipient = actor_path("remote-app", GuardianKind::User, &["user", "service"]);
+  for content in ["a-1", "a-2", "a-3"] {
+    writer.enqueue(user_message_to(content, &recipient, 2552)).expect("enqueue");
+  }
+  writer.enqueue(user_message_to("b-1", &recipient, 2553)).expect("enqueue");
+
+  let order: Vec<u16> = core::iter::from_fn(|| writer.try_next().expect("poll"))
+    .map(|envelope| envelope.remote_node().port().expect("port"))
+    .collect();
+  assert_eq!(order, [2552, 2553, 2552, 2552]);
+}
+
+#[test]
+fn poll_offer_waits_for_capacity_instead_of_failing() {
+  let (mut writer, _system) = bounded_writer(1, MailboxOverflowStrategy::Block);
+  let recipient = actor_path("remote-app", GuardianKind::User, &["user", "service"]);
+  let waker = noop_waker();
+  writer.enqueue(user_message("user-1", &recipient, None)).expect("enqueue user");
+
+  let mut slot = Some(user_message("user-2", &recipient, None));
+  assert!(writer.poll_offer(&mut slot, &waker).is_pending());
+  assert!(slot.is_some());
+
+  writer.try_next().expect("poll").expect("envelope");
+  assert!(matches!(writer.poll_offer(&mut slot, &waker), core::task::Poll::Ready(Ok(()))));
+  assert_eq!(writer.queue_depth("127.0.0.1:2552"), 1);
+}
+
+#[test]
+fn repeated_pending_polls_register_the_waker_once() {
+  let (mut writer, _system) = bounded_writer(1, MailboxOverflowStrategy::Block);
+  let recipient = actor_path("remote-app", GuardianKind::User, &["user", "service"]);
+  let waker = noop_waker();
+  writer.enqueue(user_message("user-1", &recipient, None)).expect("enqueue user");
+
+  let mut slot = Some(user_message("user-2", &recipient, None));
+  for _ in 0..3 {
+    assert!(writer.poll_offer(&mut slot, &waker).is_pending());
+  }
+  assert_eq!(writer.waiters.len(), 1);
 }
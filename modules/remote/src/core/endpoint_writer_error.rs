@@ -1,6 +1,9 @@
 //! Error variants produced by [`EndpointWriter`](crate::core::endpoint_writer::EndpointWriter).
 
-use fraktor_actor_rs::core::serialization::SerializationError;
+use alloc::string::String;
+
+use fraktor_actor_rs::core::{error::SendError, messaging::AnyMessageGeneric, serialization::SerializationError};
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
 
 use crate::core::outbound_priority::OutboundPriority;
 
@@ -18,6 +21,20 @@ pub enum EndpointWriterError {
     /// Description of the failure.
     reason:   &'static str,
   },
+  /// Backpressure is applied to the association (`host:port`); user messages are refused until
+  /// it is released.
+  Backpressure(String),
   /// Serialization failed for the message payload.
   Serialization(SerializationError),
 }
+
+impl EndpointWriterError {
+  /// Converts the error into the [`SendError`] reported to the local sender of `message`.
+  pub(crate) fn into_send_error<TB: RuntimeToolbox>(self, message: AnyMessageGeneric<TB>) -> SendError<TB> {
+    match self {
+      | Self::QueueFull(_) => SendError::full(message),
+      | Self::Backpressure(_) => SendError::suspended(message),
+      | Self::QueueClosed(_) | Self::QueueUnavailable { .. } | Self::Serialization(_) => SendError::closed(message),
+    }
+  }
+}
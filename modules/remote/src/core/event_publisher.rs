@@ -74,6 +74,14 @@ impl<TB: RuntimeToolbox + 'static> EventPublisherGeneric<TB> {
     self.publish_lifecycle(RemotingLifecycleEvent::Gated { authority: authority.into(), correlation_id });
   }
 
+  /// Emits a `HandshakeFailed` lifecycle event.
+  pub fn publish_handshake_failed(&self, authority: impl Into<String>, reason: impl Into<String>) {
+    self.publish_lifecycle(RemotingLifecycleEvent::HandshakeFailed {
+      authority: authority.into(),
+      reason:    reason.into(),
+    });
+  }
+
   /// Emits an arbitrary lifecycle event (e.g., Starting/Started/Shutdown).
   pub fn publish_lifecycle(&self, event: RemotingLifecycleEvent) {
     self.system.publish_event(&EventStreamEvent::RemotingLifecycle(event));
@@ -94,3 +94,21 @@ fn publishes_backpressure_event() {
   let events = recorder.events.lock().clone();
   assert!(events.iter().any(|event| matches!(event, EventStreamEvent::RemotingBackpressure(ev) if ev.authority() == "loopback:9000" && ev.correlation_id() == correlation)));
 }
+
+#[test]
+fn publishes_handshake_failed_event() {
+  let system = build_system();
+  let publisher = EventPublisher::new(system.clone());
+  let (recorder, _subscription) = subscribe(&system);
+
+  publisher.publish_handshake_failed("127.0.0.1:2552", "invalid peer certificate");
+
+  let events = recorder.events.lock().clone();
+  assert!(events.iter().any(|event| {
+    matches!(
+      event,
+      EventStreamEvent::RemotingLifecycle(RemotingLifecycleEvent::HandshakeFailed { authority, reason })
+      if authority == "127.0.0.1:2552" && reason == "invalid peer certificate"
+    )
+  }));
+}
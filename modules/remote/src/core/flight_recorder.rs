@@ -1,10 +1,12 @@
 //! Flight recorder modules.
 
+mod compression_sample;
 mod flight_metric_kind;
 mod remoting_flight_recorder;
 mod remoting_flight_recorder_snapshot;
 mod remoting_metric;
 
+pub use compression_sample::CompressionSample;
 pub use flight_metric_kind::FlightMetricKind;
 pub use remoting_flight_recorder::RemotingFlightRecorder;
 pub use remoting_flight_recorder_snapshot::RemotingFlightRecorderSnapshot;
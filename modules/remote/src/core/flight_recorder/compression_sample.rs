@@ -0,0 +1,37 @@
+//! Size observation of a compressed envelope payload.
+
+/// Size observation of a compressed envelope payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressionSample {
+  original_bytes:   usize,
+  compressed_bytes: usize,
+}
+
+impl CompressionSample {
+  /// Creates a sample from the payload size before and after compression.
+  #[must_use]
+  pub const fn new(original_bytes: usize, compressed_bytes: usize) -> Self {
+    Self { original_bytes, compressed_bytes }
+  }
+
+  /// Returns the uncompressed payload size.
+  #[must_use]
+  pub const fn original_bytes(&self) -> usize {
+    self.original_bytes
+  }
+
+  /// Returns the compressed payload size.
+  #[must_use]
+  pub const fn compressed_bytes(&self) -> usize {
+    self.compressed_bytes
+  }
+
+  /// Returns `compressed / original`; smaller values mean better compression.
+  #[must_use]
+  pub fn ratio(&self) -> f64 {
+    if self.original_bytes == 0 {
+      return 1.0;
+    }
+    self.compressed_bytes as f64 / self.original_bytes as f64
+  }
+}
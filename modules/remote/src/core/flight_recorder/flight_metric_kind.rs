@@ -1,7 +1,11 @@
 //! Kind of metric recorded by the flight recorder.
 
+use alloc::string::String;
+
 use fraktor_actor_rs::core::event_stream::BackpressureSignal;
 
+use super::compression_sample::CompressionSample;
+
 /// Kind of metric recorded by the flight recorder.
 #[derive(Clone, Debug, PartialEq)]
 pub enum FlightMetricKind {
@@ -14,4 +18,11 @@ pub enum FlightMetricKind {
   },
   /// Authority recovered after being suspect.
   Reachable,
+  /// An outbound payload was compressed.
+  Compression(CompressionSample),
+  /// An inbound envelope was refused by an inbound filter.
+  InboundRejected {
+    /// Reason given by the filter.
+    reason: String,
+  },
 }
@@ -9,8 +9,8 @@ use fraktor_actor_rs::core::event_stream::{BackpressureSignal, CorrelationId};
 use fraktor_utils_rs::core::{runtime_toolbox::NoStdMutex, sync::ArcShared};
 
 use super::{
-  flight_metric_kind::FlightMetricKind, remoting_flight_recorder_snapshot::RemotingFlightRecorderSnapshot,
-  remoting_metric::RemotingMetric,
+  compression_sample::CompressionSample, flight_metric_kind::FlightMetricKind,
+  remoting_flight_recorder_snapshot::RemotingFlightRecorderSnapshot, remoting_metric::RemotingMetric,
 };
 
 struct FlightBuffer {
@@ -32,6 +32,9 @@ impl FlightBuffer {
 }
 
 /// Ring-buffer backed recorder storing recent observability metrics.
+///
+/// Clones share the same buffer.
+#[derive(Clone)]
 pub struct RemotingFlightRecorder {
   buffer: ArcShared<NoStdMutex<FlightBuffer>>,
 }
@@ -70,6 +73,29 @@ impl RemotingFlightRecorder {
     self.push(RemotingMetric::new(authority, FlightMetricKind::Reachable, correlation_id, timestamp_ms));
   }
 
+  /// Records the payload sizes of a compressed outbound envelope.
+  pub fn record_compression(
+    &self,
+    authority: impl Into<String>,
+    sample: CompressionSample,
+    correlation_id: CorrelationId,
+    timestamp_ms: u64,
+  ) {
+    self.push(RemotingMetric::new(authority, FlightMetricKind::Compression(sample), correlation_id, timestamp_ms));
+  }
+
+  /// Records an inbound envelope refused by an inbound filter.
+  pub fn record_inbound_rejected(
+    &self,
+    authority: impl Into<String>,
+    reason: impl Into<String>,
+    correlation_id: CorrelationId,
+    timestamp_ms: u64,
+  ) {
+    let kind = FlightMetricKind::InboundRejected { reason: reason.into() };
+    self.push(RemotingMetric::new(authority, kind, correlation_id, timestamp_ms));
+  }
+
   fn push(&self, record: RemotingMetric) {
     self.buffer.lock().push(record);
   }
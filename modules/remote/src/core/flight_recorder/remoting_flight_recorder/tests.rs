@@ -1,6 +1,6 @@
 use fraktor_actor_rs::core::event_stream::{BackpressureSignal, CorrelationId};
 
-use super::{FlightMetricKind, RemotingFlightRecorder};
+use super::{CompressionSample, FlightMetricKind, RemotingFlightRecorder};
 
 fn correlation(id: u128) -> CorrelationId {
   CorrelationId::from_u128(id)
@@ -49,3 +49,17 @@ fn preserves_correlation_ids_across_metrics() {
       .all(|metric| metric.correlation_id() == correlation_id && metric.authority() == "loopback:4500")
   );
 }
+
+#[test]
+fn records_compression_ratios() {
+  let recorder = RemotingFlightRecorder::new(4);
+  recorder.record_compression("loopback:4600", CompressionSample::new(4000, 1000), correlation(5), 40);
+
+  let snapshot = recorder.snapshot();
+  let FlightMetricKind::Compression(sample) = snapshot.records()[0].kind() else {
+    panic!("expected compression metric");
+  };
+  assert_eq!(sample.original_bytes(), 4000);
+  assert_eq!(sample.compressed_bytes(), 1000);
+  assert!((sample.ratio() - 0.25).abs() < f64::EPSILON);
+}
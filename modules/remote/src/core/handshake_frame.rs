@@ -1,17 +1,24 @@
 //! Binary representation of remoting handshake frames.
 
+#[cfg(test)]
+mod tests;
+
 use alloc::{string::String, vec::Vec};
 use core::convert::TryInto;
 
-use crate::core::{handshake_kind::HandshakeKind, wire_error::WireError};
+use crate::core::{handshake_kind::HandshakeKind, remoting_capabilities::RemotingCapabilities, wire_error::WireError};
 
 /// Payload exchanged when establishing associations.
+///
+/// The advertised [`RemotingCapabilities`] trail the fixed fields; frames from peers that
+/// predate capability negotiation decode with [`RemotingCapabilities::NONE`].
 pub struct HandshakeFrame {
-  kind:        HandshakeKind,
-  system_name: String,
-  host:        String,
-  port:        Option<u16>,
-  uid:         u64,
+  kind:         HandshakeKind,
+  system_name:  String,
+  host:         String,
+  port:         Option<u16>,
+  uid:          u64,
+  capabilities: RemotingCapabilities,
 }
 
 impl HandshakeFrame {
@@ -24,7 +31,21 @@ impl HandshakeFrame {
     port: Option<u16>,
     uid: u64,
   ) -> Self {
-    Self { kind, system_name: system_name.into(), host: host.into(), port, uid }
+    Self {
+      kind,
+      system_name: system_name.into(),
+      host: host.into(),
+      port,
+      uid,
+      capabilities: RemotingCapabilities::NONE,
+    }
+  }
+
+  /// Sets the capabilities advertised by the sender.
+  #[must_use]
+  pub const fn with_capabilities(mut self, capabilities: RemotingCapabilities) -> Self {
+    self.capabilities = capabilities;
+    self
   }
 
   /// Returns the handshake kind.
@@ -57,6 +78,12 @@ impl HandshakeFrame {
     self.uid
   }
 
+  /// Returns the capabilities advertised by the sender.
+  #[must_use]
+  pub const fn capabilities(&self) -> RemotingCapabilities {
+    self.capabilities
+  }
+
   /// Encodes the frame into a transport payload.
   #[must_use]
   pub fn encode(&self) -> Vec<u8> {
@@ -73,6 +100,7 @@ impl HandshakeFrame {
       buffer.push(0);
     }
     buffer.extend_from_slice(&self.uid.to_le_bytes());
+    buffer.extend_from_slice(&self.capabilities.bits().to_le_bytes());
     buffer
   }
 
@@ -109,7 +137,15 @@ impl HandshakeFrame {
       return Err(WireError::InvalidFormat);
     }
     let uid = u64::from_le_bytes(bytes[cursor..cursor + 8].try_into().map_err(|_| WireError::InvalidFormat)?);
-    Ok(Self::new(kind, system_name, host, port, uid))
+    cursor += 8;
+    // capabilities を持たない旧形式のフレームは NONE として扱う
+    let capabilities = match bytes.get(cursor..cursor + 4) {
+      | Some(bits) => {
+        RemotingCapabilities::from_bits(u32::from_le_bytes(bits.try_into().map_err(|_| WireError::InvalidFormat)?))
+      },
+      | None => RemotingCapabilities::NONE,
+    };
+    Ok(Self::new(kind, system_name, host, port, uid).with_capabilities(capabilities))
   }
 }
 
@@ -0,0 +1,28 @@
+use super::HandshakeFrame;
+use crate::core::{handshake_kind::HandshakeKind, remoting_capabilities::RemotingCapabilities};
+
+#[test]
+fn capabilities_round_trip() {
+  let frame = HandshakeFrame::new(HandshakeKind::Offer, "sys", "127.0.0.1", Some(2552), 7)
+    .with_capabilities(RemotingCapabilities::LZ4_COMPRESSION);
+
+  let decoded = HandshakeFrame::decode(&frame.encode()).expect("decode");
+
+  assert_eq!(decoded.kind(), HandshakeKind::Offer);
+  assert_eq!(decoded.system_name(), "sys");
+  assert_eq!(decoded.port(), Some(2552));
+  assert_eq!(decoded.uid(), 7);
+  assert_eq!(decoded.capabilities(), RemotingCapabilities::LZ4_COMPRESSION);
+}
+
+#[test]
+fn frames_without_capabilities_decode_as_none() {
+  let frame = HandshakeFrame::new(HandshakeKind::Ack, "sys", "127.0.0.1", None, 1)
+    .with_capabilities(RemotingCapabilities::LZ4_COMPRESSION);
+  let mut legacy = frame.encode();
+  legacy.truncate(legacy.len() - 4);
+
+  let decoded = HandshakeFrame::decode(&legacy).expect("decode");
+
+  assert_eq!(decoded.capabilities(), RemotingCapabilities::NONE);
+}
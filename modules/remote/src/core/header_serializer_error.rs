@@ -0,0 +1,21 @@
+//! Errors raised while registering header serializers.
+
+use alloc::string::String;
+
+/// Registration failures of a [`HeaderSerializerRegistry`](crate::core::HeaderSerializerRegistry).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeaderSerializerError {
+  /// Another serializer already uses the key.
+  DuplicateKey(String),
+  /// Another serializer already handles the header type.
+  DuplicateType(&'static str),
+}
+
+impl core::fmt::Display for HeaderSerializerError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      | Self::DuplicateKey(key) => write!(f, "header serializer key `{key}` is already registered"),
+      | Self::DuplicateType(name) => write!(f, "header type `{name}` already has a serializer"),
+    }
+  }
+}
@@ -0,0 +1,122 @@
+//! Registry of serializers for message headers carried across remoting.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{string::String, vec::Vec};
+use core::any::{Any, TypeId};
+
+use fraktor_actor_rs::core::messaging::MessageHeaders;
+use fraktor_utils_rs::core::sync::ArcShared;
+
+use crate::core::{header_serializer_error::HeaderSerializerError, serialized_header::SerializedHeader};
+
+type EncodeFn = ArcShared<dyn Fn(&(dyn Any + Send + Sync)) -> Option<Vec<u8>> + Send + Sync>;
+type DecodeFn = ArcShared<dyn Fn(&[u8]) -> Option<ArcShared<dyn Any + Send + Sync>> + Send + Sync>;
+
+#[derive(Clone)]
+struct HeaderSerializerEntry {
+  key:     String,
+  type_id: TypeId,
+  encode:  EncodeFn,
+  decode:  DecodeFn,
+}
+
+/// Serializers that carry typed [`MessageHeaders`] across remoting hops.
+///
+/// Each header type is registered under a key that both nodes agree on. Headers without a
+/// registered serializer stay local; received headers with an unknown key are dropped.
+#[derive(Clone, Default)]
+pub struct HeaderSerializerRegistry {
+  entries: Vec<HeaderSerializerEntry>,
+}
+
+impl HeaderSerializerRegistry {
+  /// Creates an empty registry.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { entries: Vec::new() }
+  }
+
+  /// Registers the serializer for header type `T` under `key`.
+  ///
+  /// `decode` returns `None` when the bytes cannot be decoded; the header is then dropped.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`HeaderSerializerError`] when the key or the type is already registered.
+  pub fn register<T, E, D>(
+    &mut self,
+    key: impl Into<String>,
+    encode: E,
+    decode: D,
+  ) -> Result<(), HeaderSerializerError>
+  where
+    T: Any + Send + Sync + 'static,
+    E: Fn(&T) -> Vec<u8> + Send + Sync + 'static,
+    D: Fn(&[u8]) -> Option<T> + Send + Sync + 'static, {
+    let key = key.into();
+    let type_id = TypeId::of::<T>();
+    if self.entries.iter().any(|entry| entry.key == key) {
+      return Err(HeaderSerializerError::DuplicateKey(key));
+    }
+    if self.entries.iter().any(|entry| entry.type_id == type_id) {
+      return Err(HeaderSerializerError::DuplicateType(core::any::type_name::<T>()));
+    }
+    let encode: EncodeFn =
+      ArcShared::new(move |value: &(dyn Any + Send + Sync)| value.downcast_ref::<T>().map(&encode));
+    let decode: DecodeFn = ArcShared::new(move |bytes: &[u8]| {
+      decode(bytes).map(|value| {
+        let value: ArcShared<dyn Any + Send + Sync> = ArcShared::new(value);
+        value
+      })
+    });
+    self.entries.push(HeaderSerializerEntry { key, type_id, encode, decode });
+    Ok(())
+  }
+
+  /// Encodes every header of `headers` that has a registered serializer.
+  #[must_use]
+  pub fn encode(&self, headers: &MessageHeaders) -> Vec<SerializedHeader> {
+    self
+      .entries
+      .iter()
+      .filter_map(|entry| {
+        let value = headers.get_erased(entry.type_id)?;
+        let bytes = (entry.encode)(value)?;
+        Some(SerializedHeader::new(entry.key.clone(), headers.is_propagated(entry.type_id), bytes))
+      })
+      .collect()
+  }
+
+  /// Decodes `serialized` into `headers`, skipping unknown keys and undecodable values.
+  pub fn decode_into(&self, serialized: &[SerializedHeader], headers: &mut MessageHeaders) {
+    for header in serialized {
+      let Some(entry) = self.entries.iter().find(|entry| entry.key == header.key()) else {
+        continue;
+      };
+      if let Some(value) = (entry.decode)(header.bytes()) {
+        headers.insert_erased(value, header.propagate());
+      }
+    }
+  }
+
+  /// Returns the number of registered serializers.
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  /// Returns `true` when no serializer is registered.
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+}
+
+impl core::fmt::Debug for HeaderSerializerRegistry {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let keys: Vec<&str> = self.entries.iter().map(|entry| entry.key.as_str()).collect();
+    f.debug_struct("HeaderSerializerRegistry").field("keys", &keys).finish()
+  }
+}
@@ -0,0 +1,65 @@
+use alloc::{string::String, vec::Vec};
+
+use fraktor_actor_rs::core::messaging::MessageHeaders;
+
+use super::HeaderSerializerRegistry;
+use crate::core::header_serializer_error::HeaderSerializerError;
+
+#[derive(Debug, PartialEq)]
+struct Tenant(String);
+
+fn registry() -> HeaderSerializerRegistry {
+  let mut registry = HeaderSerializerRegistry::new();
+  registry
+    .register(
+      "tenant",
+      |tenant: &Tenant| tenant.0.as_bytes().to_vec(),
+      |bytes: &[u8]| String::from_utf8(bytes.to_vec()).ok().map(Tenant),
+    )
+    .expect("register");
+  registry
+}
+
+#[test]
+fn registered_headers_round_trip_with_their_propagation_flag() {
+  let registry = registry();
+  let mut headers = MessageHeaders::new();
+  headers.insert_propagated(Tenant(String::from("acme")));
+  headers.insert(7_u32);
+
+  let serialized = registry.encode(&headers);
+  assert_eq!(serialized.len(), 1);
+  assert_eq!(serialized[0].key(), "tenant");
+
+  let mut decoded = MessageHeaders::new();
+  registry.decode_into(&serialized, &mut decoded);
+  assert_eq!(decoded.get::<Tenant>(), Some(&Tenant(String::from("acme"))));
+  assert!(decoded.is_propagated(core::any::TypeId::of::<Tenant>()));
+  assert!(!decoded.contains::<u32>());
+}
+
+#[test]
+fn unknown_keys_are_skipped() {
+  let registry = registry();
+  let serialized = HeaderSerializerRegistry::new().encode(&MessageHeaders::new());
+  assert!(serialized.is_empty());
+
+  let mut decoded = MessageHeaders::new();
+  let foreign = [crate::core::SerializedHeader::new("unknown", false, Vec::from([1_u8]))];
+  registry.decode_into(&foreign, &mut decoded);
+  assert!(decoded.is_empty());
+}
+
+#[test]
+fn duplicate_registrations_are_rejected() {
+  let mut registry = registry();
+  assert_eq!(
+    registry.register("tenant", |value: &u32| value.to_le_bytes().to_vec(), |_| None::<u32>),
+    Err(HeaderSerializerError::DuplicateKey(String::from("tenant")))
+  );
+  assert!(matches!(
+    registry.register("tenant-2", |tenant: &Tenant| tenant.0.as_bytes().to_vec(), |_| None::<Tenant>),
+    Err(HeaderSerializerError::DuplicateType(_))
+  ));
+  assert_eq!(registry.len(), 1);
+}
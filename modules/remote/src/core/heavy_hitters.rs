@@ -0,0 +1,60 @@
+//! Approximate frequency counter selecting the most frequent header strings.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{borrow::ToOwned, collections::BTreeMap, string::String, vec::Vec};
+
+// 上位候補が押し出されないよう、容量より多めの値を追跡する
+const TRACKING_FACTOR: usize = 4;
+
+/// Counts occurrences of strings and reports the most frequent ones.
+///
+/// Memory stays bounded: once the tracked set is full, a new value replaces the least frequent
+/// one. Values seen only once are never reported, since compressing them saves nothing.
+pub struct HeavyHitters {
+  capacity: usize,
+  counts:   BTreeMap<String, u64>,
+}
+
+impl HeavyHitters {
+  /// Creates a counter reporting at most `capacity` values.
+  #[must_use]
+  pub const fn new(capacity: usize) -> Self {
+    Self { capacity, counts: BTreeMap::new() }
+  }
+
+  /// Records one occurrence of `value`.
+  pub fn record(&mut self, value: &str) {
+    if self.capacity == 0 {
+      return;
+    }
+    if let Some(count) = self.counts.get_mut(value) {
+      *count += 1;
+      return;
+    }
+    if self.counts.len() >= self.capacity.saturating_mul(TRACKING_FACTOR)
+      && let Some(victim) = self.counts.iter().min_by_key(|(_, count)| **count).map(|(key, _)| key.clone())
+    {
+      self.counts.remove(&victim);
+    }
+    self.counts.insert(value.to_owned(), 1);
+  }
+
+  /// Returns the most frequent values, most frequent first.
+  #[must_use]
+  pub fn top(&self) -> Vec<String> {
+    let mut entries: Vec<(&String, u64)> =
+      self.counts.iter().filter(|(_, count)| **count > 1).map(|(key, count)| (key, *count)).collect();
+    entries.sort_by(|lhs, rhs| rhs.1.cmp(&lhs.1).then_with(|| lhs.0.cmp(rhs.0)));
+    entries.into_iter().take(self.capacity).map(|(key, _)| key.clone()).collect()
+  }
+
+  /// Halves every count so that the ranking follows recent traffic.
+  pub fn decay(&mut self) {
+    self.counts.retain(|_, count| {
+      *count /= 2;
+      *count > 0
+    });
+  }
+}
@@ -0,0 +1,50 @@
+use alloc::{format, string::ToString, vec};
+
+use super::HeavyHitters;
+
+#[test]
+fn top_orders_by_frequency_and_skips_singletons() {
+  let mut counter = HeavyHitters::new(2);
+  for _ in 0..3 {
+    counter.record("a");
+  }
+  for _ in 0..5 {
+    counter.record("b");
+  }
+  counter.record("c");
+
+  assert_eq!(counter.top(), vec!["b".to_string(), "a".to_string()]);
+}
+
+#[test]
+fn top_is_capped_by_capacity() {
+  let mut counter = HeavyHitters::new(1);
+  for value in ["a", "a", "b", "b", "b"] {
+    counter.record(value);
+  }
+  assert_eq!(counter.top(), vec!["b".to_string()]);
+}
+
+#[test]
+fn tracked_set_is_bounded() {
+  let mut counter = HeavyHitters::new(1);
+  for _ in 0..10 {
+    counter.record("hot");
+  }
+  for index in 0..100 {
+    counter.record(&format!("cold-{index}"));
+  }
+  assert_eq!(counter.top(), vec!["hot".to_string()]);
+}
+
+#[test]
+fn decay_forgets_stale_values() {
+  let mut counter = HeavyHitters::new(4);
+  counter.record("a");
+  counter.record("a");
+  counter.decay();
+  assert!(counter.top().is_empty());
+  counter.decay();
+  counter.record("a");
+  assert!(counter.top().is_empty());
+}
@@ -0,0 +1,140 @@
+//! Declarative path, manifest and per-authority rules for inbound remote envelopes.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{
+  collections::BTreeMap,
+  format,
+  string::{String, ToString},
+  vec::Vec,
+};
+
+use fraktor_actor_rs::core::{actor_prim::actor_path::ActorPath, serialization::SerializerId};
+
+use crate::core::{inbound_filter::InboundFilter, remoting_envelope::RemotingEnvelope};
+
+/// Built-in [`InboundFilter`] evaluating recipient path prefixes and payload allowlists.
+///
+/// Paths are written without the address and the implicit guardian root, e.g. `/user/public`
+/// or `/system/remote`; a prefix matches the path itself and every descendant (a trailing `/*`
+/// is accepted for readability). Evaluation order:
+///
+/// 1. the rules registered for the verified sending authority replace the default rules, if
+///    present;
+/// 2. a matching deny prefix rejects the envelope;
+/// 3. when allow prefixes exist, the recipient must match one of them;
+/// 4. when manifests or serializer ids are allowlisted, the payload must match one of them.
+///
+/// The remoting extension already denies `/system` to peers whose authority is not verified;
+/// deny it here as well to restrict the verified peers that may deploy or watch remotely.
+#[derive(Clone, Debug, Default)]
+pub struct InboundAccessRules {
+  allowed_paths:       Vec<String>,
+  denied_paths:        Vec<String>,
+  allowed_manifests:   Vec<String>,
+  allowed_serializers: Vec<SerializerId>,
+  authority_rules:     BTreeMap<String, InboundAccessRules>,
+}
+
+impl InboundAccessRules {
+  /// Creates rules that accept every envelope.
+  #[must_use]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Makes the recipients below `prefix` remotely addressable.
+  #[must_use]
+  pub fn with_allowed_path(mut self, prefix: impl Into<String>) -> Self {
+    self.allowed_paths.push(normalize_prefix(&prefix.into()));
+    self
+  }
+
+  /// Refuses envelopes addressed below `prefix`, regardless of the allow prefixes.
+  #[must_use]
+  pub fn with_denied_path(mut self, prefix: impl Into<String>) -> Self {
+    self.denied_paths.push(normalize_prefix(&prefix.into()));
+    self
+  }
+
+  /// Accepts payloads carrying `manifest`.
+  #[must_use]
+  pub fn with_allowed_manifest(mut self, manifest: impl Into<String>) -> Self {
+    self.allowed_manifests.push(manifest.into());
+    self
+  }
+
+  /// Accepts payloads produced by the serializer `id`.
+  #[must_use]
+  pub fn with_allowed_serializer(mut self, id: SerializerId) -> Self {
+    self.allowed_serializers.push(id);
+    self
+  }
+
+  /// Applies `rules` instead of these rules to envelopes sent by `authority` (`host:port`).
+  ///
+  /// The rules match only when the transport verified the sender authority (see
+  /// [`InboundFilter`]), so a peer cannot obtain them by declaring another node's address.
+  #[must_use]
+  pub fn with_authority_rules(mut self, authority: impl Into<String>, rules: InboundAccessRules) -> Self {
+    self.authority_rules.insert(authority.into(), rules);
+    self
+  }
+
+  fn evaluate(&self, envelope: &RemotingEnvelope) -> Result<(), String> {
+    let path = logical_path(envelope.recipient());
+    if self.denied_paths.iter().any(|prefix| matches_prefix(&path, prefix)) {
+      return Err(format!("recipient `{path}` is denied"));
+    }
+    if !self.allowed_paths.is_empty() && !self.allowed_paths.iter().any(|prefix| matches_prefix(&path, prefix)) {
+      return Err(format!("recipient `{path}` is not remotely addressable"));
+    }
+    if self.allowed_manifests.is_empty() && self.allowed_serializers.is_empty() {
+      return Ok(());
+    }
+    let message = envelope.serialized_message();
+    let manifest_allowed =
+      message.manifest().is_some_and(|manifest| self.allowed_manifests.iter().any(|allowed| allowed == manifest));
+    if manifest_allowed || self.allowed_serializers.contains(&message.serializer_id()) {
+      return Ok(());
+    }
+    Err(format!(
+      "payload with manifest `{}` and serializer {} is not allowed",
+      message.manifest().unwrap_or(""),
+      message.serializer_id().value()
+    ))
+  }
+}
+
+impl InboundFilter for InboundAccessRules {
+  fn check(&self, authority: Option<&str>, envelope: &RemotingEnvelope) -> Result<(), String> {
+    let rules = authority.and_then(|authority| self.authority_rules.get(authority)).unwrap_or(self);
+    rules.evaluate(envelope)
+  }
+}
+
+/// Renders the path below the guardian root, e.g. `/user/svc` for `/user/user/svc`.
+fn logical_path(path: &ActorPath) -> String {
+  let segments = path.segments();
+  // ActorPath はガーディアン種別のルートセグメントを先頭に持つため、それを除いて比較する
+  let skip = usize::from(segments.len() > 1 && segments[0].as_str() == path.parts().guardian_segment());
+  let mut value = String::new();
+  for segment in &segments[skip..] {
+    value.push('/');
+    value.push_str(segment.as_str());
+  }
+  if value.is_empty() { "/".to_string() } else { value }
+}
+
+fn normalize_prefix(prefix: &str) -> String {
+  let trimmed = prefix.strip_suffix("/*").unwrap_or(prefix).trim_end_matches('/');
+  if trimmed.starts_with('/') { trimmed.to_string() } else { format!("/{trimmed}") }
+}
+
+fn matches_prefix(path: &str, prefix: &str) -> bool {
+  if prefix == "/" {
+    return true;
+  }
+  path.strip_prefix(prefix).is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
@@ -0,0 +1,80 @@
+use alloc::{string::ToString, vec};
+
+use fraktor_actor_rs::core::{
+  actor_prim::actor_path::{ActorPath, ActorPathParts},
+  event_stream::CorrelationId,
+  serialization::{SerializedMessage, SerializerId},
+};
+
+use super::InboundAccessRules;
+use crate::core::{
+  inbound_filter::InboundFilter, outbound_priority::OutboundPriority, remote_node_id::RemoteNodeId,
+  remoting_envelope::RemotingEnvelope,
+};
+
+fn envelope(segments: &[&str], manifest: Option<&str>, serializer: u32) -> RemotingEnvelope {
+  let mut recipient = ActorPath::from_parts(ActorPathParts::local("app"));
+  for segment in segments {
+    recipient = recipient.child(segment);
+  }
+  let serializer = SerializerId::try_from(serializer).expect("id");
+  let serialized = SerializedMessage::new(serializer, manifest.map(ToString::to_string), vec![1, 2, 3]);
+  RemotingEnvelope::new(
+    recipient,
+    RemoteNodeId::new("app", "127.0.0.1", Some(2552), 1),
+    None,
+    serialized,
+    CorrelationId::from_u128(1),
+    OutboundPriority::User,
+  )
+}
+
+#[test]
+fn default_rules_accept_everything() {
+  let rules = InboundAccessRules::new();
+  assert_eq!(rules.check(None, &envelope(&["system", "remote"], None, 50)), Ok(()));
+}
+
+#[test]
+fn allowed_prefixes_restrict_addressable_recipients() {
+  let rules = InboundAccessRules::new().with_allowed_path("/user/public/*");
+  assert_eq!(rules.check(None, &envelope(&["user", "public"], None, 50)), Ok(()));
+  assert_eq!(rules.check(None, &envelope(&["user", "public", "svc"], None, 50)), Ok(()));
+  assert_eq!(
+    rules.check(None, &envelope(&["user", "publicity"], None, 50)),
+    Err("recipient `/user/publicity` is not remotely addressable".into())
+  );
+  assert!(rules.check(None, &envelope(&["system", "remote"], None, 50)).is_err());
+}
+
+#[test]
+fn deny_prefixes_win_over_allow_prefixes() {
+  let rules = InboundAccessRules::new().with_allowed_path("/user").with_denied_path("/user/admin");
+  assert_eq!(rules.check(None, &envelope(&["user", "svc"], None, 50)), Ok(()));
+  assert_eq!(
+    rules.check(None, &envelope(&["user", "admin", "db"], None, 50)),
+    Err("recipient `/user/admin/db` is denied".into())
+  );
+}
+
+#[test]
+fn payload_must_match_an_allowed_manifest_or_serializer() {
+  let serializer = SerializerId::try_from(60).expect("id");
+  let rules = InboundAccessRules::new().with_allowed_manifest("app.Ping").with_allowed_serializer(serializer);
+  assert_eq!(rules.check(None, &envelope(&["user", "svc"], Some("app.Ping"), 50)), Ok(()));
+  assert_eq!(rules.check(None, &envelope(&["user", "svc"], Some("app.Other"), 60)), Ok(()));
+  assert_eq!(
+    rules.check(None, &envelope(&["user", "svc"], Some("app.Other"), 50)),
+    Err("payload with manifest `app.Other` and serializer 50 is not allowed".into())
+  );
+}
+
+#[test]
+fn authority_rules_replace_the_default_rules() {
+  let untrusted = InboundAccessRules::new().with_denied_path("/system");
+  let rules = untrusted.with_authority_rules("10.0.0.1:2552", InboundAccessRules::new());
+  let daemon = envelope(&["system", "remote"], None, 50);
+  assert_eq!(rules.check(Some("10.0.0.1:2552"), &daemon), Ok(()));
+  assert!(rules.check(Some("10.0.0.2:2552"), &daemon).is_err());
+  assert!(rules.check(None, &daemon).is_err());
+}
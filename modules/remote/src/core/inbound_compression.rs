@@ -0,0 +1,87 @@
+//! Receiver-side compression state of one association.
+
+#[cfg(test)]
+mod tests;
+
+use crate::core::{
+  compression_table::CompressionTable, compression_table_ack::CompressionTableAck,
+  compression_table_settings::CompressionTableSettings, heavy_hitters::HeavyHitters,
+};
+
+/// Counts the headers received from a peer and decides which table to advertise to it.
+///
+/// A new table version is only rolled out after the previous one was acknowledged; the
+/// receiver keeps the current and the previous version so that envelopes encoded while the
+/// advertisement was in flight can still be decoded.
+pub struct InboundCompression {
+  actor_refs:  HeavyHitters,
+  manifests:   HeavyHitters,
+  current:     Option<CompressionTable>,
+  previous:    Option<CompressionTable>,
+  pending_ack: bool,
+}
+
+impl InboundCompression {
+  /// Creates empty state sized according to `settings`.
+  #[must_use]
+  pub const fn new(settings: &CompressionTableSettings) -> Self {
+    Self {
+      actor_refs:  HeavyHitters::new(settings.max_actor_refs()),
+      manifests:   HeavyHitters::new(settings.max_manifests()),
+      current:     None,
+      previous:    None,
+      pending_ack: false,
+    }
+  }
+
+  /// Records the header strings of a received envelope.
+  pub fn record(&mut self, recipient: &str, reply_to: Option<&str>, manifest: Option<&str>) {
+    self.actor_refs.record(recipient);
+    if let Some(reply_to) = reply_to {
+      self.actor_refs.record(reply_to);
+    }
+    if let Some(manifest) = manifest {
+      self.manifests.record(manifest);
+    }
+  }
+
+  /// Returns the table to advertise now, if any.
+  ///
+  /// An unacknowledged table is returned again; otherwise a new version is created when the
+  /// heavy hitters changed since the last advertisement.
+  pub fn next_advertisement(&mut self) -> Option<CompressionTable> {
+    if self.pending_ack {
+      return self.current.clone();
+    }
+    let actor_refs = self.actor_refs.top();
+    let manifests = self.manifests.top();
+    self.actor_refs.decay();
+    self.manifests.decay();
+    let version = match &self.current {
+      | Some(current) if current.actor_refs() == actor_refs.as_slice() && current.manifests() == manifests.as_slice() =>
+      {
+        return None;
+      },
+      | Some(current) => current.version().wrapping_add(1),
+      | None if actor_refs.is_empty() && manifests.is_empty() => return None,
+      | None => 1,
+    };
+    let table = CompressionTable::new(version, actor_refs, manifests);
+    self.previous = self.current.replace(table.clone());
+    self.pending_ack = true;
+    Some(table)
+  }
+
+  /// Marks the advertised table as received by the peer.
+  pub fn acknowledge(&mut self, ack: CompressionTableAck) {
+    if self.current.as_ref().is_some_and(|current| current.version() == ack.version()) {
+      self.pending_ack = false;
+    }
+  }
+
+  /// Returns the advertised table with the given version, if still known.
+  #[must_use]
+  pub fn table(&self, version: u32) -> Option<&CompressionTable> {
+    [self.current.as_ref(), self.previous.as_ref()].into_iter().flatten().find(|table| table.version() == version)
+  }
+}
@@ -0,0 +1,59 @@
+use alloc::string::ToString;
+
+use super::InboundCompression;
+use crate::core::{compression_table_ack::CompressionTableAck, compression_table_settings::CompressionTableSettings};
+
+const SVC: &str = "fraktor.tcp://app@127.0.0.1:4100/user/user/svc";
+
+fn warmed_up() -> InboundCompression {
+  let mut state = InboundCompression::new(&CompressionTableSettings::new());
+  for _ in 0..4 {
+    state.record(SVC, None, Some("app.Ping"));
+  }
+  state
+}
+
+#[test]
+fn nothing_is_advertised_without_traffic() {
+  let mut state = InboundCompression::new(&CompressionTableSettings::new());
+  assert!(state.next_advertisement().is_none());
+}
+
+#[test]
+fn heavy_hitters_are_advertised_until_acknowledged() {
+  let mut state = warmed_up();
+  let table = state.next_advertisement().expect("advertisement");
+  assert_eq!(table.version(), 1);
+  assert_eq!(table.actor_refs(), [SVC.to_string()]);
+  assert_eq!(table.manifests(), ["app.Ping".to_string()]);
+
+  assert_eq!(state.next_advertisement(), Some(table.clone()));
+  state.acknowledge(CompressionTableAck::new(1));
+  assert!(state.next_advertisement().is_none());
+  assert_eq!(state.table(1), Some(&table));
+}
+
+#[test]
+fn new_version_keeps_previous_table_for_in_flight_envelopes() {
+  let mut state = warmed_up();
+  let first = state.next_advertisement().expect("first");
+  state.acknowledge(CompressionTableAck::new(first.version()));
+
+  for _ in 0..4 {
+    state.record(SVC, None, Some("app.Pong"));
+  }
+  let second = state.next_advertisement().expect("second");
+  assert_eq!(second.version(), 2);
+  assert!(second.manifest_id("app.Pong").is_some());
+  assert_eq!(state.table(1), Some(&first));
+  assert_eq!(state.table(2), Some(&second));
+  assert!(state.table(3).is_none());
+}
+
+#[test]
+fn stale_acknowledgement_is_ignored() {
+  let mut state = warmed_up();
+  let table = state.next_advertisement().expect("advertisement");
+  state.acknowledge(CompressionTableAck::new(table.version() + 1));
+  assert_eq!(state.next_advertisement(), Some(table));
+}
@@ -0,0 +1,20 @@
+//! Authorization hook consulted before inbound remote envelopes are delivered.
+
+use alloc::string::String;
+
+use crate::core::remoting_envelope::RemotingEnvelope;
+
+/// Decides whether an inbound envelope may be delivered to its recipient.
+///
+/// Filters run before the payload is deserialized. `authority` is the `host:port` of the sending
+/// node only when the transport bound the handshake-declared authority to the connection (the
+/// observed source host matches it, or the transport authenticated the peer, e.g. through TLS
+/// or the in-process network); it is `None` for peers that merely claim an identity.
+pub trait InboundFilter: Send + Sync + 'static {
+  /// Returns `Err` with a human readable reason when the envelope must be rejected.
+  ///
+  /// # Errors
+  ///
+  /// Returns the rejection reason recorded alongside the dead letter.
+  fn check(&self, authority: Option<&str>, envelope: &RemotingEnvelope) -> Result<(), String>;
+}
@@ -0,0 +1,63 @@
+//! Record of an inbound envelope refused by an inbound filter.
+
+use alloc::string::String;
+
+use fraktor_actor_rs::core::{actor_prim::actor_path::ActorPath, serialization::SerializerId};
+
+/// Describes an inbound envelope refused by an [`InboundFilter`](crate::core::InboundFilter).
+///
+/// The rejection is recorded as the dead letter of the refused message because the payload is
+/// never deserialized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InboundRejection {
+  authority:     Option<String>,
+  recipient:     ActorPath,
+  manifest:      Option<String>,
+  serializer_id: SerializerId,
+  reason:        String,
+}
+
+impl InboundRejection {
+  /// Creates a rejection record.
+  #[must_use]
+  pub fn new(
+    authority: Option<String>,
+    recipient: ActorPath,
+    manifest: Option<String>,
+    serializer_id: SerializerId,
+    reason: impl Into<String>,
+  ) -> Self {
+    Self { authority, recipient, manifest, serializer_id, reason: reason.into() }
+  }
+
+  /// Returns the authority of the sending node, if known.
+  #[must_use]
+  pub fn authority(&self) -> Option<&str> {
+    self.authority.as_deref()
+  }
+
+  /// Returns the path the envelope was addressed to.
+  #[must_use]
+  pub const fn recipient(&self) -> &ActorPath {
+    &self.recipient
+  }
+
+  /// Returns the manifest of the refused payload, if any.
+  #[must_use]
+  pub fn manifest(&self) -> Option<&str> {
+    self.manifest.as_deref()
+  }
+
+  /// Returns the serializer id of the refused payload.
+  #[must_use]
+  pub const fn serializer_id(&self) -> SerializerId {
+    self.serializer_id
+  }
+
+  /// Returns the reason given by the filter.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // String の Deref が const でないため const fn にできない
+  pub fn reason(&self) -> &str {
+    &self.reason
+  }
+}
@@ -44,17 +44,16 @@ impl<TB: RuntimeToolbox + 'static> ActorRefProviderInstaller<TB> for LoopbackAct
       return Err(ActorSystemBuildError::Configuration("serialization extension not installed".into()));
     };
 
-    let writer = ArcShared::new(<TB::MutexFamily as SyncMutexFamily>::create(EndpointWriterGeneric::new(
-      system.clone(),
-      serialization.clone(),
-    )));
-    let reader = ArcShared::new(EndpointReaderGeneric::new(system.clone(), serialization.clone()));
-
     let Some(extension) = extended.extension_by_type::<RemotingExtensionGeneric<TB>>() else {
       return Err(ActorSystemBuildError::Configuration("remoting extension not installed".into()));
     };
 
     let control = extension.handle();
+    let writer = ArcShared::new(<TB::MutexFamily as SyncMutexFamily>::create(
+      control.configure_writer(EndpointWriterGeneric::new(system.clone(), serialization.clone())),
+    ));
+    let reader =
+      ArcShared::new(control.configure_reader(EndpointReaderGeneric::new(system.clone(), serialization.clone())));
     control.register_endpoint_io(writer.clone(), reader.clone());
     let authority_manager = system.state().remote_authority_manager().clone();
     let provider = LoopbackActorRefProviderGeneric::from_components(system.clone(), writer, control, authority_manager)
@@ -62,6 +61,7 @@ impl<TB: RuntimeToolbox + 'static> ActorRefProviderInstaller<TB> for LoopbackAct
     let shared = RemoteWatchHookShared::new(provider, &[ActorPathScheme::FraktorTcp]);
     let shared_arc = ArcShared::new(shared.clone());
     extended.register_actor_ref_provider(&shared_arc);
+    extended.register_remote_deploy_hook(shared.clone());
     extended.register_remote_watch_hook(shared);
 
     // Always register loopback routing for LoopbackActorRefProvider
@@ -0,0 +1,9 @@
+//! Aggregated per-association remoting metrics.
+
+mod association_metrics;
+mod latency_histogram;
+mod remoting_metrics;
+
+pub use association_metrics::AssociationMetrics;
+pub use latency_histogram::LatencyHistogram;
+pub use remoting_metrics::RemotingMetrics;
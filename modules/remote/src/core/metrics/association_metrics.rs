@@ -0,0 +1,143 @@
+//! Counters aggregated for one remote association.
+
+use alloc::string::String;
+use core::time::Duration;
+
+use super::latency_histogram::LatencyHistogram;
+
+/// Snapshot of the traffic, latency and queueing measured for one remote authority.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssociationMetrics {
+  authority:            String,
+  bytes_sent:           u64,
+  bytes_received:       u64,
+  messages_sent:        u64,
+  messages_received:    u64,
+  serialization:        LatencyHistogram,
+  deserialization:      LatencyHistogram,
+  outbound_queue_depth: usize,
+  handshakes:           u64,
+  reconnects:           u64,
+  ask_latency:          LatencyHistogram,
+}
+
+impl AssociationMetrics {
+  /// Creates zeroed metrics for `authority`.
+  #[must_use]
+  pub fn new(authority: impl Into<String>) -> Self {
+    Self {
+      authority:            authority.into(),
+      bytes_sent:           0,
+      bytes_received:       0,
+      messages_sent:        0,
+      messages_received:    0,
+      serialization:        LatencyHistogram::new(),
+      deserialization:      LatencyHistogram::new(),
+      outbound_queue_depth: 0,
+      handshakes:           0,
+      reconnects:           0,
+      ask_latency:          LatencyHistogram::new(),
+    }
+  }
+
+  /// Returns the authority (`host:port`) of the association.
+  #[must_use]
+  #[allow(clippy::missing_const_for_fn)] // String の Deref が const でないため const fn にできない
+  pub fn authority(&self) -> &str {
+    &self.authority
+  }
+
+  /// Returns the number of frame bytes sent to the authority.
+  #[must_use]
+  pub const fn bytes_sent(&self) -> u64 {
+    self.bytes_sent
+  }
+
+  /// Returns the number of frame bytes received from the authority.
+  #[must_use]
+  pub const fn bytes_received(&self) -> u64 {
+    self.bytes_received
+  }
+
+  /// Returns the number of envelopes sent to the authority.
+  #[must_use]
+  pub const fn messages_sent(&self) -> u64 {
+    self.messages_sent
+  }
+
+  /// Returns the number of envelopes received from the authority.
+  #[must_use]
+  pub const fn messages_received(&self) -> u64 {
+    self.messages_received
+  }
+
+  /// Returns the time spent serializing payloads addressed to the authority.
+  #[must_use]
+  pub const fn serialization(&self) -> &LatencyHistogram {
+    &self.serialization
+  }
+
+  /// Returns the time spent deserializing payloads received from the authority.
+  #[must_use]
+  pub const fn deserialization(&self) -> &LatencyHistogram {
+    &self.deserialization
+  }
+
+  /// Returns the number of messages waiting in the endpoint writer for the authority.
+  #[must_use]
+  pub const fn outbound_queue_depth(&self) -> usize {
+    self.outbound_queue_depth
+  }
+
+  /// Returns the number of handshakes completed with the authority.
+  #[must_use]
+  pub const fn handshakes(&self) -> u64 {
+    self.handshakes
+  }
+
+  /// Returns the number of reconnect attempts made to the authority.
+  #[must_use]
+  pub const fn reconnects(&self) -> u64 {
+    self.reconnects
+  }
+
+  /// Returns the round-trip time between requests carrying a reply-to path and their replies.
+  #[must_use]
+  pub const fn ask_latency(&self) -> &LatencyHistogram {
+    &self.ask_latency
+  }
+
+  pub(crate) const fn add_sent(&mut self, bytes: usize) {
+    self.bytes_sent = self.bytes_sent.saturating_add(bytes as u64);
+    self.messages_sent += 1;
+  }
+
+  pub(crate) const fn add_received(&mut self, bytes: usize) {
+    self.bytes_received = self.bytes_received.saturating_add(bytes as u64);
+    self.messages_received += 1;
+  }
+
+  pub(crate) fn add_serialization(&mut self, elapsed: Duration) {
+    self.serialization.record(elapsed);
+  }
+
+  pub(crate) fn add_deserialization(&mut self, elapsed: Duration) {
+    self.deserialization.record(elapsed);
+  }
+
+  pub(crate) const fn set_outbound_queue_depth(&mut self, depth: usize) {
+    self.outbound_queue_depth = depth;
+  }
+
+  pub(crate) const fn add_handshake(&mut self) {
+    self.handshakes += 1;
+  }
+
+  pub(crate) const fn add_reconnect(&mut self) {
+    self.reconnects += 1;
+  }
+
+  pub(crate) fn add_ask_latency(&mut self, elapsed: Duration) {
+    self.ask_latency.record(elapsed);
+  }
+}
@@ -0,0 +1,77 @@
+//! Fixed-bucket histogram of measured durations.
+
+#[cfg(test)]
+mod tests;
+
+use core::time::Duration;
+
+const BUCKET_BOUNDS: [Duration; 14] = [
+  Duration::from_micros(50),
+  Duration::from_micros(100),
+  Duration::from_micros(250),
+  Duration::from_micros(500),
+  Duration::from_millis(1),
+  Duration::from_micros(2_500),
+  Duration::from_millis(5),
+  Duration::from_millis(10),
+  Duration::from_millis(25),
+  Duration::from_millis(50),
+  Duration::from_millis(100),
+  Duration::from_millis(250),
+  Duration::from_millis(500),
+  Duration::from_secs(1),
+];
+
+/// Histogram of durations with fixed upper bounds between 50µs and 1s.
+///
+/// Samples above the largest bound are only reflected in [`count`](Self::count) and
+/// [`sum`](Self::sum), matching the implicit `+Inf` bucket of Prometheus histograms.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+  buckets:    [u64; BUCKET_BOUNDS.len()],
+  count:      u64,
+  sum_micros: u64,
+}
+
+impl LatencyHistogram {
+  /// Creates an empty histogram.
+  #[must_use]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Records one sample.
+  pub fn record(&mut self, elapsed: Duration) {
+    if let Some(index) = BUCKET_BOUNDS.iter().position(|bound| elapsed <= *bound) {
+      self.buckets[index] += 1;
+    }
+    self.count += 1;
+    self.sum_micros = self.sum_micros.saturating_add(elapsed.as_micros() as u64);
+  }
+
+  /// Returns the number of recorded samples.
+  #[must_use]
+  pub const fn count(&self) -> u64 {
+    self.count
+  }
+
+  /// Returns the sum of the recorded samples (microsecond precision).
+  #[must_use]
+  pub const fn sum(&self) -> Duration {
+    Duration::from_micros(self.sum_micros)
+  }
+
+  /// Returns the mean of the recorded samples, or `None` when the histogram is empty.
+  #[must_use]
+  pub const fn mean(&self) -> Option<Duration> {
+    if self.count == 0 { None } else { Some(Duration::from_micros(self.sum_micros / self.count)) }
+  }
+
+  /// Returns each bucket upper bound with the number of samples less than or equal to it.
+  pub fn cumulative_buckets(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+    BUCKET_BOUNDS.iter().zip(self.buckets.iter()).scan(0_u64, |total, (bound, count)| {
+      *total += count;
+      Some((*bound, *total))
+    })
+  }
+}
@@ -0,0 +1,31 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use super::LatencyHistogram;
+
+#[test]
+fn empty_histogram_has_no_mean() {
+  let histogram = LatencyHistogram::new();
+  assert_eq!(histogram.count(), 0);
+  assert_eq!(histogram.mean(), None);
+  assert!(histogram.cumulative_buckets().all(|(_, count)| count == 0));
+}
+
+#[test]
+fn samples_accumulate_into_cumulative_buckets() {
+  let mut histogram = LatencyHistogram::new();
+  histogram.record(Duration::from_micros(40));
+  histogram.record(Duration::from_micros(100));
+  histogram.record(Duration::from_millis(3));
+  histogram.record(Duration::from_secs(2));
+
+  assert_eq!(histogram.count(), 4);
+  assert_eq!(histogram.sum(), Duration::from_micros(2_003_140));
+  assert_eq!(histogram.mean(), Some(Duration::from_micros(500_785)));
+  let buckets: Vec<(Duration, u64)> = histogram.cumulative_buckets().collect();
+  assert_eq!(buckets[0], (Duration::from_micros(50), 1));
+  assert_eq!(buckets[1], (Duration::from_micros(100), 2));
+  assert_eq!(buckets[6], (Duration::from_millis(5), 3));
+  // 1 秒を超えたサンプルは +Inf バケットにのみ含まれる
+  assert_eq!(buckets.last(), Some(&(Duration::from_secs(1), 3)));
+}
@@ -0,0 +1,98 @@
+//! Registry aggregating remoting measurements per association.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::time::Duration;
+
+use fraktor_utils_rs::core::{runtime_toolbox::NoStdMutex, sync::ArcShared};
+
+use super::association_metrics::AssociationMetrics;
+
+/// Metrics facade collecting per-association counters and histograms.
+///
+/// Clones share the same counters. The endpoint driver records into the registry while
+/// exporters read [`snapshot`](Self::snapshot), which lists associations ordered by authority.
+#[derive(Clone)]
+pub struct RemotingMetrics {
+  associations: ArcShared<NoStdMutex<BTreeMap<String, AssociationMetrics>>>,
+}
+
+impl RemotingMetrics {
+  /// Creates an empty registry.
+  #[must_use]
+  pub fn new() -> Self {
+    Self { associations: ArcShared::new(NoStdMutex::new(BTreeMap::new())) }
+  }
+
+  /// Records an envelope frame of `bytes` sent to `authority`.
+  pub fn record_sent(&self, authority: &str, bytes: usize) {
+    self.update(authority, |metrics| metrics.add_sent(bytes));
+  }
+
+  /// Records an envelope frame of `bytes` received from `authority`.
+  pub fn record_received(&self, authority: &str, bytes: usize) {
+    self.update(authority, |metrics| metrics.add_received(bytes));
+  }
+
+  /// Records the time spent serializing a payload addressed to `authority`.
+  pub fn record_serialization(&self, authority: &str, elapsed: Duration) {
+    self.update(authority, |metrics| metrics.add_serialization(elapsed));
+  }
+
+  /// Records the time spent deserializing a payload received from `authority`.
+  pub fn record_deserialization(&self, authority: &str, elapsed: Duration) {
+    self.update(authority, |metrics| metrics.add_deserialization(elapsed));
+  }
+
+  /// Updates the number of messages queued in the endpoint writer for `authority`.
+  pub fn set_outbound_queue_depth(&self, authority: &str, depth: usize) {
+    self.update(authority, |metrics| metrics.set_outbound_queue_depth(depth));
+  }
+
+  /// Records a handshake completed with `authority`.
+  pub fn record_handshake(&self, authority: &str) {
+    self.update(authority, AssociationMetrics::add_handshake);
+  }
+
+  /// Records a reconnect attempt to `authority`.
+  pub fn record_reconnect(&self, authority: &str) {
+    self.update(authority, AssociationMetrics::add_reconnect);
+  }
+
+  /// Records the round-trip time of a request answered by `authority`.
+  pub fn record_ask_latency(&self, authority: &str, elapsed: Duration) {
+    self.update(authority, |metrics| metrics.add_ask_latency(elapsed));
+  }
+
+  /// Returns the metrics of `authority`, if anything was recorded for it.
+  #[must_use]
+  pub fn association(&self, authority: &str) -> Option<AssociationMetrics> {
+    self.associations.lock().get(authority).cloned()
+  }
+
+  /// Returns the metrics of every association ordered by authority.
+  #[must_use]
+  pub fn snapshot(&self) -> Vec<AssociationMetrics> {
+    self.associations.lock().values().cloned().collect()
+  }
+
+  fn update(&self, authority: &str, apply: impl FnOnce(&mut AssociationMetrics)) {
+    let mut associations = self.associations.lock();
+    match associations.get_mut(authority) {
+      | Some(metrics) => apply(metrics),
+      | None => {
+        let mut metrics = AssociationMetrics::new(authority);
+        apply(&mut metrics);
+        associations.insert(metrics.authority().into(), metrics);
+      },
+    }
+  }
+}
+
+impl Default for RemotingMetrics {
+  fn default() -> Self {
+    Self::new()
+  }
+}
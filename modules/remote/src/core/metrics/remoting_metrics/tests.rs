@@ -0,0 +1,37 @@
+use core::time::Duration;
+
+use super::RemotingMetrics;
+
+#[test]
+fn counters_are_aggregated_per_authority() {
+  let metrics = RemotingMetrics::new();
+  metrics.record_sent("10.0.0.1:2552", 100);
+  metrics.record_sent("10.0.0.1:2552", 50);
+  metrics.record_received("10.0.0.1:2552", 30);
+  metrics.record_handshake("10.0.0.1:2552");
+  metrics.record_reconnect("10.0.0.2:2552");
+  metrics.set_outbound_queue_depth("10.0.0.2:2552", 7);
+
+  let first = metrics.association("10.0.0.1:2552").expect("first");
+  assert_eq!((first.bytes_sent(), first.messages_sent()), (150, 2));
+  assert_eq!((first.bytes_received(), first.messages_received()), (30, 1));
+  assert_eq!((first.handshakes(), first.reconnects()), (1, 0));
+  let second = metrics.association("10.0.0.2:2552").expect("second");
+  assert_eq!((second.reconnects(), second.outbound_queue_depth()), (1, 7));
+  assert!(metrics.association("10.0.0.3:2552").is_none());
+}
+
+#[test]
+fn clones_share_counters_and_snapshot_is_sorted() {
+  let metrics = RemotingMetrics::new();
+  let clone = metrics.clone();
+  clone.record_ask_latency("b:1", Duration::from_millis(40));
+  clone.record_serialization("a:1", Duration::from_micros(80));
+  metrics.record_deserialization("a:1", Duration::from_micros(20));
+
+  let snapshot = metrics.snapshot();
+  assert_eq!(snapshot.iter().map(|entry| entry.authority()).collect::<alloc::vec::Vec<_>>(), ["a:1", "b:1"]);
+  assert_eq!(snapshot[0].serialization().count(), 1);
+  assert_eq!(snapshot[0].deserialization().count(), 1);
+  assert_eq!(snapshot[1].ask_latency().mean(), Some(Duration::from_millis(40)));
+}
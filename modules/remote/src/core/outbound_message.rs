@@ -40,6 +40,10 @@ impl<TB: RuntimeToolbox + 'static> OutboundMessage<TB> {
     self.priority
   }
 
+  pub(crate) const fn remote_node(&self) -> &RemoteNodeId {
+    &self.remote_node
+  }
+
   pub(crate) fn into_parts(self) -> (AnyMessageGeneric<TB>, ActorPath, RemoteNodeId, Option<ActorPath>) {
     (self.message, self.recipient, self.remote_node, self.reply_to)
   }
@@ -0,0 +1,76 @@
+//! Future enqueueing a remote message once its association accepts it.
+
+use core::{
+  fmt,
+  future::Future,
+  pin::Pin,
+  task::{Context, Poll},
+};
+
+use fraktor_actor_rs::core::{error::SendError, messaging::AnyMessageGeneric};
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdToolbox, RuntimeToolbox},
+  sync::sync_mutex_like::SyncMutexLike,
+};
+
+use crate::core::{endpoint_writer::EndpointWriterShared, outbound_message::OutboundMessage};
+
+/// Future completing once a remote message has been queued by the endpoint writer.
+///
+/// Unlike a synchronous `tell`, which fails with [`SendError::Suspended`] while backpressure is
+/// applied and with [`SendError::Full`] when the outbound queue rejects the message, the future
+/// stays pending until the association accepts the message, letting producers slow down.
+pub struct OutboundOfferFutureGeneric<TB: RuntimeToolbox + 'static> {
+  state: OfferState<TB>,
+}
+
+enum OfferState<TB: RuntimeToolbox + 'static> {
+  Waiting { writer: EndpointWriterShared<TB>, outbound: Option<OutboundMessage<TB>>, message: AnyMessageGeneric<TB> },
+  Completed(Option<Result<(), SendError<TB>>>),
+}
+
+/// Type alias for [OutboundOfferFutureGeneric] with the default [NoStdToolbox].
+pub type OutboundOfferFuture = OutboundOfferFutureGeneric<NoStdToolbox>;
+
+impl<TB: RuntimeToolbox + 'static> OutboundOfferFutureGeneric<TB> {
+  pub(crate) const fn new(
+    writer: EndpointWriterShared<TB>,
+    outbound: OutboundMessage<TB>,
+    message: AnyMessageGeneric<TB>,
+  ) -> Self {
+    Self { state: OfferState::Waiting { writer, outbound: Some(outbound), message } }
+  }
+
+  pub(crate) const fn ready(result: Result<(), SendError<TB>>) -> Self {
+    Self { state: OfferState::Completed(Some(result)) }
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> Unpin for OutboundOfferFutureGeneric<TB> {}
+
+impl<TB: RuntimeToolbox + 'static> Future for OutboundOfferFutureGeneric<TB> {
+  type Output = Result<(), SendError<TB>>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    match &mut self.state {
+      | OfferState::Completed(result) => Poll::Ready(result.take().unwrap_or(Ok(()))),
+      | OfferState::Waiting { writer, outbound, message } => {
+        let poll = writer.lock().poll_offer(outbound, cx.waker());
+        match poll {
+          | Poll::Ready(result) => {
+            let result = result.map_err(|error| error.into_send_error(message.clone()));
+            self.state = OfferState::Completed(None);
+            Poll::Ready(result)
+          },
+          | Poll::Pending => Poll::Pending,
+        }
+      },
+    }
+  }
+}
+
+impl<TB: RuntimeToolbox> fmt::Debug for OutboundOfferFutureGeneric<TB> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("OutboundOfferFuture").finish()
+  }
+}
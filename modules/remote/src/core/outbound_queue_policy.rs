@@ -0,0 +1,76 @@
+//! Capacity and overflow behaviour of the per-association outbound queues.
+
+#[cfg(test)]
+mod tests;
+
+use core::num::NonZeroUsize;
+
+use fraktor_actor_rs::core::mailbox::{MailboxCapacity, MailboxOverflowStrategy};
+
+/// Bounds the messages the endpoint writer buffers for each remote association.
+///
+/// Every association owns a system lane and a user lane. The overflow strategy applies to the
+/// user lane with the same meaning as for mailboxes:
+///
+/// - [`DropNewest`](MailboxOverflowStrategy::DropNewest) rejects the new message;
+/// - [`DropOldest`](MailboxOverflowStrategy::DropOldest) evicts the oldest queued message;
+/// - [`Grow`](MailboxOverflowStrategy::Grow) ignores the capacity;
+/// - [`Block`](MailboxOverflowStrategy::Block) rejects synchronous sends and lets
+///   [`OutboundOfferFutureGeneric`](crate::core::OutboundOfferFutureGeneric) wait for capacity.
+///
+/// System messages are never evicted; a full system lane rejects the new message. Rejected and
+/// evicted messages are recorded as dead letters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutboundQueuePolicy {
+  system_capacity: MailboxCapacity,
+  user_capacity:   MailboxCapacity,
+  overflow:        MailboxOverflowStrategy,
+}
+
+impl OutboundQueuePolicy {
+  /// Creates a policy with unbounded lanes.
+  #[must_use]
+  pub const fn unbounded() -> Self {
+    Self {
+      system_capacity: MailboxCapacity::Unbounded,
+      user_capacity:   MailboxCapacity::Unbounded,
+      overflow:        MailboxOverflowStrategy::Grow,
+    }
+  }
+
+  /// Creates a policy bounding the user lane of each association to `capacity` messages.
+  #[must_use]
+  pub const fn bounded(capacity: NonZeroUsize, overflow: MailboxOverflowStrategy) -> Self {
+    Self { system_capacity: MailboxCapacity::Unbounded, user_capacity: MailboxCapacity::Bounded { capacity }, overflow }
+  }
+
+  /// Returns a copy of the policy with a different system lane capacity.
+  #[must_use]
+  pub const fn with_system_capacity(self, capacity: MailboxCapacity) -> Self {
+    Self { system_capacity: capacity, ..self }
+  }
+
+  /// Returns the system lane capacity.
+  #[must_use]
+  pub const fn system_capacity(&self) -> MailboxCapacity {
+    self.system_capacity
+  }
+
+  /// Returns the user lane capacity.
+  #[must_use]
+  pub const fn user_capacity(&self) -> MailboxCapacity {
+    self.user_capacity
+  }
+
+  /// Returns the overflow strategy of the user lane.
+  #[must_use]
+  pub const fn overflow(&self) -> MailboxOverflowStrategy {
+    self.overflow
+  }
+}
+
+impl Default for OutboundQueuePolicy {
+  fn default() -> Self {
+    Self::unbounded()
+  }
+}
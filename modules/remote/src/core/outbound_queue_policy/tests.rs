@@ -0,0 +1,26 @@
+use core::num::NonZeroUsize;
+
+use fraktor_actor_rs::core::mailbox::{MailboxCapacity, MailboxOverflowStrategy};
+
+use super::OutboundQueuePolicy;
+
+#[test]
+fn default_policy_is_unbounded() {
+  let policy = OutboundQueuePolicy::default();
+  assert_eq!(policy, OutboundQueuePolicy::unbounded());
+  assert_eq!(policy.system_capacity(), MailboxCapacity::Unbounded);
+  assert_eq!(policy.user_capacity(), MailboxCapacity::Unbounded);
+  assert_eq!(policy.overflow(), MailboxOverflowStrategy::Grow);
+}
+
+#[test]
+fn bounded_policy_limits_only_the_user_lane() {
+  let capacity = NonZeroUsize::new(8).expect("capacity");
+  let policy = OutboundQueuePolicy::bounded(capacity, MailboxOverflowStrategy::DropOldest);
+  assert_eq!(policy.user_capacity(), MailboxCapacity::Bounded { capacity });
+  assert_eq!(policy.system_capacity(), MailboxCapacity::Unbounded);
+  assert_eq!(policy.overflow(), MailboxOverflowStrategy::DropOldest);
+
+  let system = MailboxCapacity::Bounded { capacity: NonZeroUsize::new(2).expect("capacity") };
+  assert_eq!(policy.with_system_capacity(system).system_capacity(), system);
+}
@@ -0,0 +1,76 @@
+//! Threshold based LZ4 compression of envelope payloads.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+
+use crate::core::wire_error::WireError;
+
+const DEFAULT_THRESHOLD: usize = 1024;
+// LZ4 の最大圧縮率は約 255:1 のため、それを超える展開サイズの申告は不正とみなす
+const MAX_EXPANSION: usize = 255;
+
+/// Compresses serialized payloads once they exceed a size threshold.
+///
+/// Compression is only applied on associations whose peer advertised
+/// [`RemotingCapabilities::LZ4_COMPRESSION`](crate::core::RemotingCapabilities::LZ4_COMPRESSION)
+/// during the handshake, and only when it actually shrinks the payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PayloadCompression {
+  threshold: usize,
+}
+
+impl PayloadCompression {
+  /// Creates a compression setting with the default threshold of 1 KiB.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { threshold: DEFAULT_THRESHOLD }
+  }
+
+  /// Sets the payload size (in bytes) below which payloads are sent uncompressed.
+  #[must_use]
+  pub const fn with_threshold(mut self, threshold: usize) -> Self {
+    self.threshold = threshold;
+    self
+  }
+
+  /// Returns the compression threshold.
+  #[must_use]
+  pub const fn threshold(&self) -> usize {
+    self.threshold
+  }
+
+  /// Compresses the payload, returning `None` when it is below the threshold or incompressible.
+  #[must_use]
+  pub fn compress(&self, payload: &[u8]) -> Option<Vec<u8>> {
+    if payload.len() < self.threshold {
+      return None;
+    }
+    let compressed = lz4_flex::block::compress_prepend_size(payload);
+    (compressed.len() < payload.len()).then_some(compressed)
+  }
+
+  /// Restores a payload produced by [`compress`](Self::compress).
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::Decompression`] when the payload is corrupt or claims an implausible
+  /// decompressed size.
+  pub fn decompress(payload: &[u8]) -> Result<Vec<u8>, WireError> {
+    let Some(size) = payload.get(..4) else {
+      return Err(WireError::Decompression);
+    };
+    let declared = u32::from_le_bytes([size[0], size[1], size[2], size[3]]) as usize;
+    if declared > payload.len().saturating_mul(MAX_EXPANSION) {
+      return Err(WireError::Decompression);
+    }
+    lz4_flex::block::decompress_size_prepended(payload).map_err(|_| WireError::Decompression)
+  }
+}
+
+impl Default for PayloadCompression {
+  fn default() -> Self {
+    Self::new()
+  }
+}
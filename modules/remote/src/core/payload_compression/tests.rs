@@ -0,0 +1,44 @@
+use alloc::vec::Vec;
+
+use super::PayloadCompression;
+use crate::core::wire_error::WireError;
+
+fn json_like(len: usize) -> Vec<u8> {
+  br#"{"name":"fraktor","kind":"actor","tags":["remote","cluster"]},"#.iter().copied().cycle().take(len).collect()
+}
+
+#[test]
+fn payloads_below_threshold_are_left_alone() {
+  let compression = PayloadCompression::new().with_threshold(512);
+  assert_eq!(compression.compress(&json_like(511)), None);
+}
+
+#[test]
+fn compressible_payloads_round_trip() {
+  let payload = json_like(4096);
+  let compressed = PayloadCompression::new().compress(&payload).expect("compressed");
+  assert!(compressed.len() < payload.len() / 4);
+  assert_eq!(PayloadCompression::decompress(&compressed).expect("decompress"), payload);
+}
+
+#[test]
+fn incompressible_payloads_are_sent_raw() {
+  let mut state = 0x1234_5678_u32;
+  let payload: Vec<u8> = (0..2048)
+    .map(|_| {
+      state ^= state << 13;
+      state ^= state >> 17;
+      state ^= state << 5;
+      state as u8
+    })
+    .collect();
+  assert_eq!(PayloadCompression::new().with_threshold(0).compress(&payload), None);
+}
+
+#[test]
+fn implausible_sizes_are_rejected() {
+  let mut bomb = u32::MAX.to_le_bytes().to_vec();
+  bomb.extend_from_slice(&[0; 8]);
+  assert!(matches!(PayloadCompression::decompress(&bomb), Err(WireError::Decompression)));
+  assert!(matches!(PayloadCompression::decompress(&[1, 2]), Err(WireError::Decompression)));
+}
@@ -0,0 +1,49 @@
+//! Exponential backoff between reconnect attempts of a gated association.
+
+#[cfg(test)]
+mod tests;
+
+use core::time::Duration;
+
+/// Exponential backoff applied while reconnecting a dropped association.
+///
+/// The association is gated for the returned delay, then a new handshake is attempted; every
+/// failed attempt doubles the delay up to `max`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReconnectBackoff {
+  min:     Duration,
+  max:     Duration,
+  current: Duration,
+}
+
+impl ReconnectBackoff {
+  /// Creates a backoff starting at `min` and capped at `max`.
+  #[must_use]
+  pub const fn new(min: Duration, max: Duration) -> Self {
+    Self { min, max, current: min }
+  }
+
+  /// Returns the delay before the next attempt and doubles the following one.
+  pub fn next_delay(&mut self) -> Duration {
+    let delay = self.current;
+    self.current = self.current.saturating_mul(2).min(self.max);
+    delay
+  }
+
+  /// Restarts the backoff after a successful reconnect.
+  pub const fn reset(&mut self) {
+    self.current = self.min;
+  }
+
+  /// Returns the initial delay.
+  #[must_use]
+  pub const fn min(&self) -> Duration {
+    self.min
+  }
+
+  /// Returns the maximum delay.
+  #[must_use]
+  pub const fn max(&self) -> Duration {
+    self.max
+  }
+}
@@ -0,0 +1,24 @@
+use core::time::Duration;
+
+use super::ReconnectBackoff;
+
+#[test]
+fn delay_doubles_until_capped() {
+  let mut backoff = ReconnectBackoff::new(Duration::from_millis(100), Duration::from_millis(350));
+  let delays: Vec<_> = (0..4).map(|_| backoff.next_delay()).collect();
+  assert_eq!(delays, vec![
+    Duration::from_millis(100),
+    Duration::from_millis(200),
+    Duration::from_millis(350),
+    Duration::from_millis(350)
+  ]);
+}
+
+#[test]
+fn reset_restarts_from_minimum() {
+  let mut backoff = ReconnectBackoff::new(Duration::from_millis(10), Duration::from_secs(1));
+  let _ = backoff.next_delay();
+  let _ = backoff.next_delay();
+  backoff.reset();
+  assert_eq!(backoff.next_delay(), backoff.min());
+}
@@ -18,8 +18,11 @@ use fraktor_actor_rs::core::{
   },
   error::{ActorError, SendError},
   messaging::{AnyMessageGeneric, SystemMessage},
+  props::PropsGeneric,
+  spawn::SpawnError,
   system::{
-    ActorRefProvider, ActorSystemGeneric, RemoteAuthorityError, RemoteAuthorityManagerGeneric, RemoteWatchHook,
+    ActorRefProvider, ActorSystemGeneric, RemoteAuthorityError, RemoteAuthorityManagerGeneric, RemoteDeployHook,
+    RemoteWatchHook,
   },
 };
 use fraktor_utils_rs::core::{
@@ -29,14 +32,15 @@ use fraktor_utils_rs::core::{
 use hashbrown::HashMap;
 
 use crate::core::{
-  EndpointWriterShared, actor_ref_field_normalizer::ActorRefFieldNormalizerGeneric,
-  endpoint_writer_error::EndpointWriterError, loopback_router, loopback_router::LoopbackDeliveryOutcome,
-  outbound_message::OutboundMessage, outbound_priority::OutboundPriority,
+  EndpointWriterShared, actor_ref_field_normalizer::ActorRefFieldNormalizerGeneric, loopback_router,
+  loopback_router::LoopbackDeliveryOutcome, outbound_message::OutboundMessage,
+  outbound_offer_future::OutboundOfferFutureGeneric, outbound_priority::OutboundPriority,
   remote_actor_ref_provider_error::RemoteActorRefProviderError,
   remote_actor_ref_provider_installer::RemoteActorRefProviderInstaller,
-  remote_authority_snapshot::RemoteAuthoritySnapshot, remote_node_id::RemoteNodeId,
-  remote_watcher_command::RemoteWatcherCommand, remote_watcher_daemon::RemoteWatcherDaemon,
-  remoting_control::RemotingControl, remoting_control_handle::RemotingControlHandle, remoting_error::RemotingError,
+  remote_authority_snapshot::RemoteAuthoritySnapshot, remote_deployment_daemon::RemoteDeploymentDaemon,
+  remote_node_id::RemoteNodeId, remote_watcher_command::RemoteWatcherCommand,
+  remote_watcher_daemon::RemoteWatcherDaemon, remoting_control::RemotingControl,
+  remoting_control_handle::RemotingControlHandle, remoting_error::RemotingError,
 };
 
 /// Provider that creates [`ActorRefGeneric`] instances for remote recipients.
@@ -46,6 +50,7 @@ pub struct RemoteActorRefProviderGeneric<TB: RuntimeToolbox + 'static> {
   control:           RemotingControlHandle<TB>,
   authority_manager: ArcShared<RemoteAuthorityManagerGeneric<TB>>,
   watcher_daemon:    ActorRefGeneric<TB>,
+  deployment_daemon: ActorRefGeneric<TB>,
   watch_entries:     NoStdMutex<HashMap<Pid, RemoteWatchEntry, RandomState>>,
 }
 
@@ -68,6 +73,21 @@ impl<TB: RuntimeToolbox + 'static> RemoteActorRefProviderGeneric<TB> {
     Ok(ActorRefGeneric::with_system(pid, ArcShared::new(sender), self.system.state()))
   }
 
+  /// Sends `message` to the remote actor at `path`, waiting while its association is
+  /// backpressured or its outbound queue is full.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error when remoting is not running or `path` lacks a valid authority.
+  pub fn offer(
+    &self,
+    path: &ActorPath,
+    message: AnyMessageGeneric<TB>,
+  ) -> Result<OutboundOfferFutureGeneric<TB>, RemoteActorRefProviderError> {
+    self.control.associate(path.parts()).map_err(RemoteActorRefProviderError::from)?;
+    Ok(self.sender_for_path(path)?.offer(message))
+  }
+
   pub(crate) fn from_components(
     system: ActorSystemGeneric<TB>,
     writer: EndpointWriterShared<TB>,
@@ -75,12 +95,14 @@ impl<TB: RuntimeToolbox + 'static> RemoteActorRefProviderGeneric<TB> {
     authority_manager: ArcShared<RemoteAuthorityManagerGeneric<TB>>,
   ) -> Result<Self, RemoteActorRefProviderError> {
     let daemon = RemoteWatcherDaemon::spawn(&system, control.clone())?;
+    let deployment_daemon = RemoteDeploymentDaemon::spawn(&system, control.clone())?;
     Ok(Self {
       system,
       writer,
       control,
       authority_manager,
       watcher_daemon: daemon,
+      deployment_daemon,
       watch_entries: NoStdMutex::new(HashMap::with_hasher(RandomState::new())),
     })
   }
@@ -199,6 +221,22 @@ impl<TB: RuntimeToolbox + 'static> RemoteWatchHook<TB> for RemoteActorRefProvide
       false
     }
   }
+
+  fn remote_path(&self, pid: Pid) -> Option<ActorPath> {
+    self.watch_entries.lock().get(&pid).map(|entry| entry.path.clone())
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> RemoteDeployHook<TB> for RemoteActorRefProviderGeneric<TB> {
+  fn deploy_child(&mut self, _parent: Pid, props: &PropsGeneric<TB>) -> Result<ActorRefGeneric<TB>, SpawnError> {
+    RemoteDeploymentDaemon::request_deployment(&self.system, &self.deployment_daemon, props, |path| {
+      self.actor_ref(path)
+    })
+  }
+
+  fn stop_child(&mut self, child: Pid) {
+    RemoteDeploymentDaemon::request_stop(&self.deployment_daemon, child);
+  }
 }
 
 struct RemoteActorRefSender<TB: RuntimeToolbox + 'static> {
@@ -216,13 +254,47 @@ impl<TB: RuntimeToolbox + 'static> RemoteActorRefSender<TB> {
     }
   }
 
-  fn map_error(&self, error: EndpointWriterError, message: AnyMessageGeneric<TB>) -> SendError<TB> {
-    match error {
-      | EndpointWriterError::QueueFull(_) => SendError::full(message),
-      | EndpointWriterError::QueueClosed(_) | EndpointWriterError::QueueUnavailable { .. } => {
-        SendError::closed(message)
+  /// Validates `message` and delivers it through loopback routing when the recipient is local,
+  /// returning the outbound message to enqueue otherwise.
+  fn route(&self, message: AnyMessageGeneric<TB>) -> Result<LoopbackDeliveryOutcome<TB>, SendError<TB>> {
+    let system_state = {
+      let writer_guard = self.writer.lock();
+      writer_guard.system().state()
+    };
+    let normalizer = ActorRefFieldNormalizerGeneric::new(system_state);
+    if let Err(RemoteAuthorityError::Quarantined) = normalizer.validate_recipient(&self.recipient) {
+      return Err(SendError::closed(message));
+    }
+    if let Err(RemoteAuthorityError::Quarantined) = normalizer.validate_reply_to(&message) {
+      return Err(SendError::closed(message));
+    }
+
+    let priority = Self::determine_priority(&message);
+    let mut outbound = match priority {
+      | OutboundPriority::System => {
+        OutboundMessage::system(message.clone(), self.recipient.clone(), self.remote_node.clone())
+      },
+      | OutboundPriority::User => {
+        OutboundMessage::user(message.clone(), self.recipient.clone(), self.remote_node.clone())
       },
-      | EndpointWriterError::Serialization(_) => SendError::closed(message),
+    };
+    if let Some(reply_to) = message.reply_to()
+      && let Some(reply_path) = reply_to.canonical_path().or_else(|| reply_to.path())
+    {
+      let enriched = self.enrich_reply_path(&reply_path);
+      outbound = outbound.with_reply_to(enriched);
+    }
+    loopback_router::try_deliver(&self.remote_node, &self.writer, outbound)
+      .map_err(|error| error.into_send_error(message))
+  }
+
+  fn offer(&self, message: AnyMessageGeneric<TB>) -> OutboundOfferFutureGeneric<TB> {
+    match self.route(message.clone()) {
+      | Ok(LoopbackDeliveryOutcome::Delivered) => OutboundOfferFutureGeneric::ready(Ok(())),
+      | Ok(LoopbackDeliveryOutcome::Pending(pending)) => {
+        OutboundOfferFutureGeneric::new(self.writer.clone(), *pending, message)
+      },
+      | Err(error) => OutboundOfferFutureGeneric::ready(Err(error)),
     }
   }
 
@@ -253,41 +325,12 @@ impl<TB: RuntimeToolbox + 'static> RemoteActorRefSender<TB> {
 
 impl<TB: RuntimeToolbox + 'static> ActorRefSender<TB> for RemoteActorRefSender<TB> {
   fn send(&self, message: AnyMessageGeneric<TB>) -> Result<(), SendError<TB>> {
-    let system_state = {
-      let writer_guard = self.writer.lock();
-      writer_guard.system().state()
-    };
-    let normalizer = ActorRefFieldNormalizerGeneric::new(system_state);
-    if let Err(RemoteAuthorityError::Quarantined) = normalizer.validate_recipient(&self.recipient) {
-      return Err(SendError::closed(message));
-    }
-    if let Err(RemoteAuthorityError::Quarantined) = normalizer.validate_reply_to(&message) {
-      return Err(SendError::closed(message));
-    }
-
-    let priority = Self::determine_priority(&message);
-    let message_clone = message.clone();
-    let mut outbound = match priority {
-      | OutboundPriority::System => {
-        OutboundMessage::system(message.clone(), self.recipient.clone(), self.remote_node.clone())
-      },
-      | OutboundPriority::User => {
-        OutboundMessage::user(message.clone(), self.recipient.clone(), self.remote_node.clone())
-      },
-    };
-    if let Some(reply_to) = message.reply_to()
-      && let Some(reply_path) = reply_to.path()
-    {
-      let enriched = self.enrich_reply_path(&reply_path);
-      outbound = outbound.with_reply_to(enriched);
-    }
-    match loopback_router::try_deliver(&self.remote_node, &self.writer, outbound) {
-      | Ok(LoopbackDeliveryOutcome::Delivered) => Ok(()),
-      | Ok(LoopbackDeliveryOutcome::Pending(pending)) => {
+    match self.route(message.clone())? {
+      | LoopbackDeliveryOutcome::Delivered => Ok(()),
+      | LoopbackDeliveryOutcome::Pending(pending) => {
         let mut writer = self.writer.lock();
-        writer.enqueue(*pending).map_err(|error| self.map_error(error, message_clone))
+        writer.enqueue(*pending).map_err(|error| error.into_send_error(message))
       },
-      | Err(error) => Err(self.map_error(error, message_clone)),
     }
   }
 }
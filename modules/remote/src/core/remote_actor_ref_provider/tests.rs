@@ -1,7 +1,12 @@
 #![cfg(any(test, feature = "test-support"))]
 
 use alloc::string::String;
-use core::time::Duration;
+use core::{
+  future::Future,
+  pin::Pin,
+  task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+  time::Duration,
+};
 
 use fraktor_actor_rs::core::{
   actor_prim::{
@@ -9,14 +14,15 @@ use fraktor_actor_rs::core::{
     actor_path::{ActorPath, ActorPathParts, GuardianKind},
   },
   error::{ActorError, SendError},
+  event_stream::BackpressureSignal,
   messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
-  props::PropsGeneric,
+  props::{Deploy, PropsGeneric},
   scheduler::{ManualTestDriver, TickDriverConfig},
   serialization::{
     SerializationCallScope, SerializationExtensionGeneric, SerializationSetup, SerializationSetupBuilder, Serializer,
     SerializerId, StringSerializer,
   },
-  system::{ActorSystemConfig, ActorSystemGeneric, RemoteWatchHook},
+  system::{ActorSystemConfig, ActorSystemGeneric, RemoteDeployHook, RemoteWatchHook},
 };
 use fraktor_utils_rs::core::{
   runtime_toolbox::{NoStdToolbox, RuntimeToolbox, SyncMutexFamily},
@@ -29,6 +35,26 @@ use crate::core::{
   remoting_extension_config::RemotingExtensionConfig,
 };
 
+unsafe fn noop_clone(_: *const ()) -> RawWaker {
+  noop_raw_waker()
+}
+
+unsafe fn noop_wake(_: *const ()) {}
+
+unsafe fn noop_wake_by_ref(_: *const ()) {}
+
+unsafe fn noop_drop(_: *const ()) {}
+
+const NOOP_WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(noop_clone, noop_wake, noop_wake_by_ref, noop_drop);
+
+fn noop_raw_waker() -> RawWaker {
+  RawWaker::new(core::ptr::null(), &NOOP_WAKER_VTABLE)
+}
+
+fn noop_waker() -> Waker {
+  unsafe { Waker::from_raw(noop_raw_waker()) }
+}
+
 struct NoopActor;
 
 impl Actor<NoStdToolbox> for NoopActor {
@@ -151,3 +177,53 @@ fn sender_rejects_quarantined_authority() {
   let result = remote.tell(AnyMessageGeneric::new("hello".to_string()));
   assert!(matches!(result, Err(SendError::Closed(_))));
 }
+
+#[test]
+fn backpressure_suspends_tell_and_parks_offers_until_release() {
+  let system = build_system();
+  let provider = provider(&system);
+  let writer = provider.writer_for_test();
+  let remote = provider.actor_ref(remote_path()).expect("actor ref");
+  writer.lock().handle_backpressure("127.0.0.1:4100", BackpressureSignal::Apply);
+
+  let result = remote.tell(AnyMessageGeneric::new("hello".to_string()));
+  assert!(matches!(result, Err(SendError::Suspended(_))));
+
+  let waker = noop_waker();
+  let mut context = Context::from_waker(&waker);
+  let mut offer = provider.offer(&remote_path(), AnyMessageGeneric::new("later".to_string())).expect("offer");
+  assert!(Pin::new(&mut offer).poll(&mut context).is_pending());
+
+  writer.lock().handle_backpressure("127.0.0.1:4100", BackpressureSignal::Release);
+  assert!(matches!(Pin::new(&mut offer).poll(&mut context), Poll::Ready(Ok(()))));
+  assert!(writer.lock().try_next().expect("poll writer").is_some());
+}
+
+#[test]
+fn deploy_hook_sends_request_to_remote_deployment_daemon() {
+  let system = build_system();
+  let mut provider = provider(&system);
+  let writer = provider.writer_for_test();
+  let target = ActorPathParts::with_authority("remote-app", Some(("127.0.0.1", 4100)));
+  let props =
+    PropsGeneric::from_fn(|| NoopActor).with_name("worker-1").with_deploy(Deploy::remote(target, "worker", vec![1]));
+
+  let child = RemoteDeployHook::deploy_child(&mut provider, Pid::new(42, 0), &props).expect("deploy");
+
+  assert!(provider.registered_remote_pids_for_test().contains(&child.pid()));
+  let envelope = writer.lock().try_next().expect("poll writer").expect("envelope exists");
+  assert_eq!(envelope.recipient().to_relative_string(), "/user/system/remote");
+  assert_eq!(envelope.serialized_message().manifest(), Some("fraktor.remote.RemoteDeploymentPdu"));
+  assert!(envelope.reply_to().is_some());
+}
+
+#[test]
+fn deploy_hook_rejects_invalid_child_name() {
+  let system = build_system();
+  let mut provider = provider(&system);
+  let target = ActorPathParts::with_authority("remote-app", Some(("127.0.0.1", 4100)));
+  let props =
+    PropsGeneric::from_fn(|| NoopActor).with_name("bad/name").with_deploy(Deploy::remote(target, "worker", vec![]));
+
+  assert!(RemoteDeployHook::deploy_child(&mut provider, Pid::new(42, 0), &props).is_err());
+}
@@ -45,21 +45,23 @@ impl<TB: RuntimeToolbox + 'static> ActorRefProviderInstaller<TB> for RemoteActor
       return Err(ActorSystemBuildError::Configuration("serialization extension not installed".into()));
     };
 
-    let writer_mutex =
-      <TB::MutexFamily as SyncMutexFamily>::create(EndpointWriterGeneric::new(system.clone(), serialization));
-    let writer = ArcShared::new(writer_mutex);
-
     let Some(extension) = extended.extension_by_type::<RemotingExtensionGeneric<TB>>() else {
       return Err(ActorSystemBuildError::Configuration("remoting extension not installed".into()));
     };
 
     let control = extension.handle();
+    let writer_mutex = <TB::MutexFamily as SyncMutexFamily>::create(
+      control.configure_writer(EndpointWriterGeneric::new(system.clone(), serialization)),
+    );
+    let writer = ArcShared::new(writer_mutex);
     let authority_manager = system.state().remote_authority_manager().clone();
-    let provider = RemoteActorRefProviderGeneric::from_components(system.clone(), writer, control, authority_manager)
-      .map_err(|error| ActorSystemBuildError::Configuration(format!("{error}")))?;
+    let provider =
+      RemoteActorRefProviderGeneric::from_components(system.clone(), writer, control.clone(), authority_manager)
+        .map_err(|error| ActorSystemBuildError::Configuration(format!("{error}")))?;
     let shared = RemoteWatchHookShared::new(provider, &[ActorPathScheme::FraktorTcp]);
     let shared_arc = ArcShared::new(shared.clone());
     extended.register_actor_ref_provider(&shared_arc);
+    extended.register_remote_deploy_hook(shared.clone());
     extended.register_remote_watch_hook(shared);
 
     if self.enable_loopback {
@@ -71,7 +73,7 @@ impl<TB: RuntimeToolbox + 'static> ActorRefProviderInstaller<TB> for RemoteActor
           "serialization extension missing for loopback routing".into(),
         ));
       };
-      let reader = EndpointReaderGeneric::new(system.clone(), serialization_ext);
+      let reader = control.configure_reader(EndpointReaderGeneric::new(system.clone(), serialization_ext));
       loopback_router::register_endpoint(authority, reader, system.clone());
     }
     Ok(())
@@ -0,0 +1,23 @@
+//! Local commands handled by the remote deployment daemon.
+
+use fraktor_actor_rs::core::actor_prim::{Pid, actor_ref::ActorRefGeneric};
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
+/// Local commands handled by the remote deployment daemon.
+#[derive(Clone)]
+pub(crate) enum RemoteDeploymentCommand<TB: RuntimeToolbox + 'static> {
+  /// Records a pending deployment so that replies from the hosting node reach the parent.
+  Track {
+    /// Correlation token carried by the deployment protocol.
+    token:         u64,
+    /// Pid of the remote child reference handed to the parent.
+    child:         Pid,
+    /// Deployment daemon of the hosting node.
+    remote_daemon: ActorRefGeneric<TB>,
+  },
+  /// Asks the hosting node to stop a previously deployed child.
+  Stop {
+    /// Pid of the remote child reference handed to the parent.
+    child: Pid,
+  },
+}
@@ -0,0 +1,297 @@
+//! Hosts remotely deployed children and relays their lifecycle to the deploying node.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{collections::BTreeMap, format, string::ToString, vec::Vec};
+
+use ahash::RandomState;
+use fraktor_actor_rs::core::{
+  actor_prim::{
+    Actor, ActorContextGeneric, ChildRefGeneric, Pid,
+    actor_path::{ActorPath, ActorPathParts},
+    actor_ref::ActorRefGeneric,
+  },
+  error::ActorError,
+  event_stream::{EventStreamEvent, EventStreamGeneric, EventStreamSubscriptionGeneric, RemotingLifecycleEvent},
+  logging::LogLevel,
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+  props::PropsGeneric,
+  spawn::SpawnError,
+  system::ActorSystemGeneric,
+};
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+use hashbrown::HashMap;
+
+use crate::core::{
+  remote_actor_ref_provider_error::RemoteActorRefProviderError, remote_deployment_command::RemoteDeploymentCommand,
+  remote_deployment_pdu::RemoteDeploymentPdu, remoting_control_handle::RemotingControlHandle,
+  remoting_error::RemotingError,
+};
+
+const DAEMON_NAME: &str = "remote";
+
+/// System actor that spawns whitelisted children for remote parents and tracks children deployed
+/// onto remote nodes on behalf of local parents.
+///
+/// The daemon runs under the system guardian, so it is addressed as `/system/remote` and hosted
+/// children live at `/system/remote/<child>`. Hosted children are stopped once their origin
+/// terminates or the association with the origin's node is quarantined.
+pub(crate) struct RemoteDeploymentDaemon<TB>
+where
+  TB: RuntimeToolbox + 'static, {
+  control:      RemotingControlHandle<TB>,
+  hosted:       HashMap<Pid, HostedChild<TB>, RandomState>,
+  deployed:     BTreeMap<u64, DeployedChild<TB>>,
+  subscription: Option<EventStreamSubscriptionGeneric<TB>>,
+}
+
+struct HostedChild<TB: RuntimeToolbox + 'static> {
+  token:  u64,
+  origin: ActorRefGeneric<TB>,
+  child:  ChildRefGeneric<TB>,
+}
+
+struct DeployedChild<TB: RuntimeToolbox + 'static> {
+  child:         Pid,
+  remote_daemon: ActorRefGeneric<TB>,
+}
+
+impl<TB> RemoteDeploymentDaemon<TB>
+where
+  TB: RuntimeToolbox + 'static,
+{
+  fn new(control: RemotingControlHandle<TB>) -> Self {
+    Self { control, hosted: HashMap::with_hasher(RandomState::new()), deployed: BTreeMap::new(), subscription: None }
+  }
+
+  /// Spawns the daemon under the system guardian hierarchy.
+  pub(crate) fn spawn(
+    system: &ActorSystemGeneric<TB>,
+    control: RemotingControlHandle<TB>,
+  ) -> Result<ActorRefGeneric<TB>, RemotingError> {
+    let props = PropsGeneric::from_fn({
+      let handle = control.clone();
+      move || RemoteDeploymentDaemon::new(handle.clone())
+    })
+    .with_name(DAEMON_NAME);
+    let actor = system.extended().spawn_system_actor(&props).map_err(RemotingError::from)?;
+    Ok(actor.actor_ref().clone())
+  }
+
+  /// Returns the path of the deployment daemon (`/system/remote`) running on the node described
+  /// by `target`.
+  pub(crate) fn daemon_path(target: &ActorPathParts) -> ActorPath {
+    ActorPath::from_parts(target.clone()).child("system").child(DAEMON_NAME)
+  }
+
+  /// Sends a deployment request for `props` and returns a reference to the remote child.
+  ///
+  /// `resolve` turns remote paths into actor references using the calling provider.
+  pub(crate) fn request_deployment<F>(
+    system: &ActorSystemGeneric<TB>,
+    local_daemon: &ActorRefGeneric<TB>,
+    props: &PropsGeneric<TB>,
+    resolve: F,
+  ) -> Result<ActorRefGeneric<TB>, SpawnError>
+  where
+    F: Fn(ActorPath) -> Result<ActorRefGeneric<TB>, RemoteActorRefProviderError>, {
+    let Some(deploy) = props.deploy() else {
+      return Err(SpawnError::invalid_props("props do not request a remote deployment"));
+    };
+    let child_name = match props.name() {
+      | Some(name) => name.to_string(),
+      | None => format!("deployed-{}", system.allocate_pid().value()),
+    };
+    let daemon_path = Self::daemon_path(deploy.target());
+    let child_path = daemon_path
+      .try_child(&child_name)
+      .map_err(|error| SpawnError::invalid_props(format!("invalid deployment name `{child_name}`: {error:?}")))?;
+    let remote_daemon = resolve(daemon_path).map_err(|error| SpawnError::invalid_props(format!("{error}")))?;
+    let child = resolve(child_path).map_err(|error| SpawnError::invalid_props(format!("{error}")))?;
+    let token = child.pid().value();
+
+    let track = RemoteDeploymentCommand::Track { token, child: child.pid(), remote_daemon: remote_daemon.clone() };
+    local_daemon.tell(AnyMessageGeneric::new(track)).map_err(|_| SpawnError::system_unavailable())?;
+    let request = RemoteDeploymentPdu::Deploy {
+      token,
+      child_name,
+      factory_id: deploy.factory_id().to_string(),
+      args: deploy.args().to_vec(),
+    };
+    remote_daemon
+      .tell(AnyMessageGeneric::new(request).with_reply_to(local_daemon.clone()))
+      .map_err(|_| SpawnError::invalid_props("failed to send the deployment request"))?;
+    Ok(child)
+  }
+
+  /// Asks the hosting node of `child` to stop it on behalf of its local parent.
+  pub(crate) fn request_stop(local_daemon: &ActorRefGeneric<TB>, child: Pid) {
+    let _ = local_daemon.tell(AnyMessageGeneric::new(RemoteDeploymentCommand::<TB>::Stop { child }));
+  }
+
+  fn host(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    origin: Option<&ActorRefGeneric<TB>>,
+    token: u64,
+    child_name: &str,
+    factory_id: &str,
+    args: &[u8],
+  ) {
+    let Some(origin) = origin.cloned() else {
+      ctx.log(LogLevel::Warn, format!("dropping deployment request {token} without an origin"));
+      return;
+    };
+    let spawned = self
+      .control
+      .deployable_props(factory_id, args)
+      .and_then(|props| ctx.spawn_child_watched(&props.with_name(child_name)).map_err(|error| format!("{error:?}")));
+    match spawned {
+      | Ok(child) => {
+        // 配備元が停止したら子を孤児にしないよう、配備元を監視する
+        if self.hosted.values().all(|hosted| hosted.origin.pid() != origin.pid()) {
+          let _ = ctx.watch(&origin);
+        }
+        self.hosted.insert(child.pid(), HostedChild { token, origin, child });
+      },
+      | Err(reason) => {
+        let _ = origin.tell(AnyMessageGeneric::new(RemoteDeploymentPdu::Rejected { token, reason }));
+      },
+    }
+  }
+
+  /// Stops the hosted child identified by `token` when the request comes from its origin.
+  fn stop_hosted(&self, ctx: &mut ActorContextGeneric<'_, TB>, origin: Option<&ActorRefGeneric<TB>>, token: u64) {
+    let origin_path = origin.and_then(ActorRefGeneric::path);
+    let hosted = self
+      .hosted
+      .values()
+      .find(|hosted| hosted.token == token && origin_path.is_some() && hosted.origin.path() == origin_path);
+    match hosted {
+      | Some(hosted) => {
+        let _ = ctx.stop_child(&hosted.child);
+      },
+      | None => ctx.log(LogLevel::Warn, format!("ignoring stop request {token} from an unknown origin")),
+    }
+  }
+
+  /// Stops every hosted child whose origin matches `is_origin`.
+  fn stop_hosted_for<F>(&self, ctx: &mut ActorContextGeneric<'_, TB>, is_origin: F)
+  where
+    F: Fn(&ActorRefGeneric<TB>) -> bool, {
+    let orphans: Vec<&HostedChild<TB>> = self.hosted.values().filter(|hosted| is_origin(&hosted.origin)).collect();
+    for hosted in orphans {
+      ctx.log(LogLevel::Info, format!("stopping hosted child {} whose origin is gone", hosted.token));
+      let _ = ctx.stop_child(&hosted.child);
+    }
+  }
+
+  fn on_lifecycle(&self, ctx: &mut ActorContextGeneric<'_, TB>, event: &RemotingLifecycleEvent) {
+    if let RemotingLifecycleEvent::Quarantined { authority, .. } = event {
+      self.stop_hosted_for(ctx, |origin| {
+        origin.path().and_then(|path| path.parts().authority_endpoint()).as_ref() == Some(authority)
+      });
+    }
+  }
+
+  fn request_remote_stop(&self, ctx: &ActorContextGeneric<'_, TB>, child: Pid) {
+    if let Some((token, deployed)) = self.deployed.iter().find(|(_, deployed)| deployed.child == child) {
+      let request = AnyMessageGeneric::new(RemoteDeploymentPdu::Stop { token: *token }).with_reply_to(ctx.self_ref());
+      let _ = deployed.remote_daemon.tell(request);
+    }
+  }
+
+  fn notify_failure(ctx: &ActorContextGeneric<'_, TB>, deployed: &DeployedChild<TB>, reason: &str) {
+    ctx.system().extended().report_remote_child_failure(deployed.child, &ActorError::recoverable(reason.to_string()));
+  }
+
+  fn notify_terminated(ctx: &ActorContextGeneric<'_, TB>, deployed: &DeployedChild<TB>) {
+    ctx.system().extended().remote_child_terminated(deployed.child);
+  }
+
+  fn handle_pdu(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    pdu: &RemoteDeploymentPdu,
+    message: &AnyMessageViewGeneric<'_, TB>,
+  ) {
+    match pdu {
+      | RemoteDeploymentPdu::Deploy { token, child_name, factory_id, args } => {
+        self.host(ctx, message.reply_to(), *token, child_name, factory_id, args);
+      },
+      | RemoteDeploymentPdu::Rejected { token, reason } => {
+        if let Some(deployed) = self.deployed.remove(token) {
+          Self::notify_failure(ctx, &deployed, reason);
+          Self::notify_terminated(ctx, &deployed);
+        }
+      },
+      | RemoteDeploymentPdu::ChildFailed { token, reason } => {
+        if let Some(deployed) = self.deployed.get(token) {
+          Self::notify_failure(ctx, deployed, reason);
+        }
+      },
+      | RemoteDeploymentPdu::ChildTerminated { token } => {
+        if let Some(deployed) = self.deployed.remove(token) {
+          Self::notify_terminated(ctx, &deployed);
+        }
+      },
+      | RemoteDeploymentPdu::Stop { token } => {
+        self.stop_hosted(ctx, message.reply_to(), *token);
+      },
+    }
+  }
+}
+
+impl<TB> Actor<TB> for RemoteDeploymentDaemon<TB>
+where
+  TB: RuntimeToolbox + 'static,
+{
+  fn pre_start(&mut self, ctx: &mut ActorContextGeneric<'_, TB>) -> Result<(), ActorError> {
+    let event_stream = ctx.system().event_stream();
+    self.subscription = Some(EventStreamGeneric::subscribe_actor(&event_stream, ctx.self_ref()));
+    Ok(())
+  }
+
+  fn receive(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, TB>,
+    message: AnyMessageViewGeneric<'_, TB>,
+  ) -> Result<(), ActorError> {
+    if let Some(command) = message.downcast_ref::<RemoteDeploymentCommand<TB>>() {
+      match command {
+        | RemoteDeploymentCommand::Track { token, child, remote_daemon } => {
+          self.deployed.insert(*token, DeployedChild { child: *child, remote_daemon: remote_daemon.clone() });
+        },
+        | RemoteDeploymentCommand::Stop { child } => self.request_remote_stop(ctx, *child),
+      }
+    } else if let Some(pdu) = message.downcast_ref::<RemoteDeploymentPdu>() {
+      self.handle_pdu(ctx, pdu, &message);
+    } else if let Some(EventStreamEvent::RemotingLifecycle(event)) = message.downcast_ref::<EventStreamEvent<TB>>() {
+      self.on_lifecycle(ctx, event);
+    }
+    Ok(())
+  }
+
+  fn post_stop(&mut self, _ctx: &mut ActorContextGeneric<'_, TB>) -> Result<(), ActorError> {
+    self.subscription = None;
+    Ok(())
+  }
+
+  fn on_terminated(&mut self, ctx: &mut ActorContextGeneric<'_, TB>, terminated: Pid) -> Result<(), ActorError> {
+    if let Some(hosted) = self.hosted.remove(&terminated) {
+      let _ = hosted.origin.tell(AnyMessageGeneric::new(RemoteDeploymentPdu::ChildTerminated { token: hosted.token }));
+    } else {
+      self.stop_hosted_for(ctx, |origin| origin.pid() == terminated);
+    }
+    Ok(())
+  }
+
+  fn on_child_failed(&mut self, _ctx: &mut ActorContextGeneric<'_, TB>, child: Pid, error: &ActorError) {
+    if let Some(hosted) = self.hosted.get(&child) {
+      let failure =
+        RemoteDeploymentPdu::ChildFailed { token: hosted.token, reason: error.reason().as_str().to_string() };
+      let _ = hosted.origin.tell(AnyMessageGeneric::new(failure));
+    }
+  }
+}
@@ -0,0 +1,413 @@
+use alloc::{
+  string::{String, ToString},
+  vec,
+  vec::Vec,
+};
+
+use fraktor_actor_rs::core::{
+  actor_prim::{
+    Actor, ActorContextGeneric, ChildRefGeneric, Pid,
+    actor_path::{ActorPath, ActorPathParts},
+    actor_ref::{ActorRefGeneric, NullSender},
+  },
+  error::ActorError,
+  event_stream::{CorrelationId, EventStreamEvent, RemotingLifecycleEvent},
+  messaging::{AnyMessageGeneric, AnyMessageViewGeneric},
+  props::{Deploy, PropsGeneric},
+  scheduler::{ManualTestDriver, TickDriverConfig},
+  spawn::SpawnError,
+  system::{ActorSystemConfig, ActorSystemGeneric, RemoteDeployHook, RemoteWatchHook},
+};
+use fraktor_utils_rs::core::{
+  runtime_toolbox::{NoStdMutex, NoStdToolbox},
+  sync::ArcShared,
+};
+
+use super::RemoteDeploymentDaemon;
+use crate::core::{
+  remote_deployment_pdu::RemoteDeploymentPdu, remoting_control::RemotingControl,
+  remoting_control_handle::RemotingControlHandle, remoting_extension_config::RemotingExtensionConfig,
+};
+
+type Slot<T> = ArcShared<NoStdMutex<T>>;
+
+struct NoopActor;
+
+impl Actor<NoStdToolbox> for NoopActor {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    _message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    Ok(())
+  }
+}
+
+struct PduProbe {
+  received: Slot<Vec<RemoteDeploymentPdu>>,
+}
+
+impl Actor<NoStdToolbox> for PduProbe {
+  fn receive(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    if let Some(pdu) = message.downcast_ref::<RemoteDeploymentPdu>() {
+      self.received.lock().push(pdu.clone());
+    } else if let Some(&"stop") = message.downcast_ref::<&'static str>() {
+      let _ = ctx.stop_self();
+    }
+    Ok(())
+  }
+}
+
+const REMOTE_CHILD: Pid = Pid::new(900, 0);
+
+/// Deploys through the daemon under test, using `remote_daemon` as the hosting node's daemon.
+struct DaemonDeployHook {
+  system:        ActorSystemGeneric<NoStdToolbox>,
+  daemon:        ActorRefGeneric<NoStdToolbox>,
+  remote_daemon: ActorRefGeneric<NoStdToolbox>,
+}
+
+impl RemoteDeployHook<NoStdToolbox> for DaemonDeployHook {
+  fn deploy_child(
+    &mut self,
+    _parent: Pid,
+    props: &PropsGeneric<NoStdToolbox>,
+  ) -> Result<ActorRefGeneric<NoStdToolbox>, SpawnError> {
+    RemoteDeploymentDaemon::request_deployment(&self.system, &self.daemon, props, |path| {
+      if path.segments().last().map(|segment| segment.as_str()) == Some("remote") {
+        Ok(self.remote_daemon.clone())
+      } else {
+        Ok(ActorRefGeneric::new(REMOTE_CHILD, ArcShared::new(NullSender)))
+      }
+    })
+  }
+
+  fn stop_child(&mut self, child: Pid) {
+    RemoteDeploymentDaemon::request_stop(&self.daemon, child);
+  }
+}
+
+struct ParentProbe {
+  failures:   Slot<Vec<(Pid, String)>>,
+  terminated: Slot<Vec<Pid>>,
+}
+
+impl Actor<NoStdToolbox> for ParentProbe {
+  fn pre_start(&mut self, ctx: &mut ActorContextGeneric<'_, NoStdToolbox>) -> Result<(), ActorError> {
+    let target = ActorPathParts::with_authority("remote-app", Some(("127.0.0.1", 4100)));
+    let props =
+      PropsGeneric::from_fn(|| NoopActor).with_name("w1").with_deploy(Deploy::remote(target, "worker", Vec::new()));
+    ctx.spawn_child(&props).map_err(|error| ActorError::fatal(format!("{error:?}")))?;
+    Ok(())
+  }
+
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    _message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    Ok(())
+  }
+
+  fn on_child_failed(&mut self, _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>, child: Pid, error: &ActorError) {
+    self.failures.lock().push((child, error.reason().as_str().to_string()));
+  }
+
+  fn on_terminated(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    terminated: Pid,
+  ) -> Result<(), ActorError> {
+    self.terminated.lock().push(terminated);
+    Ok(())
+  }
+}
+
+struct Worker {
+  self_slot: Slot<Option<ActorRefGeneric<NoStdToolbox>>>,
+}
+
+impl Actor<NoStdToolbox> for Worker {
+  fn pre_start(&mut self, ctx: &mut ActorContextGeneric<'_, NoStdToolbox>) -> Result<(), ActorError> {
+    *self.self_slot.lock() = Some(ctx.self_ref());
+    Ok(())
+  }
+
+  fn receive(
+    &mut self,
+    ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    match message.downcast_ref::<&'static str>() {
+      | Some(&"fail") => Err(ActorError::recoverable("worker failed")),
+      | Some(&"stop") => {
+        let _ = ctx.stop_self();
+        Ok(())
+      },
+      | _ => Ok(()),
+    }
+  }
+}
+
+struct Fixture {
+  system:    ActorSystemGeneric<NoStdToolbox>,
+  daemon:    ActorRefGeneric<NoStdToolbox>,
+  origin:    ActorRefGeneric<NoStdToolbox>,
+  received:  Slot<Vec<RemoteDeploymentPdu>>,
+  worker:    Slot<Option<ActorRefGeneric<NoStdToolbox>>>,
+  args_seen: Slot<Vec<Vec<u8>>>,
+}
+
+fn fixture() -> Fixture {
+  let props = PropsGeneric::from_fn(|| NoopActor).with_name("deployment-daemon-tests");
+  let config = ActorSystemConfig::default().with_tick_driver(TickDriverConfig::manual(ManualTestDriver::new()));
+  let system = ActorSystemGeneric::new_with_config(&props, &config).expect("system builds");
+  let control = RemotingControlHandle::new(system.clone(), RemotingExtensionConfig::default());
+  control.start().expect("control start");
+
+  let worker: Slot<Option<ActorRefGeneric<NoStdToolbox>>> = ArcShared::new(NoStdMutex::new(None));
+  let args_seen: Slot<Vec<Vec<u8>>> = ArcShared::new(NoStdMutex::new(Vec::new()));
+  control.register_deployable("worker", {
+    let worker = worker.clone();
+    let args_seen = args_seen.clone();
+    move |args: &[u8]| {
+      args_seen.lock().push(args.to_vec());
+      let self_slot = worker.clone();
+      Ok(PropsGeneric::from_fn(move || Worker { self_slot: self_slot.clone() }))
+    }
+  });
+  let daemon = RemoteDeploymentDaemon::spawn(&system, control).expect("daemon spawns");
+
+  let received: Slot<Vec<RemoteDeploymentPdu>> = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let origin = system
+    .extended()
+    .spawn_system_actor(&PropsGeneric::from_fn({
+      let received = received.clone();
+      move || PduProbe { received: received.clone() }
+    }))
+    .expect("probe spawns")
+    .actor_ref()
+    .clone();
+  Fixture { system, daemon, origin, received, worker, args_seen }
+}
+
+fn deploy_request(factory_id: &str) -> RemoteDeploymentPdu {
+  RemoteDeploymentPdu::Deploy {
+    token:      7,
+    child_name: "w1".to_string(),
+    factory_id: factory_id.to_string(),
+    args:       vec![1, 2],
+  }
+}
+
+#[test]
+fn whitelisted_deployment_spawns_child_and_reports_failures() {
+  let fixture = fixture();
+  fixture
+    .daemon
+    .tell(AnyMessageGeneric::new(deploy_request("worker")).with_reply_to(fixture.origin.clone()))
+    .expect("deploy");
+
+  assert_eq!(*fixture.args_seen.lock(), vec![vec![1, 2]]);
+  let worker = fixture.worker.lock().clone().expect("worker started");
+  assert_eq!(
+    fixture.system.state().actor_path(&worker.pid()).expect("path").segments().last().map(|s| s.as_str()),
+    Some("w1")
+  );
+
+  worker.tell(AnyMessageGeneric::new("fail")).expect("fail");
+  assert_eq!(*fixture.received.lock(), vec![RemoteDeploymentPdu::ChildFailed {
+    token:  7,
+    reason: "worker failed".to_string(),
+  }]);
+}
+
+#[test]
+fn hosted_child_termination_is_reported_to_origin() {
+  let fixture = fixture();
+  fixture
+    .daemon
+    .tell(AnyMessageGeneric::new(deploy_request("worker")).with_reply_to(fixture.origin.clone()))
+    .expect("deploy");
+
+  let worker = fixture.worker.lock().clone().expect("worker started");
+  worker.tell(AnyMessageGeneric::new("stop")).expect("stop");
+  assert_eq!(*fixture.received.lock(), vec![RemoteDeploymentPdu::ChildTerminated { token: 7 }]);
+}
+
+#[test]
+fn unknown_factory_is_rejected() {
+  let fixture = fixture();
+  fixture
+    .daemon
+    .tell(AnyMessageGeneric::new(deploy_request("miner")).with_reply_to(fixture.origin.clone()))
+    .expect("deploy");
+
+  assert!(fixture.worker.lock().is_none());
+  let received = fixture.received.lock().clone();
+  assert!(matches!(
+    received.as_slice(),
+    [RemoteDeploymentPdu::Rejected { token: 7, reason }] if reason.contains("not whitelisted")
+  ));
+}
+
+struct ParentFixture {
+  fixture:    Fixture,
+  parent:     ChildRefGeneric<NoStdToolbox>,
+  failures:   Slot<Vec<(Pid, String)>>,
+  terminated: Slot<Vec<Pid>>,
+}
+
+fn parent_fixture() -> ParentFixture {
+  let fixture = fixture();
+  fixture.system.extended().register_remote_deploy_hook(DaemonDeployHook {
+    system:        fixture.system.clone(),
+    daemon:        fixture.daemon.clone(),
+    remote_daemon: fixture.origin.clone(),
+  });
+  let failures: Slot<Vec<(Pid, String)>> = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let terminated: Slot<Vec<Pid>> = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let parent = fixture
+    .system
+    .extended()
+    .spawn_system_actor(&PropsGeneric::from_fn({
+      let failures = failures.clone();
+      let terminated = terminated.clone();
+      move || ParentProbe { failures: failures.clone(), terminated: terminated.clone() }
+    }))
+    .expect("parent spawns");
+  assert!(matches!(fixture.received.lock().as_slice(), [RemoteDeploymentPdu::Deploy { token: 900, .. }]));
+  ParentFixture { fixture, parent, failures, terminated }
+}
+
+#[test]
+fn rejection_is_supervised_by_the_parent() {
+  let fixture = parent_fixture();
+  let reason = String::from("factory `worker` is not whitelisted");
+  fixture
+    .fixture
+    .daemon
+    .tell(AnyMessageGeneric::new(RemoteDeploymentPdu::Rejected { token: 900, reason: reason.clone() }))
+    .expect("rejected");
+
+  assert_eq!(*fixture.failures.lock(), vec![(REMOTE_CHILD, reason)]);
+  assert_eq!(*fixture.terminated.lock(), vec![REMOTE_CHILD]);
+}
+
+#[test]
+fn remote_child_failure_is_supervised_by_the_parent() {
+  let fixture = parent_fixture();
+  fixture
+    .fixture
+    .daemon
+    .tell(AnyMessageGeneric::new(RemoteDeploymentPdu::ChildFailed { token: 900, reason: "boom".to_string() }))
+    .expect("child failed");
+
+  assert_eq!(*fixture.failures.lock(), vec![(REMOTE_CHILD, "boom".to_string())]);
+  assert!(fixture.terminated.lock().is_empty());
+}
+
+#[test]
+fn stopping_the_parent_stops_the_remote_child() {
+  let fixture = parent_fixture();
+  fixture.parent.stop().expect("stop parent");
+
+  assert_eq!(fixture.fixture.received.lock().last(), Some(&RemoteDeploymentPdu::Stop { token: 900 }));
+}
+
+#[test]
+fn stop_request_from_origin_stops_hosted_child() {
+  let fixture = fixture();
+  fixture
+    .daemon
+    .tell(AnyMessageGeneric::new(deploy_request("worker")).with_reply_to(fixture.origin.clone()))
+    .expect("deploy");
+
+  fixture
+    .daemon
+    .tell(AnyMessageGeneric::new(RemoteDeploymentPdu::Stop { token: 7 }).with_reply_to(fixture.origin.clone()))
+    .expect("stop");
+  assert_eq!(*fixture.received.lock(), vec![RemoteDeploymentPdu::ChildTerminated { token: 7 }]);
+}
+
+#[test]
+fn stop_request_from_another_origin_is_ignored() {
+  let fixture = fixture();
+  fixture
+    .daemon
+    .tell(AnyMessageGeneric::new(deploy_request("worker")).with_reply_to(fixture.origin.clone()))
+    .expect("deploy");
+  let stranger = fixture
+    .system
+    .extended()
+    .spawn_system_actor(&PropsGeneric::from_fn(|| NoopActor))
+    .expect("stranger spawns")
+    .actor_ref()
+    .clone();
+
+  fixture
+    .daemon
+    .tell(AnyMessageGeneric::new(RemoteDeploymentPdu::Stop { token: 7 }).with_reply_to(stranger))
+    .expect("stop");
+  assert!(fixture.received.lock().is_empty());
+}
+
+#[test]
+fn hosted_child_is_stopped_when_its_origin_terminates() {
+  let fixture = fixture();
+  fixture
+    .daemon
+    .tell(AnyMessageGeneric::new(deploy_request("worker")).with_reply_to(fixture.origin.clone()))
+    .expect("deploy");
+  let worker = fixture.worker.lock().clone().expect("worker started");
+
+  fixture.origin.tell(AnyMessageGeneric::new("stop")).expect("stop origin");
+
+  assert!(fixture.system.state().actor_path(&worker.pid()).is_none());
+}
+
+const REMOTE_ORIGIN: Pid = Pid::new(901, 0);
+
+/// Reports the path of [`REMOTE_ORIGIN`] the way a remote actor-ref provider would.
+struct RemoteOriginHook;
+
+impl RemoteWatchHook<NoStdToolbox> for RemoteOriginHook {
+  fn handle_watch(&mut self, target: Pid, _watcher: Pid) -> bool {
+    target == REMOTE_ORIGIN
+  }
+
+  fn handle_unwatch(&mut self, target: Pid, _watcher: Pid) -> bool {
+    target == REMOTE_ORIGIN
+  }
+
+  fn remote_path(&self, pid: Pid) -> Option<ActorPath> {
+    let parts = ActorPathParts::with_authority("origin", Some(("10.0.0.7", 2552)));
+    (pid == REMOTE_ORIGIN).then(|| ActorPath::from_parts(parts).child("system").child("remote"))
+  }
+}
+
+#[test]
+fn hosted_child_is_stopped_when_its_origin_is_quarantined() {
+  let fixture = fixture();
+  fixture.system.extended().register_remote_watch_hook(RemoteOriginHook);
+  let origin = ActorRefGeneric::with_system(REMOTE_ORIGIN, ArcShared::new(NullSender), fixture.system.state());
+  fixture.daemon.tell(AnyMessageGeneric::new(deploy_request("worker")).with_reply_to(origin)).expect("deploy");
+  let worker = fixture.worker.lock().clone().expect("worker started");
+
+  let quarantined = |authority: &str| {
+    EventStreamEvent::RemotingLifecycle(RemotingLifecycleEvent::Quarantined {
+      authority:      authority.to_string(),
+      reason:         "test".to_string(),
+      correlation_id: CorrelationId::nil(),
+    })
+  };
+  fixture.system.publish_event(&quarantined("10.0.0.8:2552"));
+  assert!(fixture.system.state().actor_path(&worker.pid()).is_some());
+
+  fixture.system.publish_event(&quarantined("10.0.0.7:2552"));
+  assert!(fixture.system.state().actor_path(&worker.pid()).is_none());
+}
@@ -0,0 +1,161 @@
+//! Protocol messages exchanged between remote deployment daemons.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{string::String, vec::Vec};
+use core::convert::TryInto;
+
+use crate::core::wire_error::WireError;
+
+const VERSION: u8 = 1;
+const KIND_DEPLOY: u8 = 1;
+const KIND_REJECTED: u8 = 2;
+const KIND_CHILD_FAILED: u8 = 3;
+const KIND_CHILD_TERMINATED: u8 = 4;
+const KIND_STOP: u8 = 5;
+
+/// Message exchanged between the `/system/remote` daemons of the deploying and hosting nodes.
+///
+/// Every message carries the token chosen by the deploying node so that replies can be routed
+/// back to the local parent without trusting remote actor names.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RemoteDeploymentPdu {
+  /// Requests the hosting node to spawn a whitelisted factory under its daemon.
+  Deploy {
+    /// Token identifying the deployment on the deploying node.
+    token:      u64,
+    /// Name of the child under the hosting daemon.
+    child_name: String,
+    /// Identifier of the props factory registered on the hosting node.
+    factory_id: String,
+    /// Serialized factory arguments.
+    args:       Vec<u8>,
+  },
+  /// The hosting node refused or failed to spawn the child.
+  Rejected {
+    /// Token identifying the deployment on the deploying node.
+    token:  u64,
+    /// Human readable rejection reason.
+    reason: String,
+  },
+  /// The deployed child failed and is being supervised by the hosting daemon.
+  ChildFailed {
+    /// Token identifying the deployment on the deploying node.
+    token:  u64,
+    /// Human readable failure reason.
+    reason: String,
+  },
+  /// The deployed child stopped.
+  ChildTerminated {
+    /// Token identifying the deployment on the deploying node.
+    token: u64,
+  },
+  /// Requests the hosting node to stop the deployed child because its parent stopped it.
+  Stop {
+    /// Token identifying the deployment on the deploying node.
+    token: u64,
+  },
+}
+
+impl RemoteDeploymentPdu {
+  /// Returns the deployment token.
+  #[must_use]
+  pub const fn token(&self) -> u64 {
+    match self {
+      | Self::Deploy { token, .. }
+      | Self::Rejected { token, .. }
+      | Self::ChildFailed { token, .. }
+      | Self::ChildTerminated { token }
+      | Self::Stop { token } => *token,
+    }
+  }
+
+  /// Encodes the message into its binary representation.
+  #[must_use]
+  pub fn encode(&self) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    buffer.push(VERSION);
+    match self {
+      | Self::Deploy { token, child_name, factory_id, args } => {
+        buffer.push(KIND_DEPLOY);
+        buffer.extend_from_slice(&token.to_le_bytes());
+        write_bytes(&mut buffer, child_name.as_bytes());
+        write_bytes(&mut buffer, factory_id.as_bytes());
+        write_bytes(&mut buffer, args);
+      },
+      | Self::Rejected { token, reason } => {
+        buffer.push(KIND_REJECTED);
+        buffer.extend_from_slice(&token.to_le_bytes());
+        write_bytes(&mut buffer, reason.as_bytes());
+      },
+      | Self::ChildFailed { token, reason } => {
+        buffer.push(KIND_CHILD_FAILED);
+        buffer.extend_from_slice(&token.to_le_bytes());
+        write_bytes(&mut buffer, reason.as_bytes());
+      },
+      | Self::ChildTerminated { token } => {
+        buffer.push(KIND_CHILD_TERMINATED);
+        buffer.extend_from_slice(&token.to_le_bytes());
+      },
+      | Self::Stop { token } => {
+        buffer.push(KIND_STOP);
+        buffer.extend_from_slice(&token.to_le_bytes());
+      },
+    }
+    buffer
+  }
+
+  /// Decodes a message from its binary representation.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError`] when the payload is malformed.
+  pub fn decode(bytes: &[u8]) -> Result<Self, WireError> {
+    if bytes.len() < 10 || bytes[0] != VERSION {
+      return Err(WireError::InvalidFormat);
+    }
+    let token = u64::from_le_bytes(bytes[2..10].try_into().map_err(|_| WireError::InvalidFormat)?);
+    let mut cursor = 10;
+    let pdu = match bytes[1] {
+      | KIND_DEPLOY => {
+        let child_name = read_string(bytes, &mut cursor)?;
+        let factory_id = read_string(bytes, &mut cursor)?;
+        let args = read_bytes(bytes, &mut cursor)?.to_vec();
+        Self::Deploy { token, child_name, factory_id, args }
+      },
+      | KIND_REJECTED => Self::Rejected { token, reason: read_string(bytes, &mut cursor)? },
+      | KIND_CHILD_FAILED => Self::ChildFailed { token, reason: read_string(bytes, &mut cursor)? },
+      | KIND_CHILD_TERMINATED => Self::ChildTerminated { token },
+      | KIND_STOP => Self::Stop { token },
+      | _ => return Err(WireError::InvalidFormat),
+    };
+    if cursor != bytes.len() {
+      return Err(WireError::InvalidFormat);
+    }
+    Ok(pdu)
+  }
+}
+
+fn write_bytes(buffer: &mut Vec<u8>, value: &[u8]) {
+  buffer.extend_from_slice(&(value.len() as u32).to_le_bytes());
+  buffer.extend_from_slice(value);
+}
+
+fn read_bytes<'a>(bytes: &'a [u8], cursor: &mut usize) -> Result<&'a [u8], WireError> {
+  if bytes.len() < *cursor + 4 {
+    return Err(WireError::InvalidFormat);
+  }
+  let len = u32::from_le_bytes(bytes[*cursor..*cursor + 4].try_into().map_err(|_| WireError::InvalidFormat)?) as usize;
+  *cursor += 4;
+  if bytes.len() < *cursor + len {
+    return Err(WireError::InvalidFormat);
+  }
+  let slice = &bytes[*cursor..*cursor + len];
+  *cursor += len;
+  Ok(slice)
+}
+
+fn read_string(bytes: &[u8], cursor: &mut usize) -> Result<String, WireError> {
+  Ok(String::from_utf8(read_bytes(bytes, cursor)?.to_vec())?)
+}
@@ -0,0 +1,48 @@
+use alloc::{string::ToString, vec};
+
+use super::RemoteDeploymentPdu;
+use crate::core::wire_error::WireError;
+
+#[test]
+fn every_variant_round_trips() {
+  let messages = vec![
+    RemoteDeploymentPdu::Deploy {
+      token:      7,
+      child_name: "worker-1".to_string(),
+      factory_id: "batch-worker".to_string(),
+      args:       vec![1, 2, 3],
+    },
+    RemoteDeploymentPdu::Rejected { token: 8, reason: "factory not whitelisted".to_string() },
+    RemoteDeploymentPdu::ChildFailed { token: 9, reason: "boom".to_string() },
+    RemoteDeploymentPdu::ChildTerminated { token: 10 },
+    RemoteDeploymentPdu::Stop { token: 11 },
+  ];
+  for message in messages {
+    let decoded = RemoteDeploymentPdu::decode(&message.encode()).expect("decode");
+    assert_eq!(decoded, message);
+  }
+}
+
+#[test]
+fn token_is_shared_by_all_variants() {
+  assert_eq!(RemoteDeploymentPdu::ChildTerminated { token: 3 }.token(), 3);
+  assert_eq!(RemoteDeploymentPdu::Rejected { token: 4, reason: "no".to_string() }.token(), 4);
+  assert_eq!(RemoteDeploymentPdu::Stop { token: 5 }.token(), 5);
+}
+
+#[test]
+fn decode_rejects_truncated_and_trailing_bytes() {
+  let encoded = RemoteDeploymentPdu::Rejected { token: 1, reason: "denied".to_string() }.encode();
+  assert!(matches!(RemoteDeploymentPdu::decode(&encoded[..encoded.len() - 1]), Err(WireError::InvalidFormat)));
+
+  let mut trailing = RemoteDeploymentPdu::ChildTerminated { token: 1 }.encode();
+  trailing.push(0);
+  assert!(matches!(RemoteDeploymentPdu::decode(&trailing), Err(WireError::InvalidFormat)));
+}
+
+#[test]
+fn decode_rejects_unknown_kind() {
+  let mut encoded = RemoteDeploymentPdu::ChildTerminated { token: 1 }.encode();
+  encoded[1] = 0x7f;
+  assert!(matches!(RemoteDeploymentPdu::decode(&encoded), Err(WireError::InvalidFormat)));
+}
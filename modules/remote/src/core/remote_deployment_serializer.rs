@@ -0,0 +1,84 @@
+//! Serializer carrying remote deployment protocol messages.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+use core::any::{Any, TypeId};
+
+use fraktor_actor_rs::core::serialization::{
+  REMOTE_DEPLOYMENT_ID, SerializationError, SerializationExtensionGeneric, Serializer, SerializerId,
+  SerializerWithStringManifest,
+};
+use fraktor_utils_rs::core::{runtime_toolbox::RuntimeToolbox, sync::ArcShared};
+
+use crate::core::remote_deployment_pdu::RemoteDeploymentPdu;
+
+const MANIFEST: &str = "fraktor.remote.RemoteDeploymentPdu";
+
+/// Encodes [`RemoteDeploymentPdu`] values with their own wire format.
+pub(crate) struct RemoteDeploymentSerializer;
+
+impl RemoteDeploymentSerializer {
+  /// Registers the serializer and its binding with the provided extension.
+  ///
+  /// Registration is idempotent so that every endpoint reader and writer may call it.
+  pub(crate) fn register<TB: RuntimeToolbox + 'static>(serialization: &SerializationExtensionGeneric<TB>) {
+    let serializer: ArcShared<dyn Serializer> = ArcShared::new(Self);
+    let _ = serialization.register_serializer(REMOTE_DEPLOYMENT_ID, serializer);
+    let _ = serialization.register_binding(
+      TypeId::of::<RemoteDeploymentPdu>(),
+      core::any::type_name::<RemoteDeploymentPdu>(),
+      REMOTE_DEPLOYMENT_ID,
+    );
+  }
+}
+
+impl Serializer for RemoteDeploymentSerializer {
+  fn identifier(&self) -> SerializerId {
+    REMOTE_DEPLOYMENT_ID
+  }
+
+  fn include_manifest(&self) -> bool {
+    true
+  }
+
+  fn to_binary(&self, message: &(dyn Any + Send + Sync)) -> Result<Vec<u8>, SerializationError> {
+    let pdu = message.downcast_ref::<RemoteDeploymentPdu>().ok_or(SerializationError::InvalidFormat)?;
+    Ok(pdu.encode())
+  }
+
+  fn from_binary(
+    &self,
+    bytes: &[u8],
+    _type_hint: Option<TypeId>,
+  ) -> Result<Box<dyn Any + Send + Sync>, SerializationError> {
+    let pdu = RemoteDeploymentPdu::decode(bytes).map_err(|_| SerializationError::InvalidFormat)?;
+    Ok(Box::new(pdu))
+  }
+
+  fn as_any(&self) -> &(dyn Any + Send + Sync) {
+    self
+  }
+
+  fn as_string_manifest(&self) -> Option<&dyn SerializerWithStringManifest> {
+    Some(self)
+  }
+}
+
+impl SerializerWithStringManifest for RemoteDeploymentSerializer {
+  fn manifest(&self, _message: &(dyn Any + Send + Sync)) -> Cow<'_, str> {
+    Cow::Borrowed(MANIFEST)
+  }
+
+  fn from_binary_with_manifest(
+    &self,
+    bytes: &[u8],
+    manifest: &str,
+  ) -> Result<Box<dyn Any + Send + Sync>, SerializationError> {
+    if manifest != MANIFEST {
+      return Err(SerializationError::UnknownManifest(manifest.into()));
+    }
+    self.from_binary(bytes, None)
+  }
+}
@@ -0,0 +1,81 @@
+use alloc::{
+  string::{String, ToString},
+  vec,
+};
+
+use fraktor_actor_rs::core::{
+  actor_prim::{Actor, ActorContextGeneric},
+  error::ActorError,
+  messaging::AnyMessageViewGeneric,
+  props::PropsGeneric,
+  scheduler::{ManualTestDriver, TickDriverConfig},
+  serialization::{
+    REMOTE_DEPLOYMENT_ID, SerializationCallScope, SerializationError, SerializationExtensionGeneric,
+    SerializationSetupBuilder, Serializer, SerializerId, SerializerWithStringManifest, StringSerializer,
+  },
+  system::{ActorSystemConfig, ActorSystemGeneric},
+};
+use fraktor_utils_rs::core::{runtime_toolbox::NoStdToolbox, sync::ArcShared};
+
+use super::{MANIFEST, RemoteDeploymentSerializer};
+use crate::core::remote_deployment_pdu::RemoteDeploymentPdu;
+
+struct NoopActor;
+
+impl Actor<NoStdToolbox> for NoopActor {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    _message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    Ok(())
+  }
+}
+
+fn serialization_extension() -> SerializationExtensionGeneric<NoStdToolbox> {
+  let props = PropsGeneric::from_fn(|| NoopActor).with_name("deployment-serializer-tests");
+  let config = ActorSystemConfig::default().with_tick_driver(TickDriverConfig::manual(ManualTestDriver::new()));
+  let system = ActorSystemGeneric::new_with_config(&props, &config).expect("system builds");
+  let serializer_id = SerializerId::try_from(82).expect("serializer id");
+  let serializer: ArcShared<dyn Serializer> = ArcShared::new(StringSerializer::new(serializer_id));
+  let setup = SerializationSetupBuilder::new()
+    .register_serializer("string", serializer_id, serializer)
+    .expect("register serializer")
+    .bind::<String>("string")
+    .expect("bind string")
+    .bind_remote_manifest::<String>("tests.String")
+    .expect("manifest binding")
+    .set_fallback("string")
+    .expect("fallback")
+    .require_manifest_for_scope(SerializationCallScope::Remote)
+    .build()
+    .expect("build setup");
+  SerializationExtensionGeneric::new(&system, setup)
+}
+
+#[test]
+fn registered_serializer_round_trips_pdus_in_remote_scope() {
+  let serialization = serialization_extension();
+  RemoteDeploymentSerializer::register(&serialization);
+  RemoteDeploymentSerializer::register(&serialization);
+
+  let pdu = RemoteDeploymentPdu::Deploy {
+    token:      11,
+    child_name: "worker".to_string(),
+    factory_id: "batch".to_string(),
+    args:       vec![4, 2],
+  };
+  let serialized = serialization.serialize(&pdu, SerializationCallScope::Remote).expect("serialize");
+  assert_eq!(serialized.serializer_id(), REMOTE_DEPLOYMENT_ID);
+  assert_eq!(serialized.manifest(), Some(MANIFEST));
+
+  let decoded = serialization.deserialize(&serialized, None).expect("deserialize");
+  assert_eq!(decoded.downcast_ref::<RemoteDeploymentPdu>(), Some(&pdu));
+}
+
+#[test]
+fn unknown_manifest_is_rejected() {
+  let bytes = RemoteDeploymentPdu::ChildTerminated { token: 1 }.encode();
+  let result = RemoteDeploymentSerializer.from_binary_with_manifest(&bytes, "other");
+  assert!(matches!(result, Err(SerializationError::UnknownManifest(_))));
+}
@@ -0,0 +1,58 @@
+//! Whitelist of props factories that remote nodes may deploy onto this node.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{boxed::Box, collections::BTreeMap, format, string::String};
+
+use fraktor_actor_rs::core::props::PropsGeneric;
+use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
+
+type DeployableFactory<TB> = Box<dyn Fn(&[u8]) -> Result<PropsGeneric<TB>, String> + Send + Sync>;
+
+/// Maps factory identifiers to closures rebuilding props from serialized arguments.
+///
+/// Deployment requests naming an identifier that was never registered are rejected, so only
+/// explicitly whitelisted actors can be spawned by remote nodes.
+pub struct RemoteDeploymentWhitelist<TB: RuntimeToolbox + 'static> {
+  factories: BTreeMap<String, DeployableFactory<TB>>,
+}
+
+impl<TB: RuntimeToolbox + 'static> RemoteDeploymentWhitelist<TB> {
+  /// Creates an empty whitelist.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { factories: BTreeMap::new() }
+  }
+
+  /// Whitelists `factory_id`, replacing any factory previously registered under it.
+  pub fn register<F>(&mut self, factory_id: impl Into<String>, factory: F)
+  where
+    F: Fn(&[u8]) -> Result<PropsGeneric<TB>, String> + Send + Sync + 'static, {
+    self.factories.insert(factory_id.into(), Box::new(factory));
+  }
+
+  /// Returns `true` when `factory_id` may be deployed.
+  #[must_use]
+  pub fn contains(&self, factory_id: &str) -> bool {
+    self.factories.contains_key(factory_id)
+  }
+
+  /// Builds props for a deployment request.
+  ///
+  /// # Errors
+  ///
+  /// Returns a rejection reason when the factory is not whitelisted or refuses the arguments.
+  pub fn props_for(&self, factory_id: &str, args: &[u8]) -> Result<PropsGeneric<TB>, String> {
+    let Some(factory) = self.factories.get(factory_id) else {
+      return Err(format!("factory `{factory_id}` is not whitelisted"));
+    };
+    factory(args)
+  }
+}
+
+impl<TB: RuntimeToolbox + 'static> Default for RemoteDeploymentWhitelist<TB> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,45 @@
+use alloc::string::ToString;
+
+use fraktor_actor_rs::core::{
+  actor_prim::{Actor, ActorContextGeneric},
+  error::ActorError,
+  messaging::AnyMessageViewGeneric,
+  props::PropsGeneric,
+};
+use fraktor_utils_rs::core::runtime_toolbox::NoStdToolbox;
+
+use super::RemoteDeploymentWhitelist;
+
+struct NoopActor;
+
+impl Actor<NoStdToolbox> for NoopActor {
+  fn receive(
+    &mut self,
+    _ctx: &mut ActorContextGeneric<'_, NoStdToolbox>,
+    _message: AnyMessageViewGeneric<'_, NoStdToolbox>,
+  ) -> Result<(), ActorError> {
+    Ok(())
+  }
+}
+
+#[test]
+fn registered_factory_builds_props_from_args() {
+  let mut whitelist = RemoteDeploymentWhitelist::<NoStdToolbox>::new();
+  whitelist.register("worker", |args: &[u8]| {
+    let name = core::str::from_utf8(args).map_err(|_| "invalid name".to_string())?;
+    Ok(PropsGeneric::from_fn(|| NoopActor).with_name(name))
+  });
+
+  assert!(whitelist.contains("worker"));
+  let props = whitelist.props_for("worker", b"w-1").expect("props");
+  assert_eq!(props.name(), Some("w-1"));
+  assert_eq!(whitelist.props_for("worker", &[0xff]).err(), Some("invalid name".to_string()));
+}
+
+#[test]
+fn unknown_factory_is_rejected() {
+  let whitelist = RemoteDeploymentWhitelist::<NoStdToolbox>::default();
+  assert!(!whitelist.contains("worker"));
+  let reason = whitelist.props_for("worker", &[]).err().expect("rejected");
+  assert!(reason.contains("not whitelisted"));
+}
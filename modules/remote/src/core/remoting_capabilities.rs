@@ -0,0 +1,53 @@
+//! Optional protocol features advertised during the remoting handshake.
+
+#[cfg(test)]
+mod tests;
+
+/// Set of optional protocol features a node supports.
+///
+/// Each node advertises its capabilities in the handshake; a feature is only used on an
+/// association when both sides advertise it (see [`intersection`](Self::intersection)).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RemotingCapabilities(u32);
+
+impl RemotingCapabilities {
+  /// Actor path and manifest compression tables in envelope headers.
+  pub const COMPRESSION_TABLES: Self = Self(0b10);
+  /// Envelope frames with a flags byte (version 2), which carry trace contexts, headers and
+  /// compressed payloads; peers without it only understand version 1 frames.
+  pub const ENVELOPE_FLAGS: Self = Self(0b100);
+  /// LZ4 block compression of envelope payloads.
+  pub const LZ4_COMPRESSION: Self = Self(0b1);
+  /// No optional features; also assumed for peers that predate capability negotiation.
+  pub const NONE: Self = Self(0);
+
+  /// Restores capabilities from their wire representation, ignoring unknown bits.
+  #[must_use]
+  pub const fn from_bits(bits: u32) -> Self {
+    Self(bits & (Self::LZ4_COMPRESSION.0 | Self::COMPRESSION_TABLES.0 | Self::ENVELOPE_FLAGS.0))
+  }
+
+  /// Returns the wire representation.
+  #[must_use]
+  pub const fn bits(self) -> u32 {
+    self.0
+  }
+
+  /// Returns `true` when every feature of `other` is contained.
+  #[must_use]
+  pub const fn contains(self, other: Self) -> bool {
+    self.0 & other.0 == other.0
+  }
+
+  /// Returns the features supported by both sides.
+  #[must_use]
+  pub const fn intersection(self, other: Self) -> Self {
+    Self(self.0 & other.0)
+  }
+
+  /// Returns the features supported by either side.
+  #[must_use]
+  pub const fn union(self, other: Self) -> Self {
+    Self(self.0 | other.0)
+  }
+}
@@ -0,0 +1,30 @@
+use super::RemotingCapabilities;
+
+#[test]
+fn intersection_keeps_common_features() {
+  let local = RemotingCapabilities::LZ4_COMPRESSION;
+  assert_eq!(local.intersection(RemotingCapabilities::NONE), RemotingCapabilities::NONE);
+  assert!(local.intersection(local).contains(RemotingCapabilities::LZ4_COMPRESSION));
+}
+
+#[test]
+fn unknown_bits_are_ignored() {
+  let capabilities = RemotingCapabilities::from_bits(u32::MAX);
+  assert_eq!(
+    capabilities,
+    RemotingCapabilities::LZ4_COMPRESSION
+      .union(RemotingCapabilities::COMPRESSION_TABLES)
+      .union(RemotingCapabilities::ENVELOPE_FLAGS)
+  );
+  assert_eq!(capabilities.bits(), 0b111);
+}
+
+#[test]
+fn none_is_contained_everywhere() {
+  assert!(RemotingCapabilities::NONE.contains(RemotingCapabilities::NONE));
+  assert!(!RemotingCapabilities::NONE.contains(RemotingCapabilities::LZ4_COMPRESSION));
+  assert_eq!(
+    RemotingCapabilities::NONE.union(RemotingCapabilities::LZ4_COMPRESSION),
+    RemotingCapabilities::LZ4_COMPRESSION
+  );
+}
@@ -1,6 +1,7 @@
 //! Concrete implementation of [`RemotingControl`] backed by the actor system.
 use alloc::{
   boxed::Box,
+  format,
   string::{String, ToString},
   vec::Vec,
 };
@@ -9,6 +10,8 @@ use core::sync::atomic::{AtomicU64, Ordering};
 use fraktor_actor_rs::core::{
   actor_prim::actor_path::ActorPathParts,
   event_stream::{BackpressureSignal, CorrelationId, RemotingLifecycleEvent},
+  logging::LogLevel,
+  props::PropsGeneric,
   system::ActorSystemGeneric,
 };
 use fraktor_utils_rs::core::{
@@ -19,15 +22,25 @@ use fraktor_utils_rs::core::{
 use crate::core::{
   EndpointWriterShared,
   endpoint_reader::EndpointReaderGeneric,
+  endpoint_writer::EndpointWriterGeneric,
   event_publisher::EventPublisherGeneric,
   flight_recorder::{RemotingFlightRecorder, RemotingFlightRecorderSnapshot},
+  header_serializer_registry::HeaderSerializerRegistry,
+  inbound_filter::InboundFilter,
+  metrics::RemotingMetrics,
+  outbound_queue_policy::OutboundQueuePolicy,
   quarantine_reason::QuarantineReason,
   remote_authority_snapshot::RemoteAuthoritySnapshot,
+  remote_deployment_whitelist::RemoteDeploymentWhitelist,
   remoting_backpressure_listener::RemotingBackpressureListener,
   remoting_control::RemotingControl,
   remoting_error::RemotingError,
   remoting_extension_config::RemotingExtensionConfig,
-  transport::{RemoteTransport, RemoteTransportShared, TransportBackpressureHook, TransportBackpressureHookShared},
+  transport::{
+    RemoteTransport, RemoteTransportShared, TransportBackpressureHook, TransportBackpressureHookShared,
+    TransportLifecycleHook, TransportLifecycleHookShared,
+  },
+  unverified_system_guard::UnverifiedSystemGuard,
 };
 
 /// Shared handle used by endpoints and providers to drive remoting.
@@ -65,18 +78,40 @@ where
       _canonical_port: config.canonical_port(),
       state: <TB::MutexFamily as SyncMutexFamily>::create(RemotingLifecycleState::new()),
       listeners: <TB::MutexFamily as SyncMutexFamily>::create(listeners),
+      inbound_filters: Self::inbound_filters(&config),
+      queue_policy: config.outbound_queue_policy(),
+      header_serializers: config.header_serializers().clone(),
       snapshots: <TB::MutexFamily as SyncMutexFamily>::create(Vec::new()),
       recorder: RemotingFlightRecorder::new(config.flight_recorder_capacity()),
+      metrics: RemotingMetrics::new(),
       correlation_seq: AtomicU64::new(1),
       writer: <TB::MutexFamily as SyncMutexFamily>::create(None),
       reader: <TB::MutexFamily as SyncMutexFamily>::create(None),
       transport_ref: <TB::MutexFamily as SyncMutexFamily>::create(None),
+      deployables: <TB::MutexFamily as SyncMutexFamily>::create(RemoteDeploymentWhitelist::new()),
+      #[cfg(feature = "tokio-transport")]
+      compression: config.compression(),
+      #[cfg(feature = "tokio-transport")]
+      compression_tables: config.compression_tables(),
+      #[cfg(feature = "tokio-transport")]
+      system_buffer_size: config.system_message_buffer_size(),
+      #[cfg(feature = "tokio-transport")]
+      reconnect_backoff: config.reconnect_backoff(),
       #[cfg(feature = "tokio-transport")]
       endpoint_driver: <TB::MutexFamily as SyncMutexFamily>::create(None),
     };
     Self { inner: ArcShared::new(inner) }
   }
 
+  fn inbound_filters(config: &RemotingExtensionConfig) -> Vec<ArcShared<dyn InboundFilter>> {
+    let mut filters: Vec<ArcShared<dyn InboundFilter>> = Vec::new();
+    if !config.unverified_system_access() {
+      filters.push(ArcShared::new(UnverifiedSystemGuard::new()));
+    }
+    filters.extend(config.inbound_filters().iter().cloned());
+    filters
+  }
+
   /// Returns `true` when the handle reports a running remoting subsystem.
   #[must_use]
   pub fn is_running(&self) -> bool {
@@ -110,6 +145,20 @@ where
     let _ = self.inner.try_bootstrap_runtime();
   }
 
+  /// Attaches the configured inbound filters, header serializers and the flight recorder to
+  /// `reader`.
+  pub(crate) fn configure_reader(&self, reader: EndpointReaderGeneric<TB>) -> EndpointReaderGeneric<TB> {
+    let reader = reader
+      .with_flight_recorder(self.inner.recorder.clone())
+      .with_header_serializers(self.inner.header_serializers.clone());
+    self.inner.inbound_filters.iter().cloned().fold(reader, EndpointReaderGeneric::with_inbound_filter)
+  }
+
+  /// Applies the configured outbound queue policy and header serializers to `writer`.
+  pub(crate) fn configure_writer(&self, writer: EndpointWriterGeneric<TB>) -> EndpointWriterGeneric<TB> {
+    writer.with_queue_policy(self.inner.queue_policy).with_header_serializers(self.inner.header_serializers.clone())
+  }
+
   /// Registers endpoint IO components required for transport bridging.
   pub(crate) fn register_endpoint_io(
     &self,
@@ -141,6 +190,11 @@ where
       guard.clone()
     };
     let correlation_id = correlation.unwrap_or_else(|| self.inner.next_correlation_id());
+    // 送信側が速度を落とせるよう、対象 association へのユーザーメッセージの受付を止める
+    let writer = self.inner.writer.lock().clone();
+    if let Some(writer) = writer {
+      writer.lock().handle_backpressure(authority, signal);
+    }
     self.inner.event_publisher.publish_backpressure(authority.to_string(), signal, correlation_id);
     self.inner.record_backpressure(authority, signal, correlation_id);
     for listener in listeners {
@@ -153,16 +207,41 @@ where
     ArcShared::new(NoStdMutex::new(Box::new(ControlBackpressureHook { control: self.clone() })))
   }
 
+  #[allow(dead_code)]
+  pub(crate) fn lifecycle_hook(&self) -> TransportLifecycleHookShared {
+    ArcShared::new(NoStdMutex::new(Box::new(ControlLifecycleHook { control: self.clone() })))
+  }
+
   /// Emits a synthetic backpressure signal for diagnostics.
   pub fn emit_backpressure_signal(&self, authority: &str, signal: BackpressureSignal) {
     self.notify_backpressure(authority, signal, None);
   }
 
+  /// Whitelists a props factory that remote nodes may deploy onto this node.
+  ///
+  /// The factory receives the serialized arguments carried by the deployment request.
+  pub fn register_deployable<F>(&self, factory_id: impl Into<String>, factory: F)
+  where
+    F: Fn(&[u8]) -> Result<PropsGeneric<TB>, String> + Send + Sync + 'static, {
+    self.inner.deployables.lock().register(factory_id, factory);
+  }
+
+  /// Builds props for an inbound deployment request using the whitelist.
+  pub(crate) fn deployable_props(&self, factory_id: &str, args: &[u8]) -> Result<PropsGeneric<TB>, String> {
+    self.inner.deployables.lock().props_for(factory_id, args)
+  }
+
   /// Returns the most recent flight recorder snapshot.
   #[must_use]
   pub fn flight_recorder_snapshot(&self) -> RemotingFlightRecorderSnapshot {
     self.inner.recorder.snapshot()
   }
+
+  /// Returns the per-association metrics registry shared with the endpoint driver.
+  #[must_use]
+  pub fn metrics(&self) -> RemotingMetrics {
+    self.inner.metrics.clone()
+  }
 }
 
 impl<TB> RemotingControl<TB> for RemotingControlHandle<TB>
@@ -212,20 +291,33 @@ where
 struct RemotingControlInner<TB>
 where
   TB: RuntimeToolbox + 'static, {
-  system:          ActorSystemGeneric<TB>,
-  event_publisher: EventPublisherGeneric<TB>,
-  _canonical_host: String,
-  _canonical_port: Option<u16>,
-  state:           ToolboxMutex<RemotingLifecycleState, TB>,
-  listeners:       ToolboxMutex<Vec<ArcShared<dyn RemotingBackpressureListener>>, TB>,
-  snapshots:       ToolboxMutex<Vec<RemoteAuthoritySnapshot>, TB>,
-  recorder:        RemotingFlightRecorder,
-  correlation_seq: AtomicU64,
-  writer:          ToolboxMutex<Option<EndpointWriterShared<TB>>, TB>,
-  reader:          ToolboxMutex<Option<ArcShared<EndpointReaderGeneric<TB>>>, TB>,
-  transport_ref:   ToolboxMutex<Option<RemoteTransportShared<TB>>, TB>,
+  system:             ActorSystemGeneric<TB>,
+  event_publisher:    EventPublisherGeneric<TB>,
+  _canonical_host:    String,
+  _canonical_port:    Option<u16>,
+  state:              ToolboxMutex<RemotingLifecycleState, TB>,
+  listeners:          ToolboxMutex<Vec<ArcShared<dyn RemotingBackpressureListener>>, TB>,
+  inbound_filters:    Vec<ArcShared<dyn InboundFilter>>,
+  queue_policy:       OutboundQueuePolicy,
+  header_serializers: HeaderSerializerRegistry,
+  snapshots:          ToolboxMutex<Vec<RemoteAuthoritySnapshot>, TB>,
+  recorder:           RemotingFlightRecorder,
+  metrics:            RemotingMetrics,
+  correlation_seq:    AtomicU64,
+  writer:             ToolboxMutex<Option<EndpointWriterShared<TB>>, TB>,
+  reader:             ToolboxMutex<Option<ArcShared<EndpointReaderGeneric<TB>>>, TB>,
+  transport_ref:      ToolboxMutex<Option<RemoteTransportShared<TB>>, TB>,
+  deployables:        ToolboxMutex<RemoteDeploymentWhitelist<TB>, TB>,
+  #[cfg(feature = "tokio-transport")]
+  compression:        Option<crate::core::payload_compression::PayloadCompression>,
+  #[cfg(feature = "tokio-transport")]
+  compression_tables: Option<crate::core::compression_table_settings::CompressionTableSettings>,
   #[cfg(feature = "tokio-transport")]
-  endpoint_driver: ToolboxMutex<Option<crate::std::runtime::endpoint_driver::EndpointDriverHandle>, TB>,
+  system_buffer_size: usize,
+  #[cfg(feature = "tokio-transport")]
+  reconnect_backoff:  crate::core::reconnect_backoff::ReconnectBackoff,
+  #[cfg(feature = "tokio-transport")]
+  endpoint_driver:    ToolboxMutex<Option<crate::std::runtime::endpoint_driver::EndpointDriverHandle>, TB>,
 }
 
 impl<TB> RemotingControlInner<TB>
@@ -276,6 +368,12 @@ where
         canonical_host: self._canonical_host.clone(),
         canonical_port: port,
         system_name: self.system.state().system_name(),
+        compression: self.compression,
+        compression_tables: self.compression_tables,
+        flight_recorder: self.recorder.clone(),
+        metrics: self.metrics.clone(),
+        system_buffer_size: self.system_buffer_size,
+        reconnect_backoff: self.reconnect_backoff,
       };
       let handle = crate::std::runtime::endpoint_driver::EndpointDriver::spawn(config)
         .map_err(|error| RemotingError::TransportUnavailable(format!("{error:?}")))?;
@@ -361,3 +459,22 @@ where
     self.control.notify_backpressure(authority, signal, Some(correlation_id));
   }
 }
+
+struct ControlLifecycleHook<TB>
+where
+  TB: RuntimeToolbox + 'static, {
+  control: RemotingControlHandle<TB>,
+}
+
+impl<TB> TransportLifecycleHook for ControlLifecycleHook<TB>
+where
+  TB: RuntimeToolbox + 'static,
+{
+  fn on_handshake_failed(&mut self, authority: &str, reason: &str) {
+    self.control.inner.event_publisher.publish_handshake_failed(authority, reason);
+  }
+
+  fn on_transport_error(&mut self, authority: &str, reason: &str) {
+    self.control.inner.system.emit_log(LogLevel::Warn, format!("transport error at {authority}: {reason}"), None);
+  }
+}
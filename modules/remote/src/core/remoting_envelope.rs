@@ -1,15 +1,42 @@
 //! Serialized outbound frame metadata used by transports.
 
-use alloc::{string::String, vec::Vec};
+#[cfg(test)]
+mod tests;
+
+use alloc::{
+  string::{String, ToString},
+  vec,
+  vec::Vec,
+};
 use core::convert::TryInto;
 
 use fraktor_actor_rs::core::{
   actor_prim::actor_path::{ActorPath, ActorPathParser},
   event_stream::CorrelationId,
+  messaging::TraceContext,
   serialization::SerializedMessage,
 };
 
-use crate::core::{outbound_priority::OutboundPriority, remote_node_id::RemoteNodeId, wire_error::WireError};
+use crate::core::{
+  compression_table::CompressionTable, flight_recorder::CompressionSample, inbound_compression::InboundCompression,
+  outbound_priority::OutboundPriority, payload_compression::PayloadCompression, remote_node_id::RemoteNodeId,
+  remoting_capabilities::RemotingCapabilities, serialized_header::SerializedHeader, wire_error::WireError,
+};
+
+// version 1 はフラグバイトを持たない旧形式で、ENVELOPE_FLAGS を広告しない相手にだけ送る
+const LEGACY_VERSION: u8 = 1;
+const VERSION: u8 = 2;
+// version 3 は圧縮テーブルを受け取った相手にだけ送る
+const TABLE_VERSION: u8 = 3;
+const KIND_MESSAGE: u8 = 0x10;
+const FLAG_COMPRESSED: u8 = 0b1;
+// トレースコンテキストを持つフレームは、宛先情報の直後に traceparent/tracestate を書く
+const FLAG_TRACE_CONTEXT: u8 = 0b10;
+// ヘッダを持つフレームは、トレースコンテキストの直後に件数とキー/値の組を書く
+const FLAG_HEADERS: u8 = 0b100;
+const TAG_ABSENT: u8 = 0;
+const TAG_LITERAL: u8 = 1;
+const TAG_ID: u8 = 2;
 
 /// Fully serialized outbound message ready for transport framing.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -20,6 +47,8 @@ pub struct RemotingEnvelope {
   serialized:     SerializedMessage,
   correlation_id: CorrelationId,
   priority:       OutboundPriority,
+  trace_context:  Option<TraceContext>,
+  headers:        Vec<SerializedHeader>,
 }
 
 impl RemotingEnvelope {
@@ -33,7 +62,31 @@ impl RemotingEnvelope {
     correlation_id: CorrelationId,
     priority: OutboundPriority,
   ) -> Self {
-    Self { recipient, remote_node, reply_to, serialized, correlation_id, priority }
+    Self {
+      recipient,
+      remote_node,
+      reply_to,
+      serialized,
+      correlation_id,
+      priority,
+      trace_context: None,
+      headers: Vec::new(),
+    }
+  }
+
+  /// Attaches the W3C trace context of the sending actor.
+  #[must_use]
+  pub fn with_trace_context(mut self, trace_context: TraceContext) -> Self {
+    self.trace_context = Some(trace_context);
+    self
+  }
+
+  /// Attaches message headers encoded by a
+  /// [`HeaderSerializerRegistry`](crate::core::HeaderSerializerRegistry).
+  #[must_use]
+  pub fn with_headers(mut self, headers: Vec<SerializedHeader>) -> Self {
+    self.headers = headers;
+    self
   }
 
   /// Returns the fully qualified recipient path.
@@ -72,6 +125,18 @@ impl RemotingEnvelope {
     self.priority
   }
 
+  /// Returns the trace context propagated with the message, if any.
+  #[must_use]
+  pub const fn trace_context(&self) -> Option<&TraceContext> {
+    self.trace_context.as_ref()
+  }
+
+  /// Returns the encoded message headers.
+  #[must_use]
+  pub fn headers(&self) -> &[SerializedHeader] {
+    self.headers.as_slice()
+  }
+
   /// Returns `true` when the envelope represents a system message.
   #[must_use]
   pub const fn is_system(&self) -> bool {
@@ -79,59 +144,167 @@ impl RemotingEnvelope {
   }
 
   /// Encodes the envelope into a binary payload consumed by transports.
-  #[must_use]
-  pub fn encode_frame(&self) -> Vec<u8> {
-    const VERSION: u8 = 1;
-    const KIND_MESSAGE: u8 = 0x10;
-    let mut buffer = Vec::new();
-    buffer.push(VERSION);
-    buffer.push(KIND_MESSAGE);
-    buffer.push(self.priority.to_wire());
-    write_string(&mut buffer, &self.recipient.to_canonical_uri());
-    if let Some(reply_to) = self.reply_to.as_ref() {
-      buffer.push(1);
-      write_string(&mut buffer, &reply_to.to_canonical_uri());
-    } else {
-      buffer.push(0);
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::FieldTooLarge`] when a length or count does not fit its wire field.
+  pub fn encode_frame(&self) -> Result<Vec<u8>, WireError> {
+    Ok(self.encode_frame_with(None)?.0)
+  }
+
+  /// Encodes the envelope in the newest frame version the peer advertised in `capabilities`.
+  ///
+  /// Peers without [`RemotingCapabilities::ENVELOPE_FLAGS`] receive a version 1 frame, which
+  /// cannot carry the trace context, headers or a compressed payload, so those are left out.
+  /// Otherwise `table` is used when the peer supports compression tables and `compression` when
+  /// it supports LZ4.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::FieldTooLarge`] when a length or count does not fit its wire field.
+  pub fn encode_frame_for(
+    &self,
+    capabilities: RemotingCapabilities,
+    compression: Option<&PayloadCompression>,
+    table: Option<&CompressionTable>,
+  ) -> Result<(Vec<u8>, Option<CompressionSample>), WireError> {
+    if !capabilities.contains(RemotingCapabilities::ENVELOPE_FLAGS) {
+      return Ok((self.encode_legacy_frame()?, None));
     }
-    write_string(&mut buffer, self.remote_node.system());
-    write_string(&mut buffer, self.remote_node.host());
-    if let Some(port) = self.remote_node.port() {
-      buffer.push(1);
-      buffer.extend_from_slice(&port.to_le_bytes());
-    } else {
-      buffer.push(0);
+    let compression = compression.filter(|_| capabilities.contains(RemotingCapabilities::LZ4_COMPRESSION));
+    match table.filter(|_| capabilities.contains(RemotingCapabilities::COMPRESSION_TABLES)) {
+      | Some(table) => self.encode_frame_with_table(compression, table),
+      | None => self.encode_frame_with(compression),
     }
-    buffer.extend_from_slice(&self.remote_node.uid().to_le_bytes());
+  }
+
+  /// Encodes the envelope as a version 1 frame understood by peers predating the flags byte.
+  ///
+  /// The trace context and headers are not part of this format and are dropped.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::FieldTooLarge`] when a length or count does not fit its wire field.
+  pub fn encode_legacy_frame(&self) -> Result<Vec<u8>, WireError> {
+    let mut buffer = vec![LEGACY_VERSION, KIND_MESSAGE, self.priority.to_wire()];
+    self.write_paths(&mut buffer)?;
+    self.write_tail(&mut buffer, &self.serialized.encode())?;
+    Ok(buffer)
+  }
+
+  /// Encodes the envelope, compressing the serialized payload when `compression` applies.
+  ///
+  /// Returns the frame together with the payload sizes when compression was used; the envelope
+  /// header carries a flag so the receiver knows whether to decompress.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::FieldTooLarge`] when a length or count does not fit its wire field.
+  pub fn encode_frame_with(
+    &self,
+    compression: Option<&PayloadCompression>,
+  ) -> Result<(Vec<u8>, Option<CompressionSample>), WireError> {
     let serialized = self.serialized.encode();
-    buffer.extend_from_slice(&(serialized.len() as u32).to_le_bytes());
-    buffer.extend_from_slice(&serialized);
-    buffer
+    let (payload, sample) = Self::compress_payload(serialized, compression);
+    let mut buffer = vec![VERSION, KIND_MESSAGE, self.priority.to_wire(), self.flags(sample.as_ref())];
+    self.write_paths(&mut buffer)?;
+    self.write_trace_context(&mut buffer)?;
+    self.write_headers(&mut buffer)?;
+    self.write_tail(&mut buffer, &payload)?;
+    Ok((buffer, sample))
   }
 
-  /// Restores an envelope from a binary payload.
+  /// Encodes the envelope, replacing actor paths and the manifest found in `table` with their
+  /// ids.
+  ///
+  /// Values missing from the table are written in full. The manifest is moved from the
+  /// serialized payload into the header so that it can be compressed as well.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::FieldTooLarge`] when a length or count does not fit its wire field.
+  pub fn encode_frame_with_table(
+    &self,
+    compression: Option<&PayloadCompression>,
+    table: &CompressionTable,
+  ) -> Result<(Vec<u8>, Option<CompressionSample>), WireError> {
+    let stripped = SerializedMessage::new(self.serialized.serializer_id(), None, self.serialized.bytes().to_vec());
+    let (payload, sample) = Self::compress_payload(stripped.encode(), compression);
+    let mut buffer = vec![TABLE_VERSION, KIND_MESSAGE, self.priority.to_wire(), self.flags(sample.as_ref())];
+    buffer.extend_from_slice(&table.version().to_le_bytes());
+    let recipient = self.recipient.to_canonical_uri();
+    write_coded(&mut buffer, &recipient, table.actor_ref_id(&recipient))?;
+    match self.reply_to.as_ref() {
+      | Some(reply_to) => {
+        let reply_to = reply_to.to_canonical_uri();
+        write_coded(&mut buffer, &reply_to, table.actor_ref_id(&reply_to))?;
+      },
+      | None => buffer.push(TAG_ABSENT),
+    }
+    match self.serialized.manifest() {
+      | Some(manifest) => write_coded(&mut buffer, manifest, table.manifest_id(manifest))?,
+      | None => buffer.push(TAG_ABSENT),
+    }
+    self.write_trace_context(&mut buffer)?;
+    self.write_headers(&mut buffer)?;
+    self.write_tail(&mut buffer, &payload)?;
+    Ok((buffer, sample))
+  }
+
+  /// Restores an envelope from a binary payload, decompressing it when flagged.
   ///
   /// # Errors
   ///
   /// Returns [`WireError`] when the payload is malformed.
   pub fn decode_frame(bytes: &[u8], correlation_id: CorrelationId) -> Result<Self, WireError> {
-    const VERSION: u8 = 1;
-    const KIND_MESSAGE: u8 = 0x10;
+    Self::decode_frame_with_tables(bytes, correlation_id, None)
+  }
+
+  /// Restores an envelope whose header may refer to entries of the compression tables this node
+  /// advertised to the sender.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::UnknownCompressionEntry`] when the frame refers to a table version or
+  /// id that is not known, and other [`WireError`] variants when the payload is malformed.
+  pub fn decode_frame_with_tables(
+    bytes: &[u8],
+    correlation_id: CorrelationId,
+    tables: Option<&InboundCompression>,
+  ) -> Result<Self, WireError> {
     if bytes.len() < 3 {
       return Err(WireError::InvalidFormat);
     }
-    if bytes[0] != VERSION || bytes[1] != KIND_MESSAGE {
+    if !matches!(bytes[0], LEGACY_VERSION | VERSION | TABLE_VERSION) || bytes[1] != KIND_MESSAGE {
       return Err(WireError::InvalidFormat);
     }
     let priority = OutboundPriority::from_wire(bytes[2]).ok_or(WireError::InvalidFormat)?;
     let mut cursor = 3;
-    let recipient = ActorPathParser::parse(&read_string(bytes, &mut cursor)?)?;
-
-    let reply_to = if read_bool(bytes, &mut cursor)? {
-      Some(ActorPathParser::parse(&read_string(bytes, &mut cursor)?)?)
+    let flags = if bytes[0] == LEGACY_VERSION {
+      0
     } else {
-      None
+      let flags = *bytes.get(cursor).ok_or(WireError::InvalidFormat)?;
+      cursor += 1;
+      flags
     };
+    let (recipient, reply_to, manifest) = if bytes[0] == TABLE_VERSION {
+      let version = read_u32(bytes, &mut cursor)?;
+      let table = tables.and_then(|tables| tables.table(version));
+      let recipient = read_coded(bytes, &mut cursor, |id| table.and_then(|table| table.actor_ref(id)))?
+        .ok_or(WireError::InvalidFormat)?;
+      let reply_to = read_coded(bytes, &mut cursor, |id| table.and_then(|table| table.actor_ref(id)))?;
+      let manifest = read_coded(bytes, &mut cursor, |id| table.and_then(|table| table.manifest(id)))?;
+      (recipient, reply_to, manifest)
+    } else {
+      let recipient = read_string(bytes, &mut cursor)?;
+      let reply_to = if read_bool(bytes, &mut cursor)? { Some(read_string(bytes, &mut cursor)?) } else { None };
+      (recipient, reply_to, None)
+    };
+    let recipient = ActorPathParser::parse(&recipient)?;
+    let reply_to = reply_to.map(|reply_to| ActorPathParser::parse(&reply_to)).transpose()?;
+    let trace_context =
+      if flags & FLAG_TRACE_CONTEXT != 0 { Some(read_trace_context(bytes, &mut cursor)?) } else { None };
+    let headers = if flags & FLAG_HEADERS != 0 { read_headers(bytes, &mut cursor)? } else { Vec::new() };
 
     let system_name = read_string(bytes, &mut cursor)?;
     let host = read_string(bytes, &mut cursor)?;
@@ -150,26 +323,194 @@ impl RemotingEnvelope {
     }
     let uid = u64::from_le_bytes(bytes[cursor..cursor + 8].try_into().map_err(|_| WireError::InvalidFormat)?);
     cursor += 8;
-    if bytes.len() < cursor + 4 {
-      return Err(WireError::InvalidFormat);
-    }
-    let payload_len =
-      u32::from_le_bytes(bytes[cursor..cursor + 4].try_into().map_err(|_| WireError::InvalidFormat)?) as usize;
-    cursor += 4;
+    let payload_len = read_u32(bytes, &mut cursor)? as usize;
     if bytes.len() < cursor + payload_len {
       return Err(WireError::InvalidFormat);
     }
     let payload = &bytes[cursor..cursor + payload_len];
-    let serialized = SerializedMessage::decode(payload)?;
+    let mut serialized = if flags & FLAG_COMPRESSED != 0 {
+      SerializedMessage::decode(&PayloadCompression::decompress(payload)?)?
+    } else {
+      SerializedMessage::decode(payload)?
+    };
+    if manifest.is_some() {
+      serialized = SerializedMessage::new(serialized.serializer_id(), manifest, serialized.bytes().to_vec());
+    }
     let remote_node = RemoteNodeId::new(system_name, host, port, uid);
-    Ok(Self::new(recipient, remote_node, reply_to, serialized, correlation_id, priority))
+    let envelope =
+      Self::new(recipient, remote_node, reply_to, serialized, correlation_id, priority).with_headers(headers);
+    Ok(match trace_context {
+      | Some(trace_context) => envelope.with_trace_context(trace_context),
+      | None => envelope,
+    })
+  }
+
+  fn compress_payload(
+    serialized: Vec<u8>,
+    compression: Option<&PayloadCompression>,
+  ) -> (Vec<u8>, Option<CompressionSample>) {
+    match compression.and_then(|compression| compression.compress(&serialized)) {
+      | Some(compressed) => {
+        let sample = CompressionSample::new(serialized.len(), compressed.len());
+        (compressed, Some(sample))
+      },
+      | None => (serialized, None),
+    }
   }
+
+  fn flags(&self, sample: Option<&CompressionSample>) -> u8 {
+    let mut flags = 0;
+    if sample.is_some() {
+      flags |= FLAG_COMPRESSED;
+    }
+    if self.trace_context.is_some() {
+      flags |= FLAG_TRACE_CONTEXT;
+    }
+    if !self.headers.is_empty() {
+      flags |= FLAG_HEADERS;
+    }
+    flags
+  }
+
+  fn write_paths(&self, buffer: &mut Vec<u8>) -> Result<(), WireError> {
+    write_string(buffer, &self.recipient.to_canonical_uri())?;
+    if let Some(reply_to) = self.reply_to.as_ref() {
+      buffer.push(1);
+      write_string(buffer, &reply_to.to_canonical_uri())?;
+    } else {
+      buffer.push(0);
+    }
+    Ok(())
+  }
+
+  fn write_trace_context(&self, buffer: &mut Vec<u8>) -> Result<(), WireError> {
+    let Some(trace_context) = self.trace_context.as_ref() else {
+      return Ok(());
+    };
+    write_string(buffer, &trace_context.to_traceparent())?;
+    match trace_context.trace_state() {
+      | Some(trace_state) => {
+        buffer.push(1);
+        write_string(buffer, trace_state)?;
+      },
+      | None => buffer.push(0),
+    }
+    Ok(())
+  }
+
+  fn write_headers(&self, buffer: &mut Vec<u8>) -> Result<(), WireError> {
+    if self.headers.is_empty() {
+      return Ok(());
+    }
+    let count = u16::try_from(self.headers.len())
+      .map_err(|_| WireError::FieldTooLarge { size: self.headers.len(), max: usize::from(u16::MAX) })?;
+    buffer.extend_from_slice(&count.to_le_bytes());
+    for header in &self.headers {
+      write_string(buffer, header.key())?;
+      buffer.push(u8::from(header.propagate()));
+      write_bytes(buffer, header.bytes())?;
+    }
+    Ok(())
+  }
+
+  fn write_tail(&self, buffer: &mut Vec<u8>, payload: &[u8]) -> Result<(), WireError> {
+    write_string(buffer, self.remote_node.system())?;
+    write_string(buffer, self.remote_node.host())?;
+    if let Some(port) = self.remote_node.port() {
+      buffer.push(1);
+      buffer.extend_from_slice(&port.to_le_bytes());
+    } else {
+      buffer.push(0);
+    }
+    buffer.extend_from_slice(&self.remote_node.uid().to_le_bytes());
+    write_bytes(buffer, payload)
+  }
+}
+
+fn write_coded(buffer: &mut Vec<u8>, value: &str, id: Option<u16>) -> Result<(), WireError> {
+  match id {
+    | Some(id) => {
+      buffer.push(TAG_ID);
+      buffer.extend_from_slice(&id.to_le_bytes());
+    },
+    | None => {
+      buffer.push(TAG_LITERAL);
+      write_string(buffer, value)?;
+    },
+  }
+  Ok(())
+}
+
+fn read_coded<'a, F>(bytes: &[u8], cursor: &mut usize, lookup: F) -> Result<Option<String>, WireError>
+where
+  F: Fn(u16) -> Option<&'a str>, {
+  let tag = *bytes.get(*cursor).ok_or(WireError::InvalidFormat)?;
+  *cursor += 1;
+  match tag {
+    | TAG_ABSENT => Ok(None),
+    | TAG_LITERAL => read_string(bytes, cursor).map(Some),
+    | TAG_ID => {
+      if bytes.len() < *cursor + 2 {
+        return Err(WireError::InvalidFormat);
+      }
+      let id = u16::from_le_bytes(bytes[*cursor..*cursor + 2].try_into().map_err(|_| WireError::InvalidFormat)?);
+      *cursor += 2;
+      lookup(id).map(|value| Some(value.to_string())).ok_or(WireError::UnknownCompressionEntry)
+    },
+    | _ => Err(WireError::InvalidFormat),
+  }
+}
+
+fn read_trace_context(bytes: &[u8], cursor: &mut usize) -> Result<TraceContext, WireError> {
+  let traceparent = read_string(bytes, cursor)?;
+  let trace_context = TraceContext::parse_traceparent(&traceparent).map_err(|_| WireError::InvalidFormat)?;
+  Ok(
+    if read_bool(bytes, cursor)? { trace_context.with_trace_state(read_string(bytes, cursor)?) } else { trace_context },
+  )
+}
+
+fn read_headers(bytes: &[u8], cursor: &mut usize) -> Result<Vec<SerializedHeader>, WireError> {
+  if bytes.len() < *cursor + 2 {
+    return Err(WireError::InvalidFormat);
+  }
+  let count = u16::from_le_bytes(bytes[*cursor..*cursor + 2].try_into().map_err(|_| WireError::InvalidFormat)?);
+  *cursor += 2;
+  let mut headers = Vec::with_capacity(usize::from(count));
+  for _ in 0..count {
+    let key = read_string(bytes, cursor)?;
+    let propagate = read_bool(bytes, cursor)?;
+    let len = read_u32(bytes, cursor)? as usize;
+    if bytes.len() < *cursor + len {
+      return Err(WireError::InvalidFormat);
+    }
+    headers.push(SerializedHeader::new(key, propagate, bytes[*cursor..*cursor + len].to_vec()));
+    *cursor += len;
+  }
+  Ok(headers)
+}
+
+fn read_u32(bytes: &[u8], cursor: &mut usize) -> Result<u32, WireError> {
+  if bytes.len() < *cursor + 4 {
+    return Err(WireError::InvalidFormat);
+  }
+  let value = u32::from_le_bytes(bytes[*cursor..*cursor + 4].try_into().map_err(|_| WireError::InvalidFormat)?);
+  *cursor += 4;
+  Ok(value)
+}
+
+fn write_string(buffer: &mut Vec<u8>, value: &str) -> Result<(), WireError> {
+  write_bytes(buffer, value.as_bytes())
 }
 
-fn write_string(buffer: &mut Vec<u8>, value: &str) {
-  let bytes = value.as_bytes();
-  buffer.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+// 長さは u32 の領域に書くため、収まらない値は切り詰めずにエラーとする
+fn write_bytes(buffer: &mut Vec<u8>, bytes: &[u8]) -> Result<(), WireError> {
+  let len = u32::try_from(bytes.len()).map_err(|_| WireError::FieldTooLarge {
+    size: bytes.len(),
+    max:  usize::try_from(u32::MAX).unwrap_or(usize::MAX),
+  })?;
+  buffer.extend_from_slice(&len.to_le_bytes());
   buffer.extend_from_slice(bytes);
+  Ok(())
 }
 
 fn read_string(bytes: &[u8], cursor: &mut usize) -> Result<String, WireError> {
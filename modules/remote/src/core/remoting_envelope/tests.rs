@@ -0,0 +1,239 @@
+use alloc::{
+  string::{String, ToString},
+  vec,
+  vec::Vec,
+};
+
+use fraktor_actor_rs::core::{
+  actor_prim::actor_path::{ActorPath, ActorPathParts, GuardianKind},
+  event_stream::CorrelationId,
+  messaging::TraceContext,
+  serialization::{SerializedMessage, SerializerId},
+};
+
+use super::RemotingEnvelope;
+use crate::core::{
+  compression_table::CompressionTable, compression_table_settings::CompressionTableSettings,
+  inbound_compression::InboundCompression, outbound_priority::OutboundPriority,
+  payload_compression::PayloadCompression, remote_node_id::RemoteNodeId, remoting_capabilities::RemotingCapabilities,
+  serialized_header::SerializedHeader, wire_error::WireError,
+};
+
+fn envelope(payload: Vec<u8>) -> RemotingEnvelope {
+  let recipient = ActorPath::from_parts(ActorPathParts::local("remote-app").with_guardian(GuardianKind::User))
+    .child("user")
+    .child("svc");
+  let serialized = SerializedMessage::new(SerializerId::try_from(41).expect("id"), None, payload);
+  RemotingEnvelope::new(
+    recipient,
+    RemoteNodeId::new("remote-system", "127.0.0.1", Some(4100), 777),
+    None,
+    serialized,
+    CorrelationId::from_u128(9),
+    OutboundPriority::User,
+  )
+}
+
+fn json_like(len: usize) -> Vec<u8> {
+  br#"{"id":1,"status":"ok","items":[1,2,3]},"#.iter().copied().cycle().take(len).collect()
+}
+
+#[test]
+fn uncompressed_frame_round_trips() {
+  let original = envelope(json_like(64));
+  let (frame, sample) = original.encode_frame_with(Some(&PayloadCompression::new())).expect("encode");
+
+  assert!(sample.is_none());
+  assert_eq!(RemotingEnvelope::decode_frame(&frame, CorrelationId::from_u128(9)).expect("decode"), original);
+}
+
+#[test]
+fn compressed_frame_is_flagged_and_round_trips() {
+  let original = envelope(json_like(8192));
+  let (frame, sample) = original.encode_frame_with(Some(&PayloadCompression::new())).expect("encode");
+  let sample = sample.expect("compressed");
+
+  assert!(sample.compressed_bytes() < sample.original_bytes());
+  assert!(frame.len() < original.encode_frame().expect("encode").len());
+  assert_eq!(RemotingEnvelope::decode_frame(&frame, CorrelationId::from_u128(9)).expect("decode"), original);
+}
+
+#[test]
+fn legacy_frames_without_flags_are_accepted() {
+  let original = envelope(json_like(32));
+  let frame = original.encode_frame().expect("encode");
+  let mut legacy = Vec::with_capacity(frame.len() - 1);
+  legacy.push(1);
+  legacy.extend_from_slice(&frame[1..3]);
+  legacy.extend_from_slice(&frame[4..]);
+
+  assert_eq!(RemotingEnvelope::decode_frame(&legacy, CorrelationId::from_u128(9)).expect("decode"), original);
+}
+
+fn with_manifest(envelope: RemotingEnvelope, manifest: &str) -> RemotingEnvelope {
+  let serialized = envelope.serialized_message();
+  let serialized =
+    SerializedMessage::new(serialized.serializer_id(), Some(manifest.to_string()), serialized.bytes().to_vec());
+  RemotingEnvelope::new(
+    envelope.recipient().clone(),
+    envelope.remote_node().clone(),
+    envelope.reply_to().cloned(),
+    serialized,
+    envelope.correlation_id(),
+    envelope.priority(),
+  )
+}
+
+fn advertised(original: &RemotingEnvelope) -> (InboundCompression, CompressionTable) {
+  let mut inbound = InboundCompression::new(&CompressionTableSettings::new());
+  let recipient = original.recipient().to_canonical_uri();
+  for _ in 0..2 {
+    inbound.record(&recipient, None, original.serialized_message().manifest());
+  }
+  let table = inbound.next_advertisement().expect("advertisement");
+  (inbound, table)
+}
+
+#[test]
+fn table_encoded_frame_is_smaller_and_round_trips() {
+  let original = with_manifest(envelope(json_like(16)), "app.protocol.SomeRatherLongManifestName");
+  let (inbound, table) = advertised(&original);
+
+  let (frame, _) = original.encode_frame_with_table(None, &table).expect("encode");
+
+  assert!(frame.len() < original.encode_frame().expect("encode").len());
+  let decoded =
+    RemotingEnvelope::decode_frame_with_tables(&frame, CorrelationId::from_u128(9), Some(&inbound)).expect("decode");
+  assert_eq!(decoded, original);
+}
+
+#[test]
+fn values_missing_from_table_fall_back_to_full_strings() {
+  let original = with_manifest(envelope(json_like(16)), "app.Ping");
+  let table = CompressionTable::new(1, vec![String::from("fraktor://other/user/x")], Vec::new());
+
+  let (frame, _) = original.encode_frame_with_table(None, &table).expect("encode");
+
+  assert_eq!(RemotingEnvelope::decode_frame(&frame, CorrelationId::from_u128(9)).expect("decode"), original);
+}
+
+#[test]
+fn unknown_table_version_is_reported() {
+  let original = with_manifest(envelope(json_like(16)), "app.Ping");
+  let (_, table) = advertised(&original);
+  let (frame, _) = original.encode_frame_with_table(Some(&PayloadCompression::new()), &table).expect("encode");
+
+  let result = RemotingEnvelope::decode_frame(&frame, CorrelationId::from_u128(9));
+  assert!(matches!(result, Err(WireError::UnknownCompressionEntry)));
+}
+
+#[test]
+fn trace_context_round_trips_in_both_frame_formats() {
+  let trace_context = TraceContext::new([4; 16], [5; 8], TraceContext::FLAG_SAMPLED).with_trace_state("vendor=abc");
+  let original = with_manifest(envelope(json_like(16)), "app.Ping").with_trace_context(trace_context.clone());
+
+  let decoded = RemotingEnvelope::decode_frame(&original.encode_frame().expect("encode"), CorrelationId::from_u128(9))
+    .expect("decode");
+  assert_eq!(decoded.trace_context(), Some(&trace_context));
+  assert_eq!(decoded, original);
+
+  let (inbound, table) = advertised(&original);
+  let (frame, _) = original.encode_frame_with_table(Some(&PayloadCompression::new()), &table).expect("encode");
+  let decoded =
+    RemotingEnvelope::decode_frame_with_tables(&frame, CorrelationId::from_u128(9), Some(&inbound)).expect("decode");
+  assert_eq!(decoded.trace_context(), Some(&trace_context));
+}
+
+#[test]
+fn header_count_beyond_the_wire_field_is_an_error() {
+  let headers = vec![SerializedHeader::new("tenant", true, Vec::new()); usize::from(u16::MAX) + 1];
+  let original = envelope(json_like(16)).with_headers(headers);
+
+  let result = original.encode_frame();
+  assert!(matches!(result, Err(WireError::FieldTooLarge { size: 65_536, max: 65_535 })));
+}
+
+#[test]
+fn headers_round_trip_after_the_trace_context() {
+  let headers = vec![
+    SerializedHeader::new("tenant", true, b"acme".to_vec()),
+    SerializedHeader::new("priority", false, Vec::from([3_u8])),
+  ];
+  let original = envelope(json_like(16))
+    .with_trace_context(TraceContext::new([4; 16], [5; 8], TraceContext::FLAG_SAMPLED))
+    .with_headers(headers.clone());
+
+  let decoded = RemotingEnvelope::decode_frame(&original.encode_frame().expect("encode"), CorrelationId::from_u128(9))
+    .expect("decode");
+  assert_eq!(decoded.headers(), headers.as_slice());
+  assert_eq!(decoded, original);
+}
+
+/// Parses a frame the way a peer that only knows version 1 does: no flags byte, no trace
+/// context and no headers between the paths and the origin node.
+fn decode_as_v1_peer(frame: &[u8]) -> Option<(String, Vec<u8>)> {
+  fn read_string(frame: &[u8], cursor: &mut usize) -> Option<String> {
+    let len = u32::from_le_bytes(frame.get(*cursor..*cursor + 4)?.try_into().ok()?) as usize;
+    let value = String::from_utf8(frame.get(*cursor + 4..*cursor + 4 + len)?.to_vec()).ok()?;
+    *cursor += 4 + len;
+    Some(value)
+  }
+  if frame.first() != Some(&1) || frame.get(1) != Some(&0x10) {
+    return None;
+  }
+  let mut cursor = 3;
+  let recipient = read_string(frame, &mut cursor)?;
+  if frame[cursor] == 1 {
+    cursor += 1;
+    read_string(frame, &mut cursor)?;
+  } else {
+    cursor += 1;
+  }
+  read_string(frame, &mut cursor)?;
+  read_string(frame, &mut cursor)?;
+  cursor += if frame[cursor] == 1 { 3 } else { 1 };
+  cursor += 8;
+  let len = u32::from_le_bytes(frame.get(cursor..cursor + 4)?.try_into().ok()?) as usize;
+  let payload = frame.get(cursor + 4..cursor + 4 + len)?.to_vec();
+  (cursor + 4 + len == frame.len()).then_some((recipient, payload))
+}
+
+#[test]
+fn peers_without_envelope_flags_receive_version_1_frames() {
+  let original = envelope(json_like(8192))
+    .with_trace_context(TraceContext::new([4; 16], [5; 8], TraceContext::FLAG_SAMPLED))
+    .with_headers(vec![SerializedHeader::new("tenant", true, b"acme".to_vec())]);
+  let (_, table) = advertised(&original);
+
+  let (frame, sample) = original
+    .encode_frame_for(RemotingCapabilities::NONE, Some(&PayloadCompression::new()), Some(&table))
+    .expect("encode");
+
+  assert!(sample.is_none());
+  let (recipient, payload) = decode_as_v1_peer(&frame).expect("v1 peer parses the frame");
+  assert_eq!(recipient, original.recipient().to_canonical_uri());
+  assert_eq!(payload, original.serialized_message().encode());
+  let decoded = RemotingEnvelope::decode_frame(&frame, CorrelationId::from_u128(9)).expect("decode");
+  assert_eq!(decoded, envelope(json_like(8192)));
+}
+
+#[test]
+fn negotiated_capabilities_select_the_frame_version() {
+  let original = envelope(json_like(8192));
+  let (_, table) = advertised(&original);
+  let compression = PayloadCompression::new();
+
+  let (frame, sample) =
+    original.encode_frame_for(RemotingCapabilities::ENVELOPE_FLAGS, Some(&compression), Some(&table)).expect("encode");
+  assert_eq!(frame[0], 2);
+  assert!(sample.is_none());
+
+  let flags_and_lz4 = RemotingCapabilities::ENVELOPE_FLAGS.union(RemotingCapabilities::LZ4_COMPRESSION);
+  let (frame, sample) = original.encode_frame_for(flags_and_lz4, Some(&compression), Some(&table)).expect("encode");
+  assert_eq!(frame[0], 2);
+  assert!(sample.is_some());
+
+  let all = flags_and_lz4.union(RemotingCapabilities::COMPRESSION_TABLES);
+  let (frame, _) = original.encode_frame_for(all, Some(&compression), Some(&table)).expect("encode");
+  assert_eq!(frame[0], 3);
+}
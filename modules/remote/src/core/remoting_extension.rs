@@ -77,6 +77,7 @@ impl RemotingExtensionGeneric<StdToolbox> {
     let control = RemotingControlHandle::new(system.clone(), config.clone());
     let mut transport = TransportFactory::build(config)?;
     transport.install_backpressure_hook(control.backpressure_hook());
+    transport.install_lifecycle_hook(control.lifecycle_hook());
     let shared_transport: RemoteTransportShared<StdToolbox> = RemoteTransportShared::new(transport);
     control.register_remote_transport_shared(shared_transport.clone());
     let guardian = system.system_guardian_ref().ok_or(RemotingError::SystemGuardianUnavailable)?;
@@ -4,13 +4,23 @@ use alloc::{
   string::{String, ToString},
   vec::Vec,
 };
+use core::{
+  any::{Any, TypeId},
+  time::Duration,
+};
 
+use ahash::RandomState;
 use fraktor_actor_rs::core::event_stream::{BackpressureSignal, CorrelationId};
 use fraktor_utils_rs::core::sync::ArcShared;
+use hashbrown::HashMap;
 
 use crate::core::{
+  compression_table_settings::CompressionTableSettings,
   fn_remoting_backpressure_listener::FnRemotingBackpressureListener,
-  remoting_backpressure_listener::RemotingBackpressureListener,
+  header_serializer_registry::HeaderSerializerRegistry, inbound_filter::InboundFilter,
+  outbound_queue_policy::OutboundQueuePolicy, payload_compression::PayloadCompression,
+  reconnect_backoff::ReconnectBackoff, remoting_backpressure_listener::RemotingBackpressureListener,
+  transport::TokioTransportConfig,
 };
 
 /// Declarative configuration applied when the remoting extension is installed.
@@ -21,7 +31,18 @@ pub struct RemotingExtensionConfig {
   auto_start:               bool,
   transport_scheme:         String,
   backpressure_listeners:   Vec<ArcShared<dyn RemotingBackpressureListener>>,
+  inbound_filters:          Vec<ArcShared<dyn InboundFilter>>,
+  unverified_system_access: bool,
   flight_recorder_capacity: usize,
+  tokio_transport:          TokioTransportConfig,
+  compression:              Option<PayloadCompression>,
+  compression_tables:       Option<CompressionTableSettings>,
+  system_buffer_size:       usize,
+  reconnect_backoff:        ReconnectBackoff,
+  outbound_queue_policy:    OutboundQueuePolicy,
+  header_serializers:       HeaderSerializerRegistry,
+  unix_socket_dir:          Option<String>,
+  transport_extensions:     HashMap<TypeId, ArcShared<dyn Any + Send + Sync>, RandomState>,
 }
 
 impl RemotingExtensionConfig {
@@ -35,7 +56,18 @@ impl RemotingExtensionConfig {
       auto_start:               true,
       transport_scheme:         "fraktor.loopback".to_string(),
       backpressure_listeners:   Vec::new(),
+      inbound_filters:          Vec::new(),
+      unverified_system_access: false,
       flight_recorder_capacity: 128,
+      tokio_transport:          TokioTransportConfig::new(),
+      compression:              None,
+      compression_tables:       None,
+      system_buffer_size:       20_000,
+      reconnect_backoff:        ReconnectBackoff::new(Duration::from_millis(200), Duration::from_secs(10)),
+      outbound_queue_policy:    OutboundQueuePolicy::unbounded(),
+      header_serializers:       HeaderSerializerRegistry::new(),
+      unix_socket_dir:          None,
+      transport_extensions:     HashMap::with_hasher(RandomState::new()),
     }
   }
 
@@ -78,6 +110,41 @@ impl RemotingExtensionConfig {
     self
   }
 
+  /// Registers a filter consulted before inbound envelopes are delivered.
+  ///
+  /// Filters run in registration order; the first rejection sends the message to dead letters
+  /// and records an [`InboundRejected`](crate::core::FlightMetricKind::InboundRejected) metric.
+  #[must_use]
+  pub fn with_inbound_filter(mut self, filter: impl InboundFilter) -> Self {
+    let filter: ArcShared<dyn InboundFilter> = ArcShared::new(filter);
+    self.inbound_filters.push(filter);
+    self
+  }
+
+  /// Returns the registered inbound filters.
+  #[must_use]
+  pub fn inbound_filters(&self) -> &[ArcShared<dyn InboundFilter>] {
+    &self.inbound_filters
+  }
+
+  /// Lets peers whose authority is not verified reach `/system` actors.
+  ///
+  /// By default envelopes addressed below `/system` are rejected unless the transport bound the
+  /// sender authority to the connection (the observed source host matches the handshake, a TLS
+  /// certificate vouches for it, or the in-process network routed it). Enable this only on
+  /// fully trusted networks whose nodes advertise host names instead of their addresses.
+  #[must_use]
+  pub const fn with_unverified_system_access(mut self, enabled: bool) -> Self {
+    self.unverified_system_access = enabled;
+    self
+  }
+
+  /// Returns whether unverified peers may reach `/system` actors.
+  #[must_use]
+  pub const fn unverified_system_access(&self) -> bool {
+    self.unverified_system_access
+  }
+
   /// Overrides the flight recorder capacity.
   #[must_use]
   pub fn with_flight_recorder_capacity(mut self, capacity: usize) -> Self {
@@ -85,6 +152,146 @@ impl RemotingExtensionConfig {
     self
   }
 
+  /// Overrides the settings of the Tokio TCP transport (frame limits, large-message lane).
+  #[must_use]
+  pub const fn with_tokio_transport_config(mut self, config: TokioTransportConfig) -> Self {
+    self.tokio_transport = config;
+    self
+  }
+
+  /// Returns the settings of the Tokio TCP transport.
+  #[must_use]
+  pub const fn tokio_transport_config(&self) -> &TokioTransportConfig {
+    &self.tokio_transport
+  }
+
+  /// Enables payload compression on associations whose peer supports it.
+  ///
+  /// The capability is advertised during the handshake; payloads are only compressed when both
+  /// sides enabled compression.
+  #[must_use]
+  pub const fn with_compression(mut self, compression: PayloadCompression) -> Self {
+    self.compression = Some(compression);
+    self
+  }
+
+  /// Returns the payload compression settings, if enabled.
+  #[must_use]
+  pub const fn compression(&self) -> Option<PayloadCompression> {
+    self.compression
+  }
+
+  /// Enables actor path and manifest compression tables on associations whose peer supports
+  /// them.
+  ///
+  /// Each node counts the header strings it receives and advertises the most frequent ones to
+  /// the sender, which then refers to them by id instead of sending the full strings.
+  #[must_use]
+  pub const fn with_compression_tables(mut self, settings: CompressionTableSettings) -> Self {
+    self.compression_tables = Some(settings);
+    self
+  }
+
+  /// Returns the compression table settings, if enabled.
+  #[must_use]
+  pub const fn compression_tables(&self) -> Option<CompressionTableSettings> {
+    self.compression_tables
+  }
+
+  /// Overrides the number of unacknowledged system messages kept per association.
+  ///
+  /// An association whose resend buffer overflows is quarantined.
+  #[must_use]
+  pub fn with_system_message_buffer_size(mut self, size: usize) -> Self {
+    self.system_buffer_size = size.max(1);
+    self
+  }
+
+  /// Returns the system message resend buffer size.
+  #[must_use]
+  pub const fn system_message_buffer_size(&self) -> usize {
+    self.system_buffer_size
+  }
+
+  /// Overrides the backoff applied between reconnect attempts of a dropped association.
+  #[must_use]
+  pub const fn with_reconnect_backoff(mut self, backoff: ReconnectBackoff) -> Self {
+    self.reconnect_backoff = backoff;
+    self
+  }
+
+  /// Returns the reconnect backoff.
+  #[must_use]
+  pub const fn reconnect_backoff(&self) -> ReconnectBackoff {
+    self.reconnect_backoff
+  }
+
+  /// Overrides the capacity and overflow strategy of the per-association outbound queues.
+  #[must_use]
+  pub const fn with_outbound_queue_policy(mut self, policy: OutboundQueuePolicy) -> Self {
+    self.outbound_queue_policy = policy;
+    self
+  }
+
+  /// Returns the outbound queue policy.
+  #[must_use]
+  pub const fn outbound_queue_policy(&self) -> OutboundQueuePolicy {
+    self.outbound_queue_policy
+  }
+
+  /// Sets the serializers of the message headers carried across remoting.
+  ///
+  /// Headers whose type has no serializer in `registry` are not sent to remote nodes.
+  #[must_use]
+  pub fn with_header_serializers(mut self, registry: HeaderSerializerRegistry) -> Self {
+    self.header_serializers = registry;
+    self
+  }
+
+  /// Returns the serializers of the message headers carried across remoting.
+  #[must_use]
+  pub const fn header_serializers(&self) -> &HeaderSerializerRegistry {
+    &self.header_serializers
+  }
+
+  /// Overrides the directory holding the sockets of the `fraktor.uds` transport scheme.
+  ///
+  /// Each authority `host:port` listens on `<dir>/<host>:<port>.sock`; all systems that talk to
+  /// each other must use the same directory.
+  #[must_use]
+  pub fn with_unix_socket_dir(mut self, dir: impl Into<String>) -> Self {
+    self.unix_socket_dir = Some(dir.into());
+    self
+  }
+
+  /// Returns the socket directory of the `fraktor.uds` transport scheme, if overridden.
+  #[must_use]
+  pub fn unix_socket_dir(&self) -> Option<&str> {
+    self.unix_socket_dir.as_deref()
+  }
+
+  /// Attaches settings consumed by a runtime specific transport, keyed by their type.
+  ///
+  /// The std transports read their settings from here, e.g. the
+  /// `InProcessNetwork` joined by the `fraktor.inproc` scheme or the `TlsConfig` of the
+  /// `fraktor.tcp+tls` scheme. A later value of the same type replaces the earlier one.
+  #[must_use]
+  pub fn with_transport_extension<T>(mut self, extension: T) -> Self
+  where
+    T: Any + Send + Sync + 'static, {
+    let extension: ArcShared<dyn Any + Send + Sync> = ArcShared::new(extension);
+    self.transport_extensions.insert(TypeId::of::<T>(), extension);
+    self
+  }
+
+  /// Returns the transport settings of type `T`, if attached.
+  #[must_use]
+  pub fn transport_extension<T>(&self) -> Option<&T>
+  where
+    T: Any + Send + Sync + 'static, {
+    self.transport_extensions.get(&TypeId::of::<T>()).and_then(|extension| (**extension).downcast_ref::<T>())
+  }
+
   /// Returns the configured canonical host.
   #[must_use]
   pub fn canonical_host(&self) -> &str {
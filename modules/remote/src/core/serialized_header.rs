@@ -0,0 +1,37 @@
+//! Message header encoded for transmission inside a remoting envelope.
+
+use alloc::{string::String, vec::Vec};
+
+/// Header encoded by a [`HeaderSerializerRegistry`](crate::core::HeaderSerializerRegistry).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerializedHeader {
+  key:       String,
+  propagate: bool,
+  bytes:     Vec<u8>,
+}
+
+impl SerializedHeader {
+  /// Creates an encoded header.
+  #[must_use]
+  pub fn new(key: impl Into<String>, propagate: bool, bytes: Vec<u8>) -> Self {
+    Self { key: key.into(), propagate, bytes }
+  }
+
+  /// Returns the key the header serializer was registered under.
+  #[must_use]
+  pub fn key(&self) -> &str {
+    self.key.as_str()
+  }
+
+  /// Returns `true` when the header is propagated to replies and piped messages.
+  #[must_use]
+  pub const fn propagate(&self) -> bool {
+    self.propagate
+  }
+
+  /// Returns the encoded header value.
+  #[must_use]
+  pub fn bytes(&self) -> &[u8] {
+    self.bytes.as_slice()
+  }
+}
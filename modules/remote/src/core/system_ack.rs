@@ -0,0 +1,64 @@
+//! Cumulative acknowledgements exchanged for sequenced system messages.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::core::wire_error::WireError;
+
+const VERSION: u8 = 1;
+const KIND_ACK: u8 = 0x20;
+const KIND_NACK: u8 = 0x21;
+
+/// Cumulative acknowledgement sent by the receiver of sequenced system messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SystemAck {
+  /// Every message up to and including the sequence number was delivered.
+  Ack(u64),
+  /// Messages up to and including the sequence number were delivered, but a gap follows and
+  /// everything after it must be resent.
+  Nack(u64),
+}
+
+impl SystemAck {
+  /// Returns the cumulative sequence number.
+  #[must_use]
+  pub const fn seq(self) -> u64 {
+    match self {
+      | Self::Ack(seq) | Self::Nack(seq) => seq,
+    }
+  }
+
+  /// Encodes the acknowledgement into a transport payload.
+  #[must_use]
+  pub fn encode(self) -> Vec<u8> {
+    let kind = match self {
+      | Self::Ack(_) => KIND_ACK,
+      | Self::Nack(_) => KIND_NACK,
+    };
+    let mut buffer = Vec::with_capacity(10);
+    buffer.push(VERSION);
+    buffer.push(kind);
+    buffer.extend_from_slice(&self.seq().to_le_bytes());
+    buffer
+  }
+
+  /// Decodes an acknowledgement payload.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::InvalidFormat`] when the payload is malformed.
+  pub fn decode(bytes: &[u8]) -> Result<Self, WireError> {
+    if bytes.len() != 10 || bytes[0] != VERSION {
+      return Err(WireError::InvalidFormat);
+    }
+    let seq = u64::from_le_bytes(bytes[2..10].try_into().map_err(|_| WireError::InvalidFormat)?);
+    match bytes[1] {
+      | KIND_ACK => Ok(Self::Ack(seq)),
+      | KIND_NACK => Ok(Self::Nack(seq)),
+      | _ => Err(WireError::InvalidFormat),
+    }
+  }
+}
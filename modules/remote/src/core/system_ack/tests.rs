@@ -0,0 +1,17 @@
+use super::SystemAck;
+use crate::core::wire_error::WireError;
+
+#[test]
+fn ack_and_nack_round_trip() {
+  for ack in [SystemAck::Ack(7), SystemAck::Nack(u64::MAX)] {
+    assert_eq!(SystemAck::decode(&ack.encode()).expect("decode"), ack);
+  }
+}
+
+#[test]
+fn malformed_payload_is_rejected() {
+  let mut bytes = SystemAck::Ack(1).encode();
+  bytes[1] = 0x10;
+  assert!(matches!(SystemAck::decode(&bytes), Err(WireError::InvalidFormat)));
+  assert!(matches!(SystemAck::decode(&bytes[..4]), Err(WireError::InvalidFormat)));
+}
@@ -0,0 +1,125 @@
+//! Sender side of reliable system-message delivery for one association.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{collections::VecDeque, vec::Vec};
+use core::convert::TryInto;
+
+use crate::core::{remoting_envelope::RemotingEnvelope, system_ack::SystemAck, wire_error::WireError};
+
+const VERSION: u8 = 1;
+const KIND_SEQUENCED: u8 = 0x11;
+const HEADER_LEN: usize = 10;
+
+/// Assigns sequence numbers to outbound system messages and keeps them until acknowledged.
+///
+/// Unacknowledged messages are resent after a [`SystemAck::Nack`] or a reconnect. The resend
+/// buffer is bounded; once it is full the association must be quarantined because delivery
+/// can no longer be guaranteed.
+///
+/// Every message that arrives after a gap is answered with the same `Nack`, so once the
+/// messages after a gap were resent, repeated `Nack`s for that gap are ignored as long as they
+/// can stem from messages that were already in flight.
+pub struct SystemMessageDelivery {
+  capacity:  usize,
+  next_seq:  u64,
+  unacked:   VecDeque<(u64, RemotingEnvelope)>,
+  // 再送済みのギャップと、その時点で送信中だったメッセージ分だけ無視できる Nack の残数
+  resending: Option<(u64, usize)>,
+}
+
+impl SystemMessageDelivery {
+  /// Creates a delivery buffer holding at most `capacity` unacknowledged messages.
+  #[must_use]
+  pub fn new(capacity: usize) -> Self {
+    Self { capacity: capacity.max(1), next_seq: 1, unacked: VecDeque::new(), resending: None }
+  }
+
+  /// Assigns the next sequence number and buffers the envelope for redelivery.
+  ///
+  /// Returns `None` when the resend buffer is full.
+  #[must_use]
+  pub fn send(&mut self, envelope: RemotingEnvelope) -> Option<u64> {
+    if self.unacked.len() >= self.capacity {
+      return None;
+    }
+    let seq = self.next_seq;
+    self.next_seq += 1;
+    self.unacked.push_back((seq, envelope));
+    Some(seq)
+  }
+
+  /// Applies a cumulative acknowledgement and returns the messages that must be resent.
+  ///
+  /// An [`Ack`](SystemAck::Ack) requires no resend; a [`Nack`](SystemAck::Nack) resends every
+  /// message after the acknowledged sequence number unless that gap is already being resent.
+  #[must_use]
+  pub fn acknowledge(&mut self, ack: SystemAck) -> Vec<(u64, RemotingEnvelope)> {
+    let seq = ack.seq();
+    while self.unacked.front().is_some_and(|(pending, _)| *pending <= seq) {
+      self.unacked.pop_front();
+    }
+    if self.resending.is_some_and(|(gap, _)| gap != seq) {
+      self.resending = None;
+    }
+    match ack {
+      | SystemAck::Ack(_) => Vec::new(),
+      | SystemAck::Nack(_) => match &mut self.resending {
+        // 再送前に送信済みだったメッセージが同じギャップを報告しているだけなので無視する
+        | Some((_, ignorable)) if *ignorable > 0 => {
+          *ignorable -= 1;
+          Vec::new()
+        },
+        | _ => {
+          let resend = self.pending();
+          // ギャップ直後のメッセージは欠落しているため、Nack を返し得るのは残りのメッセージだけ
+          self.resending = Some((seq, resend.len().saturating_sub(1)));
+          resend
+        },
+      },
+    }
+  }
+
+  /// Returns every unacknowledged message in sequence order.
+  #[must_use]
+  pub fn pending(&self) -> Vec<(u64, RemotingEnvelope)> {
+    self.unacked.iter().cloned().collect()
+  }
+
+  /// Returns the number of unacknowledged messages.
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.unacked.len()
+  }
+
+  /// Returns `true` when every message was acknowledged.
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.unacked.is_empty()
+  }
+
+  /// Wraps an encoded envelope frame with its sequence number.
+  #[must_use]
+  pub fn encode_sequenced(seq: u64, frame: &[u8]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(HEADER_LEN + frame.len());
+    buffer.push(VERSION);
+    buffer.push(KIND_SEQUENCED);
+    buffer.extend_from_slice(&seq.to_le_bytes());
+    buffer.extend_from_slice(frame);
+    buffer
+  }
+
+  /// Splits a sequenced payload into its sequence number and the wrapped envelope frame.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::InvalidFormat`] when the payload is not a sequenced frame.
+  pub fn decode_sequenced(bytes: &[u8]) -> Result<(u64, &[u8]), WireError> {
+    if bytes.len() < HEADER_LEN || bytes[0] != VERSION || bytes[1] != KIND_SEQUENCED {
+      return Err(WireError::InvalidFormat);
+    }
+    let seq = u64::from_le_bytes(bytes[2..HEADER_LEN].try_into().map_err(|_| WireError::InvalidFormat)?);
+    Ok((seq, &bytes[HEADER_LEN..]))
+  }
+}
@@ -0,0 +1,113 @@
+use alloc::vec::Vec;
+
+use fraktor_actor_rs::core::{
+  actor_prim::actor_path::{ActorPath, ActorPathParts, GuardianKind},
+  event_stream::CorrelationId,
+  serialization::{SerializedMessage, SerializerId},
+};
+
+use super::SystemMessageDelivery;
+use crate::core::{
+  outbound_priority::OutboundPriority, remote_node_id::RemoteNodeId, remoting_envelope::RemotingEnvelope,
+  system_ack::SystemAck, wire_error::WireError,
+};
+
+fn envelope(label: &str) -> RemotingEnvelope {
+  let recipient = ActorPath::from_parts(ActorPathParts::local("remote-app").with_guardian(GuardianKind::System));
+  let serialized = SerializedMessage::new(SerializerId::try_from(41).expect("id"), None, label.as_bytes().to_vec());
+  RemotingEnvelope::new(
+    recipient,
+    RemoteNodeId::new("remote-system", "127.0.0.1", Some(4100), 1),
+    None,
+    serialized,
+    CorrelationId::nil(),
+    OutboundPriority::System,
+  )
+}
+
+fn seqs(messages: &[(u64, RemotingEnvelope)]) -> Vec<u64> {
+  messages.iter().map(|(seq, _)| *seq).collect()
+}
+
+#[test]
+fn sequence_numbers_increase_per_message() {
+  let mut delivery = SystemMessageDelivery::new(8);
+  assert_eq!(delivery.send(envelope("watch")), Some(1));
+  assert_eq!(delivery.send(envelope("unwatch")), Some(2));
+  assert_eq!(delivery.len(), 2);
+}
+
+#[test]
+fn cumulative_ack_releases_buffered_messages() {
+  let mut delivery = SystemMessageDelivery::new(8);
+  for label in ["a", "b", "c"] {
+    let _ = delivery.send(envelope(label));
+  }
+
+  assert!(delivery.acknowledge(SystemAck::Ack(2)).is_empty());
+  assert_eq!(seqs(&delivery.pending()), vec![3]);
+  assert!(delivery.acknowledge(SystemAck::Ack(3)).is_empty());
+  assert!(delivery.is_empty());
+}
+
+#[test]
+fn nack_resends_everything_after_the_gap() {
+  let mut delivery = SystemMessageDelivery::new(8);
+  for label in ["a", "b", "c"] {
+    let _ = delivery.send(envelope(label));
+  }
+
+  let resend = delivery.acknowledge(SystemAck::Nack(1));
+
+  assert_eq!(seqs(&resend), vec![2, 3]);
+  assert_eq!(resend[0].1, envelope("b"));
+}
+
+#[test]
+fn repeated_nacks_for_a_gap_being_resent_are_ignored() {
+  let mut delivery = SystemMessageDelivery::new(8);
+  for label in ["a", "b", "c", "d"] {
+    let _ = delivery.send(envelope(label));
+  }
+
+  assert_eq!(seqs(&delivery.acknowledge(SystemAck::Nack(1))), vec![2, 3, 4]);
+  // 3 と 4 は再送前から送信中だったため、それぞれが返す Nack は再送を繰り返さない
+  assert!(delivery.acknowledge(SystemAck::Nack(1)).is_empty());
+  assert!(delivery.acknowledge(SystemAck::Nack(1)).is_empty());
+  // それ以上の Nack は再送が失われたことを意味するため、もう一度再送する
+  assert_eq!(seqs(&delivery.acknowledge(SystemAck::Nack(1))), vec![2, 3, 4]);
+}
+
+#[test]
+fn a_new_gap_is_resent_immediately() {
+  let mut delivery = SystemMessageDelivery::new(8);
+  for label in ["a", "b", "c", "d"] {
+    let _ = delivery.send(envelope(label));
+  }
+
+  assert_eq!(seqs(&delivery.acknowledge(SystemAck::Nack(1))), vec![2, 3, 4]);
+  assert_eq!(seqs(&delivery.acknowledge(SystemAck::Nack(2))), vec![3, 4]);
+}
+
+#[test]
+fn full_buffer_refuses_new_messages() {
+  let mut delivery = SystemMessageDelivery::new(2);
+  let _ = delivery.send(envelope("a"));
+  let _ = delivery.send(envelope("b"));
+
+  assert_eq!(delivery.send(envelope("c")), None);
+  let _ = delivery.acknowledge(SystemAck::Ack(1));
+  assert_eq!(delivery.send(envelope("c")), Some(3));
+}
+
+#[test]
+fn sequenced_frames_round_trip() {
+  let frame = envelope("watch").encode_frame().expect("encode");
+  let sequenced = SystemMessageDelivery::encode_sequenced(42, &frame);
+
+  let (seq, inner) = SystemMessageDelivery::decode_sequenced(&sequenced).expect("decode");
+
+  assert_eq!(seq, 42);
+  assert_eq!(inner, frame.as_slice());
+  assert!(matches!(SystemMessageDelivery::decode_sequenced(&frame), Err(WireError::InvalidFormat)));
+}
@@ -0,0 +1,43 @@
+//! Receiver side of reliable system-message delivery for one association.
+
+#[cfg(test)]
+mod tests;
+
+use crate::core::system_ack::SystemAck;
+
+/// Tracks the next expected system-message sequence number of a peer.
+///
+/// Messages are delivered strictly in order: duplicates are dropped and acknowledged again,
+/// and a gap is answered with a [`SystemAck::Nack`] so the sender resends the missing part.
+pub struct SystemMessageReceiver {
+  incarnation: u64,
+  expected:    u64,
+}
+
+impl SystemMessageReceiver {
+  /// Creates a receiver for the peer incarnation identified by `incarnation` (its handshake uid).
+  #[must_use]
+  pub const fn new(incarnation: u64) -> Self {
+    Self { incarnation, expected: 1 }
+  }
+
+  /// Returns the peer incarnation this receiver tracks.
+  #[must_use]
+  pub const fn incarnation(&self) -> u64 {
+    self.incarnation
+  }
+
+  /// Processes an inbound sequence number.
+  ///
+  /// Returns whether the message must be delivered together with the acknowledgement to send
+  /// back.
+  #[must_use]
+  pub const fn receive(&mut self, seq: u64) -> (bool, SystemAck) {
+    if seq == self.expected {
+      self.expected += 1;
+      return (true, SystemAck::Ack(seq));
+    }
+    let delivered = self.expected - 1;
+    if seq < self.expected { (false, SystemAck::Ack(delivered)) } else { (false, SystemAck::Nack(delivered)) }
+  }
+}
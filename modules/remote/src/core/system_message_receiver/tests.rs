@@ -0,0 +1,26 @@
+use super::SystemMessageReceiver;
+use crate::core::system_ack::SystemAck;
+
+#[test]
+fn in_order_messages_are_delivered_and_acked() {
+  let mut receiver = SystemMessageReceiver::new(1);
+  assert_eq!(receiver.receive(1), (true, SystemAck::Ack(1)));
+  assert_eq!(receiver.receive(2), (true, SystemAck::Ack(2)));
+}
+
+#[test]
+fn duplicates_are_dropped_but_acknowledged() {
+  let mut receiver = SystemMessageReceiver::new(1);
+  let _ = receiver.receive(1);
+  let _ = receiver.receive(2);
+  assert_eq!(receiver.receive(1), (false, SystemAck::Ack(2)));
+}
+
+#[test]
+fn gaps_are_nacked_until_filled() {
+  let mut receiver = SystemMessageReceiver::new(1);
+  let _ = receiver.receive(1);
+  assert_eq!(receiver.receive(3), (false, SystemAck::Nack(1)));
+  assert_eq!(receiver.receive(2), (true, SystemAck::Ack(2)));
+  assert_eq!(receiver.receive(3), (true, SystemAck::Ack(3)));
+}
@@ -5,6 +5,7 @@ mod tests;
 
 mod backpressure_hook;
 mod factory;
+mod lifecycle_hook;
 mod loopback_transport;
 mod remote_transport;
 mod remote_transport_shared;
@@ -19,6 +20,7 @@ mod transport_inbound_handler;
 
 pub use backpressure_hook::{TransportBackpressureHook, TransportBackpressureHookShared};
 pub use factory::TransportFactory;
+pub use lifecycle_hook::{TransportLifecycleHook, TransportLifecycleHookShared};
 pub use loopback_transport::LoopbackTransport;
 pub use remote_transport::RemoteTransport;
 pub use remote_transport_shared::RemoteTransportShared;
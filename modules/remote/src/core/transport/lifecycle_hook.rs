@@ -0,0 +1,20 @@
+//! Hook invoked by transports when connection lifecycle events occur.
+
+use alloc::boxed::Box;
+
+use fraktor_utils_rs::core::{runtime_toolbox::NoStdMutex, sync::ArcShared};
+
+/// Receives transport-level connection lifecycle notifications.
+pub trait TransportLifecycleHook: Send + Sync + 'static {
+  /// Called whenever a handshake (e.g. TLS) with the peer at `authority` fails.
+  fn on_handshake_failed(&mut self, authority: &str, reason: &str);
+
+  /// Called when the transport hits an error that does not belong to a single frame, e.g. a
+  /// failed certificate reload or a listener that stopped accepting connections.
+  ///
+  /// `authority` is the local listener or the dialled peer the error relates to.
+  fn on_transport_error(&mut self, _authority: &str, _reason: &str) {}
+}
+
+/// Shared reference used to mutate a transport lifecycle hook under a mutex.
+pub type TransportLifecycleHookShared = ArcShared<NoStdMutex<Box<dyn TransportLifecycleHook>>>;
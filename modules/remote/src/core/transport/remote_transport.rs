@@ -3,9 +3,10 @@ use fraktor_actor_rs::core::event_stream::CorrelationId;
 use fraktor_utils_rs::core::runtime_toolbox::RuntimeToolbox;
 
 use super::{
-  backpressure_hook::TransportBackpressureHookShared, transport_bind::TransportBind,
-  transport_channel::TransportChannel, transport_endpoint::TransportEndpoint, transport_error::TransportError,
-  transport_handle::TransportHandle, transport_inbound_handler::TransportInboundShared,
+  backpressure_hook::TransportBackpressureHookShared, lifecycle_hook::TransportLifecycleHookShared,
+  transport_bind::TransportBind, transport_channel::TransportChannel, transport_endpoint::TransportEndpoint,
+  transport_error::TransportError, transport_handle::TransportHandle,
+  transport_inbound_handler::TransportInboundShared,
 };
 
 /// Abstraction over transport implementations used by remoting.
@@ -41,6 +42,13 @@ pub trait RemoteTransport<TB: RuntimeToolbox>: Send + 'static {
   /// Registers a hook used to propagate backpressure notifications.
   fn install_backpressure_hook(&mut self, hook: TransportBackpressureHookShared);
 
+  /// Registers a hook notified about connection lifecycle events such as handshake failures.
+  ///
+  /// Transports without a handshake phase may ignore the hook.
+  fn install_lifecycle_hook(&mut self, hook: TransportLifecycleHookShared) {
+    let _ = hook;
+  }
+
   /// Registers a handler that receives inbound frames accepted by the transport.
   ///
   /// The handler is wrapped in a mutex from the toolbox's `MutexFamily`, allowing
@@ -10,7 +10,7 @@ use fraktor_utils_rs::core::{
 
 use super::{
   RemoteTransport, TransportBackpressureHookShared, TransportBind, TransportChannel, TransportEndpoint, TransportError,
-  TransportHandle, TransportInboundShared,
+  TransportHandle, TransportInboundShared, TransportLifecycleHookShared,
 };
 
 /// Shared wrapper that provides thread-safe access to a [`RemoteTransport`]
@@ -80,6 +80,10 @@ impl<TB: RuntimeToolbox + 'static> RemoteTransport<TB> for RemoteTransportShared
     self.inner.lock().install_backpressure_hook(hook)
   }
 
+  fn install_lifecycle_hook(&mut self, hook: TransportLifecycleHookShared) {
+    self.inner.lock().install_lifecycle_hook(hook)
+  }
+
   fn install_inbound_handler(&mut self, handler: TransportInboundShared<TB>) {
     self.inner.lock().install_inbound_handler(handler)
   }
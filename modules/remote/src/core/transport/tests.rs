@@ -76,3 +76,43 @@ fn loopback_backpressure_hook_triggers_listener() {
   transport.install_backpressure_hook(hook);
   transport.emit_backpressure_for_test("loopback:test", BackpressureSignal::Apply, CorrelationId::from_u128(42));
 }
+
+#[cfg(feature = "tls")]
+#[test]
+fn factory_requires_tls_config_for_tls_scheme() {
+  let config = RemotingExtensionConfig::default().with_transport_scheme("fraktor.tcp+tls");
+  assert!(matches!(TransportFactory::build(&config), Err(TransportError::Tls(_))));
+}
+
+#[cfg(feature = "tls")]
+#[test]
+fn factory_reads_tls_config_from_transport_extension() {
+  let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tls");
+  let tls = crate::std::transport::TlsConfig::from_files(
+    format!("{fixtures}/node.pem"),
+    format!("{fixtures}/node-key.pem"),
+    format!("{fixtures}/ca.pem"),
+  );
+  let config =
+    RemotingExtensionConfig::default().with_transport_scheme("fraktor.tcp+tls").with_transport_extension(tls);
+  let transport = TransportFactory::build(&config).expect("transport resolved");
+  assert_eq!(transport.scheme(), "fraktor.tcp+tls");
+}
+
+#[test]
+fn factory_resolves_in_process_scheme_with_a_network() {
+  let config = RemotingExtensionConfig::default().with_transport_scheme("fraktor.inproc");
+  assert!(matches!(TransportFactory::build(&config), Err(TransportError::Io(_))));
+
+  let config = config.with_transport_extension(crate::std::transport::InProcessNetwork::new());
+  let transport = TransportFactory::build(&config).expect("transport resolved");
+  assert_eq!(transport.scheme(), "fraktor.inproc");
+}
+
+#[cfg(all(feature = "tokio-transport", unix))]
+#[test]
+fn factory_resolves_unix_socket_scheme() {
+  let config = RemotingExtensionConfig::default().with_transport_scheme("fraktor.uds").with_unix_socket_dir("/tmp");
+  let transport = TransportFactory::build(&config).expect("transport resolved");
+  assert_eq!(transport.scheme(), "fraktor.uds");
+}
@@ -1,24 +1,79 @@
 //! Configuration for Tokio TCP transport.
 
+#[cfg(test)]
+mod tests;
+
+const DEFAULT_MAX_FRAME_SIZE: usize = 256 * 1024;
+const DEFAULT_LARGE_MESSAGE_THRESHOLD: usize = 64 * 1024;
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 8 * 1024 * 1024;
+
 /// Configuration for Tokio TCP transport.
 ///
-/// This is a placeholder implementation. Future versions will include:
-/// - TCP buffer sizes
-/// - Connection timeout
-/// - Keep-alive settings
-/// - Reconnection policy
-/// - TLS configuration
+/// Every wire frame (length prefix value) is limited to `max_frame_size` on both send and
+/// receive. Payloads larger than `large_message_threshold` are split into frame-sized chunks
+/// and sent over a dedicated large-message connection, so a big payload does not delay
+/// heartbeats and system messages queued behind it; ordering is therefore only kept within
+/// each lane. Reassembled messages are limited to `max_message_size`.
 #[derive(Debug, Clone)]
 pub struct TokioTransportConfig {
-  // Placeholder for future configuration fields
-  _reserved: (),
+  max_frame_size:          usize,
+  large_message_threshold: usize,
+  max_message_size:        usize,
 }
 
 impl TokioTransportConfig {
   /// Creates a new Tokio transport configuration with default settings.
   #[must_use]
   pub const fn new() -> Self {
-    Self { _reserved: () }
+    Self {
+      max_frame_size:          DEFAULT_MAX_FRAME_SIZE,
+      large_message_threshold: DEFAULT_LARGE_MESSAGE_THRESHOLD,
+      max_message_size:        DEFAULT_MAX_MESSAGE_SIZE,
+    }
+  }
+
+  /// Sets the maximum size of a single frame in bytes (header included).
+  #[must_use]
+  pub const fn with_max_frame_size(mut self, size: usize) -> Self {
+    self.max_frame_size = size;
+    self
+  }
+
+  /// Sets the payload size above which messages use the chunked large-message lane.
+  ///
+  /// The large-message lane is a separate connection, so messages are only ordered within a
+  /// lane: a small message sent after a large one to the same recipient may be delivered
+  /// first. Raise the threshold above the largest payload of a protocol that relies on
+  /// per-sender ordering.
+  #[must_use]
+  pub const fn with_large_message_threshold(mut self, threshold: usize) -> Self {
+    self.large_message_threshold = threshold;
+    self
+  }
+
+  /// Sets the maximum size of a reassembled large message in bytes.
+  #[must_use]
+  pub const fn with_max_message_size(mut self, size: usize) -> Self {
+    self.max_message_size = size;
+    self
+  }
+
+  /// Returns the maximum frame size.
+  #[must_use]
+  pub const fn max_frame_size(&self) -> usize {
+    self.max_frame_size
+  }
+
+  /// Returns the large-message threshold.
+  #[must_use]
+  pub const fn large_message_threshold(&self) -> usize {
+    self.large_message_threshold
+  }
+
+  /// Returns the maximum reassembled message size.
+  #[must_use]
+  pub const fn max_message_size(&self) -> usize {
+    self.max_message_size
   }
 }
 
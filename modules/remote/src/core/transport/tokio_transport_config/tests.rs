@@ -0,0 +1,18 @@
+use super::TokioTransportConfig;
+
+#[test]
+fn defaults_bound_frames_and_messages() {
+  let config = TokioTransportConfig::default();
+  assert_eq!(config.max_frame_size(), 256 * 1024);
+  assert_eq!(config.large_message_threshold(), 64 * 1024);
+  assert_eq!(config.max_message_size(), 8 * 1024 * 1024);
+}
+
+#[test]
+fn builders_override_limits() {
+  let config =
+    TokioTransportConfig::new().with_max_frame_size(1024).with_large_message_threshold(512).with_max_message_size(4096);
+  assert_eq!(config.max_frame_size(), 1024);
+  assert_eq!(config.large_message_threshold(), 512);
+  assert_eq!(config.max_message_size(), 4096);
+}
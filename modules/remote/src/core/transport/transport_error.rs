@@ -4,7 +4,7 @@ use alloc::string::String;
 use core::fmt;
 
 /// Enumerates transport-specific failures.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TransportError {
   /// Scheme was unsupported by the current build.
   UnsupportedScheme(String),
@@ -12,6 +12,17 @@ pub enum TransportError {
   AuthorityNotBound(String),
   /// The requested channel could not be located.
   ChannelUnavailable(u64),
+  /// A frame or message exceeded the configured size limit.
+  FrameTooLarge {
+    /// Size of the rejected frame or message in bytes.
+    size: usize,
+    /// Configured limit in bytes.
+    max:  usize,
+  },
+  /// The connection to the authority cannot accept more frames until it drains.
+  Backpressure(String),
+  /// TLS configuration or handshake failure.
+  Tls(String),
   /// Generic failure message.
   Io(String),
 }
@@ -22,6 +33,9 @@ impl fmt::Display for TransportError {
       | Self::UnsupportedScheme(scheme) => write!(f, "unsupported transport scheme: {scheme}"),
       | Self::AuthorityNotBound(authority) => write!(f, "authority not bound: {authority}"),
       | Self::ChannelUnavailable(id) => write!(f, "channel unavailable: {id}"),
+      | Self::FrameTooLarge { size, max } => write!(f, "frame of {size} bytes exceeds limit of {max} bytes"),
+      | Self::Backpressure(authority) => write!(f, "connection to {authority} is backpressured"),
+      | Self::Tls(message) => write!(f, "tls error: {message}"),
       | Self::Io(message) => write!(f, "transport error: {message}"),
     }
   }
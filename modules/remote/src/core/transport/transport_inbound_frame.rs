@@ -10,6 +10,7 @@ pub struct InboundFrame {
   remote_address:  String,
   payload:         Vec<u8>,
   correlation_id:  CorrelationId,
+  peer_authority:  Option<String>,
 }
 
 impl InboundFrame {
@@ -21,7 +22,23 @@ impl InboundFrame {
     payload: Vec<u8>,
     correlation_id: CorrelationId,
   ) -> Self {
-    Self { local_authority: local_authority.into(), remote_address: remote_address.into(), payload, correlation_id }
+    Self {
+      local_authority: local_authority.into(),
+      remote_address: remote_address.into(),
+      payload,
+      correlation_id,
+      peer_authority: None,
+    }
+  }
+
+  /// Records the authority (`host:port`) the transport itself authenticated for the sender.
+  ///
+  /// Transports set it only when the identity does not come from the peer's own claim, e.g. a
+  /// verified TLS certificate or the listener binding of an in-process network.
+  #[must_use]
+  pub fn with_peer_authority(mut self, authority: impl Into<String>) -> Self {
+    self.peer_authority = Some(authority.into());
+    self
   }
 
   /// Returns the local authority (listener) that accepted the frame.
@@ -36,6 +53,12 @@ impl InboundFrame {
     &self.remote_address
   }
 
+  /// Returns the sender authority authenticated by the transport, if any.
+  #[must_use]
+  pub fn peer_authority(&self) -> Option<&str> {
+    self.peer_authority.as_deref()
+  }
+
   /// Returns the raw payload bytes after length-prefix decoding.
   #[must_use]
   pub fn payload(&self) -> &[u8] {
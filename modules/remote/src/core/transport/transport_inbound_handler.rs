@@ -6,7 +6,7 @@ use alloc::boxed::Box;
 
 use fraktor_utils_rs::core::{runtime_toolbox::ToolboxMutex, sync::ArcShared};
 
-use crate::core::transport::transport_inbound_frame::InboundFrame;
+use crate::core::transport::{transport_error::TransportError, transport_inbound_frame::InboundFrame};
 
 /// Receives decoded transport frames and forwards them to higher layers.
 ///
@@ -27,6 +27,12 @@ use crate::core::transport::transport_inbound_frame::InboundFrame;
 pub trait TransportInbound: Send + 'static {
   /// Handles a single inbound frame.
   fn on_frame(&mut self, frame: InboundFrame);
+
+  /// Called when the transport dropped a connection because a peer violated the framing rules
+  /// (e.g. sent an oversized frame).
+  fn on_frame_rejected(&mut self, remote_address: &str, error: &TransportError) {
+    let _ = (remote_address, error);
+  }
 }
 
 /// Shared handle to a [`TransportInbound`] implementation with external synchronization.
@@ -0,0 +1,36 @@
+//! Built-in filter keeping `/system` unreachable from peers whose identity is not verified.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::string::String;
+
+use crate::core::{
+  inbound_access_rules::InboundAccessRules, inbound_filter::InboundFilter, remoting_envelope::RemotingEnvelope,
+};
+
+/// Rejects envelopes addressed below `/system` when the sender authority is not verified.
+///
+/// Remote deployment and death watch run through `/system` daemons, so only peers whose
+/// authority the transport could bind to the connection may reach them. Installed by default;
+/// see [`RemotingExtensionConfig::with_unverified_system_access`](crate::core::RemotingExtensionConfig::with_unverified_system_access).
+pub(crate) struct UnverifiedSystemGuard {
+  rules: InboundAccessRules,
+}
+
+impl UnverifiedSystemGuard {
+  /// Creates the guard.
+  #[must_use]
+  pub(crate) fn new() -> Self {
+    Self { rules: InboundAccessRules::new().with_denied_path("/system") }
+  }
+}
+
+impl InboundFilter for UnverifiedSystemGuard {
+  fn check(&self, authority: Option<&str>, envelope: &RemotingEnvelope) -> Result<(), String> {
+    match authority {
+      | Some(_) => Ok(()),
+      | None => self.rules.check(None, envelope),
+    }
+  }
+}
@@ -0,0 +1,42 @@
+use alloc::{string::ToString, vec};
+
+use fraktor_actor_rs::core::{
+  actor_prim::actor_path::{ActorPath, ActorPathParts},
+  event_stream::CorrelationId,
+  serialization::{SerializedMessage, SerializerId},
+};
+
+use super::UnverifiedSystemGuard;
+use crate::core::{
+  inbound_filter::InboundFilter, outbound_priority::OutboundPriority, remote_node_id::RemoteNodeId,
+  remoting_envelope::RemotingEnvelope,
+};
+
+fn envelope(segments: &[&str]) -> RemotingEnvelope {
+  let mut recipient = ActorPath::from_parts(ActorPathParts::local("app"));
+  for segment in segments {
+    recipient = recipient.child(segment);
+  }
+  let serialized = SerializedMessage::new(SerializerId::try_from(50).expect("id"), Some("m".to_string()), vec![1]);
+  RemotingEnvelope::new(
+    recipient,
+    RemoteNodeId::new("app", "127.0.0.1", Some(2552), 1),
+    None,
+    serialized,
+    CorrelationId::from_u128(1),
+    OutboundPriority::System,
+  )
+}
+
+#[test]
+fn unverified_peers_cannot_reach_system_daemons() {
+  let guard = UnverifiedSystemGuard::new();
+  assert_eq!(guard.check(None, &envelope(&["system", "remote"])), Err("recipient `/system/remote` is denied".into()));
+  assert_eq!(guard.check(None, &envelope(&["user", "svc"])), Ok(()));
+}
+
+#[test]
+fn verified_peers_reach_system_daemons() {
+  let guard = UnverifiedSystemGuard::new();
+  assert_eq!(guard.check(Some("127.0.0.1:2552"), &envelope(&["system", "remote"])), Ok(()));
+}
@@ -4,7 +4,7 @@ use alloc::string::FromUtf8Error;
 
 use fraktor_actor_rs::core::{actor_prim::actor_path::ActorPathError, serialization::SerializationError};
 
-/// Represents failures while decoding transport frames.
+/// Represents failures while encoding or decoding transport frames.
 #[derive(Debug)]
 pub enum WireError {
   /// The provided payload does not follow the expected binary layout.
@@ -15,6 +15,17 @@ pub enum WireError {
   Serialization(SerializationError),
   /// UTF-8 decoding failed for textual fields.
   Utf8Error,
+  /// A compressed payload could not be decompressed.
+  Decompression,
+  /// An envelope referred to a compression table version or entry the receiver does not know.
+  UnknownCompressionEntry,
+  /// A length or count exceeded the width of its wire field while encoding.
+  FieldTooLarge {
+    /// Length or count that had to be written.
+    size: usize,
+    /// Largest value the field can hold.
+    max:  usize,
+  },
 }
 
 impl From<SerializationError> for WireError {
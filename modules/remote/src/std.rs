@@ -1,3 +1,4 @@
+pub mod metrics;
 #[cfg(feature = "tokio-transport")]
 pub mod runtime;
 pub mod transport;
@@ -0,0 +1,5 @@
+//! Collectors publishing remoting metrics to monitoring systems.
+
+mod remoting_metrics_collector;
+
+pub use remoting_metrics_collector::RemotingMetricsCollector;
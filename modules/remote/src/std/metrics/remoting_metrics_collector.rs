@@ -0,0 +1,94 @@
+//! Prometheus collector exposing per-association remoting metrics.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{
+  format,
+  string::{String, ToString},
+  vec::Vec,
+};
+
+use fraktor_actor_rs::std::metrics::{MetricsCollector, MetricsRegistry};
+
+use crate::core::{AssociationMetrics, LatencyHistogram, RemotingMetrics};
+
+const DEFAULT_NAMESPACE: &str = "fraktor_remote";
+
+type CounterReader = fn(&AssociationMetrics) -> u64;
+type HistogramReader = fn(&AssociationMetrics) -> &LatencyHistogram;
+
+const COUNTERS: [(&str, &str, CounterReader); 6] = [
+  ("bytes_sent_total", "Envelope frame bytes sent to the association.", AssociationMetrics::bytes_sent),
+  ("bytes_received_total", "Envelope frame bytes received from the association.", AssociationMetrics::bytes_received),
+  ("messages_sent_total", "Envelopes sent to the association.", AssociationMetrics::messages_sent),
+  ("messages_received_total", "Envelopes received from the association.", AssociationMetrics::messages_received),
+  ("handshakes_total", "Handshakes completed with the association.", AssociationMetrics::handshakes),
+  ("reconnects_total", "Reconnect attempts made to the association.", AssociationMetrics::reconnects),
+];
+
+const HISTOGRAMS: [(&str, &str, HistogramReader); 3] = [
+  ("serialization_seconds", "Time spent serializing outbound payloads.", AssociationMetrics::serialization),
+  ("deserialization_seconds", "Time spent deserializing inbound payloads.", AssociationMetrics::deserialization),
+  (
+    "ask_round_trip_seconds",
+    "Round-trip time of requests answered by the association.",
+    AssociationMetrics::ask_latency,
+  ),
+];
+
+/// Copies a [`RemotingMetrics`] registry into the Prometheus registry on every scrape.
+///
+/// Every series carries an `authority` label, so per-peer throughput, queue depth and latency
+/// can be compared directly: dividing the `_sum` rate of `ask_round_trip_seconds` by its `_count`
+/// rate shows which peer answers slowly. Register it with
+/// [`PrometheusMetrics::register_collector`](fraktor_actor_rs::std::metrics::PrometheusMetrics::register_collector).
+pub struct RemotingMetricsCollector {
+  metrics:   RemotingMetrics,
+  namespace: String,
+}
+
+impl RemotingMetricsCollector {
+  /// Creates a collector reading `metrics` with the `fraktor_remote` namespace.
+  #[must_use]
+  pub fn new(metrics: RemotingMetrics) -> Self {
+    Self { metrics, namespace: DEFAULT_NAMESPACE.to_string() }
+  }
+
+  /// Overrides the prefix of every metric name.
+  #[must_use]
+  pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+    self.namespace = namespace.into();
+    self
+  }
+}
+
+impl MetricsCollector for RemotingMetricsCollector {
+  fn collect(&self, registry: &mut MetricsRegistry) {
+    for association in self.metrics.snapshot() {
+      let labels = [("authority", association.authority())];
+      for (name, help, read) in COUNTERS {
+        registry.increment_counter(&format!("{}_{name}", self.namespace), help, &labels, read(&association));
+      }
+      registry.set_gauge(
+        &format!("{}_outbound_queue_depth", self.namespace),
+        "Messages waiting in the endpoint writer.",
+        &labels,
+        association.outbound_queue_depth() as f64,
+      );
+      for (name, help, read) in HISTOGRAMS {
+        let histogram = read(&association);
+        let buckets: Vec<(f64, u64)> =
+          histogram.cumulative_buckets().map(|(bound, count)| (bound.as_secs_f64(), count)).collect();
+        registry.set_histogram(
+          &format!("{}_{name}", self.namespace),
+          help,
+          &labels,
+          &buckets,
+          histogram.sum().as_secs_f64(),
+          histogram.count(),
+        );
+      }
+    }
+  }
+}
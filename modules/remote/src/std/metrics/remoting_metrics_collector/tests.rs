@@ -0,0 +1,42 @@
+use core::time::Duration;
+
+use fraktor_actor_rs::std::metrics::{MetricsCollector, MetricsRegistry};
+
+use super::RemotingMetricsCollector;
+use crate::core::RemotingMetrics;
+
+fn render(collector: &RemotingMetricsCollector) -> String {
+  let mut registry = MetricsRegistry::new();
+  collector.collect(&mut registry);
+  registry.render()
+}
+
+#[test]
+fn collects_counters_gauges_and_histograms_per_authority() {
+  let metrics = RemotingMetrics::new();
+  metrics.record_sent("10.0.0.1:2552", 120);
+  metrics.set_outbound_queue_depth("10.0.0.1:2552", 3);
+  metrics.record_ask_latency("10.0.0.1:2552", Duration::from_millis(20));
+
+  let text = render(&RemotingMetricsCollector::new(metrics));
+  assert!(text.contains("# TYPE fraktor_remote_bytes_sent_total counter\n"));
+  assert!(text.contains("fraktor_remote_bytes_sent_total{authority=\"10.0.0.1:2552\"} 120\n"));
+  assert!(text.contains("fraktor_remote_messages_sent_total{authority=\"10.0.0.1:2552\"} 1\n"));
+  assert!(text.contains("fraktor_remote_outbound_queue_depth{authority=\"10.0.0.1:2552\"} 3\n"));
+  assert!(text.contains("# TYPE fraktor_remote_ask_round_trip_seconds histogram\n"));
+  assert!(text.contains("fraktor_remote_ask_round_trip_seconds_bucket{authority=\"10.0.0.1:2552\",le=\"0.01\"} 0\n"));
+  assert!(text.contains("fraktor_remote_ask_round_trip_seconds_bucket{authority=\"10.0.0.1:2552\",le=\"0.025\"} 1\n"));
+  assert!(text.contains("fraktor_remote_ask_round_trip_seconds_bucket{authority=\"10.0.0.1:2552\",le=\"+Inf\"} 1\n"));
+  assert!(text.contains("fraktor_remote_ask_round_trip_seconds_sum{authority=\"10.0.0.1:2552\"} 0.02\n"));
+  assert!(text.contains("fraktor_remote_ask_round_trip_seconds_count{authority=\"10.0.0.1:2552\"} 1\n"));
+}
+
+#[test]
+fn namespace_and_label_escaping_are_applied() {
+  let metrics = RemotingMetrics::new();
+  metrics.record_handshake("odd\"host:1");
+
+  let text = render(&RemotingMetricsCollector::new(metrics).with_namespace("app_remote"));
+  assert!(text.contains("app_remote_handshakes_total{authority=\"odd\\\"host:1\"} 1\n"));
+  assert!(!text.contains("fraktor_remote_"));
+}
@@ -9,52 +9,81 @@ use alloc::{
   vec::Vec,
 };
 use core::time::Duration;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-use fraktor_actor_rs::core::{event_stream::CorrelationId, logging::LogLevel, system::ActorSystemGeneric};
+use fraktor_actor_rs::core::{
+  event_stream::{BackpressureSignal, CorrelationId},
+  logging::LogLevel,
+  system::ActorSystemGeneric,
+};
 use fraktor_utils_rs::core::{
   runtime_toolbox::{RuntimeToolbox, SyncMutexFamily, ToolboxMutex},
   sync::{ArcShared, sync_mutex_like::SyncMutexLike},
 };
-use tokio::{sync::Mutex as TokioMutex, task::JoinHandle, time::sleep};
+use tokio::{
+  sync::{Mutex as TokioMutex, mpsc},
+  task::JoinHandle,
+  time::sleep,
+};
 
 use crate::core::{
-  AssociationState, DeferredEnvelope, EndpointManager, EndpointManagerCommand, EndpointManagerEffect,
-  EndpointReaderGeneric, EndpointWriterGeneric, EventPublisherGeneric, HandshakeFrame, HandshakeKind, InboundFrame,
-  RemoteNodeId, RemoteTransportShared, RemotingEnvelope, TransportBind, TransportChannel, TransportEndpoint,
-  TransportError, TransportHandle, TransportInbound, TransportInboundShared, WireError,
+  AssociationState, CompressionTable, CompressionTableAck, CompressionTableSettings, DeferredEnvelope, EndpointManager,
+  EndpointManagerCommand, EndpointManagerEffect, EndpointReaderGeneric, EndpointWriterError, EndpointWriterGeneric,
+  EventPublisherGeneric, HandshakeFrame, HandshakeKind, InboundCompression, InboundFrame, OutboundMessage,
+  PayloadCompression, QuarantineReason, ReconnectBackoff, RemoteNodeId, RemoteTransportShared, RemotingCapabilities,
+  RemotingEnvelope, RemotingFlightRecorder, RemotingMetrics, SystemAck, SystemMessageDelivery, SystemMessageReceiver,
+  TransportBind, TransportChannel, TransportEndpoint, TransportError, TransportHandle, TransportInbound,
+  TransportInboundShared, WireError,
 };
 
 const OUTBOUND_IDLE_DELAY: Duration = Duration::from_millis(5);
+const BACKPRESSURE_TIMEOUT: Duration = Duration::from_secs(1);
+const MAX_PENDING_ASKS: usize = 1024;
+const ASK_TRACKING_WINDOW: Duration = Duration::from_secs(30);
 
 /// Configuration required to bootstrap the driver.
 pub struct EndpointDriverConfig<TB: RuntimeToolbox + 'static> {
   /// Actor system providing scheduling and state access.
-  pub system:          ActorSystemGeneric<TB>,
+  pub system:             ActorSystemGeneric<TB>,
   /// Shared endpoint writer feeding outbound frames.
-  pub writer:          ArcShared<ToolboxMutex<EndpointWriterGeneric<TB>, TB>>,
+  pub writer:             ArcShared<ToolboxMutex<EndpointWriterGeneric<TB>, TB>>,
   /// Shared endpoint reader decoding inbound frames.
-  pub reader:          ArcShared<EndpointReaderGeneric<TB>>,
+  pub reader:             ArcShared<EndpointReaderGeneric<TB>>,
   /// Active transport implementation wrapped in a mutex for shared mutable access.
-  pub transport:       RemoteTransportShared<TB>,
+  pub transport:          RemoteTransportShared<TB>,
   /// Event publisher for lifecycle/backpressure events.
-  pub event_publisher: EventPublisherGeneric<TB>,
+  pub event_publisher:    EventPublisherGeneric<TB>,
   /// Canonical host used when binding listeners.
-  pub canonical_host:  String,
+  pub canonical_host:     String,
   /// Canonical port used when binding listeners.
-  pub canonical_port:  u16,
+  pub canonical_port:     u16,
   /// Logical system name advertised during handshakes.
-  pub system_name:     String,
+  pub system_name:        String,
+  /// Payload compression offered to peers, if enabled.
+  pub compression:        Option<PayloadCompression>,
+  /// Actor path and manifest compression tables offered to peers, if enabled.
+  pub compression_tables: Option<CompressionTableSettings>,
+  /// Recorder receiving compression ratios.
+  pub flight_recorder:    RemotingFlightRecorder,
+  /// Registry receiving per-association traffic and latency measurements.
+  pub metrics:            RemotingMetrics,
+  /// Maximum number of unacknowledged system messages per association.
+  pub system_buffer_size: usize,
+  /// Backoff applied between reconnect attempts.
+  pub reconnect_backoff:  ReconnectBackoff,
 }
 
 /// Handle controlling driver background tasks.
 pub struct EndpointDriverHandle {
-  send_task: JoinHandle<()>,
+  send_task:    JoinHandle<()>,
+  receive_task: JoinHandle<()>,
 }
 
 impl EndpointDriverHandle {
-  /// Aborts the background outbound loop.
+  /// Aborts the background outbound and inbound loops.
   pub fn shutdown(self) {
     self.send_task.abort();
+    self.receive_task.abort();
   }
 }
 
@@ -70,9 +99,58 @@ pub(crate) struct EndpointDriver<TB: RuntimeToolbox + 'static> {
   listener:        TokioMutex<Option<TransportHandle>>,
   channels:        TokioMutex<BTreeMap<String, TransportChannel>>,
   peers:           TokioMutex<BTreeMap<String, RemoteNodeId>>,
+  // 受信接続のリモートアドレスから handshake で名乗られた正規 authority への対応
+  inbound_peers:   TokioMutex<BTreeMap<String, String>>,
+  // 名乗られた authority がトランスポートの観測した送信元と一致した受信接続だけを保持する
+  verified_peers:  TokioMutex<BTreeMap<String, String>>,
+  // handshake で各 authority が広告した capabilities
+  capabilities:    TokioMutex<BTreeMap<String, RemotingCapabilities>>,
+  compression:     Option<PayloadCompression>,
+  table_settings:  Option<CompressionTableSettings>,
+  // 各 authority から受信したヘッダの集計と、相手に広告したテーブル
+  inbound_tables:  TokioMutex<BTreeMap<String, InboundCompression>>,
+  // 各 authority から広告され、送信時に使うテーブル
+  outbound_tables: TokioMutex<BTreeMap<String, CompressionTable>>,
+  advertised_at:   TokioMutex<u64>,
+  recorder:        RemotingFlightRecorder,
+  metrics:         RemotingMetrics,
+  // reply-to を持つ送信済みリクエスト（応答の往復時間計測用）
+  pending_asks:    TokioMutex<VecDeque<PendingAsk>>,
+  uid:             u64,
+  system_outbound: TokioMutex<BTreeMap<String, SystemMessageDelivery>>,
+  system_inbound:  TokioMutex<BTreeMap<String, SystemMessageReceiver>>,
+  system_buffer:   usize,
+  backoff:         ReconnectBackoff,
+  reconnects:      TokioMutex<BTreeMap<String, PendingReconnect>>,
+  // トランスポートが受け付けなかったフレームを、他の authority の送信を止めずに後で再送する
+  stalled:         TokioMutex<BTreeMap<String, StalledLane>>,
   manager:         EndpointManager,
 }
 
+/// Frames refused by a backpressured connection, kept in send order until the connection drains.
+struct StalledLane {
+  frames: VecDeque<StalledFrame>,
+  since:  Instant,
+}
+
+struct StalledFrame {
+  payload:        Vec<u8>,
+  correlation_id: CorrelationId,
+  // 接続断になった場合に deferred キューへ戻すユーザーメッセージ
+  envelope:       Option<RemotingEnvelope>,
+}
+
+struct PendingReconnect {
+  backoff: ReconnectBackoff,
+  due_at:  u64,
+}
+
+struct PendingAsk {
+  reply_to:  String,
+  authority: String,
+  sent_at:   Instant,
+}
+
 impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
   fn new(config: EndpointDriverConfig<TB>) -> Arc<Self> {
     Arc::new(Self {
@@ -87,6 +165,25 @@ impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
       listener:        TokioMutex::new(None),
       channels:        TokioMutex::new(BTreeMap::<String, TransportChannel>::new()),
       peers:           TokioMutex::new(BTreeMap::<String, RemoteNodeId>::new()),
+      inbound_peers:   TokioMutex::new(BTreeMap::<String, String>::new()),
+      verified_peers:  TokioMutex::new(BTreeMap::<String, String>::new()),
+      capabilities:    TokioMutex::new(BTreeMap::<String, RemotingCapabilities>::new()),
+      compression:     config.compression,
+      table_settings:  config.compression_tables,
+      inbound_tables:  TokioMutex::new(BTreeMap::<String, InboundCompression>::new()),
+      outbound_tables: TokioMutex::new(BTreeMap::<String, CompressionTable>::new()),
+      advertised_at:   TokioMutex::new(0),
+      recorder:        config.flight_recorder,
+      metrics:         config.metrics,
+      pending_asks:    TokioMutex::new(VecDeque::new()),
+      // 再起動したノードを区別するため、起動時刻から uid を割り当てる
+      uid:             SystemTime::now().duration_since(UNIX_EPOCH).map_or(1, |elapsed| elapsed.as_nanos() as u64),
+      system_outbound: TokioMutex::new(BTreeMap::<String, SystemMessageDelivery>::new()),
+      system_inbound:  TokioMutex::new(BTreeMap::<String, SystemMessageReceiver>::new()),
+      system_buffer:   config.system_buffer_size,
+      backoff:         config.reconnect_backoff,
+      reconnects:      TokioMutex::new(BTreeMap::<String, PendingReconnect>::new()),
+      stalled:         TokioMutex::new(BTreeMap::<String, StalledLane>::new()),
       manager:         EndpointManager::new(),
     })
   }
@@ -97,21 +194,45 @@ impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
     let handle = driver.transport.inner().lock().spawn_listener(&bind)?;
     driver.event_publisher.publish_listen_started(bind.authority(), CorrelationId::from_u128(0));
     *driver.listener.try_lock().expect("listener mutex uncontended") = Some(handle);
-    let handler: TransportInboundShared<TB> =
-      ArcShared::new(<TB::MutexFamily as SyncMutexFamily>::create(Box::new(InboundHandler::new(driver.clone()))));
+    let (frames, receiver) = mpsc::unbounded_channel();
+    let handler: TransportInboundShared<TB> = ArcShared::new(<TB::MutexFamily as SyncMutexFamily>::create(Box::new(
+      InboundHandler::new(driver.clone(), frames),
+    )));
     driver.transport.inner().lock().install_inbound_handler(handler);
     let send_task = tokio::spawn(Self::drive_outbound(driver.clone()));
-    Ok(EndpointDriverHandle { send_task })
+    let receive_task = tokio::spawn(Self::drive_inbound(driver.clone(), receiver));
+    Ok(EndpointDriverHandle { send_task, receive_task })
+  }
+
+  /// Processes inbound frames one at a time so that handshakes and sequenced system messages
+  /// are observed in arrival order.
+  async fn drive_inbound(self: Arc<Self>, mut frames: mpsc::UnboundedReceiver<InboundFrame>) {
+    while let Some(frame) = frames.recv().await {
+      self.handle_inbound_frame(frame).await;
+    }
   }
 
   async fn drive_outbound(self: Arc<Self>) {
     loop {
-      let next = {
+      self.poll_reconnects().await;
+      self.flush_stalled().await;
+      self.advertise_tables().await;
+      let (next, depths) = {
         let mut writer = self.writer.lock();
-        writer.try_next()
+        let next = writer.try_next_message();
+        let depths: Vec<(String, usize)> =
+          writer.queue_depths().map(|(authority, depth)| (authority.to_string(), depth)).collect();
+        (next, depths)
       };
+      for (authority, depth) in depths {
+        self.metrics.set_outbound_queue_depth(&authority, depth);
+      }
+      let next = next.and_then(|message| message.map(|message| self.serialize_timed(message)).transpose());
       match next {
         | Ok(Some(envelope)) => {
+          if let Some(authority) = Self::target_authority(envelope.remote_node()) {
+            self.track_ask(&authority, &envelope).await;
+          }
           if let Err(error) = self.handle_outbound_envelope(envelope).await {
             self.emit_error(format!("failed to process outbound envelope: {error:?}"));
           }
@@ -125,6 +246,20 @@ impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
     }
   }
 
+  /// Serializes `message`, recording only the time spent in the serializer.
+  fn serialize_timed(&self, message: OutboundMessage<TB>) -> Result<RemotingEnvelope, EndpointWriterError> {
+    let authority = Self::target_authority(message.remote_node());
+    let writer = self.writer.lock();
+    let started = Instant::now();
+    let result = writer.serialize_outbound(message);
+    let elapsed = started.elapsed();
+    drop(writer);
+    if let (Ok(_), Some(authority)) = (&result, authority) {
+      self.metrics.record_serialization(&authority, elapsed);
+    }
+    result
+  }
+
   async fn handle_outbound_envelope(&self, envelope: RemotingEnvelope) -> Result<(), TransportError> {
     let authority = Self::target_authority(envelope.remote_node())
       .ok_or_else(|| TransportError::AuthorityNotBound("missing remote authority".into()))?;
@@ -136,7 +271,11 @@ impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
     });
     self.process_effects(enqueue.effects).await?;
 
-    if !matches!(self.manager.state(&authority), Some(AssociationState::Connected { .. })) {
+    // Gated/Quarantined の間は再接続処理に任せ、ここでは handshake を始めない
+    if matches!(
+      self.manager.state(&authority),
+      None | Some(AssociationState::Unassociated | AssociationState::Associating { .. })
+    ) {
       let endpoint = TransportEndpoint::new(authority.clone());
       let associate = self.manager.handle(EndpointManagerCommand::Associate {
         authority: authority.clone(),
@@ -184,9 +323,13 @@ impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
     let channel = self.transport.inner().lock().open_channel(endpoint)?;
     self.channels.lock().await.insert(authority.to_string(), channel);
     if let Some(remote) = self.peers.lock().await.get(authority).cloned() {
-      let handshake = HandshakeFrame::new(HandshakeKind::Offer, &self.system_name, &self.host, Some(self.port), 0);
-      let payload = handshake.encode();
+      let payload = self.handshake(HandshakeKind::Offer).encode();
       self.transport.inner().lock().send(&channel, &payload, CorrelationId::nil())?;
+      // 切断前に送った未確認のシステムメッセージを、新しいメッセージより先に再送する
+      let pending = self.system_outbound.lock().await.get(authority).map(SystemMessageDelivery::pending);
+      for (seq, envelope) in pending.unwrap_or_default() {
+        self.send_sequenced(authority, seq, &envelope).await?;
+      }
       let accept = self.manager.handle(EndpointManagerCommand::HandshakeAccepted {
         authority:   authority.to_string(),
         remote_node: remote,
@@ -198,13 +341,323 @@ impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
   }
 
   async fn flush_envelope(&self, authority: &str, deferred: DeferredEnvelope) -> Result<(), TransportError> {
+    if matches!(self.manager.state(authority), Some(AssociationState::Gated { .. })) {
+      // 再接続待ちの間は deferred キューに戻し、接続回復後に配送する
+      self.defer(authority, deferred);
+      return Ok(());
+    }
     let envelope = deferred.into_envelope();
-    let payload = envelope.encode_frame();
+    let result = if envelope.is_system() {
+      let seq = self
+        .system_outbound
+        .lock()
+        .await
+        .entry(authority.to_string())
+        .or_insert_with(|| SystemMessageDelivery::new(self.system_buffer))
+        .send(envelope.clone());
+      let Some(seq) = seq else {
+        self.quarantine(authority, QuarantineReason::new("system message buffer overflow"));
+        return Ok(());
+      };
+      self.send_sequenced(authority, seq, &envelope).await
+    } else {
+      self.send_envelope(authority, &envelope).await
+    };
+    match result {
+      | Ok(()) => Ok(()),
+      // サイズ超過は送信側のメッセージの問題なので、他のメッセージの配送は続ける
+      | Err(error @ TransportError::FrameTooLarge { .. }) => {
+        self.emit_error(format!("dropped outbound envelope for {authority}: {error}"));
+        Ok(())
+      },
+      | Err(error) => {
+        self.emit_error(format!("connection to {authority} lost: {error}"));
+        self.schedule_reconnect(authority).await;
+        // システムメッセージは再送バッファに残っているため、ユーザーメッセージのみ戻す
+        if !envelope.is_system() {
+          self.defer(authority, DeferredEnvelope::new(envelope));
+        }
+        Ok(())
+      },
+    }
+  }
+
+  async fn send_envelope(&self, authority: &str, envelope: &RemotingEnvelope) -> Result<(), TransportError> {
+    let payload = self.encode_envelope(authority, envelope).await?;
+    self.send_or_park(authority, &payload, envelope.correlation_id(), Some(envelope)).await?;
+    self.metrics.record_sent(authority, payload.len());
+    Ok(())
+  }
+
+  async fn send_sequenced(&self, authority: &str, seq: u64, envelope: &RemotingEnvelope) -> Result<(), TransportError> {
+    let frame = self.encode_envelope(authority, envelope).await?;
+    let payload = SystemMessageDelivery::encode_sequenced(seq, &frame);
+    // システムメッセージは再送バッファに残っているため、接続断のときに戻す必要はない
+    self.send_or_park(authority, &payload, envelope.correlation_id(), None).await?;
+    self.metrics.record_sent(authority, payload.len());
+    Ok(())
+  }
+
+  /// Encodes the envelope in the frame version negotiated with the peer, with the negotiated
+  /// payload compression and the table advertised by the peer, if any.
+  ///
+  /// A field too large for the wire format is reported as [`TransportError::FrameTooLarge`] so
+  /// that only this envelope is dropped.
+  async fn encode_envelope(&self, authority: &str, envelope: &RemotingEnvelope) -> Result<Vec<u8>, TransportError> {
+    // handshake の応答前は相手の版が分からないため、現行の形式で送る
+    let capabilities = self
+      .capabilities
+      .lock()
+      .await
+      .get(authority)
+      .map_or(RemotingCapabilities::ENVELOPE_FLAGS, |remote| self.local_capabilities().intersection(*remote));
+    let compression = self.negotiated_compression(authority).await;
+    let (frame, sample) = envelope
+      .encode_frame_for(capabilities, compression.as_ref(), self.outbound_tables.lock().await.get(authority))
+      .map_err(|error| match error {
+        | WireError::FieldTooLarge { size, max } => TransportError::FrameTooLarge { size, max },
+        | other => TransportError::Io(format!("failed to encode envelope: {other:?}")),
+      })?;
+    if let Some(sample) = sample {
+      self.recorder.record_compression(authority, sample, envelope.correlation_id(), self.now_millis());
+    }
+    Ok(frame)
+  }
+
+  async fn send_payload(
+    &self,
+    authority: &str,
+    payload: &[u8],
+    correlation_id: CorrelationId,
+  ) -> Result<(), TransportError> {
+    self.send_or_park(authority, payload, correlation_id, None).await
+  }
+
+  /// Sends `payload`, parking it behind the frames already waiting for `authority` when the
+  /// connection is backpressured so that the outbound loop keeps serving other associations.
+  ///
+  /// `envelope` is handed back to the deferred queue if the connection is lost while parked.
+  async fn send_or_park(
+    &self,
+    authority: &str,
+    payload: &[u8],
+    correlation_id: CorrelationId,
+    envelope: Option<&RemotingEnvelope>,
+  ) -> Result<(), TransportError> {
     let channel = self.ensure_channel(authority).await?;
-    self.transport.inner().lock().send(&channel, &payload, envelope.correlation_id())?;
+    let mut stalled = self.stalled.lock().await;
+    if !stalled.contains_key(authority) {
+      match self.transport.inner().lock().send(&channel, payload, correlation_id) {
+        | Err(TransportError::Backpressure(_)) => {},
+        | other => return other,
+      }
+      self.apply_stall_backpressure(authority, BackpressureSignal::Apply);
+    }
+    let frame = StalledFrame { payload: payload.to_vec(), correlation_id, envelope: envelope.cloned() };
+    stalled
+      .entry(authority.to_string())
+      .or_insert_with(|| StalledLane { frames: VecDeque::new(), since: Instant::now() })
+      .frames
+      .push_back(frame);
     Ok(())
   }
 
+  /// Resends parked frames in order, treating a connection that stays backpressured for too long
+  /// as lost.
+  async fn flush_stalled(&self) {
+    let authorities: Vec<String> = self.stalled.lock().await.keys().cloned().collect();
+    for authority in authorities {
+      let Some(channel) = self.channels.lock().await.get(&authority).copied() else {
+        self.discard_stalled(&authority).await;
+        continue;
+      };
+      let mut stalled = self.stalled.lock().await;
+      let Some(lane) = stalled.get_mut(&authority) else {
+        continue;
+      };
+      let mut failure = None;
+      while let Some(frame) = lane.frames.front() {
+        match self.transport.inner().lock().send(&channel, &frame.payload, frame.correlation_id) {
+          | Ok(()) => {
+            lane.frames.pop_front();
+            lane.since = Instant::now();
+          },
+          | Err(TransportError::Backpressure(_)) if lane.since.elapsed() < BACKPRESSURE_TIMEOUT => break,
+          | Err(error) => {
+            failure = Some(error);
+            break;
+          },
+        }
+      }
+      if lane.frames.is_empty() {
+        stalled.remove(&authority);
+        drop(stalled);
+        self.apply_stall_backpressure(&authority, BackpressureSignal::Release);
+      } else if let Some(error) = failure {
+        drop(stalled);
+        // 解除されないままの接続は切断として扱い、再接続に任せる
+        self.emit_error(format!("connection to {authority} lost: {error}"));
+        self.schedule_reconnect(&authority).await;
+      }
+    }
+  }
+
+  /// Drops the frames parked for `authority`, returning user messages to the deferred queue.
+  async fn discard_stalled(&self, authority: &str) {
+    let Some(lane) = self.stalled.lock().await.remove(authority) else {
+      return;
+    };
+    for envelope in lane.frames.into_iter().filter_map(|frame| frame.envelope) {
+      self.defer(authority, DeferredEnvelope::new(envelope));
+    }
+    self.apply_stall_backpressure(authority, BackpressureSignal::Release);
+  }
+
+  // 詰まった接続へのユーザーメッセージの取り出しを止め、送信者側にも背圧を伝える
+  fn apply_stall_backpressure(&self, authority: &str, signal: BackpressureSignal) {
+    self.writer.lock().handle_backpressure(authority, signal);
+    self.event_publisher.publish_backpressure(authority.to_string(), signal, CorrelationId::nil());
+  }
+
+  fn defer(&self, authority: &str, deferred: DeferredEnvelope) {
+    let _ = self.manager.handle(EndpointManagerCommand::EnqueueDeferred {
+      authority: authority.to_string(),
+      envelope:  Box::new(deferred),
+    });
+  }
+
+  fn quarantine(&self, authority: &str, reason: QuarantineReason) {
+    self.emit_error(format!("quarantining {authority}: {}", reason.message()));
+    let quarantine = self.manager.handle(EndpointManagerCommand::Quarantine {
+      authority: authority.to_string(),
+      reason,
+      resume_at: None,
+      now: self.now_millis(),
+    });
+    self.publish_effects(quarantine.effects);
+  }
+
+  /// Publishes lifecycle effects of commands that never start handshakes or deliveries.
+  fn publish_effects(&self, effects: Vec<EndpointManagerEffect>) {
+    for effect in effects {
+      match effect {
+        | EndpointManagerEffect::DiscardDeferred { authority, .. } => {
+          self.emit_error(format!("discarded deferred envelopes for {authority}"));
+        },
+        | EndpointManagerEffect::Lifecycle(event) => self.event_publisher.publish_lifecycle(event),
+        | EndpointManagerEffect::StartHandshake { .. } | EndpointManagerEffect::DeliverEnvelopes { .. } => {},
+      }
+    }
+  }
+
+  /// Gates the authority and schedules the next reconnect attempt.
+  async fn schedule_reconnect(&self, authority: &str) {
+    if let Some(channel) = self.channels.lock().await.remove(authority) {
+      self.transport.inner().lock().close(&channel);
+    }
+    let now = self.now_millis();
+    let due_at = {
+      let mut reconnects = self.reconnects.lock().await;
+      let pending = reconnects
+        .entry(authority.to_string())
+        .or_insert_with(|| PendingReconnect { backoff: self.backoff, due_at: now });
+      pending.due_at = now + pending.backoff.next_delay().as_millis() as u64;
+      pending.due_at
+    };
+    let gate = self.manager.handle(EndpointManagerCommand::Gate {
+      authority: authority.to_string(),
+      resume_at: Some(due_at),
+      now,
+    });
+    self.publish_effects(gate.effects);
+    // Gated に移した後で戻すことで、詰まっていたユーザーメッセージを再接続後に配送する
+    self.discard_stalled(authority).await;
+  }
+
+  async fn poll_reconnects(&self) {
+    let now = self.now_millis();
+    let due: Vec<String> = self
+      .reconnects
+      .lock()
+      .await
+      .iter()
+      .filter(|(_, pending)| pending.due_at <= now)
+      .map(|(authority, _)| authority.clone())
+      .collect();
+    for authority in due {
+      self.metrics.record_reconnect(&authority);
+      let recover = self.manager.handle(EndpointManagerCommand::Recover {
+        authority: authority.clone(),
+        endpoint: Some(TransportEndpoint::new(authority.clone())),
+        now,
+      });
+      match self.process_effects(recover.effects).await {
+        | Ok(()) => {
+          self.reconnects.lock().await.remove(&authority);
+        },
+        | Err(error) => {
+          self.emit_error(format!("reconnect to {authority} failed: {error}"));
+          self.schedule_reconnect(&authority).await;
+        },
+      }
+    }
+  }
+
+  fn local_capabilities(&self) -> RemotingCapabilities {
+    let mut capabilities = RemotingCapabilities::ENVELOPE_FLAGS;
+    if self.compression.is_some() {
+      capabilities = capabilities.union(RemotingCapabilities::LZ4_COMPRESSION);
+    }
+    if self.table_settings.is_some() {
+      capabilities = capabilities.union(RemotingCapabilities::COMPRESSION_TABLES);
+    }
+    capabilities
+  }
+
+  fn handshake(&self, kind: HandshakeKind) -> HandshakeFrame {
+    HandshakeFrame::new(kind, &self.system_name, &self.host, Some(self.port), self.uid)
+      .with_capabilities(self.local_capabilities())
+  }
+
+  async fn negotiated_compression(&self, authority: &str) -> Option<PayloadCompression> {
+    let remote = self.capabilities.lock().await.get(authority).copied().unwrap_or(RemotingCapabilities::NONE);
+    self
+      .compression
+      .filter(|_| self.local_capabilities().intersection(remote).contains(RemotingCapabilities::LZ4_COMPRESSION))
+  }
+
+  async fn tables_negotiated(&self, authority: &str) -> bool {
+    let remote = self.capabilities.lock().await.get(authority).copied().unwrap_or(RemotingCapabilities::NONE);
+    self.local_capabilities().intersection(remote).contains(RemotingCapabilities::COMPRESSION_TABLES)
+  }
+
+  /// Advertises changed (or still unacknowledged) compression tables once per interval.
+  async fn advertise_tables(&self) {
+    let Some(settings) = self.table_settings else {
+      return;
+    };
+    let now = self.now_millis();
+    {
+      let mut advertised_at = self.advertised_at.lock().await;
+      if now < *advertised_at + settings.advertisement_interval().as_millis() as u64 {
+        return;
+      }
+      *advertised_at = now;
+    }
+    let advertisements: Vec<(String, CompressionTable)> = self
+      .inbound_tables
+      .lock()
+      .await
+      .iter_mut()
+      .filter_map(|(authority, tables)| tables.next_advertisement().map(|table| (authority.clone(), table)))
+      .collect();
+    for (authority, table) in advertisements {
+      if let Err(error) = self.send_payload(&authority, &table.encode(), CorrelationId::nil()).await {
+        self.emit_error(format!("failed to advertise compression table to {authority}: {error}"));
+      }
+    }
+  }
+
   async fn ensure_channel(&self, authority: &str) -> Result<TransportChannel, TransportError> {
     if !self.channels.lock().await.contains_key(authority) {
       let endpoint = TransportEndpoint::new(authority.to_string());
@@ -219,6 +672,42 @@ impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
     self.system.emit_log(LogLevel::Error, message, None);
   }
 
+  /// Remembers an ask, i.e. a request replying to a `/temp` path, so that the reply can be timed.
+  async fn track_ask(&self, authority: &str, envelope: &RemotingEnvelope) {
+    // tell の送信者を reply_to に持つだけの通常メッセージは応答を待たないため対象外
+    let Some(reply_to) = envelope.reply_to().filter(|path| {
+      !envelope.is_system() && path.segments().first().is_some_and(|segment| segment.as_str() == "temp")
+    }) else {
+      return;
+    };
+    let mut pending = self.pending_asks.lock().await;
+    if pending.len() == MAX_PENDING_ASKS {
+      pending.pop_front();
+    }
+    pending.push_back(PendingAsk {
+      reply_to:  reply_to.to_relative_string(),
+      authority: authority.to_string(),
+      sent_at:   Instant::now(),
+    });
+  }
+
+  /// Records the round-trip time when `envelope` answers the oldest matching request.
+  async fn complete_ask(&self, authority: &str, envelope: &RemotingEnvelope) {
+    let now = Instant::now();
+    let mut pending = self.pending_asks.lock().await;
+    // 応答のないリクエスト（tell の送信者など）が古い応答と誤って対応付かないよう期限切れを捨てる
+    pending.retain(|ask| now.duration_since(ask.sent_at) < ASK_TRACKING_WINDOW);
+    if pending.is_empty() {
+      return;
+    }
+    let recipient = envelope.recipient().to_relative_string();
+    if let Some(index) = pending.iter().position(|ask| ask.authority == authority && ask.reply_to == recipient)
+      && let Some(ask) = pending.remove(index)
+    {
+      self.metrics.record_ask_latency(authority, now.duration_since(ask.sent_at));
+    }
+  }
+
   fn now_millis(&self) -> u64 {
     self.system.state().monotonic_now().as_millis() as u64
   }
@@ -233,22 +722,63 @@ impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
     }
     match frame.payload()[1] {
       | 0x01 | 0x02 => {
-        if let Err(error) = self.process_handshake_payload(frame.payload().to_vec()).await {
+        if let Err(error) = self.process_handshake_payload(&frame).await {
           self.emit_error(format!("failed to decode handshake: {error:?}"));
         }
       },
-      | 0x10 => match RemotingEnvelope::decode_frame(frame.payload(), frame.correlation_id()) {
-        | Ok(envelope) => self.deliver_inbound(envelope).await,
+      | 0x10 => match self.decode_envelope(frame.remote_address(), frame.payload(), frame.correlation_id()).await {
+        | Ok(envelope) => {
+          let authority = self.sender_authority(frame.remote_address()).await;
+          let verified = self.verified_authority(frame.remote_address()).await;
+          self.deliver_inbound(authority.as_deref(), verified.as_deref(), envelope).await;
+        },
         | Err(error) => self.emit_error(format!("failed to decode envelope: {error:?}")),
       },
+      | 0x11 => {
+        if let Err(error) = self.process_sequenced_payload(&frame).await {
+          self.emit_error(format!("failed to process system message: {error:?}"));
+        }
+      },
+      | 0x20 | 0x21 => match SystemAck::decode(frame.payload()) {
+        | Ok(ack) => self.process_system_ack(frame.remote_address(), ack).await,
+        | Err(error) => self.emit_error(format!("failed to decode system ack: {error:?}")),
+      },
+      | 0x30 => match CompressionTable::decode(frame.payload()) {
+        | Ok(table) => self.process_table_advertisement(frame.remote_address(), table).await,
+        | Err(error) => self.emit_error(format!("failed to decode compression table: {error:?}")),
+      },
+      | 0x31 => match CompressionTableAck::decode(frame.payload()) {
+        | Ok(ack) => self.process_table_ack(frame.remote_address(), ack).await,
+        | Err(error) => self.emit_error(format!("failed to decode compression table ack: {error:?}")),
+      },
       | _ => {},
     }
   }
 
-  async fn process_handshake_payload(&self, payload: Vec<u8>) -> Result<(), WireError> {
-    let frame = HandshakeFrame::decode(&payload)?;
+  async fn process_handshake_payload(&self, inbound: &InboundFrame) -> Result<(), WireError> {
+    let remote_address = inbound.remote_address();
+    let frame = HandshakeFrame::decode(inbound.payload())?;
     if let Some(port) = frame.port() {
       let authority = format!("{}:{port}", frame.host());
+      self.inbound_peers.lock().await.insert(remote_address.to_string(), authority.clone());
+      let mut verified = self.verified_peers.lock().await;
+      if Self::binds_authority(inbound, &authority, frame.host()) {
+        verified.insert(remote_address.to_string(), authority.clone());
+      } else {
+        verified.remove(remote_address);
+      }
+      drop(verified);
+      self.capabilities.lock().await.insert(authority.clone(), frame.capabilities());
+      self.track_incarnation(&authority, frame.uid()).await;
+      self.metrics.record_handshake(&authority);
+      // Offer の送信側は既に接続済みとしているため、Ack では capabilities の記録だけ行う
+      if frame.kind() == HandshakeKind::Ack {
+        return Ok(());
+      }
+      // 自ノードの capabilities を Ack で返し、相手側でも圧縮可否を判断できるようにする
+      if let Err(error) = self.reply_handshake(&authority).await {
+        self.emit_error(format!("failed to acknowledge handshake from {authority}: {error:?}"));
+      }
       let remote =
         RemoteNodeId::new(frame.system_name().to_string(), frame.host().to_string(), frame.port(), frame.uid());
       let accept = self.manager.handle(EndpointManagerCommand::HandshakeAccepted {
@@ -261,8 +791,168 @@ impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
     Ok(())
   }
 
-  async fn deliver_inbound(&self, envelope: RemotingEnvelope) {
-    match self.reader.decode(envelope) {
+  /// Resets sequence tracking when the peer restarted with a new uid.
+  async fn track_incarnation(&self, authority: &str, uid: u64) {
+    let mut inbound = self.system_inbound.lock().await;
+    match inbound.get(authority).map(SystemMessageReceiver::incarnation) {
+      | Some(known) if known == uid => {},
+      | known => {
+        inbound.insert(authority.to_string(), SystemMessageReceiver::new(uid));
+        // 再起動したノードは以前のシーケンス番号や圧縮テーブルを知らないため、送信側も作り直す
+        if known.is_some() {
+          self.system_outbound.lock().await.remove(authority);
+          self.inbound_tables.lock().await.remove(authority);
+          self.outbound_tables.lock().await.remove(authority);
+        }
+      },
+    }
+  }
+
+  async fn sender_authority(&self, remote_address: &str) -> Option<String> {
+    self.inbound_peers.lock().await.get(remote_address).cloned()
+  }
+
+  /// Returns the sender authority only when the transport vouches for it; inbound filters see
+  /// this value so that a peer cannot borrow another node's access rules by naming it.
+  async fn verified_authority(&self, remote_address: &str) -> Option<String> {
+    self.verified_peers.lock().await.get(remote_address).cloned()
+  }
+
+  fn binds_authority(frame: &InboundFrame, authority: &str, host: &str) -> bool {
+    // トランスポートが認証した識別子があればそれだけを信頼し、無ければ観測した送信元ホストと照合する
+    match frame.peer_authority() {
+      | Some(peer) => peer == authority,
+      | None => observed_host(frame.remote_address()) == Some(host),
+    }
+  }
+
+  async fn process_sequenced_payload(&self, frame: &InboundFrame) -> Result<(), WireError> {
+    let (seq, inner) = SystemMessageDelivery::decode_sequenced(frame.payload())?;
+    let envelope = self.decode_envelope(frame.remote_address(), inner, frame.correlation_id()).await?;
+    let Some(authority) = self.sender_authority(frame.remote_address()).await else {
+      // handshake 前のフレームは送信元を特定できないため、確認応答せずに破棄して再送を待つ
+      self.emit_error(format!("dropped system message from unknown peer {}", frame.remote_address()));
+      return Ok(());
+    };
+    let (deliver, ack) = match self.system_inbound.lock().await.get_mut(&authority) {
+      | Some(receiver) => receiver.receive(seq),
+      | None => return Ok(()),
+    };
+    if deliver {
+      let verified = self.verified_authority(frame.remote_address()).await;
+      self.deliver_inbound(Some(&authority), verified.as_deref(), envelope).await;
+    }
+    if let Err(error) = self.send_payload(&authority, &ack.encode(), CorrelationId::nil()).await {
+      self.emit_error(format!("failed to acknowledge system message to {authority}: {error}"));
+    }
+    Ok(())
+  }
+
+  /// Decodes an envelope using the tables advertised to its sender and counts its header strings.
+  async fn decode_envelope(
+    &self,
+    remote_address: &str,
+    payload: &[u8],
+    correlation_id: CorrelationId,
+  ) -> Result<RemotingEnvelope, WireError> {
+    let authority = self.sender_authority(remote_address).await;
+    let negotiated = match authority.as_deref() {
+      | Some(authority) => self.tables_negotiated(authority).await,
+      | None => false,
+    };
+    let mut inbound_tables = self.inbound_tables.lock().await;
+    let tables = authority.as_deref().and_then(|authority| inbound_tables.get(authority));
+    let envelope = RemotingEnvelope::decode_frame_with_tables(payload, correlation_id, tables)?;
+    if let Some(authority) = authority.as_deref() {
+      self.metrics.record_received(authority, payload.len());
+    }
+    if let (Some(authority), Some(settings), true) = (authority, self.table_settings, negotiated) {
+      let reply_to = envelope.reply_to().map(|path| path.to_canonical_uri());
+      inbound_tables.entry(authority).or_insert_with(|| InboundCompression::new(&settings)).record(
+        &envelope.recipient().to_canonical_uri(),
+        reply_to.as_deref(),
+        envelope.serialized_message().manifest(),
+      );
+    }
+    Ok(envelope)
+  }
+
+  async fn process_table_advertisement(&self, remote_address: &str, table: CompressionTable) {
+    let Some(authority) = self.sender_authority(remote_address).await else {
+      return;
+    };
+    if !self.tables_negotiated(&authority).await {
+      return;
+    }
+    let ack = CompressionTableAck::new(table.version());
+    self.outbound_tables.lock().await.insert(authority.clone(), table);
+    if let Err(error) = self.send_payload(&authority, &ack.encode(), CorrelationId::nil()).await {
+      self.emit_error(format!("failed to acknowledge compression table to {authority}: {error}"));
+    }
+  }
+
+  async fn process_table_ack(&self, remote_address: &str, ack: CompressionTableAck) {
+    let Some(authority) = self.sender_authority(remote_address).await else {
+      return;
+    };
+    if let Some(tables) = self.inbound_tables.lock().await.get_mut(&authority) {
+      tables.acknowledge(ack);
+    }
+  }
+
+  async fn process_system_ack(&self, remote_address: &str, ack: SystemAck) {
+    let Some(authority) = self.sender_authority(remote_address).await else {
+      return;
+    };
+    let resend = match self.system_outbound.lock().await.get_mut(&authority) {
+      | Some(delivery) => delivery.acknowledge(ack),
+      | None => return,
+    };
+    for (seq, envelope) in resend {
+      if let Err(error) = self.send_sequenced(&authority, seq, &envelope).await {
+        self.emit_error(format!("failed to resend system message to {authority}: {error}"));
+        break;
+      }
+    }
+  }
+
+  async fn reply_handshake(&self, authority: &str) -> Result<(), TransportError> {
+    let channel = self.ensure_channel(authority).await?;
+    let payload = self.handshake(HandshakeKind::Ack).encode();
+    self.transport.inner().lock().send(&channel, &payload, CorrelationId::nil())
+  }
+
+  async fn quarantine_rejected_peer(&self, remote_address: &str, error: String) {
+    let claimed = self.inbound_peers.lock().await.remove(remote_address);
+    // 検証済みの handshake authority だけを隔離する。ソケットアドレスや未検証の申告を隔離すると、
+    // 他ノードを騙った接続で正規のピアを隔離できてしまう
+    let Some(authority) = self.verified_peers.lock().await.remove(remote_address) else {
+      let peer = claimed.unwrap_or_else(|| remote_address.to_string());
+      self.emit_error(format!("rejected unverified inbound connection from {peer} ({remote_address}): {error}"));
+      return;
+    };
+    self.emit_error(format!("rejected inbound connection from {authority}: {error}"));
+    let quarantine = self.manager.handle(EndpointManagerCommand::Quarantine {
+      authority,
+      reason: QuarantineReason::new(format!("framing violation: {error}")),
+      resume_at: None,
+      now: self.now_millis(),
+    });
+    if let Err(error) = self.process_effects(quarantine.effects).await {
+      self.emit_error(format!("failed to quarantine peer: {error:?}"));
+    }
+  }
+
+  async fn deliver_inbound(&self, authority: Option<&str>, verified: Option<&str>, envelope: RemotingEnvelope) {
+    if let Some(authority) = authority {
+      self.complete_ask(authority, &envelope).await;
+    }
+    let started = Instant::now();
+    let decoded = self.reader.decode_from(verified, envelope);
+    if let Some(authority) = authority {
+      self.metrics.record_deserialization(authority, started.elapsed());
+    }
+    match decoded {
       | Ok(inbound) => {
         if let Err(error) = self.reader.deliver(inbound) {
           self.emit_error(format!("failed to deliver inbound envelope: {error:?}"));
@@ -273,21 +963,37 @@ impl<TB: RuntimeToolbox + 'static> EndpointDriver<TB> {
   }
 }
 
+/// Extracts the host of a `host:port` / `[v6]:port` socket address observed by a transport.
+fn observed_host(address: &str) -> Option<&str> {
+  if let Some(rest) = address.strip_prefix('[') {
+    return rest.split_once(']').map(|(host, _)| host);
+  }
+  address.rsplit_once(':').map(|(host, _)| host)
+}
+
 struct InboundHandler<TB: RuntimeToolbox + 'static> {
   driver: Arc<EndpointDriver<TB>>,
+  frames: mpsc::UnboundedSender<InboundFrame>,
 }
 
 impl<TB: RuntimeToolbox + 'static> InboundHandler<TB> {
-  fn new(driver: Arc<EndpointDriver<TB>>) -> Self {
-    Self { driver }
+  const fn new(driver: Arc<EndpointDriver<TB>>, frames: mpsc::UnboundedSender<InboundFrame>) -> Self {
+    Self { driver, frames }
   }
 }
 
 impl<TB: RuntimeToolbox + 'static> TransportInbound for InboundHandler<TB> {
   fn on_frame(&mut self, frame: InboundFrame) {
+    // 受信ループが停止している場合はフレームを破棄する
+    let _ = self.frames.send(frame);
+  }
+
+  fn on_frame_rejected(&mut self, remote_address: &str, error: &TransportError) {
     let driver = self.driver.clone();
+    let remote_address = remote_address.to_string();
+    let error = error.to_string();
     tokio::spawn(async move {
-      driver.handle_inbound_frame(frame).await;
+      driver.quarantine_rejected_peer(&remote_address, error).await;
     });
   }
 }
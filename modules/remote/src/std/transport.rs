@@ -1,9 +1,25 @@
 //! Standard library transport implementations.
 
 mod factory;
+mod in_process_network;
+mod in_process_transport;
+mod link_conditions;
+#[cfg(feature = "tls")]
+mod tls_config;
+#[cfg(feature = "tls")]
+mod tls_context;
 #[cfg(feature = "tokio-transport")]
 pub mod tokio_tcp;
+#[cfg(all(feature = "tokio-transport", unix))]
+mod unix_socket;
 
 pub use factory::StdTransportFactory;
+pub use in_process_network::InProcessNetwork;
+pub use in_process_transport::InProcessTransport;
+pub use link_conditions::LinkConditions;
+#[cfg(feature = "tls")]
+pub use tls_config::TlsConfig;
 #[cfg(feature = "tokio-transport")]
 pub use tokio_tcp::TokioTcpTransport;
+#[cfg(all(feature = "tokio-transport", unix))]
+pub use unix_socket::UnixSocketTransport;
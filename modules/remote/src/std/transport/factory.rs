@@ -4,11 +4,17 @@ use alloc::{boxed::Box, string::ToString};
 
 use fraktor_utils_rs::std::runtime_toolbox::StdToolbox;
 
+#[cfg(feature = "tls")]
+use super::tls_config::TlsConfig;
 #[cfg(feature = "tokio-transport")]
 use super::tokio_tcp::TokioTcpTransport;
+#[cfg(all(feature = "tokio-transport", unix))]
+use super::unix_socket::UnixSocketTransport;
+use super::{in_process_network::InProcessNetwork, in_process_transport::InProcessTransport};
 use crate::core::{LoopbackTransport, RemoteTransport, RemotingExtensionConfig, TransportError};
 
-/// Standard library transport factory that supports both loopback and Tokio TCP.
+/// Standard library transport factory that supports loopback, in-process, Tokio TCP, Unix domain
+/// sockets and, with the `tls` feature, Tokio TCP over TLS.
 ///
 /// This factory is specialized for [`StdToolbox`] because the Tokio TCP transport
 /// requires standard library mutex implementations for async `Send + Sync` bounds.
@@ -22,16 +28,55 @@ impl StdTransportFactory {
   pub fn build(config: &RemotingExtensionConfig) -> Result<Box<dyn RemoteTransport<StdToolbox>>, TransportError> {
     match config.transport_scheme() {
       | "fraktor.loopback" => Ok(Box::new(LoopbackTransport::<StdToolbox>::default())),
+      | "fraktor.inproc" => {
+        let network = config
+          .transport_extension::<InProcessNetwork>()
+          .cloned()
+          .ok_or_else(|| TransportError::Io("fraktor.inproc requires an in-process network".into()))?;
+        Ok(Box::new(InProcessTransport::new(network)))
+      },
       | "pekko.tcp" | "fraktor.tcp" => {
         #[cfg(feature = "tokio-transport")]
         {
-          TokioTcpTransport::build().map(|transport| Box::new(transport) as Box<dyn RemoteTransport<StdToolbox>>)
+          TokioTcpTransport::build()
+            .map(|transport| transport.with_transport_config(config.tokio_transport_config()))
+            .map(|transport| Box::new(transport) as Box<dyn RemoteTransport<StdToolbox>>)
         }
         #[cfg(not(feature = "tokio-transport"))]
         {
           Err(TransportError::UnsupportedScheme(config.transport_scheme().to_string()))
         }
       },
+      | "fraktor.tcp+tls" => {
+        #[cfg(feature = "tls")]
+        {
+          let tls = config
+            .transport_extension::<TlsConfig>()
+            .cloned()
+            .ok_or_else(|| TransportError::Tls("fraktor.tcp+tls requires a TLS configuration".into()))?;
+          let transport =
+            TokioTcpTransport::build()?.with_transport_config(config.tokio_transport_config()).with_tls(tls)?;
+          Ok(Box::new(transport) as Box<dyn RemoteTransport<StdToolbox>>)
+        }
+        #[cfg(not(feature = "tls"))]
+        {
+          Err(TransportError::UnsupportedScheme(config.transport_scheme().to_string()))
+        }
+      },
+      | "fraktor.uds" => {
+        #[cfg(all(feature = "tokio-transport", unix))]
+        {
+          let mut transport = UnixSocketTransport::try_new()?.with_transport_config(config.tokio_transport_config());
+          if let Some(dir) = config.unix_socket_dir() {
+            transport = transport.with_socket_dir(dir);
+          }
+          Ok(Box::new(transport) as Box<dyn RemoteTransport<StdToolbox>>)
+        }
+        #[cfg(not(all(feature = "tokio-transport", unix)))]
+        {
+          Err(TransportError::UnsupportedScheme(config.transport_scheme().to_string()))
+        }
+      },
       | scheme => Err(TransportError::UnsupportedScheme(scheme.to_string())),
     }
   }
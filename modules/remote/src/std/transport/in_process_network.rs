@@ -0,0 +1,343 @@
+//! Registry connecting the in-process transports of several actor systems.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  string::{String, ToString},
+  vec::Vec,
+};
+use core::time::Duration;
+
+use fraktor_utils_rs::{
+  core::{runtime_toolbox::NoStdMutex, sync::ArcShared},
+  std::runtime_toolbox::StdToolbox,
+};
+
+use super::link_conditions::LinkConditions;
+use crate::core::{InboundFrame, TransportError, TransportInboundShared};
+
+/// Inbound handler slot of a transport; the handler may be installed after the listener binds.
+pub(crate) type InboundSlot = ArcShared<NoStdMutex<Option<TransportInboundShared<StdToolbox>>>>;
+
+const DEFAULT_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Shared address space of [`InProcessTransport`](super::InProcessTransport)s.
+///
+/// Cloning the network yields a handle to the same address space. Transports created from the
+/// same network reach each other's listeners by authority without opening sockets, which lets
+/// several actor systems of one process (e.g. the nodes of a multi-node test) form a cluster.
+/// The `fraktor.inproc` scheme reads the network attached with
+/// [`RemotingExtensionConfig::with_transport_extension`](crate::core::RemotingExtensionConfig::with_transport_extension).
+///
+/// The network also simulates faults for deterministic tests: links can be given
+/// [`LinkConditions`], authorities can be partitioned from each other and listeners can be
+/// paused. Delayed frames stay in flight until [`advance`](Self::advance) moves the virtual
+/// clock of the network past their delivery time, and pseudo-random decisions derive from the
+/// seed, so a scenario replays identically.
+#[derive(Clone)]
+pub struct InProcessNetwork {
+  state: ArcShared<NoStdMutex<NetworkState>>,
+}
+
+struct NetworkState {
+  listeners:          BTreeMap<String, InboundSlot>,
+  next_transport:     u64,
+  now:                Duration,
+  default_conditions: LinkConditions,
+  conditions:         BTreeMap<(String, String), LinkConditions>,
+  partitions:         BTreeSet<(String, String)>,
+  paused:             BTreeSet<String>,
+  in_flight:          Vec<InFlightFrame>,
+  next_sequence:      u64,
+  dropped:            u64,
+  rng:                u64,
+}
+
+struct InFlightFrame {
+  deliver_at: Duration,
+  sequence:   u64,
+  source:     Option<String>,
+  frame:      InboundFrame,
+}
+
+enum Routing {
+  Deliver(InboundSlot),
+  Hold(Duration),
+  Drop,
+}
+
+impl InProcessNetwork {
+  /// Creates an empty network.
+  #[must_use]
+  pub fn new() -> Self {
+    Self::with_seed(DEFAULT_SEED)
+  }
+
+  /// Creates an empty network whose drop and reorder decisions derive from `seed`.
+  #[must_use]
+  pub fn with_seed(seed: u64) -> Self {
+    let state = NetworkState {
+      listeners:          BTreeMap::new(),
+      next_transport:     0,
+      now:                Duration::ZERO,
+      default_conditions: LinkConditions::new(),
+      conditions:         BTreeMap::new(),
+      partitions:         BTreeSet::new(),
+      paused:             BTreeSet::new(),
+      in_flight:          Vec::new(),
+      next_sequence:      0,
+      dropped:            0,
+      // xorshift は 0 を状態に取れないため固定値へ置き換える
+      rng:                if seed == 0 { DEFAULT_SEED } else { seed },
+    };
+    Self { state: ArcShared::new(NoStdMutex::new(state)) }
+  }
+
+  /// Returns `true` when a transport listens on `authority`.
+  #[must_use]
+  pub fn is_bound(&self, authority: &str) -> bool {
+    self.state.lock().listeners.contains_key(authority)
+  }
+
+  /// Returns the virtual time of the network.
+  #[must_use]
+  pub fn now(&self) -> Duration {
+    self.state.lock().now
+  }
+
+  /// Applies `conditions` to every link without specific conditions.
+  pub fn set_default_conditions(&self, conditions: LinkConditions) {
+    self.state.lock().default_conditions = conditions;
+  }
+
+  /// Applies `conditions` to the frames sent from `from` to `to`.
+  pub fn set_link_conditions(&self, from: &str, to: &str, conditions: LinkConditions) {
+    self.state.lock().conditions.insert((from.to_string(), to.to_string()), conditions);
+  }
+
+  /// Restores the default conditions of the link from `from` to `to`.
+  pub fn clear_link_conditions(&self, from: &str, to: &str) {
+    self.state.lock().conditions.remove(&(from.to_string(), to.to_string()));
+  }
+
+  /// Cuts the link between `a` and `b` in both directions; frames on it are dropped.
+  pub fn partition(&self, a: &str, b: &str) {
+    self.state.lock().partitions.insert(link_key(a, b));
+  }
+
+  /// Restores the link between `a` and `b`.
+  pub fn heal(&self, a: &str, b: &str) {
+    self.state.lock().partitions.remove(&link_key(a, b));
+  }
+
+  /// Restores every partitioned link.
+  pub fn heal_all(&self) {
+    self.state.lock().partitions.clear();
+  }
+
+  /// Returns `true` when the link between `a` and `b` is cut.
+  #[must_use]
+  pub fn is_partitioned(&self, a: &str, b: &str) -> bool {
+    self.state.lock().partitions.contains(&link_key(a, b))
+  }
+
+  /// Holds every frame sent from or to `authority` until it is resumed.
+  pub fn pause(&self, authority: &str) {
+    self.state.lock().paused.insert(authority.to_string());
+  }
+
+  /// Releases the frames held for `authority` on the next [`advance`](Self::advance).
+  pub fn resume(&self, authority: &str) {
+    self.state.lock().paused.remove(authority);
+  }
+
+  /// Returns `true` when `authority` is paused.
+  #[must_use]
+  pub fn is_paused(&self, authority: &str) -> bool {
+    self.state.lock().paused.contains(authority)
+  }
+
+  /// Returns the number of frames waiting for delivery.
+  #[must_use]
+  pub fn in_flight(&self) -> usize {
+    self.state.lock().in_flight.len()
+  }
+
+  /// Returns the number of frames dropped by partitions or link conditions so far.
+  #[must_use]
+  pub fn dropped(&self) -> u64 {
+    self.state.lock().dropped
+  }
+
+  /// Advances the virtual clock by `duration`, delivering due frames in delivery time order.
+  ///
+  /// Frames sent by the receivers while the clock advances are delivered within the same call
+  /// when they become due before its end. Returns the number of delivered frames.
+  pub fn advance(&self, duration: Duration) -> usize {
+    let target = self.state.lock().now.saturating_add(duration);
+    let mut delivered = 0;
+    loop {
+      let (slot, frame) = {
+        let mut state = self.state.lock();
+        let Some(index) = state.next_due(target) else {
+          state.now = target;
+          return delivered;
+        };
+        let in_flight = state.in_flight.remove(index);
+        state.now = state.now.max(in_flight.deliver_at);
+        let target_authority = in_flight.frame.local_authority().to_string();
+        if state.is_partitioned(in_flight.source.as_deref(), &target_authority) {
+          state.dropped += 1;
+          continue;
+        }
+        let Some(slot) = state.listeners.get(&target_authority).cloned() else {
+          state.dropped += 1;
+          continue;
+        };
+        (slot, in_flight.frame)
+      };
+      // 受信側のハンドラを呼び出す間はネットワークのロックを保持しない
+      Self::deliver(&slot, frame);
+      delivered += 1;
+    }
+  }
+
+  pub(crate) fn allocate_transport_id(&self) -> u64 {
+    let mut state = self.state.lock();
+    state.next_transport += 1;
+    state.next_transport
+  }
+
+  pub(crate) fn bind(&self, authority: &str, slot: InboundSlot) -> Result<(), TransportError> {
+    let mut state = self.state.lock();
+    if state.listeners.contains_key(authority) {
+      return Err(TransportError::Io(format!("authority already bound: {authority}")));
+    }
+    state.listeners.insert(authority.to_string(), slot);
+    Ok(())
+  }
+
+  pub(crate) fn unbind(&self, authority: &str) {
+    self.state.lock().listeners.remove(authority);
+  }
+
+  /// Routes `frame` from the listener `source` to the listener named by its local authority.
+  pub(crate) fn transmit(&self, source: Option<&str>, frame: InboundFrame) -> Result<(), TransportError> {
+    let routing = {
+      let mut state = self.state.lock();
+      let target = frame.local_authority().to_string();
+      let Some(slot) = state.listeners.get(&target).cloned() else {
+        return Err(TransportError::AuthorityNotBound(target));
+      };
+      state.route(source, &target, slot)
+    };
+    match routing {
+      | Routing::Deliver(slot) => Self::deliver(&slot, frame),
+      | Routing::Hold(deliver_at) => {
+        let mut state = self.state.lock();
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+        state.in_flight.push(InFlightFrame { deliver_at, sequence, source: source.map(ToString::to_string), frame });
+      },
+      | Routing::Drop => {},
+    }
+    Ok(())
+  }
+
+  fn deliver(slot: &InboundSlot, frame: InboundFrame) {
+    let handler = slot.lock().clone();
+    if let Some(handler) = handler {
+      handler.lock().on_frame(frame);
+    }
+  }
+}
+
+impl NetworkState {
+  fn route(&mut self, source: Option<&str>, target: &str, slot: InboundSlot) -> Routing {
+    if self.is_partitioned(source, target) {
+      self.dropped += 1;
+      return Routing::Drop;
+    }
+    let conditions = self.conditions_for(source, target);
+    if conditions.drop_probability() > 0.0 && self.next_unit() < conditions.drop_probability() {
+      self.dropped += 1;
+      return Routing::Drop;
+    }
+    // 同じリンクで配送待ちのフレームを追い越さないよう、待ちがあれば後ろに並べる
+    let queued_until = self
+      .in_flight
+      .iter()
+      .filter(|in_flight| in_flight.source.as_deref() == source && in_flight.frame.local_authority() == target)
+      .map(|in_flight| in_flight.deliver_at)
+      .max();
+    if conditions.is_immediate() && queued_until.is_none() && !self.is_held(source, target) {
+      return Routing::Deliver(slot);
+    }
+    let jitter = self.jitter(conditions.reorder_window());
+    let deliver_at = self.now.saturating_add(conditions.latency()).saturating_add(jitter);
+    match queued_until {
+      | Some(queued_until) if conditions.reorder_window().is_zero() => Routing::Hold(deliver_at.max(queued_until)),
+      | _ => Routing::Hold(deliver_at),
+    }
+  }
+
+  fn conditions_for(&self, source: Option<&str>, target: &str) -> LinkConditions {
+    source
+      .and_then(|source| self.conditions.get(&(source.to_string(), target.to_string())).copied())
+      .unwrap_or(self.default_conditions)
+  }
+
+  fn is_partitioned(&self, source: Option<&str>, target: &str) -> bool {
+    source.is_some_and(|source| self.partitions.contains(&link_key(source, target)))
+  }
+
+  fn is_held(&self, source: Option<&str>, target: &str) -> bool {
+    self.paused.contains(target) || source.is_some_and(|source| self.paused.contains(source))
+  }
+
+  fn next_due(&self, target: Duration) -> Option<usize> {
+    self
+      .in_flight
+      .iter()
+      .enumerate()
+      .filter(|(_, in_flight)| {
+        in_flight.deliver_at <= target && !self.is_held(in_flight.source.as_deref(), in_flight.frame.local_authority())
+      })
+      .min_by_key(|(_, in_flight)| (in_flight.deliver_at, in_flight.sequence))
+      .map(|(index, _)| index)
+  }
+
+  fn jitter(&mut self, window: Duration) -> Duration {
+    if window.is_zero() {
+      return Duration::ZERO;
+    }
+    let window_nanos = u64::try_from(window.as_nanos()).unwrap_or(u64::MAX);
+    Duration::from_nanos(self.next_u64() % window_nanos.saturating_add(1))
+  }
+
+  fn next_unit(&mut self) -> f64 {
+    // 上位 53 bit を [0, 1) の一様な値に変換する
+    (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+  }
+
+  fn next_u64(&mut self) -> u64 {
+    let mut x = self.rng;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    self.rng = x;
+    x
+  }
+}
+
+fn link_key(a: &str, b: &str) -> (String, String) {
+  if a <= b { (a.to_string(), b.to_string()) } else { (b.to_string(), a.to_string()) }
+}
+
+impl Default for InProcessNetwork {
+  fn default() -> Self {
+    Self::new()
+  }
+}
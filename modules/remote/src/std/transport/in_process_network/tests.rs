@@ -0,0 +1,164 @@
+//! Tests for the fault injection of InProcessNetwork.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::time::Duration;
+
+use fraktor_actor_rs::core::event_stream::CorrelationId;
+use fraktor_utils_rs::{
+  core::{
+    runtime_toolbox::{NoStdMutex, RuntimeToolbox, SyncMutexFamily},
+    sync::ArcShared,
+  },
+  std::runtime_toolbox::StdToolbox,
+};
+
+use super::InProcessNetwork;
+use crate::{
+  core::{
+    InboundFrame, RemoteTransport, TransportBind, TransportChannel, TransportEndpoint, TransportInbound,
+    TransportInboundShared,
+  },
+  std::transport::{InProcessTransport, LinkConditions},
+};
+
+const A: &str = "node-a:2552";
+const B: &str = "node-b:2552";
+
+type Recorded = ArcShared<NoStdMutex<Vec<u8>>>;
+
+struct RecordingInbound {
+  payloads: Recorded,
+}
+
+impl TransportInbound for RecordingInbound {
+  fn on_frame(&mut self, frame: InboundFrame) {
+    self.payloads.lock().extend_from_slice(frame.payload());
+  }
+}
+
+struct Node {
+  transport: InProcessTransport,
+  payloads:  Recorded,
+}
+
+impl Node {
+  fn bind(network: &InProcessNetwork, host: &str) -> Self {
+    let mut transport = InProcessTransport::new(network.clone());
+    let payloads: Recorded = ArcShared::new(NoStdMutex::new(Vec::new()));
+    let handler: Box<dyn TransportInbound> = Box::new(RecordingInbound { payloads: payloads.clone() });
+    let shared: TransportInboundShared<StdToolbox> =
+      ArcShared::new(<StdToolbox as RuntimeToolbox>::MutexFamily::create(handler));
+    transport.install_inbound_handler(shared);
+    transport.spawn_listener(&TransportBind::new(host, Some(2552))).expect("listener");
+    Self { transport, payloads }
+  }
+
+  fn channel(&mut self, authority: &str) -> TransportChannel {
+    self.transport.open_channel(&TransportEndpoint::new(authority.into())).expect("channel")
+  }
+
+  fn send(&mut self, channel: &TransportChannel, byte: u8) {
+    self.transport.send(channel, &[byte], CorrelationId::nil()).expect("send");
+  }
+
+  fn received(&self) -> Vec<u8> {
+    self.payloads.lock().clone()
+  }
+}
+
+#[test]
+fn latency_holds_frames_until_virtual_time_advances() {
+  let network = InProcessNetwork::new();
+  let mut a = Node::bind(&network, "node-a");
+  let b = Node::bind(&network, "node-b");
+  network.set_link_conditions(A, B, LinkConditions::new().with_latency(Duration::from_millis(20)));
+
+  let channel = a.channel(B);
+  a.send(&channel, 1);
+  assert!(b.received().is_empty());
+  assert_eq!(network.in_flight(), 1);
+
+  assert_eq!(network.advance(Duration::from_millis(19)), 0);
+  assert_eq!(network.advance(Duration::from_millis(1)), 1);
+  assert_eq!(b.received(), [1]);
+  assert_eq!(network.now(), Duration::from_millis(20));
+}
+
+#[test]
+fn partition_drops_frames_in_both_directions_until_healed() {
+  let network = InProcessNetwork::new();
+  let mut a = Node::bind(&network, "node-a");
+  let mut b = Node::bind(&network, "node-b");
+  let to_b = a.channel(B);
+  let to_a = b.channel(A);
+
+  network.partition(B, A);
+  assert!(network.is_partitioned(A, B));
+  a.send(&to_b, 1);
+  b.send(&to_a, 2);
+  assert!(a.received().is_empty() && b.received().is_empty());
+  assert_eq!(network.dropped(), 2);
+
+  network.heal(A, B);
+  a.send(&to_b, 3);
+  assert_eq!(b.received(), [3]);
+}
+
+#[test]
+fn paused_listener_receives_held_frames_after_resume() {
+  let network = InProcessNetwork::new();
+  let mut a = Node::bind(&network, "node-a");
+  let b = Node::bind(&network, "node-b");
+  let channel = a.channel(B);
+
+  network.pause(B);
+  a.send(&channel, 1);
+  a.send(&channel, 2);
+  network.advance(Duration::from_millis(100));
+  assert!(b.received().is_empty());
+
+  network.resume(B);
+  network.advance(Duration::ZERO);
+  assert_eq!(b.received(), [1, 2]);
+}
+
+#[test]
+fn reordering_and_drops_replay_identically_for_the_same_seed() {
+  let run = |seed: u64| {
+    let network = InProcessNetwork::with_seed(seed);
+    let mut a = Node::bind(&network, "node-a");
+    let b = Node::bind(&network, "node-b");
+    network.set_default_conditions(
+      LinkConditions::new().with_reorder_window(Duration::from_millis(10)).with_drop_probability(0.2),
+    );
+    let channel = a.channel(B);
+    for byte in 0..32 {
+      a.send(&channel, byte);
+    }
+    network.advance(Duration::from_millis(10));
+    (b.received(), network.dropped())
+  };
+
+  let (first, dropped) = run(7);
+  assert_eq!(run(7), (first.clone(), dropped));
+  assert!(dropped > 0);
+  assert_eq!(first.len() as u64 + dropped, 32);
+  assert!(first.windows(2).any(|pair| pair[0] > pair[1]), "frames were not reordered: {first:?}");
+}
+
+#[test]
+fn frames_of_a_delayed_link_keep_their_order_when_conditions_are_cleared() {
+  let network = InProcessNetwork::new();
+  let mut a = Node::bind(&network, "node-a");
+  let b = Node::bind(&network, "node-b");
+  let channel = a.channel(B);
+
+  network.set_link_conditions(A, B, LinkConditions::new().with_latency(Duration::from_millis(5)));
+  a.send(&channel, 1);
+  network.clear_link_conditions(A, B);
+  a.send(&channel, 2);
+  assert!(b.received().is_empty());
+
+  network.advance(Duration::from_millis(5));
+  assert_eq!(b.received(), [1, 2]);
+}
@@ -0,0 +1,126 @@
+//! Transport delivering frames between actor systems of the same process.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{
+  collections::BTreeMap,
+  string::{String, ToString},
+  vec::Vec,
+};
+
+use fraktor_actor_rs::core::event_stream::CorrelationId;
+use fraktor_utils_rs::{
+  core::{runtime_toolbox::NoStdMutex, sync::ArcShared},
+  std::runtime_toolbox::StdToolbox,
+};
+
+use super::in_process_network::{InProcessNetwork, InboundSlot};
+use crate::core::{
+  InboundFrame, RemoteTransport, TransportBackpressureHookShared, TransportBind, TransportChannel, TransportEndpoint,
+  TransportError, TransportHandle, TransportInboundShared,
+};
+
+/// Transport of the `fraktor.inproc` scheme connecting actor systems through an
+/// [`InProcessNetwork`].
+///
+/// Frames are handed to the inbound handler of the target listener synchronously unless the
+/// network holds them for fault injection, so no sockets, threads or serialization of the frame
+/// header are involved. The first listener of the transport identifies its side of each link. Each
+/// channel reports a distinct remote address so the peer can tell associations apart as it would
+/// for TCP connections.
+pub struct InProcessTransport {
+  network:      InProcessNetwork,
+  id:           u64,
+  inbound:      InboundSlot,
+  listeners:    Vec<String>,
+  channels:     BTreeMap<u64, String>,
+  next_channel: u64,
+}
+
+impl InProcessTransport {
+  /// Creates a transport attached to `network`.
+  #[must_use]
+  pub fn new(network: InProcessNetwork) -> Self {
+    let id = network.allocate_transport_id();
+    Self {
+      network,
+      id,
+      inbound: ArcShared::new(NoStdMutex::new(None)),
+      listeners: Vec::new(),
+      channels: BTreeMap::new(),
+      next_channel: 1,
+    }
+  }
+
+  /// Returns the network the transport is attached to.
+  #[must_use]
+  pub const fn network(&self) -> &InProcessNetwork {
+    &self.network
+  }
+
+  fn remote_address(&self, channel: u64) -> String {
+    format!("inproc:{}/{}", self.id, channel)
+  }
+}
+
+impl Drop for InProcessTransport {
+  fn drop(&mut self) {
+    for authority in &self.listeners {
+      self.network.unbind(authority);
+    }
+  }
+}
+
+impl RemoteTransport<StdToolbox> for InProcessTransport {
+  fn scheme(&self) -> &str {
+    "fraktor.inproc"
+  }
+
+  fn spawn_listener(&mut self, bind: &TransportBind) -> Result<TransportHandle, TransportError> {
+    let authority = bind.authority().to_string();
+    self.network.bind(&authority, self.inbound.clone())?;
+    self.listeners.push(authority.clone());
+    Ok(TransportHandle::new(&authority))
+  }
+
+  fn open_channel(&mut self, endpoint: &TransportEndpoint) -> Result<TransportChannel, TransportError> {
+    if !self.network.is_bound(endpoint.authority()) {
+      return Err(TransportError::AuthorityNotBound(endpoint.authority().to_string()));
+    }
+    let id = self.next_channel;
+    self.next_channel += 1;
+    self.channels.insert(id, endpoint.authority().to_string());
+    Ok(TransportChannel::new(id))
+  }
+
+  fn send(
+    &mut self,
+    channel: &TransportChannel,
+    payload: &[u8],
+    correlation_id: CorrelationId,
+  ) -> Result<(), TransportError> {
+    let authority = self.channels.get(&channel.id()).ok_or(TransportError::ChannelUnavailable(channel.id()))?;
+    let source = self.listeners.first().map(String::as_str);
+    let mut frame =
+      InboundFrame::new(authority.clone(), self.remote_address(channel.id()), payload.to_vec(), correlation_id);
+    // ネットワーク上の listener 名は排他的に bind されるため、送信元の authority として信頼できる
+    if let Some(source) = source {
+      frame = frame.with_peer_authority(source);
+    }
+    self.network.transmit(source, frame)
+  }
+
+  fn close(&mut self, channel: &TransportChannel) {
+    self.channels.remove(&channel.id());
+  }
+
+  fn install_backpressure_hook(&mut self, hook: TransportBackpressureHookShared) {
+    // 同期的に配送するため送信キューが溜まらず、背圧は発生しない
+    let _ = hook;
+  }
+
+  fn install_inbound_handler(&mut self, handler: TransportInboundShared<StdToolbox>) {
+    *self.inbound.lock() = Some(handler);
+  }
+}
@@ -0,0 +1,150 @@
+//! Tests for InProcessTransport.
+
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use fraktor_actor_rs::core::event_stream::CorrelationId;
+use fraktor_utils_rs::{
+  core::{
+    runtime_toolbox::{NoStdMutex, RuntimeToolbox, SyncMutexFamily},
+    sync::ArcShared,
+  },
+  std::runtime_toolbox::StdToolbox,
+};
+
+use super::InProcessTransport;
+use crate::{
+  core::{
+    InboundFrame, RemoteTransport, TransportBind, TransportEndpoint, TransportError, TransportInbound,
+    TransportInboundShared,
+  },
+  std::transport::InProcessNetwork,
+};
+
+type Recorded = ArcShared<NoStdMutex<Vec<(String, String, Vec<u8>, CorrelationId)>>>;
+
+struct RecordingInbound {
+  frames: Recorded,
+}
+
+impl TransportInbound for RecordingInbound {
+  fn on_frame(&mut self, frame: InboundFrame) {
+    self.frames.lock().push((
+      frame.local_authority().into(),
+      frame.remote_address().into(),
+      frame.payload().to_vec(),
+      frame.correlation_id(),
+    ));
+  }
+}
+
+fn record_inbound(transport: &mut InProcessTransport) -> Recorded {
+  let frames: Recorded = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let handler: Box<dyn TransportInbound> = Box::new(RecordingInbound { frames: frames.clone() });
+  let shared: TransportInboundShared<StdToolbox> =
+    ArcShared::new(<StdToolbox as RuntimeToolbox>::MutexFamily::create(handler));
+  transport.install_inbound_handler(shared);
+  frames
+}
+
+#[test]
+fn transport_scheme_is_fraktor_inproc() {
+  let transport = InProcessTransport::new(InProcessNetwork::new());
+  assert_eq!(transport.scheme(), "fraktor.inproc");
+}
+
+#[test]
+fn frames_are_delivered_to_the_listener_of_the_same_network() {
+  let network = InProcessNetwork::new();
+  let mut server = InProcessTransport::new(network.clone());
+  let frames = record_inbound(&mut server);
+  server.spawn_listener(&TransportBind::new("node-a", Some(2552))).expect("listener");
+
+  let mut client = InProcessTransport::new(network);
+  let channel = client.open_channel(&TransportEndpoint::new("node-a:2552".into())).expect("channel");
+  let correlation = CorrelationId::from_u128(7);
+  client.send(&channel, &[1, 2, 3], correlation).expect("send");
+  client.send(&channel, &[4], correlation).expect("send");
+
+  let frames = frames.lock().clone();
+  assert_eq!(frames.len(), 2);
+  assert_eq!(frames[0].0, "node-a:2552");
+  assert_eq!(frames[0].2, vec![1, 2, 3]);
+  assert_eq!(frames[0].3, correlation);
+  // 同じチャネルのフレームは同じ送信元アドレスを持つ
+  assert_eq!(frames[0].1, frames[1].1);
+}
+
+#[test]
+fn channels_report_distinct_remote_addresses() {
+  let network = InProcessNetwork::new();
+  let mut server = InProcessTransport::new(network.clone());
+  let frames = record_inbound(&mut server);
+  server.spawn_listener(&TransportBind::new("node-a", Some(2552))).expect("listener");
+
+  let endpoint = TransportEndpoint::new("node-a:2552".into());
+  let mut first = InProcessTransport::new(network.clone());
+  let mut second = InProcessTransport::new(network);
+  let first_channel = first.open_channel(&endpoint).expect("channel");
+  let second_channel = second.open_channel(&endpoint).expect("channel");
+  first.send(&first_channel, &[1], CorrelationId::nil()).expect("send");
+  second.send(&second_channel, &[2], CorrelationId::nil()).expect("send");
+
+  let frames = frames.lock().clone();
+  assert_ne!(frames[0].1, frames[1].1);
+}
+
+#[test]
+fn networks_are_isolated() {
+  let mut server = InProcessTransport::new(InProcessNetwork::new());
+  server.spawn_listener(&TransportBind::new("node-a", Some(2552))).expect("listener");
+
+  let mut client = InProcessTransport::new(InProcessNetwork::new());
+  let result = client.open_channel(&TransportEndpoint::new("node-a:2552".into()));
+  assert!(matches!(result, Err(TransportError::AuthorityNotBound(authority)) if authority == "node-a:2552"));
+}
+
+#[test]
+fn dropping_the_transport_releases_its_authorities() {
+  let network = InProcessNetwork::new();
+  let mut server = InProcessTransport::new(network.clone());
+  server.spawn_listener(&TransportBind::new("node-a", Some(2552))).expect("listener");
+  assert!(network.is_bound("node-a:2552"));
+
+  let mut duplicate = InProcessTransport::new(network.clone());
+  assert!(duplicate.spawn_listener(&TransportBind::new("node-a", Some(2552))).is_err());
+
+  drop(server);
+  assert!(!network.is_bound("node-a:2552"));
+  assert!(duplicate.spawn_listener(&TransportBind::new("node-a", Some(2552))).is_ok());
+}
+
+struct PeerRecordingInbound {
+  peers: ArcShared<NoStdMutex<Vec<Option<String>>>>,
+}
+
+impl TransportInbound for PeerRecordingInbound {
+  fn on_frame(&mut self, frame: InboundFrame) {
+    self.peers.lock().push(frame.peer_authority().map(String::from));
+  }
+}
+
+#[test]
+fn frames_carry_the_listener_authority_of_the_sender() {
+  let network = InProcessNetwork::new();
+  let mut server = InProcessTransport::new(network.clone());
+  let peers = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let handler: Box<dyn TransportInbound> = Box::new(PeerRecordingInbound { peers: peers.clone() });
+  server.install_inbound_handler(ArcShared::new(<StdToolbox as RuntimeToolbox>::MutexFamily::create(handler)));
+  server.spawn_listener(&TransportBind::new("node-a", Some(2552))).expect("listener");
+
+  let mut anonymous = InProcessTransport::new(network.clone());
+  let channel = anonymous.open_channel(&TransportEndpoint::new("node-a:2552".into())).expect("channel");
+  anonymous.send(&channel, &[1], CorrelationId::nil()).expect("send");
+
+  let mut client = InProcessTransport::new(network);
+  client.spawn_listener(&TransportBind::new("node-b", Some(2553))).expect("listener");
+  let channel = client.open_channel(&TransportEndpoint::new("node-a:2552".into())).expect("channel");
+  client.send(&channel, &[2], CorrelationId::nil()).expect("send");
+
+  assert_eq!(peers.lock().clone(), vec![None, Some("node-b:2553".into())]);
+}
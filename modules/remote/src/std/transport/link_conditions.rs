@@ -0,0 +1,66 @@
+//! Fault injection settings of an in-process network link.
+
+use core::time::Duration;
+
+/// Describes how an [`InProcessNetwork`](super::InProcessNetwork) treats the frames of a link.
+///
+/// Links with default conditions deliver frames synchronously, as a plain in-process network
+/// does. Latency and reordering hold frames in flight until virtual time is advanced through
+/// [`InProcessNetwork::advance`](super::InProcessNetwork::advance).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LinkConditions {
+  latency:          Duration,
+  reorder_window:   Duration,
+  drop_probability: f64,
+}
+
+impl LinkConditions {
+  /// Creates conditions delivering every frame immediately.
+  #[must_use]
+  pub const fn new() -> Self {
+    Self { latency: Duration::ZERO, reorder_window: Duration::ZERO, drop_probability: 0.0 }
+  }
+
+  /// Delays every frame by `latency` of virtual time.
+  #[must_use]
+  pub const fn with_latency(mut self, latency: Duration) -> Self {
+    self.latency = latency;
+    self
+  }
+
+  /// Adds a pseudo-random extra delay of up to `window`, so that frames may overtake each other.
+  #[must_use]
+  pub const fn with_reorder_window(mut self, window: Duration) -> Self {
+    self.reorder_window = window;
+    self
+  }
+
+  /// Drops each frame with the given probability, clamped to `0.0..=1.0`.
+  #[must_use]
+  pub const fn with_drop_probability(mut self, probability: f64) -> Self {
+    self.drop_probability = probability.clamp(0.0, 1.0);
+    self
+  }
+
+  /// Returns the fixed delay of every frame.
+  #[must_use]
+  pub const fn latency(&self) -> Duration {
+    self.latency
+  }
+
+  /// Returns the maximum extra delay used to reorder frames.
+  #[must_use]
+  pub const fn reorder_window(&self) -> Duration {
+    self.reorder_window
+  }
+
+  /// Returns the probability of dropping a frame.
+  #[must_use]
+  pub const fn drop_probability(&self) -> f64 {
+    self.drop_probability
+  }
+
+  pub(crate) fn is_immediate(&self) -> bool {
+    self.latency.is_zero() && self.reorder_window.is_zero()
+  }
+}
@@ -0,0 +1,143 @@
+//! TLS settings for the Tokio TCP transport.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use std::{fs, path::PathBuf, time::SystemTime};
+
+use crate::core::TransportError;
+
+#[derive(Clone)]
+enum PemSource {
+  Inline(Vec<u8>),
+  File(PathBuf),
+}
+
+impl PemSource {
+  fn read(&self) -> Result<Vec<u8>, TransportError> {
+    match self {
+      | Self::Inline(pem) => Ok(pem.clone()),
+      | Self::File(path) => {
+        fs::read(path).map_err(|error| TransportError::Tls(format!("failed to read {}: {error}", path.display())))
+      },
+    }
+  }
+}
+
+/// TLS settings enabling the `fraktor.tcp+tls` scheme of
+/// [`TokioTcpTransport`](super::TokioTcpTransport).
+///
+/// Every node presents `certificate_chain` both as a server and as a client, and verifies its
+/// peers against `trusted_roots`. Outbound connections check that the server certificate is
+/// issued for the host of the dialled authority (IP or DNS subject alternative name), and
+/// inbound connections require a client certificate unless client authentication is disabled.
+///
+/// Configurations created with [`from_files`](Self::from_files) are re-read when the files
+/// change, so certificates can be rotated without restarting the transport.
+///
+/// The `fraktor.tcp+tls` scheme reads the configuration attached with
+/// [`RemotingExtensionConfig::with_transport_extension`](crate::core::RemotingExtensionConfig::with_transport_extension).
+#[derive(Clone)]
+pub struct TlsConfig {
+  certificate_chain:   PemSource,
+  private_key:         PemSource,
+  trusted_roots:       PemSource,
+  require_client_auth: bool,
+  server_name:         Option<String>,
+}
+
+impl fmt::Debug for TlsConfig {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    // 秘密鍵の内容を出力しないよう一部のフィールドのみを表示する
+    f.debug_struct("TlsConfig")
+      .field("require_client_auth", &self.require_client_auth)
+      .field("server_name", &self.server_name)
+      .finish_non_exhaustive()
+  }
+}
+
+impl TlsConfig {
+  /// Creates a configuration from PEM encoded certificate chain, PKCS#8/PKCS#1/SEC1 private key
+  /// and trusted CA certificates.
+  #[must_use]
+  pub fn from_pem(
+    certificate_chain: impl Into<Vec<u8>>,
+    private_key: impl Into<Vec<u8>>,
+    trusted_roots: impl Into<Vec<u8>>,
+  ) -> Self {
+    Self::with_sources(
+      PemSource::Inline(certificate_chain.into()),
+      PemSource::Inline(private_key.into()),
+      PemSource::Inline(trusted_roots.into()),
+    )
+  }
+
+  /// Creates a configuration reading PEM files that are re-read whenever they change.
+  #[must_use]
+  pub fn from_files(
+    certificate_chain: impl Into<PathBuf>,
+    private_key: impl Into<PathBuf>,
+    trusted_roots: impl Into<PathBuf>,
+  ) -> Self {
+    Self::with_sources(
+      PemSource::File(certificate_chain.into()),
+      PemSource::File(private_key.into()),
+      PemSource::File(trusted_roots.into()),
+    )
+  }
+
+  const fn with_sources(certificate_chain: PemSource, private_key: PemSource, trusted_roots: PemSource) -> Self {
+    Self { certificate_chain, private_key, trusted_roots, require_client_auth: true, server_name: None }
+  }
+
+  /// Enables or disables mandatory client certificates on inbound connections (mutual TLS).
+  #[must_use]
+  pub const fn with_client_auth(mut self, required: bool) -> Self {
+    self.require_client_auth = required;
+    self
+  }
+
+  /// Overrides the name expected in server certificates instead of the dialled host.
+  #[must_use]
+  pub fn with_server_name(mut self, name: impl Into<String>) -> Self {
+    self.server_name = Some(name.into());
+    self
+  }
+
+  /// Returns whether inbound connections must present a client certificate.
+  #[must_use]
+  pub const fn require_client_auth(&self) -> bool {
+    self.require_client_auth
+  }
+
+  /// Returns the server name override.
+  #[must_use]
+  pub fn server_name(&self) -> Option<&str> {
+    self.server_name.as_deref()
+  }
+
+  pub(crate) fn certificate_chain_pem(&self) -> Result<Vec<u8>, TransportError> {
+    self.certificate_chain.read()
+  }
+
+  pub(crate) fn private_key_pem(&self) -> Result<Vec<u8>, TransportError> {
+    self.private_key.read()
+  }
+
+  pub(crate) fn trusted_roots_pem(&self) -> Result<Vec<u8>, TransportError> {
+    self.trusted_roots.read()
+  }
+
+  /// Returns the modification times of file sources, or `None` for inline PEM.
+  pub(crate) fn modification_stamps(&self) -> Option<Vec<SystemTime>> {
+    [&self.certificate_chain, &self.private_key, &self.trusted_roots]
+      .into_iter()
+      .map(|source| match source {
+        | PemSource::Inline(_) => None,
+        | PemSource::File(path) => fs::metadata(path).and_then(|metadata| metadata.modified()).ok(),
+      })
+      .collect()
+  }
+}
@@ -0,0 +1,121 @@
+//! rustls acceptor/connector built from a [`TlsConfig`].
+
+use alloc::{string::ToString, sync::Arc, vec::Vec};
+use std::time::SystemTime;
+
+use rustls::{
+  ClientConfig, RootCertStore, ServerConfig,
+  crypto::{CryptoProvider, ring},
+  pki_types::{CertificateDer, PrivateKeyDer, ServerName, pem::PemObject},
+  server::WebPkiClientVerifier,
+};
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+use super::tls_config::TlsConfig;
+use crate::core::TransportError;
+
+/// Current TLS acceptor/connector pair, rebuilt when the configured certificates change.
+pub(crate) struct TlsContext {
+  config:    TlsConfig,
+  stamps:    Option<Vec<SystemTime>>,
+  acceptor:  TlsAcceptor,
+  connector: TlsConnector,
+}
+
+impl TlsContext {
+  /// Builds a context, failing when the certificates or the key cannot be loaded.
+  pub(crate) fn new(config: TlsConfig) -> Result<Self, TransportError> {
+    let stamps = config.modification_stamps();
+    let (acceptor, connector) = build(&config)?;
+    Ok(Self { config, stamps, acceptor, connector })
+  }
+
+  /// Re-reads the configured certificates; the previous ones stay in use on failure.
+  pub(crate) fn reload(&mut self) -> Result<(), TransportError> {
+    let stamps = self.config.modification_stamps();
+    let (acceptor, connector) = build(&self.config)?;
+    self.stamps = stamps;
+    self.acceptor = acceptor;
+    self.connector = connector;
+    Ok(())
+  }
+
+  /// Returns the acceptor for a new inbound connection.
+  pub(crate) fn acceptor(&self) -> TlsAcceptor {
+    self.acceptor.clone()
+  }
+
+  /// Returns the connector and the name the server certificate must match for `authority`.
+  pub(crate) fn connector(&self, authority: &str) -> Result<(TlsConnector, ServerName<'static>), TransportError> {
+    let host = match self.config.server_name() {
+      | Some(name) => name,
+      | None => host_of(authority),
+    };
+    let server_name = ServerName::try_from(host.to_string())
+      .map_err(|error| TransportError::Tls(format!("invalid server name {host}: {error}")))?;
+    Ok((self.connector.clone(), server_name))
+  }
+
+  /// Re-reads the certificates when the configured files changed since the last load.
+  ///
+  /// On failure the previous certificates stay in use and the error is returned so the caller
+  /// can report it; the next call retries the reload.
+  pub(crate) fn refresh(&mut self) -> Result<(), TransportError> {
+    if self.stamps.is_some() && self.config.modification_stamps() != self.stamps {
+      return self.reload();
+    }
+    Ok(())
+  }
+}
+
+fn build(config: &TlsConfig) -> Result<(TlsAcceptor, TlsConnector), TransportError> {
+  let chain_pem = config.certificate_chain_pem()?;
+  let certificates = CertificateDer::pem_slice_iter(&chain_pem)
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|error| TransportError::Tls(format!("invalid certificate chain: {error}")))?;
+  if certificates.is_empty() {
+    return Err(TransportError::Tls("certificate chain is empty".into()));
+  }
+  let key = PrivateKeyDer::from_pem_slice(&config.private_key_pem()?)
+    .map_err(|error| TransportError::Tls(format!("invalid private key: {error}")))?;
+  let mut roots = RootCertStore::empty();
+  for certificate in CertificateDer::pem_slice_iter(&config.trusted_roots_pem()?) {
+    let certificate = certificate.map_err(|error| TransportError::Tls(format!("invalid trusted root: {error}")))?;
+    roots.add(certificate).map_err(|error| TransportError::Tls(format!("invalid trusted root: {error}")))?;
+  }
+  if roots.is_empty() {
+    return Err(TransportError::Tls("no trusted roots configured".into()));
+  }
+  let roots = Arc::new(roots);
+  let provider: Arc<CryptoProvider> = Arc::new(ring::default_provider());
+
+  let server_builder =
+    ServerConfig::builder_with_provider(provider.clone()).with_safe_default_protocol_versions().map_err(tls_error)?;
+  let server_builder = if config.require_client_auth() {
+    let verifier =
+      WebPkiClientVerifier::builder_with_provider(roots.clone(), provider.clone()).build().map_err(tls_error)?;
+    server_builder.with_client_cert_verifier(verifier)
+  } else {
+    server_builder.with_no_client_auth()
+  };
+  let server = server_builder.with_single_cert(certificates.clone(), key.clone_key()).map_err(tls_error)?;
+
+  let client = ClientConfig::builder_with_provider(provider)
+    .with_safe_default_protocol_versions()
+    .map_err(tls_error)?
+    .with_root_certificates(roots)
+    .with_client_auth_cert(certificates, key)
+    .map_err(tls_error)?;
+
+  Ok((TlsAcceptor::from(Arc::new(server)), TlsConnector::from(Arc::new(client))))
+}
+
+fn tls_error(error: impl core::fmt::Display) -> TransportError {
+  TransportError::Tls(error.to_string())
+}
+
+// `host:port` / `[v6]:port` から証明書照合に使うホスト部分を取り出す
+fn host_of(authority: &str) -> &str {
+  let host = authority.rsplit_once(':').map_or(authority, |(host, _)| host);
+  host.trim_start_matches('[').trim_end_matches(']')
+}
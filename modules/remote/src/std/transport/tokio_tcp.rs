@@ -1,5 +1,10 @@
 //! Tokio TCP transport for production remoting.
 
+mod chunk_reassembler;
+pub(super) mod frame_codec;
+mod frame_limits;
+#[cfg(feature = "tls")]
+mod peer_identity;
 #[cfg(test)]
 mod tests;
 
@@ -9,41 +14,83 @@ use alloc::{
   sync::Arc,
   vec::Vec,
 };
-use core::{fmt, future::Future};
-use std::thread;
+use core::{
+  fmt,
+  future::Future,
+  sync::atomic::{AtomicBool, Ordering},
+};
+use std::{net::SocketAddr, thread};
 
+use chunk_reassembler::ChunkReassembler;
 use fraktor_actor_rs::core::event_stream::{BackpressureSignal, CorrelationId};
 use fraktor_utils_rs::{
   core::{runtime_toolbox::NoStdMutex, sync::ArcShared},
   std::runtime_toolbox::StdToolbox,
 };
+use frame_codec::{
+  LARGE_LANE_MARKER, MAX_LANE_PAIRING_LEN, decode_correlation_id, encode_chunks, encode_frame,
+  encode_large_lane_preamble,
+};
+use frame_limits::FrameLimits;
+#[cfg(feature = "tls")]
+use peer_identity::PeerIdentity;
 use tokio::{
-  io::{AsyncReadExt, AsyncWriteExt},
+  io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
   net::{TcpListener, TcpStream},
   runtime::{Builder, Runtime},
-  sync::mpsc,
+  sync::mpsc::{self, error::TrySendError},
   task::JoinHandle,
 };
 
+#[cfg(feature = "tls")]
+use super::{tls_config::TlsConfig, tls_context::TlsContext};
 use crate::core::{
-  InboundFrame, RemoteTransport, TransportBackpressureHookShared, TransportBind, TransportChannel, TransportEndpoint,
-  TransportError, TransportHandle, TransportInboundShared,
+  InboundFrame, RemoteTransport, TokioTransportConfig, TransportBackpressureHookShared, TransportBind,
+  TransportChannel, TransportEndpoint, TransportError, TransportHandle, TransportInboundShared,
+  TransportLifecycleHookShared,
 };
 
-const BACKPRESSURE_THRESHOLD: usize = 1024;
 const CHANNEL_BUFFER_SIZE: usize = 256;
+/// Queued frames below which a backpressured lane signals release.
+const RELEASE_THRESHOLD: usize = CHANNEL_BUFFER_SIZE / 4;
+
+type SharedLifecycleHook = ArcShared<NoStdMutex<Option<TransportLifecycleHookShared>>>;
+#[cfg(feature = "tls")]
+type SharedTlsContext = ArcShared<NoStdMutex<Option<TlsContext>>>;
 
 /// Tokio-based TCP transport implementing the Pekko wire protocol.
 ///
 /// This transport is specialized for [`StdToolbox`] because it uses Tokio's async runtime
 /// and requires `Send + Sync` bounds on the inbound handler that are only available
 /// with standard library mutex implementations.
+///
+/// With the `tls` feature, [`with_tls`](Self::with_tls) switches the transport to the
+/// `fraktor.tcp+tls` scheme: every connection is protected by (mutual) TLS and handshake
+/// failures are reported through the installed lifecycle hook. An inbound connection whose
+/// handshake claims an authority that its client certificate is not issued for is rejected;
+/// otherwise its frames carry the certified authority as
+/// [`peer_authority`](crate::core::InboundFrame::peer_authority).
+///
+/// Frames are bounded by [`TokioTransportConfig::max_frame_size`] in both directions; a peer
+/// sending an oversized frame is disconnected and reported through
+/// [`TransportInbound::on_frame_rejected`](crate::core::TransportInbound::on_frame_rejected).
+/// Inbound connections that fail and a listener that stops accepting are reported through
+/// [`TransportLifecycleHook::on_transport_error`](crate::core::TransportLifecycleHook::on_transport_error).
+/// Payloads above the large-message threshold are chunked onto a second connection per
+/// channel so they cannot delay the ordinary traffic. That connection names the ordinary
+/// connection it belongs to, and its frames and rejections are reported under the ordinary
+/// connection's remote address, so the peer verified by the handshake on the ordinary lane is
+/// held responsible for them.
 pub struct TokioTcpTransport {
-  state:   TokioTcpState,
+  state:     TokioTcpState,
   // hook と inbound は非同期タスクとの共有のため Arc<Mutex> を維持
-  hook:    ArcShared<NoStdMutex<Option<TransportBackpressureHookShared>>>,
-  inbound: ArcShared<NoStdMutex<Option<TransportInboundShared<StdToolbox>>>>,
-  runtime: Arc<Runtime>,
+  hook:      ArcShared<NoStdMutex<Option<TransportBackpressureHookShared>>>,
+  inbound:   ArcShared<NoStdMutex<Option<TransportInboundShared<StdToolbox>>>>,
+  lifecycle: SharedLifecycleHook,
+  #[cfg(feature = "tls")]
+  tls:       SharedTlsContext,
+  limits:    FrameLimits,
+  runtime:   Arc<Runtime>,
 }
 
 struct TokioTcpState {
@@ -57,9 +104,24 @@ struct ListenerHandle {
 }
 
 struct ChannelHandle {
-  #[allow(dead_code)]
   authority: String,
-  sender:    mpsc::Sender<OutboundFrame>,
+  lane:      OutboundLane,
+  // 大きなメッセージ専用の接続は最初の大きな送信時に遅延して開く
+  large:     Option<OutboundLane>,
+}
+
+/// Sending side of one connection; `backpressured` is set while the frame buffer is full.
+#[derive(Clone)]
+struct OutboundLane {
+  sender:        mpsc::Sender<OutboundFrame>,
+  backpressured: Arc<AtomicBool>,
+  local_address: String,
+}
+
+/// Settings of a large-message connection paired with an ordinary connection.
+struct LargeLane {
+  chunk_capacity: usize,
+  preamble:       Vec<u8>,
 }
 
 struct OutboundFrame {
@@ -98,13 +160,78 @@ impl TokioTcpTransport {
   /// Creates a new Tokio TCP transport using the provided runtime.
   pub fn with_runtime(runtime: Runtime) -> Self {
     Self {
-      state:   TokioTcpState { listeners: BTreeMap::new(), channels: BTreeMap::new(), next_channel: 1 },
-      hook:    ArcShared::new(NoStdMutex::new(None)),
+      state: TokioTcpState { listeners: BTreeMap::new(), channels: BTreeMap::new(), next_channel: 1 },
+      hook: ArcShared::new(NoStdMutex::new(None)),
       inbound: ArcShared::new(NoStdMutex::new(None)),
+      lifecycle: ArcShared::new(NoStdMutex::new(None)),
+      #[cfg(feature = "tls")]
+      tls: ArcShared::new(NoStdMutex::new(None)),
+      limits: FrameLimits::from_config(&TokioTransportConfig::new()),
       runtime: Arc::new(runtime),
     }
   }
 
+  /// Applies frame size limits and large-message settings.
+  #[must_use]
+  pub const fn with_transport_config(mut self, config: &TokioTransportConfig) -> Self {
+    self.limits = FrameLimits::from_config(config);
+    self
+  }
+
+  /// Enables TLS for all listeners and channels created afterwards.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TransportError::Tls`] if the certificates or the private key cannot be loaded.
+  #[cfg(feature = "tls")]
+  pub fn with_tls(self, config: TlsConfig) -> Result<Self, TransportError> {
+    *self.tls.lock() = Some(TlsContext::new(config)?);
+    Ok(self)
+  }
+
+  /// Replaces the TLS configuration; new connections use the new certificates.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TransportError::Tls`] if the certificates or the private key cannot be loaded,
+  /// in which case the previous configuration stays active.
+  #[cfg(feature = "tls")]
+  pub fn replace_tls_config(&mut self, config: TlsConfig) -> Result<(), TransportError> {
+    let context = TlsContext::new(config)?;
+    *self.tls.lock() = Some(context);
+    Ok(())
+  }
+
+  /// Re-reads the configured certificates without restarting listeners or channels.
+  ///
+  /// File based configurations are also reloaded automatically when the files change; a failed
+  /// automatic reload keeps the previous certificates and is reported through
+  /// [`TransportLifecycleHook::on_transport_error`](crate::core::TransportLifecycleHook::on_transport_error).
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TransportError::Tls`] if TLS is not enabled or the certificates cannot be loaded.
+  #[cfg(feature = "tls")]
+  pub fn reload_tls(&mut self) -> Result<(), TransportError> {
+    match self.tls.lock().as_mut() {
+      | Some(context) => context.reload(),
+      | None => Err(TransportError::Tls("tls is not enabled".into())),
+    }
+  }
+
+  /// Returns whether TLS is enabled.
+  #[must_use]
+  pub fn is_tls_enabled(&self) -> bool {
+    #[cfg(feature = "tls")]
+    {
+      self.tls.lock().is_some()
+    }
+    #[cfg(not(feature = "tls"))]
+    {
+      false
+    }
+  }
+
   /// Builds a transport instance for the factory.
   pub(crate) fn build() -> Result<Self, TransportError> {
     Self::try_new()
@@ -120,29 +247,100 @@ impl TokioTcpTransport {
       .map_err(|_| TransportError::Io("tokio runtime worker panicked".into()))?
   }
 
-  fn encode_frame(payload: &[u8], correlation_id: CorrelationId) -> Vec<u8> {
-    let header_len = 12_usize;
-    let total = header_len + payload.len();
-    let mut frame = Vec::with_capacity(4 + total);
-    frame.extend_from_slice(&(total as u32).to_be_bytes());
-    frame.extend_from_slice(&correlation_id.to_be_bytes());
-    frame.extend_from_slice(payload);
-    frame
+  // 証明書ファイルの再読み込みに失敗した場合は既存の証明書を使い続け、失敗だけを通知する
+  #[cfg(feature = "tls")]
+  fn refresh_tls(context: &mut TlsContext, lifecycle: &SharedLifecycleHook, authority: &str) {
+    if let Err(error) = context.refresh() {
+      Self::fire_transport_error(lifecycle, authority, &format!("certificate reload failed: {error}"));
+    }
+  }
+
+  fn fire_transport_error(lifecycle: &SharedLifecycleHook, authority: &str, reason: &str) {
+    if let Some(hook) = lifecycle.lock().clone() {
+      hook.lock().on_transport_error(authority, reason);
+    }
+  }
+
+  #[cfg(feature = "tls")]
+  fn fire_handshake_failed(lifecycle: &SharedLifecycleHook, authority: &str, error: &TransportError) {
+    if let Some(hook) = lifecycle.lock().clone() {
+      hook.lock().on_handshake_failed(authority, &error.to_string());
+    }
+  }
+
+  fn register_channel(&mut self, authority: String, lane: OutboundLane) -> TransportChannel {
+    let id = self.state.next_channel;
+    self.state.next_channel += 1;
+    self.state.channels.insert(id, ChannelHandle { authority, lane, large: None });
+    TransportChannel::new(id)
   }
 
-  #[allow(dead_code)]
-  fn fire_backpressure(&self, authority: &str, signal: BackpressureSignal, correlation_id: CorrelationId) {
-    if let Some(hook) = self.hook.lock().clone() {
-      let mut guard = hook.lock();
-      guard.on_backpressure(signal, authority, correlation_id);
+  fn fire_frame_rejected(
+    inbound: &ArcShared<NoStdMutex<Option<TransportInboundShared<StdToolbox>>>>,
+    remote: &str,
+    error: &TransportError,
+  ) {
+    if let Some(handler) = inbound.lock().clone() {
+      handler.lock().on_frame_rejected(remote, error);
     }
   }
 
+  /// Connects to `authority` (with TLS when enabled) and spawns the sender task for one lane.
+  ///
+  /// `paired_lane` is the local address of the ordinary connection a large-message lane
+  /// belongs to; `None` opens an ordinary lane.
+  fn connect_lane(&mut self, authority: &str, paired_lane: Option<&str>) -> Result<OutboundLane, TransportError> {
+    let hook = self.hook.clone();
+    let large = paired_lane.map(|paired| LargeLane {
+      chunk_capacity: self.limits.chunk_capacity(),
+      preamble:       encode_large_lane_preamble(paired),
+    });
+
+    let stream = {
+      let authority_clone = authority.to_string();
+      self.block_on_future(async move {
+        TcpStream::connect(&authority_clone).await.map_err(|e| TransportError::Io(format!("connection failed: {e}")))
+      })?
+    };
+
+    let local_address =
+      stream.local_addr().map_err(|e| TransportError::Io(format!("local address unavailable: {e}")))?.to_string();
+    let (sender, receiver) = mpsc::channel(CHANNEL_BUFFER_SIZE);
+    let lane = OutboundLane { sender, backpressured: Arc::new(AtomicBool::new(false)), local_address };
+    let backpressured = lane.backpressured.clone();
+
+    #[cfg(feature = "tls")]
+    let connector = self
+      .tls
+      .lock()
+      .as_mut()
+      .map(|context| {
+        Self::refresh_tls(context, &self.lifecycle, authority);
+        context.connector(authority)
+      })
+      .transpose()?;
+    #[cfg(feature = "tls")]
+    if let Some((connector, server_name)) = connector {
+      let handshake = self.block_on_future(async move {
+        connector.connect(server_name, stream).await.map_err(|e| TransportError::Tls(format!("handshake failed: {e}")))
+      });
+      let stream = handshake.inspect_err(|error| Self::fire_handshake_failed(&self.lifecycle, authority, error))?;
+      self.runtime.spawn(Self::sender_loop(stream, authority.to_string(), receiver, hook, backpressured, large));
+      return Ok(lane);
+    }
+
+    self.runtime.spawn(Self::sender_loop(stream, authority.to_string(), receiver, hook, backpressured, large));
+    Ok(lane)
+  }
+
   async fn accept_loop(
     listener: TcpListener,
     authority: String,
     hook: ArcShared<NoStdMutex<Option<TransportBackpressureHookShared>>>,
     inbound: ArcShared<NoStdMutex<Option<TransportInboundShared<StdToolbox>>>>,
+    limits: FrameLimits,
+    lifecycle: SharedLifecycleHook,
+    #[cfg(feature = "tls")] tls: SharedTlsContext,
   ) {
     loop {
       match listener.accept().await {
@@ -151,81 +349,202 @@ impl TokioTcpTransport {
           let hook_clone = hook.clone();
           let inbound_clone = inbound.clone();
           let remote = peer.to_string();
+          let lifecycle_clone = lifecycle.clone();
+          #[cfg(feature = "tls")]
+          let acceptor = tls.lock().as_mut().map(|context| {
+            Self::refresh_tls(context, &lifecycle, &authority);
+            context.acceptor()
+          });
           tokio::spawn(async move {
-            if let Err(e) = Self::handle_inbound(stream, authority_clone, remote, hook_clone, inbound_clone).await {
-              eprintln!("Inbound connection error: {e:?}");
+            #[cfg(feature = "tls")]
+            if let Some(acceptor) = acceptor {
+              let result = match acceptor.accept(stream).await {
+                | Ok(stream) => {
+                  let certificate = stream
+                    .get_ref()
+                    .1
+                    .peer_certificates()
+                    .and_then(|chain| chain.first())
+                    .map(|certificate| certificate.clone().into_owned());
+                  let identity = PeerIdentity::new(certificate);
+                  Self::handle_inbound(
+                    stream,
+                    authority_clone,
+                    remote.clone(),
+                    hook_clone,
+                    inbound_clone,
+                    limits,
+                    identity,
+                  )
+                  .await
+                },
+                | Err(error) => {
+                  // ハンドシェイク失敗は on_handshake_failed で通知済みのため重ねて報告しない
+                  let error = TransportError::Tls(format!("handshake failed: {error}"));
+                  Self::fire_handshake_failed(&lifecycle_clone, &remote, &error);
+                  return;
+                },
+              };
+              if let Err(error) = result {
+                Self::fire_transport_error(&lifecycle_clone, &remote, &format!("inbound connection failed: {error}"));
+              }
+              return;
+            }
+            if let Err(error) = Self::handle_inbound(
+              stream,
+              authority_clone,
+              remote.clone(),
+              hook_clone,
+              inbound_clone,
+              limits,
+              #[cfg(feature = "tls")]
+              PeerIdentity::new(None),
+            )
+            .await
+            {
+              Self::fire_transport_error(&lifecycle_clone, &remote, &format!("inbound connection failed: {error}"));
             }
           });
         },
-        | Err(e) => {
-          eprintln!("Accept error: {e:?}");
+        | Err(error) => {
+          Self::fire_transport_error(&lifecycle, &authority, &format!("listener stopped accepting: {error}"));
           break;
         },
       }
     }
   }
 
-  async fn handle_inbound(
-    mut stream: TcpStream,
+  async fn handle_inbound<S: AsyncRead + Unpin>(
+    mut stream: S,
     authority: String,
-    remote: String,
+    mut remote: String,
     hook: ArcShared<NoStdMutex<Option<TransportBackpressureHookShared>>>,
     inbound: ArcShared<NoStdMutex<Option<TransportInboundShared<StdToolbox>>>>,
+    limits: FrameLimits,
+    #[cfg(feature = "tls")] mut identity: PeerIdentity,
   ) -> Result<(), TransportError> {
     let mut buffer = Vec::new();
+    let mut reassembler: Option<ChunkReassembler> = None;
+    let mut first_frame = true;
     loop {
       let mut len_bytes = [0u8; 4];
       if stream.read_exact(&mut len_bytes).await.is_err() {
         break;
       }
-      let total_len = u32::from_be_bytes(len_bytes) as usize;
-      if total_len < 12 {
-        return Err(TransportError::Io("invalid frame: length too short".into()));
+      let prefix = u32::from_be_bytes(len_bytes);
+      // 大きなメッセージ用レーンへの切り替えは接続の先頭でのみ受け付け、
+      // 途中のマーカーは長さ超過として拒否する
+      if core::mem::replace(&mut first_frame, false) && prefix == LARGE_LANE_MARKER {
+        // 以降のフレームと違反は対応する通常接続の送信元として扱い、handshake で検証済みのピアに帰属させる
+        remote = Self::read_lane_pairing(&mut stream, &remote)
+          .await
+          .inspect_err(|error| Self::fire_frame_rejected(&inbound, &remote, error))?;
+        reassembler = Some(ChunkReassembler::new(limits.max_message_size()));
+        continue;
+      }
+      let total_len = prefix as usize;
+      // 長さを検証してからバッファを確保する
+      if let Err(error) = limits.check_inbound(total_len) {
+        Self::fire_frame_rejected(&inbound, &remote, &error);
+        return Err(error);
       }
       buffer.resize(total_len, 0);
       stream.read_exact(&mut buffer).await.map_err(|e| TransportError::Io(format!("frame read failed: {e}")))?;
-      // CorrelationId は 96bit (12 bytes) = u64 (8 bytes) + u32 (4 bytes)
-      let hi = u64::from_be_bytes(buffer[0..8].try_into().unwrap());
-      let lo = u32::from_be_bytes(buffer[8..12].try_into().unwrap());
-      let correlation_id = CorrelationId::new(hi, lo);
-      let _payload = &buffer[12..];
+      let (correlation_id, payload) = match reassembler.as_mut() {
+        | Some(reassembler) => match reassembler.push(&buffer) {
+          | Ok(Some(message)) => message,
+          | Ok(None) => continue,
+          | Err(error) => {
+            Self::fire_frame_rejected(&inbound, &remote, &error);
+            return Err(error);
+          },
+        },
+        | None => (decode_correlation_id(&buffer), buffer[12..].to_vec()),
+      };
       if let Some(hook_ref) = hook.lock().clone() {
         let mut guard = hook_ref.lock();
         guard.on_backpressure(BackpressureSignal::Release, &authority, correlation_id);
       }
+      let frame = InboundFrame::new(&authority, remote.clone(), payload, correlation_id);
+      // クライアント証明書で確認できない authority を名乗る接続は切断する
+      #[cfg(feature = "tls")]
+      let frame = identity.stamp(frame).inspect_err(|error| Self::fire_frame_rejected(&inbound, &remote, error))?;
       if let Some(handler) = inbound.lock().clone() {
-        handler.lock().on_frame(InboundFrame::new(&authority, remote.clone(), buffer[12..].to_vec(), correlation_id));
+        handler.lock().on_frame(frame);
       }
     }
     Ok(())
   }
 
-  async fn sender_loop(
-    mut stream: TcpStream,
+  /// Reads the ordinary-connection address announced by a large-lane preamble.
+  ///
+  /// The announced address must be on the same host as the large-lane connection itself, so a
+  /// connection cannot attribute its frames to another host's peer.
+  async fn read_lane_pairing<S: AsyncRead + Unpin>(stream: &mut S, remote: &str) -> Result<String, TransportError> {
+    let mut len_bytes = [0u8; 2];
+    stream
+      .read_exact(&mut len_bytes)
+      .await
+      .map_err(|e| TransportError::Io(format!("lane pairing read failed: {e}")))?;
+    let len = usize::from(u16::from_be_bytes(len_bytes));
+    if len > MAX_LANE_PAIRING_LEN {
+      return Err(TransportError::FrameTooLarge { size: len, max: MAX_LANE_PAIRING_LEN });
+    }
+    let mut paired = vec![0u8; len];
+    stream.read_exact(&mut paired).await.map_err(|e| TransportError::Io(format!("lane pairing read failed: {e}")))?;
+    let paired = String::from_utf8(paired).map_err(|_| TransportError::Io("lane pairing is not UTF-8".into()))?;
+    let paired_host = paired.parse::<SocketAddr>().map(|address| address.ip());
+    let remote_host = remote.parse::<SocketAddr>().map(|address| address.ip());
+    match (paired_host, remote_host) {
+      | (Ok(paired_host), Ok(remote_host)) if paired_host == remote_host => Ok(paired),
+      | _ => Err(TransportError::Io(format!("large lane from {remote} claims foreign connection {paired}"))),
+    }
+  }
+
+  async fn sender_loop<S: AsyncWrite + Unpin>(
+    mut stream: S,
     authority: String,
     mut receiver: mpsc::Receiver<OutboundFrame>,
     hook: ArcShared<NoStdMutex<Option<TransportBackpressureHookShared>>>,
+    backpressured: Arc<AtomicBool>,
+    large: Option<LargeLane>,
   ) {
-    let mut pending_count = 0_usize;
+    if let Some(large) = &large
+      && stream.write_all(&large.preamble).await.is_err()
+    {
+      return;
+    }
+    let chunk_capacity = large.map(|large| large.chunk_capacity);
     while let Some(frame) = receiver.recv().await {
-      let encoded = Self::encode_frame(&frame.payload, frame.correlation_id);
+      let encoded = match chunk_capacity {
+        | Some(capacity) => encode_chunks(&frame.payload, frame.correlation_id, capacity).concat(),
+        | None => encode_frame(&frame.payload, frame.correlation_id),
+      };
       if stream.write_all(&encoded).await.is_err() {
         break;
       }
-      pending_count += 1;
-      if pending_count >= BACKPRESSURE_THRESHOLD
-        && let Some(hook_ref) = hook.lock().clone()
-      {
-        let mut guard = hook_ref.lock();
-        guard.on_backpressure(BackpressureSignal::Apply, &authority, frame.correlation_id);
+      // バッファが十分に空いたら、送信側に課していたバックプレッシャーを解除する
+      if receiver.len() <= RELEASE_THRESHOLD && backpressured.swap(false, Ordering::AcqRel) {
+        Self::fire_backpressure(&hook, BackpressureSignal::Release, &authority, frame.correlation_id);
       }
     }
   }
+
+  fn fire_backpressure(
+    hook: &ArcShared<NoStdMutex<Option<TransportBackpressureHookShared>>>,
+    signal: BackpressureSignal,
+    authority: &str,
+    correlation_id: CorrelationId,
+  ) {
+    if let Some(hook_ref) = hook.lock().clone() {
+      hook_ref.lock().on_backpressure(signal, authority, correlation_id);
+    }
+  }
 }
 
 impl RemoteTransport<StdToolbox> for TokioTcpTransport {
   fn scheme(&self) -> &str {
-    "fraktor.tcp"
+    if self.is_tls_enabled() { "fraktor.tcp+tls" } else { "fraktor.tcp" }
   }
 
   fn spawn_listener(&mut self, bind: &TransportBind) -> Result<TransportHandle, TransportError> {
@@ -240,7 +559,16 @@ impl RemoteTransport<StdToolbox> for TokioTcpTransport {
       })?
     };
 
-    let task = self.runtime.spawn(Self::accept_loop(listener, authority.clone(), hook, inbound));
+    let task = self.runtime.spawn(Self::accept_loop(
+      listener,
+      authority.clone(),
+      hook,
+      inbound,
+      self.limits,
+      self.lifecycle.clone(),
+      #[cfg(feature = "tls")]
+      self.tls.clone(),
+    ));
 
     self.state.listeners.insert(authority.clone(), ListenerHandle { _task: task });
 
@@ -249,24 +577,8 @@ impl RemoteTransport<StdToolbox> for TokioTcpTransport {
 
   fn open_channel(&mut self, endpoint: &TransportEndpoint) -> Result<TransportChannel, TransportError> {
     let authority = endpoint.authority().to_string();
-    let hook = self.hook.clone();
-
-    let stream = {
-      let authority_clone = authority.clone();
-      self.block_on_future(async move {
-        TcpStream::connect(&authority_clone).await.map_err(|e| TransportError::Io(format!("connection failed: {e}")))
-      })?
-    };
-
-    let (sender, receiver) = mpsc::channel(CHANNEL_BUFFER_SIZE);
-
-    self.runtime.spawn(Self::sender_loop(stream, authority.clone(), receiver, hook));
-
-    let id = self.state.next_channel;
-    self.state.next_channel += 1;
-    self.state.channels.insert(id, ChannelHandle { authority, sender });
-
-    Ok(TransportChannel::new(id))
+    let lane = self.connect_lane(&authority, None)?;
+    Ok(self.register_channel(authority, lane))
   }
 
   fn send(
@@ -275,13 +587,39 @@ impl RemoteTransport<StdToolbox> for TokioTcpTransport {
     payload: &[u8],
     correlation_id: CorrelationId,
   ) -> Result<(), TransportError> {
+    self.limits.check_outbound(payload.len())?;
     let handle = self.state.channels.get(&channel.id()).ok_or(TransportError::ChannelUnavailable(channel.id()))?;
 
-    let frame = OutboundFrame { payload: payload.to_vec(), correlation_id };
+    let authority = handle.authority.clone();
+    let lane = if self.limits.is_large(payload.len()) {
+      match handle.large.clone() {
+        | Some(lane) => lane,
+        | None => {
+          let paired_lane = handle.lane.local_address.clone();
+          let lane = self.connect_lane(&authority, Some(&paired_lane))?;
+          if let Some(handle) = self.state.channels.get_mut(&channel.id()) {
+            handle.large = Some(lane.clone());
+          }
+          lane
+        },
+      }
+    } else {
+      handle.lane.clone()
+    };
 
-    handle.sender.try_send(frame).map_err(|_| TransportError::ChannelUnavailable(channel.id()))?;
+    let frame = OutboundFrame { payload: payload.to_vec(), correlation_id };
 
-    Ok(())
+    match lane.sender.try_send(frame) {
+      | Ok(()) => Ok(()),
+      | Err(TrySendError::Full(_)) => {
+        // 接続の書き込みが追いつかないため、解除されるまで association のユーザー送信を止める
+        if !lane.backpressured.swap(true, Ordering::AcqRel) {
+          Self::fire_backpressure(&self.hook, BackpressureSignal::Apply, &authority, correlation_id);
+        }
+        Err(TransportError::Backpressure(authority))
+      },
+      | Err(TrySendError::Closed(_)) => Err(TransportError::ChannelUnavailable(channel.id())),
+    }
   }
 
   fn close(&mut self, channel: &TransportChannel) {
@@ -292,6 +630,10 @@ impl RemoteTransport<StdToolbox> for TokioTcpTransport {
     *self.hook.lock() = Some(hook);
   }
 
+  fn install_lifecycle_hook(&mut self, hook: TransportLifecycleHookShared) {
+    *self.lifecycle.lock() = Some(hook);
+  }
+
   fn install_inbound_handler(&mut self, handler: TransportInboundShared<StdToolbox>) {
     *self.inbound.lock() = Some(handler);
   }
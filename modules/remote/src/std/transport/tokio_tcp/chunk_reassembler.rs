@@ -0,0 +1,61 @@
+//! Reassembles chunked large messages received on one connection.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+
+use fraktor_actor_rs::core::event_stream::CorrelationId;
+
+use super::frame_codec::{CHUNK_HEADER_LEN, FRAME_HEADER_LEN, decode_correlation_id};
+use crate::core::TransportError;
+
+/// Collects the chunks of the message currently being received.
+///
+/// Chunks of one message arrive back to back on a large-message connection, so only a single
+/// message is in progress at a time.
+pub(crate) struct ChunkReassembler {
+  max_message_size: usize,
+  expected:         Option<(CorrelationId, usize)>,
+  buffer:           Vec<u8>,
+}
+
+impl ChunkReassembler {
+  pub(crate) const fn new(max_message_size: usize) -> Self {
+    Self { max_message_size, expected: None, buffer: Vec::new() }
+  }
+
+  /// Adds a chunk frame body and returns the message once all of its chunks arrived.
+  pub(crate) fn push(&mut self, body: &[u8]) -> Result<Option<(CorrelationId, Vec<u8>)>, TransportError> {
+    if body.len() < CHUNK_HEADER_LEN {
+      return Err(TransportError::Io("invalid chunk: length too short".into()));
+    }
+    let correlation_id = decode_correlation_id(body);
+    let mut length = [0_u8; 4];
+    length.copy_from_slice(&body[FRAME_HEADER_LEN..CHUNK_HEADER_LEN]);
+    let message_len = u32::from_be_bytes(length) as usize;
+    match self.expected {
+      | None => {
+        if message_len > self.max_message_size {
+          return Err(TransportError::FrameTooLarge { size: message_len, max: self.max_message_size });
+        }
+        self.expected = Some((correlation_id, message_len));
+        self.buffer = Vec::with_capacity(message_len);
+      },
+      | Some(expected) if expected != (correlation_id, message_len) => {
+        return Err(TransportError::Io("invalid chunk: interleaved message".into()));
+      },
+      | Some(_) => {},
+    }
+    let chunk = &body[CHUNK_HEADER_LEN..];
+    if self.buffer.len() + chunk.len() > message_len {
+      return Err(TransportError::Io("invalid chunk: message length exceeded".into()));
+    }
+    self.buffer.extend_from_slice(chunk);
+    if self.buffer.len() < message_len {
+      return Ok(None);
+    }
+    self.expected = None;
+    Ok(Some((correlation_id, core::mem::take(&mut self.buffer))))
+  }
+}
@@ -0,0 +1,51 @@
+use fraktor_actor_rs::core::event_stream::CorrelationId;
+
+use super::ChunkReassembler;
+use crate::{core::TransportError, std::transport::tokio_tcp::frame_codec::encode_chunks};
+
+fn bodies(payload: &[u8], correlation: CorrelationId, capacity: usize) -> Vec<Vec<u8>> {
+  encode_chunks(payload, correlation, capacity).into_iter().map(|frame| frame[4..].to_vec()).collect()
+}
+
+#[test]
+fn message_is_returned_after_last_chunk() {
+  let correlation = CorrelationId::from_u128(3);
+  let payload: Vec<u8> = (0..25).collect();
+  let mut reassembler = ChunkReassembler::new(1024);
+
+  let mut results = bodies(&payload, correlation, 10).into_iter().map(|body| reassembler.push(&body).expect("chunk"));
+
+  assert_eq!(results.next(), Some(None));
+  assert_eq!(results.next(), Some(None));
+  assert_eq!(results.next(), Some(Some((correlation, payload))));
+}
+
+#[test]
+fn consecutive_messages_are_reassembled_independently() {
+  let mut reassembler = ChunkReassembler::new(1024);
+  for id in 0..2_u128 {
+    let correlation = CorrelationId::from_u128(id);
+    let payload = vec![id as u8; 8];
+    let mut last = None;
+    for body in bodies(&payload, correlation, 5) {
+      last = reassembler.push(&body).expect("chunk");
+    }
+    assert_eq!(last, Some((correlation, payload)));
+  }
+}
+
+#[test]
+fn oversized_message_is_rejected_on_first_chunk() {
+  let mut reassembler = ChunkReassembler::new(16);
+  let body = &bodies(&[0; 32], CorrelationId::from_u128(1), 8)[0];
+  assert_eq!(reassembler.push(body), Err(TransportError::FrameTooLarge { size: 32, max: 16 }));
+}
+
+#[test]
+fn interleaved_message_is_rejected() {
+  let mut reassembler = ChunkReassembler::new(1024);
+  let first = bodies(&[1; 16], CorrelationId::from_u128(1), 8);
+  let second = bodies(&[2; 16], CorrelationId::from_u128(2), 8);
+  reassembler.push(&first[0]).expect("chunk");
+  assert!(matches!(reassembler.push(&second[0]), Err(TransportError::Io(_))));
+}
@@ -0,0 +1,69 @@
+//! Wire encoding of Tokio TCP transport frames.
+//!
+//! Ordinary frames are `[u32 length][12 byte correlation id][payload]`. A large-message
+//! connection starts with [`LARGE_LANE_MARKER`] in place of the first length, followed by
+//! `[u16 length][address]` naming the local address of the ordinary connection it belongs to,
+//! and then carries chunk frames `[u32 length][12 byte correlation id][u32 message length][chunk]`.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+
+use fraktor_actor_rs::core::event_stream::CorrelationId;
+
+/// Bytes following the length prefix of an ordinary frame before the payload.
+pub(crate) const FRAME_HEADER_LEN: usize = 12;
+/// Bytes following the length prefix of a chunk frame before the chunk.
+pub(crate) const CHUNK_HEADER_LEN: usize = FRAME_HEADER_LEN + 4;
+/// Preamble announcing a large-message connection; never a valid frame length.
+pub(crate) const LARGE_LANE_MARKER: u32 = u32::MAX;
+/// Maximum length of the ordinary-connection address carried by a large-lane preamble.
+pub(crate) const MAX_LANE_PAIRING_LEN: usize = 128;
+
+/// Encodes the preamble of a large-message connection paired with the ordinary connection
+/// whose local address is `paired_lane`.
+pub(crate) fn encode_large_lane_preamble(paired_lane: &str) -> Vec<u8> {
+  let mut preamble = Vec::with_capacity(6 + paired_lane.len());
+  preamble.extend_from_slice(&LARGE_LANE_MARKER.to_be_bytes());
+  preamble.extend_from_slice(&(paired_lane.len() as u16).to_be_bytes());
+  preamble.extend_from_slice(paired_lane.as_bytes());
+  preamble
+}
+
+/// Encodes an ordinary frame.
+pub(crate) fn encode_frame(payload: &[u8], correlation_id: CorrelationId) -> Vec<u8> {
+  let total = FRAME_HEADER_LEN + payload.len();
+  let mut frame = Vec::with_capacity(4 + total);
+  frame.extend_from_slice(&(total as u32).to_be_bytes());
+  frame.extend_from_slice(&correlation_id.to_be_bytes());
+  frame.extend_from_slice(payload);
+  frame
+}
+
+/// Splits a payload into chunk frames carrying at most `chunk_capacity` payload bytes each.
+pub(crate) fn encode_chunks(payload: &[u8], correlation_id: CorrelationId, chunk_capacity: usize) -> Vec<Vec<u8>> {
+  let message_len = (payload.len() as u32).to_be_bytes();
+  payload
+    .chunks(chunk_capacity.max(1))
+    .map(|chunk| {
+      let total = CHUNK_HEADER_LEN + chunk.len();
+      let mut frame = Vec::with_capacity(4 + total);
+      frame.extend_from_slice(&(total as u32).to_be_bytes());
+      frame.extend_from_slice(&correlation_id.to_be_bytes());
+      frame.extend_from_slice(&message_len);
+      frame.extend_from_slice(chunk);
+      frame
+    })
+    .collect()
+}
+
+/// Decodes the correlation id at the start of a frame body.
+pub(crate) fn decode_correlation_id(body: &[u8]) -> CorrelationId {
+  // CorrelationId は 96bit (12 bytes) = u64 (8 bytes) + u32 (4 bytes)
+  let mut hi = [0_u8; 8];
+  let mut lo = [0_u8; 4];
+  hi.copy_from_slice(&body[0..8]);
+  lo.copy_from_slice(&body[8..12]);
+  CorrelationId::new(u64::from_be_bytes(hi), u32::from_be_bytes(lo))
+}
@@ -0,0 +1,38 @@
+use fraktor_actor_rs::core::event_stream::CorrelationId;
+
+use super::{
+  CHUNK_HEADER_LEN, FRAME_HEADER_LEN, LARGE_LANE_MARKER, decode_correlation_id, encode_chunks, encode_frame,
+  encode_large_lane_preamble,
+};
+
+#[test]
+fn frame_has_length_prefix_and_correlation() {
+  let correlation = CorrelationId::from_u128(0xABCD);
+  let frame = encode_frame(&[1, 2, 3], correlation);
+  assert_eq!(&frame[..4], &((FRAME_HEADER_LEN + 3) as u32).to_be_bytes());
+  assert_eq!(decode_correlation_id(&frame[4..]), correlation);
+  assert_eq!(&frame[16..], &[1, 2, 3]);
+}
+
+#[test]
+fn chunks_split_payload_and_repeat_message_length() {
+  let correlation = CorrelationId::from_u128(7);
+  let payload: Vec<u8> = (0..10).collect();
+  let chunks = encode_chunks(&payload, correlation, 4);
+
+  assert_eq!(chunks.len(), 3);
+  for chunk in &chunks {
+    assert_eq!(&chunk[16..20], &10_u32.to_be_bytes());
+  }
+  assert_eq!(&chunks[2][..4], &((CHUNK_HEADER_LEN + 2) as u32).to_be_bytes());
+  let joined: Vec<u8> = chunks.iter().flat_map(|chunk| chunk[20..].to_vec()).collect();
+  assert_eq!(joined, payload);
+}
+
+#[test]
+fn large_lane_preamble_names_the_paired_connection() {
+  let preamble = encode_large_lane_preamble("127.0.0.1:4000");
+  assert_eq!(&preamble[..4], &LARGE_LANE_MARKER.to_be_bytes());
+  assert_eq!(&preamble[4..6], &14_u16.to_be_bytes());
+  assert_eq!(&preamble[6..], b"127.0.0.1:4000");
+}
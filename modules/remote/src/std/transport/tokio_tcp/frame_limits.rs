@@ -0,0 +1,63 @@
+//! Size limits applied to frames of the Tokio TCP transport.
+
+#[cfg(test)]
+mod tests;
+
+use super::frame_codec::{CHUNK_HEADER_LEN, FRAME_HEADER_LEN};
+use crate::core::{TokioTransportConfig, TransportError};
+
+/// Frame and message size limits derived from [`TokioTransportConfig`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct FrameLimits {
+  max_frame_size:          usize,
+  large_message_threshold: usize,
+  max_message_size:        usize,
+}
+
+impl FrameLimits {
+  pub(crate) const fn from_config(config: &TokioTransportConfig) -> Self {
+    Self {
+      max_frame_size:          config.max_frame_size(),
+      large_message_threshold: config.large_message_threshold(),
+      max_message_size:        config.max_message_size(),
+    }
+  }
+
+  pub(crate) const fn max_message_size(&self) -> usize {
+    self.max_message_size
+  }
+
+  /// Returns the payload bytes carried by one large-message chunk.
+  pub(crate) const fn chunk_capacity(&self) -> usize {
+    let capacity = self.max_frame_size.saturating_sub(CHUNK_HEADER_LEN);
+    if capacity == 0 { 1 } else { capacity }
+  }
+
+  /// Returns whether a payload is sent over the large-message lane.
+  pub(crate) const fn is_large(&self, payload_len: usize) -> bool {
+    payload_len > self.large_message_threshold
+  }
+
+  /// Validates a payload before it is queued for sending.
+  pub(crate) const fn check_outbound(&self, payload_len: usize) -> Result<(), TransportError> {
+    if self.is_large(payload_len) {
+      if payload_len > self.max_message_size {
+        return Err(TransportError::FrameTooLarge { size: payload_len, max: self.max_message_size });
+      }
+    } else if FRAME_HEADER_LEN + payload_len > self.max_frame_size {
+      return Err(TransportError::FrameTooLarge { size: FRAME_HEADER_LEN + payload_len, max: self.max_frame_size });
+    }
+    Ok(())
+  }
+
+  /// Validates the length prefix of a received frame before its body is allocated.
+  pub(crate) fn check_inbound(&self, frame_len: usize) -> Result<(), TransportError> {
+    if frame_len < FRAME_HEADER_LEN {
+      return Err(TransportError::Io("invalid frame: length too short".into()));
+    }
+    if frame_len > self.max_frame_size {
+      return Err(TransportError::FrameTooLarge { size: frame_len, max: self.max_frame_size });
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,38 @@
+use super::FrameLimits;
+use crate::core::{TokioTransportConfig, TransportError};
+
+fn limits() -> FrameLimits {
+  FrameLimits::from_config(
+    &TokioTransportConfig::new().with_max_frame_size(64).with_large_message_threshold(32).with_max_message_size(256),
+  )
+}
+
+#[test]
+fn small_payloads_must_fit_in_one_frame() {
+  let limits = FrameLimits::from_config(&TokioTransportConfig::new().with_max_frame_size(32));
+  assert!(limits.check_outbound(20).is_ok());
+  assert_eq!(limits.check_outbound(21), Err(TransportError::FrameTooLarge { size: 33, max: 32 }));
+}
+
+#[test]
+fn large_payloads_are_bounded_by_message_size() {
+  let limits = limits();
+  assert!(!limits.is_large(32));
+  assert!(limits.is_large(33));
+  assert!(limits.check_outbound(256).is_ok());
+  assert_eq!(limits.check_outbound(257), Err(TransportError::FrameTooLarge { size: 257, max: 256 }));
+}
+
+#[test]
+fn inbound_length_is_checked_before_allocation() {
+  let limits = limits();
+  assert!(limits.check_inbound(64).is_ok());
+  assert_eq!(limits.check_inbound(65), Err(TransportError::FrameTooLarge { size: 65, max: 64 }));
+  assert!(matches!(limits.check_inbound(11), Err(TransportError::Io(_))));
+}
+
+#[test]
+fn chunk_capacity_leaves_room_for_header() {
+  assert_eq!(limits().chunk_capacity(), 48);
+  assert_eq!(FrameLimits::from_config(&TokioTransportConfig::new().with_max_frame_size(0)).chunk_capacity(), 1);
+}
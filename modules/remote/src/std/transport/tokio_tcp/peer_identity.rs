@@ -0,0 +1,60 @@
+//! Binds the authority claimed in a handshake to the client certificate of a TLS connection.
+
+use alloc::{
+  format,
+  string::{String, ToString},
+};
+
+use rustls::pki_types::{CertificateDer, ServerName};
+use webpki::EndEntityCert;
+
+use crate::core::{HandshakeFrame, InboundFrame, TransportError};
+
+/// Identity proven by the client certificate of one inbound TLS connection.
+///
+/// The first handshake frame must claim an authority whose host is covered by the certificate
+/// (IP or DNS subject alternative name). Once verified, every frame of the connection carries
+/// that authority as its [`peer_authority`](InboundFrame::peer_authority).
+pub(crate) struct PeerIdentity {
+  certificate: Option<CertificateDer<'static>>,
+  verified:    Option<String>,
+}
+
+impl PeerIdentity {
+  /// Creates the identity of a connection that presented `certificate`, if any.
+  pub(crate) const fn new(certificate: Option<CertificateDer<'static>>) -> Self {
+    Self { certificate, verified: None }
+  }
+
+  /// Stamps `frame` with the verified authority.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TransportError::Tls`] when a handshake claims an authority the certificate is not
+  /// issued for.
+  pub(crate) fn stamp(&mut self, frame: InboundFrame) -> Result<InboundFrame, TransportError> {
+    let Some(certificate) = &self.certificate else {
+      return Ok(frame);
+    };
+    if let Ok(handshake) = HandshakeFrame::decode(frame.payload())
+      && let Some(port) = handshake.port()
+    {
+      let claimed = format!("{}:{port}", handshake.host());
+      if !certifies(certificate, handshake.host()) {
+        return Err(TransportError::Tls(format!("client certificate is not issued for {claimed}")));
+      }
+      self.verified = Some(claimed);
+    }
+    Ok(match &self.verified {
+      | Some(authority) => frame.with_peer_authority(authority.clone()),
+      | None => frame,
+    })
+  }
+}
+
+fn certifies(certificate: &CertificateDer<'static>, host: &str) -> bool {
+  let Ok(name) = ServerName::try_from(host.to_string()) else {
+    return false;
+  };
+  EndEntityCert::try_from(certificate).is_ok_and(|cert| cert.verify_is_valid_for_subject_name(&name).is_ok())
+}
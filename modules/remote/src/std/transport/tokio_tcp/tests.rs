@@ -60,3 +60,496 @@ fn can_send_message() {
   let result = transport.send(&channel, &payload, correlation);
   assert!(result.is_ok());
 }
+
+mod frames {
+  use alloc::{boxed::Box, string::String, vec::Vec};
+  use core::time::Duration;
+  use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Instant,
+  };
+
+  use fraktor_actor_rs::core::event_stream::CorrelationId;
+  use fraktor_utils_rs::{
+    core::{
+      runtime_toolbox::{NoStdMutex, RuntimeToolbox, SyncMutexFamily},
+      sync::ArcShared,
+    },
+    std::runtime_toolbox::StdToolbox,
+  };
+
+  use super::find_free_port;
+  use crate::{
+    core::{
+      InboundFrame, RemoteTransport, TokioTransportConfig, TransportBind, TransportEndpoint, TransportError,
+      TransportInbound, TransportInboundShared,
+    },
+    std::transport::TokioTcpTransport,
+  };
+
+  type Recorded<T> = ArcShared<NoStdMutex<Vec<T>>>;
+
+  struct RecordingInbound {
+    payloads:  Recorded<Vec<u8>>,
+    rejection: Recorded<(String, TransportError)>,
+  }
+
+  impl TransportInbound for RecordingInbound {
+    fn on_frame(&mut self, frame: InboundFrame) {
+      self.payloads.lock().push(frame.payload().to_vec());
+    }
+
+    fn on_frame_rejected(&mut self, remote_address: &str, error: &TransportError) {
+      self.rejection.lock().push((remote_address.into(), error.clone()));
+    }
+  }
+
+  fn small_frames() -> TokioTransportConfig {
+    TokioTransportConfig::new().with_max_frame_size(64).with_large_message_threshold(32).with_max_message_size(4096)
+  }
+
+  fn record_inbound(transport: &mut TokioTcpTransport) -> (Recorded<Vec<u8>>, Recorded<(String, TransportError)>) {
+    let payloads: Recorded<Vec<u8>> = ArcShared::new(NoStdMutex::new(Vec::new()));
+    let rejection: Recorded<(String, TransportError)> = ArcShared::new(NoStdMutex::new(Vec::new()));
+    let handler: Box<dyn TransportInbound> =
+      Box::new(RecordingInbound { payloads: payloads.clone(), rejection: rejection.clone() });
+    let shared: TransportInboundShared<StdToolbox> =
+      ArcShared::new(<StdToolbox as RuntimeToolbox>::MutexFamily::create(handler));
+    transport.install_inbound_handler(shared);
+    (payloads, rejection)
+  }
+
+  fn listen(transport: &mut TokioTcpTransport) -> String {
+    let port = find_free_port();
+    transport.spawn_listener(&TransportBind::new("127.0.0.1", Some(port))).expect("listener");
+    thread::sleep(Duration::from_millis(50));
+    format!("127.0.0.1:{port}")
+  }
+
+  fn wait_until(condition: impl Fn() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+      if condition() {
+        return true;
+      }
+      thread::sleep(Duration::from_millis(10));
+    }
+    false
+  }
+
+  #[test]
+  fn oversized_inbound_frame_is_rejected_before_allocation() {
+    let mut server = TokioTcpTransport::default().with_transport_config(&small_frames());
+    let (payloads, rejection) = record_inbound(&mut server);
+    let authority = listen(&mut server);
+
+    let mut peer = TcpStream::connect(&authority).expect("connect");
+    let local = peer.local_addr().expect("addr").to_string();
+    peer.write_all(&0x8000_0000_u32.to_be_bytes()).expect("write");
+
+    assert!(wait_until(|| !rejection.lock().is_empty()));
+    assert_eq!(rejection.lock().clone(), vec![(local, TransportError::FrameTooLarge { size: 0x8000_0000, max: 64 })]);
+    assert!(payloads.lock().is_empty());
+    // 違反した接続は切断される
+    let mut buffer = [0_u8; 1];
+    peer.set_read_timeout(Some(Duration::from_secs(5))).expect("timeout");
+    assert!(matches!(peer.read(&mut buffer), Ok(0) | Err(_)));
+  }
+
+  #[test]
+  fn large_lane_marker_after_the_first_frame_is_rejected() {
+    let mut server = TokioTcpTransport::default().with_transport_config(&small_frames());
+    let (payloads, rejection) = record_inbound(&mut server);
+    let authority = listen(&mut server);
+
+    let mut peer = TcpStream::connect(&authority).expect("connect");
+    let local = peer.local_addr().expect("addr").to_string();
+    let mut frame = 14_u32.to_be_bytes().to_vec();
+    frame.extend_from_slice(&[0; 12]);
+    frame.extend_from_slice(&[7, 7]);
+    frame.extend_from_slice(&u32::MAX.to_be_bytes());
+    peer.write_all(&frame).expect("write");
+
+    assert!(wait_until(|| !rejection.lock().is_empty()));
+    assert_eq!(payloads.lock().clone(), vec![vec![7, 7]]);
+    assert_eq!(rejection.lock().clone(), vec![(local, TransportError::FrameTooLarge {
+      size: u32::MAX as usize,
+      max:  64,
+    })]);
+  }
+
+  fn large_lane_preamble(paired_lane: &str) -> Vec<u8> {
+    let mut preamble = u32::MAX.to_be_bytes().to_vec();
+    preamble.extend_from_slice(&(paired_lane.len() as u16).to_be_bytes());
+    preamble.extend_from_slice(paired_lane.as_bytes());
+    preamble
+  }
+
+  #[test]
+  fn oversized_chunk_on_large_lane_is_attributed_to_the_paired_connection() {
+    let mut server = TokioTcpTransport::default().with_transport_config(&small_frames());
+    let (payloads, rejection) = record_inbound(&mut server);
+    let authority = listen(&mut server);
+
+    let ordinary = TcpStream::connect(&authority).expect("connect");
+    let paired_lane = ordinary.local_addr().expect("addr").to_string();
+    let mut large = TcpStream::connect(&authority).expect("connect large lane");
+    let mut frames = large_lane_preamble(&paired_lane);
+    frames.extend_from_slice(&0x8000_0000_u32.to_be_bytes());
+    large.write_all(&frames).expect("write");
+
+    // 拒否は大きなメッセージ用接続ではなく、handshake を行う通常接続の送信元として報告される
+    assert!(wait_until(|| !rejection.lock().is_empty()));
+    assert_eq!(rejection.lock().clone(), vec![(paired_lane, TransportError::FrameTooLarge {
+      size: 0x8000_0000,
+      max:  64,
+    })]);
+    assert!(payloads.lock().is_empty());
+  }
+
+  #[test]
+  fn large_lane_claiming_a_foreign_host_is_rejected() {
+    let mut server = TokioTcpTransport::default().with_transport_config(&small_frames());
+    let (_payloads, rejection) = record_inbound(&mut server);
+    let authority = listen(&mut server);
+
+    let mut large = TcpStream::connect(&authority).expect("connect large lane");
+    let local = large.local_addr().expect("addr").to_string();
+    large.write_all(&large_lane_preamble("10.9.9.9:2552")).expect("write");
+
+    assert!(wait_until(|| !rejection.lock().is_empty()));
+    let rejected = rejection.lock().clone();
+    assert_eq!(rejected.len(), 1);
+    assert_eq!(rejected[0].0, local);
+    assert!(matches!(rejected[0].1, TransportError::Io(_)));
+  }
+
+  #[test]
+  fn oversized_outbound_payload_is_refused() {
+    let mut server = TokioTcpTransport::default();
+    let authority = listen(&mut server);
+
+    let mut client = TokioTcpTransport::default().with_transport_config(&small_frames());
+    let channel = client.open_channel(&TransportEndpoint::new(authority)).expect("channel");
+
+    let result = client.send(&channel, &[0; 4097], CorrelationId::from_u128(1));
+    assert_eq!(result, Err(TransportError::FrameTooLarge { size: 4097, max: 4096 }));
+  }
+
+  #[test]
+  fn large_messages_are_chunked_and_reassembled() {
+    let mut server = TokioTcpTransport::default().with_transport_config(&small_frames());
+    let (payloads, rejection) = record_inbound(&mut server);
+    let authority = listen(&mut server);
+
+    let mut client = TokioTcpTransport::default().with_transport_config(&small_frames());
+    let channel = client.open_channel(&TransportEndpoint::new(authority)).expect("channel");
+    let large: Vec<u8> = (0..1000_u32).map(|value| value as u8).collect();
+    client.send(&channel, &large, CorrelationId::from_u128(1)).expect("large send");
+    client.send(&channel, &large, CorrelationId::from_u128(2)).expect("large send");
+
+    assert!(wait_until(|| payloads.lock().len() == 2));
+    assert_eq!(payloads.lock().clone(), vec![large.clone(), large]);
+    assert!(rejection.lock().is_empty());
+  }
+
+  #[test]
+  fn small_frames_are_not_blocked_by_a_large_message() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let authority = listener.local_addr().expect("addr").to_string();
+
+    let mut client = TokioTcpTransport::default();
+    let channel = client.open_channel(&TransportEndpoint::new(authority)).expect("channel");
+    let (mut ordinary, _) = listener.accept().expect("ordinary lane");
+
+    // 大きなメッセージ用レーンは読まずに放置し、送信を詰まらせる
+    client.send(&channel, &vec![0_u8; 4 * 1024 * 1024], CorrelationId::from_u128(1)).expect("large send");
+    let (_large, _) = listener.accept().expect("large lane");
+    client.send(&channel, &[9, 9], CorrelationId::from_u128(2)).expect("small send");
+
+    ordinary.set_read_timeout(Some(Duration::from_secs(5))).expect("timeout");
+    let mut frame = [0_u8; 18];
+    ordinary.read_exact(&mut frame).expect("small frame");
+    assert_eq!(&frame[..4], &14_u32.to_be_bytes());
+    assert_eq!(&frame[16..], &[9, 9]);
+  }
+}
+
+#[cfg(feature = "tls")]
+mod tls {
+  use alloc::{boxed::Box, string::String, vec::Vec};
+  use core::time::Duration;
+  use std::{fs, path::PathBuf, thread, time::Instant};
+
+  use fraktor_actor_rs::core::event_stream::CorrelationId;
+  use fraktor_utils_rs::{
+    core::{
+      runtime_toolbox::{NoStdMutex, RuntimeToolbox, SyncMutexFamily},
+      sync::ArcShared,
+    },
+    std::runtime_toolbox::StdToolbox,
+  };
+
+  use super::find_free_port;
+  use crate::{
+    core::{
+      HandshakeFrame, HandshakeKind, InboundFrame, RemoteTransport, TransportBind, TransportEndpoint, TransportError,
+      TransportInbound, TransportInboundShared, TransportLifecycleHook, TransportLifecycleHookShared,
+    },
+    std::transport::{TlsConfig, TokioTcpTransport},
+  };
+
+  const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tls");
+
+  type Recorded<T> = ArcShared<NoStdMutex<Vec<T>>>;
+
+  struct RecordingInbound {
+    payloads:   Recorded<Vec<u8>>,
+    peers:      Recorded<Option<String>>,
+    rejections: Recorded<TransportError>,
+  }
+
+  impl TransportInbound for RecordingInbound {
+    fn on_frame(&mut self, frame: InboundFrame) {
+      self.peers.lock().push(frame.peer_authority().map(String::from));
+      self.payloads.lock().push(frame.payload().to_vec());
+    }
+
+    fn on_frame_rejected(&mut self, _remote_address: &str, error: &TransportError) {
+      self.rejections.lock().push(error.clone());
+    }
+  }
+
+  struct RecordingLifecycle {
+    failures: Recorded<String>,
+    errors:   Recorded<String>,
+  }
+
+  impl TransportLifecycleHook for RecordingLifecycle {
+    fn on_handshake_failed(&mut self, authority: &str, _reason: &str) {
+      self.failures.lock().push(authority.into());
+    }
+
+    fn on_transport_error(&mut self, _authority: &str, reason: &str) {
+      self.errors.lock().push(reason.into());
+    }
+  }
+
+  fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(FIXTURES).join(name)
+  }
+
+  fn tls_config(node: &str) -> TlsConfig {
+    let read = |name: String| fs::read(fixture(&name)).expect("fixture");
+    TlsConfig::from_pem(read(format!("{node}.pem")), read(format!("{node}-key.pem")), read("ca.pem".into()))
+  }
+
+  fn tls_transport(node: &str) -> TokioTcpTransport {
+    TokioTcpTransport::default().with_tls(tls_config(node)).expect("tls transport")
+  }
+
+  fn record_lifecycle(transport: &mut TokioTcpTransport) -> Recorded<String> {
+    record_lifecycle_errors(transport).0
+  }
+
+  fn record_lifecycle_errors(transport: &mut TokioTcpTransport) -> (Recorded<String>, Recorded<String>) {
+    let failures: Recorded<String> = ArcShared::new(NoStdMutex::new(Vec::new()));
+    let errors: Recorded<String> = ArcShared::new(NoStdMutex::new(Vec::new()));
+    let hook: TransportLifecycleHookShared = ArcShared::new(NoStdMutex::new(Box::new(RecordingLifecycle {
+      failures: failures.clone(),
+      errors:   errors.clone(),
+    })));
+    transport.install_lifecycle_hook(hook);
+    (failures, errors)
+  }
+
+  fn record_inbound(transport: &mut TokioTcpTransport) -> Recorded<Vec<u8>> {
+    record_inbound_identity(transport).0
+  }
+
+  fn record_inbound_identity(
+    transport: &mut TokioTcpTransport,
+  ) -> (Recorded<Vec<u8>>, Recorded<Option<String>>, Recorded<TransportError>) {
+    let payloads: Recorded<Vec<u8>> = ArcShared::new(NoStdMutex::new(Vec::new()));
+    let peers: Recorded<Option<String>> = ArcShared::new(NoStdMutex::new(Vec::new()));
+    let rejections: Recorded<TransportError> = ArcShared::new(NoStdMutex::new(Vec::new()));
+    let handler: Box<dyn TransportInbound> = Box::new(RecordingInbound {
+      payloads:   payloads.clone(),
+      peers:      peers.clone(),
+      rejections: rejections.clone(),
+    });
+    let shared: TransportInboundShared<StdToolbox> =
+      ArcShared::new(<StdToolbox as RuntimeToolbox>::MutexFamily::create(handler));
+    transport.install_inbound_handler(shared);
+    (payloads, peers, rejections)
+  }
+
+  fn handshake_claiming(host: &str) -> Vec<u8> {
+    HandshakeFrame::new(HandshakeKind::Offer, "client-system", host, Some(2552), 1).encode()
+  }
+
+  fn listen(transport: &mut TokioTcpTransport) -> String {
+    let port = find_free_port();
+    transport.spawn_listener(&TransportBind::new("127.0.0.1", Some(port))).expect("listener");
+    thread::sleep(Duration::from_millis(50));
+    format!("127.0.0.1:{port}")
+  }
+
+  fn wait_until(condition: impl Fn() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+      if condition() {
+        return true;
+      }
+      thread::sleep(Duration::from_millis(10));
+    }
+    false
+  }
+
+  #[test]
+  fn tls_transport_uses_tls_scheme() {
+    let transport = tls_transport("node");
+    assert!(transport.is_tls_enabled());
+    assert_eq!(transport.scheme(), "fraktor.tcp+tls");
+  }
+
+  #[test]
+  fn invalid_certificates_are_rejected() {
+    let config = TlsConfig::from_pem(b"not a certificate".to_vec(), b"not a key".to_vec(), b"".to_vec());
+    assert!(matches!(TokioTcpTransport::default().with_tls(config), Err(TransportError::Tls(_))));
+  }
+
+  #[test]
+  fn mutual_tls_delivers_frames() {
+    let mut server = tls_transport("node");
+    let payloads = record_inbound(&mut server);
+    let authority = listen(&mut server);
+
+    let mut client = tls_transport("node");
+    let channel = client.open_channel(&TransportEndpoint::new(authority)).expect("channel");
+    client.send(&channel, &[1, 2, 3], CorrelationId::from_u128(7)).expect("send");
+
+    assert!(wait_until(|| payloads.lock().clone() == vec![vec![1, 2, 3]]));
+  }
+
+  #[test]
+  fn certified_handshake_authority_is_attached_to_frames() {
+    let mut server = tls_transport("node");
+    let (payloads, peers, rejections) = record_inbound_identity(&mut server);
+    let authority = listen(&mut server);
+
+    let mut client = tls_transport("node");
+    let channel = client.open_channel(&TransportEndpoint::new(authority)).expect("channel");
+    client.send(&channel, &handshake_claiming("127.0.0.1"), CorrelationId::from_u128(1)).expect("handshake");
+    client.send(&channel, &[1, 2, 3], CorrelationId::from_u128(2)).expect("send");
+
+    assert!(wait_until(|| payloads.lock().len() == 2));
+    let certified = Some(String::from("127.0.0.1:2552"));
+    assert_eq!(peers.lock().clone(), vec![certified.clone(), certified]);
+    assert!(rejections.lock().is_empty());
+  }
+
+  #[test]
+  fn handshake_claiming_an_uncertified_authority_is_rejected() {
+    let mut server = tls_transport("node");
+    let (payloads, _peers, rejections) = record_inbound_identity(&mut server);
+    let authority = listen(&mut server);
+
+    let mut client = tls_transport("other-host");
+    let channel = client.open_channel(&TransportEndpoint::new(authority)).expect("channel");
+    client.send(&channel, &handshake_claiming("127.0.0.1"), CorrelationId::from_u128(1)).expect("handshake");
+
+    assert!(wait_until(|| rejections.lock().len() == 1));
+    assert!(matches!(rejections.lock().first(), Some(TransportError::Tls(_))));
+    assert!(payloads.lock().is_empty());
+  }
+
+  #[test]
+  fn server_certificate_must_match_dialled_authority() {
+    let mut server = tls_transport("other-host");
+    let authority = listen(&mut server);
+
+    let mut client = tls_transport("node");
+    let failures = record_lifecycle(&mut client);
+    let result = client.open_channel(&TransportEndpoint::new(authority.clone()));
+
+    assert!(matches!(result, Err(TransportError::Tls(_))));
+    assert_eq!(failures.lock().clone(), vec![authority]);
+  }
+
+  #[test]
+  fn untrusted_client_certificate_is_reported_by_server() {
+    let mut server = tls_transport("node");
+    let failures = record_lifecycle(&mut server);
+    let payloads = record_inbound(&mut server);
+    let authority = listen(&mut server);
+
+    let mut client = tls_transport("foreign-node");
+    // TLS 1.3 ではクライアント証明書の検証はクライアント側のハンドシェイク完了後に行われる
+    if let Ok(channel) = client.open_channel(&TransportEndpoint::new(authority)) {
+      let _ = client.send(&channel, &[9], CorrelationId::from_u128(1));
+    }
+
+    assert!(wait_until(|| failures.lock().len() == 1));
+    assert!(payloads.lock().is_empty());
+  }
+
+  #[test]
+  fn replaced_certificates_apply_to_new_connections() {
+    let mut server = tls_transport("foreign-node");
+    let authority = listen(&mut server);
+    let mut client = tls_transport("node");
+    assert!(client.open_channel(&TransportEndpoint::new(authority.clone())).is_err());
+
+    server.replace_tls_config(tls_config("node")).expect("replace");
+
+    assert!(client.open_channel(&TransportEndpoint::new(authority)).is_ok());
+  }
+
+  #[test]
+  fn file_certificates_are_reloaded_when_changed() {
+    let dir = std::env::temp_dir().join(format!("fraktor-tls-reload-{}", find_free_port()));
+    fs::create_dir_all(&dir).expect("dir");
+    let install = |node: &str| {
+      fs::copy(fixture(&format!("{node}.pem")), dir.join("cert.pem")).expect("cert");
+      fs::copy(fixture(&format!("{node}-key.pem")), dir.join("key.pem")).expect("key");
+    };
+    install("foreign-node");
+    fs::copy(fixture("ca.pem"), dir.join("ca.pem")).expect("ca");
+    let config = TlsConfig::from_files(dir.join("cert.pem"), dir.join("key.pem"), dir.join("ca.pem"));
+    let mut server = TokioTcpTransport::default().with_tls(config).expect("tls transport");
+    let authority = listen(&mut server);
+    let mut client = tls_transport("node");
+    assert!(client.open_channel(&TransportEndpoint::new(authority.clone())).is_err());
+
+    install("node");
+    server.reload_tls().expect("reload");
+
+    assert!(client.open_channel(&TransportEndpoint::new(authority)).is_ok());
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn failed_automatic_reload_is_reported_and_keeps_previous_certificates() {
+    let dir = std::env::temp_dir().join(format!("fraktor-tls-broken-{}", find_free_port()));
+    fs::create_dir_all(&dir).expect("dir");
+    fs::copy(fixture("node.pem"), dir.join("cert.pem")).expect("cert");
+    fs::copy(fixture("node-key.pem"), dir.join("key.pem")).expect("key");
+    fs::copy(fixture("ca.pem"), dir.join("ca.pem")).expect("ca");
+    let config = TlsConfig::from_files(dir.join("cert.pem"), dir.join("key.pem"), dir.join("ca.pem"));
+    let mut server = TokioTcpTransport::default().with_tls(config).expect("tls transport");
+    let (_failures, errors) = record_lifecycle_errors(&mut server);
+    let authority = listen(&mut server);
+
+    // 更新時刻が確実に変わるよう少し待ってから壊れた証明書を書き込む
+    thread::sleep(Duration::from_millis(20));
+    fs::write(dir.join("cert.pem"), b"broken").expect("overwrite");
+    let mut client = tls_transport("node");
+
+    assert!(client.open_channel(&TransportEndpoint::new(authority)).is_ok());
+    assert!(wait_until(|| errors.lock().iter().any(|reason| reason.contains("certificate reload failed"))));
+    let _ = fs::remove_dir_all(&dir);
+  }
+}
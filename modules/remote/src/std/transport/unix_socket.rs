@@ -0,0 +1,346 @@
+//! Tokio Unix domain socket transport for actor systems running on the same host.
+
+#[cfg(test)]
+mod tests;
+
+use alloc::{
+  collections::BTreeMap,
+  string::{String, ToString},
+  sync::Arc,
+  vec::Vec,
+};
+use core::{fmt, future::Future};
+use std::{env, fs, path::PathBuf, thread};
+
+use fraktor_actor_rs::core::event_stream::CorrelationId;
+use fraktor_utils_rs::{
+  core::{runtime_toolbox::NoStdMutex, sync::ArcShared},
+  std::runtime_toolbox::StdToolbox,
+};
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::{UnixListener, UnixStream},
+  runtime::{Builder, Runtime},
+  sync::mpsc::{self, error::TrySendError},
+  task::JoinHandle,
+};
+
+use super::tokio_tcp::frame_codec::{FRAME_HEADER_LEN, decode_correlation_id, encode_frame};
+use crate::core::{
+  InboundFrame, RemoteTransport, TokioTransportConfig, TransportBackpressureHookShared, TransportBind,
+  TransportChannel, TransportEndpoint, TransportError, TransportHandle, TransportInboundShared,
+  TransportLifecycleHookShared,
+};
+
+const CHANNEL_BUFFER_SIZE: usize = 256;
+const DEFAULT_SOCKET_DIR: &str = "fraktor-uds";
+/// Size of `sockaddr_un::sun_path`, including the terminating NUL byte.
+#[cfg(target_os = "linux")]
+const SUN_PATH_MAX: usize = 108;
+/// Size of `sockaddr_un::sun_path`, including the terminating NUL byte.
+#[cfg(not(target_os = "linux"))]
+const SUN_PATH_MAX: usize = 104;
+
+type SharedInbound = ArcShared<NoStdMutex<Option<TransportInboundShared<StdToolbox>>>>;
+type SharedLifecycleHook = ArcShared<NoStdMutex<Option<TransportLifecycleHookShared>>>;
+
+/// Transport of the `fraktor.uds` scheme exchanging frames over Unix domain sockets.
+///
+/// Authorities keep the `host:port` form used in actor paths; the listener of an authority is
+/// the socket `<socket dir>/<host>:<port>.sock`. Frames use the Tokio TCP framing, but since
+/// the sockets never leave the host every message travels as a single frame bounded by
+/// [`TokioTransportConfig::max_message_size`].
+///
+/// Socket paths must fit `sun_path` (108 bytes on Linux including the NUL byte), so binding or
+/// dialling an authority whose path is longer fails with [`TransportError::Io`]. Failed inbound
+/// connections and a listener that stops accepting are reported through the installed
+/// lifecycle hook.
+pub struct UnixSocketTransport {
+  socket_dir:       PathBuf,
+  max_message_size: usize,
+  listeners:        BTreeMap<String, ListenerHandle>,
+  channels:         BTreeMap<u64, (String, mpsc::Sender<OutboundFrame>)>,
+  next_channel:     u64,
+  inbound:          SharedInbound,
+  lifecycle:        SharedLifecycleHook,
+  runtime:          Arc<Runtime>,
+}
+
+struct ListenerHandle {
+  path: PathBuf,
+  task: JoinHandle<()>,
+}
+
+struct OutboundFrame {
+  payload:        Vec<u8>,
+  correlation_id: CorrelationId,
+}
+
+impl fmt::Debug for UnixSocketTransport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("UnixSocketTransport").field("socket_dir", &self.socket_dir).finish_non_exhaustive()
+  }
+}
+
+impl Default for UnixSocketTransport {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl UnixSocketTransport {
+  /// Creates a new transport backed by an internal Tokio runtime.
+  #[must_use]
+  pub fn new() -> Self {
+    Self::try_new().expect("tokio runtime unavailable")
+  }
+
+  /// Attempts to create a new transport instance, returning a transport error on failure.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`TransportError::Io`] if the Tokio runtime cannot be built.
+  pub fn try_new() -> Result<Self, TransportError> {
+    let runtime = thread::spawn(|| Builder::new_multi_thread().enable_io().build())
+      .join()
+      .map_err(|_| TransportError::Io("failed to join tokio runtime builder thread".into()))?
+      .map_err(|error| TransportError::Io(format!("failed to build tokio runtime: {error}")))?;
+    Ok(Self::with_runtime(runtime))
+  }
+
+  /// Creates a new transport using the provided runtime.
+  #[must_use]
+  pub fn with_runtime(runtime: Runtime) -> Self {
+    Self {
+      socket_dir:       env::temp_dir().join(DEFAULT_SOCKET_DIR),
+      max_message_size: TokioTransportConfig::new().max_message_size(),
+      listeners:        BTreeMap::new(),
+      channels:         BTreeMap::new(),
+      next_channel:     1,
+      inbound:          ArcShared::new(NoStdMutex::new(None)),
+      lifecycle:        ArcShared::new(NoStdMutex::new(None)),
+      runtime:          Arc::new(runtime),
+    }
+  }
+
+  /// Overrides the directory holding the sockets (defaults to `$TMPDIR/fraktor-uds`).
+  #[must_use]
+  pub fn with_socket_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+    self.socket_dir = dir.into();
+    self
+  }
+
+  /// Applies the message size limit of the Tokio transport settings.
+  #[must_use]
+  pub const fn with_transport_config(mut self, config: &TokioTransportConfig) -> Self {
+    self.max_message_size = config.max_message_size();
+    self
+  }
+
+  /// Returns the socket path of the listener bound to `authority`.
+  #[must_use]
+  pub fn socket_path(&self, authority: &str) -> PathBuf {
+    self.socket_dir.join(format!("{authority}.sock"))
+  }
+
+  fn checked_socket_path(&self, authority: &str) -> Result<PathBuf, TransportError> {
+    let path = self.socket_path(authority);
+    let len = path.as_os_str().len();
+    if len >= SUN_PATH_MAX {
+      return Err(TransportError::Io(format!(
+        "socket path {} is {len} bytes, exceeding the {}-byte unix socket path limit",
+        path.display(),
+        SUN_PATH_MAX - 1
+      )));
+    }
+    Ok(path)
+  }
+
+  fn block_on_future<F, T>(&self, future: F) -> Result<T, TransportError>
+  where
+    F: Future<Output = Result<T, TransportError>> + Send + 'static,
+    T: Send + 'static, {
+    let runtime = self.runtime.clone();
+    thread::spawn(move || runtime.block_on(future))
+      .join()
+      .map_err(|_| TransportError::Io("tokio runtime worker panicked".into()))?
+  }
+
+  fn fire_frame_rejected(inbound: &SharedInbound, remote: &str, error: &TransportError) {
+    if let Some(handler) = inbound.lock().clone() {
+      handler.lock().on_frame_rejected(remote, error);
+    }
+  }
+
+  fn fire_transport_error(lifecycle: &SharedLifecycleHook, authority: &str, reason: &str) {
+    if let Some(hook) = lifecycle.lock().clone() {
+      hook.lock().on_transport_error(authority, reason);
+    }
+  }
+
+  async fn accept_loop(
+    listener: UnixListener,
+    authority: String,
+    inbound: SharedInbound,
+    lifecycle: SharedLifecycleHook,
+    max_message_size: usize,
+  ) {
+    let mut next_connection = 0_u64;
+    loop {
+      match listener.accept().await {
+        | Ok((stream, _)) => {
+          // Unix ソケットの接続元は名前を持たないため、接続ごとに一意なアドレスを割り当てる
+          next_connection += 1;
+          let remote = format!("uds:{authority}#{next_connection}");
+          let authority_clone = authority.clone();
+          let inbound_clone = inbound.clone();
+          let lifecycle_clone = lifecycle.clone();
+          tokio::spawn(async move {
+            if let Err(error) =
+              Self::handle_inbound(stream, authority_clone, remote.clone(), inbound_clone, max_message_size).await
+            {
+              Self::fire_transport_error(&lifecycle_clone, &remote, &format!("inbound connection failed: {error}"));
+            }
+          });
+        },
+        | Err(error) => {
+          Self::fire_transport_error(&lifecycle, &authority, &format!("listener stopped accepting: {error}"));
+          break;
+        },
+      }
+    }
+  }
+
+  async fn handle_inbound(
+    mut stream: UnixStream,
+    authority: String,
+    remote: String,
+    inbound: SharedInbound,
+    max_message_size: usize,
+  ) -> Result<(), TransportError> {
+    let mut buffer = Vec::new();
+    loop {
+      let mut len_bytes = [0u8; 4];
+      if stream.read_exact(&mut len_bytes).await.is_err() {
+        break;
+      }
+      let total_len = u32::from_be_bytes(len_bytes) as usize;
+      // 長さを検証してからバッファを確保する
+      if total_len < FRAME_HEADER_LEN {
+        return Err(TransportError::Io("invalid frame: length too short".into()));
+      }
+      if total_len > FRAME_HEADER_LEN + max_message_size {
+        let error = TransportError::FrameTooLarge { size: total_len, max: FRAME_HEADER_LEN + max_message_size };
+        Self::fire_frame_rejected(&inbound, &remote, &error);
+        return Err(error);
+      }
+      buffer.resize(total_len, 0);
+      stream.read_exact(&mut buffer).await.map_err(|e| TransportError::Io(format!("frame read failed: {e}")))?;
+      let correlation_id = decode_correlation_id(&buffer);
+      if let Some(handler) = inbound.lock().clone() {
+        let frame = InboundFrame::new(&authority, remote.clone(), buffer[FRAME_HEADER_LEN..].to_vec(), correlation_id);
+        handler.lock().on_frame(frame);
+      }
+    }
+    Ok(())
+  }
+
+  async fn sender_loop(mut stream: UnixStream, mut receiver: mpsc::Receiver<OutboundFrame>) {
+    while let Some(frame) = receiver.recv().await {
+      if stream.write_all(&encode_frame(&frame.payload, frame.correlation_id)).await.is_err() {
+        break;
+      }
+    }
+  }
+}
+
+impl Drop for UnixSocketTransport {
+  fn drop(&mut self) {
+    for listener in self.listeners.values() {
+      listener.task.abort();
+      let _ = fs::remove_file(&listener.path);
+    }
+  }
+}
+
+impl RemoteTransport<StdToolbox> for UnixSocketTransport {
+  fn scheme(&self) -> &str {
+    "fraktor.uds"
+  }
+
+  fn spawn_listener(&mut self, bind: &TransportBind) -> Result<TransportHandle, TransportError> {
+    let authority = bind.authority().to_string();
+    let path = self.checked_socket_path(&authority)?;
+    fs::create_dir_all(&self.socket_dir)
+      .map_err(|e| TransportError::Io(format!("failed to create socket directory: {e}")))?;
+    // 異常終了したプロセスが残したソケットファイルは bind を妨げるため削除する
+    if path.exists() {
+      fs::remove_file(&path).map_err(|e| TransportError::Io(format!("failed to remove stale socket: {e}")))?;
+    }
+
+    let listener = {
+      let path = path.clone();
+      self.block_on_future(async move {
+        UnixListener::bind(&path).map_err(|e| TransportError::Io(format!("bind failed: {e}")))
+      })?
+    };
+    let task = self.runtime.spawn(Self::accept_loop(
+      listener,
+      authority.clone(),
+      self.inbound.clone(),
+      self.lifecycle.clone(),
+      self.max_message_size,
+    ));
+    self.listeners.insert(authority.clone(), ListenerHandle { path, task });
+    Ok(TransportHandle::new(&authority))
+  }
+
+  fn open_channel(&mut self, endpoint: &TransportEndpoint) -> Result<TransportChannel, TransportError> {
+    let path = self.checked_socket_path(endpoint.authority())?;
+    let stream = self.block_on_future(async move {
+      UnixStream::connect(&path).await.map_err(|e| TransportError::Io(format!("connection failed: {e}")))
+    })?;
+    let (sender, receiver) = mpsc::channel(CHANNEL_BUFFER_SIZE);
+    self.runtime.spawn(Self::sender_loop(stream, receiver));
+
+    let id = self.next_channel;
+    self.next_channel += 1;
+    self.channels.insert(id, (endpoint.authority().to_string(), sender));
+    Ok(TransportChannel::new(id))
+  }
+
+  fn send(
+    &mut self,
+    channel: &TransportChannel,
+    payload: &[u8],
+    correlation_id: CorrelationId,
+  ) -> Result<(), TransportError> {
+    if payload.len() > self.max_message_size {
+      return Err(TransportError::FrameTooLarge { size: payload.len(), max: self.max_message_size });
+    }
+    let (authority, sender) =
+      self.channels.get(&channel.id()).ok_or(TransportError::ChannelUnavailable(channel.id()))?;
+    let frame = OutboundFrame { payload: payload.to_vec(), correlation_id };
+    sender.try_send(frame).map_err(|error| match error {
+      | TrySendError::Full(_) => TransportError::Backpressure(authority.clone()),
+      | TrySendError::Closed(_) => TransportError::ChannelUnavailable(channel.id()),
+    })
+  }
+
+  fn close(&mut self, channel: &TransportChannel) {
+    self.channels.remove(&channel.id());
+  }
+
+  fn install_backpressure_hook(&mut self, hook: TransportBackpressureHookShared) {
+    // 送信キューが溢れた場合は send がエラーを返すため、背圧信号は発行しない
+    let _ = hook;
+  }
+
+  fn install_inbound_handler(&mut self, handler: TransportInboundShared<StdToolbox>) {
+    *self.inbound.lock() = Some(handler);
+  }
+
+  fn install_lifecycle_hook(&mut self, hook: TransportLifecycleHookShared) {
+    *self.lifecycle.lock() = Some(hook);
+  }
+}
@@ -0,0 +1,172 @@
+//! Tests for UnixSocketTransport.
+
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use core::time::Duration;
+use std::{env, io::Write, os::unix::net::UnixStream, path::PathBuf, process, thread, time::Instant};
+
+use fraktor_actor_rs::core::event_stream::CorrelationId;
+use fraktor_utils_rs::{
+  core::{
+    runtime_toolbox::{NoStdMutex, RuntimeToolbox, SyncMutexFamily},
+    sync::ArcShared,
+  },
+  std::runtime_toolbox::StdToolbox,
+};
+
+use super::UnixSocketTransport;
+use crate::core::{
+  InboundFrame, RemoteTransport, TokioTransportConfig, TransportBind, TransportEndpoint, TransportError,
+  TransportInbound, TransportInboundShared, TransportLifecycleHook, TransportLifecycleHookShared,
+};
+
+type Recorded<T> = ArcShared<NoStdMutex<Vec<T>>>;
+type ReceivedFrame = (String, Vec<u8>, CorrelationId);
+
+struct RecordingInbound {
+  frames:    Recorded<ReceivedFrame>,
+  rejection: Recorded<TransportError>,
+}
+
+impl TransportInbound for RecordingInbound {
+  fn on_frame(&mut self, frame: InboundFrame) {
+    self.frames.lock().push((frame.remote_address().into(), frame.payload().to_vec(), frame.correlation_id()));
+  }
+
+  fn on_frame_rejected(&mut self, _remote_address: &str, error: &TransportError) {
+    self.rejection.lock().push(error.clone());
+  }
+}
+
+struct RecordingLifecycle {
+  errors: Recorded<(String, String)>,
+}
+
+impl TransportLifecycleHook for RecordingLifecycle {
+  fn on_handshake_failed(&mut self, _authority: &str, _reason: &str) {}
+
+  fn on_transport_error(&mut self, authority: &str, reason: &str) {
+    self.errors.lock().push((authority.into(), reason.into()));
+  }
+}
+
+fn socket_dir(name: &str) -> PathBuf {
+  env::temp_dir().join(format!("fraktor-uds-tests-{}-{name}", process::id()))
+}
+
+fn record_inbound(transport: &mut UnixSocketTransport) -> (Recorded<ReceivedFrame>, Recorded<TransportError>) {
+  let frames: Recorded<ReceivedFrame> = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let rejection: Recorded<TransportError> = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let handler: Box<dyn TransportInbound> =
+    Box::new(RecordingInbound { frames: frames.clone(), rejection: rejection.clone() });
+  let shared: TransportInboundShared<StdToolbox> =
+    ArcShared::new(<StdToolbox as RuntimeToolbox>::MutexFamily::create(handler));
+  transport.install_inbound_handler(shared);
+  (frames, rejection)
+}
+
+fn wait_until(condition: impl Fn() -> bool) -> bool {
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while Instant::now() < deadline {
+    if condition() {
+      return true;
+    }
+    thread::sleep(Duration::from_millis(10));
+  }
+  false
+}
+
+#[test]
+fn transport_scheme_is_fraktor_uds() {
+  let transport = UnixSocketTransport::default();
+  assert_eq!(transport.scheme(), "fraktor.uds");
+}
+
+#[test]
+fn socket_path_is_derived_from_authority() {
+  let transport = UnixSocketTransport::default().with_socket_dir("/run/fraktor");
+  assert_eq!(transport.socket_path("node-a:2552"), PathBuf::from("/run/fraktor/node-a:2552.sock"));
+}
+
+#[test]
+fn frames_are_exchanged_over_the_socket() {
+  let dir = socket_dir("exchange");
+  let mut server = UnixSocketTransport::default().with_socket_dir(&dir);
+  let (frames, _) = record_inbound(&mut server);
+  server.spawn_listener(&TransportBind::new("node-a", Some(2552))).expect("listener");
+  assert!(dir.join("node-a:2552.sock").exists());
+
+  let mut client = UnixSocketTransport::default().with_socket_dir(&dir);
+  let channel = client.open_channel(&TransportEndpoint::new("node-a:2552".into())).expect("channel");
+  let correlation = CorrelationId::from_u128(0x42);
+  client.send(&channel, &[1, 2, 3], correlation).expect("send");
+
+  assert!(wait_until(|| !frames.lock().is_empty()));
+  let (remote, payload, received) = frames.lock()[0].clone();
+  assert!(remote.starts_with("uds:node-a:2552#"));
+  assert_eq!(payload, vec![1, 2, 3]);
+  assert_eq!(received, correlation);
+}
+
+#[test]
+fn dropping_the_transport_removes_its_sockets() {
+  let dir = socket_dir("drop");
+  let mut server = UnixSocketTransport::default().with_socket_dir(&dir);
+  server.spawn_listener(&TransportBind::new("node-a", Some(2552))).expect("listener");
+  let path = server.socket_path("node-a:2552");
+  assert!(path.exists());
+
+  drop(server);
+  assert!(!path.exists());
+}
+
+#[test]
+fn oversized_frames_are_refused_in_both_directions() {
+  let dir = socket_dir("limits");
+  let config = TokioTransportConfig::new().with_max_message_size(16);
+  let mut server = UnixSocketTransport::default().with_socket_dir(&dir).with_transport_config(&config);
+  let (frames, rejection) = record_inbound(&mut server);
+  server.spawn_listener(&TransportBind::new("node-a", Some(2552))).expect("listener");
+
+  let mut client = UnixSocketTransport::default().with_socket_dir(&dir).with_transport_config(&config);
+  let channel = client.open_channel(&TransportEndpoint::new("node-a:2552".into())).expect("channel");
+  assert_eq!(
+    client.send(&channel, &[0; 17], CorrelationId::nil()),
+    Err(TransportError::FrameTooLarge { size: 17, max: 16 })
+  );
+
+  let mut peer = UnixStream::connect(server.socket_path("node-a:2552")).expect("connect");
+  peer.write_all(&0x8000_0000_u32.to_be_bytes()).expect("write");
+  assert!(wait_until(|| !rejection.lock().is_empty()));
+  assert_eq!(rejection.lock().clone(), vec![TransportError::FrameTooLarge { size: 0x8000_0000, max: 28 }]);
+  assert!(frames.lock().is_empty());
+}
+
+#[test]
+fn malformed_inbound_connections_are_reported_to_the_lifecycle_hook() {
+  let dir = socket_dir("lifecycle");
+  let mut server = UnixSocketTransport::default().with_socket_dir(&dir);
+  let errors: Recorded<(String, String)> = ArcShared::new(NoStdMutex::new(Vec::new()));
+  let hook: TransportLifecycleHookShared =
+    ArcShared::new(NoStdMutex::new(Box::new(RecordingLifecycle { errors: errors.clone() })));
+  server.install_lifecycle_hook(hook);
+  server.spawn_listener(&TransportBind::new("node-a", Some(2552))).expect("listener");
+
+  let mut peer = UnixStream::connect(server.socket_path("node-a:2552")).expect("connect");
+  peer.write_all(&1_u32.to_be_bytes()).expect("write");
+  assert!(wait_until(|| !errors.lock().is_empty()));
+  let (remote, reason) = errors.lock()[0].clone();
+  assert_eq!(remote, "uds:node-a:2552#1");
+  assert!(reason.contains("length too short"));
+}
+
+#[test]
+fn socket_paths_longer_than_sun_path_are_refused() {
+  let dir = socket_dir(&"d".repeat(120));
+  let mut transport = UnixSocketTransport::default().with_socket_dir(&dir);
+
+  let bind = transport.spawn_listener(&TransportBind::new("node-a", Some(2552)));
+  assert!(matches!(bind, Err(TransportError::Io(message)) if message.contains("unix socket path limit")));
+  assert!(!dir.exists());
+  let open = transport.open_channel(&TransportEndpoint::new("node-a:2552".into()));
+  assert!(matches!(open, Err(TransportError::Io(message)) if message.contains("unix socket path limit")));
+}